@@ -1,4 +1,6 @@
 use clap::{Parser, Subcommand};
+use mcp_projectfiles_core::cli::{HttpArgs, ServerArgs};
+use mcp_projectfiles_core::logging::LogFormat;
 
 #[derive(Parser)]
 #[command(name = "projectfiles")]
@@ -6,6 +8,10 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Log format on stderr: human-readable text, or one JSON object per line for log collectors
+    #[arg(long, global = true, env = "MCP_LOG_FORMAT", value_enum, default_value_t = LogFormat::Text)]
+    pub log_format: LogFormat,
 }
 
 #[derive(Subcommand)]
@@ -20,9 +26,16 @@ pub enum Commands {
         #[arg(long, default_value = env!("CARGO_PKG_VERSION"))]
         version: String,
 
-        /// Project root directory (defaults to current working directory)
-        #[arg(long, env = "MCP_PROJECT_ROOT")]
-        project_root: Option<std::path::PathBuf>,
+        #[command(flatten)]
+        server: ServerArgs,
+    },
+    /// Run the MCP server using the Streamable HTTP transport
+    Http {
+        #[command(flatten)]
+        server: ServerArgs,
+
+        #[command(flatten)]
+        http: HttpArgs,
     },
     /// Test the tool handler implementation
     Test,
@@ -1,8 +1,7 @@
 use anyhow::Result;
 use clap::Parser;
-use std::io::IsTerminal;
 use tracing::info;
-use tracing_subscriber::{EnvFilter, fmt};
+use mcp_projectfiles_core::logging;
 
 mod cli;
 mod registration;
@@ -16,29 +15,21 @@ use status::show_claude_status_table;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize logging with TTY detection for conditional ANSI colors
-    let filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new("mcp_projectfiles_core=info,mcp_projectfiles_bin=info,info"));
-
-    fmt()
-        .with_ansi(std::io::stderr().is_terminal()) // Only use ANSI colors if stderr is a TTY
-        .with_env_filter(filter)
-        .with_target(false)
-        .with_writer(std::io::stderr)
-        .init();
-
     let cli = Cli::parse();
 
+    logging::init(cli.log_format, "mcp_projectfiles_bin");
+
     match cli.command {
-        Commands::Stdio { name: _, version: _, project_root } => {
-            // Initialize project root if provided
-            if let Some(root) = project_root {
-                info!("Setting project root to: {:?}", root);
-                mcp_projectfiles_core::config::init_project_root(root);
-            }
+        Commands::Stdio { name: _, version: _, server } => {
+            server.init(None);
             info!("Starting MCP server with stdio transport");
             mcp_projectfiles_core::run_stdio_server().await
         }
+        Commands::Http { server, http } => {
+            server.init(None);
+            info!("Starting MCP server with HTTP transport on {}", http.addr());
+            http.run().await
+        }
         Commands::Test => mcp_projectfiles_core::test_handler().await,
         Commands::Show { command } => match command {
             ShowCommands::Tools { name } => {
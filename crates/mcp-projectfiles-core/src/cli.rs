@@ -0,0 +1,136 @@
+use crate::config;
+use clap::Args;
+use clap::builder::BoolishValueParser;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use tracing::info;
+
+/// Startup options shared by every server binary, flattened into each transport subcommand
+#[derive(Args, Clone, Debug, Default, PartialEq)]
+pub struct ServerArgs {
+    /// Project root directory (defaults to current working directory)
+    #[arg(long, env = "MCP_PROJECT_ROOT")]
+    pub project_root: Option<PathBuf>,
+
+    /// Disable every tool that writes, moves, deletes, changes permissions or kills processes
+    #[arg(long, env = "MCP_READ_ONLY", value_parser = BoolishValueParser::new())]
+    pub read_only: bool,
+
+    /// Only expose these tools (comma-separated names, e.g. read,grep,list)
+    #[arg(long, env = "MCP_ENABLE_TOOLS", value_delimiter = ',')]
+    pub enable_tools: Vec<String>,
+
+    /// Hide these tools (comma-separated names, e.g. delete,kill)
+    #[arg(long, env = "MCP_DISABLE_TOOLS", value_delimiter = ',')]
+    pub disable_tools: Vec<String>,
+
+    /// Without --project-root, use the nearest directory at or above the current one
+    /// containing one of these markers (comma-separated, e.g. .git,Cargo.toml)
+    #[arg(long, env = "MCP_ROOT_MARKERS", value_delimiter = ',')]
+    pub root_markers: Vec<String>,
+}
+
+impl ServerArgs {
+    /// Apply the options to the global config. Call once at startup, before the transport runs.
+    /// `fallback_root` is the project root when neither --project-root nor a root marker names one;
+    /// with `None` the tools use the current directory
+    pub fn init(self, fallback_root: Option<PathBuf>) {
+        // Initialize project root if provided, otherwise from the nearest root marker
+        let root = self
+            .project_root
+            .or_else(|| {
+                let current_dir = std::env::current_dir().ok()?;
+                config::find_marked_root(&current_dir, &self.root_markers)
+            })
+            .or(fallback_root);
+        if let Some(root) = root {
+            info!("Setting project root to: {:?}", root);
+            config::init_project_root(root);
+        }
+        if self.read_only {
+            info!("Read-only mode: write-capable tools are disabled");
+            config::init_read_only(true);
+        }
+        if !self.enable_tools.is_empty() || !self.disable_tools.is_empty() {
+            info!(enable_tools = ?self.enable_tools, disable_tools = ?self.disable_tools, "Filtering exposed tools");
+            config::init_tool_filter(config::ToolFilter::new(&self.enable_tools, &self.disable_tools));
+        }
+    }
+}
+
+/// Options of the Streamable HTTP transport
+#[derive(Args, Clone, Debug, PartialEq)]
+pub struct HttpArgs {
+    /// Address to listen on; use 0.0.0.0 to accept connections from other hosts
+    #[arg(long, env = "MCP_HTTP_HOST", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub host: IpAddr,
+
+    /// Port to listen on
+    #[arg(long, env = "MCP_HTTP_PORT", default_value_t = 3000)]
+    pub port: u16,
+
+    /// Require `Authorization: Bearer <token>` on the MCP and metrics endpoints. Prefer the
+    /// environment variable, since command lines are visible to other local users
+    #[arg(long, env = "MCP_AUTH_TOKEN", hide_env_values = true)]
+    pub auth_token: Option<String>,
+}
+
+impl HttpArgs {
+    /// The socket address the server binds
+    pub fn addr(&self) -> SocketAddr {
+        SocketAddr::new(self.host, self.port)
+    }
+
+    /// Serve the MCP endpoint on [`Self::addr`] until the server fails
+    pub async fn run(self) -> anyhow::Result<()> {
+        if let Some(token) = &self.auth_token {
+            config::init_auth_token(token);
+        }
+        crate::run_http_server(self.addr()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Command, FromArgMatches};
+
+    /// Parse `args` with the read-only flag reading `env` instead of `MCP_READ_ONLY`, so the
+    /// test doesn't switch on read-only mode for tools running in parallel tests
+    fn parse_with_read_only_env(env: &'static str, args: &[&str]) -> ServerArgs {
+        let command = ServerArgs::augment_args(Command::new("server")).mut_arg("read_only", |arg| arg.env(env));
+        let matches = command.try_get_matches_from(args).unwrap();
+        ServerArgs::from_arg_matches(&matches).unwrap()
+    }
+
+    #[test]
+    fn test_read_only_accepts_documented_env_values() {
+        for (env, value, expected) in [
+            ("MCP_TEST_READ_ONLY_ONE", "1", true),
+            ("MCP_TEST_READ_ONLY_TRUE", "true", true),
+            ("MCP_TEST_READ_ONLY_ZERO", "0", false),
+            ("MCP_TEST_READ_ONLY_FALSE", "false", false),
+        ] {
+            unsafe { std::env::set_var(env, value) };
+            let args = parse_with_read_only_env(env, &["server"]);
+            unsafe { std::env::remove_var(env) };
+            assert_eq!(args.read_only, expected, "MCP_READ_ONLY={}", value);
+        }
+
+        let args = parse_with_read_only_env("MCP_TEST_READ_ONLY_UNSET", &["server", "--read-only"]);
+        assert!(args.read_only);
+        let args = parse_with_read_only_env("MCP_TEST_READ_ONLY_UNSET", &["server"]);
+        assert!(!args.read_only);
+    }
+
+    #[test]
+    fn test_http_args_bind_address() {
+        let parse = |args: &[&str]| {
+            let matches = HttpArgs::augment_args(Command::new("http")).try_get_matches_from(args).unwrap();
+            HttpArgs::from_arg_matches(&matches).unwrap()
+        };
+        assert_eq!(parse(&["http"]).addr(), "127.0.0.1:3000".parse().unwrap());
+        assert_eq!(parse(&["http", "--host", "0.0.0.0", "--port", "8080"]).addr(), "0.0.0.0:8080".parse().unwrap());
+        assert_eq!(parse(&["http", "--auth-token", "s3cret"]).auth_token.as_deref(), Some("s3cret"));
+    }
+}
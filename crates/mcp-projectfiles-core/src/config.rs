@@ -1,11 +1,166 @@
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 static PROJECT_ROOT: RwLock<Option<PathBuf>> = RwLock::new(None);
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+static TOOL_FILTER: RwLock<Option<ToolFilter>> = RwLock::new(None);
+static AUTH_TOKEN: RwLock<Option<String>> = RwLock::new(None);
 
 /// The name of this MCP server
 pub const SERVER_NAME: &str = "projectfiles";
 
+/// Default cap on the text a single tool result may return, in bytes
+pub const MAX_RESULT_BYTES: usize = 1024 * 1024;
+
+/// Environment variable overriding [`MAX_RESULT_BYTES`]
+pub const MAX_RESULT_BYTES_ENV: &str = "MCP_MAX_RESULT_BYTES";
+
+/// The result size cap: `MCP_MAX_RESULT_BYTES` when set to a positive number,
+/// otherwise [`MAX_RESULT_BYTES`]
+pub fn max_result_bytes() -> usize {
+    std::env::var(MAX_RESULT_BYTES_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|&limit| limit > 0)
+        .unwrap_or(MAX_RESULT_BYTES)
+}
+
+/// Default time a single tool call may run, in milliseconds
+pub const TOOL_TIMEOUT_MS: u64 = 30_000;
+
+/// Environment variable overriding [`TOOL_TIMEOUT_MS`]
+pub const TOOL_TIMEOUT_MS_ENV: &str = "MCP_TOOL_TIMEOUT_MS";
+
+/// The per-call time limit: `MCP_TOOL_TIMEOUT_MS` when set to a positive number,
+/// otherwise [`TOOL_TIMEOUT_MS`]
+pub fn tool_timeout() -> Duration {
+    let millis = std::env::var(TOOL_TIMEOUT_MS_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|&millis| millis > 0)
+        .unwrap_or(TOOL_TIMEOUT_MS);
+    Duration::from_millis(millis)
+}
+
+/// Environment variable that turns on read-only mode when set to `1` or `true`
+pub const READ_ONLY_ENV: &str = "MCP_READ_ONLY";
+
+/// Turn read-only mode on or off. Call at server startup, before the handler is created
+pub fn init_read_only(read_only: bool) {
+    READ_ONLY.store(read_only, Ordering::SeqCst);
+}
+
+/// Whether write-capable tools are disabled, via [`init_read_only`] or `MCP_READ_ONLY`
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::SeqCst)
+        || std::env::var(READ_ONLY_ENV)
+            .map(|value| matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true"))
+            .unwrap_or(false)
+}
+
+/// Environment variable holding the bearer token the HTTP transport requires, when set
+pub const AUTH_TOKEN_ENV: &str = "MCP_AUTH_TOKEN";
+
+/// Set the bearer token the HTTP transport requires, overriding `MCP_AUTH_TOKEN`. Call at
+/// server startup, before the handler is created; a blank token is ignored
+pub fn init_auth_token(token: &str) {
+    let token = token.trim();
+    if !token.is_empty() {
+        *AUTH_TOKEN.write().unwrap() = Some(token.to_string());
+    }
+}
+
+/// The token HTTP clients must send as `Authorization: Bearer`, from [`init_auth_token`] or
+/// `MCP_AUTH_TOKEN`. `None` when neither is set or both are blank, leaving the transport open
+pub fn auth_token() -> Option<String> {
+    AUTH_TOKEN.read().unwrap().clone().or_else(|| {
+        std::env::var(AUTH_TOKEN_ENV)
+            .ok()
+            .map(|token| token.trim().to_string())
+            .filter(|token| !token.is_empty())
+    })
+}
+
+/// Environment variable listing the only tools to expose, comma-separated
+pub const ENABLE_TOOLS_ENV: &str = "MCP_ENABLE_TOOLS";
+
+/// Environment variable listing tools to hide, comma-separated
+pub const DISABLE_TOOLS_ENV: &str = "MCP_DISABLE_TOOLS";
+
+/// Which tools the server advertises in `tools/list` and agrees to call
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ToolFilter {
+    /// When set, only these tools are exposed
+    pub enabled: Option<HashSet<String>>,
+    /// Tools that are never exposed, even when enabled
+    pub disabled: HashSet<String>,
+}
+
+impl ToolFilter {
+    /// Build a filter from tool names; an empty `enabled` list exposes every tool
+    pub fn new<S: AsRef<str>>(enabled: &[S], disabled: &[S]) -> Self {
+        let names = |list: &[S]| -> HashSet<String> {
+            list.iter()
+                .map(|name| name.as_ref().trim().to_string())
+                .filter(|name| !name.is_empty())
+                .collect()
+        };
+        let enabled = names(enabled);
+        Self {
+            enabled: (!enabled.is_empty()).then_some(enabled),
+            disabled: names(disabled),
+        }
+    }
+
+    /// Read the filter from `MCP_ENABLE_TOOLS` and `MCP_DISABLE_TOOLS`
+    pub fn from_env() -> Self {
+        let list = |var: &str| -> Vec<String> {
+            std::env::var(var)
+                .map(|value| value.split(',').map(str::to_string).collect())
+                .unwrap_or_default()
+        };
+        Self::new(&list(ENABLE_TOOLS_ENV), &list(DISABLE_TOOLS_ENV))
+    }
+
+    /// Whether the named tool is exposed
+    pub fn allows(&self, tool: &str) -> bool {
+        self.enabled.as_ref().is_none_or(|enabled| enabled.contains(tool))
+            && !self.disabled.contains(tool)
+    }
+
+    /// Names in either list that are not in `known`, sorted, for reporting typos
+    pub fn unknown_names<'a>(&'a self, known: &[&str]) -> Vec<&'a str> {
+        let mut unknown: Vec<&str> = self
+            .enabled
+            .iter()
+            .flatten()
+            .chain(&self.disabled)
+            .map(String::as_str)
+            .filter(|name| !known.contains(name))
+            .collect();
+        unknown.sort_unstable();
+        unknown.dedup();
+        unknown
+    }
+}
+
+/// Set the tool filter. Call at server startup, before the handler is created
+pub fn init_tool_filter(filter: ToolFilter) {
+    *TOOL_FILTER.write().unwrap() = Some(filter);
+}
+
+/// The configured tool filter, or the one from the environment if none was set
+pub fn tool_filter() -> ToolFilter {
+    TOOL_FILTER
+        .read()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(ToolFilter::from_env)
+}
+
 /// Format an error message with the server and tool name
 pub fn format_tool_error(tool_name: &str, message: &str) -> String {
     format!("{}:{} - {}", SERVER_NAME, tool_name, message)
@@ -32,8 +187,8 @@ pub mod tool_errors {
     }
 
     /// Create a binary file error for a tool
-    pub fn binary_file(tool: &str, path: &str) -> Error {
-        Error::binary_file(SERVER_NAME, tool, path)
+    pub fn binary_file(tool: &str, path: &str, reason: &str) -> Error {
+        Error::binary_file(SERVER_NAME, tool, path, reason)
     }
 
     /// Create a pattern error for a tool
@@ -55,6 +210,16 @@ pub mod tool_errors {
     pub fn limit_exceeded(tool: &str, limit: &str, actual: &str) -> Error {
         Error::limit_exceeded(SERVER_NAME, tool, limit, actual)
     }
+
+    /// Create a cancelled error for a tool
+    pub fn cancelled(tool: &str) -> Error {
+        Error::cancelled(SERVER_NAME, tool)
+    }
+
+    /// Create a timed out error for a tool
+    pub fn timed_out(tool: &str, timeout_ms: u128) -> Error {
+        Error::timed_out(SERVER_NAME, tool, timeout_ms)
+    }
 }
 
 /// Initialize the project root directory
@@ -72,10 +237,29 @@ pub fn reset_project_root() {
     *project_root = None;
 }
 
+/// Environment variable listing root markers, comma-separated (e.g. `.git,Cargo.toml`).
+/// When set and no root is configured, the project root is the nearest directory at or
+/// above the current one that contains a marker
+pub const ROOT_MARKERS_ENV: &str = "MCP_ROOT_MARKERS";
+
+/// Walk up from `start` to the nearest directory containing any of `markers`
+pub fn find_marked_root<S: AsRef<str>>(start: &Path, markers: &[S]) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| {
+            markers.iter().any(|marker| {
+                let marker = marker.as_ref().trim();
+                !marker.is_empty() && dir.join(marker).exists()
+            })
+        })
+        .map(Path::to_path_buf)
+}
+
 /// Get the project root directory
 /// 
-/// Returns the configured project root, or the current working directory
-/// if no root has been configured.
+/// Returns the configured project root, then `MCP_PROJECT_ROOT`, then the nearest
+/// directory holding one of the `MCP_ROOT_MARKERS`, and finally the current
+/// working directory.
 pub fn get_project_root() -> Result<PathBuf, String> {
     let project_root = PROJECT_ROOT.read().unwrap();
     if let Some(root) = project_root.as_ref() {
@@ -92,9 +276,20 @@ pub fn get_project_root() -> Result<PathBuf, String> {
             }
         }
         
+        let current_dir = std::env::current_dir()
+            .map_err(|e| format!("Failed to get current directory: {}", e))?;
+
+        // Walk up to the nearest root marker when MCP_ROOT_MARKERS is set
+        if let Ok(markers) = std::env::var(ROOT_MARKERS_ENV) {
+            let markers: Vec<&str> = markers.split(',').collect();
+            if let Some(root) = find_marked_root(&current_dir, &markers) {
+                init_project_root(root.clone());
+                return Ok(root);
+            }
+        }
+
         // Default to current working directory
-        std::env::current_dir()
-            .map_err(|e| format!("Failed to get current directory: {}", e))
+        Ok(current_dir)
     }
 }
 
@@ -187,4 +382,50 @@ mod tests {
         // Clean up
         reset_project_root();
     }
-}
\ No newline at end of file
+
+    #[test]
+    #[serial]
+    fn test_read_only_initialization() {
+        init_read_only(true);
+        assert!(is_read_only());
+
+        init_read_only(false);
+        assert_eq!(is_read_only(), std::env::var(READ_ONLY_ENV).is_ok_and(|v| v == "1" || v == "true"));
+    }
+
+    #[test]
+    fn test_find_marked_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("repo");
+        let deep = root.join("crates/core/src");
+        std::fs::create_dir_all(&deep).unwrap();
+        std::fs::create_dir(root.join(".git")).unwrap();
+        std::fs::write(root.join("crates/core/Cargo.toml"), "[package]").unwrap();
+
+        assert_eq!(find_marked_root(&deep, &[".git"]), Some(root.clone()));
+        // The nearest marker wins, whichever marker it is
+        assert_eq!(find_marked_root(&deep, &[".git", "Cargo.toml"]), Some(root.join("crates/core")));
+        assert_eq!(find_marked_root(&root, &[" .git "]), Some(root.clone()));
+        assert_eq!(find_marked_root(&deep, &["no-such-marker", ""]), None);
+    }
+
+    #[test]
+    fn test_tool_filter() {
+        let all = ToolFilter::new::<&str>(&[], &[]);
+        assert!(all.allows("write"));
+
+        let read_only = ToolFilter::new(&["read", " grep ", "list", ""], &["list"]);
+        assert!(read_only.allows("read"));
+        assert!(read_only.allows("grep"));
+        assert!(!read_only.allows("list"));
+        assert!(!read_only.allows("write"));
+
+        let no_delete = ToolFilter::new(&[], &["delete", "kill"]);
+        assert!(no_delete.enabled.is_none());
+        assert!(no_delete.allows("read"));
+        assert!(!no_delete.allows("kill"));
+
+        let typo = ToolFilter::new(&["read", "raed"], &["kil"]);
+        assert_eq!(typo.unknown_names(&["read", "kill"]), vec!["kil", "raed"]);
+    }
+}
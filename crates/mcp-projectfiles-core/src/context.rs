@@ -1,10 +1,62 @@
 use async_trait::async_trait;
-use rust_mcp_schema::{CallToolResult, schema_utils::CallToolError};
+use rust_mcp_schema::{
+    CallToolResult, ProgressNotification, ProgressNotificationParams, ProgressToken,
+    schema_utils::CallToolError,
+};
 use std::any::Any;
-use std::collections::HashMap;
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+
+/// Minimum time between progress notifications, so long walks don't flood the client
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Files created, changed or removed during the session.
+/// Kept apart from the `HashSet<PathBuf>` of files read, since custom state is keyed by type.
+#[derive(Clone, Debug, Default)]
+pub struct ModifiedFiles(pub HashSet<PathBuf>);
+
+/// Sends `notifications/progress` for a request that carried a progress token.
+/// The transport owning the receiving end forwards them to the client
+#[derive(Clone)]
+pub struct ProgressReporter {
+    token: ProgressToken,
+    sender: UnboundedSender<ProgressNotification>,
+    last_sent: Arc<std::sync::Mutex<Option<Instant>>>,
+}
+
+impl ProgressReporter {
+    /// Create a reporter for the request identified by `token`
+    pub fn new(token: ProgressToken, sender: UnboundedSender<ProgressNotification>) -> Self {
+        Self {
+            token,
+            sender,
+            last_sent: Arc::new(std::sync::Mutex::new(None)),
+        }
+    }
+
+    /// Send an update unless one went out within the last [`PROGRESS_INTERVAL`].
+    /// `progress` must grow between calls; `message` is only built when an update is sent
+    pub fn report(&self, progress: u64, total: Option<u64>, message: impl FnOnce() -> String) {
+        let mut last_sent = self.last_sent.lock().unwrap();
+        if last_sent.is_some_and(|sent| sent.elapsed() < PROGRESS_INTERVAL) {
+            return;
+        }
+        *last_sent = Some(Instant::now());
+
+        // Progress is best-effort; the client may have gone away
+        let _ = self.sender.send(ProgressNotification::new(ProgressNotificationParams {
+            message: Some(message()),
+            progress: progress as f64,
+            progress_token: self.token.clone(),
+            total: total.map(|total| total as f64),
+        }));
+    }
+}
 
 /// Shared context for stateful tools containing custom state
 #[derive(Clone)]
@@ -14,6 +66,15 @@ pub struct ToolContext {
     pub custom_state: Arc<Mutex<HashMap<std::any::TypeId, Box<dyn Any + Send + Sync>>>>,
     /// Optional override for project root (useful for testing)
     pub project_root_override: Option<PathBuf>,
+    /// Session working directory relative to the project root, set by the cd tool.
+    /// Shared between clones so every tool call sees the same one
+    working_dir: Arc<RwLock<Option<PathBuf>>>,
+    /// Reject write-capable tools; [`ToolContext::new`] takes it from [`crate::config::is_read_only`]
+    pub read_only: bool,
+    /// Progress reporter for the current request, when the client asked for progress
+    progress: Option<ProgressReporter>,
+    /// Cancelled when the client cancels the current request
+    cancellation: CancellationToken,
 }
 
 impl ToolContext {
@@ -22,6 +83,10 @@ impl ToolContext {
         Self {
             custom_state: Arc::new(Mutex::new(HashMap::new())),
             project_root_override: None,
+            working_dir: Arc::new(RwLock::new(None)),
+            read_only: crate::config::is_read_only(),
+            progress: None,
+            cancellation: CancellationToken::new(),
         }
     }
 
@@ -30,17 +95,109 @@ impl ToolContext {
         Self {
             custom_state: Arc::new(Mutex::new(HashMap::new())),
             project_root_override: Some(project_root),
+            working_dir: Arc::new(RwLock::new(None)),
+            read_only: false,
+            progress: None,
+            cancellation: CancellationToken::new(),
+        }
+    }
+
+    /// A fresh context for another client session: same project root and read-only setting,
+    /// but its own custom state and working directory
+    pub fn for_new_session(&self) -> Self {
+        Self {
+            custom_state: Arc::new(Mutex::new(HashMap::new())),
+            project_root_override: self.project_root_override.clone(),
+            working_dir: Arc::new(RwLock::new(None)),
+            read_only: self.read_only,
+            progress: None,
+            cancellation: CancellationToken::new(),
         }
     }
 
-    /// Get the effective project root (override or current directory)
+    /// Get the project root (override, else [`crate::config::get_project_root`]).
+    /// Tools stay inside it whatever the working directory is
     pub fn get_project_root(&self) -> Result<PathBuf, std::io::Error> {
         match &self.project_root_override {
             Some(root) => Ok(root.clone()),
-            None => std::env::current_dir(),
+            None => crate::config::get_project_root().map_err(std::io::Error::other),
+        }
+    }
+
+    /// A path argument as seen from the project root: relative paths are taken from the
+    /// working directory, absolute ones are returned as is. Containment is still checked
+    /// against [`ToolContext::get_project_root`], so `..` may leave the working directory
+    pub fn resolve_in_working_dir(&self, path: &str) -> String {
+        match self.working_dir() {
+            Some(working_dir) if Path::new(path).is_relative() => {
+                working_dir.join(path).to_string_lossy().into_owned()
+            }
+            _ => path.to_string(),
+        }
+    }
+
+    /// The working directory relative to the project root, if one is set
+    pub fn working_dir(&self) -> Option<PathBuf> {
+        self.working_dir.read().unwrap().clone()
+    }
+
+    /// Set the working directory relative to the project root; `None` returns to the root.
+    /// Callers are responsible for checking that it exists inside the project
+    pub fn set_working_dir(&self, working_dir: Option<PathBuf>) {
+        *self.working_dir.write().unwrap() = working_dir;
+    }
+
+    /// Fail with `operation_not_permitted` in read-only mode.
+    /// Write-capable tools call this before doing anything else
+    pub fn ensure_writable(&self, tool: &str) -> Result<(), CallToolError> {
+        if self.read_only {
+            return Err(CallToolError::from(crate::config::tool_errors::operation_not_permitted(
+                tool,
+                "The server is running in read-only mode (MCP_READ_ONLY), so this tool is disabled",
+            )));
+        }
+        Ok(())
+    }
+
+    /// A copy of this context that reports progress for one request.
+    /// State and the working directory stay shared with the original
+    pub fn with_progress(&self, reporter: ProgressReporter) -> Self {
+        Self {
+            progress: Some(reporter),
+            ..self.clone()
+        }
+    }
+
+    /// Report progress on a long-running operation; does nothing unless the client asked for it
+    pub fn report_progress(&self, progress: u64, total: Option<u64>, message: impl FnOnce() -> String) {
+        if let Some(reporter) = &self.progress {
+            reporter.report(progress, total, message);
         }
     }
 
+    /// A copy of this context whose long-running loops stop once `token` is cancelled.
+    /// State and the working directory stay shared with the original
+    pub fn with_cancellation(&self, token: CancellationToken) -> Self {
+        Self {
+            cancellation: token,
+            ..self.clone()
+        }
+    }
+
+    /// Whether the client has cancelled the current request
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Fail with a cancelled error once the client has cancelled the current request.
+    /// Long-running loops call this between steps so they stop promptly
+    pub fn check_cancelled(&self, tool: &str) -> Result<(), CallToolError> {
+        if self.is_cancelled() {
+            return Err(CallToolError::from(crate::config::tool_errors::cancelled(tool)));
+        }
+        Ok(())
+    }
+
     /// Get a typed value from custom state
     pub async fn get_custom_state<T: 'static + Send + Sync>(&self) -> Option<Arc<T>> {
         let state = self.custom_state.lock().await;
@@ -55,6 +212,19 @@ impl ToolContext {
         let type_id = std::any::TypeId::of::<T>();
         state.insert(type_id, Box::new(Arc::new(value)));
     }
+
+    /// Record that a tool created, changed or removed a file
+    pub async fn record_modified(&self, path: &Path) {
+        let mut state = self.custom_state.lock().await;
+        let type_id = std::any::TypeId::of::<ModifiedFiles>();
+        let mut files = state
+            .get(&type_id)
+            .and_then(|value| value.downcast_ref::<Arc<ModifiedFiles>>())
+            .map(|files| (**files).clone())
+            .unwrap_or_default();
+        files.0.insert(path.to_path_buf());
+        state.insert(type_id, Box::new(Arc::new(files)));
+    }
 }
 
 impl Default for ToolContext {
@@ -61,11 +61,12 @@ pub enum Error {
     },
 
     /// Binary file detected when text was expected
-    #[error("{server}:{tool} - Binary file detected: {path}")]
+    #[error("{server}:{tool} - Binary file detected: {path} ({reason})")]
     BinaryFile {
         server: String,
         tool: String,
         path: String,
+        reason: String,
     },
 
     /// Regex or pattern compilation error
@@ -103,6 +104,21 @@ pub enum Error {
         actual: String,
     },
 
+    /// The client cancelled the request before the tool finished
+    #[error("{server}:{tool} - Cancelled by the client")]
+    Cancelled {
+        server: String,
+        tool: String,
+    },
+
+    /// The tool ran past the handler's time limit
+    #[error("{server}:{tool} - Timed out after {timeout_ms} ms")]
+    TimedOut {
+        server: String,
+        tool: String,
+        timeout_ms: u128,
+    },
+
     /// Validation errors for inputs
     #[error("Validation error: {message}")]
     Validation {
@@ -243,13 +259,14 @@ impl Error {
     }
 
     /// Create a binary file error
-    pub fn binary_file<S: Into<String>, T: Into<String>, P: Into<String>>(
-        server: S, tool: T, path: P
+    pub fn binary_file<S: Into<String>, T: Into<String>, P: Into<String>, R: Into<String>>(
+        server: S, tool: T, path: P, reason: R
     ) -> Self {
         Self::BinaryFile {
             server: server.into(),
             tool: tool.into(),
             path: path.into(),
+            reason: reason.into(),
         }
     }
 
@@ -300,6 +317,23 @@ impl Error {
         }
     }
 
+    /// Create a cancelled error
+    pub fn cancelled<S: Into<String>, T: Into<String>>(server: S, tool: T) -> Self {
+        Self::Cancelled {
+            server: server.into(),
+            tool: tool.into(),
+        }
+    }
+
+    /// Create a timed out error
+    pub fn timed_out<S: Into<String>, T: Into<String>>(server: S, tool: T, timeout_ms: u128) -> Self {
+        Self::TimedOut {
+            server: server.into(),
+            tool: tool.into(),
+            timeout_ms,
+        }
+    }
+
     /// Create a symlink access denied error
     pub fn symlink_access_denied<S: Into<String>, T: Into<String>, P: Into<String>>(
         server: S, tool: T, path: P
@@ -312,8 +346,80 @@ impl Error {
     }
 }
 
+impl Error {
+    /// Stable machine-readable code for the kind of error, e.g. `FILE_NOT_FOUND`.
+    /// Clients can branch on it instead of matching the message
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Transport { .. } => "TRANSPORT_ERROR",
+            Error::Protocol { .. } => "PROTOCOL_ERROR",
+            Error::ToolExecution { .. } => "TOOL_EXECUTION_ERROR",
+            Error::FileNotFound { .. } => "FILE_NOT_FOUND",
+            Error::AccessDenied { .. } => "ACCESS_DENIED",
+            Error::SymlinkAccessDenied { .. } => "SYMLINK_ACCESS_DENIED",
+            Error::InvalidInput { .. } => "INVALID_INPUT",
+            Error::BinaryFile { .. } => "BINARY_FILE",
+            Error::PatternError { .. } => "PATTERN_ERROR",
+            Error::EncodingError { .. } => "ENCODING_ERROR",
+            Error::OperationNotPermitted { .. } => "OPERATION_NOT_PERMITTED",
+            Error::LimitExceeded { .. } => "LIMIT_EXCEEDED",
+            Error::Cancelled { .. } => "CANCELLED",
+            Error::TimedOut { .. } => "TIMED_OUT",
+            Error::Validation { .. } => "VALIDATION_ERROR",
+            Error::Configuration { .. } => "CONFIGURATION_ERROR",
+            Error::Json(_) => "JSON_ERROR",
+            Error::Io(_) => "IO_ERROR",
+            Error::Unknown(_) => "UNKNOWN_ERROR",
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// An [`Error`] as carried inside a `CallToolError`: the same message and `io::ErrorKind`,
+/// plus the error's [`Error::code`]
+#[derive(Debug)]
+pub struct CodedError {
+    code: &'static str,
+    error: std::io::Error,
+}
+
+impl CodedError {
+    /// The machine-readable code, e.g. `FILE_NOT_FOUND`
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// The I/O error kind the error maps to
+    pub fn kind(&self) -> std::io::ErrorKind {
+        self.error.kind()
+    }
+
+    /// Take the error back out of a `CallToolError`, which isn't `Send`, so it can cross
+    /// threads. Errors that didn't come from an [`Error`] keep only their message
+    pub fn from_call_tool_error(error: rust_mcp_schema::schema_utils::CallToolError) -> Self {
+        match error.0.downcast::<CodedError>() {
+            Ok(coded) => *coded,
+            Err(other) => CodedError {
+                code: "TOOL_EXECUTION_ERROR",
+                error: std::io::Error::other(other.to_string()),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for CodedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for CodedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
 /// Convert our Error type to CallToolError for MCP protocol responses
 impl From<Error> for rust_mcp_schema::schema_utils::CallToolError {
     fn from(error: Error) -> Self {
@@ -343,6 +449,12 @@ impl From<Error> for rust_mcp_schema::schema_utils::CallToolError {
             Error::LimitExceeded { .. } => {
                 std::io::Error::new(std::io::ErrorKind::Other, error.to_string())
             }
+            Error::Cancelled { .. } => {
+                std::io::Error::new(std::io::ErrorKind::Interrupted, error.to_string())
+            }
+            Error::TimedOut { .. } => {
+                std::io::Error::new(std::io::ErrorKind::TimedOut, error.to_string())
+            }
             Error::Io(io_err) => {
                 std::io::Error::new(io_err.kind(), error.to_string())
             }
@@ -351,6 +463,9 @@ impl From<Error> for rust_mcp_schema::schema_utils::CallToolError {
             }
         };
         
-        rust_mcp_schema::schema_utils::CallToolError::new(io_error)
+        rust_mcp_schema::schema_utils::CallToolError::new(CodedError {
+            code: error.code(),
+            error: io_error,
+        })
     }
 }
\ No newline at end of file
@@ -1,16 +1,29 @@
-use crate::context::{StatefulTool, ToolContext};
+use crate::config::{self, ToolFilter};
+use crate::context::{ProgressReporter, StatefulTool, ToolContext};
+use crate::error::CodedError;
+use crate::metrics::ToolMetrics;
 use crate::tools::ProtocolTools;
 use rust_mcp_schema::{
-    CallToolRequest, CallToolResult, InitializeResult, ListToolsRequest, ListToolsResult, RpcError,
-    ServerCapabilities, ServerCapabilitiesTools, schema_utils::CallToolError,
+    CallToolRequest, CallToolResult, InitializeResult, ListToolsRequest, ListToolsResult, RequestId,
+    RpcError, ServerCapabilities, ServerCapabilitiesTools,
+    schema_utils::{CallToolError, UnknownTool},
 };
-use tracing::{debug, error, info, instrument};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::{Span, debug, error, field, info, instrument, warn};
 
 /// Custom error type for tool execution errors with proper naming
 #[derive(Debug)]
 struct ToolExecutionError {
     tool_name: String,
     message: String,
+    /// Code of the error this one rewraps, see [`error_code`]
+    code: &'static str,
 }
 
 impl std::fmt::Display for ToolExecutionError {
@@ -21,23 +34,168 @@ impl std::fmt::Display for ToolExecutionError {
 
 impl std::error::Error for ToolExecutionError {}
 
+/// Machine-readable code of a tool call error, such as `FILE_NOT_FOUND` or `UNKNOWN_TOOL`
+pub fn error_code(error: &CallToolError) -> &'static str {
+    if let Some(coded) = error.0.downcast_ref::<CodedError>() {
+        coded.code()
+    } else if let Some(execution) = error.0.downcast_ref::<ToolExecutionError>() {
+        execution.code
+    } else if error.0.is::<UnknownTool>() {
+        "UNKNOWN_TOOL"
+    } else {
+        "TOOL_EXECUTION_ERROR"
+    }
+}
+
+/// The in-band result reporting a failed tool call: the error message as text, with
+/// its [`error_code`] under `code` in the result metadata
+pub fn error_result(error: CallToolError) -> CallToolResult {
+    let code = error_code(&error);
+    let mut meta = serde_json::Map::new();
+    meta.insert("code".to_string(), serde_json::Value::from(code));
+    CallToolResult::with_error(error).with_meta(Some(meta))
+}
+
+/// How long a timed-out tool gets to stop on its own after being cancelled
+const CANCEL_GRACE: Duration = Duration::from_millis(500);
+
+/// Run a tool call, giving up after `limit`. On timeout the call's token is cancelled and
+/// tools that check it get [`CANCEL_GRACE`] to stop cleanly before the call is dropped
+async fn run_with_timeout<F>(
+    call: F,
+    limit: Duration,
+    cancellation: &CancellationToken,
+    tool_name: &str,
+) -> std::result::Result<CallToolResult, CallToolError>
+where
+    F: Future<Output = std::result::Result<CallToolResult, CallToolError>>,
+{
+    tokio::pin!(call);
+    if let Ok(result) = tokio::time::timeout(limit, &mut call).await {
+        return result;
+    }
+
+    warn!(tool_name, timeout_ms = limit.as_millis(), "Tool call timed out");
+    cancellation.cancel();
+    let _ = tokio::time::timeout(CANCEL_GRACE, &mut call).await;
+    Err(CallToolError::from(config::tool_errors::timed_out(tool_name, limit.as_millis())))
+}
+
+/// Argument names whose values are file contents or edit text, logged only by length.
+/// Aliases are listed too, since the arguments are summarized before they are parsed
+const CONTENT_ARGUMENTS: &[&str] = &[
+    "content", "old", "old_string", "new", "new_string", "new_content", "patch", "replacement", "edits",
+];
+
+/// Longest string argument logged as is; longer ones are logged by length
+const MAX_LOGGED_STRING: usize = 80;
+
+/// A one-line summary of tool arguments for logs. File contents and long strings are reduced
+/// to their length, and absolute paths are shown relative to `project_root` so logs don't
+/// reveal where the project lives
+fn summarize_arguments(arguments: Option<&Map<String, Value>>, project_root: Option<&Path>) -> String {
+    let Some(arguments) = arguments else {
+        return String::new();
+    };
+    arguments
+        .iter()
+        .map(|(name, value)| {
+            let summary = match value {
+                Value::String(text) if CONTENT_ARGUMENTS.contains(&name.as_str()) => {
+                    format!("<{} chars>", text.chars().count())
+                }
+                Value::String(text) if Path::new(text).is_absolute() => {
+                    match project_root.and_then(|root| Path::new(text).strip_prefix(root).ok()) {
+                        Some(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+                        Some(relative) => relative.display().to_string(),
+                        None => "<outside project>".to_string(),
+                    }
+                }
+                Value::String(text) if text.chars().count() > MAX_LOGGED_STRING => {
+                    format!("<{} chars>", text.chars().count())
+                }
+                Value::String(text) => format!("{:?}", text),
+                Value::Array(items) => format!("[{} items]", items.len()),
+                Value::Object(fields) => format!("{{{} fields}}", fields.len()),
+                other => other.to_string(),
+            };
+            format!("{}={}", name, summary)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Cancellation tokens of in-flight tool calls, keyed by the client session (if any) and
+/// the JSON-RPC request id, since request ids are only unique within one session
+type InFlightCalls = Mutex<HashMap<(Option<String>, String), CancellationToken>>;
+
+/// Request ids compare by their JSON form, so `1` and `"1"` stay distinct
+fn request_key(request_id: &RequestId) -> String {
+    serde_json::to_string(request_id).unwrap_or_default()
+}
+
+/// Forgets a call's cancellation token when the call finishes or is dropped
+struct InFlightGuard<'a> {
+    calls: &'a InFlightCalls,
+    key: Option<(Option<String>, String)>,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = &self.key {
+            self.calls.lock().unwrap().remove(key);
+        }
+    }
+}
+
 /// Core MCP server handler with transport-agnostic business logic
 pub struct CoreHandler {
     /// Shared context for stateful tools
     context: ToolContext,
+    /// Tools this handler lists and dispatches
+    tool_filter: ToolFilter,
+    /// Calls that can still be cancelled by request id
+    in_flight: InFlightCalls,
+    /// How long a single tool call may run
+    timeout: Duration,
+    /// Call counts, errors and durations per tool
+    metrics: ToolMetrics,
 }
 
 impl CoreHandler {
     /// Create a new handler with default context
     pub fn new() -> Self {
-        Self {
-            context: ToolContext::new(),
-        }
+        Self::new_with_context(ToolContext::new())
     }
 
     /// Create a new handler with custom context
     pub fn new_with_context(context: ToolContext) -> Self {
-        Self { context }
+        Self {
+            context,
+            tool_filter: ToolFilter::default(),
+            in_flight: Mutex::new(HashMap::new()),
+            timeout: config::tool_timeout(),
+            metrics: ToolMetrics::new(),
+        }
+        .with_tool_filter(config::tool_filter())
+    }
+
+    /// Replace the filter deciding which tools are listed and dispatched
+    pub fn with_tool_filter(mut self, tool_filter: ToolFilter) -> Self {
+        let tools = ProtocolTools::tools();
+        let known: Vec<&str> = tools.iter().map(|tool| tool.name.as_str()).collect();
+        let unknown = tool_filter.unknown_names(&known);
+        if !unknown.is_empty() {
+            warn!(tools = ?unknown, "Tool filter names tools that do not exist");
+        }
+        self.tool_filter = tool_filter;
+        self
+    }
+
+    /// Replace the time limit for a single tool call
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
     }
 
     /// Get a reference to the tool context
@@ -45,6 +203,11 @@ impl CoreHandler {
         &self.context
     }
 
+    /// Per-tool metrics of the calls this handler has run
+    pub fn metrics(&self) -> &ToolMetrics {
+        &self.metrics
+    }
+
     /// Handle tool listing requests (transport-agnostic)
     #[instrument(level = "debug", skip(self))]
     pub async fn list_tools(
@@ -52,7 +215,8 @@ impl CoreHandler {
         _request: ListToolsRequest,
     ) -> std::result::Result<ListToolsResult, RpcError> {
         debug!("Handling list_tools request");
-        let tools = ProtocolTools::tools();
+        let mut tools = ProtocolTools::tools();
+        tools.retain(|tool| self.tool_filter.allows(&tool.name));
         info!(tool_count = tools.len(), "Listed available tools");
 
         Ok(ListToolsResult {
@@ -63,62 +227,164 @@ impl CoreHandler {
     }
 
     /// Handle tool call requests (transport-agnostic)
-    #[instrument(level = "debug", skip(self), fields(tool_name = %request.params.name))]
     pub async fn call_tool(
         &self,
         request: CallToolRequest,
     ) -> std::result::Result<CallToolResult, CallToolError> {
+        self.call_tool_for_request(request, None, None).await
+    }
+
+    /// Handle a tool call for a transport that tracks requests. While it runs,
+    /// [`CoreHandler::cancel_request`] with `request_id` stops it, and long-running
+    /// tools send progress through `progress`
+    pub async fn call_tool_for_request(
+        &self,
+        request: CallToolRequest,
+        request_id: Option<RequestId>,
+        progress: Option<ProgressReporter>,
+    ) -> std::result::Result<CallToolResult, CallToolError> {
+        self.call_tool_in_context(request, &self.context, None, request_id, progress).await
+    }
+
+    /// Like [`CoreHandler::call_tool_for_request`], but running the tool against `context`
+    /// instead of the handler's own, for transports that keep state per client session.
+    /// `session_id` scopes `request_id`, so only that session can cancel the call.
+    /// The call runs in a `tool_call` span carrying the request id (generated when the
+    /// transport has none), a redacted argument summary, the outcome and the duration
+    #[instrument(
+        name = "tool_call",
+        level = "info",
+        skip_all,
+        fields(
+            request_id = field::Empty,
+            tool_name = %request.params.name,
+            arguments = field::Empty,
+            outcome = field::Empty,
+            duration_ms = field::Empty,
+        )
+    )]
+    pub async fn call_tool_in_context(
+        &self,
+        request: CallToolRequest,
+        context: &ToolContext,
+        session_id: Option<&str>,
+        request_id: Option<RequestId>,
+        progress: Option<ProgressReporter>,
+    ) -> std::result::Result<CallToolResult, CallToolError> {
+        let span = Span::current();
+        let correlation_id = request_id
+            .as_ref()
+            .map(request_key)
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        span.record("request_id", correlation_id.as_str());
+        let project_root = context.get_project_root().ok().and_then(|root| root.canonicalize().ok());
+        span.record(
+            "arguments",
+            summarize_arguments(request.params.arguments.as_ref(), project_root.as_deref()).as_str(),
+        );
+
         let tool_name = &request.params.name;
+        if !self.tool_filter.allows(tool_name) {
+            error!(tool_name, "Tool is disabled on this server");
+            span.record("outcome", "error");
+            return Err(CallToolError::unknown_tool(tool_name.clone()));
+        }
+
         debug!(tool_name, "Parsing tool request");
 
         let tool = ProtocolTools::try_from(request.params.clone()).map_err(|e| {
             error!(tool_name, error = %e, "Failed to parse tool request");
+            span.record("outcome", "error");
             CallToolError::new(ToolExecutionError {
                 tool_name: format!("projectfiles:{}", tool_name),
                 message: format!("Failed to parse tool request: {}", e),
+                code: "INVALID_INPUT",
             })
         })?;
 
+        let cancellation = CancellationToken::new();
+        let key = request_id
+            .as_ref()
+            .map(|id| (session_id.map(str::to_string), request_key(id)));
+        if let Some(key) = &key {
+            self.in_flight.lock().unwrap().insert(key.clone(), cancellation.clone());
+        }
+        let _in_flight = InFlightGuard { calls: &self.in_flight, key };
+
+        let context = context.with_cancellation(cancellation.clone());
+        let context = match progress {
+            Some(reporter) => context.with_progress(reporter),
+            None => context,
+        };
+
+        // watch and read with follow wait out their own window on purpose, so that comes
+        // on top of the limit
+        let limit = match &tool {
+            ProtocolTools::WatchTool(watch) => self.timeout + Duration::from_millis(watch.timeout_ms),
+            ProtocolTools::ReadTool(read) if read.follow => self.timeout + Duration::from_millis(read.follow_timeout_ms),
+            _ => self.timeout,
+        };
+
         info!(tool_name, "Executing tool");
-        let result = match tool {
-            // Stateful file tools - call with context
-            ProtocolTools::ReadTool(read) => read.call_with_context(&self.context).await,
-            ProtocolTools::WriteTool(write) => write.call_with_context(&self.context).await,
-            ProtocolTools::EditTool(edit) => edit.call_with_context(&self.context).await,
-            ProtocolTools::MoveTool(move_tool) => move_tool.call_with_context(&self.context).await,
-            ProtocolTools::CopyTool(copy) => copy.call_with_context(&self.context).await,
-            ProtocolTools::DeleteTool(delete) => delete.call_with_context(&self.context).await,
-            ProtocolTools::GrepTool(grep) => grep.call_with_context(&self.context).await,
+        let started = Instant::now();
+        let call = async {
+            match tool {
+                // Stateful file tools - call with context
+                ProtocolTools::ReadTool(read) => read.call_with_context(&context).await,
+                ProtocolTools::WriteTool(write) => write.call_with_context(&context).await,
+                ProtocolTools::EditTool(edit) => edit.call_with_context(&context).await,
+                ProtocolTools::MoveTool(move_tool) => move_tool.call_with_context(&context).await,
+                ProtocolTools::CopyTool(copy) => copy.call_with_context(&context).await,
+                ProtocolTools::DeleteTool(delete) => delete.call_with_context(&context).await,
+                ProtocolTools::GrepTool(grep) => grep.call_with_context(&context).await,
+                ProtocolTools::SedTool(sed) => sed.call_with_context(&context).await,
             
-            ProtocolTools::ListTool(list) => list.call_with_context(&self.context).await,
+                ProtocolTools::ListTool(list) => list.call_with_context(&context).await,
             
-            // Priority 1 StatefulTool implementations
-            ProtocolTools::MkdirTool(mkdir) => mkdir.call_with_context(&self.context).await,
-            ProtocolTools::TouchTool(touch) => touch.call_with_context(&self.context).await,
-            ProtocolTools::ChmodTool(chmod) => chmod.call_with_context(&self.context).await,
-            ProtocolTools::FindTool(find) => find.call_with_context(&self.context).await,
+                // Priority 1 StatefulTool implementations
+                ProtocolTools::MkdirTool(mkdir) => mkdir.call_with_context(&context).await,
+                ProtocolTools::TouchTool(touch) => touch.call_with_context(&context).await,
+                ProtocolTools::ChmodTool(chmod) => chmod.call_with_context(&context).await,
+                ProtocolTools::ChownTool(chown) => chown.call_with_context(&context).await,
+                ProtocolTools::FindTool(find) => find.call_with_context(&context).await,
+                ProtocolTools::WatchTool(watch) => watch.call_with_context(&context).await,
             
-            // Priority 2 StatefulTool implementations
-            ProtocolTools::ExistsTool(exists) => exists.call_with_context(&self.context).await,
-            ProtocolTools::StatTool(stat) => stat.call_with_context(&self.context).await,
-            ProtocolTools::DiffTool(diff) => diff.call_with_context(&self.context).await,
-            ProtocolTools::FileTool(file) => file.call_with_context(&self.context).await,
+                // Priority 2 StatefulTool implementations
+                ProtocolTools::ExistsTool(exists) => exists.call_with_context(&context).await,
+                ProtocolTools::StatTool(stat) => stat.call_with_context(&context).await,
+                ProtocolTools::StatusTool(status) => status.call_with_context(&context).await,
+                ProtocolTools::GitStatusTool(git_status) => git_status.call_with_context(&context).await,
+                ProtocolTools::GitBlameTool(git_blame) => git_blame.call_with_context(&context).await,
+                ProtocolTools::CdTool(cd) => cd.call_with_context(&context).await,
+                ProtocolTools::DiffTool(diff) => diff.call_with_context(&context).await,
+                ProtocolTools::PatchTool(patch) => patch.call_with_context(&context).await,
+                ProtocolTools::FileTool(file) => file.call_with_context(&context).await,
             
-            // Priority 3 StatefulTool implementations
-            ProtocolTools::TreeTool(tree) => tree.call_with_context(&self.context).await,
-            ProtocolTools::WcTool(wc) => wc.call_with_context(&self.context).await,
-            ProtocolTools::HashTool(hash) => hash.call_with_context(&self.context).await,
+                // Priority 3 StatefulTool implementations
+                ProtocolTools::TreeTool(tree) => tree.call_with_context(&context).await,
+                ProtocolTools::WcTool(wc) => wc.call_with_context(&context).await,
+                ProtocolTools::HashTool(hash) => hash.call_with_context(&context).await,
             
-            // Process management tools
-            ProtocolTools::ProcessTool(process) => process.call().await,
-            ProtocolTools::KillTool(kill) => kill.call_with_context(&self.context).await,
-            ProtocolTools::LsofTool(lsof) => lsof.call().await,
+                // Process management tools
+                ProtocolTools::ProcessTool(process) => process.call().await,
+                ProtocolTools::KillTool(kill) => kill.call_with_context(&context).await,
+                ProtocolTools::LsofTool(lsof) => lsof.call().await,
             
-            // Structured data tools
-            ProtocolTools::JsonQueryTool(jq) => jq.call_with_context(&self.context).await,
-            ProtocolTools::YamlQueryTool(yq) => yq.call_with_context(&self.context).await,
-            ProtocolTools::TomlQueryTool(tomlq) => tomlq.call_with_context(&self.context).await,
-        }.map_err(|e| {
+                // Structured data tools
+                ProtocolTools::JsonQueryTool(jq) => jq.call_with_context(&context).await,
+                ProtocolTools::YamlQueryTool(yq) => yq.call_with_context(&context).await,
+                ProtocolTools::TomlQueryTool(tomlq) => tomlq.call_with_context(&context).await,
+                ProtocolTools::XmlQueryTool(xq) => xq.call_with_context(&context).await,
+                ProtocolTools::ConvertTool(convert) => convert.call_with_context(&context).await,
+                ProtocolTools::ArchiveTool(archive) => archive.call_with_context(&context).await,
+                ProtocolTools::CompressTool(compress) => compress.call_with_context(&context).await,
+                ProtocolTools::DecompressTool(decompress) => decompress.call_with_context(&context).await,
+                ProtocolTools::TailLinesTool(tail) => tail.call_with_context(&context).await,
+                ProtocolTools::RealpathTool(realpath) => realpath.call_with_context(&context).await,
+                ProtocolTools::DiskUsageTool(du) => du.call_with_context(&context).await,
+            }
+        };
+        let result = run_with_timeout(call, limit, &cancellation, tool_name).await.map_err(|e| {
             // Improve error message by adding tool context when the error message doesn't already include it
             let error_msg = e.to_string();
             if !error_msg.starts_with(&format!("projectfiles:{}", tool_name)) && 
@@ -126,12 +392,17 @@ impl CoreHandler {
                 CallToolError::new(ToolExecutionError {
                     tool_name: format!("projectfiles:{}", tool_name),
                     message: error_msg,
+                    code: error_code(&e),
                 })
             } else {
                 e
             }
         });
 
+        let elapsed = started.elapsed();
+        self.metrics.record(tool_name, elapsed, result.is_err());
+        span.record("outcome", if result.is_ok() { "ok" } else { "error" });
+        span.record("duration_ms", elapsed.as_millis() as u64);
         match &result {
             Ok(_) => info!(tool_name, "Tool execution completed successfully"),
             Err(e) => error!(tool_name, error = %e, "Tool execution failed"),
@@ -139,6 +410,20 @@ impl CoreHandler {
 
         result
     }
+
+    /// Cancel the in-flight tool call for `request_id` in `session_id`, as asked by
+    /// `notifications/cancelled`. Returns false when no such call is running
+    pub fn cancel_request(&self, session_id: Option<&str>, request_id: &RequestId) -> bool {
+        let key = (session_id.map(str::to_string), request_key(request_id));
+        match self.in_flight.lock().unwrap().get(&key) {
+            Some(token) => {
+                info!(request_id = %request_key(request_id), "Cancelling tool call");
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 impl Default for CoreHandler {
@@ -218,9 +503,16 @@ pub async fn test_handler() -> anyhow::Result<()> {
         recursive: false,
         filter: None,
         sort_by: "name".to_string(),
+        reverse: false,
         show_hidden: false,
         show_metadata: false,
         follow_symlinks: true,
+        max_results: 1000,
+        max_depth: None,
+        null_separated: false,
+        color: false,
+        cursor: None,
+        page_size: None,
     };
 
     match file_list_tool.call().await {
@@ -250,4 +542,202 @@ pub async fn test_handler() -> anyhow::Result<()> {
         "🎉 File operation tests passed! File tools are working correctly."
     );
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_mcp_schema::CallToolRequestParams;
+    use tempfile::TempDir;
+
+    fn call(name: &str, arguments: serde_json::Value) -> CallToolRequest {
+        CallToolRequest::new(CallToolRequestParams {
+            name: name.to_string(),
+            arguments: arguments.as_object().cloned(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_tool_filter_limits_listing_and_dispatch() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(project_root.join("notes.txt"), "hello").unwrap();
+        let handler = CoreHandler::new_with_context(ToolContext::with_project_root(project_root))
+            .with_tool_filter(ToolFilter::new(&["read", "write", "exists"], &["write"]));
+
+        let listed = handler.list_tools(ListToolsRequest::new(None)).await.unwrap();
+        let mut names: Vec<&str> = listed.tools.iter().map(|tool| tool.name.as_str()).collect();
+        names.sort_unstable();
+        assert_eq!(names, vec!["exists", "read"]);
+
+        let result = handler
+            .call_tool(call("read", serde_json::json!({"path": "notes.txt"})))
+            .await
+            .unwrap();
+        assert_eq!(result.is_error, Some(false));
+
+        for name in ["write", "delete"] {
+            let error = handler
+                .call_tool(call(name, serde_json::json!({"path": "notes.txt"})))
+                .await
+                .unwrap_err();
+            assert!(error.to_string().contains(name), "{}", error);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancel_request_stops_long_walk() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        for dir in 0..40 {
+            let dir = project_root.join(format!("dir{:02}", dir));
+            std::fs::create_dir(&dir).unwrap();
+            for file in 0..50 {
+                std::fs::write(dir.join(format!("file{:02}.txt", file)), "").unwrap();
+            }
+        }
+        let handler = CoreHandler::new_with_context(ToolContext::with_project_root(project_root));
+        let request_id = RequestId::Integer(7);
+
+        let started = std::time::Instant::now();
+        let walk = handler.call_tool_for_request(
+            call("find", serde_json::json!({"max_results": 100000})),
+            Some(request_id.clone()),
+            None,
+        );
+        let cancel = async {
+            // Let the walk get going before cancelling it
+            tokio::task::yield_now().await;
+            // The same request id in another session is a different call
+            assert!(!handler.cancel_request(Some("other-session"), &request_id));
+            assert!(handler.cancel_request(None, &request_id));
+        };
+        let (result, ()) = tokio::join!(walk, cancel);
+
+        let error = result.unwrap_err();
+        assert!(error.to_string().contains("Cancelled by the client"), "{}", error);
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        // Finished calls can no longer be cancelled
+        assert!(!handler.cancel_request(None, &request_id));
+    }
+
+    fn ok_result() -> std::result::Result<CallToolResult, CallToolError> {
+        Ok(CallToolResult { content: vec![], is_error: Some(false), meta: None })
+    }
+
+    #[tokio::test]
+    async fn test_run_with_timeout_gives_up_on_slow_tool() {
+        let cancellation = CancellationToken::new();
+        let slow_tool = async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            ok_result()
+        };
+
+        let started = std::time::Instant::now();
+        let error = run_with_timeout(slow_tool, Duration::from_millis(20), &cancellation, "slow")
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "projectfiles:slow - Timed out after 20 ms");
+        assert!(cancellation.is_cancelled());
+        // A tool that ignores cancellation is dropped once the grace period ends
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let quick_tool = async { ok_result() };
+        let result = run_with_timeout(quick_tool, Duration::from_millis(20), &CancellationToken::new(), "quick").await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_run_with_timeout_cancels_cooperative_tool() {
+        let cancellation = CancellationToken::new();
+        let context = ToolContext::with_project_root(std::env::temp_dir()).with_cancellation(cancellation.clone());
+        let stopped_cleanly = std::sync::atomic::AtomicBool::new(false);
+        let cooperative_tool = async {
+            loop {
+                if let Err(e) = context.check_cancelled("walk") {
+                    stopped_cleanly.store(true, std::sync::atomic::Ordering::SeqCst);
+                    return Err(e);
+                }
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        };
+
+        let error = run_with_timeout(cooperative_tool, Duration::from_millis(20), &cancellation, "walk")
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Timed out after 20 ms"));
+        assert!(stopped_cleanly.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_follow_window_extends_the_time_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(project_root.join("app.log"), "started\n").unwrap();
+        let handler = CoreHandler::new_with_context(ToolContext::with_project_root(project_root))
+            .with_timeout(Duration::from_millis(100));
+
+        let result = handler
+            .call_tool(call("read", serde_json::json!({"path": "app.log", "follow": true, "follow_timeout_ms": 300})))
+            .await;
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+
+    #[test]
+    fn test_summarize_arguments_redacts_contents_and_paths() {
+        let root = Path::new("/home/someone/project");
+        let arguments = serde_json::json!({
+            "path": "/home/someone/project/src/main.rs",
+            "destination": "/etc/passwd",
+            "pattern": "fn main",
+            "content": "secret file body",
+            "edits": [{"old": "a", "new": "b"}],
+            "line_range": "x".repeat(100),
+            "append": true,
+            "lines": 10,
+        });
+        let summary = summarize_arguments(arguments.as_object(), Some(root));
+        assert_eq!(
+            summary,
+            "append=true content=<16 chars> destination=<outside project> edits=[1 items] \
+             line_range=<100 chars> lines=10 path=src/main.rs pattern=\"fn main\""
+        );
+        assert!(!summary.contains("/home/someone"));
+        assert_eq!(summarize_arguments(None, Some(root)), "");
+
+        // Aliases of content arguments are redacted too
+        for alias in ["old_string", "new_string", "new_content"] {
+            let arguments = serde_json::json!({ alias: "api_key = hunter2" });
+            let summary = summarize_arguments(arguments.as_object(), Some(root));
+            assert_eq!(summary, format!("{}=<17 chars>", alias));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_error_result_carries_code() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap().join("project");
+        std::fs::create_dir(&project_root).unwrap();
+        std::fs::write(temp_dir.path().join("outside.txt"), "secret").unwrap();
+        let handler = CoreHandler::new_with_context(ToolContext::with_project_root(project_root))
+            .with_tool_filter(ToolFilter::new(&[], &["kill"]));
+
+        for (request, code) in [
+            (call("read", serde_json::json!({"path": "missing.txt"})), "FILE_NOT_FOUND"),
+            (call("read", serde_json::json!({"path": "../outside.txt"})), "ACCESS_DENIED"),
+            (call("grep", serde_json::json!({"pattern": "("})), "PATTERN_ERROR"),
+            (call("read", serde_json::json!({"limit": 5})), "INVALID_INPUT"),
+            (call("kill", serde_json::json!({"pid": 1})), "UNKNOWN_TOOL"),
+        ] {
+            let error = handler.call_tool(request).await.unwrap_err();
+            let message = error.to_string();
+            let result = error_result(error);
+            assert_eq!(result.is_error, Some(true));
+            assert_eq!(result.meta.unwrap()["code"], code, "{}", message);
+            // The message itself is unchanged
+            match &result.content[0] {
+                rust_mcp_schema::CallToolResultContentItem::TextContent(text) => assert_eq!(text.text, message),
+                _ => panic!("Expected text content"),
+            }
+        }
+    }
+}
@@ -1,7 +1,10 @@
+pub mod cli;
 pub mod config;
 pub mod context;
 pub mod error;
 pub mod handler;
+pub mod logging;
+pub mod metrics;
 pub mod protocol;
 pub mod server;
 pub mod theme;
@@ -12,8 +15,8 @@ pub use context::{StatefulTool, ToolContext, ToolContextBuilder};
 pub use error::{Error, Result};
 pub use handler::{CoreHandler, create_server_details, test_handler};
 pub use protocol::*;
-pub use server::run_stdio_server;
+pub use server::{run_http_server, run_stdio_server};
 pub use tools::{
     ListTool, ReadTool, WriteTool, ProtocolTools,
 };
-pub use transports::StdioHandler;
\ No newline at end of file
+pub use transports::{HttpHandler, StdioHandler};
\ No newline at end of file
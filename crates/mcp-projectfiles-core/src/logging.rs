@@ -0,0 +1,29 @@
+use std::io::IsTerminal;
+
+use clap::ValueEnum;
+use tracing_subscriber::{EnvFilter, fmt};
+
+/// How log lines are written to stderr
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines, colored when stderr is a terminal
+    Text,
+    /// One JSON object per event
+    Json,
+}
+
+/// Install the global logger writing to stderr
+///
+/// `RUST_LOG` takes precedence; otherwise the core crate and `binary_target` (the
+/// binary's crate name, e.g. `yolo_memento`) log at info level.
+pub fn init(format: LogFormat, binary_target: &str) {
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(format!("mcp_projectfiles_core=info,{}=info,info", binary_target)));
+
+    let logger = fmt().with_env_filter(filter).with_target(false).with_writer(std::io::stderr);
+    match format {
+        // Only use ANSI colors if stderr is a TTY
+        LogFormat::Text => logger.with_ansi(std::io::stderr().is_terminal()).init(),
+        LogFormat::Json => logger.json().init(),
+    }
+}
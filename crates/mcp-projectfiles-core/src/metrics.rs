@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Counters for one tool since the server started
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ToolStats {
+    /// Calls that reached the tool, failed ones included
+    pub calls: u64,
+    /// Calls that returned an error, including timeouts and cancellations
+    pub errors: u64,
+    /// Time spent in all calls together
+    pub total_duration: Duration,
+}
+
+/// Per-tool call counts, errors and durations collected by [`crate::CoreHandler`]
+#[derive(Debug, Default)]
+pub struct ToolMetrics {
+    tools: Mutex<BTreeMap<String, ToolStats>>,
+}
+
+impl ToolMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one call of `tool` that took `duration`
+    pub fn record(&self, tool: &str, duration: Duration, failed: bool) {
+        let mut tools = self.tools.lock().unwrap();
+        let stats = tools.entry(tool.to_string()).or_default();
+        stats.calls += 1;
+        stats.total_duration += duration;
+        if failed {
+            stats.errors += 1;
+        }
+    }
+
+    /// Stats of every tool called so far, by tool name
+    pub fn snapshot(&self) -> BTreeMap<String, ToolStats> {
+        self.tools.lock().unwrap().clone()
+    }
+
+    /// The stats in the Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let tools = self.snapshot();
+        let mut output = String::new();
+        write_counter(&mut output, &tools, "projectfiles_tool_calls_total", "Tool calls, failed ones included", |s| {
+            s.calls.to_string()
+        });
+        write_counter(&mut output, &tools, "projectfiles_tool_errors_total", "Tool calls that returned an error", |s| {
+            s.errors.to_string()
+        });
+        write_counter(&mut output, &tools, "projectfiles_tool_duration_seconds_total", "Time spent in tool calls", |s| {
+            s.total_duration.as_secs_f64().to_string()
+        });
+        output
+    }
+}
+
+/// One counter family with a sample per tool
+fn write_counter(
+    output: &mut String,
+    tools: &BTreeMap<String, ToolStats>,
+    name: &str,
+    help: &str,
+    value: impl Fn(&ToolStats) -> String,
+) {
+    let _ = writeln!(output, "# HELP {} {}", name, help);
+    let _ = writeln!(output, "# TYPE {} counter", name);
+    for (tool, stats) in tools {
+        let _ = writeln!(output, "{}{{tool=\"{}\"}} {}", name, tool, value(stats));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_accumulate_per_tool() {
+        let metrics = ToolMetrics::new();
+        metrics.record("read", Duration::from_millis(250), false);
+        metrics.record("read", Duration::from_millis(500), true);
+        metrics.record("grep", Duration::from_secs(2), false);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(
+            snapshot["read"],
+            ToolStats { calls: 2, errors: 1, total_duration: Duration::from_millis(750) }
+        );
+        assert_eq!(snapshot["grep"].calls, 1);
+
+        let text = metrics.to_prometheus();
+        assert!(text.contains("# TYPE projectfiles_tool_calls_total counter\n"));
+        assert!(text.contains("projectfiles_tool_calls_total{tool=\"grep\"} 1\nprojectfiles_tool_calls_total{tool=\"read\"} 2\n"));
+        assert!(text.contains("projectfiles_tool_errors_total{tool=\"read\"} 1\n"));
+        assert!(text.contains("projectfiles_tool_duration_seconds_total{tool=\"read\"} 0.75\n"));
+    }
+}
@@ -1,6 +1,6 @@
 // Re-export the modular handlers for users who want specific transport implementations
 pub use crate::handler::CoreHandler;
-pub use crate::transports::StdioHandler;
+pub use crate::transports::{HttpHandler, StdioHandler};
 
 // Re-export the transport-specific server functions
-pub use crate::transports::run_stdio_server;
\ No newline at end of file
+pub use crate::transports::{run_http_server, run_stdio_server};
\ No newline at end of file
@@ -125,4 +125,59 @@ impl DiffTheme {
             DiffTheme::Classic => text.green().to_string(),
         }
     }
-}
\ No newline at end of file
+    
+    /// Apply theme colors to a directory listing entry, by kind
+    pub fn colorize_entry(&self, text: &str, kind: EntryKind) -> String {
+        match kind {
+            EntryKind::File => text.to_string(),
+            EntryKind::Directory => match self {
+                DiffTheme::None => text.to_string(),
+                DiffTheme::GitHub => text.truecolor(3, 102, 214).bold().to_string(), // GitHub blue
+                DiffTheme::GitLab => text.truecolor(31, 117, 203).bold().to_string(), // GitLab blue
+                DiffTheme::Monokai => text.truecolor(102, 217, 239).bold().to_string(), // Monokai blue
+                DiffTheme::Solarized => text.truecolor(38, 139, 210).bold().to_string(), // Solarized blue
+                DiffTheme::Dracula => text.truecolor(189, 147, 249).bold().to_string(), // Dracula purple
+                DiffTheme::Classic => text.blue().bold().to_string(),
+            },
+            EntryKind::Executable => self.colorize_header_new(text),
+            EntryKind::Symlink => match self {
+                DiffTheme::None => text.to_string(),
+                DiffTheme::GitHub => text.truecolor(27, 124, 131).to_string(), // GitHub teal
+                DiffTheme::GitLab => text.truecolor(0, 142, 155).to_string(), // GitLab teal
+                DiffTheme::Monokai => text.truecolor(174, 129, 255).to_string(), // Monokai purple
+                DiffTheme::Solarized => text.truecolor(42, 161, 152).to_string(), // Solarized cyan
+                DiffTheme::Dracula => text.truecolor(139, 233, 253).to_string(), // Dracula cyan
+                DiffTheme::Classic => text.cyan().to_string(),
+            },
+        }
+    }
+}
+
+/// What a listed entry is, for coloring names in list and tree output
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntryKind {
+    File,
+    Directory,
+    Executable,
+    Symlink,
+}
+
+impl EntryKind {
+    /// Classify an entry from metadata that was read without following symlinks
+    pub fn from_metadata(metadata: &std::fs::Metadata) -> Self {
+        if metadata.file_type().is_symlink() {
+            return EntryKind::Symlink;
+        }
+        if metadata.is_dir() {
+            return EntryKind::Directory;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if metadata.permissions().mode() & 0o111 != 0 {
+                return EntryKind::Executable;
+            }
+        }
+        EntryKind::File
+    }
+}
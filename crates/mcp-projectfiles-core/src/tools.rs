@@ -1,11 +1,20 @@
+mod archive;
+mod cd;
 mod chmod;
+mod chown;
+mod compress;
+mod convert;
 mod copy;
+mod decompress;
 mod delete;
 mod diff;
+mod du;
 mod edit;
 mod exists;
 mod file;
 mod find;
+mod git_blame;
+mod git_status;
 mod grep;
 mod hash;
 mod jq;
@@ -14,28 +23,44 @@ mod list;
 mod lsof;
 mod mkdir;
 mod r#move;
+mod patch;
 mod process;
 mod query_engine;
 mod read;
+mod realpath;
+mod sed;
 mod stat;
+mod status;
+mod tail;
 mod tomlq;
 mod touch;
 mod tree;
 mod utils;
+mod watch;
 mod wc;
 mod write;
+mod xq;
 mod yq;
 
 use rust_mcp_sdk::tool_box;
 
+pub use archive::ArchiveTool;
+pub use cd::CdTool;
 pub use chmod::ChmodTool;
+pub use chown::ChownTool;
+pub use compress::CompressTool;
+pub use convert::ConvertTool;
 pub use copy::CopyTool;
+pub use decompress::DecompressTool;
 pub use delete::DeleteTool;
 pub use diff::DiffTool;
+pub use du::DiskUsageTool;
 pub use edit::{EditTool, EditOperation};
 pub use exists::ExistsTool;
 pub use file::FileTool;
 pub use find::FindTool;
+pub use git_blame::GitBlameTool;
+pub use git_status::GitStatusTool;
 pub use grep::GrepTool;
 pub use hash::HashTool;
 pub use jq::JsonQueryTool;
@@ -44,14 +69,21 @@ pub use list::ListTool;
 pub use lsof::LsofTool;
 pub use mkdir::MkdirTool;
 pub use r#move::MoveTool;
+pub use patch::PatchTool;
 pub use process::ProcessTool;
 pub use read::ReadTool;
+pub use realpath::RealpathTool;
+pub use sed::SedTool;
 pub use stat::StatTool;
+pub use status::StatusTool;
+pub use tail::TailLinesTool;
 pub use tomlq::TomlQueryTool;
 pub use touch::TouchTool;
 pub use tree::TreeTool;
+pub use watch::WatchTool;
 pub use wc::WcTool;
 pub use write::WriteTool;
+pub use xq::XmlQueryTool;
 pub use yq::YamlQueryTool;
 
 tool_box!(
@@ -67,12 +99,20 @@ tool_box!(
         MkdirTool,
         TouchTool,
         ChmodTool,
+        ChownTool,
         GrepTool,
+        SedTool,
         ExistsTool,
         StatTool,
+        StatusTool,
+        GitStatusTool,
+        GitBlameTool,
+        CdTool,
         DiffTool,
+        PatchTool,
         FindTool,
         TreeTool,
+        WatchTool,
         FileTool,
         WcTool,
         HashTool,
@@ -81,6 +121,14 @@ tool_box!(
         LsofTool,
         JsonQueryTool,
         YamlQueryTool,
-        TomlQueryTool
+        TomlQueryTool,
+        XmlQueryTool,
+        ConvertTool,
+        ArchiveTool,
+        CompressTool,
+        DecompressTool,
+        TailLinesTool,
+        RealpathTool,
+        DiskUsageTool
     ]
 );
\ No newline at end of file
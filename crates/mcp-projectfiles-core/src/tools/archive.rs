@@ -0,0 +1,656 @@
+use crate::config::tool_errors;
+use crate::context::{StatefulTool, ToolContext};
+use crate::tools::utils::{
+    BoundedOutput, create_dir_all_in_project, create_new_in_project, format_count, format_path, format_size,
+    open_for_write_in_project, resolve_path_for_read, resolve_path_for_write,
+    run_blocking,
+};
+
+use async_trait::async_trait;
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+const TOOL_NAME: &str = "archive";
+
+fn default_destination() -> String {
+    ".".to_string()
+}
+
+#[mcp_tool(
+    name = "archive",
+    description = "Create or extract zip, tar and tar.gz archives inside the project. Create stores files under paths relative to the project root; symlinks are followed only to files inside the project. Extract refuses entries that would land outside the destination and skips links. Returns the list of files archived or extracted.
+Examples: {\"operation\": \"create\", \"archive_path\": \"dist/src.zip\", \"source\": \"src\"}, {\"operation\": \"extract\", \"archive_path\": \"vendor.tar.gz\", \"destination\": \"vendor\"}"
+)]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct ArchiveTool {
+    /// "create" or "extract"
+    pub operation: String,
+    /// Archive file to create or extract (relative to project root)
+    pub archive_path: String,
+    /// File or directory to archive when creating (relative to project root)
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Directory to extract into, created if missing (default: ".")
+    #[serde(default = "default_destination")]
+    pub destination: String,
+    /// Archive format: "zip", "tar" or "tar.gz" (default: from the archive_path extension)
+    #[serde(default)]
+    pub format: Option<String>,
+    /// Replace an existing archive, or existing files when extracting (default: false)
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// The requested format, else the one named by the archive's extension
+    fn resolve(format: Option<&str>, archive_path: &str) -> Result<Self, CallToolError> {
+        let name = match format {
+            Some(format) => format.to_lowercase(),
+            None => {
+                let lower = archive_path.to_lowercase();
+                if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+                    "tar.gz".to_string()
+                } else {
+                    Path::new(&lower)
+                        .extension()
+                        .map(|ext| ext.to_string_lossy().to_string())
+                        .unwrap_or_default()
+                }
+            }
+        };
+        match name.as_str() {
+            "zip" => Ok(ArchiveFormat::Zip),
+            "tar" => Ok(ArchiveFormat::Tar),
+            "tar.gz" | "tgz" => Ok(ArchiveFormat::TarGz),
+            _ => Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!(
+                    "Cannot determine the archive format of '{}'. Set format to 'zip', 'tar' or 'tar.gz'",
+                    archive_path
+                ),
+            ))),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::Tar => "tar",
+            ArchiveFormat::TarGz => "tar.gz",
+        }
+    }
+}
+
+/// What an archive entry holds, as far as extraction cares
+enum EntryKind {
+    File,
+    Directory,
+    Link,
+    Other,
+}
+
+/// An entry's path as a relative path with no `..`, root or prefix components, empty for
+/// the archive's own root like "./"; `None` for names that would escape the directory they
+/// are extracted into
+fn sanitize_entry_path(name: &str) -> Option<PathBuf> {
+    let mut sanitized = PathBuf::new();
+    for component in Path::new(name).components() {
+        match component {
+            Component::Normal(part) => sanitized.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(sanitized)
+}
+
+fn io_error(action: &str, path: &Path, error: impl std::fmt::Display) -> CallToolError {
+    CallToolError::from(tool_errors::invalid_input(
+        TOOL_NAME,
+        &format!("Failed to {} '{}': {}", action, path.display(), error),
+    ))
+}
+
+/// Entry name for a file: its path relative to the project root with `/` separators
+fn entry_name(path: &Path, project_root: &Path) -> String {
+    path.strip_prefix(project_root)
+        .unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Files to archive, as (path to read, entry name)
+type ArchiveFiles = Vec<(PathBuf, String)>;
+
+/// Files under `source` to archive, sorted by name.
+/// Symlinks are followed only to files inside the project; other links are returned as skipped
+fn collect_files(
+    source: &Path,
+    project_root: &Path,
+    archive: &Path,
+    context: &ToolContext,
+) -> Result<(ArchiveFiles, Vec<String>), CallToolError> {
+    let mut files = Vec::new();
+    let mut skipped = Vec::new();
+    let mut pending = vec![source.to_path_buf()];
+
+    while let Some(path) = pending.pop() {
+        context.check_cancelled(TOOL_NAME)?;
+        let metadata = std::fs::symlink_metadata(&path).map_err(|e| io_error("read", &path, e))?;
+        let name = entry_name(&path, project_root);
+
+        if metadata.file_type().is_symlink() {
+            match path.canonicalize() {
+                Ok(target) if target.starts_with(project_root) && target.is_file() => {
+                    files.push((target, name));
+                }
+                _ => skipped.push(name),
+            }
+        } else if metadata.is_dir() {
+            let entries = std::fs::read_dir(&path).map_err(|e| io_error("read directory", &path, e))?;
+            for entry in entries {
+                pending.push(entry.map_err(|e| io_error("read directory", &path, e))?.path());
+            }
+        } else if metadata.is_file() && path != archive {
+            files.push((path, name));
+        }
+    }
+
+    files.sort_by(|a, b| a.1.cmp(&b.1));
+    skipped.sort();
+    Ok((files, skipped))
+}
+
+fn write_zip(file: File, files: &[(PathBuf, String)], context: &ToolContext) -> Result<(), CallToolError> {
+    let mut zip = zip::ZipWriter::new(file);
+    for (path, name) in files {
+        context.check_cancelled(TOOL_NAME)?;
+        let metadata = std::fs::metadata(path).map_err(|e| io_error("read", path, e))?;
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .large_file(metadata.len() >= u32::MAX as u64);
+        #[cfg(unix)]
+        let options = {
+            use std::os::unix::fs::PermissionsExt;
+            options.unix_permissions(metadata.permissions().mode() & 0o777)
+        };
+        zip.start_file(name.as_str(), options).map_err(|e| io_error("add", path, e))?;
+        let mut input = File::open(path).map_err(|e| io_error("read", path, e))?;
+        std::io::copy(&mut input, &mut zip).map_err(|e| io_error("add", path, e))?;
+    }
+    zip.finish().map_err(|e| io_error("finish", Path::new("zip archive"), e))?;
+    Ok(())
+}
+
+fn write_tar<W: Write>(writer: W, files: &[(PathBuf, String)], context: &ToolContext) -> Result<W, CallToolError> {
+    let mut builder = tar::Builder::new(writer);
+    for (path, name) in files {
+        context.check_cancelled(TOOL_NAME)?;
+        builder.append_path_with_name(path, name).map_err(|e| io_error("add", path, e))?;
+    }
+    builder.into_inner().map_err(|e| io_error("finish", Path::new("tar archive"), e))
+}
+
+/// One entry to extract: its kind, sanitized path and mode
+struct Planned {
+    kind: EntryKind,
+    name: String,
+    path: Option<PathBuf>,
+    mode: Option<u32>,
+}
+
+/// Walk every entry of the archive, calling `visit` with the entry's plan and a reader
+/// for its contents. Unsafe names are reported with `path: None`
+fn for_each_entry(
+    archive: &Path,
+    format: ArchiveFormat,
+    mut visit: impl FnMut(Planned, &mut dyn Read) -> Result<(), CallToolError>,
+) -> Result<(), CallToolError> {
+    let file = File::open(archive).map_err(|e| io_error("open", archive, e))?;
+    match format {
+        ArchiveFormat::Zip => {
+            let mut zip = zip::ZipArchive::new(file).map_err(|e| io_error("read", archive, e))?;
+            for index in 0..zip.len() {
+                let mut entry = zip.by_index(index).map_err(|e| io_error("read", archive, e))?;
+                let kind = if entry.is_symlink() {
+                    EntryKind::Link
+                } else if entry.is_dir() {
+                    EntryKind::Directory
+                } else {
+                    EntryKind::File
+                };
+                let name = entry.name().to_string();
+                let planned = Planned {
+                    kind,
+                    path: sanitize_entry_path(&name),
+                    name,
+                    mode: entry.unix_mode(),
+                };
+                visit(planned, &mut entry)?;
+            }
+        }
+        ArchiveFormat::Tar | ArchiveFormat::TarGz => {
+            let reader: Box<dyn Read> = if format == ArchiveFormat::TarGz {
+                Box::new(flate2::read::GzDecoder::new(file))
+            } else {
+                Box::new(file)
+            };
+            let mut tar = tar::Archive::new(reader);
+            let entries = tar.entries().map_err(|e| io_error("read", archive, e))?;
+            for entry in entries {
+                let mut entry = entry.map_err(|e| io_error("read", archive, e))?;
+                let header = entry.header();
+                let kind = match header.entry_type() {
+                    tar::EntryType::Regular | tar::EntryType::Continuous => EntryKind::File,
+                    tar::EntryType::Directory => EntryKind::Directory,
+                    tar::EntryType::Symlink | tar::EntryType::Link => EntryKind::Link,
+                    _ => EntryKind::Other,
+                };
+                let mode = header.mode().ok();
+                let name = entry
+                    .path()
+                    .map_err(|e| io_error("read", archive, e))?
+                    .to_string_lossy()
+                    .to_string();
+                let planned = Planned {
+                    kind,
+                    path: sanitize_entry_path(&name),
+                    name,
+                    mode,
+                };
+                visit(planned, &mut entry)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Create `dir` and everything above it, checking each level against the project root
+/// before creating the next, so an existing symlink in the destination cannot redirect
+/// the extraction
+fn create_dir_in_project(dir: &Path, project_root: &Path, name: &str) -> Result<(), CallToolError> {
+    create_dir_all_in_project(dir, project_root, name, TOOL_NAME).map(|_| ())
+}
+
+impl ArchiveTool {
+    fn create(
+        &self,
+        project_root: &Path,
+        format: ArchiveFormat,
+        context: &ToolContext,
+    ) -> Result<(PathBuf, String), CallToolError> {
+        let source = self.source.as_deref().ok_or_else(|| {
+            CallToolError::from(tool_errors::invalid_input(TOOL_NAME, "source is required to create an archive"))
+        })?;
+        let source_path = resolve_path_for_read(source, project_root, false, TOOL_NAME)?;
+        let archive = resolve_path_for_write(&self.archive_path, project_root, TOOL_NAME)?;
+        if archive.exists() && !self.overwrite {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("'{}' already exists. Set overwrite=true to replace it", self.archive_path),
+            )));
+        }
+
+        let (files, skipped) = collect_files(&source_path, project_root, &archive, context)?;
+
+        if let Some(parent) = archive.parent() {
+            create_dir_in_project(parent, project_root, &self.archive_path)?;
+        }
+        let (file, _) = if self.overwrite {
+            open_for_write_in_project(&archive, project_root, false, TOOL_NAME)?
+        } else {
+            create_new_in_project(&archive, project_root, TOOL_NAME)?
+        };
+        match format {
+            ArchiveFormat::Zip => write_zip(file, &files, context)?,
+            ArchiveFormat::Tar => {
+                write_tar(file, &files, context)?;
+            }
+            ArchiveFormat::TarGz => {
+                let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+                write_tar(encoder, &files, context)?
+                    .finish()
+                    .map_err(|e| io_error("finish", &archive, e))?;
+            }
+        }
+
+        let size = std::fs::metadata(&archive).map(|metadata| metadata.len()).unwrap_or(0);
+        let mut output = BoundedOutput::new();
+        output.push_str(&format!(
+            "Created {} archive {} with {} ({})",
+            format.name(),
+            format_path(archive.strip_prefix(project_root).unwrap_or(&archive)),
+            format_count(files.len(), "file", "files"),
+            format_size(size)
+        ));
+        for (_, name) in &files {
+            if !output.push_str(&format!("\n  {}", name)) {
+                break;
+            }
+        }
+        push_skipped(&mut output, &skipped, "link outside the project or to a directory", "links outside the project or to directories");
+        Ok((archive, output.into_string()))
+    }
+
+    fn extract(
+        &self,
+        project_root: &Path,
+        format: ArchiveFormat,
+        context: &ToolContext,
+    ) -> Result<(Vec<PathBuf>, String), CallToolError> {
+        let archive = resolve_path_for_read(&self.archive_path, project_root, false, TOOL_NAME)?;
+        if !archive.is_file() {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Archive is not a file: {}", self.archive_path),
+            )));
+        }
+        let destination = resolve_path_for_write(&self.destination, project_root, TOOL_NAME)?;
+
+        // Check every entry before writing anything, so a bad archive leaves no partial extraction
+        for_each_entry(&archive, format, |planned, _| {
+            context.check_cancelled(TOOL_NAME)?;
+            let path = match &planned.path {
+                Some(path) if !(path.as_os_str().is_empty() && matches!(planned.kind, EntryKind::File)) => path,
+                _ => return Err(CallToolError::from(tool_errors::access_denied(
+                    TOOL_NAME,
+                    &planned.name,
+                    "Archive entry would extract outside the destination directory",
+                ))),
+            };
+            let target = destination.join(path);
+            if matches!(planned.kind, EntryKind::File) && target.exists() && !self.overwrite {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    TOOL_NAME,
+                    &format!(
+                        "{} already exists. Set overwrite=true to replace it",
+                        format_path(target.strip_prefix(project_root).unwrap_or(&target))
+                    ),
+                )));
+            }
+            Ok(())
+        })?;
+
+        create_dir_in_project(&destination, project_root, &self.destination)?;
+        let mut extracted = Vec::new();
+        let mut skipped = Vec::new();
+        for_each_entry(&archive, format, |planned, reader| {
+            context.check_cancelled(TOOL_NAME)?;
+            let Some(path) = planned.path else {
+                return Ok(());
+            };
+            let target = destination.join(&path);
+            match planned.kind {
+                EntryKind::Directory => create_dir_in_project(&target, project_root, &planned.name),
+                EntryKind::Link | EntryKind::Other => {
+                    skipped.push(planned.name);
+                    Ok(())
+                }
+                EntryKind::File => {
+                    if let Some(parent) = target.parent() {
+                        create_dir_in_project(parent, project_root, &planned.name)?;
+                    }
+                    // Re-checks containment on the opened file, and refuses to write through a symlink
+                    let (mut file, real_path) = if self.overwrite {
+                        open_for_write_in_project(&target, project_root, false, TOOL_NAME)?
+                    } else {
+                        create_new_in_project(&target, project_root, TOOL_NAME)?
+                    };
+                    std::io::copy(reader, &mut file).map_err(|e| io_error("extract", &path, e))?;
+                    #[cfg(unix)]
+                    if let Some(mode) = planned.mode {
+                        use std::os::unix::fs::PermissionsExt;
+                        // Never restore setuid, setgid or sticky bits from an archive
+                        let _ = file.set_permissions(std::fs::Permissions::from_mode(mode & 0o777));
+                    }
+                    #[cfg(not(unix))]
+                    let _ = planned.mode;
+                    extracted.push(real_path);
+                    Ok(())
+                }
+            }
+        })?;
+
+        let mut output = BoundedOutput::new();
+        output.push_str(&format!(
+            "Extracted {} from {} into {}",
+            format_count(extracted.len(), "file", "files"),
+            format_path(archive.strip_prefix(project_root).unwrap_or(&archive)),
+            format_path(destination.strip_prefix(project_root).unwrap_or(Path::new(".")))
+        ));
+        for path in &extracted {
+            if !output.push_str(&format!("\n  {}", entry_name(path, project_root))) {
+                break;
+            }
+        }
+        push_skipped(&mut output, &skipped, "link or special file", "links or special files");
+        Ok((extracted, output.into_string()))
+    }
+}
+
+/// Append a warning listing entries that were left out
+fn push_skipped(output: &mut BoundedOutput, skipped: &[String], singular: &str, plural: &str) {
+    if skipped.is_empty() {
+        return;
+    }
+    output.push_str(&format!("\nWarning: skipped {}:", format_count(skipped.len(), singular, plural)));
+    for name in skipped {
+        if !output.push_str(&format!("\n  {}", format_path(Path::new(name)))) {
+            break;
+        }
+    }
+}
+
+#[async_trait]
+impl StatefulTool for ArchiveTool {
+    async fn call_with_context(
+        mut self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        self.archive_path = context.resolve_in_working_dir(&self.archive_path);
+        self.source = self.source.map(|path| context.resolve_in_working_dir(&path));
+        self.destination = context.resolve_in_working_dir(&self.destination);
+
+        context.ensure_writable(TOOL_NAME)?;
+
+        let project_root = context.get_project_root().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to get project root: {}", e),
+            ))
+        })?;
+        let project_root = project_root.canonicalize().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to canonicalize project root: {}", e),
+            ))
+        })?;
+        let format = ArchiveFormat::resolve(self.format.as_deref(), &self.archive_path)?;
+
+        let blocking_context = context.clone();
+        let (modified, message) = run_blocking(TOOL_NAME, move || match self.operation.as_str() {
+            "create" => {
+                let (archive, message) = self.create(&project_root, format, &blocking_context)?;
+                Ok((vec![archive], message))
+            }
+            "extract" => self.extract(&project_root, format, &blocking_context),
+            _ => Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Invalid operation '{}'. Must be 'create' or 'extract'", self.operation),
+            ))),
+        })
+        .await?;
+        for path in &modified {
+            context.record_modified(path).await;
+        }
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                message, None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, PathBuf, ToolContext) {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path().join("project");
+        std::fs::create_dir_all(project_root.join("src/nested")).unwrap();
+        std::fs::write(project_root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(project_root.join("src/nested/lib.rs"), "pub fn lib() {}\n").unwrap();
+        let project_root = project_root.canonicalize().unwrap();
+        let context = ToolContext::with_project_root(project_root.clone());
+        (temp_dir, project_root, context)
+    }
+
+    fn archive(operation: &str, archive_path: &str) -> ArchiveTool {
+        ArchiveTool {
+            operation: operation.to_string(),
+            archive_path: archive_path.to_string(),
+            source: Some("src".to_string()),
+            destination: "out".to_string(),
+            format: None,
+            overwrite: false,
+        }
+    }
+
+    fn text(result: &CallToolResult) -> &str {
+        match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => &text.text,
+            _ => panic!("Expected text content"),
+        }
+    }
+
+    #[test]
+    fn test_sanitize_entry_path() {
+        assert_eq!(sanitize_entry_path("src/./main.rs"), Some(PathBuf::from("src/main.rs")));
+        assert_eq!(sanitize_entry_path("../evil.txt"), None);
+        assert_eq!(sanitize_entry_path("src/../../evil.txt"), None);
+        assert_eq!(sanitize_entry_path("/etc/passwd"), None);
+        assert_eq!(sanitize_entry_path("./"), Some(PathBuf::new()));
+    }
+
+    #[tokio::test]
+    async fn test_archive_round_trip_in_every_format() {
+        let (_temp_dir, project_root, context) = setup();
+
+        for archive_path in ["dist/src.zip", "dist/src.tar", "dist/src.tar.gz"] {
+            let result = archive("create", archive_path).call_with_context(&context).await.unwrap();
+            assert!(text(&result).contains("with 2 files"), "unexpected output: {}", text(&result));
+            assert!(text(&result).ends_with("\n  src/main.rs\n  src/nested/lib.rs"), "unexpected output: {}", text(&result));
+
+            let mut extract = archive("extract", archive_path);
+            extract.destination = format!("out/{}", archive_path.replace('/', "_"));
+            let result = extract.clone().call_with_context(&context).await.unwrap();
+            assert!(text(&result).starts_with("Extracted 2 files"), "unexpected output: {}", text(&result));
+            let out = project_root.join(&extract.destination);
+            assert_eq!(std::fs::read_to_string(out.join("src/main.rs")).unwrap(), "fn main() {}\n");
+            assert_eq!(std::fs::read_to_string(out.join("src/nested/lib.rs")).unwrap(), "pub fn lib() {}\n");
+
+            // Extracting again would overwrite, which needs to be asked for
+            assert!(extract.clone().call_with_context(&context).await.is_err());
+            extract.overwrite = true;
+            extract.call_with_context(&context).await.unwrap();
+        }
+
+        assert!(archive("create", "dist/src.zip").call_with_context(&context).await.is_err());
+        assert!(archive("create", "dist/src.rar").call_with_context(&context).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_archive_extract_refuses_zip_slip() {
+        let (_temp_dir, project_root, context) = setup();
+
+        let mut zip = zip::ZipWriter::new(File::create(project_root.join("evil.zip")).unwrap());
+        zip.start_file("safe.txt", zip::write::SimpleFileOptions::default()).unwrap();
+        zip.write_all(b"safe").unwrap();
+        zip.start_file("../../escaped.txt", zip::write::SimpleFileOptions::default()).unwrap();
+        zip.write_all(b"escaped").unwrap();
+        zip.finish().unwrap();
+
+        let error = archive("extract", "evil.zip").call_with_context(&context).await.unwrap_err();
+        assert!(error.to_string().contains("outside the destination"), "{}", error);
+        // Nothing was written, not even the safe entry
+        assert!(!project_root.join("out").exists());
+        assert!(!project_root.parent().unwrap().join("escaped.txt").exists());
+
+        // Link entries are skipped rather than recreated
+        let mut builder = tar::Builder::new(File::create(project_root.join("links.tar")).unwrap());
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        builder.append_link(&mut header, "escape", "/etc").unwrap();
+        builder.into_inner().unwrap();
+        let result = archive("extract", "links.tar").call_with_context(&context).await.unwrap();
+        assert!(text(&result).contains("Warning: skipped 1 link or special file:\n  'escape'"), "{}", text(&result));
+        assert!(!project_root.join("out/escape").exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_archive_create_skips_links_outside_project() {
+        let (temp_dir, project_root, context) = setup();
+        std::fs::write(temp_dir.path().join("secret.txt"), "secret").unwrap();
+        std::os::unix::fs::symlink(temp_dir.path().join("secret.txt"), project_root.join("src/secret.txt")).unwrap();
+        std::os::unix::fs::symlink(project_root.join("src/main.rs"), project_root.join("src/alias.rs")).unwrap();
+
+        let result = archive("create", "src.tar").call_with_context(&context).await.unwrap();
+        let output = text(&result);
+        assert!(output.contains("with 3 files"), "{}", output);
+        assert!(output.contains("\n  src/alias.rs\n"), "{}", output);
+        assert!(output.contains("Warning: skipped 1 link outside the project or to a directory:\n  'src/secret.txt'"), "{}", output);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_archive_extract_creates_nothing_through_symlinked_destination() {
+        let (temp_dir, project_root, context) = setup();
+        archive("create", "src.tar").call_with_context(&context).await.unwrap();
+        let outside = temp_dir.path().join("outside");
+        std::fs::create_dir(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, project_root.join("out")).unwrap();
+
+        let mut extract = archive("extract", "src.tar");
+        extract.destination = "out/deep/er".to_string();
+        let error = extract.call_with_context(&context).await.unwrap_err();
+        assert!(error.to_string().contains("outside the project directory"), "{}", error);
+        assert!(!outside.join("deep").exists());
+    }
+
+    #[tokio::test]
+    async fn test_archive_stops_when_cancelled() {
+        let (_temp_dir, project_root, context) = setup();
+        archive("create", "src.tar").call_with_context(&context).await.unwrap();
+
+        let token = tokio_util::sync::CancellationToken::new();
+        token.cancel();
+        let cancelled = context.with_cancellation(token);
+        let error = archive("extract", "src.tar").call_with_context(&cancelled).await.unwrap_err();
+        assert!(error.to_string().contains("Cancelled by the client"), "{}", error);
+        assert!(!project_root.join("out/src/main.rs").exists());
+        let error = archive("create", "other.zip").call_with_context(&cancelled).await.unwrap_err();
+        assert!(error.to_string().contains("Cancelled by the client"), "{}", error);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::config::tool_errors;
+use crate::context::{StatefulTool, ToolContext};
+use crate::tools::utils::format_path;
+
+use async_trait::async_trait;
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+
+const TOOL_NAME: &str = "cd";
+
+#[mcp_tool(
+    name = "cd",
+    description = "Set the session's working directory inside the project. Later relative paths in every tool resolve against it; tools can still reach the rest of the project with '..'. Omit path to return to the project root.
+Examples: {\"path\": \"crates/core/src\"}, {\"path\": \"..\"}, {}"
+)]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct CdTool {
+    /// Directory to switch to, relative to the current working directory (default: the project root)
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+#[async_trait]
+impl StatefulTool for CdTool {
+    async fn call_with_context(
+        self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        let project_root = context.get_project_root().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to get project root: {}", e),
+            ))
+        })?;
+        let canonical_root = project_root.canonicalize().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to canonicalize project root: {}", e),
+            ))
+        })?;
+
+        let working_dir = match &self.path {
+            Some(path) => {
+                // Relative paths start from the current working directory, like a shell
+                let target = project_root.join(context.resolve_in_working_dir(path));
+
+                let canonical_target = target
+                    .canonicalize()
+                    .map_err(|_| CallToolError::from(tool_errors::file_not_found(TOOL_NAME, path)))?;
+                if !canonical_target.starts_with(&canonical_root) {
+                    return Err(CallToolError::from(tool_errors::access_denied(
+                        TOOL_NAME,
+                        path,
+                        "Working directory must be inside the project directory",
+                    )));
+                }
+                if !canonical_target.is_dir() {
+                    return Err(CallToolError::from(tool_errors::invalid_input(
+                        TOOL_NAME,
+                        &format!("Path is not a directory: {}", path),
+                    )));
+                }
+
+                let relative = canonical_target
+                    .strip_prefix(&canonical_root)
+                    .unwrap_or(&canonical_target)
+                    .to_path_buf();
+                (!relative.as_os_str().is_empty()).then_some(relative)
+            }
+            None => None,
+        };
+
+        let message = match &working_dir {
+            Some(relative) => format!("Working directory is now {}", format_path(relative)),
+            None => "Working directory is now the project root".to_string(),
+        };
+        context.set_working_dir(working_dir);
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                message, None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::{ReadTool, WriteTool};
+    use tempfile::TempDir;
+
+    fn cd(path: Option<&str>) -> CdTool {
+        CdTool { path: path.map(str::to_string) }
+    }
+
+    fn text(result: &CallToolResult) -> &str {
+        match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => &text.text,
+            _ => panic!("Expected text content"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cd_resolves_later_paths_in_working_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(project_root.clone());
+        std::fs::create_dir_all(project_root.join("crates/core/src")).unwrap();
+        std::fs::write(project_root.join("crates/core/Cargo.toml"), "[package]").unwrap();
+        std::fs::write(project_root.join("top.txt"), "top").unwrap();
+
+        let result = cd(Some("crates/core/src")).call_with_context(&context).await.unwrap();
+        assert_eq!(text(&result), "Working directory is now 'crates/core/src'");
+
+        let write: WriteTool = serde_json::from_value(serde_json::json!({
+            "path": "lib.rs",
+            "content": "pub fn core() {}"
+        }))
+        .unwrap();
+        write.call_with_context(&context).await.unwrap();
+        assert!(project_root.join("crates/core/src/lib.rs").exists());
+
+        // Relative cd starts from the working directory
+        let result = cd(Some("..")).call_with_context(&context).await.unwrap();
+        assert_eq!(text(&result), "Working directory is now 'crates/core'");
+        let read: ReadTool = serde_json::from_value(serde_json::json!({"path": "Cargo.toml"})).unwrap();
+        assert!(text(&read.call_with_context(&context).await.unwrap()).contains("[package]"));
+
+        // '..' reaches the rest of the project
+        let read: ReadTool = serde_json::from_value(serde_json::json!({"path": "../../top.txt"})).unwrap();
+        assert!(text(&read.call_with_context(&context).await.unwrap()).contains("top"));
+
+        let result = cd(None).call_with_context(&context).await.unwrap();
+        assert_eq!(text(&result), "Working directory is now the project root");
+        let read: ReadTool = serde_json::from_value(serde_json::json!({"path": "top.txt"})).unwrap();
+        assert!(text(&read.call_with_context(&context).await.unwrap()).contains("top"));
+    }
+
+    #[tokio::test]
+    async fn test_cd_rejects_paths_outside_project_and_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(project_root.clone());
+        std::fs::create_dir(project_root.join("sub")).unwrap();
+        std::fs::write(project_root.join("file.txt"), "content").unwrap();
+
+        cd(Some("sub")).call_with_context(&context).await.unwrap();
+        for path in ["../..", "file.txt", "../file.txt", "missing"] {
+            assert!(cd(Some(path)).call_with_context(&context).await.is_err(), "{}", path);
+        }
+        // A rejected cd leaves the working directory alone
+        assert_eq!(context.working_dir(), Some(std::path::PathBuf::from("sub")));
+        assert_eq!(context.get_project_root().unwrap(), project_root);
+    }
+
+    #[tokio::test]
+    async fn test_cd_keeps_the_whole_project_reachable() {
+        let temp_dir = TempDir::new().unwrap();
+        let outside_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path().join("project");
+        std::fs::create_dir_all(project_root.join("sub")).unwrap();
+        let project_root = project_root.canonicalize().unwrap();
+        let context = ToolContext::with_project_root(project_root.clone());
+        std::fs::write(project_root.join("x"), "sibling").unwrap();
+        std::fs::write(temp_dir.path().join("secret"), "secret").unwrap();
+        std::fs::write(outside_dir.path().join("secret"), "secret").unwrap();
+
+        cd(Some("sub")).call_with_context(&context).await.unwrap();
+
+        let read: ReadTool = serde_json::from_value(serde_json::json!({"path": "../x"})).unwrap();
+        assert!(text(&read.call_with_context(&context).await.unwrap()).contains("sibling"));
+
+        // Containment is still the project root, not the working directory's parent
+        let read: ReadTool = serde_json::from_value(serde_json::json!({"path": "../../secret"})).unwrap();
+        assert!(read.call_with_context(&context).await.is_err());
+        let outside = outside_dir.path().join("secret").to_string_lossy().into_owned();
+        let read: ReadTool = serde_json::from_value(serde_json::json!({"path": outside})).unwrap();
+        assert!(read.call_with_context(&context).await.is_err());
+    }
+}
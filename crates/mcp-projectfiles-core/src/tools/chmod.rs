@@ -1,43 +1,51 @@
 use crate::context::{StatefulTool, ToolContext};
 use crate::config::tool_errors;
-use crate::tools::utils::{format_path, format_count};
+use crate::tools::utils::{format_path, format_count, expand_pattern_in_project, outside_project_reason};
 use async_trait::async_trait;
 use rust_mcp_schema::{
     CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
 };
 use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tokio::fs;
-use glob::{glob_with, MatchOptions};
 
 const TOOL_NAME: &str = "chmod";
 
 #[mcp_tool(
     name = "chmod", 
-    description = "Change file permissions (Unix). Octal modes, recursive, patterns.
-Examples: {\"path\": \"script.sh\", \"mode\": \"755\"} or {\"path\": \"*.sh\", \"mode\": \"755\", \"pattern\": true}"
+    description = "Change file permissions (Unix). Octal or symbolic modes, recursive, patterns.
+Examples: {\"path\": \"script.sh\", \"mode\": \"755\"} or {\"path\": \"*.sh\", \"mode\": \"u+x\", \"pattern\": true, \"dry_run\": true}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct ChmodTool {
     /// Path to the file or directory (relative to project root)
     pub path: String,
-    /// Permissions mode in octal format (e.g., "755", "644")
+    /// Permissions mode in octal ("755", "644") or symbolic ("u+x", "go-w", "a=r") format
     pub mode: String,
     /// Whether to apply permissions recursively to directories (default: false)
     #[serde(default)]
     pub recursive: bool,
-    /// Pattern matching mode - treat path as a glob pattern for bulk operations (default: false)
+    /// Pattern matching mode - treat path as a glob pattern for bulk operations, with braces
+    /// like "bin/*.{sh,py}" expanding to several patterns and "**" matching nested directories,
+    /// as in "src/**/*.sh". Matches resolving outside the project are skipped and listed (default: false)
     #[serde(default)]
     pub pattern: bool,
+    /// Perform a dry run - list each path with its old and new mode without changing anything (default: false)
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 #[async_trait]
 impl StatefulTool for ChmodTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
+        context.ensure_writable(TOOL_NAME)?;
+
         // Check if we're on a Unix-like system
         #[cfg(not(unix))]
         {
@@ -59,40 +67,20 @@ impl StatefulTool for ChmodTool {
                 .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to canonicalize project root: {}", e))))?;
             
             if self.pattern {
-                // Pattern matching mode - treat path as glob pattern
-                let pattern_path = if Path::new(&self.path).is_absolute() {
-                    self.path.clone()
-                } else {
-                    format!("{}/{}", current_dir.display(), self.path)
-                };
-                
-                let options = MatchOptions {
-                    require_literal_separator: false,
-                    require_literal_leading_dot: false,
-                    ..Default::default()
-                };
-                
-                let paths: Vec<_> = glob_with(&pattern_path, options)
-                    .map_err(|e| CallToolError::from(tool_errors::pattern_error(TOOL_NAME, &self.path, &e.to_string())))?
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to expand pattern: {}", e))))?;
-                
-                if paths.is_empty() {
-                    return Err(CallToolError::from(tool_errors::file_not_found(
-                        TOOL_NAME,
-                        &format!("No files found matching pattern: {}", self.path)
-                    )));
-                }
+                // Pattern matching mode - treat path as glob pattern.
+                // `*` stays within one directory; `**` descends into subdirectories
+                let paths: Vec<PathBuf> = expand_pattern_in_project(&self.path, &current_dir, TOOL_NAME)?
+                    .into_iter()
+                    .map(|relative| current_dir.join(relative))
+                    .collect();
                 
                 // Parse the mode
-                let mode = u32::from_str_radix(&self.mode, 8)
-                    .map_err(|_| CallToolError::from(tool_errors::invalid_input(
-                        TOOL_NAME,
-                        &format!("Invalid mode '{}'. Must be an octal number like '755' or '644'", self.mode)
-                    )))?;
+                let mode = ModeSpec::parse(&self.mode)?;
                 
                 let mut changed_paths = Vec::new();
                 let mut _total_changed = 0;
+                let mut planned = Vec::new();
+                let mut skipped = Vec::new();
                 
                 for path in paths {
                     // Security check: ensure path is within project directory
@@ -100,7 +88,9 @@ impl StatefulTool for ChmodTool {
                         .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to resolve path '{}': {}", path.display(), e))))?;
                     
                     if !canonical_path.starts_with(&current_dir) {
-                        continue; // Skip paths outside project directory
+                        // Skip paths outside project directory, but report them
+                        skipped.push(path);
+                        continue;
                     }
                     
                     // Apply chmod
@@ -108,12 +98,16 @@ impl StatefulTool for ChmodTool {
                         .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read metadata for '{}': {}", path.display(), e))))?;
                     
                     let changed_count = if metadata.is_file() || (metadata.is_dir() && !self.recursive) {
-                        let permissions = std::fs::Permissions::from_mode(mode);
-                        fs::set_permissions(&canonical_path, permissions).await
-                            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to set permissions for '{}': {}", path.display(), e))))?;
+                        let (old_mode, new_mode) = mode_change(&metadata, &mode);
+                        if !self.dry_run {
+                            let permissions = std::fs::Permissions::from_mode(new_mode);
+                            fs::set_permissions(&canonical_path, permissions).await
+                                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to set permissions for '{}': {}", path.display(), e))))?;
+                        }
+                        planned.push((canonical_path.clone(), old_mode, new_mode));
                         1
                     } else if metadata.is_dir() && self.recursive {
-                        chmod_recursive(&canonical_path, mode).await?
+                        chmod_recursive(&canonical_path, &mode, self.dry_run, &mut planned, context).await?
                     } else {
                         0
                     };
@@ -124,15 +118,26 @@ impl StatefulTool for ChmodTool {
                     }
                 }
                 
+                if self.dry_run {
+                    return Ok(dry_run_result(
+                        &format!("matching pattern '{}'", self.path),
+                        &self.mode,
+                        &planned,
+                        &skipped,
+                        &current_dir,
+                    ));
+                }
+                
                 let summary = format!(
-                    "Changed permissions to {} for {} matching pattern '{}':\n{}",
+                    "Changed permissions to {} for {} matching pattern '{}':\n{}{}",
                     self.mode,
                     format_count(changed_paths.len(), "path", "paths"),
                     self.path,
                     changed_paths.iter()
                         .map(|p| format!("  {}", format_path(Path::new(p))))
                         .collect::<Vec<_>>()
-                        .join("\n")
+                        .join("\n"),
+                    skipped_warning(&skipped, &current_dir)
                 );
                 
                 return Ok(CallToolResult {
@@ -159,7 +164,7 @@ impl StatefulTool for ChmodTool {
                 return Err(CallToolError::from(tool_errors::access_denied(
                     TOOL_NAME,
                     &self.path,
-                    "Path is outside the project directory"
+                    &outside_project_reason("Path is outside the project directory", &canonical_path)
                 )));
             }
             
@@ -171,34 +176,45 @@ impl StatefulTool for ChmodTool {
             }
             
             // Parse the mode
-            let mode = u32::from_str_radix(&self.mode, 8)
-                .map_err(|_| CallToolError::from(tool_errors::invalid_input(
-                    TOOL_NAME,
-                    &format!("Invalid mode '{}'. Must be an octal number like '755' or '644'", self.mode)
-                )))?;
+            let mode = ModeSpec::parse(&self.mode)?;
             
             let metadata = fs::metadata(&canonical_path)
                 .await
                 .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read metadata: {}", e))))?;
             
             let mut changed_count = 0;
+            let mut planned = Vec::new();
             
             if metadata.is_file() || (metadata.is_dir() && !self.recursive) {
                 // Single file or non-recursive directory
-                let permissions = std::fs::Permissions::from_mode(mode);
-                fs::set_permissions(&canonical_path, permissions)
-                    .await
-                    .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to set permissions: {}", e))))?;
+                let (old_mode, new_mode) = mode_change(&metadata, &mode);
+                if !self.dry_run {
+                    let permissions = std::fs::Permissions::from_mode(new_mode);
+                    fs::set_permissions(&canonical_path, permissions)
+                        .await
+                        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to set permissions: {}", e))))?;
+                }
+                planned.push((canonical_path.clone(), old_mode, new_mode));
                 changed_count = 1;
             } else if metadata.is_dir() && self.recursive {
                 // Recursive directory permissions
-                changed_count = chmod_recursive(&canonical_path, mode).await?;
+                changed_count = chmod_recursive(&canonical_path, &mode, self.dry_run, &mut planned, context).await?;
             }
             
             // Format path relative to project root
             let relative_path = canonical_path.strip_prefix(&current_dir)
                 .unwrap_or(&canonical_path);
             
+            if self.dry_run {
+                return Ok(dry_run_result(
+                    &format!("for {}", format_path(relative_path)),
+                    &self.mode,
+                    &planned,
+                    &[],
+                    &current_dir,
+                ));
+            }
+            
             let message = if self.recursive && changed_count > 1 {
                 format!(
                     "Changed permissions to {} for {} ({})",
@@ -225,18 +241,230 @@ impl StatefulTool for ChmodTool {
     }
 }
 
+/// A parsed permissions mode: either an absolute octal value or a list of
+/// symbolic clauses applied relative to each file's current permissions.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ModeSpec {
+    Octal(u32),
+    Symbolic(Vec<SymbolicClause>),
+}
+
+/// A single symbolic clause such as "u+x" or "go=rw"
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SymbolicClause {
+    /// Bitmask of the permission classes affected (user, group, other)
+    who: u32,
+    /// Operators and the permission symbols they apply
+    actions: Vec<(char, String)>,
+}
+
+/// Permission bits owned by each class, including the matching special bit
+const USER_MASK: u32 = 0o4700;
+const GROUP_MASK: u32 = 0o2070;
+const OTHER_MASK: u32 = 0o1007;
+
+impl ModeSpec {
+    /// Parse an octal ("755") or symbolic ("u+x,go-w") mode string
+    pub(crate) fn parse(mode: &str) -> Result<Self, CallToolError> {
+        let invalid = || CallToolError::from(tool_errors::invalid_input(
+            TOOL_NAME,
+            &format!("Invalid mode '{}'. Must be an octal number like '755' or '644', or a symbolic mode like 'u+x' or 'go-w'", mode)
+        ));
+
+        if !mode.chars().any(|c| "ugoa+-=".contains(c)) {
+            let value = u32::from_str_radix(mode, 8).map_err(|_| invalid())?;
+            return Ok(ModeSpec::Octal(value));
+        }
+
+        let mut clauses = Vec::new();
+        for clause in mode.split(',') {
+            let mut chars = clause.chars().peekable();
+
+            let mut who = 0;
+            while let Some(&c) = chars.peek() {
+                who |= match c {
+                    'u' => USER_MASK,
+                    'g' => GROUP_MASK,
+                    'o' => OTHER_MASK,
+                    'a' => USER_MASK | GROUP_MASK | OTHER_MASK,
+                    _ => break,
+                };
+                chars.next();
+            }
+            if who == 0 {
+                // No class given: apply to everyone, like "a"
+                who = USER_MASK | GROUP_MASK | OTHER_MASK;
+            }
+
+            let mut actions: Vec<(char, String)> = Vec::new();
+            for c in chars {
+                match c {
+                    '+' | '-' | '=' => actions.push((c, String::new())),
+                    'r' | 'w' | 'x' | 'X' | 's' | 't' | 'u' | 'g' | 'o' => {
+                        let (_, perms) = actions.last_mut().ok_or_else(invalid)?;
+                        perms.push(c);
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+            if actions.is_empty() {
+                return Err(invalid());
+            }
+
+            clauses.push(SymbolicClause { who, actions });
+        }
+
+        Ok(ModeSpec::Symbolic(clauses))
+    }
+
+    /// Compute the new mode for a file given its current metadata
+    #[cfg(unix)]
+    fn apply(&self, metadata: &std::fs::Metadata) -> u32 {
+        use std::os::unix::fs::PermissionsExt;
+        self.apply_to(metadata.permissions().mode() & 0o7777, metadata.is_dir())
+    }
+
+    /// Compute the new mode from a current mode value
+    fn apply_to(&self, current: u32, is_dir: bool) -> u32 {
+        let clauses = match self {
+            ModeSpec::Octal(mode) => return *mode,
+            ModeSpec::Symbolic(clauses) => clauses,
+        };
+
+        let mut mode = current;
+        for clause in clauses {
+            for (op, perms) in &clause.actions {
+                let mut bits = 0;
+                for p in perms.chars() {
+                    bits |= match p {
+                        'r' => 0o444,
+                        'w' => 0o222,
+                        'x' => 0o111,
+                        'X' if is_dir || mode & 0o111 != 0 => 0o111,
+                        'X' => 0,
+                        's' => 0o6000,
+                        't' => 0o1000,
+                        // Copy permissions from another class of the current mode
+                        'u' => ((mode >> 6) & 0o7) * 0o111,
+                        'g' => ((mode >> 3) & 0o7) * 0o111,
+                        'o' => (mode & 0o7) * 0o111,
+                        _ => 0,
+                    };
+                }
+                let bits = bits & clause.who;
+                match op {
+                    '+' => mode |= bits,
+                    '-' => mode &= !bits,
+                    _ => mode = (mode & !clause.who) | bits,
+                }
+            }
+        }
+        mode
+    }
+}
+
+/// Compute the current and new permission bits for a file
+#[cfg(unix)]
+fn mode_change(metadata: &std::fs::Metadata, mode: &ModeSpec) -> (u32, u32) {
+    use std::os::unix::fs::PermissionsExt;
+    (metadata.permissions().mode() & 0o7777, mode.apply(metadata))
+}
+
+/// Apply a mode to a single path, returning true when its permissions changed.
+/// Callers are responsible for checking the path is inside the project.
+#[cfg(unix)]
+pub(crate) async fn apply_mode(path: &Path, mode: &ModeSpec) -> std::io::Result<bool> {
+    use std::os::unix::fs::PermissionsExt;
+    let metadata = fs::metadata(path).await?;
+    let (old_mode, new_mode) = mode_change(&metadata, mode);
+    if old_mode == new_mode {
+        return Ok(false);
+    }
+    fs::set_permissions(path, std::fs::Permissions::from_mode(new_mode)).await?;
+    Ok(true)
+}
+
+/// Build the dry run summary listing each path with its old and new mode
+#[cfg(unix)]
+fn dry_run_result(target: &str, mode: &str, planned: &[(PathBuf, u32, u32)], skipped: &[PathBuf], project_root: &Path) -> CallToolResult {
+    let mut message = format!(
+        "[DRY RUN] Would change permissions to {} {} ({}):\n",
+        mode,
+        target,
+        format_count(planned.len(), "item", "items")
+    );
+    for (path, old_mode, new_mode) in planned {
+        let relative_path = path.strip_prefix(project_root).unwrap_or(path);
+        message.push_str(&format!(
+            "  {} {:o} -> {:o}\n",
+            format_path(relative_path),
+            old_mode,
+            new_mode
+        ));
+    }
+    message.push_str("No permissions were changed (dry run mode).");
+    message.push_str(&skipped_warning(skipped, project_root));
+    
+    CallToolResult {
+        content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+            message, None,
+        ))],
+        is_error: Some(false),
+        meta: None,
+    }
+}
+
+/// Warning listing pattern matches that resolve outside the project and were left alone
 #[cfg(unix)]
-fn chmod_recursive(path: &Path, mode: u32) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<usize, CallToolError>> + Send + '_>> {
+fn skipped_warning(skipped: &[PathBuf], project_root: &Path) -> String {
+    if skipped.is_empty() {
+        return String::new();
+    }
+    let mut warning = format!(
+        "\nWarning: skipped {} outside the project directory:",
+        format_count(skipped.len(), "match", "matches")
+    );
+    for path in skipped {
+        let relative_path = path.strip_prefix(project_root).unwrap_or(path);
+        warning.push_str(&format!("\n  {}", format_path(relative_path)));
+    }
+    warning
+}
+
+/// Report how many paths a recursive chmod has handled so far
+#[cfg(unix)]
+fn report_progress(context: &ToolContext, planned: &[(PathBuf, u32, u32)]) {
+    context.report_progress(planned.len() as u64, None, || {
+        format!("Processed {}", format_count(planned.len(), "path", "paths"))
+    });
+}
+
+#[cfg(unix)]
+fn chmod_recursive<'a>(
+    path: &'a Path,
+    mode: &'a ModeSpec,
+    dry_run: bool,
+    planned: &'a mut Vec<(PathBuf, u32, u32)>,
+    context: &'a ToolContext,
+) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<usize, CallToolError>> + Send + 'a>> {
     Box::pin(async move {
     use std::os::unix::fs::PermissionsExt;
     
     let mut count = 1;
     
     // Set permissions on the directory itself
-    let permissions = std::fs::Permissions::from_mode(mode);
-    fs::set_permissions(path, permissions)
+    let metadata = fs::metadata(path)
         .await
-        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to set permissions: {}", e))))?;
+        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read metadata: {}", e))))?;
+    let (old_mode, new_mode) = mode_change(&metadata, mode);
+    if !dry_run {
+        let permissions = std::fs::Permissions::from_mode(new_mode);
+        fs::set_permissions(path, permissions)
+            .await
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to set permissions: {}", e))))?;
+    }
+    planned.push((path.to_path_buf(), old_mode, new_mode));
+    report_progress(context, planned);
     
     // Read directory entries
     let mut entries = fs::read_dir(path)
@@ -244,6 +472,7 @@ fn chmod_recursive(path: &Path, mode: u32) -> std::pin::Pin<Box<dyn std::future:
         .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read directory: {}", e))))?;
     
     loop {
+        context.check_cancelled(TOOL_NAME)?;
         match entries.next_entry().await {
             Ok(Some(entry)) => {
                 let entry_path = entry.path();
@@ -251,12 +480,20 @@ fn chmod_recursive(path: &Path, mode: u32) -> std::pin::Pin<Box<dyn std::future:
                     .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get file type: {}", e))))?;
                 
                 if file_type.is_dir() {
-                    count += Box::pin(chmod_recursive(&entry_path, mode)).await?;
+                    count += Box::pin(chmod_recursive(&entry_path, mode, dry_run, &mut *planned, context)).await?;
                 } else {
-                    let permissions = std::fs::Permissions::from_mode(mode);
-                    fs::set_permissions(&entry_path, permissions)
+                    let metadata = fs::metadata(&entry_path)
                         .await
-                        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to set permissions: {}", e))))?;
+                        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read metadata: {}", e))))?;
+                    let (old_mode, new_mode) = mode_change(&metadata, mode);
+                    if !dry_run {
+                        let permissions = std::fs::Permissions::from_mode(new_mode);
+                        fs::set_permissions(&entry_path, permissions)
+                            .await
+                            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to set permissions: {}", e))))?;
+                    }
+                    planned.push((entry_path, old_mode, new_mode));
+                    report_progress(context, planned);
                     count += 1;
                 }
             }
@@ -298,6 +535,7 @@ mod tests {
             mode: "644".to_string(),
             recursive: false,
             pattern: false,
+            dry_run: false,
         };
         
         let result = chmod_tool.call_with_context(&context).await;
@@ -339,6 +577,7 @@ mod tests {
             mode: "755".to_string(),
             recursive: true,
             pattern: false,
+            dry_run: false,
         };
         
         let result = chmod_tool.call_with_context(&context).await;
@@ -380,6 +619,7 @@ mod tests {
             mode: "600".to_string(),
             recursive: false,
             pattern: true,
+            dry_run: false,
         };
         
         let result = chmod_tool.call_with_context(&context).await;
@@ -407,6 +647,80 @@ mod tests {
         }
     }
     
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_chmod_pattern_with_braces() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let project_root = context.get_project_root().unwrap();
+        fs::create_dir(project_root.join("bin")).await.unwrap();
+        for name in ["bin/run.sh", "bin/tool.py", "bin/notes.md", "setup.sh"] {
+            fs::write(project_root.join(name), "content").await.unwrap();
+        }
+        
+        // Overlapping expansions only change each file once
+        let chmod_tool = ChmodTool {
+            path: "{bin/*.{sh,py},bin/run.*,setup.sh}".to_string(),
+            mode: "700".to_string(),
+            recursive: false,
+            pattern: true,
+            dry_run: false,
+        };
+        let result = chmod_tool.call_with_context(&context).await.unwrap();
+        if let CallToolResultContentItem::TextContent(text) = &result.content[0] {
+            assert!(text.text.contains("for 3 paths"), "{}", text.text);
+        }
+        
+        use std::os::unix::fs::PermissionsExt;
+        for (name, expected) in [("bin/run.sh", true), ("bin/tool.py", true), ("setup.sh", true), ("bin/notes.md", false)] {
+            let mode = fs::metadata(project_root.join(name)).await.unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode == 0o700, expected, "{}", name);
+        }
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_chmod_pattern_recursive_reports_skipped() {
+        let (context, _temp_dir) = setup_test_context().await;
+        let outside = TempDir::new().unwrap();
+        
+        let project_root = context.get_project_root().unwrap();
+        fs::create_dir_all(project_root.join("src/a/b")).await.unwrap();
+        for name in ["src/top.sh", "src/a/mid.sh", "src/a/b/deep.sh", "src/a/b/keep.txt"] {
+            fs::write(project_root.join(name), "content").await.unwrap();
+        }
+        fs::write(outside.path().join("escape.sh"), "content").await.unwrap();
+        std::os::unix::fs::symlink(outside.path(), project_root.join("src/linked")).unwrap();
+        
+        let chmod_tool = |dry_run: bool| ChmodTool {
+            path: "src/**/*.sh".to_string(),
+            mode: "700".to_string(),
+            recursive: false,
+            pattern: true,
+            dry_run,
+        };
+        
+        let result = chmod_tool(true).call_with_context(&context).await.unwrap();
+        if let CallToolResultContentItem::TextContent(text) = &result.content[0] {
+            assert!(text.text.contains("(3 items)"), "{}", text.text);
+            assert!(text.text.ends_with("\nWarning: skipped 1 match outside the project directory:\n  'src/linked/escape.sh'"));
+        }
+        
+        let result = chmod_tool(false).call_with_context(&context).await.unwrap();
+        if let CallToolResultContentItem::TextContent(text) = &result.content[0] {
+            assert!(text.text.contains("for 3 paths"), "{}", text.text);
+            assert!(text.text.ends_with("\nWarning: skipped 1 match outside the project directory:\n  'src/linked/escape.sh'"));
+        }
+        
+        use std::os::unix::fs::PermissionsExt;
+        for name in ["src/top.sh", "src/a/mid.sh", "src/a/b/deep.sh"] {
+            let mode = fs::metadata(project_root.join(name)).await.unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode, 0o700, "{}", name);
+        }
+        let mode = std::fs::metadata(outside.path().join("escape.sh")).unwrap().permissions().mode() & 0o777;
+        assert_ne!(mode, 0o700);
+    }
+    
     #[cfg(unix)]
     #[tokio::test]
     async fn test_chmod_invalid_mode() {
@@ -421,6 +735,7 @@ mod tests {
             mode: "999".to_string(), // Invalid octal mode
             recursive: false,
             pattern: false,
+            dry_run: false,
         };
         
         let result = chmod_tool.call_with_context(&context).await;
@@ -440,6 +755,7 @@ mod tests {
             mode: "644".to_string(),
             recursive: false,
             pattern: false,
+            dry_run: false,
         };
         
         let result = chmod_tool.call_with_context(&context).await;
@@ -459,6 +775,7 @@ mod tests {
             mode: "644".to_string(),
             recursive: false,
             pattern: false,
+            dry_run: false,
         };
         
         let result = chmod_tool.call_with_context(&context).await;
@@ -478,6 +795,7 @@ mod tests {
             mode: "644".to_string(),
             recursive: false,
             pattern: false,
+            dry_run: false,
         };
         
         let result = chmod_tool.call_with_context(&context).await;
@@ -503,6 +821,7 @@ mod tests {
             mode: "700".to_string(),
             recursive: false,
             pattern: false,
+            dry_run: false,
         };
         
         let result = chmod_tool.call_with_context(&context).await;
@@ -536,6 +855,7 @@ mod tests {
             mode: "644".to_string(),
             recursive: false,
             pattern: true,
+            dry_run: false,
         };
         
         let result = chmod_tool.call_with_context(&context).await;
@@ -564,6 +884,7 @@ mod tests {
             mode: "600".to_string(),
             recursive: false,
             pattern: false,
+            dry_run: false,
         };
         
         let result = chmod_tool.call_with_context(&context).await;
@@ -594,6 +915,7 @@ mod tests {
                 mode: mode.to_string(),
                 recursive: false,
                 pattern: false,
+                dry_run: false,
             };
             
             let result = chmod_tool.call_with_context(&context).await;
@@ -624,6 +946,7 @@ mod tests {
                 mode: mode_str.to_string(),
                 recursive: false,
                 pattern: false,
+                dry_run: false,
             };
             
             let result = chmod_tool.call_with_context(&context).await;
@@ -662,6 +985,7 @@ mod tests {
             mode: "755".to_string(),
             recursive: false,
             pattern: true,
+            dry_run: false,
         };
         
         let result = chmod_tool.call_with_context(&context).await;
@@ -710,6 +1034,7 @@ mod tests {
             mode: "644".to_string(),
             recursive: false,
             pattern: false,
+            dry_run: false,
         };
         
         let result = chmod_tool.call_with_context(&context).await;
@@ -738,6 +1063,7 @@ mod tests {
             mode: "600".to_string(),
             recursive: false,
             pattern: true,
+            dry_run: false,
         };
         
         let result = chmod_tool.call_with_context(&context).await;
@@ -754,4 +1080,166 @@ mod tests {
         let normal_mode = normal_metadata.permissions().mode() & 0o777;
         assert_ne!(normal_mode, 0o600);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_parse_symbolic_modes() {
+        let cases = vec![
+            ("u+x", 0o644, false, 0o744),
+            ("go-w", 0o666, false, 0o644),
+            ("a=r", 0o755, false, 0o444),
+            ("u=rwx,g=rx,o=", 0o000, false, 0o750),
+            ("+x", 0o644, false, 0o755),
+            ("a+X", 0o644, false, 0o644),
+            ("a+X", 0o644, true, 0o755),
+            ("u+s", 0o755, false, 0o4755),
+            ("g=u", 0o740, false, 0o770),
+            ("u-x+w", 0o544, false, 0o644),
+        ];
+        
+        for (mode, current, is_dir, expected) in cases {
+            let spec = ModeSpec::parse(mode).unwrap();
+            assert_eq!(spec.apply_to(current, is_dir), expected,
+                      "Applying '{}' to {:o} should give {:o}", mode, current, expected);
+        }
+    }
+    
+    #[test]
+    fn test_parse_invalid_symbolic_modes() {
+        for mode in ["u", "u+q", "z+x", "u+x,", "+x7"] {
+            assert!(ModeSpec::parse(mode).is_err(), "Mode '{}' should be invalid", mode);
+        }
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_chmod_symbolic_relative_to_current() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        use std::os::unix::fs::PermissionsExt;
+        let project_root = context.get_project_root().unwrap();
+        let file_path = project_root.join("script.sh");
+        fs::write(&file_path, "#!/bin/sh").await.unwrap();
+        fs::set_permissions(&file_path, std::fs::Permissions::from_mode(0o640)).await.unwrap();
+        
+        let chmod_tool = ChmodTool {
+            path: "script.sh".to_string(),
+            mode: "u+x".to_string(),
+            recursive: false,
+            pattern: false,
+            dry_run: false,
+        };
+        
+        let result = chmod_tool.call_with_context(&context).await;
+        assert!(result.is_ok());
+        
+        let mode = fs::metadata(&file_path).await.unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o740);
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_chmod_symbolic_recursive() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        use std::os::unix::fs::PermissionsExt;
+        let project_root = context.get_project_root().unwrap();
+        let dir_path = project_root.join("data");
+        fs::create_dir(&dir_path).await.unwrap();
+        fs::write(dir_path.join("a.txt"), "a").await.unwrap();
+        fs::write(dir_path.join("b.txt"), "b").await.unwrap();
+        fs::set_permissions(dir_path.join("a.txt"), std::fs::Permissions::from_mode(0o666)).await.unwrap();
+        fs::set_permissions(dir_path.join("b.txt"), std::fs::Permissions::from_mode(0o622)).await.unwrap();
+        
+        let chmod_tool = ChmodTool {
+            path: "data".to_string(),
+            mode: "go-w".to_string(),
+            recursive: true,
+            pattern: false,
+            dry_run: false,
+        };
+        
+        let result = chmod_tool.call_with_context(&context).await;
+        assert!(result.is_ok());
+        
+        let a_mode = fs::metadata(dir_path.join("a.txt")).await.unwrap().permissions().mode() & 0o777;
+        let b_mode = fs::metadata(dir_path.join("b.txt")).await.unwrap().permissions().mode() & 0o777;
+        assert_eq!(a_mode, 0o644);
+        assert_eq!(b_mode, 0o600);
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_chmod_dry_run_single_file() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        use std::os::unix::fs::PermissionsExt;
+        let project_root = context.get_project_root().unwrap();
+        let file_path = project_root.join("test.txt");
+        fs::write(&file_path, "content").await.unwrap();
+        fs::set_permissions(&file_path, std::fs::Permissions::from_mode(0o644)).await.unwrap();
+        
+        let chmod_tool = ChmodTool {
+            path: "test.txt".to_string(),
+            mode: "u+x".to_string(),
+            recursive: false,
+            pattern: false,
+            dry_run: true,
+        };
+        
+        let result = chmod_tool.call_with_context(&context).await.unwrap();
+        if let CallToolResultContentItem::TextContent(text) = &result.content[0] {
+            assert!(text.text.contains("[DRY RUN]"));
+            assert!(text.text.contains("'test.txt' 644 -> 744"));
+            assert!(text.text.contains("No permissions were changed"));
+        }
+        
+        // File must be untouched
+        let mode = fs::metadata(&file_path).await.unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o644);
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_chmod_dry_run_recursive_and_pattern() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        use std::os::unix::fs::PermissionsExt;
+        let project_root = context.get_project_root().unwrap();
+        let dir_path = project_root.join("scripts");
+        fs::create_dir(&dir_path).await.unwrap();
+        fs::write(dir_path.join("a.sh"), "a").await.unwrap();
+        fs::write(dir_path.join("b.sh"), "b").await.unwrap();
+        fs::set_permissions(dir_path.join("a.sh"), std::fs::Permissions::from_mode(0o600)).await.unwrap();
+        
+        let chmod_tool = ChmodTool {
+            path: "scripts".to_string(),
+            mode: "700".to_string(),
+            recursive: true,
+            pattern: false,
+            dry_run: true,
+        };
+        
+        let result = chmod_tool.call_with_context(&context).await.unwrap();
+        if let CallToolResultContentItem::TextContent(text) = &result.content[0] {
+            assert!(text.text.contains("3 items"));
+            assert!(text.text.contains("'scripts/a.sh' 600 -> 700"));
+        }
+        
+        let chmod_tool = ChmodTool {
+            path: "scripts/*.sh".to_string(),
+            mode: "700".to_string(),
+            recursive: false,
+            pattern: true,
+            dry_run: true,
+        };
+        
+        let result = chmod_tool.call_with_context(&context).await.unwrap();
+        if let CallToolResultContentItem::TextContent(text) = &result.content[0] {
+            assert!(text.text.contains("matching pattern 'scripts/*.sh'"));
+            assert!(text.text.contains("2 items"));
+        }
+        
+        let mode = fs::metadata(dir_path.join("a.sh")).await.unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o600);
+    }
+}
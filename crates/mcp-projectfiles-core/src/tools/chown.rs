@@ -0,0 +1,424 @@
+use crate::context::{StatefulTool, ToolContext};
+use crate::config::tool_errors;
+use crate::tools::utils::{format_path, format_count};
+use async_trait::async_trait;
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use glob::{glob_with, MatchOptions};
+
+const TOOL_NAME: &str = "chown";
+
+#[mcp_tool(
+    name = "chown",
+    description = "Change file owner and group (Unix). Names or numeric ids, recursive, patterns.
+Examples: {\"path\": \"build\", \"owner\": \"alice\", \"recursive\": true} or {\"path\": \"*.log\", \"owner\": \"1000\", \"group\": \"1000\", \"pattern\": true}"
+)]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct ChownTool {
+    /// Path to the file or directory (relative to project root)
+    pub path: String,
+    /// New owner as a user name or numeric uid (e.g., "alice", "1000")
+    pub owner: String,
+    /// New group as a group name or numeric gid (optional, group is left unchanged when omitted)
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Whether to apply ownership recursively to directories (default: false)
+    #[serde(default)]
+    pub recursive: bool,
+    /// Pattern matching mode - treat path as a glob pattern for bulk operations (default: false)
+    #[serde(default)]
+    pub pattern: bool,
+}
+
+#[async_trait]
+impl StatefulTool for ChownTool {
+    async fn call_with_context(
+        mut self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
+        context.ensure_writable(TOOL_NAME)?;
+
+        // Check if we're on a Unix-like system
+        #[cfg(not(unix))]
+        {
+            return Err(CallToolError::from(tool_errors::operation_not_permitted(
+                TOOL_NAME,
+                "chown is only available on Unix-like systems"
+            )));
+        }
+
+        #[cfg(unix)]
+        {
+            let project_root = context.get_project_root()
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
+
+            // Canonicalize project root for consistent path comparison
+            let current_dir = project_root.canonicalize()
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to canonicalize project root: {}", e))))?;
+
+            let uid = resolve_uid(&self.owner)?;
+            let gid = self.group.as_deref().map(resolve_gid).transpose()?;
+
+            let ownership = match &self.group {
+                Some(group) => format!("{}:{}", self.owner, group),
+                None => self.owner.clone(),
+            };
+
+            if self.pattern {
+                // Pattern matching mode - treat path as glob pattern
+                let pattern_path = if Path::new(&self.path).is_absolute() {
+                    self.path.clone()
+                } else {
+                    format!("{}/{}", current_dir.display(), self.path)
+                };
+
+                let options = MatchOptions {
+                    require_literal_separator: false,
+                    require_literal_leading_dot: false,
+                    ..Default::default()
+                };
+
+                let paths: Vec<_> = glob_with(&pattern_path, options)
+                    .map_err(|e| CallToolError::from(tool_errors::pattern_error(TOOL_NAME, &self.path, &e.to_string())))?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to expand pattern: {}", e))))?;
+
+                if paths.is_empty() {
+                    return Err(CallToolError::from(tool_errors::file_not_found(
+                        TOOL_NAME,
+                        &format!("No files found matching pattern: {}", self.path)
+                    )));
+                }
+
+                let mut changed_paths = Vec::new();
+                let mut total_changed = 0;
+
+                for path in paths {
+                    // Security check: ensure path is within project directory
+                    let canonical_path = path.canonicalize()
+                        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to resolve path '{}': {}", path.display(), e))))?;
+
+                    if !canonical_path.starts_with(&current_dir) {
+                        continue; // Skip paths outside project directory
+                    }
+
+                    let changed_count = if self.recursive && canonical_path.is_dir() {
+                        chown_recursive(&canonical_path, uid, gid)?
+                    } else {
+                        set_owner(&canonical_path, uid, gid)?;
+                        1
+                    };
+
+                    changed_paths.push(path.display().to_string());
+                    total_changed += changed_count;
+                }
+
+                let summary = format!(
+                    "Changed ownership to {} for {} ({}) matching pattern '{}':\n{}",
+                    ownership,
+                    format_count(changed_paths.len(), "path", "paths"),
+                    format_count(total_changed, "item", "items"),
+                    self.path,
+                    changed_paths.iter()
+                        .map(|p| format!("  {}", format_path(Path::new(p))))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                );
+
+                return Ok(CallToolResult {
+                    content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                        summary, None,
+                    ))],
+                    is_error: None,
+                    meta: None,
+                });
+            }
+
+            // Single path mode
+            let requested_path = Path::new(&self.path);
+            let absolute_path = if requested_path.is_absolute() {
+                requested_path.to_path_buf()
+            } else {
+                current_dir.join(requested_path)
+            };
+
+            let canonical_path = absolute_path.canonicalize()
+                .map_err(|_e| CallToolError::from(tool_errors::file_not_found(TOOL_NAME, &self.path)))?;
+
+            if !canonical_path.starts_with(&current_dir) {
+                return Err(CallToolError::from(tool_errors::access_denied(
+                    TOOL_NAME,
+                    &self.path,
+                    "Path is outside the project directory"
+                )));
+            }
+
+            let changed_count = if self.recursive && canonical_path.is_dir() {
+                chown_recursive(&canonical_path, uid, gid)?
+            } else {
+                set_owner(&canonical_path, uid, gid)?;
+                1
+            };
+
+            // Format path relative to project root
+            let relative_path = canonical_path.strip_prefix(&current_dir)
+                .unwrap_or(&canonical_path);
+
+            let message = if self.recursive && changed_count > 1 {
+                format!(
+                    "Changed ownership to {} for {} ({})",
+                    ownership,
+                    format_path(relative_path),
+                    format_count(changed_count, "item", "items")
+                )
+            } else {
+                format!(
+                    "Changed ownership to {} for {}",
+                    ownership,
+                    format_path(relative_path)
+                )
+            };
+
+            Ok(CallToolResult {
+                content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                    message, None,
+                ))],
+                is_error: Some(false),
+                meta: None,
+            })
+        }
+    }
+}
+
+/// Resolve a user name or numeric uid to a uid
+#[cfg(unix)]
+fn resolve_uid(owner: &str) -> Result<u32, CallToolError> {
+    if let Ok(uid) = owner.parse::<u32>() {
+        return Ok(uid);
+    }
+    users::get_user_by_name(owner)
+        .map(|user| user.uid())
+        .ok_or_else(|| CallToolError::from(tool_errors::invalid_input(
+            TOOL_NAME,
+            &format!("Unknown user '{}'", owner)
+        )))
+}
+
+/// Resolve a group name or numeric gid to a gid
+#[cfg(unix)]
+fn resolve_gid(group: &str) -> Result<u32, CallToolError> {
+    if let Ok(gid) = group.parse::<u32>() {
+        return Ok(gid);
+    }
+    users::get_group_by_name(group)
+        .map(|group| group.gid())
+        .ok_or_else(|| CallToolError::from(tool_errors::invalid_input(
+            TOOL_NAME,
+            &format!("Unknown group '{}'", group)
+        )))
+}
+
+#[cfg(unix)]
+fn set_owner(path: &Path, uid: u32, gid: Option<u32>) -> Result<(), CallToolError> {
+    std::os::unix::fs::chown(path, Some(uid), gid)
+        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to change ownership of '{}': {}", path.display(), e))))
+}
+
+#[cfg(unix)]
+fn chown_recursive(path: &Path, uid: u32, gid: Option<u32>) -> Result<usize, CallToolError> {
+    let mut count = 1;
+
+    // Change ownership of the directory itself
+    set_owner(path, uid, gid)?;
+
+    let entries = std::fs::read_dir(path)
+        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read directory: {}", e))))?;
+
+    for entry in entries {
+        let entry = entry
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read entry: {}", e))))?;
+        let entry_path = entry.path();
+        let file_type = entry.file_type()
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get file type: {}", e))))?;
+
+        if file_type.is_dir() {
+            count += chown_recursive(&entry_path, uid, gid)?;
+        } else if file_type.is_symlink() {
+            // Change the link itself rather than following it out of the tree
+            std::os::unix::fs::lchown(&entry_path, Some(uid), gid)
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to change ownership of '{}': {}", entry_path.display(), e))))?;
+            count += 1;
+        } else {
+            set_owner(&entry_path, uid, gid)?;
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::ToolContext;
+    use tempfile::TempDir;
+    use tokio::fs;
+
+    async fn setup_test_context() -> (ToolContext, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let canonical_path = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(canonical_path);
+        (context, temp_dir)
+    }
+
+    #[cfg(unix)]
+    fn current_ids(path: &Path) -> (u32, u32) {
+        use std::os::unix::fs::MetadataExt;
+        let metadata = std::fs::metadata(path).unwrap();
+        (metadata.uid(), metadata.gid())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_chown_to_current_owner() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let project_root = context.get_project_root().unwrap();
+        let file_path = project_root.join("test.txt");
+        fs::write(&file_path, "content").await.unwrap();
+        let (uid, gid) = current_ids(&file_path);
+
+        // Changing to the current owner is always permitted, even without root
+        let chown_tool = ChownTool {
+            path: "test.txt".to_string(),
+            owner: uid.to_string(),
+            group: Some(gid.to_string()),
+            recursive: false,
+            pattern: false,
+        };
+
+        let result = chown_tool.call_with_context(&context).await;
+        assert!(result.is_ok());
+
+        let output = result.unwrap();
+        if let CallToolResultContentItem::TextContent(text) = &output.content[0] {
+            assert!(text.text.contains(&format!("Changed ownership to {}:{}", uid, gid)));
+            assert!(text.text.contains("test.txt"));
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_chown_recursive_counts_entries() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let project_root = context.get_project_root().unwrap();
+        let dir_path = project_root.join("build");
+        fs::create_dir_all(dir_path.join("nested")).await.unwrap();
+        fs::write(dir_path.join("a.txt"), "a").await.unwrap();
+        fs::write(dir_path.join("nested/b.txt"), "b").await.unwrap();
+        let (uid, _) = current_ids(&dir_path);
+
+        let chown_tool = ChownTool {
+            path: "build".to_string(),
+            owner: uid.to_string(),
+            group: None,
+            recursive: true,
+            pattern: false,
+        };
+
+        let result = chown_tool.call_with_context(&context).await.unwrap();
+        if let CallToolResultContentItem::TextContent(text) = &result.content[0] {
+            assert!(text.text.contains("4 items"));
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_chown_unknown_user() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let project_root = context.get_project_root().unwrap();
+        fs::write(project_root.join("test.txt"), "content").await.unwrap();
+
+        let chown_tool = ChownTool {
+            path: "test.txt".to_string(),
+            owner: "no-such-user-for-chown-test".to_string(),
+            group: None,
+            recursive: false,
+            pattern: false,
+        };
+
+        let result = chown_tool.call_with_context(&context).await;
+        assert!(result.is_err());
+
+        let error_msg = format!("{:?}", result.unwrap_err());
+        assert!(error_msg.contains("Unknown user"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_chown_nonexistent_file() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let chown_tool = ChownTool {
+            path: "nonexistent.txt".to_string(),
+            owner: "0".to_string(),
+            group: None,
+            recursive: false,
+            pattern: false,
+        };
+
+        let result = chown_tool.call_with_context(&context).await;
+        assert!(result.is_err());
+
+        let error_msg = format!("{:?}", result.unwrap_err());
+        assert!(error_msg.contains("not found"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_chown_pattern_no_matches() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let chown_tool = ChownTool {
+            path: "*.nonexistent".to_string(),
+            owner: "0".to_string(),
+            group: None,
+            recursive: false,
+            pattern: true,
+        };
+
+        let result = chown_tool.call_with_context(&context).await;
+        assert!(result.is_err());
+
+        let error_msg = format!("{:?}", result.unwrap_err());
+        assert!(error_msg.contains("No files found matching pattern"));
+    }
+
+    #[cfg(not(unix))]
+    #[tokio::test]
+    async fn test_chown_non_unix_system() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let chown_tool = ChownTool {
+            path: "test.txt".to_string(),
+            owner: "0".to_string(),
+            group: None,
+            recursive: false,
+            pattern: false,
+        };
+
+        let result = chown_tool.call_with_context(&context).await;
+        assert!(result.is_err());
+
+        let error_msg = format!("{:?}", result.unwrap_err());
+        assert!(error_msg.contains("Unix-like systems"));
+    }
+}
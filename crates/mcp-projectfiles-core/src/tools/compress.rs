@@ -0,0 +1,394 @@
+use crate::config::tool_errors;
+use crate::context::{StatefulTool, ToolContext};
+use crate::tools::utils::{
+    CancellableReader, create_new_in_project, format_path, format_size, open_for_write_in_project,
+    resolve_path_for_read, resolve_path_for_write, run_blocking,
+};
+
+use async_trait::async_trait;
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+const TOOL_NAME: &str = "compress";
+
+fn default_algorithm() -> String {
+    "gzip".to_string()
+}
+
+/// Single-file compression formats shared by the compress and decompress tools
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Algorithm {
+    Gzip,
+    Zstd,
+    Bzip2,
+}
+
+impl Algorithm {
+    pub(super) fn parse(name: &str, tool: &str) -> Result<Self, CallToolError> {
+        match name.to_lowercase().as_str() {
+            "gzip" | "gz" => Ok(Algorithm::Gzip),
+            "zstd" | "zst" => Ok(Algorithm::Zstd),
+            "bzip2" | "bz2" => Ok(Algorithm::Bzip2),
+            _ => Err(CallToolError::from(tool_errors::invalid_input(
+                tool,
+                &format!("Invalid algorithm '{}'. Must be 'gzip', 'zstd' or 'bzip2'", name),
+            ))),
+        }
+    }
+
+    /// Recognize a compressed stream from its first bytes
+    pub(super) fn from_magic(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            Some(Algorithm::Gzip)
+        } else if bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Algorithm::Zstd)
+        } else if bytes.starts_with(b"BZh") {
+            Some(Algorithm::Bzip2)
+        } else {
+            None
+        }
+    }
+
+    /// Recognize a compressed file from its extension
+    pub(super) fn from_extension(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "gz" | "gzip" => Some(Algorithm::Gzip),
+            "zst" | "zstd" => Some(Algorithm::Zstd),
+            "bz2" => Some(Algorithm::Bzip2),
+            _ => None,
+        }
+    }
+
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            Algorithm::Gzip => "gzip",
+            Algorithm::Zstd => "zstd",
+            Algorithm::Bzip2 => "bzip2",
+        }
+    }
+
+    pub(super) fn extension(self) -> &'static str {
+        match self {
+            Algorithm::Gzip => "gz",
+            Algorithm::Zstd => "zst",
+            Algorithm::Bzip2 => "bz2",
+        }
+    }
+
+    /// Valid compression levels and the default
+    fn levels(self) -> (i32, i32, i32) {
+        match self {
+            Algorithm::Gzip => (0, 9, 6),
+            Algorithm::Zstd => (1, 22, 3),
+            Algorithm::Bzip2 => (1, 9, 9),
+        }
+    }
+}
+
+/// Stream `reader` through a compressor into `writer`, returning the bytes read
+fn compress_stream(
+    algorithm: Algorithm,
+    level: i32,
+    reader: &mut impl Read,
+    writer: impl Write,
+) -> std::io::Result<u64> {
+    match algorithm {
+        Algorithm::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(writer, flate2::Compression::new(level as u32));
+            let read = std::io::copy(reader, &mut encoder)?;
+            encoder.finish()?.flush()?;
+            Ok(read)
+        }
+        Algorithm::Zstd => {
+            let mut encoder = zstd::stream::write::Encoder::new(writer, level)?;
+            let read = std::io::copy(reader, &mut encoder)?;
+            encoder.finish()?.flush()?;
+            Ok(read)
+        }
+        Algorithm::Bzip2 => {
+            let mut encoder = bzip2::write::BzEncoder::new(writer, bzip2::Compression::new(level as u32));
+            let read = std::io::copy(reader, &mut encoder)?;
+            encoder.finish()?.flush()?;
+            Ok(read)
+        }
+    }
+}
+
+/// Open the destination of a compress or decompress run. Without `overwrite` an existing file
+/// is refused; either way the file is opened without following a symlink swapped in since
+/// validation
+pub(super) fn open_destination(
+    destination: &Path,
+    display: &str,
+    project_root: &Path,
+    overwrite: bool,
+    tool: &str,
+) -> Result<File, CallToolError> {
+    if destination.exists() && !overwrite {
+        return Err(CallToolError::from(tool_errors::invalid_input(
+            tool,
+            &format!("'{}' already exists. Set overwrite=true to replace it", display),
+        )));
+    }
+    let (file, _) = if overwrite {
+        open_for_write_in_project(destination, project_root, false, tool)?
+    } else {
+        create_new_in_project(destination, project_root, tool)?
+    };
+    Ok(file)
+}
+
+/// Size change and original:compressed ratio, e.g. "10.0 MB -> 1.2 MB (ratio 8.33:1)".
+/// Sizes are listed in the order the run went
+pub(super) fn ratio_summary(original: u64, compressed: u64, decompressing: bool) -> String {
+    let ratio = if compressed == 0 { 0.0 } else { original as f64 / compressed as f64 };
+    let (before, after) = if decompressing { (compressed, original) } else { (original, compressed) };
+    format!("{} -> {} (ratio {:.2}:1)", format_size(before), format_size(after), ratio)
+}
+
+#[mcp_tool(
+    name = "compress",
+    description = "Compress a single file with gzip, zstd or bzip2, streaming so large files work. The source is kept. Returns the sizes and compression ratio.
+Examples: {\"source\": \"logs/app.log\"}, {\"source\": \"dump.sql\", \"algorithm\": \"zstd\", \"level\": 19, \"destination\": \"backups/dump.sql.zst\"}"
+)]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct CompressTool {
+    /// File to compress (relative to project root)
+    pub source: String,
+    /// Compressed file to write (default: source plus .gz, .zst or .bz2)
+    #[serde(default)]
+    pub destination: Option<String>,
+    /// "gzip" (default), "zstd" or "bzip2"
+    #[serde(default = "default_algorithm")]
+    pub algorithm: String,
+    /// Compression level: gzip 0-9 (default 6), zstd 1-22 (default 3), bzip2 1-9 (default 9)
+    #[serde(default)]
+    pub level: Option<i32>,
+    /// Replace the destination if it exists (default: false)
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[async_trait]
+impl StatefulTool for CompressTool {
+    async fn call_with_context(
+        mut self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        self.source = context.resolve_in_working_dir(&self.source);
+        self.destination = self.destination.map(|path| context.resolve_in_working_dir(&path));
+
+        context.ensure_writable(TOOL_NAME)?;
+
+        let project_root = context.get_project_root().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to get project root: {}", e),
+            ))
+        })?;
+        let project_root = project_root.canonicalize().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to canonicalize project root: {}", e),
+            ))
+        })?;
+        let algorithm = Algorithm::parse(&self.algorithm, TOOL_NAME)?;
+        let (min, max, default) = algorithm.levels();
+        let level = self.level.unwrap_or(default);
+        if !(min..=max).contains(&level) {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("{} level must be between {} and {}, got {}", algorithm.name(), min, max, level),
+            )));
+        }
+
+        let source = resolve_path_for_read(&self.source, &project_root, false, TOOL_NAME)?;
+        if !source.is_file() {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Path is not a file: {}", self.source),
+            )));
+        }
+        let destination_name = self
+            .destination
+            .clone()
+            .unwrap_or_else(|| format!("{}.{}", self.source, algorithm.extension()));
+        let destination = resolve_path_for_write(&destination_name, &project_root, TOOL_NAME)?;
+        if destination == source {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                "Source and destination must be different files",
+            )));
+        }
+
+        let blocking_source = source.clone();
+        let blocking_destination = destination.clone();
+        let blocking_root = project_root.clone();
+        let blocking_context = context.clone();
+        let original = run_blocking(TOOL_NAME, move || {
+            let (source, destination) = (blocking_source, blocking_destination);
+            let input = File::open(&source).map_err(|e| {
+                CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to open '{}': {}", self.source, e)))
+            })?;
+            let mut input = BufReader::new(CancellableReader::new(input, &blocking_context));
+            let created = !destination.exists();
+            let output = open_destination(&destination, &destination_name, &blocking_root, self.overwrite, TOOL_NAME)?;
+            compress_stream(algorithm, level, &mut input, BufWriter::new(output)).map_err(|e| {
+                if created {
+                    let _ = std::fs::remove_file(&destination);
+                }
+                if blocking_context.is_cancelled() {
+                    return CallToolError::from(tool_errors::cancelled(TOOL_NAME));
+                }
+                CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to compress '{}': {}", self.source, e)))
+            })
+        })
+        .await?;
+        context.record_modified(&destination).await;
+
+        let compressed = std::fs::metadata(&destination).map(|metadata| metadata.len()).unwrap_or(0);
+        let relative = |path: &PathBuf| format_path(path.strip_prefix(&project_root).unwrap_or(path));
+        let message = format!(
+            "Compressed {} to {} with {} level {}: {}",
+            relative(&source),
+            relative(&destination),
+            algorithm.name(),
+            level,
+            ratio_summary(original, compressed, false)
+        );
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                message, None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::DecompressTool;
+    use tempfile::TempDir;
+
+    fn text(result: &CallToolResult) -> &str {
+        match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => &text.text,
+            _ => panic!("Expected text content"),
+        }
+    }
+
+    fn compress(source: &str, algorithm: &str) -> CompressTool {
+        CompressTool {
+            source: source.to_string(),
+            destination: None,
+            algorithm: algorithm.to_string(),
+            level: None,
+            overwrite: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compress_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(project_root.clone());
+        let content = "2024-01-01 INFO request handled\n".repeat(2000);
+        std::fs::write(project_root.join("app.log"), &content).unwrap();
+
+        for (algorithm, extension) in [("gzip", "gz"), ("zstd", "zst"), ("bzip2", "bz2")] {
+            let result = compress("app.log", algorithm).call_with_context(&context).await.unwrap();
+            assert!(
+                text(&result).starts_with(&format!("Compressed 'app.log' to 'app.log.{}' with {}", extension, algorithm)),
+                "unexpected output: {}",
+                text(&result)
+            );
+            assert!(text(&result).contains("(ratio "), "{}", text(&result));
+
+            // Renamed so only the magic bytes tell the format
+            let compressed = project_root.join(format!("app.log.{}", extension));
+            std::fs::rename(&compressed, project_root.join("packed.bin")).unwrap();
+            let decompress = DecompressTool {
+                source: "packed.bin".to_string(),
+                destination: Some(format!("restored-{}.log", algorithm)),
+                algorithm: None,
+                overwrite: false,
+            };
+            let result = decompress.call_with_context(&context).await.unwrap();
+            assert!(text(&result).contains(&format!("({})", algorithm)), "{}", text(&result));
+            assert_eq!(
+                std::fs::read_to_string(project_root.join(format!("restored-{}.log", algorithm))).unwrap(),
+                content
+            );
+            std::fs::remove_file(project_root.join("packed.bin")).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compress_rejects_bad_input() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path().join("project");
+        std::fs::create_dir(&project_root).unwrap();
+        let context = ToolContext::with_project_root(project_root.clone());
+        std::fs::write(project_root.join("data.txt"), "data").unwrap();
+        std::fs::write(project_root.join("data.txt.gz"), "existing").unwrap();
+
+        // Existing destinations need overwrite
+        assert!(compress("data.txt", "gzip").call_with_context(&context).await.is_err());
+        assert_eq!(std::fs::read_to_string(project_root.join("data.txt.gz")).unwrap(), "existing");
+
+        let mut tool = compress("data.txt", "zstd");
+        tool.level = Some(30);
+        assert!(tool.call_with_context(&context).await.is_err());
+        assert!(compress("data.txt", "lzma").call_with_context(&context).await.is_err());
+
+        let mut tool = compress("data.txt", "gzip");
+        tool.destination = Some("../escaped.gz".to_string());
+        assert!(tool.call_with_context(&context).await.is_err());
+        assert!(!temp_dir.path().join("escaped.gz").exists());
+    }
+
+    #[tokio::test]
+    async fn test_decompress_stops_when_cancelled() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(project_root.clone());
+        std::fs::write(project_root.join("app.log"), "line\n".repeat(1000)).unwrap();
+        compress("app.log", "gzip").call_with_context(&context).await.unwrap();
+
+        let token = tokio_util::sync::CancellationToken::new();
+        token.cancel();
+        let decompress = DecompressTool {
+            source: "app.log.gz".to_string(),
+            destination: Some("restored.log".to_string()),
+            algorithm: None,
+            overwrite: false,
+        };
+        let error = decompress.call_with_context(&context.with_cancellation(token)).await.unwrap_err();
+        assert!(error.to_string().contains("Cancelled by the client"), "{}", error);
+        // The partial output is removed
+        assert!(!project_root.join("restored.log").exists());
+    }
+
+    #[tokio::test]
+    async fn test_compress_stops_when_cancelled() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(project_root.clone());
+        std::fs::write(project_root.join("app.log"), "line\n".repeat(1000)).unwrap();
+
+        let token = tokio_util::sync::CancellationToken::new();
+        token.cancel();
+        let error = compress("app.log", "zstd").call_with_context(&context.with_cancellation(token)).await.unwrap_err();
+        assert!(error.to_string().contains("Cancelled by the client"), "{}", error);
+        assert!(!project_root.join("app.log.zst").exists());
+    }
+}
@@ -0,0 +1,346 @@
+use crate::context::{StatefulTool, ToolContext};
+use crate::config::tool_errors;
+use crate::tools::utils::{format_path, format_size, open_for_write_in_project, resolve_path_for_read, resolve_path_for_write};
+use async_trait::async_trait;
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+const TOOL_NAME: &str = "convert";
+
+#[mcp_tool(
+    name = "convert",
+    description = "Convert a data file between JSON, YAML and TOML. The input format is detected from the file extension unless from_format is set.
+Writes to output_path, or next to the input with the extension of to_format. Values the target format cannot represent (e.g. null in TOML) are reported as errors.
+Examples: {\"input_path\": \"config.json\", \"to_format\": \"yaml\"} or {\"input_path\": \"Cargo.toml\", \"output_path\": \"cargo.json\", \"to_format\": \"json\"}"
+)]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct ConvertTool {
+    /// File to convert (relative to project root)
+    pub input_path: String,
+    /// Where to write the converted file (default: input_path with the extension of to_format)
+    pub output_path: Option<String>,
+    /// Input format: "json", "yaml", or "toml" (default: detected from the input file extension)
+    pub from_format: Option<String>,
+    /// Output format: "json", "yaml", or "toml"
+    pub to_format: String,
+    /// Follow a symlinked input to a file outside the project directory (default: false)
+    #[serde(default)]
+    pub follow_symlinks: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DataFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl DataFormat {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "json" => Some(DataFormat::Json),
+            "yaml" | "yml" => Some(DataFormat::Yaml),
+            "toml" => Some(DataFormat::Toml),
+            _ => None,
+        }
+    }
+
+    fn from_extension(path: &Path) -> Option<Self> {
+        path.extension().and_then(|ext| ext.to_str()).and_then(Self::parse)
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            DataFormat::Json => "json",
+            DataFormat::Yaml => "yaml",
+            DataFormat::Toml => "toml",
+        }
+    }
+
+    /// Parse file content into the JSON data model shared by the query tools
+    fn read(&self, content: &str) -> Result<Value, String> {
+        match self {
+            DataFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+            DataFormat::Yaml => {
+                let mut documents = serde_yaml::Deserializer::from_str(content);
+                let Some(document) = documents.next() else {
+                    return Ok(Value::Null);
+                };
+                let value = serde_yaml::Value::deserialize(document).map_err(|e| e.to_string())?;
+                if documents.next().is_some() {
+                    return Err("multi-document YAML cannot be converted; split the documents first".to_string());
+                }
+                serde_json::to_value(value).map_err(|e| e.to_string())
+            }
+            DataFormat::Toml => {
+                let value: toml::Value = toml::from_str(content).map_err(|e| e.message().to_string())?;
+                Ok(toml_to_json(value))
+            }
+        }
+    }
+
+    fn render(&self, value: &Value) -> Result<String, String> {
+        match self {
+            DataFormat::Json => serde_json::to_string_pretty(value)
+                .map(|json| json + "\n")
+                .map_err(|e| e.to_string()),
+            DataFormat::Yaml => serde_yaml::to_string(value).map_err(|e| e.to_string()),
+            DataFormat::Toml => match json_to_toml(value, "")? {
+                table @ toml::Value::Table(_) => toml::to_string_pretty(&table).map_err(|e| e.to_string()),
+                _ => Err(format!("TOML documents must be a table at the top level, not {}", type_name(value))),
+            },
+        }
+    }
+}
+
+/// TOML datetimes have no JSON counterpart, so they become strings
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Value::from(f),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(dt) => Value::String(dt.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Value::Object(table.into_iter().map(|(k, v)| (k, toml_to_json(v))).collect()),
+    }
+}
+
+/// Convert to a TOML value, naming the path of the first value TOML cannot hold
+fn json_to_toml(value: &Value, path: &str) -> Result<toml::Value, String> {
+    let location = if path.is_empty() { "." } else { path };
+    match value {
+        Value::Null => Err(format!("{} is null, which TOML cannot represent", location)),
+        Value::Bool(b) => Ok(toml::Value::Boolean(*b)),
+        Value::Number(n) => n.as_i64()
+            .map(toml::Value::Integer)
+            .or_else(|| n.is_f64().then(|| n.as_f64().map(toml::Value::Float)).flatten())
+            .ok_or_else(|| format!("{} is {}, which is too large for a TOML integer", location, n)),
+        Value::String(s) => Ok(toml::Value::String(s.clone())),
+        Value::Array(items) => items.iter()
+            .enumerate()
+            .map(|(i, item)| json_to_toml(item, &format!("{}[{}]", path, i)))
+            .collect::<Result<Vec<_>, _>>()
+            .map(toml::Value::Array),
+        Value::Object(map) => map.iter()
+            .map(|(k, v)| json_to_toml(v, &format!("{}.{}", path, k)).map(|v| (k.clone(), v)))
+            .collect::<Result<toml::map::Map<_, _>, _>>()
+            .map(toml::Value::Table),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+#[async_trait]
+impl StatefulTool for ConvertTool {
+    async fn call_with_context(
+        mut self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        self.input_path = context.resolve_in_working_dir(&self.input_path);
+        self.output_path = self.output_path.map(|path| context.resolve_in_working_dir(&path));
+
+        context.ensure_writable(TOOL_NAME)?;
+
+        let project_root = context.get_project_root()
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
+
+        let input_path = resolve_path_for_read(&self.input_path, &project_root, self.follow_symlinks, TOOL_NAME)?;
+
+        let from = match &self.from_format {
+            Some(name) => DataFormat::parse(name).ok_or_else(|| CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Unsupported from_format '{}'. Must be 'json', 'yaml', or 'toml'", name)
+            )))?,
+            None => DataFormat::from_extension(&input_path).ok_or_else(|| CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Cannot detect the format of '{}' from its extension; set from_format", self.input_path)
+            )))?,
+        };
+        let to = DataFormat::parse(&self.to_format).ok_or_else(|| CallToolError::from(tool_errors::invalid_input(
+            TOOL_NAME,
+            &format!("Unsupported to_format '{}'. Must be 'json', 'yaml', or 'toml'", self.to_format)
+        )))?;
+
+        let output = self.output_path.clone().unwrap_or_else(|| {
+            Path::new(&self.input_path).with_extension(to.name()).to_string_lossy().into_owned()
+        });
+        let output_path = resolve_path_for_write(&output, &project_root, TOOL_NAME)?;
+
+        // Overwriting an existing file follows the same read-before-write rule as the write tool
+        let read_files = context.get_custom_state::<HashSet<PathBuf>>().await
+            .unwrap_or_else(|| std::sync::Arc::new(HashSet::new()));
+        if output_path.exists() && !read_files.contains(&output_path) {
+            return Err(CallToolError::from(tool_errors::operation_not_permitted(
+                TOOL_NAME,
+                &format!("Cannot write to '{}': File must be read first before overwriting", output)
+            )));
+        }
+
+        let content = fs::read_to_string(&input_path).await
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read '{}': {}", self.input_path, e))))?;
+        let value = from.read(&content)
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to parse '{}' as {}: {}", self.input_path, from.name(), e)
+            )))?;
+        let converted = to.render(&value)
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Cannot convert '{}' to {}: {}", self.input_path, to.name(), e)
+            )))?;
+
+        if let Some(parent) = output_path.parent().filter(|parent| !parent.exists()) {
+            fs::create_dir_all(parent).await
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to create parent directories: {}", e))))?;
+        }
+        let (mut file, written_path) = open_for_write_in_project(&output_path, &project_root, false, TOOL_NAME)?;
+        file.write_all(converted.as_bytes())
+            .and_then(|_| file.flush())
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to write '{}': {}", output, e))))?;
+        context.record_modified(&written_path).await;
+
+        let relative_input = input_path.strip_prefix(&project_root).unwrap_or(&input_path);
+        let relative_output = written_path.strip_prefix(&project_root).unwrap_or(&written_path);
+        let message = format!(
+            "Converted {} ({}) to {} ({}, {})",
+            format_path(relative_input),
+            from.name(),
+            format_path(relative_output),
+            to.name(),
+            format_size(converted.len() as u64)
+        );
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                message, None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::ToolContext;
+    use tempfile::TempDir;
+
+    async fn setup_test_context() -> (ToolContext, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let canonical_path = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(canonical_path);
+        (context, temp_dir)
+    }
+
+    fn convert(input: &str, output: Option<&str>, to_format: &str) -> ConvertTool {
+        ConvertTool {
+            input_path: input.to_string(),
+            output_path: output.map(str::to_string),
+            from_format: None,
+            to_format: to_format.to_string(),
+            follow_symlinks: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_convert_round_trip_between_formats() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("config.json"),
+            r#"{"name": "demo", "port": 8080, "ratio": 0.5, "tags": ["a", "b"], "db": {"host": "localhost"}}"#,
+        ).unwrap();
+
+        convert("config.json", None, "yaml").call_with_context(&context).await.unwrap();
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&std::fs::read_to_string(root.join("config.yaml")).unwrap()).unwrap();
+        assert_eq!(yaml["db"]["host"], "localhost");
+
+        convert("config.yaml", Some("out/config.toml"), "toml").call_with_context(&context).await.unwrap();
+        let toml_text = std::fs::read_to_string(root.join("out/config.toml")).unwrap();
+        assert!(toml_text.contains("[db]"), "unexpected TOML: {}", toml_text);
+
+        convert("out/config.toml", Some("roundtrip.json"), "json").call_with_context(&context).await.unwrap();
+        let original: Value = serde_json::from_str(&std::fs::read_to_string(root.join("config.json")).unwrap()).unwrap();
+        let roundtrip: Value = serde_json::from_str(&std::fs::read_to_string(root.join("roundtrip.json")).unwrap()).unwrap();
+        assert_eq!(original, roundtrip);
+    }
+
+    #[tokio::test]
+    async fn test_convert_toml_datetimes_become_strings() {
+        let (context, temp_dir) = setup_test_context().await;
+        std::fs::write(temp_dir.path().join("release.toml"), "published = 1979-05-27T07:32:00Z\n").unwrap();
+
+        convert("release.toml", None, "json").call_with_context(&context).await.unwrap();
+        let json: Value = serde_json::from_str(&std::fs::read_to_string(temp_dir.path().join("release.json")).unwrap()).unwrap();
+        assert_eq!(json, serde_json::json!({"published": "1979-05-27T07:32:00Z"}));
+    }
+
+    #[tokio::test]
+    async fn test_convert_null_to_toml_is_an_error() {
+        let (context, temp_dir) = setup_test_context().await;
+        std::fs::write(temp_dir.path().join("data.json"), r#"{"db": {"password": null}}"#).unwrap();
+
+        let error = convert("data.json", None, "toml").call_with_context(&context).await.unwrap_err();
+        assert!(error.to_string().contains(".db.password is null"), "unexpected error: {}", error);
+        assert!(!temp_dir.path().join("data.toml").exists());
+
+        std::fs::write(temp_dir.path().join("list.json"), "[1, 2]").unwrap();
+        let error = convert("list.json", None, "toml").call_with_context(&context).await.unwrap_err();
+        assert!(error.to_string().contains("table at the top level"), "unexpected error: {}", error);
+    }
+
+    #[tokio::test]
+    async fn test_convert_requires_reading_existing_output() {
+        let (context, temp_dir) = setup_test_context().await;
+        std::fs::write(temp_dir.path().join("data.json"), r#"{"a": 1}"#).unwrap();
+        std::fs::write(temp_dir.path().join("data.yaml"), "a: 0\n").unwrap();
+
+        assert!(convert("data.json", None, "yaml").call_with_context(&context).await.is_err());
+        assert_eq!(std::fs::read_to_string(temp_dir.path().join("data.yaml")).unwrap(), "a: 0\n");
+    }
+
+    #[tokio::test]
+    async fn test_convert_outside_project_directory() {
+        let (context, temp_dir) = setup_test_context().await;
+        std::fs::write(temp_dir.path().join("data.json"), r#"{"a": 1}"#).unwrap();
+
+        assert!(convert("../data.json", None, "yaml").call_with_context(&context).await.is_err());
+        assert!(convert("data.json", Some("../data.yaml"), "yaml").call_with_context(&context).await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_convert_follows_symlinks_only_when_asked() {
+        let (context, temp_dir) = setup_test_context().await;
+        let outside = TempDir::new().unwrap();
+        std::fs::write(outside.path().join("secret.json"), r#"{"token": "abc"}"#).unwrap();
+        std::os::unix::fs::symlink(outside.path().join("secret.json"), temp_dir.path().join("link.json")).unwrap();
+
+        assert!(convert("link.json", None, "yaml").call_with_context(&context).await.is_err());
+        assert!(!temp_dir.path().join("link.yaml").exists());
+
+        let mut tool = convert("link.json", None, "yaml");
+        tool.follow_symlinks = true;
+        tool.call_with_context(&context).await.unwrap();
+        assert_eq!(std::fs::read_to_string(temp_dir.path().join("link.yaml")).unwrap(), "token: abc\n");
+    }
+}
@@ -15,7 +15,7 @@ const TOOL_NAME: &str = "copy";
 
 #[mcp_tool(
     name = "copy", 
-    description = "Copy files/directories recursively. Auto-creates parents, preserves structure, prevents self-copy.
+    description = "Copy files/directories recursively. Auto-creates parents, preserves permissions and timestamps, prevents self-copy.
 Examples: {\"source\": \"config.json\", \"destination\": \"config.backup.json\"}, {\"source\": \"src/\", \"destination\": \"src_backup/\"}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
@@ -27,11 +27,18 @@ pub struct CopyTool {
     /// Whether to overwrite existing files (default: false)
     #[serde(default)]
     pub overwrite: bool,
-    /// Whether to preserve file metadata (default: true)
+    /// Whether to copy directories and their contents (default: true)
+    #[serde(default = "default_recursive")]
+    pub recursive: bool,
+    /// Whether to preserve permissions and modification/access times (default: true)
     #[serde(default = "default_preserve_metadata")]
     pub preserve_metadata: bool,
 }
 
+fn default_recursive() -> bool {
+    true
+}
+
 fn default_preserve_metadata() -> bool {
     true
 }
@@ -39,9 +46,14 @@ fn default_preserve_metadata() -> bool {
 #[async_trait]
 impl StatefulTool for CopyTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.source = context.resolve_in_working_dir(&self.source);
+        self.destination = context.resolve_in_working_dir(&self.destination);
+
+        context.ensure_writable(TOOL_NAME)?;
+
         let project_root = context.get_project_root()
             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
         
@@ -176,6 +188,13 @@ impl StatefulTool for CopyTool {
             )));
         }
         
+        if canonical_source.is_dir() && !self.recursive {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Source '{}' is a directory. Set recursive=true to copy directories.", self.source)
+            )));
+        }
+        
         // Prevent copying into itself
         if canonical_source.is_dir() && canonical_dest.starts_with(&canonical_source) {
             return Err(CallToolError::from(tool_errors::invalid_input(
@@ -234,12 +253,11 @@ impl StatefulTool for CopyTool {
             
             // Preserve metadata if requested
             if self.preserve_metadata {
-                // Metadata preservation is best-effort
-                // Rust's async fs doesn't have direct timestamp setting
+                copy_metadata(&canonical_source, &canonical_dest).await?;
             }
         } else if canonical_source.is_dir() {
             // Recursive directory copy
-            let stats = copy_dir_recursive(&canonical_source, &canonical_dest, self.overwrite).await?;
+            let stats = copy_dir_recursive(&canonical_source, &canonical_dest, self.overwrite, self.preserve_metadata).await?;
             total_size = stats.total_size;
             file_count = stats.file_count;
             dir_count = stats.dir_count;
@@ -249,6 +267,7 @@ impl StatefulTool for CopyTool {
                 "Source is neither a file nor a directory"
             )));
         }
+        context.record_modified(&canonical_dest).await;
         
         let _duration = start_time.elapsed();
         let file_type = if canonical_source.is_dir() { "directory" } else { "file" };
@@ -295,10 +314,31 @@ struct CopyStats {
     dir_count: usize,
 }
 
+/// Copy permissions and access/modification times from one path to another
+async fn copy_metadata(src: &Path, dst: &Path) -> Result<(), CallToolError> {
+    let metadata = fs::metadata(src)
+        .await
+        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read source metadata: {}", e))))?;
+    
+    fs::set_permissions(dst, metadata.permissions())
+        .await
+        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to preserve permissions: {}", e))))?;
+    
+    filetime::set_file_times(
+        dst,
+        filetime::FileTime::from_last_access_time(&metadata),
+        filetime::FileTime::from_last_modification_time(&metadata),
+    )
+    .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to preserve timestamps: {}", e))))?;
+    
+    Ok(())
+}
+
 fn copy_dir_recursive<'a>(
     src: &'a Path, 
     dst: &'a Path, 
-    overwrite: bool
+    overwrite: bool,
+    preserve_metadata: bool,
 ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<CopyStats, CallToolError>> + Send + 'a>> {
     Box::pin(async move {
     let mut stats = CopyStats::default();
@@ -325,7 +365,7 @@ fn copy_dir_recursive<'a>(
                 let dst_path = dst.join(entry.file_name());
                 
                 if file_type.is_dir() {
-                    let sub_stats = Box::pin(copy_dir_recursive(&src_path, &dst_path, overwrite)).await?;
+                    let sub_stats = Box::pin(copy_dir_recursive(&src_path, &dst_path, overwrite, preserve_metadata)).await?;
                     stats.total_size += sub_stats.total_size;
                     stats.file_count += sub_stats.file_count;
                     stats.dir_count += sub_stats.dir_count;
@@ -347,6 +387,10 @@ fn copy_dir_recursive<'a>(
                     fs::copy(&src_path, &dst_path)
                         .await
                         .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to copy file: {}", e))))?;
+                    
+                    if preserve_metadata {
+                        copy_metadata(&src_path, &dst_path).await?;
+                    }
                 }
             }
             Ok(None) => break,
@@ -354,6 +398,11 @@ fn copy_dir_recursive<'a>(
         }
     }
     
+    // Directory times are set last, since copying entries into it updates its mtime
+    if preserve_metadata {
+        copy_metadata(src, dst).await?;
+    }
+    
     Ok(stats)
     })
 }
@@ -393,6 +442,7 @@ mod tests {
             source: "source.txt".to_string(),
             destination: "dest.txt".to_string(),
             overwrite: false,
+            recursive: true,
             preserve_metadata: true,
         };
         
@@ -432,6 +482,7 @@ mod tests {
             source: "source_dir".to_string(),
             destination: "dest_dir".to_string(),
             overwrite: false,
+            recursive: true,
             preserve_metadata: true,
         };
         
@@ -481,6 +532,7 @@ mod tests {
             source: "source.txt".to_string(),
             destination: "dest.txt".to_string(),
             overwrite: true,
+            recursive: true,
             preserve_metadata: true,
         };
         
@@ -508,6 +560,7 @@ mod tests {
             source: "source.txt".to_string(),
             destination: "dest.txt".to_string(),
             overwrite: false,
+            recursive: true,
             preserve_metadata: true,
         };
         
@@ -534,6 +587,7 @@ mod tests {
             source: "source.txt".to_string(),
             destination: "subdir/dest.txt".to_string(),
             overwrite: false,
+            recursive: true,
             preserve_metadata: true,
         };
         
@@ -556,6 +610,7 @@ mod tests {
             source: "nonexistent.txt".to_string(),
             destination: "dest.txt".to_string(),
             overwrite: false,
+            recursive: true,
             preserve_metadata: true,
         };
         
@@ -582,6 +637,7 @@ mod tests {
             source: "source.txt".to_string(),
             destination: outside_path.to_string_lossy().to_string(),
             overwrite: false,
+            recursive: true,
             preserve_metadata: true,
         };
         
@@ -608,6 +664,7 @@ mod tests {
             source: "source.txt".to_string(),
             destination: "dest.txt".to_string(),
             overwrite: false,
+            recursive: true,
             preserve_metadata: false,
         };
         
@@ -634,6 +691,7 @@ mod tests {
             source: "empty.txt".to_string(),
             destination: "empty_copy.txt".to_string(),
             overwrite: false,
+            recursive: true,
             preserve_metadata: true,
         };
         
@@ -662,6 +720,7 @@ mod tests {
             source: "source_dir".to_string(),
             destination: "source_dir/subdest".to_string(),
             overwrite: false,
+            recursive: true,
             preserve_metadata: true,
         };
         
@@ -691,6 +750,7 @@ mod tests {
             source: "source_dir".to_string(),
             destination: "dest_dir".to_string(),
             overwrite: false,
+            recursive: true,
             preserve_metadata: true,
         };
         
@@ -720,6 +780,7 @@ mod tests {
             source: special_name.to_string(),
             destination: "copy of special file.txt".to_string(),
             overwrite: false,
+            recursive: true,
             preserve_metadata: true,
         };
         
@@ -747,6 +808,7 @@ mod tests {
             source: "large.txt".to_string(),
             destination: "large_copy.txt".to_string(),
             overwrite: false,
+            recursive: true,
             preserve_metadata: true,
         };
         
@@ -783,6 +845,7 @@ mod tests {
             source: "source".to_string(),
             destination: "dest".to_string(),
             overwrite: true,
+            recursive: true,
             preserve_metadata: true,
         };
         
@@ -800,4 +863,72 @@ mod tests {
         let file3_content = fs::read_to_string(dest_dir.join("file3.txt")).await.unwrap();
         assert_eq!(file3_content, "Existing file 3");
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_copy_directory_without_recursive_fails() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let project_root = context.get_project_root().unwrap();
+        fs::create_dir(project_root.join("source_dir")).await.unwrap();
+        fs::write(project_root.join("source_dir/file.txt"), "content").await.unwrap();
+        
+        let copy_tool = CopyTool {
+            source: "source_dir".to_string(),
+            destination: "dest_dir".to_string(),
+            overwrite: false,
+            recursive: false,
+            preserve_metadata: true,
+        };
+        
+        let result = copy_tool.call_with_context(&context).await;
+        assert!(result.is_err());
+        
+        let error_msg = format!("{:?}", result.unwrap_err());
+        assert!(error_msg.contains("Set recursive=true"));
+        assert!(!project_root.join("dest_dir").exists());
+    }
+    
+    #[tokio::test]
+    async fn test_copy_preserves_permissions_and_mtime() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let project_root = context.get_project_root().unwrap();
+        fs::create_dir(project_root.join("source_dir")).await.unwrap();
+        let source_file = project_root.join("source_dir/script.sh");
+        fs::write(&source_file, "#!/bin/sh").await.unwrap();
+        
+        let old_time = filetime::FileTime::from_unix_time(1_600_000_000, 0);
+        filetime::set_file_times(&source_file, old_time, old_time).unwrap();
+        filetime::set_file_times(project_root.join("source_dir"), old_time, old_time).unwrap();
+        
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&source_file, std::fs::Permissions::from_mode(0o750)).await.unwrap();
+        }
+        
+        let copy_tool = CopyTool {
+            source: "source_dir".to_string(),
+            destination: "dest_dir".to_string(),
+            overwrite: false,
+            recursive: true,
+            preserve_metadata: true,
+        };
+        
+        let result = copy_tool.call_with_context(&context).await;
+        assert!(result.is_ok());
+        
+        let dest_file = project_root.join("dest_dir/script.sh");
+        let file_metadata = std::fs::metadata(&dest_file).unwrap();
+        assert_eq!(filetime::FileTime::from_last_modification_time(&file_metadata), old_time);
+        
+        let dir_metadata = std::fs::metadata(project_root.join("dest_dir")).unwrap();
+        assert_eq!(filetime::FileTime::from_last_modification_time(&dir_metadata), old_time);
+        
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(file_metadata.permissions().mode() & 0o777, 0o750);
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use crate::config::tool_errors;
+use crate::context::{StatefulTool, ToolContext};
+use crate::tools::compress::{Algorithm, open_destination, ratio_summary};
+use crate::tools::utils::{
+    CancellableReader, format_path, resolve_path_for_read, resolve_path_for_write, run_blocking,
+};
+
+use async_trait::async_trait;
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+const TOOL_NAME: &str = "decompress";
+
+#[mcp_tool(
+    name = "decompress",
+    description = "Decompress a single gzip, zstd or bzip2 file, streaming so large files work. The format is detected from the file's magic bytes, then its extension. The source is kept. Returns the sizes and compression ratio.
+Examples: {\"source\": \"logs/app.log.gz\"}, {\"source\": \"dump.bin\", \"destination\": \"dump.sql\", \"algorithm\": \"zstd\"}"
+)]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct DecompressTool {
+    /// Compressed file to read (relative to project root)
+    pub source: String,
+    /// File to write (default: source without its .gz, .zst or .bz2 extension)
+    #[serde(default)]
+    pub destination: Option<String>,
+    /// "gzip", "zstd" or "bzip2" (default: detected)
+    #[serde(default)]
+    pub algorithm: Option<String>,
+    /// Replace the destination if it exists (default: false)
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+/// Stream a compressed `reader` into `writer`, returning the bytes written
+fn decompress_stream(algorithm: Algorithm, reader: impl Read, mut writer: impl Write) -> std::io::Result<u64> {
+    let written = match algorithm {
+        // Multi-member readers so concatenated gzip and bzip2 files come out whole, like gunzip
+        Algorithm::Gzip => std::io::copy(&mut flate2::read::MultiGzDecoder::new(reader), &mut writer)?,
+        Algorithm::Zstd => std::io::copy(&mut zstd::stream::read::Decoder::new(reader)?, &mut writer)?,
+        Algorithm::Bzip2 => std::io::copy(&mut bzip2::read::MultiBzDecoder::new(reader), &mut writer)?,
+    };
+    writer.flush()?;
+    Ok(written)
+}
+
+impl DecompressTool {
+    /// Detect the format and stream the source into the destination, on a blocking thread.
+    /// Returns the source, destination, algorithm and decompressed size
+    fn decompress(
+        &self,
+        project_root: &Path,
+        context: &ToolContext,
+    ) -> Result<(PathBuf, PathBuf, Algorithm, u64), CallToolError> {
+        let source = resolve_path_for_read(&self.source, project_root, false, TOOL_NAME)?;
+        if !source.is_file() {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Path is not a file: {}", self.source),
+            )));
+        }
+        let file = File::open(&source).map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to open '{}': {}", self.source, e)))
+        })?;
+        let mut input = BufReader::new(CancellableReader::new(file, context));
+
+        let algorithm = match &self.algorithm {
+            Some(name) => Algorithm::parse(name, TOOL_NAME)?,
+            None => {
+                // Peek at the header without consuming it, so the decoder still sees it
+                let magic = input.fill_buf().map_err(|e| {
+                    if context.is_cancelled() {
+                        return CallToolError::from(tool_errors::cancelled(TOOL_NAME));
+                    }
+                    CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read '{}': {}", self.source, e)))
+                })?;
+                Algorithm::from_magic(magic)
+                    .or_else(|| Algorithm::from_extension(&source))
+                    .ok_or_else(|| CallToolError::from(tool_errors::invalid_input(
+                        TOOL_NAME,
+                        &format!("Cannot detect the compression format of '{}'. Set algorithm to 'gzip', 'zstd' or 'bzip2'", self.source),
+                    )))?
+            }
+        };
+
+        let destination_name = match &self.destination {
+            Some(destination) => destination.clone(),
+            None => self
+                .source
+                .strip_suffix(&format!(".{}", algorithm.extension()))
+                .map(str::to_string)
+                .ok_or_else(|| CallToolError::from(tool_errors::invalid_input(
+                    TOOL_NAME,
+                    &format!("'{}' has no .{} extension to strip. Set destination", self.source, algorithm.extension()),
+                )))?,
+        };
+        let destination = resolve_path_for_write(&destination_name, project_root, TOOL_NAME)?;
+        if destination == source {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                "Source and destination must be different files",
+            )));
+        }
+
+        let created = !destination.exists();
+        let output = open_destination(&destination, &destination_name, project_root, self.overwrite, TOOL_NAME)?;
+        let original = decompress_stream(algorithm, input, BufWriter::new(output)).map_err(|e| {
+            if created {
+                let _ = std::fs::remove_file(&destination);
+            }
+            if context.is_cancelled() {
+                return CallToolError::from(tool_errors::cancelled(TOOL_NAME));
+            }
+            CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to decompress '{}': {}", self.source, e)))
+        })?;
+        Ok((source, destination, algorithm, original))
+    }
+}
+
+#[async_trait]
+impl StatefulTool for DecompressTool {
+    async fn call_with_context(
+        mut self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        self.source = context.resolve_in_working_dir(&self.source);
+        self.destination = self.destination.map(|path| context.resolve_in_working_dir(&path));
+
+        context.ensure_writable(TOOL_NAME)?;
+
+        let project_root = context.get_project_root().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to get project root: {}", e),
+            ))
+        })?;
+        let project_root = project_root.canonicalize().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to canonicalize project root: {}", e),
+            ))
+        })?;
+
+        let tool = self.clone();
+        let blocking_root = project_root.clone();
+        let blocking_context = context.clone();
+        let (source, destination, algorithm, original) =
+            run_blocking(TOOL_NAME, move || tool.decompress(&blocking_root, &blocking_context)).await?;
+        context.record_modified(&destination).await;
+
+        let compressed = std::fs::metadata(&source).map(|metadata| metadata.len()).unwrap_or(0);
+        let relative = |path: &PathBuf| format_path(path.strip_prefix(&project_root).unwrap_or(path));
+        let message = format!(
+            "Decompressed {} ({}) to {}: {}",
+            relative(&source),
+            algorithm.name(),
+            relative(&destination),
+            ratio_summary(original, compressed, true)
+        );
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                message, None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+}
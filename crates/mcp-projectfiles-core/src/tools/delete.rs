@@ -17,8 +17,8 @@ const TOOL_NAME: &str = "delete";
 
 #[mcp_tool(
     name = "delete",
-    description = "Delete files/directories with safety checks. Requires confirm or force. Supports patterns, recursive deletion.
-Examples: {\"path\": \"old.txt\", \"confirm\": true}, {\"path\": \"*.tmp\", \"pattern\": true, \"force\": true}"
+    description = "Delete files/directories with safety checks. Requires confirm or force. Supports patterns, recursive deletion, moving to trash.
+Examples: {\"path\": \"old.txt\", \"confirm\": true}, {\"path\": \"*.tmp\", \"pattern\": true, \"force\": true, \"use_trash\": true}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct DeleteTool {
@@ -36,14 +36,30 @@ pub struct DeleteTool {
     /// Pattern matching mode - treat path as a glob pattern for bulk deletes (optional, default: false)
     #[serde(default)]
     pub pattern: bool,
+    /// Move entries to the OS trash/recycle bin instead of permanently deleting them (optional, default: false)
+    #[serde(default)]
+    pub use_trash: bool,
+}
+
+/// Move a file or directory to the OS trash/recycle bin
+async fn move_to_trash(path: &Path) -> std::io::Result<()> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || trash::delete(&path))
+        .await
+        .map_err(std::io::Error::other)?
+        .map_err(std::io::Error::other)
 }
 
 #[async_trait]
 impl StatefulTool for DeleteTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
+        context.ensure_writable(TOOL_NAME)?;
+
         if !self.confirm && !self.force {
             return Err(CallToolError::from(tool_errors::operation_not_permitted(
                 TOOL_NAME,
@@ -81,7 +97,12 @@ impl StatefulTool for DeleteTool {
                     ))
                 })?
                 .filter_map(Result::ok)
-                .filter(|p| p.starts_with(&current_dir) && p != &current_dir)
+                // Skip anything that resolves outside the project or to the root itself
+                .filter(|p| {
+                    p.canonicalize()
+                        .map(|c| c.starts_with(&current_dir) && c != current_dir)
+                        .unwrap_or(false)
+                })
                 .collect();
 
             if paths.is_empty() {
@@ -108,7 +129,12 @@ impl StatefulTool for DeleteTool {
                 if metadata.is_file() {
                     total_size += metadata.len();
                     file_count += 1;
-                    fs::remove_file(&path).await.map_err(|e| {
+                    let removed = if self.use_trash {
+                        move_to_trash(&path).await
+                    } else {
+                        fs::remove_file(&path).await
+                    };
+                    removed.map_err(|e| {
                         CallToolError::from(tool_errors::invalid_input(
                             TOOL_NAME,
                             &format!("Failed to delete file '{}': {}", path.display(), e),
@@ -120,7 +146,12 @@ impl StatefulTool for DeleteTool {
                     total_size += stats.total_size;
                     file_count += stats.file_count;
                     dir_count += stats.dir_count;
-                    fs::remove_dir_all(&path).await.map_err(|e| {
+                    let removed = if self.use_trash {
+                        move_to_trash(&path).await
+                    } else {
+                        fs::remove_dir_all(&path).await
+                    };
+                    removed.map_err(|e| {
                         CallToolError::from(tool_errors::invalid_input(
                             TOOL_NAME,
                             &format!("Failed to delete directory '{}': {}", path.display(), e),
@@ -145,6 +176,7 @@ impl StatefulTool for DeleteTool {
                 let mut read_files_clone = (*read_files).clone();
                 read_files_clone.remove(&path);
                 context.set_custom_state(read_files_clone).await;
+                context.record_modified(&path).await;
             }
 
             // Format deleted paths with proper quotes
@@ -161,7 +193,16 @@ impl StatefulTool for DeleteTool {
                 (dir_count, "directory", "directories"),
             ]);
 
-            let summary = if total_size > 0 {
+            let summary = if self.use_trash {
+                format!(
+                    "Moved {} matching pattern '{}' to trash ({}, {}):\n{}",
+                    format_count(deleted_paths.len(), "item", "items"),
+                    self.path,
+                    counts,
+                    format_size(total_size),
+                    formatted_paths.join("\n")
+                )
+            } else if total_size > 0 {
                 format!(
                     "Deleted {} matching pattern '{}' ({}, {} freed):\n{}",
                     format_count(deleted_paths.len(), "item", "items"),
@@ -242,7 +283,12 @@ impl StatefulTool for DeleteTool {
             total_size = metadata.len();
             file_count = 1;
             dir_count = 0;
-            fs::remove_file(&canonical_path).await.map_err(|e| {
+            let removed = if self.use_trash {
+                move_to_trash(&canonical_path).await
+            } else {
+                fs::remove_file(&canonical_path).await
+            };
+            removed.map_err(|e| {
                 CallToolError::from(tool_errors::invalid_input(
                     TOOL_NAME,
                     &format!("Failed to delete file: {}", e),
@@ -255,7 +301,12 @@ impl StatefulTool for DeleteTool {
                 total_size = stats.total_size;
                 file_count = stats.file_count;
                 dir_count = stats.dir_count;
-                fs::remove_dir_all(&canonical_path).await.map_err(|e| {
+                let removed = if self.use_trash {
+                    move_to_trash(&canonical_path).await
+                } else {
+                    fs::remove_dir_all(&canonical_path).await
+                };
+                removed.map_err(|e| {
                     CallToolError::from(tool_errors::invalid_input(
                         TOOL_NAME,
                         &format!("Failed to delete directory: {}", e),
@@ -287,7 +338,12 @@ impl StatefulTool for DeleteTool {
                     )));
                 }
 
-                fs::remove_dir(&canonical_path).await.map_err(|e| {
+                let removed = if self.use_trash {
+                    move_to_trash(&canonical_path).await
+                } else {
+                    fs::remove_dir(&canonical_path).await
+                };
+                removed.map_err(|e| {
                     CallToolError::from(tool_errors::invalid_input(
                         TOOL_NAME,
                         &format!("Failed to delete empty directory: {}", e),
@@ -320,6 +376,7 @@ impl StatefulTool for DeleteTool {
         let mut written_files_clone = (*written_files).clone();
         written_files_clone.remove(&canonical_path);
         context.set_custom_state(written_files_clone).await;
+        context.record_modified(&canonical_path).await;
 
         let _duration = start_time.elapsed();
 
@@ -330,24 +387,34 @@ impl StatefulTool for DeleteTool {
             .unwrap_or(&canonical_path);
 
         // Build metrics string
+        let freed = if self.use_trash { "" } else { " freed" };
         let metrics = if metadata.is_dir() && self.recursive {
             let counts = format_counts(&[
                 (file_count, "file", "files"),
                 (dir_count, "directory", "directories"),
             ]);
-            format!(" ({}, {} freed)", counts, format_size(total_size))
+            format!(" ({}, {}{})", counts, format_size(total_size), freed)
         } else if total_size > 0 {
-            format!(" ({} freed)", format_size(total_size))
+            format!(" ({}{})", format_size(total_size), freed)
         } else {
             String::new()
         };
 
-        let message = format!(
-            "Deleted {} {}{}",
-            file_type,
-            format_path(relative_path),
-            metrics
-        );
+        let message = if self.use_trash {
+            format!(
+                "Moved {} {} to trash{}",
+                file_type,
+                format_path(relative_path),
+                metrics
+            )
+        } else {
+            format!(
+                "Deleted {} {}{}",
+                file_type,
+                format_path(relative_path),
+                metrics
+            )
+        };
 
         Ok(CallToolResult {
             content: vec![CallToolResultContentItem::TextContent(TextContent::new(
@@ -454,6 +521,7 @@ mod tests {
             confirm: false,
             force: false,
             pattern: false,
+            use_trash: false,
         };
 
         let result = delete_tool.call_with_context(&context).await;
@@ -481,6 +549,7 @@ mod tests {
             confirm: true,
             force: false,
             pattern: false,
+            use_trash: false,
         };
 
         let result = delete_tool.call_with_context(&context).await;
@@ -505,6 +574,7 @@ mod tests {
             confirm: false,
             force: true,
             pattern: false,
+            use_trash: false,
         };
 
         let result = delete_tool.call_with_context(&context).await;
@@ -542,6 +612,7 @@ mod tests {
             confirm: true,
             force: false,
             pattern: false,
+            use_trash: false,
         };
 
         let result = delete_tool.call_with_context(&context).await;
@@ -569,6 +640,7 @@ mod tests {
             confirm: true,
             force: false,
             pattern: false,
+            use_trash: false,
         };
 
         let result = delete_tool.call_with_context(&context).await;
@@ -603,6 +675,7 @@ mod tests {
             confirm: true,
             force: false,
             pattern: true,
+            use_trash: false,
         };
 
         let result = delete_tool.call_with_context(&context).await;
@@ -626,6 +699,7 @@ mod tests {
             confirm: true,
             force: false,
             pattern: false,
+            use_trash: false,
         };
 
         let result = delete_tool.call_with_context(&context).await;
@@ -645,6 +719,7 @@ mod tests {
             confirm: true,
             force: false,
             pattern: false,
+            use_trash: false,
         };
 
         let result = delete_tool.call_with_context(&context).await;
@@ -669,6 +744,7 @@ mod tests {
             confirm: true,
             force: false,
             pattern: false,
+            use_trash: false,
         };
 
         let result = delete_tool.call_with_context(&context).await;
@@ -703,6 +779,7 @@ mod tests {
             confirm: true,
             force: false,
             pattern: false,
+            use_trash: false,
         };
 
         let result = delete_tool.call_with_context(&context).await;
@@ -734,6 +811,7 @@ mod tests {
             confirm: true,
             force: false,
             pattern: true,
+            use_trash: false,
         };
 
         let result = delete_tool.call_with_context(&context).await;
@@ -760,6 +838,7 @@ mod tests {
             confirm: false, // Explicitly false
             force: true,    // Force should override
             pattern: false,
+            use_trash: false,
         };
 
         let result = delete_tool.call_with_context(&context).await;
@@ -785,6 +864,7 @@ mod tests {
             confirm: true,
             force: false,
             pattern: true,
+            use_trash: false,
         };
 
         let result = delete_tool.call_with_context(&context).await;
@@ -807,6 +887,7 @@ mod tests {
             confirm: true,
             force: false,
             pattern: false,
+            use_trash: false,
         };
 
         let result = delete_tool.call_with_context(&context).await;
@@ -815,5 +896,89 @@ mod tests {
         let error_msg = format!("{:?}", result.unwrap_err());
         assert!(error_msg.contains("Cannot delete the project root directory"));
     }
-}
 
+    #[tokio::test]
+    async fn test_delete_project_root_fails_with_trash() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let delete_tool = DeleteTool {
+            path: ".".to_string(),
+            recursive: true,
+            confirm: true,
+            force: false,
+            pattern: false,
+            use_trash: true,
+        };
+
+        let result = delete_tool.call_with_context(&context).await;
+        assert!(result.is_err());
+        let error_msg = format!("{:?}", result.unwrap_err());
+        assert!(error_msg.contains("Cannot delete the project root directory"));
+        assert!(context.get_project_root().unwrap().exists());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_delete_moves_file_to_trash() {
+        let (context, _temp_dir) = setup_test_context().await;
+        let project_root = context.get_project_root().unwrap();
+        fs::write(project_root.join("trashed.txt"), "content").await.unwrap();
+
+        // Point the freedesktop home trash into a temp dir so the test never touches the real one
+        let data_home = TempDir::new().unwrap();
+        let previous = std::env::var_os("XDG_DATA_HOME");
+        unsafe { std::env::set_var("XDG_DATA_HOME", data_home.path()) };
+
+        let delete_tool = DeleteTool {
+            path: "trashed.txt".to_string(),
+            recursive: false,
+            confirm: true,
+            force: false,
+            pattern: false,
+            use_trash: true,
+        };
+        let result = delete_tool.call_with_context(&context).await;
+
+        match previous {
+            Some(value) => unsafe { std::env::set_var("XDG_DATA_HOME", value) },
+            None => unsafe { std::env::remove_var("XDG_DATA_HOME") },
+        }
+
+        assert!(result.is_ok(), "{:?}", result.err());
+        assert!(!project_root.join("trashed.txt").exists());
+        let trash = data_home.path().join("Trash");
+        assert_eq!(std::fs::read_to_string(trash.join("files/trashed.txt")).unwrap(), "content");
+        let info = std::fs::read_to_string(trash.join("info/trashed.txt.trashinfo")).unwrap();
+        assert!(info.contains("[Trash Info]"));
+        assert!(info.contains("trashed.txt"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_delete_pattern_skips_symlinks_outside_project() {
+        let (context, _temp_dir) = setup_test_context().await;
+        let project_root = context.get_project_root().unwrap();
+
+        let outside_dir = TempDir::new().unwrap();
+        let outside_file = outside_dir.path().join("keep.tmp");
+        fs::write(&outside_file, "outside").await.unwrap();
+        std::os::unix::fs::symlink(&outside_file, project_root.join("link.tmp")).unwrap();
+        fs::write(project_root.join("local.tmp"), "local").await.unwrap();
+
+        let delete_tool = DeleteTool {
+            path: "*.tmp".to_string(),
+            recursive: false,
+            confirm: true,
+            force: false,
+            pattern: true,
+            use_trash: false,
+        };
+
+        let result = delete_tool.call_with_context(&context).await;
+        assert!(result.is_ok());
+        assert!(!project_root.join("local.tmp").exists());
+        assert!(project_root.join("link.tmp").is_symlink());
+        assert!(outside_file.exists());
+    }
+}
@@ -9,21 +9,24 @@ use rust_mcp_schema::{
 use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use serde::{Deserialize, Serialize};
 use tokio::fs;
-use similar::{ChangeTag, TextDiff};
+use similar::{ChangeTag, DiffOp, TextDiff};
 
 const TOOL_NAME: &str = "diff";
 
 #[mcp_tool(
     name = "diff",
-    description = "Compare files showing unified diff. Configurable context lines, whitespace handling.
-Examples: {\"file1\": \"old.txt\", \"file2\": \"new.txt\"}, {\"file1\": \"a.js\", \"file2\": \"b.js\", \"ignore_whitespace\": true}"
+    description = "Compare files showing unified or side-by-side diff. Compare against a string with new_content. Configurable context lines, whitespace handling.
+Examples: {\"file1\": \"old.txt\", \"file2\": \"new.txt\"}, {\"file1\": \"a.js\", \"file2\": \"b.js\", \"ignore_whitespace\": true}, {\"file1\": \"a.txt\", \"new_content\": \"draft\", \"output_format\": \"side_by_side\"}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct DiffTool {
-    /// First file to compare (relative to project root)
+    /// First file to compare (relative to project root). Also accepted as "old_path"
+    #[serde(alias = "old_path")]
     pub file1: String,
     
-    /// Second file to compare (relative to project root)
+    /// Second file to compare (relative to project root). Also accepted as "new_path".
+    /// May be omitted when new_content is provided
+    #[serde(alias = "new_path", default)]
     pub file2: String,
     
     /// Number of context lines to show around changes (optional, default: 3)
@@ -38,7 +41,13 @@ pub struct DiffTool {
     #[serde(default = "default_follow_symlinks")]
     pub follow_symlinks: bool,
     
-
+    /// Output format: "unified" or "side_by_side" (optional, default: "unified")
+    #[serde(default = "default_output_format")]
+    pub output_format: String,
+    
+    /// Compare file1 against this text instead of file2 (optional)
+    #[serde(default)]
+    pub new_content: Option<String>,
 }
 
 fn default_context_lines() -> u32 {
@@ -49,6 +58,10 @@ fn default_follow_symlinks() -> bool {
     true
 }
 
+fn default_output_format() -> String {
+    "unified".to_string()
+}
+
 
 
 impl Default for DiffTool {
@@ -59,6 +72,8 @@ impl Default for DiffTool {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: default_output_format(),
+            new_content: None,
         }
     }
 }
@@ -66,22 +81,66 @@ impl Default for DiffTool {
 #[async_trait]
 impl StatefulTool for DiffTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.file1 = context.resolve_in_working_dir(&self.file1);
+        if !self.file2.is_empty() {
+            self.file2 = context.resolve_in_working_dir(&self.file2);
+        }
+
         let project_root = context.get_project_root()
             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
         
+        if self.output_format != "unified" && self.output_format != "side_by_side" {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Invalid output_format '{}'. Must be 'unified' or 'side_by_side'", self.output_format)
+            )));
+        }
+        
+        if self.file2.is_empty() && self.new_content.is_none() {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                "Either file2 or new_content must be provided"
+            )));
+        }
+        
         // Use the utility function to resolve both file paths with symlink support
         let canonical_file1 = resolve_path_for_read(&self.file1, &project_root, self.follow_symlinks, TOOL_NAME)?;
-        let canonical_file2 = resolve_path_for_read(&self.file2, &project_root, self.follow_symlinks, TOOL_NAME)?;
         
-        // Read both files
-        let content1 = fs::read_to_string(&canonical_file1).await
+        let bytes1 = fs::read(&canonical_file1).await
             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read file1 '{}': {}", self.file1, e))))?;
         
-        let content2 = fs::read_to_string(&canonical_file2).await
-            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read file2 '{}': {}", self.file2, e))))?;
+        let (label2, bytes2) = match &self.new_content {
+            Some(new_content) => ("new_content".to_string(), new_content.clone().into_bytes()),
+            None => {
+                let canonical_file2 = resolve_path_for_read(&self.file2, &project_root, self.follow_symlinks, TOOL_NAME)?;
+                let bytes = fs::read(&canonical_file2).await
+                    .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read file2 '{}': {}", self.file2, e))))?;
+                (self.file2.clone(), bytes)
+            }
+        };
+        
+        // Binary files are only compared for equality
+        let (content1, content2) = match (text_content(bytes1.clone()), text_content(bytes2.clone())) {
+            (Some(content1), Some(content2)) => (content1, content2),
+            _ => {
+                let message = if bytes1 == bytes2 {
+                    format!("Binary files {} and {} are identical\n", self.file1, label2)
+                } else {
+                    format!("Binary files {} and {} differ\n", self.file1, label2)
+                };
+                return Ok(CallToolResult {
+                    content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                        message,
+                        None,
+                    ))],
+                    is_error: Some(false),
+                    meta: None,
+                });
+            }
+        };
         
         // Process content if ignoring whitespace
         let (text1, text2) = if self.ignore_whitespace {
@@ -93,16 +152,19 @@ impl StatefulTool for DiffTool {
         // Create the diff
         let diff = TextDiff::from_lines(&text1, &text2);
         
-        // Generate unified diff format
         let mut output = String::new();
         
         // Add header
         output.push_str(&format!("--- {}\n", self.file1));
-        output.push_str(&format!("+++ {}\n", self.file2));
+        output.push_str(&format!("+++ {}\n", label2));
         
-        // Generate hunks with context
-        for hunk in diff.unified_diff().context_radius(self.context_lines as usize).iter_hunks() {
-            output.push_str(&hunk.to_string());
+        if self.output_format == "side_by_side" {
+            output.push_str(&side_by_side(&diff, self.context_lines as usize));
+        } else {
+            // Generate hunks with context
+            for hunk in diff.unified_diff().context_radius(self.context_lines as usize).iter_hunks() {
+                output.push_str(&hunk.to_string());
+            }
         }
         
         // If files are identical
@@ -145,6 +207,54 @@ struct DiffStats {
     unchanged: usize,
 }
 
+/// Width of each column in side-by-side output
+const SIDE_BY_SIDE_WIDTH: usize = 40;
+
+/// Decode file content as text, returning None for binary content
+fn text_content(bytes: Vec<u8>) -> Option<String> {
+    if bytes.iter().take(8000).any(|&b| b == 0) {
+        return None;
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Render changed regions as two columns. Changed line pairs are marked with "|",
+/// removed lines with "<" and added lines with ">"
+fn side_by_side(diff: &TextDiff<'_, '_, '_, str>, context_lines: usize) -> String {
+    let old_lines = diff.old_slices();
+    let new_lines = diff.new_slices();
+    let column = |line: Option<&&str>| -> String {
+        let line = line.map(|l| l.trim_end_matches(['\n', '\r'])).unwrap_or("");
+        let truncated: String = line.chars().take(SIDE_BY_SIDE_WIDTH).collect();
+        format!("{:<width$}", truncated, width = SIDE_BY_SIDE_WIDTH)
+    };
+    
+    let mut output = String::new();
+    for (i, group) in diff.grouped_ops(context_lines).iter().enumerate() {
+        if i > 0 {
+            output.push_str("...\n");
+        }
+        for op in group {
+            let (old_range, new_range) = (op.old_range(), op.new_range());
+            let rows = old_range.len().max(new_range.len());
+            for row in 0..rows {
+                let old = old_range.clone().nth(row).and_then(|idx| old_lines.get(idx));
+                let new = new_range.clone().nth(row).and_then(|idx| new_lines.get(idx));
+                let marker = match (op, old.is_some(), new.is_some()) {
+                    (DiffOp::Equal { .. }, _, _) => ' ',
+                    (_, true, true) => '|',
+                    (_, true, false) => '<',
+                    _ => '>',
+                };
+                let line = format!("{} {} {}", column(old), marker, column(new));
+                output.push_str(line.trim_end());
+                output.push('\n');
+            }
+        }
+    }
+    output
+}
+
 /// Normalize whitespace for comparison
 fn normalize_whitespace(text: &str) -> String {
     text.lines()
@@ -188,6 +298,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -217,6 +329,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -253,6 +367,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -285,6 +401,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -317,6 +435,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: true,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -345,6 +465,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -374,6 +496,8 @@ mod tests {
             context_lines: 1, // Only 1 context line
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -401,6 +525,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -429,6 +555,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -464,6 +592,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -490,6 +620,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -511,6 +643,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -532,6 +666,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -554,6 +690,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -579,6 +717,8 @@ mod tests {
             context_lines: default_context_lines(),
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -619,6 +759,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -664,6 +806,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -711,6 +855,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -755,6 +901,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: false,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -789,6 +937,8 @@ mod tests {
             context_lines: 3,
             ignore_whitespace: false,
             follow_symlinks: true,
+            output_format: "unified".to_string(),
+            new_content: None,
         };
         
         let result = diff_tool.call_with_context(&context).await;
@@ -797,4 +947,68 @@ mod tests {
         let error_msg = format!("{:?}", result.unwrap_err());
         assert!(error_msg.contains("not found") || error_msg.contains("does not exist"));
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_diff_side_by_side() {
+        let (context, temp_dir) = setup_test_context().await;
+        create_test_file(temp_dir.path(), "old.txt", "keep\nchange me\nremove\n").await;
+        create_test_file(temp_dir.path(), "new.txt", "keep\nchanged\n").await;
+        
+        let diff_tool = DiffTool {
+            file1: "old.txt".to_string(),
+            file2: "new.txt".to_string(),
+            output_format: "side_by_side".to_string(),
+            ..Default::default()
+        };
+        
+        let result = diff_tool.call_with_context(&context).await.unwrap();
+        if let Some(CallToolResultContentItem::TextContent(text)) = result.content.first() {
+            let lines: Vec<&str> = text.text.lines().collect();
+            assert!(lines.iter().any(|l| l.starts_with("keep") && l.ends_with("keep")));
+            assert!(lines.iter().any(|l| l.starts_with("change me") && l.contains(" | changed")));
+            assert!(lines.iter().any(|l| l.starts_with("remove") && l.trim_end().ends_with('<')));
+        } else {
+            panic!("Expected text content");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_diff_against_new_content() {
+        let (context, temp_dir) = setup_test_context().await;
+        create_test_file(temp_dir.path(), "file.txt", "Line 1\nLine 2\n").await;
+        
+        let diff_tool: DiffTool = serde_json::from_value(serde_json::json!({
+            "old_path": "file.txt",
+            "new_content": "Line 1\nLine two\n"
+        })).unwrap();
+        
+        let result = diff_tool.call_with_context(&context).await.unwrap();
+        if let Some(CallToolResultContentItem::TextContent(text)) = result.content.first() {
+            assert!(text.text.contains("+++ new_content"));
+            assert!(text.text.contains("-Line 2"));
+            assert!(text.text.contains("+Line two"));
+        } else {
+            panic!("Expected text content");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_diff_binary_files() {
+        let (context, temp_dir) = setup_test_context().await;
+        fs::write(temp_dir.path().join("a.bin"), [0u8, 1, 2, 3]).await.unwrap();
+        fs::write(temp_dir.path().join("b.bin"), [0u8, 1, 2, 4]).await.unwrap();
+        
+        let diff_tool = DiffTool {
+            file1: "a.bin".to_string(),
+            file2: "b.bin".to_string(),
+            ..Default::default()
+        };
+        
+        let result = diff_tool.call_with_context(&context).await.unwrap();
+        if let Some(CallToolResultContentItem::TextContent(text)) = result.content.first() {
+            assert_eq!(text.text.trim(), "Binary files a.bin and b.bin differ");
+        } else {
+            panic!("Expected text content");
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use crate::config::tool_errors;
+use crate::context::{StatefulTool, ToolContext};
+use crate::tools::utils::{BoundedOutput, file_identity, format_count, format_size, resolve_path_for_read};
+
+use async_trait::async_trait;
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+const TOOL_NAME: &str = "du";
+
+fn default_path() -> String {
+    ".".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[mcp_tool(
+    name = "du",
+    description = "Summarize disk usage like du. Walks the whole subtree and lists the cumulative size of each directory down to max_depth, with a grand total. Hard-linked files are counted once. Symlinked directories are only descended into with follow_symlinks, and only when they lead back into the project.
+Examples: {\"path\": \".\", \"max_depth\": 1, \"sort_by_size\": true}, {\"path\": \"target\", \"human_readable\": false}"
+)]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct DiskUsageTool {
+    /// Directory to measure (relative to project root, default: ".")
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// Deepest directory level to list, 0 for the total only. Deeper content still counts
+    /// toward its parents (default: unlimited)
+    #[serde(default)]
+    pub max_depth: Option<u32>,
+    /// List the largest directories first instead of by path (default: false)
+    #[serde(default)]
+    pub sort_by_size: bool,
+    /// Size symlinked files by their target and descend into symlinked directories inside
+    /// the project (default: false, links count as themselves)
+    #[serde(default)]
+    pub follow_symlinks: bool,
+    /// Show sizes as KiB/MiB/GiB rather than bytes (default: true)
+    #[serde(default = "default_true")]
+    pub human_readable: bool,
+}
+
+/// A directory's cumulative size
+struct DirectoryUsage {
+    path: PathBuf,
+    size: u64,
+}
+
+/// State for one walk of the subtree
+struct UsageWalk<'a> {
+    context: &'a ToolContext,
+    project_root: &'a Path,
+    request: &'a DiskUsageTool,
+    /// (device, inode) of files already counted, so hard links count once
+    seen_files: HashSet<(u64, u64)>,
+    /// Canonical directories already walked, so a symlink back up the tree cannot loop
+    seen_dirs: HashSet<PathBuf>,
+    directories: Vec<DirectoryUsage>,
+    /// Symlinked directories, walked once everything reachable without links has been
+    pending_links: Vec<(PathBuf, u32)>,
+    files: usize,
+    unreadable: Vec<String>,
+}
+
+impl UsageWalk<'_> {
+    /// Total size of `dir`, recording it and its subdirectories
+    fn walk(&mut self, dir: &Path, depth: u32) -> Result<u64, CallToolError> {
+        self.context.check_cancelled(TOOL_NAME)?;
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if depth > 0 => {
+                self.unreadable.push(format!("{}: {}", self.relative(dir), e));
+                return Ok(0);
+            }
+            Err(e) => {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    TOOL_NAME,
+                    &format!("Failed to read directory: {}", e),
+                )));
+            }
+        };
+
+        let mut total = 0;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let is_link = metadata.is_symlink();
+            let metadata = if is_link && self.request.follow_symlinks {
+                match std::fs::metadata(&path) {
+                    Ok(target) => target,
+                    // Broken links count as themselves
+                    Err(_) => metadata,
+                }
+            } else {
+                metadata
+            };
+
+            if metadata.is_dir() && is_link {
+                self.pending_links.push((path, depth + 1));
+            } else if metadata.is_dir() {
+                // Under a followed link this may be a directory the measured path already holds
+                let real = path.canonicalize().unwrap_or_else(|_| path.clone());
+                if self.seen_dirs.insert(real) {
+                    total += self.walk(&path, depth + 1)?;
+                }
+            } else {
+                self.files += 1;
+                if file_identity(&metadata).is_none_or(|id| self.seen_files.insert(id)) {
+                    total += metadata.len();
+                }
+            }
+        }
+
+        self.context.report_progress((self.directories.len() + self.files) as u64, None, || {
+            format!("Measured {}", format_count(self.files, "file", "files"))
+        });
+        if self.request.max_depth.is_none_or(|max_depth| depth <= max_depth) {
+            self.directories.push(DirectoryUsage { path: dir.to_path_buf(), size: total });
+        }
+        Ok(total)
+    }
+
+    fn relative(&self, path: &Path) -> String {
+        match path.strip_prefix(self.project_root) {
+            Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+            Ok(relative) => relative.display().to_string(),
+            Err(_) => path.display().to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl StatefulTool for DiskUsageTool {
+    async fn call_with_context(
+        mut self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
+        let project_root = context.get_project_root().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to get project root: {}", e),
+            ))
+        })?;
+        let canonical_root = project_root.canonicalize().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to canonicalize project root: {}", e),
+            ))
+        })?;
+
+        let target = resolve_path_for_read(&self.path, &canonical_root, self.follow_symlinks, TOOL_NAME)?;
+        if !target.is_dir() {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Path '{}' is not a directory", self.path),
+            )));
+        }
+
+        let mut walk = UsageWalk {
+            context,
+            project_root: &canonical_root,
+            request: &self,
+            seen_files: HashSet::new(),
+            seen_dirs: HashSet::from([target.clone()]),
+            directories: Vec::new(),
+            pending_links: Vec::new(),
+            files: 0,
+            unreadable: Vec::new(),
+        };
+        let mut total = walk.walk(&target, 0)?;
+
+        // A link only adds a directory nothing else reached, so real paths win over link paths
+        while let Some((link, depth)) = walk.pending_links.pop() {
+            let Ok(real) = link.canonicalize() else {
+                continue;
+            };
+            if !real.starts_with(&canonical_root) || !walk.seen_dirs.insert(real) {
+                continue;
+            }
+            let size = walk.walk(&link, depth)?;
+            total += size;
+            for directory in &mut walk.directories {
+                if link.starts_with(&directory.path) && link != directory.path {
+                    directory.size += size;
+                }
+            }
+        }
+
+        let mut directories = std::mem::take(&mut walk.directories);
+        if self.sort_by_size {
+            directories.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+        } else {
+            directories.sort_by(|a, b| a.path.cmp(&b.path));
+        }
+        let size = |bytes: u64| if self.human_readable { format_size(bytes) } else { bytes.to_string() };
+
+        let mut output = BoundedOutput::new();
+        output.push_str(&format!(
+            "Total: {} in {} and {}\n\n",
+            size(total),
+            format_count(walk.files, "file", "files"),
+            format_count(walk.seen_dirs.len(), "directory", "directories")
+        ));
+        for directory in &directories {
+            if !output.push_str(&format!("{:>10}  {}\n", size(directory.size), walk.relative(&directory.path))) {
+                break;
+            }
+        }
+        if !walk.unreadable.is_empty() {
+            output.push_str(&format!("\nWarning: could not read {}\n", walk.unreadable.join(", ")));
+        }
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                output.into_string(),
+                None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn du(path: &str) -> DiskUsageTool {
+        DiskUsageTool {
+            path: path.to_string(),
+            max_depth: None,
+            sort_by_size: false,
+            follow_symlinks: false,
+            human_readable: false,
+        }
+    }
+
+    async fn run(context: &ToolContext, tool: DiskUsageTool) -> String {
+        let result = tool.call_with_context(context).await.unwrap();
+        match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        }
+    }
+
+    fn setup() -> (TempDir, PathBuf, ToolContext) {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("big/nested")).unwrap();
+        std::fs::create_dir_all(root.join("small")).unwrap();
+        std::fs::write(root.join("top.txt"), vec![b'a'; 10]).unwrap();
+        std::fs::write(root.join("big/data.bin"), vec![b'b'; 1000]).unwrap();
+        std::fs::write(root.join("big/nested/deep.bin"), vec![b'c'; 500]).unwrap();
+        std::fs::write(root.join("small/note.txt"), vec![b'd'; 20]).unwrap();
+        let context = ToolContext::with_project_root(root.clone());
+        (temp_dir, root, context)
+    }
+
+    #[tokio::test]
+    async fn test_du_totals_per_directory() {
+        let (_temp_dir, _root, context) = setup();
+
+        let output = run(&context, du(".")).await;
+        assert_eq!(
+            output,
+            "Total: 1530 in 4 files and 4 directories\n\n      1530  .\n      1500  big\n       500  big/nested\n        20  small\n"
+        );
+
+        let tool = DiskUsageTool { max_depth: Some(1), sort_by_size: true, ..du(".") };
+        let output = run(&context, tool).await;
+        assert!(output.ends_with("      1530  .\n      1500  big\n        20  small\n"), "{}", output);
+
+        let tool = DiskUsageTool { max_depth: Some(0), human_readable: true, ..du("big") };
+        let output = run(&context, tool).await;
+        assert!(output.starts_with("Total: 1.5 KiB in 2 files"), "{}", output);
+        assert!(output.ends_with("   1.5 KiB  big\n"), "{}", output);
+
+        assert!(du("../").call_with_context(&context).await.is_err());
+        assert!(du("top.txt").call_with_context(&context).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_du_counts_links_once() {
+        let (_temp_dir, root, context) = setup();
+        let elsewhere = TempDir::new().unwrap();
+        std::fs::hard_link(root.join("big/data.bin"), root.join("small/hard.bin")).unwrap();
+        std::os::unix::fs::symlink(root.join("big"), root.join("small/big_link")).unwrap();
+        std::os::unix::fs::symlink(&root, root.join("small/loop")).unwrap();
+        std::os::unix::fs::symlink(elsewhere.path(), root.join("outside")).unwrap();
+
+        // Without following, links are counted as themselves and the hard link adds nothing
+        let output = run(&context, DiskUsageTool { max_depth: Some(0), ..du(".") }).await;
+        let link_sizes: u64 = ["small/big_link", "small/loop", "outside"]
+            .iter()
+            .map(|link| std::fs::symlink_metadata(root.join(link)).unwrap().len())
+            .sum();
+        assert!(output.starts_with(&format!("Total: {} in 8 files", 1530 + link_sizes)), "{}", output);
+
+        // Following never walks a directory twice or leaves the project
+        let tool = DiskUsageTool { follow_symlinks: true, ..du(".") };
+        let output = run(&context, tool).await;
+        assert!(output.starts_with("Total: 1530 in 5 files and 4 directories"), "{}", output);
+        assert!(!output.contains("outside"), "{}", output);
+
+        // A link out of the measured directory adds what it leads to, rows included
+        std::fs::remove_file(root.join("small/loop")).unwrap();
+        let tool = DiskUsageTool { follow_symlinks: true, ..du("small") };
+        let output = run(&context, tool).await;
+        assert!(output.starts_with("Total: 1520 in 4 files"), "{}", output);
+        assert!(output.contains("       500  small/big_link/nested
+"), "{}", output);
+    }
+}
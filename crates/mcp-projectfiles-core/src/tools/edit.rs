@@ -1,7 +1,9 @@
 use crate::config::tool_errors;
 use crate::context::{StatefulTool, ToolContext};
 use crate::theme::DiffTheme;
-use crate::tools::utils::{format_count, format_path};
+use crate::tools::utils::{
+    LineEnding, TextStyle, convert_line_endings, format_count, format_path, write_atomically,
+};
 use async_trait::async_trait;
 use colored::control;
 use colored::*;
@@ -148,12 +150,14 @@ fn generate_colored_diff(original: &str, modified: &str, file_path: &str) -> Str
 
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct EditOperation {
-    /// The exact string to find and replace
+    /// The exact string to find and replace. Also accepted as "old_string"
+    #[serde(alias = "old_string")]
     pub old: String,
-    /// The string to replace it with
+    /// The string to replace it with. Also accepted as "new_string"
+    #[serde(alias = "new_string")]
     pub new: String,
-    /// Expected number of replacements (defaults to 1)
-    #[serde(default = "default_expected")]
+    /// Expected number of replacements (defaults to 1). Also accepted as "expected_count"
+    #[serde(default = "default_expected", alias = "expected_count")]
     pub expected: u32,
     /// Replace all occurrences (when true, ignores expected count)
     #[serde(default)]
@@ -164,9 +168,64 @@ fn default_expected() -> u32 {
     1
 }
 
+/// Parse a 1-based inclusive line range ("N", "N-M", "N-" or "-M") against the file's line count
+fn parse_line_range(range: &str, total_lines: usize) -> Result<(usize, usize), String> {
+    let parse = |part: &str, what: &str| {
+        part.trim()
+            .parse::<usize>()
+            .map_err(|_| format!("Invalid {} line number: {}", what, part))
+    };
+    let (start, end) = match range.split_once('-') {
+        None => {
+            let line = parse(range, "")?;
+            (line, line)
+        }
+        Some((start, end)) => (
+            if start.trim().is_empty() { 1 } else { parse(start, "start")? },
+            if end.trim().is_empty() { total_lines } else { parse(end, "end")? },
+        ),
+    };
+    if start == 0 {
+        return Err("Line numbers start at 1".to_string());
+    }
+    if start > end {
+        return Err(format!("Invalid line range: start ({}) is greater than end ({})", start, end));
+    }
+    if end > total_lines {
+        return Err(format!(
+            "Line range {}-{} is out of bounds: file has {}",
+            start,
+            end,
+            format_count(total_lines, "line", "lines")
+        ));
+    }
+    Ok((start, end))
+}
+
+/// Replace lines start..=end with new content, keeping the file's line endings.
+/// Returns the new content and the number of lines inserted.
+fn replace_line_range(content: &str, start: usize, end: usize, new: &str) -> (String, usize) {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let ending = TextStyle::detect(content)
+        .and_then(|style| style.line_ending)
+        .unwrap_or(LineEnding::Lf);
+
+    let mut replacement = convert_line_endings(new, ending);
+    // Keep the line after the range on its own line
+    if !replacement.is_empty() && lines[end - 1].ends_with('\n') && !replacement.ends_with('\n') {
+        replacement.push_str(ending.as_str());
+    }
+    let inserted = replacement.lines().count();
+
+    let mut result = lines[..start - 1].concat();
+    result.push_str(&replacement);
+    result.push_str(&lines[end..].concat());
+    (result, inserted)
+}
+
 /// Replace exact strings in files. Preferred over system text editors.
 ///
-/// IMPORTANT: You must choose either single edit mode OR multi-edit mode, not both.
+/// IMPORTANT: You must choose one of single edit mode, multi-edit mode or line range mode.
 /// NOTE: Omit optional parameters when not needed, don't pass null.
 ///
 /// Parameters:
@@ -175,8 +234,13 @@ fn default_expected() -> u32 {
 /// - new: Replacement string (optional - required for single mode)
 /// - expected: Expected match count (optional - default: 1)
 /// - edits: Array of edit operations (optional - required for multi mode)
+/// - line_range: Lines to replace with 'new', e.g. "10-15" (optional - required for line range mode)
 /// - show_diff: Show changes made (optional - default: false)
 ///
+/// old, new and expected are also accepted as old_string, new_string and expected_count.
+/// The file is only written if every edit matches its expected count, and is replaced
+/// atomically so a failed write never leaves it half-edited.
+///
 /// # Single Edit Mode
 /// Use for simple, one-time replacements. Requires 'old' and 'new' parameters:
 /// ```json
@@ -227,6 +291,17 @@ fn default_expected() -> u32 {
 /// }
 /// ```
 ///
+/// # Line Range Mode
+/// Replace exactly lines 10-15 (1-based, inclusive) with the lines in 'new'.
+/// An empty 'new' deletes the lines:
+/// ```json
+/// {
+///   "path": "src/lib.rs",
+///   "line_range": "10-15",
+///   "new": "fn helper() -> u32 {\n    42\n}"
+/// }
+/// ```
+///
 /// # Creating New Files
 /// To create a new file, use multi-edit mode with an empty old in the first edit:
 /// ```json
@@ -259,21 +334,22 @@ fn default_expected() -> u32 {
 Examples:
 - {\"path\": \"config.json\", \"old\": \"foo\", \"new\": \"bar\"}
 - {\"path\": \"src/main.rs\", \"edits\": [{\"old\": \"old1\", \"new\": \"new1\"}, {\"old\": \"old2\", \"new\": \"new2\"}]}
-- {\"path\": \"README.md\", \"old\": \"typo\", \"new\": \"correct\", \"dry_run\": true}"
+- {\"path\": \"README.md\", \"old\": \"typo\", \"new\": \"correct\", \"dry_run\": true}
+- {\"path\": \"src/lib.rs\", \"line_range\": \"10-15\", \"new\": \"replacement lines\"}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct EditTool {
     /// Path to the file to edit (relative to project root)
     pub path: String,
 
-    /// The exact string to find and replace (for single edit mode)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The exact string to find and replace (for single edit mode). Also accepted as "old_string"
+    #[serde(alias = "old_string", skip_serializing_if = "Option::is_none")]
     pub old: Option<String>,
-    /// The string to replace it with (for single edit mode)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The string to replace it with (for single edit mode). Also accepted as "new_string"
+    #[serde(alias = "new_string", skip_serializing_if = "Option::is_none")]
     pub new: Option<String>,
-    /// Expected number of replacements (for single edit mode, defaults to 1)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Expected number of replacements (for single edit mode, defaults to 1). Also accepted as "expected_count"
+    #[serde(alias = "expected_count", skip_serializing_if = "Option::is_none")]
     pub expected: Option<u32>,
     /// Replace all occurrences (for single edit mode, when true ignores expected count)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -284,6 +360,11 @@ pub struct EditTool {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub edits: Option<Vec<EditOperation>>,
 
+    // Line range mode
+    /// Lines to replace with 'new' (1-based, inclusive, e.g. "10-15" or "7"). The file must have been read first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_range: Option<String>,
+
     /// Show a diff of the changes made (default: false)
     #[serde(default)]
     pub show_diff: bool,
@@ -296,9 +377,13 @@ pub struct EditTool {
 #[async_trait]
 impl StatefulTool for EditTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
+        context.ensure_writable(TOOL_NAME)?;
+
         // Validate that single and multi-edit parameters are not mixed
         if self.edits.is_some()
             && (self.old.is_some() || self.new.is_some() || self.expected.is_some() || self.replace_all.is_some())
@@ -309,8 +394,30 @@ impl StatefulTool for EditTool {
             )));
         }
 
+        if self.line_range.is_some()
+            && (self.edits.is_some() || self.old.is_some() || self.expected.is_some() || self.replace_all.is_some())
+        {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                "Cannot combine 'line_range' with old/expected/replace_all or an edits array. Pass the replacement lines in 'new'",
+            )));
+        }
+        let range_content = match (&self.line_range, &self.new) {
+            (Some(_), Some(new)) => Some(new.clone()),
+            (Some(_), None) => {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    TOOL_NAME,
+                    "'line_range' requires 'new' with the replacement lines (use an empty string to delete them)",
+                )));
+            }
+            _ => None,
+        };
+
         // Determine which mode we're in and normalize to a list of edits
-        let edits = if let Some(edits) = self.edits {
+        let edits = if range_content.is_some() {
+            // Line range mode is applied separately once the file is read
+            Vec::new()
+        } else if let Some(edits) = self.edits {
             // Multi-edit mode
             if !edits.is_empty() {
                 edits
@@ -463,6 +570,22 @@ impl StatefulTool for EditTool {
             None
         };
 
+        // Replace the requested lines, validating the range against the current content
+        let mut range_summary = None;
+        if let (Some(range), Some(new)) = (&self.line_range, &range_content) {
+            let total_lines = content.lines().count();
+            let (start, end) = parse_line_range(range, total_lines)
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &e)))?;
+            let (replaced, inserted) = replace_line_range(&content, start, end, new);
+            content = replaced;
+            let removed = end - start + 1;
+            range_summary = Some(if start == end {
+                format!("line {}: -{} +{}", start, removed, format_count(inserted, "line", "lines"))
+            } else {
+                format!("lines {}-{}: -{} +{}", start, end, removed, format_count(inserted, "line", "lines"))
+            });
+        }
+
         // Apply edits sequentially
         let mut total_replacements = 0;
         let mut first_edit_line = None;
@@ -567,12 +690,7 @@ impl StatefulTool for EditTool {
 
         // Write back to file (unless dry run)
         if !self.dry_run {
-            fs::write(&canonical_path, &content).await.map_err(|e| {
-                CallToolError::from(tool_errors::invalid_input(
-                    TOOL_NAME,
-                    &format!("Failed to write file: {}", e),
-                ))
-            })?;
+            write_atomically(&canonical_path, &canonical_project_root, content.as_bytes(), TOOL_NAME)?;
 
             // Track written files
             let written_files = context
@@ -582,6 +700,7 @@ impl StatefulTool for EditTool {
             let mut written_files_clone = (*written_files).clone();
             written_files_clone.insert(canonical_path.clone());
             context.set_custom_state(written_files_clone).await;
+            context.record_modified(&canonical_path).await;
 
             // If this was a new file, also add it to read files
             if is_new_file {
@@ -596,7 +715,14 @@ impl StatefulTool for EditTool {
             .strip_prefix(&project_root)
             .unwrap_or(&canonical_path);
 
-        let mut message = if self.dry_run {
+        let mut message = if let Some(summary) = &range_summary {
+            format!(
+                "{} file {} (replaced {})",
+                if self.dry_run { "[DRY RUN] Would edit" } else { "Edited" },
+                format_path(relative_path),
+                summary
+            )
+        } else if self.dry_run {
             if edits.len() == 1 {
                 format!(
                     "[DRY RUN] Would edit file {} ({} at line {})",
@@ -710,9 +836,12 @@ mod tests {
             limit: 0,
             line_range: None,
             binary_check: true,
+            binary_threshold: 10,
+            force_text: false,
             tail: false,
             pattern: None,
             invert_match: false,
+            only_matching: false,
             context_before: 0,
             context_after: 0,
             case: "sensitive".to_string(),
@@ -721,6 +850,10 @@ mod tests {
             follow_symlinks: true,
             preview_only: false,
             include_metadata: false,
+            follow: false,
+            follow_timeout_ms: 5000,
+            hex_dump: false,
+            no_cache: false,
         };
         let _ = read_tool.call_with_context(context).await.unwrap();
     }
@@ -745,6 +878,7 @@ mod tests {
             show_diff: false,
             dry_run: false,
             replace_all: None,
+            line_range: None,
         };
 
         let result = tool.call_with_context(&context).await;
@@ -777,6 +911,7 @@ mod tests {
             show_diff: false,
             dry_run: false,
             replace_all: None,
+            line_range: None,
         };
 
         let result = tool.call_with_context(&context).await.unwrap();
@@ -821,6 +956,7 @@ mod tests {
             show_diff: false,
             dry_run: false,
             replace_all: None,
+            line_range: None,
         };
 
         let result = tool.call_with_context(&context).await.unwrap();
@@ -851,6 +987,7 @@ mod tests {
             show_diff: true,
             dry_run: false,
             replace_all: None,
+            line_range: None,
         };
 
         let result = tool.call_with_context(&context).await.unwrap();
@@ -882,6 +1019,7 @@ mod tests {
             show_diff: false,
             dry_run: false,
             replace_all: None,
+            line_range: None,
         };
 
         let result = tool.call_with_context(&context).await;
@@ -915,6 +1053,7 @@ mod tests {
             show_diff: false,
             dry_run: true,
             replace_all: None,
+            line_range: None,
         };
         
         let result = tool.call_with_context(&context).await.unwrap();
@@ -969,6 +1108,7 @@ mod tests {
             show_diff: false,
             dry_run: true,
             replace_all: None,
+            line_range: None,
         };
         
         let result = tool.call_with_context(&context).await.unwrap();
@@ -1005,6 +1145,7 @@ mod tests {
             edits: None,
             show_diff: false,
             dry_run: false,
+            line_range: None,
         };
         
         let result = tool.call_with_context(&context).await.unwrap();
@@ -1053,6 +1194,7 @@ mod tests {
             show_diff: false,
             dry_run: false,
             replace_all: None,
+            line_range: None,
         };
         
         let result = tool.call_with_context(&context).await.unwrap();
@@ -1086,6 +1228,7 @@ mod tests {
             edits: None,
             show_diff: false,
             dry_run: false,
+            line_range: None,
         };
         
         let result = tool.call_with_context(&context).await;
@@ -1095,4 +1238,103 @@ mod tests {
         let error_msg = error.to_string();
         assert!(error_msg.contains("Cannot use both 'replace_all: true' and 'expected' parameters"));
     }
+
+    fn line_range_tool(range: &str, new: &str) -> EditTool {
+        EditTool {
+            path: "test.txt".to_string(),
+            old: None,
+            new: Some(new.to_string()),
+            expected: None,
+            replace_all: None,
+            edits: None,
+            show_diff: false,
+            dry_run: false,
+            line_range: Some(range.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_line_range_replace() {
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::with_project_root(temp_dir.path().to_path_buf());
+        let file_path = temp_dir.path().join("test.txt");
+
+        // Requires a prior read like other edits
+        tokio::fs::write(&file_path, "one\r\ntwo\r\nthree\r\nfour\r\n").await.unwrap();
+        let result = line_range_tool("2-3", "TWO\nTHREE\nextra").call_with_context(&context).await;
+        assert!(result.unwrap_err().to_string().contains("File must be read before editing"));
+
+        setup_test_file_with_read(&context, "test.txt", "one\r\ntwo\r\nthree\r\nfour\r\n").await;
+        let result = line_range_tool("2-3", "TWO\nTHREE\nextra").call_with_context(&context).await.unwrap();
+        let message = extract_text_content(&result);
+        assert!(message.contains("replaced lines 2-3: -2 +3 lines"), "{}", message);
+
+        // Line endings follow the file and the rest is untouched
+        let content = tokio::fs::read_to_string(&file_path).await.unwrap();
+        assert_eq!(content, "one\r\nTWO\r\nTHREE\r\nextra\r\nfour\r\n");
+
+        // An empty replacement deletes the lines
+        line_range_tool("5", "").call_with_context(&context).await.unwrap();
+        let content = tokio::fs::read_to_string(&file_path).await.unwrap();
+        assert_eq!(content, "one\r\nTWO\r\nTHREE\r\nextra\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_line_range_validation() {
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::with_project_root(temp_dir.path().to_path_buf());
+        setup_test_file_with_read(&context, "test.txt", "a\nb\nc").await;
+
+        let error = line_range_tool("2-4", "x").call_with_context(&context).await.unwrap_err();
+        assert!(error.to_string().contains("out of bounds: file has 3 lines"));
+        let error = line_range_tool("3-2", "x").call_with_context(&context).await.unwrap_err();
+        assert!(error.to_string().contains("start (3) is greater than end (2)"));
+
+        let mut tool = line_range_tool("1", "x");
+        tool.old = Some("a".to_string());
+        let error = tool.call_with_context(&context).await.unwrap_err();
+        assert!(error.to_string().contains("Cannot combine 'line_range'"));
+
+        // Replacing the unterminated last line keeps it unterminated
+        let result = line_range_tool("3", "C").call_with_context(&context).await.unwrap();
+        assert!(extract_text_content(&result).contains("replaced line 3: -1 +1 line"));
+        let content = tokio::fs::read_to_string(temp_dir.path().join("test.txt")).await.unwrap();
+        assert_eq!(content, "a\nb\nC");
+    }
+
+    #[tokio::test]
+    async fn test_string_aliases_and_unique_match() {
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::with_project_root(temp_dir.path().to_path_buf());
+        let file_path = temp_dir.path().join("test.txt");
+        setup_test_file_with_read(&context, "test.txt", "let a = 1;\nlet a = 1;\nlet b = 2;\n").await;
+
+        // A snippet that appears twice is refused and the file is left alone
+        let tool: EditTool = serde_json::from_value(serde_json::json!({
+            "path": "test.txt",
+            "old_string": "let a = 1;",
+            "new_string": "let a = 3;"
+        }))
+        .unwrap();
+        let error = tool.call_with_context(&context).await.unwrap_err();
+        assert!(error.to_string().contains("Expected 1 replacements but found 2 occurrences"));
+        assert_eq!(
+            tokio::fs::read_to_string(&file_path).await.unwrap(),
+            "let a = 1;\nlet a = 1;\nlet b = 2;\n"
+        );
+
+        let tool: EditTool = serde_json::from_value(serde_json::json!({
+            "path": "test.txt",
+            "old_string": "let a = 1;",
+            "new_string": "let a = 3;",
+            "expected_count": 2
+        }))
+        .unwrap();
+        tool.call_with_context(&context).await.unwrap();
+        assert_eq!(
+            tokio::fs::read_to_string(&file_path).await.unwrap(),
+            "let a = 3;\nlet a = 3;\nlet b = 2;\n"
+        );
+        assert!(!temp_dir.path().join("test.txt.tmp").exists());
+    }
 }
@@ -13,6 +13,9 @@ use tokio::io::AsyncReadExt;
 
 const TOOL_NAME: &str = "file";
 
+/// Bytes read from the start of a file to analyze its content
+pub(super) const CONTENT_SAMPLE_SIZE: usize = 8192;
+
 fn default_follow_symlinks() -> bool {
     true
 }
@@ -34,9 +37,11 @@ pub struct FileTool {
 #[async_trait]
 impl StatefulTool for FileTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
         // Get project root and resolve path
         let project_root = context.get_project_root().map_err(|e| {
             CallToolError::from(tool_errors::invalid_input(
@@ -79,7 +84,7 @@ impl StatefulTool for FileTool {
             ))
         })?;
 
-        let mut buffer = vec![0u8; 8192]; // Read up to 8KB for analysis
+        let mut buffer = vec![0u8; CONTENT_SAMPLE_SIZE];
         let bytes_read = file.read(&mut buffer).await.map_err(|e| {
             CallToolError::from(tool_errors::invalid_input(
                 TOOL_NAME,
@@ -91,7 +96,7 @@ impl StatefulTool for FileTool {
 
         // Analyze the file
         let is_text = is_text_file(&buffer);
-        let mime_type = detect_mime_type(&normalized_path, &buffer);
+        let (mime_type, description) = detect_content_type(&normalized_path, &buffer);
         let encoding = if is_text {
             detect_encoding(&buffer)
         } else {
@@ -121,6 +126,7 @@ impl StatefulTool for FileTool {
             "is_binary": !is_text,
             "encoding": encoding,
             "mime_type": mime_type,
+            "description": description,
             "size": metadata.len(),
             "size_human": format_size(metadata.len()),
             "has_bom": has_bom,
@@ -215,118 +221,127 @@ fn detect_bom(data: &[u8]) -> Option<String> {
     }
 }
 
-fn detect_mime_type(path: &Path, data: &[u8]) -> String {
-    // First check by extension
-    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-        let mime = match ext.to_lowercase().as_str() {
-            // Text files
-            "txt" => "text/plain",
-            "md" | "markdown" => "text/markdown",
-            "html" | "htm" => "text/html",
-            "css" => "text/css",
-            "js" | "mjs" => "text/javascript",
-            "json" => "application/json",
-            "xml" => "application/xml",
-            "yaml" | "yml" => "text/yaml",
-            "toml" => "text/toml",
-
-            // Programming languages
-            "rs" => "text/rust",
-            "py" => "text/x-python",
-            "java" => "text/x-java",
-            "c" => "text/x-c",
-            "cpp" | "cc" | "cxx" => "text/x-c++",
-            "h" | "hpp" => "text/x-c-header",
-            "go" => "text/x-go",
-            "rb" => "text/x-ruby",
-            "php" => "text/x-php",
-            "swift" => "text/x-swift",
-            "kt" => "text/x-kotlin",
-            "ts" | "tsx" => "text/typescript",
-            "jsx" => "text/jsx",
-            "vue" => "text/vue",
-            "sh" | "bash" => "text/x-shellscript",
-            "ps1" => "text/x-powershell",
-
-            // Data files
-            "csv" => "text/csv",
-            "tsv" => "text/tab-separated-values",
-            "sql" => "application/sql",
-
-            // Image files
-            "jpg" | "jpeg" => "image/jpeg",
-            "png" => "image/png",
-            "gif" => "image/gif",
-            "bmp" => "image/bmp",
-            "svg" => "image/svg+xml",
-            "webp" => "image/webp",
-            "ico" => "image/x-icon",
-
-            // Archive files
-            "zip" => "application/zip",
-            "tar" => "application/x-tar",
-            "gz" | "gzip" => "application/gzip",
-            "rar" => "application/x-rar-compressed",
-            "7z" => "application/x-7z-compressed",
-
-            // Document files
-            "pdf" => "application/pdf",
-            "doc" => "application/msword",
-            "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
-            "xls" => "application/vnd.ms-excel",
-            "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
-            "ppt" => "application/vnd.ms-powerpoint",
-            "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
-
-            // Binary executables
-            "exe" => "application/x-msdownload",
-            "dll" => "application/x-msdownload",
-            "so" => "application/x-sharedlib",
-            "dylib" => "application/x-sharedlib",
-
-            _ => {
-                // Check by content if extension doesn't match
-                return detect_mime_by_content(data);
-            }
-        };
-        return mime.to_string();
+/// MIME type and a short description of a file from `data`, its first bytes. Magic bytes
+/// win for binary formats, so a PNG named `.dat` is still a PNG. Text, unrecognized content
+/// and plain zip containers such as `.docx` fall back to the extension
+pub(super) fn detect_content_type(path: &Path, data: &[u8]) -> (String, String) {
+    let extension_mime = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(|ext| mime_from_extension(&ext.to_lowercase()));
+
+    if let Some(kind) = infer::get(data) {
+        let text = kind.matcher_type() == infer::MatcherType::Text;
+        let generic_zip = kind.mime_type() == "application/zip" && extension_mime.is_some();
+        if !text && !generic_zip {
+            return (kind.mime_type().to_string(), describe_kind(&kind));
+        }
     }
 
-    // Fallback to content detection
-    detect_mime_by_content(data)
+    let description = if data.is_empty() {
+        "empty"
+    } else if is_text_file(data) {
+        "text"
+    } else {
+        "binary data"
+    };
+    let mime = match extension_mime {
+        Some(mime) => mime.to_string(),
+        None if is_text_file(data) => "text/plain".to_string(),
+        None => "application/octet-stream".to_string(),
+    };
+    (mime, description.to_string())
 }
 
-fn detect_mime_by_content(data: &[u8]) -> String {
-    // Check magic bytes for common formats
-    if data.len() >= 4 {
-        // Images
-        if &data[0..2] == b"\xFF\xD8" {
-            return "image/jpeg".to_string();
-        }
-        if data.len() >= 8 && &data[0..8] == b"\x89\x50\x4E\x47\x0D\x0A\x1A\x0A" {
-            return "image/png".to_string();
-        }
-        if data.len() >= 6 && (&data[0..6] == b"GIF87a" || &data[0..6] == b"GIF89a") {
-            return "image/gif".to_string();
-        }
-
-        // Archives
-        if &data[0..2] == b"PK" {
-            return "application/zip".to_string();
-        }
-
-        // PDF
-        if data.len() >= 5 && &data[0..5] == b"%PDF-" {
-            return "application/pdf".to_string();
-        }
-    }
+/// "PNG image", "ZIP archive" and so on
+fn describe_kind(kind: &infer::Type) -> String {
+    let noun = match kind.matcher_type() {
+        infer::MatcherType::App => "executable",
+        // infer files PDF under archives
+        infer::MatcherType::Archive if kind.extension() == "pdf" => "document",
+        infer::MatcherType::Archive => "archive",
+        infer::MatcherType::Audio => "audio",
+        infer::MatcherType::Book => "ebook",
+        infer::MatcherType::Doc => "document",
+        infer::MatcherType::Font => "font",
+        infer::MatcherType::Image => "image",
+        infer::MatcherType::Text => "text",
+        infer::MatcherType::Video => "video",
+        infer::MatcherType::Custom => "data",
+    };
+    format!("{} {}", kind.extension().to_uppercase(), noun)
+}
 
-    // Default based on whether it's text or binary
-    if is_text_file(data) {
-        "text/plain".to_string()
-    } else {
-        "application/octet-stream".to_string()
-    }
+fn mime_from_extension(ext: &str) -> Option<&'static str> {
+    let mime = match ext {
+        // Text files
+        "txt" => "text/plain",
+        "md" | "markdown" => "text/markdown",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" | "mjs" => "text/javascript",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "yaml" | "yml" => "text/yaml",
+        "toml" => "text/toml",
+
+        // Programming languages
+        "rs" => "text/rust",
+        "py" => "text/x-python",
+        "java" => "text/x-java",
+        "c" => "text/x-c",
+        "cpp" | "cc" | "cxx" => "text/x-c++",
+        "h" | "hpp" => "text/x-c-header",
+        "go" => "text/x-go",
+        "rb" => "text/x-ruby",
+        "php" => "text/x-php",
+        "swift" => "text/x-swift",
+        "kt" => "text/x-kotlin",
+        "ts" | "tsx" => "text/typescript",
+        "jsx" => "text/jsx",
+        "vue" => "text/vue",
+        "sh" | "bash" => "text/x-shellscript",
+        "ps1" => "text/x-powershell",
+
+        // Data files
+        "csv" => "text/csv",
+        "tsv" => "text/tab-separated-values",
+        "sql" => "application/sql",
+
+        // Image files
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+
+        // Archive files
+        "zip" => "application/zip",
+        "tar" => "application/x-tar",
+        "gz" | "gzip" => "application/gzip",
+        "rar" => "application/x-rar-compressed",
+        "7z" => "application/x-7z-compressed",
+
+        // Document files
+        "pdf" => "application/pdf",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "ppt" => "application/vnd.ms-powerpoint",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+
+        // Binary executables
+        "exe" => "application/x-msdownload",
+        "dll" => "application/x-msdownload",
+        "so" => "application/x-sharedlib",
+        "dylib" => "application/x-sharedlib",
+
+        _ => return None,
+    };
+    Some(mime)
 }
 
 fn format_size(size: u64) -> String {
@@ -714,6 +729,30 @@ mod tests {
         assert_eq!(json["is_binary"], true);
     }
 
+    #[tokio::test]
+    async fn test_file_magic_bytes_beat_extension() {
+        let (context, temp_dir) = setup_test_context().await;
+        let png_signature = vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D];
+        create_test_file(temp_dir.path(), "export.dat", &png_signature).await;
+        create_test_file(temp_dir.path(), "report.docx", &[0x50, 0x4B, 0x03, 0x04]).await;
+
+        let json = parse_output(&create_file_tool("export.dat").call_with_context(&context).await.unwrap()).await;
+        assert_eq!(json["mime_type"], "image/png");
+        assert_eq!(json["description"], "PNG image");
+
+        // A bare zip signature says less than a zip-based extension
+        let json = parse_output(&create_file_tool("report.docx").call_with_context(&context).await.unwrap()).await;
+        assert_eq!(
+            json["mime_type"],
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+        );
+
+        let (mime_type, description) = detect_content_type(Path::new("notes.md"), b"# Notes\n");
+        assert_eq!((mime_type.as_str(), description.as_str()), ("text/markdown", "text"));
+        let (mime_type, description) = detect_content_type(Path::new("blob"), &[0x00, 0x01, 0x02]);
+        assert_eq!((mime_type.as_str(), description.as_str()), ("application/octet-stream", "binary data"));
+    }
+
     #[tokio::test]
     async fn test_file_jpeg_magic_bytes() {
         let (context, temp_dir) = setup_test_context().await;
@@ -1,24 +1,27 @@
 use crate::context::{StatefulTool, ToolContext};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use rust_mcp_schema::{
     CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
 };
 use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use serde::{Deserialize, Serialize};
 use tokio::fs;
-use glob::Pattern;
-use chrono::{Local, Duration};
+use regex::Regex;
+use chrono::{DateTime, Local, Duration};
 use std::time::SystemTime;
 use async_trait::async_trait;
 use crate::config::tool_errors;
-use crate::tools::utils::{format_size, format_count, resolve_path_for_read};
+#[cfg(unix)]
+use crate::tools::chmod::apply_mode;
+use crate::tools::chmod::ModeSpec;
+use crate::tools::utils::{format_size, format_count, resolve_path_for_read, GitignoreFilter, GlobPattern};
 
 const TOOL_NAME: &str = "find";
 
 #[mcp_tool(
     name = "find",
-    description = "Find files by name, path, size, date. Supports wildcards, depth limits, multiple output formats.
-Examples: {\"name_pattern\": \"*.test.js\"}, {\"size_filter\": \"+1M\", \"date_filter\": \"-7d\"}"
+    description = "Find files by name, path, size, date. Supports wildcards or regex names, empty entries, depth limits, multiple output formats, and an optional chmod of every match.
+Examples: {\"name_pattern\": \"*.test.js\"}, {\"name_regex\": \"^test_.*\\\\.rs$\"}, {\"size_filter\": \"+1M\", \"date_filter\": \"-7d\"}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct FindTool {
@@ -26,11 +29,16 @@ pub struct FindTool {
     #[serde(default = "default_path")]
     pub path: String,
     
-    /// Name pattern to match (supports wildcards like *.rs, test_*.js)
+    /// Name pattern to match (supports wildcards like *.rs, test_*.js, and braces like *.{rs,toml})
     #[serde(default)]
     pub name_pattern: Option<String>,
     
-    /// Path pattern to match against full file path (supports wildcards)
+    /// Regular expression matched against each file name (e.g., "^test_.*\\.rs$").
+    /// Takes precedence over name_pattern when both are given
+    #[serde(default)]
+    pub name_regex: Option<String>,
+    
+    /// Path pattern to match against full file path (supports wildcards and braces)
     /// Examples: "*/test/*", "**/src/**", "!target/**"
     #[serde(default)]
     pub path_pattern: Option<String>,
@@ -43,10 +51,24 @@ pub struct FindTool {
     #[serde(default)]
     pub size_filter: Option<String>,
     
+    /// Only match empty regular files and directories with no entries (default: false)
+    #[serde(default)]
+    pub empty_only: bool,
+    
     /// Date filter (e.g., "-7d" for last 7 days, "+30d" for older than 30 days)
     #[serde(default)]
     pub date_filter: Option<String>,
     
+    /// Only match entries modified after this point: an RFC3339 timestamp or a
+    /// project-relative path whose mtime is used (like `find -newer`)
+    #[serde(default)]
+    pub newer_than: Option<String>,
+    
+    /// Only match entries modified before this point: an RFC3339 timestamp or a
+    /// project-relative path whose mtime is used
+    #[serde(default)]
+    pub older_than: Option<String>,
+    
     /// Maximum depth to search (None = unlimited)
     #[serde(default)]
     pub max_depth: Option<u32>,
@@ -69,6 +91,20 @@ pub struct FindTool {
     /// - "compact": Minimal info
     #[serde(default = "default_output_format")]
     pub output_format: String,
+    
+    /// Terminate paths with NUL bytes instead of newlines in the "names" and "compact" formats,
+    /// like `find -print0`, so names containing spaces or newlines survive piping (default: false)
+    #[serde(default)]
+    pub null_separated: bool,
+    
+    /// Skip paths ignored by the project's .gitignore or .git/info/exclude (default: false)
+    #[serde(default)]
+    pub respect_gitignore: bool,
+    
+    /// Mode to apply to every reported match, like `find ... -exec chmod` (e.g. "644" or "u+x").
+    /// Unix only; matches resolving outside the project are skipped (default: none)
+    #[serde(default)]
+    pub then_chmod: Option<String>,
 }
 
 fn default_path() -> String {
@@ -93,6 +129,7 @@ fn default_output_format() -> String {
 
 #[derive(Debug)]
 struct SearchResult {
+    path: PathBuf,
     relative_path: String,
     is_dir: bool,
     size: u64,
@@ -101,9 +138,15 @@ struct SearchResult {
 #[async_trait]
 impl StatefulTool for FindTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
+        if self.then_chmod.is_some() {
+            context.ensure_writable(TOOL_NAME)?;
+        }
+
         let project_root = context.get_project_root()
             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
         
@@ -111,13 +154,19 @@ impl StatefulTool for FindTool {
         let canonical_search_path = resolve_path_for_read(&self.path, &project_root, self.follow_search_path, TOOL_NAME)?;
         
         // Parse filters
-        let name_pattern = self.name_pattern.as_ref()
-            .map(|p| Pattern::new(p))
-            .transpose()
-            .map_err(|e| CallToolError::from(tool_errors::pattern_error(TOOL_NAME, &self.name_pattern.as_ref().unwrap_or(&"".to_string()), &e.to_string())))?;
+        let name_pattern = if let Some(regex) = &self.name_regex {
+            Some(NameFilter::Regex(Regex::new(regex)
+                .map_err(|e| CallToolError::from(tool_errors::pattern_error(TOOL_NAME, regex, &e.to_string())))?))
+        } else {
+            self.name_pattern.as_ref()
+                .map(|p| GlobPattern::new(p))
+                .transpose()
+                .map_err(|e| CallToolError::from(tool_errors::pattern_error(TOOL_NAME, self.name_pattern.as_ref().unwrap_or(&"".to_string()), &e.to_string())))?
+                .map(NameFilter::Glob)
+        };
         
         let path_pattern = self.path_pattern.as_ref()
-            .map(|p| Pattern::new(p))
+            .map(|p| GlobPattern::new(p))
             .transpose()
             .map_err(|e| CallToolError::from(tool_errors::pattern_error(TOOL_NAME, &self.path_pattern.as_ref().unwrap_or(&"".to_string()), &e.to_string())))?;
         
@@ -126,22 +175,43 @@ impl StatefulTool for FindTool {
             .transpose()
             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Invalid size filter: {}", e))))?;
         
-        let date_filter = self.date_filter.as_ref()
-            .map(|f| parse_date_filter(f))
-            .transpose()
-            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Invalid date filter: {}", e))))?;
+        let mut date_filters = Vec::new();
+        if let Some(filter) = &self.date_filter {
+            date_filters.push(parse_date_filter(filter)
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Invalid date filter: {}", e))))?);
+        }
+        if let Some(reference) = &self.newer_than {
+            date_filters.push(DateFilter::NewerThan(parse_reference_time("newer_than", reference, context, &project_root)?));
+        }
+        if let Some(reference) = &self.older_than {
+            date_filters.push(DateFilter::OlderThan(parse_reference_time("older_than", reference, context, &project_root)?));
+        }
+        
+        // Validate the follow-up action before touching anything
+        let chmod_mode = self.then_chmod.as_deref().map(ModeSpec::parse).transpose()?;
+        #[cfg(not(unix))]
+        if chmod_mode.is_some() {
+            return Err(CallToolError::from(tool_errors::operation_not_permitted(
+                TOOL_NAME,
+                "then_chmod is only available on Unix-like systems"
+            )));
+        }
+        
+        let gitignore = GitignoreFilter::new(&project_root, self.respect_gitignore);
         
         // Perform search
         let mut results: Vec<SearchResult> = Vec::new();
         let mut search_count = 0;
         
         self.search_directory(
+            context,
             &canonical_search_path,
             &project_root,
+            &gitignore,
             &name_pattern,
             &path_pattern,
             &size_filter,
-            &date_filter,
+            &date_filters,
             0,
             &mut results,
             &mut search_count,
@@ -159,18 +229,19 @@ impl StatefulTool for FindTool {
             &results
         };
         
+        let terminator = if self.null_separated { '\0' } else { '\n' };
         match self.output_format.as_str() {
             "names" => {
                 // Clean output - just file paths
                 for result in display_results {
-                    output.push_str(&format!("{}\n", result.relative_path));
+                    output.push_str(&format!("{}{}", result.relative_path, terminator));
                 }
             },
             "compact" => {
                 // Minimal info - type and path
                 for result in display_results {
                     let type_char = if result.is_dir { "D" } else { "F" };
-                    output.push_str(&format!("{} {}\n", type_char, result.relative_path));
+                    output.push_str(&format!("{} {}{}", type_char, result.relative_path, terminator));
                 }
             },
             _ => { // "detailed" or default
@@ -198,6 +269,15 @@ impl StatefulTool for FindTool {
             }
         }
         
+        #[cfg(unix)]
+        if let Some(mode) = &chmod_mode {
+            let summary = self.chmod_matches(mode, display_results, &project_root).await?;
+            if !output.is_empty() && !output.ends_with('\n') {
+                output.push('\n');
+            }
+            output.push_str(&summary);
+        }
+        
         Ok(CallToolResult {
             content: vec![CallToolResultContentItem::TextContent(TextContent::new(
                 output,
@@ -210,14 +290,59 @@ impl StatefulTool for FindTool {
 }
 
 impl FindTool {
+    /// Apply then_chmod to each reported match and summarize how many changed
+    #[cfg(unix)]
+    async fn chmod_matches(
+        &self,
+        mode: &ModeSpec,
+        matches: &[SearchResult],
+        project_root: &Path,
+    ) -> Result<String, CallToolError> {
+        let canonical_root = project_root.canonicalize()
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to canonicalize project root: {}", e))))?;
+        
+        let mut changed = 0;
+        let mut skipped = 0;
+        for result in matches {
+            // Containment is checked per match since symlinks may point anywhere
+            let Ok(canonical_path) = result.path.canonicalize() else {
+                skipped += 1;
+                continue;
+            };
+            if !canonical_path.starts_with(&canonical_root) {
+                skipped += 1;
+                continue;
+            }
+            
+            let was_changed = apply_mode(&canonical_path, mode).await
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to set permissions for '{}': {}", result.relative_path, e))))?;
+            if was_changed {
+                changed += 1;
+            }
+        }
+        
+        let mut summary = format!(
+            "Changed permissions to {} for {} of {}",
+            self.then_chmod.as_deref().unwrap_or_default(),
+            changed,
+            format_count(matches.len(), "matched item", "matched items")
+        );
+        if skipped > 0 {
+            summary.push_str(&format!(" ({} outside the project skipped)", skipped));
+        }
+        Ok(summary)
+    }
+    
     fn search_directory<'a>(
         &'a self,
+        context: &'a ToolContext,
         dir: &'a Path,
         project_root: &'a Path,
-        name_pattern: &'a Option<Pattern>,
-        path_pattern: &'a Option<Pattern>,
+        gitignore: &'a GitignoreFilter,
+        name_pattern: &'a Option<NameFilter>,
+        path_pattern: &'a Option<GlobPattern>,
         size_filter: &'a Option<SizeFilter>,
-        date_filter: &'a Option<DateFilter>,
+        date_filters: &'a [DateFilter],
         current_depth: u32,
         results: &'a mut Vec<SearchResult>,
         search_count: &'a mut usize,
@@ -241,20 +366,29 @@ impl FindTool {
         };
         
         loop {
+            context.check_cancelled(TOOL_NAME)?;
             let entry = match entries.next_entry().await {
                 Ok(Some(entry)) => entry,
                 Ok(None) => break,
                 Err(e) => return Err(CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read entry: {}", e)))),
             };
             
-            *search_count += 1;
-            
             let path = entry.path();
             let metadata = match entry.metadata().await {
                 Ok(metadata) => metadata,
                 Err(e) => return Err(CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get metadata: {}", e)))),
             };
             
+            // Ignored directories are not descended into
+            if gitignore.is_ignored(&path, metadata.is_dir()) {
+                continue;
+            }
+            
+            *search_count += 1;
+            context.report_progress(*search_count as u64, None, || {
+                format!("Searched {} entries, {} matched", search_count, results.len())
+            });
+            
             let relative_path = path.strip_prefix(project_root)
                 .unwrap_or(&path)
                 .to_string_lossy()
@@ -271,12 +405,14 @@ impl FindTool {
                 if metadata.is_dir() && current_depth < self.max_depth.unwrap_or(u32::MAX) {
                     // Still recurse into directories even if they don't match
                     Box::pin(self.search_directory(
+                        context,
                         &path,
                         project_root,
+                        gitignore,
                         name_pattern,
                         path_pattern,
                         size_filter,
-                        date_filter,
+                        date_filters,
                         current_depth + 1,
                         results,
                         search_count,
@@ -293,12 +429,14 @@ impl FindTool {
                 if !pattern.matches(file_name) {
                     if metadata.is_dir() && current_depth < self.max_depth.unwrap_or(u32::MAX) {
                         Box::pin(self.search_directory(
+                            context,
                             &path,
                             project_root,
+                            gitignore,
                             name_pattern,
                             path_pattern,
                             size_filter,
-                            date_filter,
+                            date_filters,
                             current_depth + 1,
                             results,
                             search_count,
@@ -314,12 +452,14 @@ impl FindTool {
                 if !pattern.matches(&path_str) {
                     if metadata.is_dir() && current_depth < self.max_depth.unwrap_or(u32::MAX) {
                         Box::pin(self.search_directory(
+                            context,
                             &path,
                             project_root,
+                            gitignore,
                             name_pattern,
                             path_pattern,
                             size_filter,
-                            date_filter,
+                            date_filters,
                             current_depth + 1,
                             results,
                             search_count,
@@ -338,30 +478,52 @@ impl FindTool {
                 }
             }
             
-            // Apply date filter
-            if let Some(filter) = date_filter {
-                if let Ok(modified) = metadata.modified() {
-                    if !filter.matches(modified) {
-                        if metadata.is_dir() && current_depth < self.max_depth.unwrap_or(u32::MAX) {
-                            Box::pin(self.search_directory(
-                                &path,
-                                project_root,
-                                name_pattern,
-                                path_pattern,
-                                size_filter,
-                                date_filter,
-                                current_depth + 1,
-                                results,
-                                search_count,
-                            )).await?;
-                        }
-                        continue;
-                    }
+            // Apply empty filter; non-empty directories are still searched
+            if self.empty_only && !is_empty(&path, &metadata).await {
+                if metadata.is_dir() && current_depth < self.max_depth.unwrap_or(u32::MAX) {
+                    Box::pin(self.search_directory(
+                        context,
+                        &path,
+                        project_root,
+                        gitignore,
+                        name_pattern,
+                        path_pattern,
+                        size_filter,
+                        date_filters,
+                        current_depth + 1,
+                        results,
+                        search_count,
+                    )).await?;
                 }
+                continue;
+            }
+            
+            // Apply date filters
+            let matches_dates = metadata.modified()
+                .map(|modified| date_filters.iter().all(|filter| filter.matches(modified)))
+                .unwrap_or(true);
+            if !matches_dates {
+                if metadata.is_dir() && current_depth < self.max_depth.unwrap_or(u32::MAX) {
+                    Box::pin(self.search_directory(
+                        context,
+                        &path,
+                        project_root,
+                        gitignore,
+                        name_pattern,
+                        path_pattern,
+                        size_filter,
+                        date_filters,
+                        current_depth + 1,
+                        results,
+                        search_count,
+                    )).await?;
+                }
+                continue;
             }
             
             // Add to results
             results.push(SearchResult {
+                path: path.clone(),
                 relative_path,
                 is_dir: metadata.is_dir(),
                 size: metadata.len(),
@@ -370,12 +532,14 @@ impl FindTool {
             // Recurse into directories
             if metadata.is_dir() && current_depth < self.max_depth.unwrap_or(u32::MAX) {
                 Box::pin(self.search_directory(
+                    context,
                     &path,
                     project_root,
+                    gitignore,
                     name_pattern,
                     path_pattern,
                     size_filter,
-                    date_filter,
+                    date_filters,
                     current_depth + 1,
                     results,
                     search_count,
@@ -388,6 +552,36 @@ impl FindTool {
     }
 }
 
+/// Whether an entry is an empty regular file or a directory without entries
+async fn is_empty(path: &Path, metadata: &std::fs::Metadata) -> bool {
+    if metadata.is_file() {
+        metadata.len() == 0
+    } else if metadata.is_dir() {
+        match fs::read_dir(path).await {
+            Ok(mut entries) => matches!(entries.next_entry().await, Ok(None)),
+            Err(_) => false,
+        }
+    } else {
+        false
+    }
+}
+
+/// File name matcher: a glob from name_pattern or a regex from name_regex
+#[derive(Debug)]
+enum NameFilter {
+    Glob(GlobPattern),
+    Regex(Regex),
+}
+
+impl NameFilter {
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            NameFilter::Glob(pattern) => pattern.matches(name),
+            NameFilter::Regex(regex) => regex.is_match(name),
+        }
+    }
+}
+
 #[derive(Debug)]
 enum SizeFilter {
     GreaterThan(u64),
@@ -501,6 +695,30 @@ fn parse_date_filter(s: &str) -> Result<DateFilter, String> {
     })
 }
 
+/// Parse a newer_than/older_than value: an RFC3339 timestamp, or otherwise a path
+/// inside the project whose modification time becomes the threshold
+fn parse_reference_time(field: &str, value: &str, context: &ToolContext, project_root: &Path) -> Result<SystemTime, CallToolError> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.into());
+    }
+    
+    let path = context.resolve_in_working_dir(value);
+    if project_root.join(&path).symlink_metadata().is_err() {
+        return Err(CallToolError::from(tool_errors::invalid_input(
+            TOOL_NAME,
+            &format!("Invalid {}: '{}' is neither an RFC3339 timestamp nor an existing path", field, value)
+        )));
+    }
+    
+    let reference = resolve_path_for_read(&path, project_root, false, TOOL_NAME)?;
+    std::fs::metadata(&reference)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|e| CallToolError::from(tool_errors::invalid_input(
+            TOOL_NAME,
+            &format!("Failed to read modification time of '{}': {}", value, e)
+        )))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -530,15 +748,22 @@ mod tests {
         let find_tool = FindTool {
             path: ".".to_string(),
             name_pattern: None,
+            name_regex: None,
             path_pattern: None,
             type_filter: "any".to_string(),
             size_filter: None,
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -568,15 +793,22 @@ mod tests {
         let find_tool = FindTool {
             path: ".".to_string(),
             name_pattern: Some("*.txt".to_string()),
+            name_regex: None,
             path_pattern: None,
             type_filter: "file".to_string(),
             size_filter: None,
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -605,15 +837,22 @@ mod tests {
         let find_tool = FindTool {
             path: ".".to_string(),
             name_pattern: None,
+            name_regex: None,
             path_pattern: None,
             type_filter: "file".to_string(),
             size_filter: None,
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -630,15 +869,22 @@ mod tests {
         let find_tool = FindTool {
             path: ".".to_string(),
             name_pattern: None,
+            name_regex: None,
             path_pattern: None,
             type_filter: "directory".to_string(),
             size_filter: None,
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -665,15 +911,22 @@ mod tests {
         let find_tool = FindTool {
             path: ".".to_string(),
             name_pattern: None,
+            name_regex: None,
             path_pattern: None,
             type_filter: "file".to_string(),
             size_filter: Some("+1K".to_string()),
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -703,15 +956,22 @@ mod tests {
         let find_tool = FindTool {
             path: ".".to_string(),
             name_pattern: None,
+            name_regex: None,
             path_pattern: None,
             type_filter: "file".to_string(),
             size_filter: None,
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: Some(1),
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -726,6 +986,93 @@ mod tests {
         }
     }
     
+    #[tokio::test]
+    async fn test_find_by_name_regex() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let project_root = context.get_project_root().unwrap();
+        fs::write(project_root.join("test_parser.rs"), "content").await.unwrap();
+        fs::write(project_root.join("test_parser.rs.bak"), "content").await.unwrap();
+        fs::write(project_root.join("parser_test.rs"), "content").await.unwrap();
+        fs::write(project_root.join("main.py"), "content").await.unwrap();
+        
+        let find_tool = |name_regex: &str| FindTool {
+            path: ".".to_string(),
+            // Ignored because name_regex takes precedence
+            name_pattern: Some("*.py".to_string()),
+            name_regex: Some(name_regex.to_string()),
+            path_pattern: None,
+            type_filter: "file".to_string(),
+            size_filter: None,
+            empty_only: false,
+            date_filter: None,
+            newer_than: None,
+            older_than: None,
+            max_depth: None,
+            follow_symlinks: false,
+            follow_search_path: true,
+            max_results: 1000,
+            output_format: "names".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
+        };
+        
+        let result = find_tool(r"^test_.*\.rs$").call_with_context(&context).await.unwrap();
+        if let CallToolResultContentItem::TextContent(text) = &result.content[0] {
+            assert_eq!(text.text, "test_parser.rs\n");
+        }
+        
+        let result = find_tool(r"^(main|parser_test)\.").call_with_context(&context).await.unwrap();
+        if let CallToolResultContentItem::TextContent(text) = &result.content[0] {
+            assert_eq!(text.text, "main.py\nparser_test.rs\n");
+        }
+        
+        let error = find_tool("(unclosed").call_with_context(&context).await.unwrap_err();
+        assert!(error.to_string().contains("(unclosed"));
+    }
+    
+    #[tokio::test]
+    async fn test_find_respect_gitignore() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let project_root = context.get_project_root().unwrap();
+        fs::write(project_root.join(".gitignore"), "node_modules/\n*.tmp\n").await.unwrap();
+        fs::create_dir_all(project_root.join("node_modules/pkg")).await.unwrap();
+        fs::write(project_root.join("node_modules/pkg/index.js"), "content").await.unwrap();
+        fs::write(project_root.join("app.js"), "content").await.unwrap();
+        fs::write(project_root.join("scratch.tmp"), "content").await.unwrap();
+        
+        let find_tool = FindTool {
+            path: ".".to_string(),
+            name_pattern: Some("*.*".to_string()),
+            name_regex: None,
+            path_pattern: None,
+            type_filter: "file".to_string(),
+            size_filter: None,
+            empty_only: false,
+            date_filter: None,
+            newer_than: None,
+            older_than: None,
+            max_depth: None,
+            follow_symlinks: false,
+            follow_search_path: true,
+            max_results: 1000,
+            output_format: "names".to_string(),
+            respect_gitignore: true,
+            then_chmod: None,
+            null_separated: false,
+        };
+        
+        let result = find_tool.call_with_context(&context).await.unwrap();
+        if let CallToolResultContentItem::TextContent(text) = &result.content[0] {
+            assert!(text.text.contains("app.js"));
+            assert!(text.text.contains(".gitignore"));
+            assert!(!text.text.contains("index.js"));
+            assert!(!text.text.contains("scratch.tmp"));
+        }
+    }
+    
     #[tokio::test]
     async fn test_find_with_max_results() {
         let (context, _temp_dir) = setup_test_context().await;
@@ -740,15 +1087,22 @@ mod tests {
         let find_tool = FindTool {
             path: ".".to_string(),
             name_pattern: None,
+            name_regex: None,
             path_pattern: None,
             type_filter: "file".to_string(),
             size_filter: None,
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 3,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -763,6 +1117,102 @@ mod tests {
         }
     }
     
+    #[tokio::test]
+    async fn test_find_empty_only() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let project_root = context.get_project_root().unwrap();
+        fs::create_dir_all(project_root.join("empty_dir")).await.unwrap();
+        fs::create_dir_all(project_root.join("full_dir/nested_empty")).await.unwrap();
+        fs::write(project_root.join("empty.txt"), "").await.unwrap();
+        fs::write(project_root.join("full.txt"), "content").await.unwrap();
+        fs::write(project_root.join("full_dir/empty.log"), "").await.unwrap();
+        
+        let find_tool = |type_filter: &str, max_depth: Option<u32>| FindTool {
+            path: ".".to_string(),
+            name_pattern: None,
+            name_regex: None,
+            path_pattern: None,
+            type_filter: type_filter.to_string(),
+            size_filter: None,
+            empty_only: true,
+            date_filter: None,
+            newer_than: None,
+            older_than: None,
+            max_depth,
+            follow_symlinks: false,
+            follow_search_path: true,
+            max_results: 1000,
+            output_format: "compact".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
+        };
+        let text = |result: CallToolResult| match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        };
+        
+        let output = text(find_tool("any", None).call_with_context(&context).await.unwrap());
+        assert_eq!(output, "F empty.txt\nD empty_dir\nF full_dir/empty.log\nD full_dir/nested_empty\n");
+        
+        let output = text(find_tool("directory", None).call_with_context(&context).await.unwrap());
+        assert_eq!(output, "D empty_dir\nD full_dir/nested_empty\n");
+        
+        let output = text(find_tool("any", Some(0)).call_with_context(&context).await.unwrap());
+        assert_eq!(output, "F empty.txt\nD empty_dir\n");
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_find_then_chmod() {
+        use std::os::unix::fs::PermissionsExt;
+        let (context, _temp_dir) = setup_test_context().await;
+        let outside_dir = TempDir::new().unwrap();
+        
+        let project_root = context.get_project_root().unwrap();
+        for name in ["a.sh", "b.sh", "notes.txt"] {
+            fs::write(project_root.join(name), "content").await.unwrap();
+            fs::set_permissions(project_root.join(name), std::fs::Permissions::from_mode(0o644)).await.unwrap();
+        }
+        fs::write(outside_dir.path().join("external.sh"), "content").await.unwrap();
+        fs::set_permissions(outside_dir.path().join("external.sh"), std::fs::Permissions::from_mode(0o644)).await.unwrap();
+        std::os::unix::fs::symlink(outside_dir.path().join("external.sh"), project_root.join("link.sh")).unwrap();
+        
+        let find_tool = |then_chmod: &str| FindTool {
+            path: ".".to_string(),
+            name_pattern: Some("*.sh".to_string()),
+            name_regex: None,
+            path_pattern: None,
+            type_filter: "any".to_string(),
+            size_filter: None,
+            empty_only: false,
+            date_filter: None,
+            newer_than: None,
+            older_than: None,
+            max_depth: None,
+            follow_symlinks: false,
+            follow_search_path: true,
+            max_results: 1000,
+            output_format: "names".to_string(),
+            respect_gitignore: false,
+            then_chmod: Some(then_chmod.to_string()),
+            null_separated: false,
+        };
+        
+        let result = find_tool("755").call_with_context(&context).await.unwrap();
+        if let CallToolResultContentItem::TextContent(text) = &result.content[0] {
+            assert!(text.text.contains("Changed permissions to 755 for 2 of 3 matched items (1 outside the project skipped)"));
+        }
+        let mode = |path: std::path::PathBuf| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(project_root.join("a.sh")), 0o755);
+        assert_eq!(mode(project_root.join("b.sh")), 0o755);
+        assert_eq!(mode(project_root.join("notes.txt")), 0o644);
+        assert_eq!(mode(outside_dir.path().join("external.sh")), 0o644);
+        
+        assert!(find_tool("999").call_with_context(&context).await.is_err());
+    }
+    
     #[tokio::test]
     async fn test_find_empty_directory() {
         let (context, _temp_dir) = setup_test_context().await;
@@ -774,15 +1224,22 @@ mod tests {
         let find_tool = FindTool {
             path: "empty_dir".to_string(),
             name_pattern: None,
+            name_regex: None,
             path_pattern: None,
             type_filter: "any".to_string(),
             size_filter: None,
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -804,15 +1261,22 @@ mod tests {
         let find_tool = FindTool {
             path: "nonexistent".to_string(),
             name_pattern: None,
+            name_regex: None,
             path_pattern: None,
             type_filter: "any".to_string(),
             size_filter: None,
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -832,15 +1296,22 @@ mod tests {
         let find_tool = FindTool {
             path: outside_path.to_string_lossy().to_string(),
             name_pattern: None,
+            name_regex: None,
             path_pattern: None,
             type_filter: "any".to_string(),
             size_filter: None,
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -871,15 +1342,22 @@ mod tests {
         let find_tool = FindTool {
             path: ".".to_string(),
             name_pattern: Some("*.rs".to_string()),
+            name_regex: None,
             path_pattern: Some("*/test/*".to_string()),
             type_filter: "file".to_string(),
             size_filter: None,
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -911,15 +1389,22 @@ mod tests {
         let find_tool = FindTool {
             path: ".".to_string(),
             name_pattern: Some("*.txt".to_string()),
+            name_regex: None,
             path_pattern: None,
             type_filter: "file".to_string(),
             size_filter: None,
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "names".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -940,15 +1425,22 @@ mod tests {
         let find_tool = FindTool {
             path: ".".to_string(),
             name_pattern: None,
+            name_regex: None,
             path_pattern: None,
             type_filter: "any".to_string(),
             size_filter: None,
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "compact".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -966,6 +1458,99 @@ mod tests {
         }
     }
     
+    #[tokio::test]
+    async fn test_find_null_separated() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let project_root = context.get_project_root().unwrap();
+        fs::write(project_root.join("with space.txt"), "content").await.unwrap();
+        fs::write(project_root.join("line\nbreak.txt"), "content").await.unwrap();
+        
+        let find = |output_format: &str| FindTool {
+            path: ".".to_string(),
+            name_pattern: Some("*.txt".to_string()),
+            name_regex: None,
+            path_pattern: None,
+            type_filter: "file".to_string(),
+            size_filter: None,
+            empty_only: false,
+            date_filter: None,
+            newer_than: None,
+            older_than: None,
+            max_depth: None,
+            follow_symlinks: false,
+            follow_search_path: true,
+            max_results: 1000,
+            output_format: output_format.to_string(),
+            null_separated: true,
+            respect_gitignore: false,
+            then_chmod: None,
+        };
+        let text = |result: CallToolResult| match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        };
+        
+        let output = text(find("names").call_with_context(&context).await.unwrap());
+        assert_eq!(output, "line\nbreak.txt\0with space.txt\0");
+        let output = text(find("compact").call_with_context(&context).await.unwrap());
+        assert_eq!(output, "F line\nbreak.txt\0F with space.txt\0");
+        
+        // The detailed format is unchanged
+        let output = text(find("detailed").call_with_context(&context).await.unwrap());
+        assert!(!output.contains('\0'));
+        assert!(output.contains("[FILE] with space.txt (7 B)\n"));
+    }
+    
+    #[tokio::test]
+    async fn test_find_newer_and_older_than() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let project_root = context.get_project_root().unwrap();
+        let base = filetime::FileTime::from_unix_time(1_700_000_000, 0);
+        for (name, offset) in [("old.txt", -100), ("Makefile", 0), ("new.txt", 100)] {
+            let path = project_root.join(name);
+            fs::write(&path, "content").await.unwrap();
+            filetime::set_file_mtime(&path, filetime::FileTime::from_unix_time(base.unix_seconds() + offset, 0)).unwrap();
+        }
+        
+        let find_tool = |newer_than: Option<&str>, older_than: Option<&str>| FindTool {
+            path: ".".to_string(),
+            name_pattern: None,
+            name_regex: None,
+            path_pattern: None,
+            type_filter: "file".to_string(),
+            size_filter: None,
+            empty_only: false,
+            date_filter: None,
+            newer_than: newer_than.map(|s| s.to_string()),
+            older_than: older_than.map(|s| s.to_string()),
+            max_depth: None,
+            follow_symlinks: false,
+            follow_search_path: true,
+            max_results: 1000,
+            output_format: "names".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
+        };
+        let names = |result: CallToolResult| match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        };
+        
+        // Reference file: strictly newer than its mtime, like find -newer
+        let result = find_tool(Some("Makefile"), None).call_with_context(&context).await.unwrap();
+        assert_eq!(names(result), "new.txt\n");
+        
+        // RFC3339 timestamp, combined with a reference file
+        let result = find_tool(Some("2023-11-14T22:00:00Z"), Some("Makefile")).call_with_context(&context).await.unwrap();
+        assert_eq!(names(result), "old.txt\n");
+        
+        assert!(find_tool(Some("missing.txt"), None).call_with_context(&context).await.is_err());
+        assert!(find_tool(None, Some("../outside.txt")).call_with_context(&context).await.is_err());
+    }
+    
     #[tokio::test]
     async fn test_find_date_filter() {
         let (context, _temp_dir) = setup_test_context().await;
@@ -983,15 +1568,22 @@ mod tests {
         let find_tool = FindTool {
             path: ".".to_string(),
             name_pattern: None,
+            name_regex: None,
             path_pattern: None,
             type_filter: "file".to_string(),
             size_filter: None,
+            empty_only: false,
             date_filter: Some("-1h".to_string()), // Files modified in last hour
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -1025,15 +1617,22 @@ mod tests {
         let find_tool = FindTool {
             path: ".".to_string(),
             name_pattern: Some("*.rs".to_string()),
+            name_regex: None,
             path_pattern: Some("*/test/*".to_string()),
             type_filter: "file".to_string(),
             size_filter: Some("+1K".to_string()),
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -1058,15 +1657,22 @@ mod tests {
         let find_tool = FindTool {
             path: ".".to_string(),
             name_pattern: None,
+            name_regex: None,
             path_pattern: None,
             type_filter: "invalid".to_string(),
             size_filter: None,
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         // Should still work - "any" behavior for unknown type_filter
@@ -1077,15 +1683,22 @@ mod tests {
         let find_tool = FindTool {
             path: ".".to_string(),
             name_pattern: None,
+            name_regex: None,
             path_pattern: None,
             type_filter: "file".to_string(),
             size_filter: Some("invalid_size".to_string()),
+            empty_only: false,
             date_filter: None,
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
@@ -1097,15 +1710,22 @@ mod tests {
         let find_tool = FindTool {
             path: ".".to_string(),
             name_pattern: None,
+            name_regex: None,
             path_pattern: None,
             type_filter: "file".to_string(),
             size_filter: None,
+            empty_only: false,
             date_filter: Some("invalid_date".to_string()),
+            newer_than: None,
+            older_than: None,
             max_depth: None,
             follow_symlinks: false,
             follow_search_path: true,
             max_results: 1000,
             output_format: "detailed".to_string(),
+            respect_gitignore: false,
+            then_chmod: None,
+            null_separated: false,
         };
         
         let result = find_tool.call_with_context(&context).await;
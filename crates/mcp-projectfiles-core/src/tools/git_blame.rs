@@ -0,0 +1,351 @@
+use crate::config::tool_errors;
+use crate::context::{StatefulTool, ToolContext};
+use crate::tools::utils::{resolve_path_for_read, run_blocking};
+
+use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset};
+use git2::{ErrorCode, Oid, Repository};
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+use similar::{DiffOp, TextDiff};
+use std::collections::HashMap;
+use std::path::Path;
+
+const TOOL_NAME: &str = "git_blame";
+
+#[mcp_tool(
+    name = "git_blame",
+    description = "Show who last changed each line of a git-tracked file. Returns JSON with the line number, commit hash, author, email, date, commit summary and content of every line. Lines changed since the last commit have an all-zero commit.
+Examples: {\"path\": \"src/main.rs\"}, {\"path\": \"src/lib.rs\", \"line_range\": \"40-60\"}"
+)]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct GitBlameTool {
+    /// Path to the file to blame (relative to project root)
+    pub path: String,
+    /// Line range to blame (e.g., "10-20", "15", "100-" for the rest of the file)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_range: Option<String>,
+}
+
+#[derive(Serialize, Debug, Default, PartialEq)]
+struct BlameLine {
+    line: usize,
+    commit: String,
+    author: String,
+    email: String,
+    date: String,
+    summary: String,
+    content: String,
+}
+
+/// Parse a "start-end" range into its first line and optional last line
+fn blame_range(range: &str) -> Result<(usize, Option<usize>), String> {
+    let parse = |part: &str| -> Result<Option<usize>, String> {
+        let part = part.trim();
+        if part.is_empty() {
+            return Ok(None);
+        }
+        match part.parse::<usize>() {
+            Ok(0) => Err("Line numbers start at 1".to_string()),
+            Ok(line) => Ok(Some(line)),
+            Err(_) => Err(format!("Invalid line number: {}", part)),
+        }
+    };
+    let (start, end) = match range.split_once('-') {
+        None => {
+            let line = parse(range)?.ok_or_else(|| "Line range cannot be empty".to_string())?;
+            (line, Some(line))
+        }
+        Some((start, end)) => (parse(start)?.unwrap_or(1), parse(end)?),
+    };
+    match end {
+        Some(end) if start > end => Err(format!(
+            "Invalid line range: start ({}) is greater than end ({})",
+            start, end
+        )),
+        _ => Ok((start, end)),
+    }
+}
+
+/// Author date as RFC 3339 in the author's own time zone
+fn format_date(time: git2::Time) -> String {
+    let offset = FixedOffset::east_opt(time.offset_minutes() * 60)
+        .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+    DateTime::from_timestamp(time.seconds(), 0)
+        .map(|date| date.with_timezone(&offset).to_rfc3339())
+        .unwrap_or_default()
+}
+
+/// Blame the working copy of `file`: lines are blamed in HEAD, and lines changed since the
+/// last commit show up with an all-zero commit like `git blame` does
+fn blame_file(file: &Path, display: &str, range: Option<(usize, Option<usize>)>) -> Result<Vec<BlameLine>, CallToolError> {
+    let failed = |e: git2::Error| CallToolError::from(tool_errors::invalid_input(
+        TOOL_NAME,
+        &format!("git blame failed: {}", e.message()),
+    ));
+    let repo = Repository::discover(file.parent().unwrap_or(file)).map_err(|e| {
+        if e.code() == ErrorCode::NotFound {
+            CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("'{}' is not inside a git repository", display)))
+        } else {
+            failed(e)
+        }
+    })?;
+    let workdir = repo
+        .workdir()
+        .and_then(|dir| dir.canonicalize().ok())
+        .ok_or_else(|| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, "The git repository has no working directory")))?;
+    let relative = file.strip_prefix(&workdir).map_err(|_| {
+        CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("'{}' is not inside a git repository", display)))
+    })?;
+
+    let status = repo.status_file(relative).map_err(failed)?;
+    if status.is_wt_new() || status.is_ignored() {
+        return Err(CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("'{}' is not tracked by git", display))));
+    }
+
+    let content = std::fs::read(file).map_err(|e| {
+        CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read '{}': {}", display, e)))
+    })?;
+    let text = String::from_utf8_lossy(&content);
+    let total = text.lines().count();
+    let (start, end) = range.unwrap_or((1, None));
+    if start > total.max(1) {
+        return Err(CallToolError::from(tool_errors::invalid_input(
+            TOOL_NAME,
+            &format!("Line range starts past the end of '{}', which has {} lines", display, total),
+        )));
+    }
+    let end = end.map_or(total, |end| end.min(total));
+
+    // Map each working-copy line to its line in HEAD; lines without one are uncommitted.
+    // A file only staged so far has no history to blame yet
+    let mut origins = vec![None; total];
+    let blame = if status.is_index_new() {
+        None
+    } else {
+        let head = repo
+            .head()
+            .and_then(|head| head.peel_to_tree())
+            .and_then(|tree| tree.get_path(relative))
+            .and_then(|entry| entry.to_object(&repo))
+            .and_then(|object| object.peel_to_blob())
+            .map_err(failed)?;
+        let committed = String::from_utf8_lossy(head.content());
+        for op in TextDiff::from_lines(committed.as_ref(), text.as_ref()).ops() {
+            if let DiffOp::Equal { old_index, new_index, len } = *op {
+                for offset in 0..len {
+                    origins[new_index + offset] = Some(old_index + offset + 1);
+                }
+            }
+        }
+        Some(repo.blame_file(relative, None).map_err(failed)?)
+    };
+
+    let mut summaries: HashMap<Oid, String> = HashMap::new();
+    let mut lines = Vec::new();
+    for (index, content) in text.lines().enumerate().take(end).skip(start - 1) {
+        let line = index + 1;
+        let mut entry = BlameLine {
+            line,
+            commit: Oid::zero().to_string(),
+            author: "Not Committed Yet".to_string(),
+            content: content.to_string(),
+            ..Default::default()
+        };
+        let hunk = blame.as_ref().zip(origins[index]).and_then(|(blame, origin)| blame.get_line(origin));
+        if let Some(hunk) = hunk {
+            let id = hunk.final_commit_id();
+            let signature = hunk.final_signature();
+            entry.commit = id.to_string();
+            entry.author = String::from_utf8_lossy(signature.name_bytes()).to_string();
+            entry.email = String::from_utf8_lossy(signature.email_bytes()).to_string();
+            entry.date = format_date(signature.when());
+            entry.summary = match summaries.get(&id) {
+                Some(summary) => summary.clone(),
+                None => {
+                    let summary = repo
+                        .find_commit(id)
+                        .ok()
+                        .and_then(|commit| commit.summary().map(str::to_string))
+                        .unwrap_or_default();
+                    summaries.insert(id, summary.clone());
+                    summary
+                }
+            };
+        }
+        lines.push(entry);
+    }
+    Ok(lines)
+}
+
+#[async_trait]
+impl StatefulTool for GitBlameTool {
+    async fn call_with_context(
+        mut self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
+        let project_root = context.get_project_root().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to get project root: {}", e),
+            ))
+        })?;
+        let canonical_root = project_root.canonicalize().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to canonicalize project root: {}", e),
+            ))
+        })?;
+
+        let file = resolve_path_for_read(&self.path, &canonical_root, false, TOOL_NAME)?;
+        if !file.is_file() {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Path is not a file: {}", self.path),
+            )));
+        }
+        let range = self
+            .line_range
+            .as_deref()
+            .map(blame_range)
+            .transpose()
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &e)))?;
+
+        // libgit2 blocks while it walks history, so blame on the blocking pool
+        let blamed = file.clone();
+        let display = self.path.clone();
+        let lines = run_blocking(TOOL_NAME, move || blame_file(&blamed, &display, range)).await?;
+
+        let relative_path = file.strip_prefix(&canonical_root).unwrap_or(&file);
+        let result = serde_json::json!({
+            "path": relative_path.display().to_string(),
+            "lines": lines,
+        });
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                serde_json::to_string_pretty(&result).unwrap(),
+                None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=Test Author", "-c", "user.email=author@example.com"])
+            .args(args)
+            .env("GIT_AUTHOR_DATE", "2024-03-01T12:00:00+01:00")
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    fn blame(path: &str, line_range: Option<&str>) -> GitBlameTool {
+        GitBlameTool {
+            path: path.to_string(),
+            line_range: line_range.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_blame_range() {
+        assert_eq!(blame_range("10-20").unwrap(), (10, Some(20)));
+        assert_eq!(blame_range("15").unwrap(), (15, Some(15)));
+        assert_eq!(blame_range("100-").unwrap(), (100, None));
+        assert_eq!(blame_range("-5").unwrap(), (1, Some(5)));
+        assert!(blame_range("0-3").is_err());
+        assert!(blame_range("9-3").is_err());
+        assert!(blame_range("abc").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_git_blame_reports_authorship() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(repo.clone());
+        std::fs::write(repo.join("lib.rs"), "one\ntwo\nthree\n").unwrap();
+
+        let error = blame("lib.rs", None).call_with_context(&context).await.unwrap_err();
+        assert!(error.to_string().contains("not inside a git repository"), "{}", error);
+
+        git(&repo, &["init", "-q"]);
+        git(&repo, &["add", "lib.rs"]);
+        git(&repo, &["commit", "-q", "-m", "Add lib"]);
+        std::fs::write(repo.join("notes.txt"), "draft").unwrap();
+
+        let result = blame("lib.rs", Some("2-3")).call_with_context(&context).await.unwrap();
+        let json: serde_json::Value = match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => serde_json::from_str(&text.text).unwrap(),
+            _ => panic!("Expected text content"),
+        };
+        let lines = json["lines"].as_array().unwrap();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["line"], 2);
+        assert_eq!(lines[0]["content"], "two");
+        assert_eq!(lines[0]["author"], "Test Author");
+        assert_eq!(lines[0]["email"], "author@example.com");
+        assert_eq!(lines[0]["date"], "2024-03-01T12:00:00+01:00");
+        assert_eq!(lines[0]["summary"], "Add lib");
+        assert_eq!(lines[0]["commit"].as_str().unwrap().len(), 40);
+        assert_eq!(lines[1]["line"], 3);
+
+        let error = blame("notes.txt", None).call_with_context(&context).await.unwrap_err();
+        assert!(error.to_string().contains("not tracked by git"), "{}", error);
+        assert!(blame("../outside.rs", None).call_with_context(&context).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_git_blame_marks_uncommitted_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(repo.clone());
+        std::fs::write(repo.join("lib.rs"), "one\ntwo\nthree\n").unwrap();
+        git(&repo, &["init", "-q"]);
+        git(&repo, &["add", "lib.rs"]);
+        git(&repo, &["commit", "-q", "-m", "Add lib"]);
+        std::fs::write(repo.join("lib.rs"), "one\nTWO\nthree\n").unwrap();
+        std::fs::write(repo.join("staged.rs"), "new\n").unwrap();
+        git(&repo, &["add", "staged.rs"]);
+
+        let lines = |result: CallToolResult| -> Vec<serde_json::Value> {
+            match &result.content[0] {
+                CallToolResultContentItem::TextContent(text) => {
+                    let json: serde_json::Value = serde_json::from_str(&text.text).unwrap();
+                    json["lines"].as_array().unwrap().clone()
+                }
+                _ => panic!("Expected text content"),
+            }
+        };
+        let zero = "0".repeat(40);
+
+        let blamed = lines(blame("lib.rs", None).call_with_context(&context).await.unwrap());
+        assert_eq!(blamed.len(), 3);
+        assert_ne!(blamed[0]["commit"], zero);
+        assert_eq!(blamed[1]["commit"], zero);
+        assert_eq!(blamed[1]["content"], "TWO");
+        assert_eq!(blamed[2]["author"], "Test Author");
+
+        let blamed = lines(blame("staged.rs", None).call_with_context(&context).await.unwrap());
+        assert_eq!(blamed.len(), 1);
+        assert_eq!(blamed[0]["commit"], zero);
+
+        let error = blame("lib.rs", Some("7-")).call_with_context(&context).await.unwrap_err();
+        assert!(error.to_string().contains("past the end"), "{}", error);
+    }
+}
@@ -0,0 +1,378 @@
+use crate::config::tool_errors;
+use crate::context::{StatefulTool, ToolContext};
+use crate::tools::utils::{resolve_path_for_read, run_blocking};
+
+use async_trait::async_trait;
+use git2::{ErrorCode, Repository, Status, StatusOptions};
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const TOOL_NAME: &str = "git_status";
+
+fn default_path() -> String {
+    ".".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_max_results() -> u32 {
+    1000
+}
+
+#[mcp_tool(
+    name = "git_status",
+    description = "Report the git working-tree status of project files. Returns JSON with the branch and each changed path relative to the project root, its status (modified, added, deleted, renamed, copied, type_changed, untracked or conflicted) and whether the change is staged and/or unstaged. Reports repository: false outside a git repository.
+Examples: {}, {\"path\": \"src\", \"include_untracked\": false}"
+)]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct GitStatusTool {
+    /// Directory to report on, relative to the project root (default: ".")
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// Include untracked files (default: true)
+    #[serde(default = "default_true")]
+    pub include_untracked: bool,
+    /// Maximum number of files to return (default: 1000)
+    #[serde(default = "default_max_results")]
+    pub max_results: u32,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct FileStatus {
+    path: String,
+    status: &'static str,
+    staged: bool,
+    unstaged: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    original_path: Option<String>,
+}
+
+/// Status name for the staged (index) side of an entry, if it has one
+fn index_change(status: Status) -> Option<&'static str> {
+    if status.is_index_new() {
+        Some("added")
+    } else if status.is_index_modified() {
+        Some("modified")
+    } else if status.is_index_deleted() {
+        Some("deleted")
+    } else if status.is_index_renamed() {
+        Some("renamed")
+    } else if status.is_index_typechange() {
+        Some("type_changed")
+    } else {
+        None
+    }
+}
+
+/// Status name for the unstaged (working tree) side of an entry, if it has one
+fn worktree_change(status: Status) -> Option<&'static str> {
+    if status.is_wt_modified() {
+        Some("modified")
+    } else if status.is_wt_deleted() {
+        Some("deleted")
+    } else if status.is_wt_renamed() {
+        Some("renamed")
+    } else if status.is_wt_typechange() {
+        Some("type_changed")
+    } else {
+        None
+    }
+}
+
+/// Name of the checked-out branch: the branch an unborn HEAD points at, or "HEAD" when detached
+fn branch_name(repo: &Repository) -> Option<String> {
+    match repo.head() {
+        Ok(head) if head.is_branch() => head.shorthand().map(str::to_string),
+        Ok(_) => Some("HEAD".to_string()),
+        Err(e) if e.code() == ErrorCode::UnbornBranch => repo
+            .find_reference("HEAD")
+            .ok()?
+            .symbolic_target()?
+            .strip_prefix("refs/heads/")
+            .map(str::to_string),
+        Err(_) => None,
+    }
+}
+
+/// Status of the repository containing the listed directory
+struct RepositoryStatus {
+    root: PathBuf,
+    branch: Option<String>,
+    /// Each change's absolute path with its status, whose paths are relative to `root`
+    entries: Vec<(PathBuf, FileStatus)>,
+}
+
+/// Status of the repository around `target`, limited to paths under it.
+/// None when `target` is not inside a git repository
+fn repository_status(target: &Path, include_untracked: bool) -> Result<Option<RepositoryStatus>, CallToolError> {
+    let failed = |e: git2::Error| CallToolError::from(tool_errors::invalid_input(
+        TOOL_NAME,
+        &format!("git status failed: {}", e.message()),
+    ));
+    let repo = match Repository::discover(target) {
+        Ok(repo) => repo,
+        Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+        Err(e) => return Err(failed(e)),
+    };
+    let repo_root = repo
+        .workdir()
+        .and_then(|dir| dir.canonicalize().ok())
+        .ok_or_else(|| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, "The git repository has no working directory")))?;
+
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(include_untracked)
+        .recurse_untracked_dirs(include_untracked)
+        .include_ignored(false)
+        .renames_head_to_index(true);
+    // The pathspec keeps libgit2 itself inside the requested directory
+    let prefix = target.strip_prefix(&repo_root).unwrap_or(Path::new(""));
+    if !prefix.as_os_str().is_empty() {
+        options.pathspec(prefix);
+    }
+    let statuses = repo.statuses(Some(&mut options)).map_err(failed)?;
+
+    let mut entries = Vec::new();
+    for entry in statuses.iter() {
+        let status = entry.status();
+        // A staged rename is listed under its new path, with the path it came from
+        let renamed = entry.head_to_index().filter(|_| status.is_index_renamed());
+        let path = renamed
+            .as_ref()
+            .and_then(|delta| delta.new_file().path())
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_else(|| String::from_utf8_lossy(entry.path_bytes()).to_string());
+        let original_path = renamed
+            .as_ref()
+            .and_then(|delta| delta.old_file().path())
+            .map(|path| path.to_string_lossy().to_string());
+
+        let (status_name, staged, unstaged) = if status.is_ignored() {
+            continue;
+        } else if status.is_conflicted() {
+            ("conflicted", false, true)
+        } else if status.is_wt_new() {
+            ("untracked", false, true)
+        } else {
+            let staged = index_change(status);
+            let unstaged = worktree_change(status);
+            match staged.or(unstaged) {
+                Some(name) => (name, staged.is_some(), unstaged.is_some()),
+                None => continue,
+            }
+        };
+
+        entries.push((
+            repo_root.join(&path),
+            FileStatus {
+                path,
+                status: status_name,
+                staged,
+                unstaged,
+                original_path,
+            },
+        ));
+    }
+    // Like git status, tracked changes come before untracked files
+    entries.sort_by_key(|(_, file)| file.status == "untracked");
+
+    Ok(Some(RepositoryStatus {
+        branch: branch_name(&repo),
+        root: repo_root,
+        entries,
+    }))
+}
+
+fn json_result(value: serde_json::Value) -> CallToolResult {
+    CallToolResult {
+        content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+            serde_json::to_string_pretty(&value).unwrap(),
+            None,
+        ))],
+        is_error: Some(false),
+        meta: None,
+    }
+}
+
+#[async_trait]
+impl StatefulTool for GitStatusTool {
+    async fn call_with_context(
+        mut self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
+        let project_root = context.get_project_root().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to get project root: {}", e),
+            ))
+        })?;
+        let canonical_root = project_root.canonicalize().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to canonicalize project root: {}", e),
+            ))
+        })?;
+
+        let target = resolve_path_for_read(&self.path, &canonical_root, false, TOOL_NAME)?;
+        if !target.is_dir() {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Path is not a directory: {}", self.path),
+            )));
+        }
+
+        // libgit2 blocks while it scans the working tree, so run it on the blocking pool
+        let scanned = target.clone();
+        let include_untracked = self.include_untracked;
+        let Some(RepositoryStatus { root: repo_root, branch, entries }) =
+            run_blocking(TOOL_NAME, move || repository_status(&scanned, include_untracked)).await?
+        else {
+            return Ok(json_result(serde_json::json!({
+                "repository": false,
+                "files": [],
+            })));
+        };
+
+        // Report paths relative to the project root and drop anything outside it
+        let relative = |path: &str| -> Option<String> {
+            repo_root
+                .join(path)
+                .strip_prefix(&canonical_root)
+                .ok()
+                .map(|path| path.display().to_string())
+        };
+        let mut files: Vec<FileStatus> = entries
+            .into_iter()
+            .filter(|(absolute, _)| absolute.starts_with(&target))
+            .filter_map(|(_, mut file)| {
+                file.path = relative(&file.path)?;
+                file.original_path = file.original_path.as_deref().and_then(relative);
+                Some(file)
+            })
+            .collect();
+
+        let truncated = files.len() > self.max_results as usize;
+        files.truncate(self.max_results as usize);
+
+        let mut result = serde_json::json!({
+            "repository": true,
+            "branch": branch,
+            "files": files,
+        });
+        if truncated {
+            result["truncated"] = serde_json::json!(true);
+        }
+        Ok(json_result(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com", "-c", "init.defaultBranch=main"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(status.status.success(), "{}", String::from_utf8_lossy(&status.stderr));
+    }
+
+    async fn status_json(context: &ToolContext, path: &str) -> serde_json::Value {
+        let tool = GitStatusTool {
+            path: path.to_string(),
+            include_untracked: true,
+            max_results: 1000,
+        };
+        let result = tool.call_with_context(context).await.unwrap();
+        match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => serde_json::from_str(&text.text).unwrap(),
+            _ => panic!("Expected text content"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_git_status_reports_changes_within_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(repo.clone());
+
+        let status = status_json(&context, ".").await;
+        assert_eq!(status["repository"], false);
+
+        git(&repo, &["init", "-q"]);
+        std::fs::create_dir(repo.join("app")).unwrap();
+        std::fs::write(repo.join("app/tracked.rs"), "fn a() {}").unwrap();
+        std::fs::write(repo.join("outside.rs"), "fn b() {}").unwrap();
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-q", "-m", "initial"]);
+
+        std::fs::write(repo.join("app/tracked.rs"), "fn a() { changed }").unwrap();
+        std::fs::write(repo.join("app/staged.rs"), "fn c() {}").unwrap();
+        git(&repo, &["add", "app/staged.rs"]);
+        std::fs::write(repo.join("app/new.txt"), "untracked").unwrap();
+        std::fs::write(repo.join("outside.rs"), "fn b() { changed }").unwrap();
+
+        let status = status_json(&context, ".").await;
+        assert_eq!(status["repository"], true);
+        assert_eq!(status["branch"], "main");
+        let files = status["files"].as_array().unwrap();
+        assert_eq!(files.len(), 4);
+
+        // A project root inside the repository only sees its own files, relative to itself
+        let context = ToolContext::with_project_root(repo.join("app"));
+        let status = status_json(&context, ".").await;
+        assert_eq!(
+            status["files"],
+            serde_json::json!([
+                { "path": "staged.rs", "status": "added", "staged": true, "unstaged": false },
+                { "path": "tracked.rs", "status": "modified", "staged": false, "unstaged": true },
+                { "path": "new.txt", "status": "untracked", "staged": false, "unstaged": true },
+            ])
+        );
+
+        let tool = GitStatusTool { path: "..".to_string(), include_untracked: true, max_results: 1000 };
+        assert!(tool.call_with_context(&context).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_git_status_renames_and_branches() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(repo.clone());
+
+        // No commits yet: the branch HEAD points at is still reported
+        git(&repo, &["init", "-q"]);
+        std::fs::write(repo.join("old.rs"), "fn a() {}\n".repeat(20)).unwrap();
+        let status = status_json(&context, ".").await;
+        assert_eq!(status["branch"], "main");
+        assert_eq!(status["files"][0]["status"], "untracked");
+
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-q", "-m", "initial"]);
+        git(&repo, &["mv", "old.rs", "new.rs"]);
+        std::fs::write(repo.join("new.rs"), "fn a() {}\n".repeat(21)).unwrap();
+        let status = status_json(&context, ".").await;
+        assert_eq!(
+            status["files"],
+            serde_json::json!([
+                { "path": "new.rs", "status": "renamed", "staged": true, "unstaged": true, "original_path": "old.rs" },
+            ])
+        );
+
+        git(&repo, &["checkout", "-q", "--detach"]);
+        assert_eq!(status_json(&context, ".").await["branch"], "HEAD");
+    }
+}
@@ -1,8 +1,8 @@
 use crate::config::{format_tool_error, tool_errors};
 use crate::context::{StatefulTool, ToolContext};
-use crate::tools::utils::{format_count, resolve_path_for_read};
+use crate::tools::utils::{BoundedOutput, format_count, resolve_path_for_read, GitignoreFilter, GlobPattern};
 use async_trait::async_trait;
-use glob::Pattern;
+use memmap2::Mmap;
 use regex::{Regex, RegexBuilder};
 use rust_mcp_schema::{
     CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
@@ -10,18 +10,168 @@ use rust_mcp_schema::{
 use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use tokio::fs;
 use tokio::io::AsyncReadExt;
+use tokio::task::JoinSet;
 
 const TOOL_NAME: &str = "grep";
 
+/// Files at least this large are memory-mapped rather than read into memory
+const MMAP_THRESHOLD: u64 = 8 * 1024 * 1024;
+
+/// Bytes checked for control characters before a file is treated as binary
+pub(crate) const BINARY_SAMPLE_SIZE: usize = 512;
+
+/// Too many control characters in the sample means binary (tab, LF and CR are allowed)
+pub(crate) fn is_binary_sample(sample: &[u8]) -> bool {
+    let non_text_bytes = sample
+        .iter()
+        .filter(|&&b| b < 32 && b != 9 && b != 10 && b != 13)
+        .count();
+    non_text_bytes > BINARY_SAMPLE_SIZE / 10
+}
+
+/// The contents of a file being searched, mapped for large files and read for small ones
+enum FileBytes {
+    Mapped(Mmap),
+    Buffered(Vec<u8>),
+}
+
+impl std::ops::Deref for FileBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            FileBytes::Mapped(map) => map,
+            FileBytes::Buffered(buffer) => buffer,
+        }
+    }
+}
+
+/// Load a file for searching, or None if it looks binary
+async fn load_text_file(path: &Path) -> std::io::Result<Option<FileBytes>> {
+    let file = std::fs::File::open(path)?;
+
+    if file.metadata()?.len() >= MMAP_THRESHOLD {
+        // SAFETY: the map is read-only and dropped once this file has been searched. As with
+        // other mmap-based search tools, a file truncated by another process mid-search can fault.
+        if let Ok(map) = unsafe { Mmap::map(&file) } {
+            if is_binary_sample(&map[..BINARY_SAMPLE_SIZE.min(map.len())]) {
+                return Ok(None);
+            }
+            return Ok(Some(FileBytes::Mapped(map)));
+        }
+    }
+
+    // Check a sample before reading the rest so binary files are skipped cheaply
+    let mut file = fs::File::from_std(file);
+    let mut buffer = vec![0; BINARY_SAMPLE_SIZE];
+    let bytes_read = file.read(&mut buffer).await?;
+    buffer.truncate(bytes_read);
+    if is_binary_sample(&buffer) {
+        return Ok(None);
+    }
+    file.read_to_end(&mut buffer).await?;
+    Ok(Some(FileBytes::Buffered(buffer)))
+}
+
+/// Collect the files under `dir_path` that pass the include/exclude and gitignore filters,
+/// skipping hidden directories. Also used by sed
+pub(crate) async fn collect_files(
+    dir_path: &Path,
+    include_pattern: &Option<GlobPattern>,
+    exclude_pattern: &Option<GlobPattern>,
+    gitignore: &GitignoreFilter,
+    files: &mut Vec<PathBuf>,
+    context: &ToolContext,
+    tool_name: &str,
+) -> Result<(), CallToolError> {
+    let mut entries = fs::read_dir(dir_path).await.map_err(|e| {
+        CallToolError::from(tool_errors::invalid_input(
+            tool_name,
+            &format!("Failed to read directory: {}", e),
+        ))
+    })?;
+
+    loop {
+        context.check_cancelled(tool_name)?;
+        let entry = match entries.next_entry().await {
+            Ok(Some(entry)) => entry,
+            Ok(None) => break,
+            Err(e) => {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    tool_name,
+                    &format!("Failed to read directory entry: {}", e),
+                )));
+            }
+        };
+
+        let entry_path = entry.path();
+        let file_type = match entry.file_type().await {
+            Ok(ft) => ft,
+            Err(e) => {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    tool_name,
+                    &format!("Failed to get file type: {}", e),
+                )));
+            }
+        };
+
+        if gitignore.is_ignored(&entry_path, file_type.is_dir()) {
+            continue;
+        }
+
+        if file_type.is_dir() {
+            // Skip hidden directories
+            if entry_path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.')) {
+                continue;
+            }
+
+            // Recursively collect subdirectories
+            Box::pin(collect_files(
+                &entry_path,
+                include_pattern,
+                exclude_pattern,
+                gitignore,
+                files,
+                context,
+                tool_name,
+            ))
+            .await?;
+        } else if file_type.is_file() {
+            // Check include/exclude patterns
+            if let Some(file_name) = entry_path.file_name() {
+                let file_name_str = file_name.to_string_lossy();
+
+                if include_pattern.as_ref().is_some_and(|include| !include.matches(&file_name_str)) {
+                    continue;
+                }
+
+                if exclude_pattern.as_ref().is_some_and(|exclude| exclude.matches(&file_name_str)) {
+                    continue;
+                }
+            }
+
+            files.push(entry_path);
+        }
+    }
+
+    Ok(())
+}
+
 #[mcp_tool(
     name = "grep",
     description = "Search patterns in text files with regex, context lines, and filtering.
 
 Examples:
 - {\"pattern\": \"TODO\", \"path\": \"src/\"}
-- {\"patterns\": [\"TODO\", \"FIXME\"], \"include\": \"*.rs\", \"case\": \"insensitive\"}"
+- {\"patterns\": [\"TODO\", \"FIXME\"], \"include\": \"*.rs\", \"case\": \"insensitive\"}
+- {\"pattern\": \"TODO\", \"count_only\": true}
+- {\"pattern\": \"deprecated\", \"list_files_only\": true}
+- {\"pattern\": \"foo.bar(x)\", \"fixed_string\": true}
+- {\"pattern\": \"BEGIN.*?END\", \"multiline\": true}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct GrepTool {
@@ -34,10 +184,10 @@ pub struct GrepTool {
     /// File or directory to search in (optional, default: "." - current directory)
     #[serde(default = "default_path")]
     pub path: String,
-    /// File pattern to include, e.g., "*.rs", "*.txt" (optional)
+    /// File pattern to include, e.g., "*.rs", "*.{rs,toml}" (optional)
     #[serde(default)]
     pub include: Option<String>,
-    /// File pattern to exclude, e.g., "*.log", "*.tmp" (optional)
+    /// File pattern to exclude, e.g., "*.log", "*.{tmp,bak}" (optional)
     #[serde(default)]
     pub exclude: Option<String>,
     /// Case sensitivity for pattern matching: "sensitive" or "insensitive" (optional, default: "sensitive")
@@ -61,6 +211,22 @@ pub struct GrepTool {
     /// Invert match - show lines that do NOT match the pattern (optional, default: false)
     #[serde(default)]
     pub invert_match: bool,
+    /// Only report the number of matching lines per file plus a total; line numbers and context are ignored (optional, default: false)
+    #[serde(default)]
+    pub count_only: bool,
+    /// Only list the relative paths of files containing a match, one per line (optional, default: false)
+    #[serde(default)]
+    pub list_files_only: bool,
+    /// Treat pattern/patterns as literal strings instead of regular expressions (optional, default: false)
+    #[serde(default)]
+    pub fixed_string: bool,
+    /// Match across line boundaries: the whole file is searched at once with `.` matching newlines,
+    /// and each match is reported at its starting line. Cannot be combined with invert_match (optional, default: false)
+    #[serde(default)]
+    pub multiline: bool,
+    /// Skip files and directories ignored by the project's .gitignore or .git/info/exclude (optional, default: false)
+    #[serde(default)]
+    pub respect_gitignore: bool,
 }
 
 fn default_path() -> String {
@@ -83,6 +249,32 @@ fn default_follow_search_path() -> bool {
     true
 }
 
+/// How lines are tested against the requested pattern(s)
+enum LineMatcher {
+    Regex(Regex),
+    /// Literal substrings, matched with OR logic. Stored lowercased when case-insensitive.
+    Fixed {
+        needles: Vec<String>,
+        case_insensitive: bool,
+    },
+}
+
+impl LineMatcher {
+    fn is_match(&self, line: &str) -> bool {
+        match self {
+            LineMatcher::Regex(regex) => regex.is_match(line),
+            LineMatcher::Fixed { needles, case_insensitive } => {
+                if *case_insensitive {
+                    let line = line.to_lowercase();
+                    needles.iter().any(|needle| line.contains(needle.as_str()))
+                } else {
+                    needles.iter().any(|needle| line.contains(needle.as_str()))
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct Match {
     file_path: PathBuf,
@@ -95,9 +287,11 @@ struct Match {
 #[async_trait]
 impl StatefulTool for GrepTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
         let project_root = context.get_project_root().map_err(|e| {
             CallToolError::new(std::io::Error::new(
                 std::io::ErrorKind::Other,
@@ -124,6 +318,13 @@ impl StatefulTool for GrepTool {
             )));
         }
 
+        if self.count_only && self.list_files_only {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                "count_only and list_files_only cannot be used together",
+            )));
+        }
+
         // Use the utility function to resolve search path with symlink support
         let canonical_search_path = resolve_path_for_read(
             &self.path,
@@ -139,42 +340,81 @@ impl StatefulTool for GrepTool {
             )));
         }
 
-        // Compile regex pattern(s)
-        let regex = if let Some(patterns) = &self.patterns {
-            if patterns.is_empty() {
-                return Err(CallToolError::from(tool_errors::invalid_input(
-                    TOOL_NAME,
-                    "patterns array cannot be empty",
-                )));
+        if self.multiline && self.invert_match {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                "multiline and invert_match cannot be used together",
+            )));
+        }
+
+        if self.patterns.as_ref().is_some_and(|p| p.is_empty()) {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                "patterns array cannot be empty",
+            )));
+        }
+
+        // Literal patterns are escaped into a regex when they must be matched across lines
+        let escape = |p: &String| {
+            if self.fixed_string {
+                regex::escape(p)
+            } else {
+                p.clone()
             }
+        };
+
+        // Compile regex pattern(s), or collect literal strings in fixed-string mode
+        let matcher = if self.fixed_string && !self.multiline {
+            let case_insensitive = self.case == "insensitive";
+            let needles = self
+                .patterns
+                .clone()
+                .or_else(|| self.pattern.clone().map(|p| vec![p]))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|p| if case_insensitive { p.to_lowercase() } else { p })
+                .collect();
+            LineMatcher::Fixed {
+                needles,
+                case_insensitive,
+            }
+        } else if let Some(patterns) = &self.patterns {
             // Combine multiple patterns with OR logic
             let combined_pattern = patterns
                 .iter()
-                .map(|p| format!("({})", p))
+                .map(|p| format!("({})", escape(p)))
                 .collect::<Vec<_>>()
                 .join("|");
-            RegexBuilder::new(&combined_pattern)
-                .case_insensitive(self.case == "insensitive")
-                .build()
-                .map_err(|e| {
-                    CallToolError::from(tool_errors::pattern_error(
-                        TOOL_NAME,
-                        &combined_pattern,
-                        &e.to_string(),
-                    ))
-                })?
+            LineMatcher::Regex(
+                RegexBuilder::new(&combined_pattern)
+                    .case_insensitive(self.case == "insensitive")
+                    .multi_line(self.multiline)
+                    .dot_matches_new_line(self.multiline)
+                    .build()
+                    .map_err(|e| {
+                        CallToolError::from(tool_errors::pattern_error(
+                            TOOL_NAME,
+                            &combined_pattern,
+                            &e.to_string(),
+                        ))
+                    })?,
+            )
         } else if let Some(pattern) = &self.pattern {
             // Use single pattern
-            RegexBuilder::new(pattern)
-                .case_insensitive(self.case == "insensitive")
-                .build()
-                .map_err(|e| {
-                    CallToolError::from(tool_errors::pattern_error(
-                        TOOL_NAME,
-                        pattern,
-                        &e.to_string(),
-                    ))
-                })?
+            LineMatcher::Regex(
+                RegexBuilder::new(&escape(pattern))
+                    .case_insensitive(self.case == "insensitive")
+                    .multi_line(self.multiline)
+                    .dot_matches_new_line(self.multiline)
+                    .build()
+                    .map_err(|e| {
+                        CallToolError::from(tool_errors::pattern_error(
+                            TOOL_NAME,
+                            pattern,
+                            &e.to_string(),
+                        ))
+                    })?,
+            )
         } else {
             // This should never happen due to validation above
             return Err(CallToolError::from(tool_errors::invalid_input(
@@ -187,7 +427,7 @@ impl StatefulTool for GrepTool {
         let include_pattern = self
             .include
             .as_ref()
-            .map(|p| Pattern::new(p))
+            .map(|p| GlobPattern::new(p))
             .transpose()
             .map_err(|e| {
                 CallToolError::from(tool_errors::pattern_error(
@@ -200,7 +440,7 @@ impl StatefulTool for GrepTool {
         let exclude_pattern = self
             .exclude
             .as_ref()
-            .map(|p| Pattern::new(p))
+            .map(|p| GlobPattern::new(p))
             .transpose()
             .map_err(|e| {
                 CallToolError::from(tool_errors::pattern_error(
@@ -210,25 +450,27 @@ impl StatefulTool for GrepTool {
                 ))
             })?;
 
-        // Collect all matches
-        let mut all_matches = Vec::new();
-        let mut files_searched = 0;
-
-        if canonical_search_path.is_file() {
-            self.search_file(&canonical_search_path, &regex, &mut all_matches)
+        // Collect all matches, searching directories in path order
+        let (all_matches, files_searched) = if canonical_search_path.is_file() {
+            let mut all_matches = Vec::new();
+            self.search_file(&canonical_search_path, &matcher, &mut all_matches)
                 .await?;
-            files_searched = 1;
+            (all_matches, 1)
         } else {
-            self.search_directory(
+            let mut files = Vec::new();
+            collect_files(
                 &canonical_search_path,
-                &regex,
                 &include_pattern,
                 &exclude_pattern,
-                &mut all_matches,
-                &mut files_searched,
+                &GitignoreFilter::new(&project_root, self.respect_gitignore),
+                &mut files,
+                context,
+                TOOL_NAME,
             )
             .await?;
-        }
+            files.sort();
+            self.search_files(files, matcher, context).await?
+        };
 
         // Check if results were limited
         let was_truncated = self.max_results > 0 && all_matches.len() == self.max_results as usize;
@@ -251,8 +493,19 @@ impl StatefulTool for GrepTool {
         };
 
         // Format output
-        let mut output = String::new();
-        if all_matches.is_empty() {
+        let mut output = BoundedOutput::new();
+        if self.list_files_only && !all_matches.is_empty() {
+            // Each file contributes at most one match in this mode
+            for (i, m) in all_matches.iter().enumerate() {
+                let relative_path = m.file_path.strip_prefix(&project_root).unwrap_or(&m.file_path);
+                let separator = if i > 0 { "\n" } else { "" };
+                if !output.push_str(&format!("{}{}", separator, relative_path.display())) {
+                    break;
+                }
+            }
+        } else if self.count_only {
+            output.push_str(&self.format_counts(&all_matches, &project_root, &pattern_desc, files_searched, was_truncated));
+        } else if all_matches.is_empty() {
             output.push_str(&format!(
                 "No matches found for {} in {} searched.",
                 pattern_desc,
@@ -319,7 +572,10 @@ impl StatefulTool for GrepTool {
                 }
 
                 if i < all_matches.len() - 1 {
-                    output.push('\n');
+                    output.push_str("\n");
+                }
+                if output.is_truncated() {
+                    break;
                 }
             }
 
@@ -330,7 +586,7 @@ impl StatefulTool for GrepTool {
 
         Ok(CallToolResult {
             content: vec![CallToolResultContentItem::TextContent(TextContent::new(
-                output, None,
+                output.into_string(), None,
             ))],
             is_error: Some(false),
             meta: None,
@@ -339,150 +595,159 @@ impl StatefulTool for GrepTool {
 }
 
 impl GrepTool {
-    async fn search_directory(
+    fn format_counts(
         &self,
-        dir_path: &Path,
-        regex: &Regex,
-        include_pattern: &Option<Pattern>,
-        exclude_pattern: &Option<Pattern>,
-        all_matches: &mut Vec<Match>,
-        files_searched: &mut usize,
-    ) -> Result<(), CallToolError> {
-        let mut entries = fs::read_dir(dir_path).await.map_err(|e| {
-            CallToolError::from(tool_errors::invalid_input(
-                TOOL_NAME,
-                &format!("Failed to read directory: {}", e),
-            ))
-        })?;
-
-        loop {
-            let entry = match entries.next_entry().await {
-                Ok(Some(entry)) => entry,
-                Ok(None) => break,
-                Err(e) => {
-                    return Err(CallToolError::from(tool_errors::invalid_input(
-                        TOOL_NAME,
-                        &format!("Failed to read directory entry: {}", e),
-                    )));
-                }
-            };
+        all_matches: &[Match],
+        project_root: &Path,
+        pattern_desc: &str,
+        files_searched: usize,
+        was_truncated: bool,
+    ) -> String {
+        // Matches arrive grouped by file, so consecutive runs give the per-file counts
+        let mut file_counts: Vec<(&Path, usize)> = Vec::new();
+        for m in all_matches {
+            match file_counts.last_mut() {
+                Some((path, count)) if *path == m.file_path.as_path() => *count += 1,
+                _ => file_counts.push((m.file_path.as_path(), 1)),
+            }
+        }
 
-            let entry_path = entry.path();
-            let file_type = match entry.file_type().await {
-                Ok(ft) => ft,
-                Err(e) => {
-                    return Err(CallToolError::from(tool_errors::invalid_input(
-                        TOOL_NAME,
-                        &format!("Failed to get file type: {}", e),
-                    )));
-                }
-            };
+        let mut output = format!(
+            "Match counts for {} in {}:\n",
+            pattern_desc,
+            format_count(files_searched, "file", "files")
+        );
+        for (path, count) in &file_counts {
+            let relative_path = path.strip_prefix(project_root).unwrap_or(path);
+            output.push_str(&format!("\n{}:{}", relative_path.display(), count));
+        }
+        output.push_str(&format!(
+            "\n\nTotal: {} in {}",
+            format_count(all_matches.len(), "match", "matches"),
+            format_count(file_counts.len(), "file", "files")
+        ));
+
+        if was_truncated {
+            output.push_str(&format!("\n\n[counting stopped at {} results]", self.max_results));
+        }
 
-            if file_type.is_dir() {
-                // Skip hidden directories
-                if let Some(name) = entry_path.file_name() {
-                    if name.to_string_lossy().starts_with('.') {
-                        continue;
-                    }
-                }
+        output
+    }
 
-                // Recursively search subdirectories
-                Box::pin(self.search_directory(
-                    &entry_path,
-                    regex,
-                    include_pattern,
-                    exclude_pattern,
-                    all_matches,
-                    files_searched,
-                ))
-                .await?;
-            } else if file_type.is_file() {
-                // Check include/exclude patterns
-                if let Some(file_name) = entry_path.file_name() {
-                    let file_name_str = file_name.to_string_lossy();
-
-                    if let Some(include) = include_pattern {
-                        if !include.matches(&file_name_str) {
-                            continue;
-                        }
-                    }
+    /// Search `files` on a bounded pool of tasks, returning the matches in the order of `files`
+    /// and how many files were searched.
+    ///
+    /// Files are handed out in order and no new ones are started once `max_results` matches
+    /// have been found, so every file a serial search would reach is searched. The results are
+    /// then cut off at the same file and match as a serial search, keeping the output identical.
+    async fn search_files(
+        &self,
+        files: Vec<PathBuf>,
+        matcher: LineMatcher,
+        context: &ToolContext,
+    ) -> Result<(Vec<Match>, usize), CallToolError> {
+        let workers = std::thread::available_parallelism().map_or(4, |n| n.get());
+        let max_results = self.max_results as usize;
+        let tool = Arc::new(self.clone());
+        let matcher = Arc::new(matcher);
+        let found = Arc::new(AtomicUsize::new(0));
+
+        let mut results: Vec<Option<Vec<Match>>> = vec![None; files.len()];
+        let mut tasks = JoinSet::new();
+        let mut next = 0;
+        let mut completed = 0;
+        loop {
+            // Dropping the JoinSet on return aborts the searches still running
+            context.check_cancelled(TOOL_NAME)?;
+            while tasks.len() < workers
+                && next < files.len()
+                && (max_results == 0 || found.load(Ordering::Relaxed) < max_results)
+            {
+                let (tool, matcher, found) = (tool.clone(), matcher.clone(), found.clone());
+                let (index, path) = (next, files[next].clone());
+                tasks.spawn(async move {
+                    // CallToolError is not Send, so errors cross the task boundary as their message
+                    let mut matches = Vec::new();
+                    tool.search_file(&path, &matcher, &mut matches).await.map_err(|e| e.to_string())?;
+                    found.fetch_add(matches.len(), Ordering::Relaxed);
+                    Ok::<_, String>((index, matches))
+                });
+                next += 1;
+            }
 
-                    if let Some(exclude) = exclude_pattern {
-                        if exclude.matches(&file_name_str) {
-                            continue;
-                        }
-                    }
-                }
+            let Some(joined) = tasks.join_next().await else {
+                break;
+            };
+            let (index, matches) = joined
+                .map_err(|e| {
+                    CallToolError::from(tool_errors::invalid_input(
+                        TOOL_NAME,
+                        &format!("Search task failed: {}", e),
+                    ))
+                })?
+                .map_err(|message| CallToolError::new(std::io::Error::other(message)))?;
+            results[index] = Some(matches);
 
-                // Search the file
-                self.search_file(&entry_path, regex, all_matches).await?;
-                *files_searched += 1;
+            completed += 1;
+            context.report_progress(completed, Some(files.len() as u64), || {
+                format!("Searched {} of {} files", completed, files.len())
+            });
+        }
 
-                // Stop if we've hit the max results (0 means no limit)
-                if self.max_results > 0 && all_matches.len() >= self.max_results as usize {
-                    break;
-                }
+        let mut all_matches = Vec::new();
+        let mut files_searched = 0;
+        for matches in results.into_iter().map_while(|matches| matches) {
+            all_matches.extend(matches);
+            files_searched += 1;
+
+            // Stop if we've hit the max results (0 means no limit)
+            if max_results > 0 && all_matches.len() >= max_results {
+                all_matches.truncate(max_results);
+                break;
             }
         }
 
-        Ok(())
+        Ok((all_matches, files_searched))
     }
 
     async fn search_file(
         &self,
         file_path: &Path,
-        regex: &Regex,
+        matcher: &LineMatcher,
         all_matches: &mut Vec<Match>,
     ) -> Result<(), CallToolError> {
-        // Quick binary file check
-        let _file = fs::File::open(file_path).await.map_err(|e| {
-            CallToolError::from(tool_errors::invalid_input(
-                TOOL_NAME,
-                &format!("Failed to open file: {}", e),
-            ))
-        })?;
-
-        // Check if file is binary by reading first 512 bytes
-        let mut buffer = [0; 512];
-        let mut file_for_check = fs::File::open(file_path).await.map_err(|e| {
-            CallToolError::from(tool_errors::invalid_input(
-                TOOL_NAME,
-                &format!("Failed to open file: {}", e),
-            ))
-        })?;
-        let bytes_read = file_for_check.read(&mut buffer).await.map_err(|e| {
+        let bytes = match load_text_file(file_path).await {
+            Ok(Some(bytes)) => bytes,
+            // Skip binary files silently
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    TOOL_NAME,
+                    &format!("Failed to read file: {}", e),
+                )));
+            }
+        };
+        let content = std::str::from_utf8(&bytes).map_err(|e| {
             CallToolError::from(tool_errors::invalid_input(
                 TOOL_NAME,
                 &format!("Failed to read file: {}", e),
             ))
         })?;
 
-        if bytes_read > 0 {
-            let non_text_bytes = buffer[..bytes_read]
-                .iter()
-                .filter(|&&b| b < 32 && b != 9 && b != 10 && b != 13) // Allow tab, LF, CR
-                .count();
+        // Lines borrow from the file contents so large files are not copied
+        let all_lines: Vec<&str> = content.lines().collect();
 
-            if non_text_bytes > buffer.len() / 10 {
-                // Skip binary files silently
-                return Ok(());
+        if self.multiline {
+            if let LineMatcher::Regex(regex) = matcher {
+                self.search_content_multiline(file_path, content, &all_lines, regex, all_matches);
             }
+            return Ok(());
         }
 
-        // Read all lines at once to support context
-        let content = tokio::fs::read_to_string(&file_path).await.map_err(|e| {
-            CallToolError::from(tool_errors::invalid_input(
-                TOOL_NAME,
-                &format!("Failed to read file: {}", e),
-            ))
-        })?;
-
-        let all_lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
-
         for (line_idx, line) in all_lines.iter().enumerate() {
             let line_number = line_idx + 1;
 
-            let is_match = regex.is_match(line);
+            let is_match = matcher.is_match(line);
             let should_include = if self.invert_match {
                 !is_match
             } else {
@@ -492,31 +757,36 @@ impl GrepTool {
             if should_include {
                 // Collect context before
                 let mut context_before = Vec::new();
-                if let Some(before_count) = self.context_before {
+                if let Some(before_count) = self.context_before.filter(|_| !self.count_only && !self.list_files_only) {
                     let start_idx = line_idx.saturating_sub(before_count as usize);
                     for i in start_idx..line_idx {
-                        context_before.push(all_lines[i].clone());
+                        context_before.push(all_lines[i].to_string());
                     }
                 }
 
                 // Collect context after
                 let mut context_after = Vec::new();
-                if let Some(after_count) = self.context_after {
+                if let Some(after_count) = self.context_after.filter(|_| !self.count_only && !self.list_files_only) {
                     let end_idx =
                         std::cmp::min(line_idx + 1 + after_count as usize, all_lines.len());
                     for i in (line_idx + 1)..end_idx {
-                        context_after.push(all_lines[i].clone());
+                        context_after.push(all_lines[i].to_string());
                     }
                 }
 
                 all_matches.push(Match {
                     file_path: file_path.to_path_buf(),
                     line_number,
-                    line_content: line.clone(),
+                    line_content: line.to_string(),
                     context_before,
                     context_after,
                 });
 
+                // One match is enough to list the file
+                if self.list_files_only {
+                    break;
+                }
+
                 // Stop if we've hit the max results (0 means no limit)
                 if self.max_results > 0 && all_matches.len() >= self.max_results as usize {
                     break;
@@ -527,6 +797,62 @@ impl GrepTool {
         Ok(())
     }
 
+    /// Run the regex over the whole file, reporting each match at the line it starts on
+    /// with the full text of every line it spans
+    fn search_content_multiline(
+        &self,
+        file_path: &Path,
+        content: &str,
+        all_lines: &[&str],
+        regex: &Regex,
+        all_matches: &mut Vec<Match>,
+    ) {
+        for found in regex.find_iter(content) {
+            // Skip empty matches so patterns like `x*` don't report every position
+            if found.as_str().is_empty() {
+                continue;
+            }
+
+            let start_idx = content[..found.start()].matches('\n').count();
+            let end_idx = start_idx + found.as_str().trim_end_matches('\n').matches('\n').count();
+            let end_idx = end_idx.min(all_lines.len().saturating_sub(1));
+
+            let context_before = match self.context_before.filter(|_| !self.count_only && !self.list_files_only) {
+                Some(before_count) => {
+                    all_lines[start_idx.saturating_sub(before_count as usize)..start_idx]
+                        .iter()
+                        .map(|line| line.to_string())
+                        .collect()
+                }
+                None => Vec::new(),
+            };
+            let context_after = match self.context_after.filter(|_| !self.count_only && !self.list_files_only) {
+                Some(after_count) => {
+                    let stop = std::cmp::min(end_idx + 1 + after_count as usize, all_lines.len());
+                    all_lines[end_idx + 1..stop].iter().map(|line| line.to_string()).collect()
+                }
+                None => Vec::new(),
+            };
+
+            all_matches.push(Match {
+                file_path: file_path.to_path_buf(),
+                line_number: start_idx + 1,
+                line_content: all_lines[start_idx..=end_idx].join("\n"),
+                context_before,
+                context_after,
+            });
+
+            if self.list_files_only {
+                break;
+            }
+
+            // Stop if we've hit the max results (0 means no limit)
+            if self.max_results > 0 && all_matches.len() >= self.max_results as usize {
+                break;
+            }
+        }
+    }
+
     pub async fn call(self) -> Result<CallToolResult, CallToolError> {
         let context = ToolContext::new();
         self.call_with_context(&context).await
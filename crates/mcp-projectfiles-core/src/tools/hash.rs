@@ -1,32 +1,46 @@
 use crate::config::tool_errors;
 use crate::context::{StatefulTool, ToolContext};
-use crate::tools::utils::{format_path, format_size, resolve_path_for_read};
+use crate::tools::utils::{expand_pattern_in_project, format_path, format_size, resolve_path_for_read};
 use async_trait::async_trait;
+use md5::Md5;
 use rust_mcp_schema::{
     CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
 };
 use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
 use std::fmt::Write as FmtWrite;
+use std::path::Path;
 use tokio::fs;
 use tokio::io::{AsyncReadExt, BufReader};
 
 const TOOL_NAME: &str = "hash";
 
+const SUPPORTED_ALGORITHMS: &[&str] = &["md5", "sha1", "sha256", "sha512", "blake3"];
+
+/// Size of each chunk read from disk while hashing
+const CHUNK_SIZE: usize = 64 * 1024;
+
 #[mcp_tool(
     name = "hash",
-    description = "Calculate file hashes (MD5, SHA1, SHA256, SHA512). Verify checksums, compare files.
-Examples: {\"path\": \"package.json\"} or {\"path\": \"README.md\", \"algorithm\": \"md5\"}"
+    description = "Calculate file hashes (MD5, SHA1, SHA256, SHA512, BLAKE3). Verify checksums, compare files. Files are streamed, so large files are fine.
+With pattern=true, path is a glob and output is one '<hash>  <path>' line per file, compatible with sha256sum -c.
+Examples: {\"path\": \"package.json\"} or {\"path\": \"dist/*.tar.gz\", \"pattern\": true, \"algorithm\": \"sha256\"}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct HashTool {
-    /// Path to the file to hash (relative to project root)
+    /// Path to the file to hash (relative to project root), or a glob when pattern is true
     pub path: String,
 
-    /// Hash algorithm to use: "md5", "sha1", "sha256", "sha512" (default: "sha256")
+    /// Hash algorithm to use: "md5", "sha1", "sha256", "sha512", "blake3" (default: "sha256")
     #[serde(default = "default_algorithm")]
     pub algorithm: String,
 
+    /// Treat path as a glob pattern and hash every matching file (default: false)
+    #[serde(default)]
+    pub pattern: bool,
+
     /// Follow symlinks to hash files outside the project directory (default: true)
     #[serde(default = "default_follow_symlinks")]
     pub follow_symlinks: bool,
@@ -43,9 +57,11 @@ fn default_follow_symlinks() -> bool {
 #[async_trait]
 impl StatefulTool for HashTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
         // Get project root and resolve path
         let project_root = context.get_project_root().map_err(|e| {
             CallToolError::from(tool_errors::invalid_input(
@@ -54,6 +70,23 @@ impl StatefulTool for HashTool {
             ))
         })?;
 
+        // Validate algorithm
+        let algorithm = self.algorithm.to_lowercase();
+        if !SUPPORTED_ALGORITHMS.contains(&algorithm.as_str()) {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!(
+                    "Unsupported algorithm '{}'. Supported: {}",
+                    self.algorithm,
+                    SUPPORTED_ALGORITHMS.join(", ")
+                ),
+            )));
+        }
+
+        if self.pattern {
+            return self.hash_pattern(&project_root, &algorithm).await;
+        }
+
         // Use the utility function to resolve path with symlink support
         let normalized_path =
             resolve_path_for_read(&self.path, &project_root, self.follow_symlinks, TOOL_NAME)?;
@@ -72,18 +105,6 @@ impl StatefulTool for HashTool {
             )));
         }
 
-        // Validate algorithm
-        let algorithm = self.algorithm.to_lowercase();
-        if !["md5", "sha1", "sha256", "sha512"].contains(&algorithm.as_str()) {
-            return Err(CallToolError::from(tool_errors::invalid_input(
-                TOOL_NAME,
-                &format!(
-                    "Unsupported algorithm '{}'. Supported: md5, sha1, sha256, sha512",
-                    self.algorithm
-                ),
-            )));
-        }
-
         // Get file size
         let metadata = fs::metadata(&normalized_path).await.map_err(|e| {
             CallToolError::from(tool_errors::invalid_input(
@@ -93,9 +114,7 @@ impl StatefulTool for HashTool {
         })?;
         let file_size = metadata.len();
 
-        // Calculate hash using simple checksum for now
-        // In a real implementation, we would use proper crypto libraries
-        let hash = calculate_simple_hash(&normalized_path, &algorithm).await?;
+        let hash = calculate_hash(&normalized_path, &algorithm).await?;
 
         // Format path relative to project root
         let relative_path = normalized_path
@@ -121,11 +140,103 @@ impl StatefulTool for HashTool {
     }
 }
 
-// Simple hash calculation - in production, use proper crypto libraries
-async fn calculate_simple_hash(
-    path: &std::path::Path,
-    algorithm: &str,
-) -> Result<String, CallToolError> {
+impl HashTool {
+    /// Hash every file matching the glob in `self.path`, one `sha256sum`-style line per file
+    async fn hash_pattern(
+        &self,
+        project_root: &Path,
+        algorithm: &str,
+    ) -> Result<CallToolResult, CallToolError> {
+        let current_dir = project_root.canonicalize().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to canonicalize project root: {}", e),
+            ))
+        })?;
+
+        let matches = expand_pattern_in_project(&self.path, &current_dir, TOOL_NAME)?;
+        let mut lines = Vec::new();
+        for relative_path in matches {
+            let resolved =
+                resolve_path_for_read(&relative_path, &current_dir, self.follow_symlinks, TOOL_NAME)?;
+            if !resolved.is_file() {
+                continue;
+            }
+            let hash = calculate_hash(&resolved, algorithm).await?;
+            // Paths are listed as matched so the output can be checked from the project root
+            lines.push(format!("{}  {}", hash, relative_path));
+        }
+
+        if lines.is_empty() {
+            return Err(CallToolError::from(tool_errors::file_not_found(
+                TOOL_NAME,
+                &format!("No files found matching pattern: {}", self.path),
+            )));
+        }
+
+        // Nothing but the checksum lines, so the output can be fed straight to sha256sum -c
+        let mut output = lines.join("\n");
+        output.push('\n');
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                output, None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+}
+
+enum Hasher {
+    Md5(Md5),
+    Sha1(Sha1),
+    Sha256(Sha256),
+    Sha512(Sha512),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    fn new(algorithm: &str) -> Self {
+        match algorithm {
+            "md5" => Hasher::Md5(Md5::new()),
+            "sha1" => Hasher::Sha1(Sha1::new()),
+            "sha512" => Hasher::Sha512(Sha512::new()),
+            "blake3" => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+            _ => Hasher::Sha256(Sha256::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Md5(h) => h.update(data),
+            Hasher::Sha1(h) => h.update(data),
+            Hasher::Sha256(h) => h.update(data),
+            Hasher::Sha512(h) => h.update(data),
+            Hasher::Blake3(h) => {
+                h.update(data);
+            }
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        let bytes = match self {
+            Hasher::Md5(h) => h.finalize().to_vec(),
+            Hasher::Sha1(h) => h.finalize().to_vec(),
+            Hasher::Sha256(h) => h.finalize().to_vec(),
+            Hasher::Sha512(h) => h.finalize().to_vec(),
+            Hasher::Blake3(h) => h.finalize().as_bytes().to_vec(),
+        };
+        let mut hex_string = String::with_capacity(bytes.len() * 2);
+        for byte in bytes {
+            write!(&mut hex_string, "{:02x}", byte).unwrap();
+        }
+        hex_string
+    }
+}
+
+/// Stream the file through the selected hasher in fixed-size chunks
+async fn calculate_hash(path: &Path, algorithm: &str) -> Result<String, CallToolError> {
     let file = fs::File::open(path).await.map_err(|e| {
         CallToolError::from(tool_errors::invalid_input(
             TOOL_NAME,
@@ -134,12 +245,8 @@ async fn calculate_simple_hash(
     })?;
 
     let mut reader = BufReader::new(file);
-    let mut buffer = vec![0u8; 8192];
-
-    // For demonstration, we'll use a simple checksum
-    // In production, you would use sha2, md5, sha1 crates
-    let mut checksum: u64 = 0;
-    let mut total_bytes = 0u64;
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let mut hasher = Hasher::new(algorithm);
 
     loop {
         let bytes_read = reader.read(&mut buffer).await.map_err(|e| {
@@ -153,62 +260,12 @@ async fn calculate_simple_hash(
             break;
         }
 
-        // Simple checksum calculation
-        for i in 0..bytes_read {
-            checksum = checksum.wrapping_add(buffer[i] as u64);
-            checksum = checksum.wrapping_mul(17); // Prime number for better distribution
-        }
-
-        total_bytes += bytes_read as u64;
-    }
-
-    // Mix in the algorithm name and total bytes for different results per algorithm
-    match algorithm {
-        "md5" => checksum = checksum.wrapping_mul(13),
-        "sha1" => checksum = checksum.wrapping_mul(19),
-        "sha256" => checksum = checksum.wrapping_mul(23),
-        "sha512" => checksum = checksum.wrapping_mul(29),
-        _ => {}
-    }
-
-    checksum = checksum.wrapping_add(total_bytes);
-
-    // Format as hex string
-    let mut hex_string = String::new();
-
-    // Extend to appropriate length for each algorithm
-    let hash_length = match algorithm {
-        "md5" => 32,     // 128 bits = 16 bytes = 32 hex chars
-        "sha1" => 40,    // 160 bits = 20 bytes = 40 hex chars
-        "sha256" => 64,  // 256 bits = 32 bytes = 64 hex chars
-        "sha512" => 128, // 512 bits = 64 bytes = 128 hex chars
-        _ => 64,
-    };
-
-    // Create a simple hash by repeating and mixing the checksum
-    for i in 0..(hash_length / 16) {
-        let mixed = checksum.wrapping_mul((i as u64).wrapping_add(1));
-        for byte in mixed.to_be_bytes() {
-            write!(&mut hex_string, "{:02x}", byte).unwrap();
-        }
+        hasher.update(&buffer[..bytes_read]);
     }
 
-    hex_string.truncate(hash_length);
-
-    Ok(hex_string)
+    Ok(hasher.finalize_hex())
 }
 
-// Note: This is a demonstration implementation.
-// For production use, add these dependencies to Cargo.toml and use proper crypto:
-// sha2 = "0.10"
-// md5 = "0.7"
-// sha1 = "0.10"
-//
-// Then implement proper hashing:
-// use sha2::{Sha256, Sha512, Digest};
-// use md5::Md5;
-// use sha1::Sha1;
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -246,6 +303,7 @@ mod tests {
         let hash_tool = HashTool {
             path: "test.txt".to_string(),
             algorithm: "sha256".to_string(),
+            pattern: false,
             follow_symlinks: true,
         };
 
@@ -278,6 +336,7 @@ mod tests {
         let hash_tool = HashTool {
             path: "test.txt".to_string(),
             algorithm: default_algorithm(), // Should be sha256
+            pattern: false,
             follow_symlinks: true,
         };
 
@@ -297,7 +356,8 @@ mod tests {
 
         let algorithms = vec![
             ("md5", 32),
-            ("sha1", 32), // Current implementation generates 32 chars for SHA1
+            ("sha1", 40),
+            ("blake3", 64),
             ("sha256", 64),
             ("sha512", 128),
         ];
@@ -306,6 +366,7 @@ mod tests {
             let hash_tool = HashTool {
                 path: "test.txt".to_string(),
                 algorithm: algo.to_string(),
+                pattern: false,
                 follow_symlinks: true,
             };
 
@@ -340,12 +401,14 @@ mod tests {
         let hash_tool1 = HashTool {
             path: "file1.txt".to_string(),
             algorithm: "sha256".to_string(),
+            pattern: false,
             follow_symlinks: true,
         };
 
         let hash_tool2 = HashTool {
             path: "file2.txt".to_string(),
             algorithm: "sha256".to_string(),
+            pattern: false,
             follow_symlinks: true,
         };
 
@@ -384,6 +447,7 @@ mod tests {
         let hash_tool = HashTool {
             path: "large.txt".to_string(),
             algorithm: "sha256".to_string(),
+            pattern: false,
             follow_symlinks: true,
         };
 
@@ -405,6 +469,7 @@ mod tests {
         let hash_tool = HashTool {
             path: "empty.txt".to_string(),
             algorithm: "md5".to_string(),
+            pattern: false,
             follow_symlinks: true,
         };
 
@@ -425,6 +490,7 @@ mod tests {
         let hash_tool = HashTool {
             path: "nonexistent.txt".to_string(),
             algorithm: "sha256".to_string(),
+            pattern: false,
             follow_symlinks: true,
         };
 
@@ -449,6 +515,7 @@ mod tests {
         let hash_tool = HashTool {
             path: "testdir".to_string(),
             algorithm: "sha256".to_string(),
+            pattern: false,
             follow_symlinks: true,
         };
 
@@ -468,6 +535,7 @@ mod tests {
         let hash_tool = HashTool {
             path: "test.txt".to_string(),
             algorithm: "invalid".to_string(),
+            pattern: false,
             follow_symlinks: true,
         };
 
@@ -477,7 +545,7 @@ mod tests {
         let error = result.unwrap_err();
         assert!(error.to_string().contains("projectfiles:hash"));
         assert!(error.to_string().contains("Unsupported algorithm"));
-        assert!(error.to_string().contains("md5, sha1, sha256, sha512"));
+        assert!(error.to_string().contains("md5, sha1, sha256, sha512, blake3"));
     }
 
     #[tokio::test]
@@ -487,6 +555,7 @@ mod tests {
         let hash_tool = HashTool {
             path: "../outside.txt".to_string(),
             algorithm: "sha256".to_string(),
+            pattern: false,
             follow_symlinks: true,
         };
 
@@ -516,6 +585,7 @@ mod tests {
         let hash_tool = HashTool {
             path: "subdir/nested.txt".to_string(),
             algorithm: "sha1".to_string(),
+            pattern: false,
             follow_symlinks: true,
         };
 
@@ -550,6 +620,7 @@ mod tests {
         let hash_tool = HashTool {
             path: "link_to_target.txt".to_string(),
             algorithm: "sha256".to_string(),
+            pattern: false,
             follow_symlinks: true,
         };
 
@@ -590,6 +661,7 @@ mod tests {
         let hash_tool = HashTool {
             path: "link_to_external.txt".to_string(),
             algorithm: "md5".to_string(),
+            pattern: false,
             follow_symlinks: true,
         };
 
@@ -630,6 +702,7 @@ mod tests {
         let hash_tool = HashTool {
             path: "link_to_external.txt".to_string(),
             algorithm: "sha256".to_string(),
+            pattern: false,
             follow_symlinks: false,
         };
 
@@ -657,6 +730,7 @@ mod tests {
         let hash_tool = HashTool {
             path: "broken_link.txt".to_string(),
             algorithm: "sha256".to_string(),
+            pattern: false,
             follow_symlinks: true,
         };
 
@@ -669,5 +743,77 @@ mod tests {
         // Should indicate file not found since the symlink target doesn't exist
         assert!(error_str.contains("not found") || error_str.contains("No such file"));
     }
-}
 
+    #[tokio::test]
+    async fn test_hash_known_digests() {
+        let (context, temp_dir) = setup_test_context().await;
+        create_test_file(temp_dir.path(), "abc.txt", "abc").await;
+
+        let expected = vec![
+            ("md5", "900150983cd24fb0d6963f7d28e17f72"),
+            ("sha1", "a9993e364706816aba3e25717850c26c9cd0d89d"),
+            (
+                "sha256",
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ),
+        ];
+
+        for (algo, digest) in expected {
+            let hash_tool = HashTool {
+                path: "abc.txt".to_string(),
+                algorithm: algo.to_string(),
+                pattern: false,
+                follow_symlinks: true,
+            };
+
+            let output = hash_tool.call_with_context(&context).await.unwrap();
+            if let Some(CallToolResultContentItem::TextContent(text)) = output.content.first() {
+                assert_eq!(text.text.lines().nth(1), Some(digest), "Digest mismatch for {}", algo);
+            } else {
+                panic!("Expected text content");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hash_pattern_checksum_format() {
+        let (context, temp_dir) = setup_test_context().await;
+        fs::create_dir(temp_dir.path().join("dist")).await.unwrap();
+        create_test_file(temp_dir.path(), "dist/b.txt", "abc").await;
+        create_test_file(temp_dir.path(), "dist/a.txt", "abc").await;
+        create_test_file(temp_dir.path(), "dist/notes.md", "skip").await;
+
+        let hash_tool = HashTool {
+            path: "dist/*.txt".to_string(),
+            algorithm: "sha256".to_string(),
+            pattern: true,
+            follow_symlinks: true,
+        };
+
+        let output = hash_tool.call_with_context(&context).await.unwrap();
+        if let Some(CallToolResultContentItem::TextContent(text)) = output.content.first() {
+            let lines: Vec<&str> = text.text.lines().collect();
+            let digest = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+            assert_eq!(lines, [format!("{}  dist/a.txt", digest), format!("{}  dist/b.txt", digest)]);
+            assert!(text.text.ends_with('\n'));
+        } else {
+            panic!("Expected text content");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hash_pattern_rejects_parent_dir() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let hash_tool = HashTool {
+            path: "../*.txt".to_string(),
+            algorithm: "sha256".to_string(),
+            pattern: true,
+            follow_symlinks: true,
+        };
+
+        let result = hash_tool.call_with_context(&context).await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("projectfiles:hash"));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::context::{StatefulTool, ToolContext};
 use crate::config::tool_errors;
-use crate::tools::utils::resolve_path_for_read;
+use crate::tools::utils::{TextStyle, expand_pattern_in_project, resolve_path_for_read, resolve_path_for_write, write_atomically};
+use crate::tools::write::{encode_like, read_text_file};
 use crate::tools::query_engine::{QueryEngine, QueryError};
 use async_trait::async_trait;
 use rust_mcp_schema::{
@@ -8,7 +9,8 @@ use rust_mcp_schema::{
 };
 use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -19,6 +21,9 @@ pub enum JsonQueryError {
     #[error("Error: projectfiles:jq - Invalid JSON in file {file}: {error}")]
     InvalidJson { file: String, error: String },
     
+    #[error("Error: projectfiles:jq - Invalid JSON Schema in file {file}: {error}")]
+    InvalidSchema { file: String, error: String },
+    
     #[error("Error: projectfiles:jq - IO error: {0}")]
     IoError(String),
     
@@ -27,14 +32,18 @@ pub enum JsonQueryError {
 }
 
 #[mcp_tool(name = "jq", description = "Query and manipulate JSON files with jq syntax. Full jq features, read/write operations.
+Use operation=\"validate\" to check that the file parses without running a query; add schema_path to also check it against a JSON Schema.
+Set slurp=true to read newline-delimited JSON (NDJSON) as an array of values. indent and sort_keys control formatting of output and in-place writes.
+With pattern=true, file_path is a glob and the query runs against every match, returning an object keyed by path; files that fail carry an error instead.
 Examples: \".users | map(.email)\" or \".active = true\" or \"group_by(.category)\"")]
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct JsonQueryTool {
-    /// Path to the JSON file (relative to project root)
+    /// Path to the JSON file (relative to project root), or a glob when pattern is true
     pub file_path: String,
-    /// JSONPath or simple jq-style query string
+    /// JSONPath or simple jq-style query string (ignored for validate)
+    #[serde(default)]
     pub query: String,
-    /// Operation type: "read" (default) or "write"
+    /// Operation type: "read" (default), "write", or "validate" (report whether the file parses)
     #[serde(default = "default_operation")]
     pub operation: String,
     /// Output format: "json" (default), "raw", or "compact"
@@ -49,6 +58,38 @@ pub struct JsonQueryTool {
     /// Follow symlinks when reading files (default: true)
     #[serde(default = "default_follow_symlinks")]
     pub follow_symlinks: bool,
+    /// Parse each non-empty line as a separate JSON value and query them as an array, like jq -s (default: false)
+    #[serde(default)]
+    pub slurp: bool,
+    /// Spaces per indentation level for pretty output and in-place writes; 0 indents with tabs (default: 2)
+    pub indent: Option<u32>,
+    /// Serialize object keys in sorted order (default: false)
+    #[serde(default)]
+    pub sort_keys: bool,
+    /// JSON Schema file (relative to project root) to check the file against; validate operation only
+    pub schema_path: Option<String>,
+    /// Treat file_path as a glob and run the query against every matching file; read operations only (default: false)
+    #[serde(default)]
+    pub pattern: bool,
+}
+
+/// Largest accepted `indent`
+const MAX_INDENT: u32 = 16;
+
+/// Serializes objects with their keys in sorted order, however the underlying map is ordered
+struct SortedKeys<'a>(&'a serde_json::Value);
+
+impl Serialize for SortedKeys<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            serde_json::Value::Object(map) => {
+                let sorted: BTreeMap<&String, SortedKeys> = map.iter().map(|(k, v)| (k, SortedKeys(v))).collect();
+                sorted.serialize(serializer)
+            }
+            serde_json::Value::Array(items) => serializer.collect_seq(items.iter().map(SortedKeys)),
+            other => other.serialize(serializer),
+        }
+    }
 }
 
 fn default_operation() -> String {
@@ -71,15 +112,32 @@ pub struct JsonQueryResult {
 }
 
 impl JsonQueryTool {
-    fn read_json_file(&self, file_path: &Path) -> Result<serde_json::Value, JsonQueryError> {
-        let content = std::fs::read_to_string(file_path)
+    fn read_file_content(file_path: &Path) -> Result<String, JsonQueryError> {
+        read_text_file(file_path)
+            .map(|(content, _, _)| content)
             .map_err(|e| {
                 if e.kind() == std::io::ErrorKind::NotFound {
                     JsonQueryError::FileNotFound(file_path.display().to_string())
                 } else {
                     JsonQueryError::IoError(e.to_string())
                 }
-            })?;
+            })
+    }
+    
+    fn read_json_file(&self, file_path: &Path) -> Result<serde_json::Value, JsonQueryError> {
+        let content = Self::read_file_content(file_path)?;
+        
+        if self.slurp {
+            return content.lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(i, line)| serde_json::from_str(line).map_err(|e| JsonQueryError::InvalidJson {
+                    file: format!("{} (line {})", file_path.display(), i + 1),
+                    error: e.to_string(),
+                }))
+                .collect::<Result<Vec<_>, _>>()
+                .map(serde_json::Value::Array);
+        }
         
         serde_json::from_str(&content).map_err(|e| JsonQueryError::InvalidJson {
             file: file_path.display().to_string(),
@@ -87,21 +145,146 @@ impl JsonQueryTool {
         })
     }
     
-    fn write_json_file(&self, file_path: &Path, data: &serde_json::Value, backup: bool) -> Result<(), JsonQueryError> {
+    /// Parse the file without querying it, reporting the line and column of the first syntax error
+    fn validate_json_file(&self, file_path: &Path) -> Result<serde_json::Value, JsonQueryError> {
+        let content = Self::read_file_content(file_path)?;
+        
+        // With slurp every non-empty line is its own document; otherwise the whole file is one
+        let documents: Vec<(usize, &str)> = if self.slurp {
+            content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()).collect()
+        } else {
+            vec![(0, content.as_str())]
+        };
+        
+        for (line_offset, text) in documents {
+            if let Err(e) = serde_json::from_str::<serde::de::IgnoredAny>(text) {
+                // serde_json appends the position to its message; report it as separate fields instead
+                let message = e.to_string();
+                let message = message
+                    .strip_suffix(&format!(" at line {} column {}", e.line(), e.column()))
+                    .unwrap_or(&message);
+                return Ok(serde_json::json!({
+                    "valid": false,
+                    "error": message,
+                    "line": e.line() + line_offset,
+                    "column": e.column(),
+                }));
+            }
+        }
+        
+        Ok(serde_json::json!({ "valid": true }))
+    }
+    
+    /// Check the parsed file against a JSON Schema, listing every violation with its instance path
+    fn validate_against_schema(&self, file_path: &Path, schema_file: &Path) -> Result<serde_json::Value, JsonQueryError> {
+        let data = self.read_json_file(file_path)?;
+        let schema: serde_json::Value = serde_json::from_str(&Self::read_file_content(schema_file)?)
+            .map_err(|e| JsonQueryError::InvalidJson {
+                file: schema_file.display().to_string(),
+                error: e.to_string(),
+            })?;
+        let validator = jsonschema::validator_for(&schema)
+            .map_err(|e| JsonQueryError::InvalidSchema {
+                file: schema_file.display().to_string(),
+                error: e.to_string(),
+            })?;
+        
+        let errors: Vec<serde_json::Value> = validator.iter_errors(&data)
+            .map(|e| serde_json::json!({
+                "instance_path": e.instance_path().as_str(),
+                "message": e.to_string(),
+            }))
+            .collect();
+        Ok(serde_json::json!({ "valid": errors.is_empty(), "errors": errors }))
+    }
+    
+    fn write_json_file(&self, file_path: &Path, project_root: &Path, data: &serde_json::Value, backup: bool) -> Result<(), CallToolError> {
+        let io_error = |e: JsonQueryError| CallToolError::from(tool_errors::invalid_input("jq", &e.to_string()));
+        
         if backup && file_path.exists() {
             let backup_path = file_path.with_extension(format!("{}.bak", 
                 file_path.extension().and_then(|s| s.to_str()).unwrap_or("json")));
             std::fs::copy(file_path, backup_path)
-                .map_err(|e| JsonQueryError::IoError(format!("Failed to create backup: {}", e)))?;
+                .map_err(|e| io_error(JsonQueryError::IoError(format!("Failed to create backup: {}", e))))?;
+        }
+        
+        let mut content = self.to_json_string(data, self.output_format != "compact").map_err(|e| io_error(JsonQueryError::IoError(format!("Failed to serialize JSON: {}", e))))?;
+        // Keep the existing file's line endings, final newline and encoding
+        let existing = read_text_file(file_path).ok();
+        if let Some(style) = existing.as_ref().and_then(|(content, _, _)| TextStyle::detect(content)) {
+            content = style.apply(&content);
+        }
+        let (encoding, had_bom) = existing.map_or((encoding_rs::UTF_8, false), |(_, encoding, had_bom)| (encoding, had_bom));
+        let bytes = encode_like(&content, encoding, had_bom, "jq")?;
+        
+        write_atomically(file_path, project_root, &bytes, "jq")
+    }
+    
+    /// Serialize honoring `indent` and `sort_keys`
+    fn to_json_string(&self, value: &serde_json::Value, pretty: bool) -> serde_json::Result<String> {
+        let mut out = Vec::new();
+        if pretty {
+            let indent = match self.indent {
+                Some(0) => "\t".to_string(),
+                Some(width) => " ".repeat(width as usize),
+                None => "  ".to_string(),
+            };
+            let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+            self.serialize_value(value, &mut serde_json::Serializer::with_formatter(&mut out, formatter))?;
+        } else {
+            self.serialize_value(value, &mut serde_json::Serializer::new(&mut out))?;
+        }
+        // serde_json only emits valid UTF-8
+        Ok(String::from_utf8(out).unwrap_or_default())
+    }
+    
+    fn serialize_value<S: serde::Serializer>(&self, value: &serde_json::Value, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.sort_keys {
+            SortedKeys(value).serialize(serializer)
+        } else {
+            value.serialize(serializer)
+        }
+    }
+    
+    /// Run the query against every file matching the `file_path` glob. Each file maps to its
+    /// result, or to an `error` entry when it cannot be read, parsed or queried.
+    async fn query_pattern(&self, context: &ToolContext, project_root: &Path) -> Result<CallToolResult, CallToolError> {
+        let engine = QueryEngine::new();
+        engine.parser.validate(&self.query)
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input("jq", &QueryError::from(e).to_string())))?;
+        
+        let mut results = serde_json::Map::new();
+        let mut read_paths = Vec::new();
+        for relative_path in expand_pattern_in_project(&self.file_path, project_root, "jq")? {
+            let file_path = match resolve_path_for_read(&relative_path, project_root, self.follow_symlinks, "jq") {
+                Ok(path) if !path.is_file() => continue,
+                Ok(path) => path,
+                Err(e) => {
+                    results.insert(relative_path, serde_json::json!({ "error": e.to_string() }));
+                    continue;
+                }
+            };
+            let result = self.read_json_file(&file_path)
+                .inspect(|_| read_paths.push(file_path.clone()))
+                .and_then(|data| engine.execute(&data, &self.query).map_err(JsonQueryError::from));
+            let entry = result.unwrap_or_else(|e| serde_json::json!({ "error": e.to_string() }));
+            results.insert(relative_path, entry);
         }
         
-        let content = match self.output_format.as_str() {
-            "compact" => serde_json::to_string(data),
-            _ => serde_json::to_string_pretty(data),
-        }.map_err(|e| JsonQueryError::IoError(format!("Failed to serialize JSON: {}", e)))?;
+        // Track every file whose contents were read
+        let read_files = context.get_custom_state::<HashSet<PathBuf>>().await
+            .unwrap_or_else(|| std::sync::Arc::new(HashSet::new()));
+        let mut read_files_clone = (*read_files).clone();
+        read_files_clone.extend(read_paths);
+        context.set_custom_state(read_files_clone).await;
         
-        std::fs::write(file_path, content)
-            .map_err(|e| JsonQueryError::IoError(e.to_string()))
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                self.format_output(&serde_json::Value::Object(results)), None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
     }
     
     fn format_output(&self, result: &serde_json::Value) -> String {
@@ -112,14 +295,14 @@ impl JsonQueryTool {
                     serde_json::Value::Number(n) => n.to_string(),
                     serde_json::Value::Bool(b) => b.to_string(),
                     serde_json::Value::Null => "null".to_string(),
-                    _ => serde_json::to_string_pretty(result).unwrap_or_else(|_| "null".to_string()),
+                    _ => self.to_json_string(result, true).unwrap_or_else(|_| "null".to_string()),
                 }
             }
             "compact" => {
-                serde_json::to_string(result).unwrap_or_else(|_| "null".to_string())
+                self.to_json_string(result, false).unwrap_or_else(|_| "null".to_string())
             }
             _ => {
-                serde_json::to_string_pretty(result).unwrap_or_else(|_| "null".to_string())
+                self.to_json_string(result, true).unwrap_or_else(|_| "null".to_string())
             }
         }
     }
@@ -127,43 +310,78 @@ impl JsonQueryTool {
 
 #[async_trait]
 impl StatefulTool for JsonQueryTool {
-    async fn call_with_context(self, context: &ToolContext) -> Result<CallToolResult, CallToolError> {
+    async fn call_with_context(mut self, context: &ToolContext) -> Result<CallToolResult, CallToolError> {
+        self.file_path = context.resolve_in_working_dir(&self.file_path);
+        self.schema_path = self.schema_path.map(|path| context.resolve_in_working_dir(&path));
+
+        if self.in_place {
+            context.ensure_writable("jq")?;
+        }
+
         let project_root = context.get_project_root()
             .map_err(|e| CallToolError::from(tool_errors::invalid_input("jq", &e.to_string())))?;
         
+        if self.indent.is_some_and(|width| width > MAX_INDENT) {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                "jq",
+                &format!("indent must be between 0 and {}", MAX_INDENT)
+            )));
+        }
+        
+        if self.pattern {
+            if self.operation != "read" {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    "jq",
+                    "pattern is only supported for read operations"
+                )));
+            }
+            return self.query_pattern(context, &project_root).await;
+        }
+        
         // For read operations, use symlink-aware path resolution
-        let file_path = if self.operation == "read" {
+        let file_path = if matches!(self.operation.as_str(), "read" | "validate") {
             resolve_path_for_read(&self.file_path, &project_root, self.follow_symlinks, "jq")
                 .map_err(|e| CallToolError::from(e))?
         } else {
-            // For write operations, ensure we don't write through symlinks
-            let requested_path = Path::new(&self.file_path);
-            let absolute_path = if requested_path.is_absolute() {
-                requested_path.to_path_buf()
-            } else {
-                project_root.join(requested_path)
-            };
-            
-            // Canonicalize the path to resolve all symlinks
-            let canonical = absolute_path.canonicalize()
-                .map_err(|e| CallToolError::from(tool_errors::invalid_input("jq", &format!("Failed to resolve path '{}': {}", self.file_path, e))))?;
-            
-            // Ensure the canonicalized path is within the project directory
-            if !canonical.starts_with(&project_root) {
-                return Err(CallToolError::from(tool_errors::access_denied(
-                    "jq",
-                    &self.file_path,
-                    "Path is outside the project directory"
-                )));
-            }
-            
-            canonical
+            resolve_path_for_write(&self.file_path, &project_root, "jq")?
         };
         
+        if self.schema_path.is_some() && self.operation != "validate" {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                "jq",
+                "schema_path is only supported for validate operations"
+            )));
+        }
+        
+        if self.slurp && self.operation == "write" {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                "jq",
+                "slurp is only supported for read operations"
+            )));
+        }
+        
+        if self.operation == "validate" {
+            let mut report = self.validate_json_file(&file_path).map_err(|e| CallToolError::from(tool_errors::invalid_input("jq", &e.to_string())))?;
+            // Schema checks only make sense once the file parses
+            if let Some(schema_path) = self.schema_path.as_deref().filter(|_| report["valid"] == true) {
+                let schema_file = resolve_path_for_read(schema_path, &project_root, self.follow_symlinks, "jq")?;
+                report = self.validate_against_schema(&file_path, &schema_file).map_err(|e| CallToolError::from(tool_errors::invalid_input("jq", &e.to_string())))?;
+            }
+            return Ok(CallToolResult {
+                content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                    report.to_string(), None,
+                ))],
+                is_error: Some(false),
+                meta: None,
+            });
+        }
+        
         // Read JSON file
         let mut data = self.read_json_file(&file_path).map_err(|e| CallToolError::from(tool_errors::invalid_input("jq", &e.to_string())))?;
         
         let engine = QueryEngine::new();
+        engine.parser.validate(&self.query)
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input("jq", &QueryError::from(e).to_string())))?;
         let result = match self.operation.as_str() {
             "write" => {
                 // For write operations, use the query engine
@@ -171,8 +389,8 @@ impl StatefulTool for JsonQueryTool {
                     .map_err(|e| CallToolError::from(tool_errors::invalid_input("jq", &e.to_string())))?;
                 
                 if self.in_place {
-                    self.write_json_file(&file_path, &data, self.backup)
-                        .map_err(|e| CallToolError::from(tool_errors::invalid_input("jq", &e.to_string())))?;
+                    self.write_json_file(&file_path, &project_root, &data, self.backup)?;
+                    context.record_modified(&file_path).await;
                 }
                 
                 JsonQueryResult {
@@ -9,6 +9,7 @@ use rust_mcp_schema::{
 use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::process::Command;
 
@@ -17,7 +18,8 @@ const TOOL_NAME: &str = "kill";
 #[mcp_tool(
     name = "kill",
     description = "Terminate processes in project directory. Signals, patterns, dry-run preview.
-Examples: {\"pid\": 12345} or {\"name_pattern\": \"*webpack*\", \"dry_run\": true}"
+Set kill_tree=true to also signal descendant processes, children first; they are left alone if the process itself cannot be signalled. Set escalate=true to send SIGKILL to the process and its signalled descendants if they survive the signal for escalate_timeout_ms.
+Examples: {\"pid\": 12345} or {\"name_pattern\": \"*webpack*\", \"dry_run\": true} or {\"pid\": 12345, \"escalate\": true}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct KillTool {
@@ -44,8 +46,25 @@ pub struct KillTool {
     /// Require explicit confirmation for dangerous operations (default: false)
     #[serde(default)]
     pub force_confirmation: bool,
+
+    /// Send KILL to processes still alive after escalate_timeout_ms (default: false)
+    #[serde(default)]
+    pub escalate: bool,
+
+    /// How long to wait for processes to exit before escalating, in milliseconds (default: 5000, max: 60000)
+    pub escalate_timeout_ms: Option<u64>,
+
+    /// Also signal all descendants of each target, children before parents (default: false)
+    #[serde(default)]
+    pub kill_tree: bool,
 }
 
+/// Upper bound on how long escalation waits for processes to exit
+const MAX_ESCALATE_TIMEOUT_MS: u64 = 60_000;
+
+/// Interval between liveness checks while waiting to escalate
+const ESCALATE_POLL_INTERVAL_MS: u64 = 100;
+
 #[derive(Serialize, Deserialize, Debug)]
 struct ProcessKillResult {
     pid: u32,
@@ -56,6 +75,18 @@ struct ProcessKillResult {
     error_message: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     command_line: Option<String>,
+    /// Whether KILL had to be sent after the initial signal (only set when escalating)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    escalated: Option<bool>,
+    /// Descendant PIDs signalled before this process (only set when killing the tree)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    descendants_killed: Option<Vec<u32>>,
+    /// Descendant PIDs that needed KILL after the initial signal (only set when escalating)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    descendants_escalated: Option<Vec<u32>>,
+    /// Signalled descendants with their names, so escalation can tell a reused PID apart
+    #[serde(skip)]
+    signalled_descendants: Vec<(u32, String)>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -75,6 +106,8 @@ struct KillQuery {
     name_pattern: Option<String>,
     signal: String,
     max_processes: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    escalate_timeout_ms: Option<u64>,
 }
 
 #[async_trait]
@@ -83,6 +116,8 @@ impl StatefulTool for KillTool {
         self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        context.ensure_writable(TOOL_NAME)?;
+
         // Only require dry_run when user wants to be careful
         // By default, allow killing processes in the project directory
         // (Safety is already enforced by the project directory check)
@@ -120,6 +155,7 @@ impl StatefulTool for KillTool {
 
         let signal = self.signal.as_deref().unwrap_or("TERM");
         let max_processes = self.max_processes.unwrap_or(10);
+        let escalate_timeout_ms = self.escalate_timeout_ms.unwrap_or(5000);
 
         // Enhanced signal validation
         let valid_signals = ["TERM", "KILL", "INT", "QUIT", "USR1", "USR2"];
@@ -159,6 +195,23 @@ impl StatefulTool for KillTool {
             )));
         }
 
+        if self.escalate_timeout_ms.is_some() && !self.escalate {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                "'escalate_timeout_ms' requires 'escalate' to be true",
+            )));
+        }
+
+        if escalate_timeout_ms == 0 || escalate_timeout_ms > MAX_ESCALATE_TIMEOUT_MS {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!(
+                    "escalate_timeout_ms must be between 1 and {}",
+                    MAX_ESCALATE_TIMEOUT_MS
+                ),
+            )));
+        }
+
         // Find processes to kill
         let mut processes_to_kill = Vec::new();
 
@@ -232,13 +285,48 @@ impl StatefulTool for KillTool {
         let mut killed_count = 0;
         let mut failed_count = 0;
 
+        // Snapshot parent relationships once so every target sees a consistent tree
+        let parent_map = if self.kill_tree {
+            Some(list_parent_pids()?)
+        } else {
+            None
+        };
+        let target_pids: HashSet<u32> = processes_to_kill.iter().map(|p| p.pid).collect();
+
         for process in &processes_to_kill {
+            // The tree is only touched once the target itself can be signalled, so a failed
+            // kill doesn't leave the target running with its children gone
+            let probe = if self.dry_run { Ok(()) } else { can_signal(process.pid) };
+
+            // Descendants that are targets themselves get their own result entry
+            let mut signalled_descendants = Vec::new();
+            let descendants_killed = parent_map.as_ref().map(|map| {
+                if probe.is_err() {
+                    return Vec::new();
+                }
+                let descendants = collect_descendants(map, process.pid)
+                    .into_iter()
+                    .filter(|pid| !target_pids.contains(pid));
+                if self.dry_run {
+                    return descendants.collect();
+                }
+                let mut killed = Vec::new();
+                for pid in descendants {
+                    let name = get_process_info(pid).ok().flatten().map(|info| info.name);
+                    if kill_process(pid, signal).is_ok() {
+                        killed.push(pid);
+                        signalled_descendants.extend(name.map(|name| (pid, name)));
+                    }
+                }
+                killed
+            });
+
             let (success, error_message) = if self.dry_run {
                 // Dry run - don't actually kill
                 (true, None)
             } else {
                 // Actually kill the process
-                let kill_result = kill_process(process.pid, signal);
+                let kill_result = probe.and_then(|()| kill_process(process.pid, signal));
                 let success = kill_result.is_ok();
 
                 if success {
@@ -261,9 +349,20 @@ impl StatefulTool for KillTool {
                 success,
                 error_message,
                 command_line,
+                escalated: None,
+                descendants_killed,
+                descendants_escalated: None,
+                signalled_descendants,
             });
         }
 
+        // Give signalled processes until the deadline to exit, then force-kill the survivors
+        if self.escalate && !self.dry_run {
+            escalate_survivors(&mut results, &project_root, escalate_timeout_ms).await;
+            failed_count = results.iter().filter(|r| !r.success).count();
+            killed_count = results.len() - failed_count;
+        }
+
         // For dry run, we don't actually kill anything
         if self.dry_run {
             killed_count = 0;
@@ -282,6 +381,7 @@ impl StatefulTool for KillTool {
                 name_pattern: self.name_pattern.clone(),
                 signal: signal.to_string(),
                 max_processes,
+                escalate_timeout_ms: self.escalate.then_some(escalate_timeout_ms),
             },
         };
 
@@ -317,7 +417,19 @@ impl StatefulTool for KillTool {
                     response.push_str(&format!("    Command: {}\n", cmd.dimmed()));
                 }
 
+                if let Some(descendants) = process.descendants_killed.as_ref().filter(|d| !d.is_empty()) {
+                    let pids: Vec<String> = descendants.iter().map(|pid| pid.to_string()).collect();
+                    response.push_str(&format!("    Descendants: {}\n", pids.join(", ")));
+                }
+
                 response.push_str(&format!("    Signal to send: {}\n", signal.yellow()));
+                if self.escalate && signal != "KILL" {
+                    response.push_str(&format!(
+                        "    Escalates to {} if still running after {}ms\n",
+                        "KILL".yellow(),
+                        escalate_timeout_ms
+                    ));
+                }
                 response.push('\n');
             }
 
@@ -820,6 +932,197 @@ fn is_process_in_project_directory(
     }
 }
 
+/// Map each parent PID to its direct children
+fn list_parent_pids() -> Result<HashMap<u32, Vec<u32>>, CallToolError> {
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    {
+        let output = Command::new("ps")
+            .args(["-A", "-o", "pid=,ppid="])
+            .output()
+            .map_err(|e| {
+                CallToolError::from(tool_errors::invalid_input(
+                    TOOL_NAME,
+                    &format!("Failed to list processes: {}", e),
+                ))
+            })?;
+
+        if !output.status.success() {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!(
+                    "ps command failed: {}",
+                    String::from_utf8_lossy(&output.stderr)
+                ),
+            )));
+        }
+
+        Ok(parse_parent_pids(&String::from_utf8_lossy(&output.stdout)))
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        Err(CallToolError::from(tool_errors::invalid_input(
+            TOOL_NAME,
+            "kill_tree is not supported on this platform",
+        )))
+    }
+}
+
+/// Parse `pid ppid` lines into a parent -> children map
+fn parse_parent_pids(output: &str) -> HashMap<u32, Vec<u32>> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for line in output.lines() {
+        let mut fields = line.split_whitespace();
+        let pid = fields.next().and_then(|f| f.parse::<u32>().ok());
+        let ppid = fields.next().and_then(|f| f.parse::<u32>().ok());
+        if let (Some(pid), Some(ppid)) = (pid, ppid) {
+            children.entry(ppid).or_default().push(pid);
+        }
+    }
+    children
+}
+
+/// All descendants of `root` in post-order, so children come before their parents
+fn collect_descendants(children: &HashMap<u32, Vec<u32>>, root: u32) -> Vec<u32> {
+    fn visit(children: &HashMap<u32, Vec<u32>>, pid: u32, seen: &mut HashSet<u32>, out: &mut Vec<u32>) {
+        for &child in children.get(&pid).into_iter().flatten() {
+            if seen.insert(child) {
+                visit(children, child, seen, out);
+                out.push(child);
+            }
+        }
+    }
+
+    let mut seen = HashSet::from([root]);
+    let mut out = Vec::new();
+    visit(children, root, &mut seen, &mut out);
+    out
+}
+
+/// Wait for signalled processes and their signalled descendants to exit, and send KILL to any
+/// still running at the deadline, descendants first. Each surviving target is re-checked against
+/// the project directory, and each descendant against its name, in case its PID was reused.
+async fn escalate_survivors(
+    results: &mut [ProcessKillResult],
+    project_root: &PathBuf,
+    timeout_ms: u64,
+) {
+    let deadline =
+        tokio::time::Instant::now() + std::time::Duration::from_millis(timeout_ms);
+    // (result index, descendant PID), with None standing for the target itself
+    let mut pending: Vec<(usize, Option<u32>)> = results
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.success)
+        .flat_map(|(i, r)| {
+            r.signalled_descendants
+                .iter()
+                .map(move |&(pid, _)| (i, Some(pid)))
+                .chain(std::iter::once((i, None)))
+        })
+        .collect();
+
+    loop {
+        pending.retain(|&(i, descendant)| {
+            let alive = is_process_alive(descendant.unwrap_or(results[i].pid));
+            if !alive && descendant.is_none() {
+                results[i].escalated = Some(false);
+            }
+            alive
+        });
+        if pending.is_empty() || tokio::time::Instant::now() >= deadline {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(ESCALATE_POLL_INTERVAL_MS)).await;
+    }
+
+    // Descendants go first, like the initial signal
+    pending.sort_by_key(|&(_, descendant)| descendant.is_none());
+    for (i, descendant) in pending {
+        let result = &mut results[i];
+        if let Some(pid) = descendant {
+            let expected = result.signalled_descendants.iter().find(|(p, _)| *p == pid).map(|(_, name)| name);
+            let same_process = matches!(get_process_info(pid), Ok(Some(info)) if Some(&info.name) == expected);
+            if same_process && kill_process(pid, "KILL").is_ok() {
+                result.descendants_escalated.get_or_insert_with(Vec::new).push(pid);
+            }
+            continue;
+        }
+
+        // Killing its descendants may have let the target exit on its own
+        if !is_process_alive(result.pid) {
+            result.escalated = Some(false);
+            continue;
+        }
+        let still_in_project = matches!(
+            get_process_info(result.pid),
+            Ok(Some(info)) if is_process_in_project_directory(&info.cwd, project_root).unwrap_or(false)
+        );
+        if !still_in_project {
+            result.success = false;
+            result.error_message = Some(format!(
+                "Process {} is no longer within the project directory; not escalating to KILL",
+                result.pid
+            ));
+            continue;
+        }
+
+        result.escalated = Some(true);
+        if let Err(e) = kill_process(result.pid, "KILL") {
+            result.success = false;
+            result.error_message = Some(format!("Escalation to KILL failed: {}", e));
+        }
+    }
+}
+
+/// Whether a process is still running. Zombies count as exited since they can no longer be signalled.
+fn is_process_alive(pid: u32) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        match std::fs::read_to_string(format!("/proc/{}/stat", pid)) {
+            // The state follows the parenthesised command name, which may itself contain spaces
+            Ok(stat) => stat
+                .rsplit_once(')')
+                .and_then(|(_, rest)| rest.split_whitespace().next())
+                .is_some_and(|state| state != "Z" && state != "X"),
+            Err(_) => false,
+        }
+    }
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("ps")
+            .args(["-p", &pid.to_string(), "-o", "stat="])
+            .output()
+            .map(|output| {
+                let state = String::from_utf8_lossy(&output.stdout);
+                let state = state.trim();
+                !state.is_empty() && !state.starts_with('Z')
+            })
+            .unwrap_or(false)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        matches!(get_process_info(pid), Ok(Some(_)))
+    }
+}
+
+/// Check that `pid` exists and may be signalled, without sending anything
+fn can_signal(pid: u32) -> Result<(), std::io::Error> {
+    #[cfg(unix)]
+    {
+        // Signal 0 runs the existence and permission checks only
+        if unsafe { libc::kill(pid as libc::pid_t, 0) } == 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = pid;
+        Ok(())
+    }
+}
+
 fn kill_process(pid: u32, signal: &str) -> Result<(), std::io::Error> {
     #[cfg(any(target_os = "macos", target_os = "linux"))]
     {
@@ -918,5 +1221,68 @@ mod tests {
         assert!(!matches_pattern("python", "node"));
         assert!(!matches_pattern("ruby", "*node*"));
     }
-}
 
+    #[test]
+    fn test_collect_descendants_bottom_up() {
+        let children = parse_parent_pids("  1     0\n 10     1\n 11    10\n 12    10\n 13    11\n 20     1\n");
+
+        let descendants = collect_descendants(&children, 10);
+        assert_eq!(descendants.len(), 3);
+        let position = |pid| descendants.iter().position(|&p| p == pid).unwrap();
+        assert!(position(13) < position(11));
+        assert!(descendants.contains(&12));
+        assert!(!descendants.contains(&20));
+
+        assert!(collect_descendants(&children, 13).is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_kill_tree_escalates_descendants() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(project_root.clone());
+
+        // Both the shell and its child ignore TERM, so only KILL stops them
+        let mut root = std::process::Command::new("sh")
+            .args(["-c", "trap '' TERM; (trap '' TERM; exec sleep 30) & wait; exec sleep 30"])
+            .current_dir(&project_root)
+            .spawn()
+            .unwrap();
+        let pid = root.id();
+        let mut child = None;
+        for _ in 0..50 {
+            child = list_parent_pids().unwrap().get(&pid).and_then(|children| children.first().copied());
+            if child.is_some() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        let child = child.expect("child process did not start");
+
+        let tool = KillTool {
+            pid: Some(pid),
+            name_pattern: None,
+            signal: None,
+            dry_run: false,
+            max_processes: None,
+            preview_only: false,
+            force_confirmation: false,
+            escalate: true,
+            escalate_timeout_ms: Some(300),
+            kill_tree: true,
+        };
+        let result = tool.call_with_context(&context).await.unwrap();
+        let _ = root.wait();
+        let json: serde_json::Value = match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => serde_json::from_str(&text.text).unwrap(),
+            _ => panic!("Expected text content"),
+        };
+
+        let target = &json["results"][0];
+        assert_eq!(target["escalated"], true, "{}", json);
+        assert_eq!(target["descendants_killed"], serde_json::json!([child]));
+        assert_eq!(target["descendants_escalated"], serde_json::json!([child]));
+        assert!(!is_process_alive(child));
+    }
+}
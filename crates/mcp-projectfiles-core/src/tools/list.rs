@@ -3,15 +3,20 @@ use rust_mcp_schema::{
 };
 use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::SystemTime;
 use tokio::fs;
-use glob::{Pattern, MatchOptions};
+use glob::MatchOptions;
 use chrono::{DateTime, Local};
 use async_trait::async_trait;
+use base64::Engine;
+use sha2::{Digest, Sha256};
 use crate::config::tool_errors;
 use crate::context::{StatefulTool, ToolContext};
-use crate::tools::utils::{format_count, format_path, resolve_path_for_read};
+use crate::theme::{DiffTheme, EntryKind};
+use crate::tools::utils::{BoundedOutput, GlobPattern, format_count, format_path, resolve_path_for_read};
 
 const TOOL_NAME: &str = "list";
 
@@ -21,7 +26,9 @@ const TOOL_NAME: &str = "list";
 
 Examples:
 - {\"path\": \"src\", \"filter\": \"*.rs\"}
-- {\"path\": \".\", \"recursive\": true, \"show_metadata\": true}"
+- {\"path\": \".\", \"recursive\": true, \"show_metadata\": true}
+- {\"path\": \".\", \"recursive\": true, \"max_depth\": 2, \"max_results\": 200}
+- {\"path\": \".\", \"recursive\": true, \"page_size\": 500} then repeat with \"cursor\" set to the returned next_cursor"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct ListTool {
@@ -36,10 +43,15 @@ pub struct ListTool {
     #[serde(default)]
     pub filter: Option<String>,
     
-    /// Sort by: "name" (default), "size", "modified"
+    /// Sort by: "name" (default), "size", "modified", "extension", "type" (directories first).
+    /// Ties are broken by name
     #[serde(default = "default_sort_by")]
     pub sort_by: String,
     
+    /// Reverse the sort order, e.g. files before directories for "type" (default: false)
+    #[serde(default)]
+    pub reverse: bool,
+    
     /// Whether to show hidden files (files starting with dot) (default: false)
     #[serde(default)]
     pub show_hidden: bool,
@@ -51,6 +63,35 @@ pub struct ListTool {
     /// Follow symlinks to list directories outside the project directory (default: true)
     #[serde(default = "default_follow_symlinks")]
     pub follow_symlinks: bool,
+    
+    /// Maximum number of entries to return, applied after sorting (default: 1000)
+    #[serde(default = "default_max_results")]
+    pub max_results: u32,
+    
+    /// Maximum depth for recursive listing, 1 = direct children only (None = unlimited)
+    #[serde(default)]
+    pub max_depth: Option<u32>,
+    
+    /// Print bare paths terminated by NUL bytes instead of the newline-separated listing, like
+    /// `find -print0`. Ignored with show_metadata (default: false)
+    #[serde(default)]
+    pub null_separated: bool,
+    
+    /// Color directories, executables and symlinks with the diff theme (YOLO_PROJECTFILES_THEME).
+    /// Leave off for programmatic consumers, since the escape codes end up in the text (default: false)
+    #[serde(default)]
+    pub color: bool,
+    
+    /// next_cursor from the previous page, to continue a paginated listing. Other arguments
+    /// must match the call that returned it. Pages after the first come from the listing as
+    /// it was when the first page was taken
+    #[serde(default)]
+    pub cursor: Option<String>,
+    
+    /// Entries per page. Setting it or cursor pages through the whole listing instead of
+    /// cutting it at max_results, with a next_cursor while entries remain (default: max_results)
+    #[serde(default)]
+    pub page_size: Option<u32>,
 }
 
 fn default_sort_by() -> String {
@@ -61,11 +102,76 @@ fn default_follow_symlinks() -> bool {
     true
 }
 
-#[derive(Debug)]
+fn default_max_results() -> u32 {
+    1000
+}
+
+/// Where a paginated listing resumes, handed to the client as an opaque base64 string
+#[derive(Serialize, Deserialize, Debug)]
+struct ListCursor {
+    /// Fingerprint of the arguments that shape the listing, see [`ListTool::query_fingerprint`]
+    query: String,
+    /// Name of the last entry returned
+    after: String,
+    /// Entries returned so far, used when the last entry has since disappeared
+    offset: usize,
+}
+
+impl ListCursor {
+    fn encode(&self) -> String {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    fn decode(cursor: &str) -> Option<Self> {
+        let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(cursor.trim()).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+/// Result metadata carrying `next_cursor`, when there is one
+fn cursor_meta(next_cursor: Option<&str>) -> Option<serde_json::Map<String, serde_json::Value>> {
+    next_cursor.map(|cursor| {
+        let mut meta = serde_json::Map::new();
+        meta.insert("next_cursor".to_string(), serde_json::Value::from(cursor));
+        meta
+    })
+}
+
+/// Write entry lines to `output` until it is full, returning how many entries were shown.
+/// An entry larger than the whole cap is shown truncated rather than skipped, so every page
+/// moves the listing forward
+fn push_entries(
+    output: &mut BoundedOutput,
+    lines: impl IntoIterator<Item = Result<String, CallToolError>>,
+) -> Result<usize, CallToolError> {
+    let mut emitted = 0;
+    for line in lines {
+        let fits = output.push_str(&line?);
+        if fits || emitted == 0 {
+            emitted += 1;
+        }
+        if !fits {
+            break;
+        }
+    }
+    Ok(emitted)
+}
+
+/// The sorted listing behind a paginated call, kept in the session so later pages are cut
+/// from it instead of walking and sorting the tree again
+#[derive(Default)]
+struct ListingSnapshot {
+    /// Fingerprint of the listing, see [`ListTool::query_fingerprint`]
+    query: String,
+    entries: Arc<Vec<FileEntry>>,
+}
+
+#[derive(Debug, Clone)]
 struct FileEntry {
     name: String,
     _path: PathBuf,
     is_dir: bool,
+    kind: EntryKind,
     size: u64,
     modified: SystemTime,
     #[cfg(unix)]
@@ -74,7 +180,9 @@ struct FileEntry {
 
 #[async_trait]
 impl StatefulTool for ListTool {
-    async fn call_with_context(self, context: &ToolContext) -> Result<CallToolResult, CallToolError> {
+    async fn call_with_context(mut self, context: &ToolContext) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
         let project_root = context.get_project_root()
             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
         
@@ -83,6 +191,13 @@ impl StatefulTool for ListTool {
         
         let path = &canonical_path;
 
+        if self.max_depth == Some(0) {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                "max_depth must be at least 1"
+            )));
+        }
+
         if !path.is_dir() {
             return Err(CallToolError::from(tool_errors::invalid_input(
                 TOOL_NAME,
@@ -92,46 +207,80 @@ impl StatefulTool for ListTool {
 
         // Prepare glob pattern if provided
         let glob_pattern = self.filter.as_ref().map(|f| {
-            Pattern::new(f)
+            GlobPattern::new(f)
                 .map_err(|e| CallToolError::from(tool_errors::pattern_error(TOOL_NAME, f, &e.to_string())))
         }).transpose()?;
 
-        let mut entries = if self.recursive {
-            self.list_recursive(path, &project_root, &glob_pattern).await?
+        // Later pages reuse the sorted listing from the first page instead of walking again
+        let query = self.query_fingerprint(&canonical_path);
+        let snapshot = match &self.cursor {
+            Some(_) => context.get_custom_state::<ListingSnapshot>().await
+                .filter(|snapshot| snapshot.query == query)
+                .map(|snapshot| snapshot.entries.clone()),
+            None => None,
+        };
+        let sorted = match snapshot {
+            Some(entries) => entries,
+            None => Arc::new(self.sorted_entries(path, &project_root, &glob_pattern).await?),
+        };
+
+        // Pages and the cap are both cut from the sorted list, so they are deterministic
+        let paginated = self.cursor.is_some() || self.page_size.is_some();
+        let mut start = 0;
+        let mut more_pages = false;
+        let (entries, limited, snapshot) = if paginated {
+            let page_size = self.page_size.unwrap_or(self.max_results).max(1) as usize;
+            if let Some(cursor) = &self.cursor {
+                start = self.resume_position(cursor, &canonical_path, &sorted)?;
+            }
+            let end = sorted.len().min(start + page_size);
+            more_pages = end < sorted.len();
+            (sorted[start..end].to_vec(), false, Some(sorted))
         } else {
-            self.list_directory(path, &glob_pattern).await?
+            let mut entries = Arc::unwrap_or_clone(sorted);
+            let limited = entries.len() > self.max_results as usize;
+            entries.truncate(self.max_results as usize);
+            (entries, limited, None)
         };
 
-        // Sort entries based on sort_by parameter
-        match self.sort_by.as_str() {
-            "name" => entries.sort_by(|a, b| a.name.cmp(&b.name)),
-            "size" => entries.sort_by(|a, b| {
-                // Directories first, then by size
-                match (a.is_dir, b.is_dir) {
-                    (true, false) => std::cmp::Ordering::Less,
-                    (false, true) => std::cmp::Ordering::Greater,
-                    _ => a.size.cmp(&b.size),
-                }
-            }),
-            "modified" => entries.sort_by(|a, b| a.modified.cmp(&b.modified)),
-            _ => return Err(CallToolError::from(tool_errors::invalid_input(
-                TOOL_NAME,
-                &format!("Invalid sort_by value '{}'. Use 'name', 'size', or 'modified'", self.sort_by)
-            ))),
+        // Bare NUL-terminated paths for piping, without the summary
+        if self.null_separated && !self.show_metadata {
+            let mut output = BoundedOutput::new();
+            let emitted = push_entries(&mut output, entries.iter().map(|entry| Ok(format!("{}\0", entry.name))))?;
+            let next_cursor = paginated
+                .then(|| self.next_cursor(&canonical_path, &entries, start, emitted, more_pages))
+                .flatten();
+            Self::store_snapshot(context, query, snapshot, &next_cursor).await;
+            let meta = cursor_meta(next_cursor.as_deref());
+            return Ok(CallToolResult {
+                content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                    output.into_string(), None,
+                ))],
+                is_error: Some(false),
+                meta,
+            });
         }
 
         // Format output
-        let mut output_lines = Vec::new();
-        for entry in &entries {
+        let theme = if self.color { DiffTheme::current() } else { DiffTheme::None };
+        let mut output = BoundedOutput::new();
+        let lines = entries.iter().enumerate().map(|(i, entry)| {
             let line = if self.show_metadata {
-                self.format_with_metadata(entry)?
+                self.format_with_metadata(entry, theme)?
             } else {
-                self.format_simple(entry)
+                self.format_simple(entry, theme)
             };
-            output_lines.push(line);
-        }
-
-        let listing = output_lines.join("\n");
+            let separator = if i > 0 { "\n" } else { "" };
+            Ok(format!("{}{}", separator, line))
+        });
+        let emitted = push_entries(&mut output, lines)?;
+        let next_cursor = paginated
+            .then(|| self.next_cursor(&canonical_path, &entries, start, emitted, more_pages))
+            .flatten();
+        Self::store_snapshot(context, query, snapshot, &next_cursor).await;
+        let meta = cursor_meta(next_cursor.as_deref());
+
+        let listing = output.into_string();
         
         // Add summary
         let _file_count = entries.iter().filter(|e| !e.is_dir).count();
@@ -140,10 +289,16 @@ impl StatefulTool for ListTool {
         let relative_path = canonical_path.strip_prefix(&project_root)
             .unwrap_or(&canonical_path);
         
-        let summary = format!("\nListed {} in {}", 
-            format_count(entries.len(), "item", "items"),
+        let mut summary = format!("\nListed {} in {}", 
+            format_count(emitted, "item", "items"),
             format_path(relative_path)
         );
+        if limited {
+            summary.push_str(&format!("\n[limited to {} entries]", self.max_results));
+        }
+        if let Some(cursor) = &next_cursor {
+            summary.push_str(&format!("\n[more entries: pass cursor \"{}\" for the next page]", cursor));
+        }
         
         let final_output = if !listing.is_empty() {
             format!("{}{}", listing, summary)
@@ -156,7 +311,7 @@ impl StatefulTool for ListTool {
                 final_output, None,
             ))],
             is_error: Some(false),
-            meta: None,
+            meta,
         })
     }
 }
@@ -167,7 +322,110 @@ impl ListTool {
         StatefulTool::call_with_context(self, &context).await
     }
 
-    async fn list_directory(&self, path: &Path, glob_pattern: &Option<Pattern>) -> Result<Vec<FileEntry>, CallToolError> {
+    /// Hash of the arguments that decide which entries are listed and in what order, so a
+    /// cursor is only accepted by the listing it came from
+    fn query_fingerprint(&self, path: &Path) -> String {
+        let query = serde_json::json!([
+            path.display().to_string(),
+            self.recursive,
+            self.filter,
+            self.sort_by,
+            self.reverse,
+            self.show_hidden,
+            self.follow_symlinks,
+            self.max_depth,
+        ]);
+        let digest = Sha256::digest(query.to_string().as_bytes());
+        digest[..8].iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    /// Cursor for the page after `page`, which starts at `start` in the full listing. Only
+    /// the first `emitted` entries made it into the output before the byte cap, so the next
+    /// page resumes after the last of those; `None` when the listing is complete
+    fn next_cursor(&self, path: &Path, page: &[FileEntry], start: usize, emitted: usize, more_pages: bool) -> Option<String> {
+        if emitted == page.len() && !more_pages {
+            return None;
+        }
+        let last = page.get(emitted.checked_sub(1)?)?;
+        Some(
+            ListCursor {
+                query: self.query_fingerprint(path),
+                after: last.name.clone(),
+                offset: start + emitted,
+            }
+            .encode(),
+        )
+    }
+
+    /// Walk the listing and sort it by `sort_by`, with names breaking ties
+    async fn sorted_entries(&self, path: &Path, project_root: &Path, glob_pattern: &Option<GlobPattern>) -> Result<Vec<FileEntry>, CallToolError> {
+        let mut entries = if self.recursive {
+            self.list_recursive(path, project_root, glob_pattern).await?
+        } else {
+            self.list_directory(path, glob_pattern).await?
+        };
+
+        // Sort entries based on sort_by parameter
+        let primary: fn(&FileEntry, &FileEntry) -> Ordering = match self.sort_by.as_str() {
+            "name" => |a, b| a.name.cmp(&b.name),
+            "size" => |a, b| {
+                // Directories first, then by size
+                b.is_dir.cmp(&a.is_dir).then(a.size.cmp(&b.size))
+            },
+            "modified" => |a, b| a.modified.cmp(&b.modified),
+            "extension" => |a, b| {
+                // Directories first, then files grouped by extension
+                b.is_dir.cmp(&a.is_dir).then_with(|| extension_of(&a.name).cmp(&extension_of(&b.name)))
+            },
+            "type" => |a, b| b.is_dir.cmp(&a.is_dir),
+            _ => return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Invalid sort_by value '{}'. Use 'name', 'size', 'modified', 'extension', or 'type'", self.sort_by)
+            ))),
+        };
+        entries.sort_by(|a, b| {
+            let ordering = primary(a, b);
+            let ordering = if self.reverse { ordering.reverse() } else { ordering };
+            // Secondary name ordering keeps results stable
+            ordering.then_with(|| a.name.cmp(&b.name))
+        });
+
+        Ok(entries)
+    }
+
+    /// Keep the sorted listing for the next page, or drop it once the last page is out
+    async fn store_snapshot(context: &ToolContext, query: String, snapshot: Option<Arc<Vec<FileEntry>>>, next_cursor: &Option<String>) {
+        let Some(entries) = snapshot else {
+            return;
+        };
+        let snapshot = if next_cursor.is_some() {
+            ListingSnapshot { query, entries }
+        } else {
+            ListingSnapshot::default()
+        };
+        context.set_custom_state(snapshot).await;
+    }
+
+    /// Index of the first entry after `cursor` in the sorted `entries`
+    fn resume_position(&self, cursor: &str, path: &Path, entries: &[FileEntry]) -> Result<usize, CallToolError> {
+        let cursor = ListCursor::decode(cursor).ok_or_else(|| {
+            CallToolError::from(tool_errors::invalid_input(TOOL_NAME, "Invalid cursor. Use the next_cursor of a previous page"))
+        })?;
+        if cursor.query != self.query_fingerprint(path) {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                "Cursor belongs to a listing with different arguments. Repeat the original arguments or start again without a cursor",
+            )));
+        }
+        // Continue after the last entry returned; if it is gone, fall back to the count so far
+        Ok(entries
+            .iter()
+            .position(|entry| entry.name == cursor.after)
+            .map_or(cursor.offset, |index| index + 1)
+            .min(entries.len()))
+    }
+
+    async fn list_directory(&self, path: &Path, glob_pattern: &Option<GlobPattern>) -> Result<Vec<FileEntry>, CallToolError> {
         let mut entries_stream = fs::read_dir(path)
             .await
             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read directory: {}", e))))?;
@@ -207,6 +465,7 @@ impl ListTool {
                 name: file_name,
                 _path: entry.path(),
                 is_dir: metadata.is_dir(),
+                kind: EntryKind::from_metadata(&metadata),
                 size: metadata.len(),
                 modified: metadata.modified()
                     .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get modified time: {}", e))))?,
@@ -221,11 +480,11 @@ impl ListTool {
         Ok(entries)
     }
 
-    async fn list_recursive(&self, path: &Path, _project_root: &Path, glob_pattern: &Option<Pattern>) -> Result<Vec<FileEntry>, CallToolError> {
+    async fn list_recursive(&self, path: &Path, _project_root: &Path, glob_pattern: &Option<GlobPattern>) -> Result<Vec<FileEntry>, CallToolError> {
         let mut all_entries = Vec::new();
-        let mut dirs_to_process = vec![path.to_path_buf()];
+        let mut dirs_to_process = vec![(path.to_path_buf(), 1u32)];
 
-        while let Some(current_dir) = dirs_to_process.pop() {
+        while let Some((current_dir, depth)) = dirs_to_process.pop() {
             let mut entries_stream = fs::read_dir(&current_dir)
                 .await
                 .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read directory '{}': {}", current_dir.display(), e))))?;
@@ -274,6 +533,7 @@ impl ListTool {
                         name: relative_path,
                         _path: entry_path.clone(),
                         is_dir: true,
+                        kind: EntryKind::from_metadata(&metadata),
                         size: 0, // Directories don't have meaningful size
                         modified: metadata.modified()
                             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get modified time: {}", e))))?,
@@ -285,12 +545,15 @@ impl ListTool {
                     });
                     
                     // Add to dirs to process for recursion
-                    dirs_to_process.push(entry_path);
+                    if depth < self.max_depth.unwrap_or(u32::MAX) {
+                        dirs_to_process.push((entry_path, depth + 1));
+                    }
                 } else if should_include {
                     all_entries.push(FileEntry {
                         name: relative_path,
                         _path: entry_path,
                         is_dir: false,
+                        kind: EntryKind::from_metadata(&metadata),
                         size: metadata.len(),
                         modified: metadata.modified()
                             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get modified time: {}", e))))?,
@@ -307,12 +570,12 @@ impl ListTool {
         Ok(all_entries)
     }
 
-    fn format_simple(&self, entry: &FileEntry) -> String {
+    fn format_simple(&self, entry: &FileEntry, theme: DiffTheme) -> String {
         let type_indicator = if entry.is_dir { "[DIR]" } else { "[FILE]" };
-        format!("{} {}", type_indicator, entry.name)
+        format!("{} {}", type_indicator, theme.colorize_entry(&entry.name, entry.kind))
     }
 
-    fn format_with_metadata(&self, entry: &FileEntry) -> Result<String, CallToolError> {
+    fn format_with_metadata(&self, entry: &FileEntry, theme: DiffTheme) -> Result<String, CallToolError> {
         let type_indicator = if entry.is_dir { "[DIR]" } else { "[FILE]" };
         
         // Format size
@@ -338,11 +601,19 @@ impl ListTool {
             size_str,
             perms_str,
             modified_str,
-            entry.name
+            theme.colorize_entry(&entry.name, entry.kind)
         ))
     }
 }
 
+/// Lowercased extension of an entry name, empty when there is none
+fn extension_of(name: &str) -> String {
+    Path::new(name)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
 fn format_size(size: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
     let mut size_f = size as f64;
@@ -390,4 +661,26 @@ fn format_permissions(mode: u32) -> String {
     );
 
     format!("{}{}{}{}", file_type, user, group, other)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_entries_never_skips_an_oversized_entry() {
+        let lines = |names: &[&str]| names.iter().map(|name| Ok(name.to_string())).collect::<Vec<_>>();
+
+        let mut output = BoundedOutput::with_limit(8);
+        assert_eq!(push_entries(&mut output, lines(&["a\n", "b\n"])).unwrap(), 2);
+
+        // The first entry alone overflows the cap: it is shown cut short and counted
+        let mut output = BoundedOutput::with_limit(8);
+        assert_eq!(push_entries(&mut output, lines(&["0123456789", "b"])).unwrap(), 1);
+        assert!(output.into_string().starts_with("01234567\n[output truncated"));
+
+        // Later entries that do not fit are left for the next page
+        let mut output = BoundedOutput::with_limit(8);
+        assert_eq!(push_entries(&mut output, lines(&["abc\n", "0123456789"])).unwrap(), 1);
+    }
+}
@@ -7,14 +7,14 @@ use rust_mcp_schema::{
 };
 use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 use tokio::fs;
 
 const TOOL_NAME: &str = "mkdir";
 
 #[mcp_tool(
     name = "mkdir", 
-    description = "Create directories with auto parent creation. Supports Unix permissions.
+    description = "Create directories with auto parent creation. Supports Unix permissions. Reports only the directories actually created.
 Examples: {\"path\": \"src/utils\"}, {\"path\": \"scripts\", \"mode\": \"755\"}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
@@ -24,7 +24,7 @@ pub struct MkdirTool {
     /// Whether to create parent directories if they don't exist (default: true)
     #[serde(default = "default_create_parents")]
     pub parents: bool,
-    /// File permissions mode in octal (e.g., "755"). Platform-specific.
+    /// File permissions mode in octal (e.g., "755") applied to the target directory. Platform-specific.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mode: Option<String>,
 }
@@ -36,9 +36,13 @@ fn default_create_parents() -> bool {
 #[async_trait]
 impl StatefulTool for MkdirTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
+        context.ensure_writable(TOOL_NAME)?;
+
         let project_root = context.get_project_root()
             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
             
@@ -87,24 +91,7 @@ impl StatefulTool for MkdirTool {
             
             // Also ensure the absolute path itself would be within bounds
             // This prevents creating directories with names like "../outside"
-            let normalized = absolute_path.components()
-                .fold(std::path::PathBuf::new(), |mut acc, comp| {
-                    match comp {
-                        std::path::Component::ParentDir => {
-                            acc.pop();
-                        }
-                        std::path::Component::Normal(name) => {
-                            acc.push(name);
-                        }
-                        std::path::Component::RootDir => {
-                            acc.push("/");
-                        }
-                        _ => {}
-                    }
-                    acc
-                });
-            
-            if !current_dir.join(&normalized).starts_with(&current_dir) {
+            if !current_dir.join(normalize_lexically(&absolute_path)).starts_with(&current_dir) {
                 return Err(CallToolError::from(tool_errors::access_denied(
                     TOOL_NAME,
                     &self.path,
@@ -113,6 +100,9 @@ impl StatefulTool for MkdirTool {
             }
         }
         
+        // Work on the normalized path from here on, so `a/../b` creates and reports only `b`
+        let absolute_path = normalize_lexically(&absolute_path);
+        
         // Check if already exists
         if absolute_path.exists() {
             let metadata = fs::metadata(&absolute_path)
@@ -135,43 +125,80 @@ impl StatefulTool for MkdirTool {
             }
         }
         
-        // Create the directory
-        if self.parents {
-            fs::create_dir_all(&absolute_path)
-                .await
-                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to create directory: {}", e))))?;
-        } else {
-            fs::create_dir(&absolute_path)
-                .await
-                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to create directory: {}", e))))?;
+        // Validate the mode before touching the filesystem
+        let mode = match &self.mode {
+            Some(mode_str) => Some(u32::from_str_radix(mode_str, 8).map_err(|_| {
+                CallToolError::from(tool_errors::invalid_input(
+                    TOOL_NAME,
+                    &format!("Invalid mode '{}'. Must be an octal number like '755'", mode_str)
+                ))
+            })?),
+            None => None,
+        };
+        
+        // Collect the missing directories, outermost first
+        let mut missing = Vec::new();
+        let mut check_path = absolute_path.as_path();
+        while !check_path.exists() {
+            missing.push(check_path.to_path_buf());
+            match check_path.parent() {
+                Some(parent) => check_path = parent,
+                None => break,
+            }
+        }
+        missing.reverse();
+        
+        if !self.parents && missing.len() > 1 {
+            let first_missing = missing[0].strip_prefix(&current_dir).unwrap_or(&missing[0]);
+            return Err(CallToolError::from(tool_errors::file_not_found(
+                TOOL_NAME,
+                &format!(
+                    "Parent directory {} not found. Set parents=true to create it",
+                    format_path(first_missing)
+                )
+            )));
+        }
+        
+        // Create each missing directory in turn, tracking what was actually created
+        let mut created: Vec<PathBuf> = Vec::new();
+        for dir in &missing {
+            match fs::create_dir(dir).await {
+                Ok(()) => created.push(dir.clone()),
+                // Another process may have created it in the meantime
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && dir.is_dir() => {}
+                Err(e) => {
+                    return Err(CallToolError::from(tool_errors::invalid_input(
+                        TOOL_NAME,
+                        &format!("Failed to create directory: {}", e)
+                    )));
+                }
+            }
         }
         
         // Set permissions if specified (Unix-like systems only)
         #[cfg(unix)]
-        if let Some(mode_str) = &self.mode {
+        if let Some(mode) = mode {
             use std::os::unix::fs::PermissionsExt;
             
-            if let Ok(mode) = u32::from_str_radix(mode_str, 8) {
-                let permissions = std::fs::Permissions::from_mode(mode);
-                fs::set_permissions(&absolute_path, permissions)
-                    .await
-                    .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to set permissions: {}", e))))?;
-            } else {
-                return Err(CallToolError::from(tool_errors::invalid_input(
-                    TOOL_NAME,
-                    &format!("Invalid mode '{}'. Must be an octal number like '755'", mode_str)
-                )));
-            }
+            let permissions = std::fs::Permissions::from_mode(mode);
+            fs::set_permissions(&absolute_path, permissions)
+                .await
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to set permissions: {}", e))))?;
         }
+        #[cfg(not(unix))]
+        let _ = mode;
         
-        // Format path relative to project root
-        let relative_path = absolute_path.strip_prefix(&current_dir)
-            .unwrap_or(&absolute_path);
+        // Format paths relative to project root
+        let relative = |path: &Path| format_path(path.strip_prefix(&current_dir).unwrap_or(path));
         
-        let message = if self.parents {
-            format!("Created directory {} (with parents)", format_path(relative_path))
+        let message = if created.len() == 1 {
+            format!("Created directory {}", relative(&created[0]))
         } else {
-            format!("Created directory {}", format_path(relative_path))
+            let mut message = format!("Created {} directories:", created.len());
+            for dir in &created {
+                message.push_str(&format!("\n  {}", relative(dir)));
+            }
+            message
         };
 
         Ok(CallToolResult {
@@ -184,6 +211,21 @@ impl StatefulTool for MkdirTool {
     }
 }
 
+/// Drop `.` and apply `..` without touching the filesystem
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,6 +297,75 @@ mod tests {
         
         let error_msg = format!("{:?}", result.unwrap_err());
         assert!(error_msg.contains("No such file") || error_msg.contains("not found"));
+        
+        // Nothing should have been created
+        let project_root = context.get_project_root().unwrap();
+        assert!(!project_root.join("nonexistent").exists());
+    }
+    
+    #[tokio::test]
+    async fn test_mkdir_reports_only_created_directories() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let project_root = context.get_project_root().unwrap();
+        fs::create_dir(project_root.join("existing")).await.unwrap();
+        
+        let mkdir_tool = MkdirTool {
+            path: "existing/new/leaf".to_string(),
+            parents: true,
+            mode: None,
+        };
+        
+        let result = mkdir_tool.call_with_context(&context).await.unwrap();
+        let text = match &result.content[0] {
+            CallToolResultContentItem::TextContent(t) => t.text.clone(),
+            _ => panic!("Expected text content"),
+        };
+        
+        assert!(text.contains("Created 2 directories"));
+        assert!(text.contains("'existing/new'"));
+        assert!(text.contains("'existing/new/leaf'"));
+        assert!(!text.contains("\n  'existing'\n"));
+        assert!(project_root.join("existing/new/leaf").is_dir());
+    }
+    
+    #[tokio::test]
+    async fn test_mkdir_normalizes_parent_components() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let mkdir_tool = MkdirTool {
+            path: "a/../b".to_string(),
+            parents: true,
+            mode: None,
+        };
+        
+        let result = mkdir_tool.call_with_context(&context).await.unwrap();
+        let text = match &result.content[0] {
+            CallToolResultContentItem::TextContent(t) => t.text.clone(),
+            _ => panic!("Expected text content"),
+        };
+        
+        assert_eq!(text, "Created directory 'b'");
+        let project_root = context.get_project_root().unwrap();
+        assert!(project_root.join("b").is_dir());
+        assert!(!project_root.join("a").exists());
+    }
+    
+    #[tokio::test]
+    async fn test_mkdir_invalid_mode_creates_nothing() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let mkdir_tool = MkdirTool {
+            path: "bad_mode".to_string(),
+            parents: true,
+            mode: Some("9z9".to_string()),
+        };
+        
+        let result = mkdir_tool.call_with_context(&context).await;
+        assert!(result.is_err());
+        
+        let project_root = context.get_project_root().unwrap();
+        assert!(!project_root.join("bad_mode").exists());
     }
     
     #[tokio::test]
@@ -16,7 +16,7 @@ const TOOL_NAME: &str = "move";
 
 #[mcp_tool(
     name = "move", 
-    description = "Move or rename files/directories. Supports overwrite, metadata preservation, dry run preview.
+    description = "Move or rename files/directories. Falls back to copy+delete across filesystems. Supports overwrite, metadata preservation, dry run preview.
 Examples: {\"source\": \"old.txt\", \"destination\": \"new.txt\"}, {\"source\": \"file.txt\", \"destination\": \"archive/file.txt\"}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
@@ -57,12 +57,109 @@ async fn calculate_dir_size(path: &Path) -> std::io::Result<u64> {
     Ok(total_size)
 }
 
+/// Check whether a rename failed because source and destination are on different filesystems
+fn is_cross_device_error(e: &std::io::Error) -> bool {
+    e.kind() == std::io::ErrorKind::CrossesDevices
+        || (cfg!(unix) && e.raw_os_error() == Some(18))
+        || (cfg!(target_os = "windows") && e.raw_os_error() == Some(17))
+}
+
+/// Copy a file or directory tree to the destination, preserving permissions and timestamps
+fn copy_recursive<'a>(
+    src: &'a Path,
+    dst: &'a Path,
+) -> std::pin::Pin<Box<dyn std::future::Future<Output = std::io::Result<()>> + Send + 'a>> {
+    Box::pin(async move {
+        let metadata = fs::symlink_metadata(src).await?;
+        
+        if metadata.is_dir() {
+            fs::create_dir_all(dst).await?;
+            let mut entries = fs::read_dir(src).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                copy_recursive(&entry.path(), &dst.join(entry.file_name())).await?;
+            }
+            fs::set_permissions(dst, metadata.permissions()).await?;
+        } else if metadata.file_type().is_symlink() {
+            let target = fs::read_link(src).await?;
+            #[cfg(unix)]
+            fs::symlink(&target, dst).await?;
+            #[cfg(not(unix))]
+            fs::copy(src, dst).await.map(|_| ())?;
+            return Ok(());
+        } else {
+            // fs::copy carries the permission bits over
+            fs::copy(src, dst).await?;
+        }
+        
+        filetime::set_file_times(
+            dst,
+            filetime::FileTime::from_last_access_time(&metadata),
+            filetime::FileTime::from_last_modification_time(&metadata),
+        )
+    })
+}
+
+/// A hidden, unique path next to `path`, so it is on the same filesystem and can be renamed over it
+fn sibling_temp_path(path: &Path, purpose: &str) -> PathBuf {
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    path.with_file_name(format!(".{}.{}.{}", name, uuid::Uuid::new_v4().simple(), purpose))
+}
+
+async fn remove_any(path: &Path) -> std::io::Result<()> {
+    if fs::symlink_metadata(path).await?.is_dir() {
+        fs::remove_dir_all(path).await
+    } else {
+        fs::remove_file(path).await
+    }
+}
+
+/// Move across filesystems by copying the source next to the destination, renaming the
+/// copy into place and then deleting the source. An existing destination is only
+/// replaced once the copy is complete, so a failed copy leaves both sides untouched.
+async fn move_across_devices(src: &Path, dst: &Path, overwrite: bool) -> std::io::Result<()> {
+    let staged = sibling_temp_path(dst, "tmp");
+    if let Err(e) = copy_recursive(src, &staged).await {
+        // Don't leave a partial copy behind; the source is still intact
+        let _ = remove_any(&staged).await;
+        return Err(e);
+    }
+    
+    // rename() replaces files atomically but can't replace a non-empty directory,
+    // so an existing directory is set aside first and restored if the swap fails
+    let displaced = match fs::symlink_metadata(dst).await {
+        Ok(metadata) if overwrite && metadata.is_dir() => {
+            let displaced = sibling_temp_path(dst, "old");
+            fs::rename(dst, &displaced).await?;
+            Some(displaced)
+        }
+        _ => None,
+    };
+    
+    if let Err(e) = fs::rename(&staged, dst).await {
+        if let Some(displaced) = &displaced {
+            let _ = fs::rename(displaced, dst).await;
+        }
+        let _ = remove_any(&staged).await;
+        return Err(e);
+    }
+    if let Some(displaced) = displaced {
+        fs::remove_dir_all(displaced).await?;
+    }
+    
+    remove_any(src).await
+}
+
 #[async_trait]
 impl StatefulTool for MoveTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.source = context.resolve_in_working_dir(&self.source);
+        self.destination = context.resolve_in_working_dir(&self.destination);
+
+        context.ensure_writable(TOOL_NAME)?;
+
         let current_dir = context.get_project_root()
             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
         
@@ -192,28 +289,34 @@ impl StatefulTool for MoveTool {
             source_metadata.len()
         };
         
+        let mut crossed_devices = false;
+        
         // Perform the move or simulate it for dry run
         if !self.dry_run {
-            fs::rename(&canonical_source, &canonical_dest)
-                .await
-                .map_err(|e| {
-                    // Provide more context about the failure
-                    let error_context = if e.kind() == std::io::ErrorKind::PermissionDenied {
-                        "Permission denied. Check file permissions and ownership."
-                    } else if e.kind() == std::io::ErrorKind::NotFound {
-                        "Source file was removed or destination parent directory doesn't exist."
-                    } else if cfg!(target_os = "windows") && e.raw_os_error() == Some(17) {
-                        "Cross-device move not supported. Source and destination must be on the same drive."
-                    } else if cfg!(unix) && e.raw_os_error() == Some(18) {
-                        "Cross-device move not supported. Source and destination must be on the same filesystem."
-                    } else {
-                        "Operation failed. This might be due to filesystem limitations or permissions."
-                    };
-                    
-                    CallToolError::from(tool_errors::invalid_input(TOOL_NAME, 
-                        &format!("Failed to move '{}' to '{}': {} {}", 
-                            self.source, self.destination, e, error_context)))
-                })?;
+            let rename_result = match fs::rename(&canonical_source, &canonical_dest).await {
+                Err(e) if is_cross_device_error(&e) => {
+                    crossed_devices = true;
+                    move_across_devices(&canonical_source, &canonical_dest, self.overwrite).await
+                }
+                other => other,
+            };
+            
+            rename_result.map_err(|e| {
+                // Provide more context about the failure
+                let error_context = if e.kind() == std::io::ErrorKind::PermissionDenied {
+                    "Permission denied. Check file permissions and ownership."
+                } else if e.kind() == std::io::ErrorKind::NotFound {
+                    "Source file was removed or destination parent directory doesn't exist."
+                } else if crossed_devices {
+                    "Cross-filesystem copy and delete fallback failed."
+                } else {
+                    "Operation failed. This might be due to filesystem limitations or permissions."
+                };
+                
+                CallToolError::from(tool_errors::invalid_input(TOOL_NAME, 
+                    &format!("Failed to move '{}' to '{}': {} {}", 
+                        self.source, self.destination, e, error_context)))
+            })?;
             
             // Restore metadata if requested
             if self.preserve_metadata {
@@ -256,6 +359,10 @@ impl StatefulTool for MoveTool {
                 written_files_clone.insert(canonical_dest.clone());
                 context.set_custom_state(written_files_clone).await;
             }
+
+            // Both the vacated source and the new destination count as modified
+            context.record_modified(&canonical_source).await;
+            context.record_modified(&canonical_dest).await;
         }
         
         let _duration = start_time.elapsed();
@@ -275,6 +382,9 @@ impl StatefulTool for MoveTool {
         if self.preserve_metadata {
             metrics_parts.push("metadata preserved".to_string());
         }
+        if crossed_devices {
+            metrics_parts.push("copied across filesystems".to_string());
+        }
         
         let metrics = if !metrics_parts.is_empty() {
             format!(" ({})", metrics_parts.join(", "))
@@ -725,4 +835,100 @@ mod tests {
         let existing_content = fs::read_to_string(project_root.join("destdir/file.txt")).await.unwrap();
         assert_eq!(existing_content, "Old content");
     }
+    
+    #[tokio::test]
+    async fn test_move_across_devices_fallback_directory() {
+        let (context, _temp_dir) = setup_test_context().await;
+        let project_root = context.get_project_root().unwrap();
+        
+        // Exercise the copy+delete fallback directly, since a real cross-device
+        // rename can't be staged inside a single temp directory
+        let source_dir = project_root.join("src_dir");
+        fs::create_dir_all(source_dir.join("nested")).await.unwrap();
+        fs::write(source_dir.join("a.txt"), "alpha").await.unwrap();
+        fs::write(source_dir.join("nested/b.txt"), "beta").await.unwrap();
+        
+        let old_time = filetime::FileTime::from_unix_time(1_600_000_000, 0);
+        filetime::set_file_mtime(source_dir.join("a.txt"), old_time).unwrap();
+        
+        let dest_dir = project_root.join("dest_dir");
+        move_across_devices(&source_dir, &dest_dir, false).await.unwrap();
+        
+        assert!(!source_dir.exists());
+        assert_eq!(fs::read_to_string(dest_dir.join("a.txt")).await.unwrap(), "alpha");
+        assert_eq!(fs::read_to_string(dest_dir.join("nested/b.txt")).await.unwrap(), "beta");
+        
+        let metadata = std::fs::metadata(dest_dir.join("a.txt")).unwrap();
+        assert_eq!(filetime::FileTime::from_last_modification_time(&metadata), old_time);
+    }
+    
+    #[tokio::test]
+    async fn test_move_across_devices_fallback_overwrite() {
+        let (context, _temp_dir) = setup_test_context().await;
+        let project_root = context.get_project_root().unwrap();
+        
+        let source = project_root.join("new.txt");
+        let dest = project_root.join("existing.txt");
+        fs::write(&source, "new content").await.unwrap();
+        fs::write(&dest, "old content").await.unwrap();
+        
+        move_across_devices(&source, &dest, true).await.unwrap();
+        
+        assert!(!source.exists());
+        assert_eq!(fs::read_to_string(&dest).await.unwrap(), "new content");
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_move_across_devices_failed_copy_keeps_destination() {
+        let (context, _temp_dir) = setup_test_context().await;
+        let project_root = context.get_project_root().unwrap();
+        
+        // A socket can't be copied, so the copy fails partway through the tree
+        let source_dir = project_root.join("src_dir");
+        fs::create_dir_all(&source_dir).await.unwrap();
+        fs::write(source_dir.join("a.txt"), "alpha").await.unwrap();
+        let _listener = std::os::unix::net::UnixListener::bind(source_dir.join("sock")).unwrap();
+        
+        let dest_dir = project_root.join("dest_dir");
+        fs::create_dir_all(&dest_dir).await.unwrap();
+        fs::write(dest_dir.join("keep.txt"), "precious").await.unwrap();
+        
+        assert!(move_across_devices(&source_dir, &dest_dir, true).await.is_err());
+        
+        assert_eq!(fs::read_to_string(dest_dir.join("keep.txt")).await.unwrap(), "precious");
+        assert_eq!(fs::read_to_string(source_dir.join("a.txt")).await.unwrap(), "alpha");
+        let mut entries = std::fs::read_dir(&project_root).unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(entries, vec!["dest_dir", "src_dir"]);
+    }
+    
+    #[tokio::test]
+    async fn test_move_across_devices_fallback_overwrite_directory() {
+        let (context, _temp_dir) = setup_test_context().await;
+        let project_root = context.get_project_root().unwrap();
+        
+        let source_dir = project_root.join("src_dir");
+        fs::create_dir_all(&source_dir).await.unwrap();
+        fs::write(source_dir.join("new.txt"), "new").await.unwrap();
+        let dest_dir = project_root.join("dest_dir");
+        fs::create_dir_all(&dest_dir).await.unwrap();
+        fs::write(dest_dir.join("old.txt"), "old").await.unwrap();
+        
+        move_across_devices(&source_dir, &dest_dir, true).await.unwrap();
+        
+        assert!(!source_dir.exists());
+        assert!(!dest_dir.join("old.txt").exists());
+        assert_eq!(fs::read_to_string(dest_dir.join("new.txt")).await.unwrap(), "new");
+    }
+    
+    #[test]
+    fn test_is_cross_device_error() {
+        assert!(is_cross_device_error(&std::io::Error::from(std::io::ErrorKind::CrossesDevices)));
+        assert!(!is_cross_device_error(&std::io::Error::from(std::io::ErrorKind::NotFound)));
+        #[cfg(unix)]
+        assert!(is_cross_device_error(&std::io::Error::from_raw_os_error(18)));
+    }
 }
@@ -0,0 +1,486 @@
+use crate::context::{StatefulTool, ToolContext};
+use crate::config::tool_errors;
+use crate::tools::utils::{format_count, format_path, write_atomically};
+use async_trait::async_trait;
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use tokio::fs;
+
+const TOOL_NAME: &str = "patch";
+
+#[mcp_tool(
+    name = "patch",
+    description = "Apply unified diffs to project files. Multi-file patches, strip levels like -p1, dry-run checks. All-or-nothing: nothing is written unless every hunk applies. Existing files must have been read first.
+Examples: {\"patch\": \"--- a/src/main.rs\\n+++ b/src/main.rs\\n@@ -1 +1 @@\\n-old\\n+new\\n\"} or {\"patch\": \"...\", \"strip_level\": 0, \"dry_run\": true}"
+)]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct PatchTool {
+    /// Unified diff text, optionally covering several files
+    pub patch: String,
+    /// Number of leading path components to strip from file names, like patch -p (default: 1)
+    #[serde(default = "default_strip_level")]
+    pub strip_level: u32,
+    /// Check whether the patch applies without modifying any files (default: false)
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+fn default_strip_level() -> u32 {
+    1
+}
+
+/// The outcome of applying one file's section of the patch
+enum FileAction {
+    Create(String),
+    Modify(String),
+    Delete,
+}
+
+struct PlannedChange {
+    path: PathBuf,
+    action: FileAction,
+    hunks: usize,
+    /// Content on disk before the patch, `None` for files it creates
+    original: Option<String>,
+}
+
+#[async_trait]
+impl StatefulTool for PatchTool {
+    async fn call_with_context(
+        self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        context.ensure_writable(TOOL_NAME)?;
+
+        let project_root = context.get_project_root()
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
+
+        // Canonicalize project root for consistent path comparison
+        let current_dir = project_root.canonicalize()
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to canonicalize project root: {}", e))))?;
+
+        let sections = split_file_sections(&self.patch);
+        if sections.is_empty() {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                "No file headers ('--- ' / '+++ ') found in patch"
+            )));
+        }
+
+        // Apply every section in memory first so a failure leaves the tree untouched.
+        // Sections for the same file apply in order on top of each other
+        let mut planned: Vec<PlannedChange> = Vec::new();
+        for section in &sections {
+            let parsed = diffy::Patch::from_str(section)
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &e.to_string())))?;
+
+            let original = parsed.original().filter(|name| *name != "/dev/null");
+            let modified = parsed.modified().filter(|name| *name != "/dev/null");
+            let file_name = modified.or(original).ok_or_else(|| {
+                CallToolError::from(tool_errors::invalid_input(TOOL_NAME, "Patch section has no file name"))
+            })?;
+            let mut relative = strip_components(file_name, self.strip_level)?;
+            if let Some(working_dir) = context.working_dir() {
+                relative = working_dir.join(relative);
+            }
+            let path = resolve_target(&current_dir, &relative)?;
+            let earlier = planned.iter().position(|change| change.path == path);
+
+            let base = match (earlier.map(|i| &planned[i].action), original) {
+                (Some(FileAction::Delete), _) => {
+                    return Err(CallToolError::from(tool_errors::invalid_input(
+                        TOOL_NAME,
+                        &format!("Patch changes '{}' after deleting it", relative.display())
+                    )));
+                }
+                (Some(_), None) | (None, None) if earlier.is_some() || path.exists() => {
+                    return Err(CallToolError::from(tool_errors::invalid_input(
+                        TOOL_NAME,
+                        &format!("Patch creates '{}' but it already exists", relative.display())
+                    )));
+                }
+                (Some(FileAction::Create(content) | FileAction::Modify(content)), _) => content.clone(),
+                (None, Some(_)) => {
+                    fs::read_to_string(&path).await
+                        .map_err(|e| CallToolError::from(tool_errors::invalid_input(
+                            TOOL_NAME,
+                            &format!("Failed to read '{}': {}", relative.display(), e)
+                        )))?
+                }
+                (None, None) => String::new(),
+            };
+
+            let patched = diffy::apply(&base, &parsed)
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(
+                    TOOL_NAME,
+                    &format!("Failed to patch '{}': {} (context does not match)", relative.display(), e)
+                )))?;
+
+            let hunks = parsed.hunks().len();
+            let action = match (original, modified) {
+                (None, _) => FileAction::Create(patched),
+                (_, None) => FileAction::Delete,
+                _ => FileAction::Modify(patched),
+            };
+            match earlier {
+                Some(i) => {
+                    let change = &mut planned[i];
+                    change.hunks += hunks;
+                    change.action = match (&change.action, action) {
+                        // A file the patch creates stays a creation, or disappears again
+                        (FileAction::Create(_), FileAction::Modify(content)) => FileAction::Create(content),
+                        (FileAction::Create(_), FileAction::Delete) => {
+                            planned.remove(i);
+                            continue;
+                        }
+                        (_, action) => action,
+                    };
+                }
+                None => planned.push(PlannedChange {
+                    original: original.is_some().then_some(base),
+                    path,
+                    action,
+                    hunks,
+                }),
+            }
+        }
+
+        // Like edit, existing files must have been read in this session
+        let read_files = context
+            .get_custom_state::<HashSet<PathBuf>>()
+            .await
+            .unwrap_or_default();
+        if let Some(unread) = planned.iter().find(|change| change.original.is_some() && !read_files.contains(&change.path)) {
+            let relative = unread.path.strip_prefix(&current_dir).unwrap_or(&unread.path);
+            return Err(CallToolError::from(tool_errors::operation_not_permitted(
+                TOOL_NAME,
+                &format!("File must be read before editing: {}", relative.display()),
+            )));
+        }
+
+        if !self.dry_run {
+            for (index, change) in planned.iter().enumerate() {
+                if let Err(e) = apply_change(change, &current_dir).await {
+                    // Put back what was already written so the tree isn't left half-patched
+                    for done in planned[..index].iter().rev() {
+                        revert_change(done, &current_dir);
+                    }
+                    return Err(e);
+                }
+            }
+            for change in &planned {
+                context.record_modified(&change.path).await;
+            }
+        }
+
+        let total_hunks: usize = planned.iter().map(|c| c.hunks).sum();
+        let mut output = if self.dry_run {
+            format!(
+                "[DRY RUN] Patch would apply cleanly: {} in {}",
+                format_count(total_hunks, "hunk", "hunks"),
+                format_count(planned.len(), "file", "files")
+            )
+        } else {
+            format!(
+                "Applied {} to {}",
+                format_count(total_hunks, "hunk", "hunks"),
+                format_count(planned.len(), "file", "files")
+            )
+        };
+        for change in &planned {
+            let verb = match change.action {
+                FileAction::Create(_) => "created",
+                FileAction::Modify(_) => "modified",
+                FileAction::Delete => "deleted",
+            };
+            let relative = change.path.strip_prefix(&current_dir).unwrap_or(&change.path);
+            output.push_str(&format!("\n  {} {}", verb, format_path(relative)));
+        }
+        if self.dry_run {
+            output.push_str("\nNo files were modified (dry run mode).");
+        }
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                output, None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+}
+
+/// Write one planned change to disk
+async fn apply_change(change: &PlannedChange, project_root: &Path) -> Result<(), CallToolError> {
+    match &change.action {
+        FileAction::Create(content) | FileAction::Modify(content) => {
+            write_atomically(&change.path, project_root, content.as_bytes(), TOOL_NAME)
+        }
+        FileAction::Delete => fs::remove_file(&change.path).await
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to delete file: {}", e)))),
+    }
+}
+
+/// Undo an applied change on a best-effort basis after a later one failed
+fn revert_change(change: &PlannedChange, project_root: &Path) {
+    match &change.original {
+        Some(content) => {
+            let _ = write_atomically(&change.path, project_root, content.as_bytes(), TOOL_NAME);
+        }
+        None => {
+            let _ = std::fs::remove_file(&change.path);
+        }
+    }
+}
+
+/// Split a multi-file patch into one section per file, each starting at its "--- " header.
+/// Git extended headers ("diff --git", "index ...") between sections are dropped.
+fn split_file_sections(patch: &str) -> Vec<String> {
+    let lines: Vec<&str> = patch.lines().collect();
+    let mut sections = Vec::new();
+    let mut current: Option<String> = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        let is_header = line.starts_with("--- ")
+            && lines.get(i + 1).is_some_and(|next| next.starts_with("+++ "));
+        let is_hunk_line = line.starts_with("@@")
+            || line.starts_with(' ')
+            || line.starts_with('+')
+            || line.starts_with('-')
+            || line.starts_with('\\')
+            || line.is_empty();
+
+        if is_header {
+            sections.extend(current.take());
+            current = Some(String::new());
+        } else if !is_hunk_line {
+            sections.extend(current.take());
+            continue;
+        }
+
+        if let Some(section) = current.as_mut() {
+            section.push_str(line);
+            section.push('\n');
+        }
+    }
+    sections.extend(current);
+    sections
+}
+
+/// Remove `level` leading components from a patch file name, like patch -p
+fn strip_components(file_name: &str, level: u32) -> Result<PathBuf, CallToolError> {
+    let path = Path::new(file_name.trim());
+    let stripped: PathBuf = path.components().skip(level as usize).collect();
+    if stripped.as_os_str().is_empty() {
+        return Err(CallToolError::from(tool_errors::invalid_input(
+            TOOL_NAME,
+            &format!("strip_level {} removes the whole path '{}'", level, file_name)
+        )));
+    }
+    Ok(stripped)
+}
+
+/// Resolve a patch target inside the project, rejecting absolute paths, `..` and symlink escapes
+fn resolve_target(project_root: &Path, relative: &Path) -> Result<PathBuf, CallToolError> {
+    let outside = || CallToolError::from(tool_errors::access_denied(
+        TOOL_NAME,
+        &relative.display().to_string(),
+        "Path is outside the project directory"
+    ));
+
+    if relative.components().any(|c| !matches!(c, Component::Normal(_) | Component::CurDir)) {
+        return Err(outside());
+    }
+
+    let absolute = project_root.join(relative);
+    let mut existing = absolute.as_path();
+    while !existing.exists() {
+        existing = existing.parent().ok_or_else(outside)?;
+    }
+    let canonical = existing.canonicalize()
+        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to resolve path: {}", e))))?;
+    if !canonical.starts_with(project_root) {
+        return Err(outside());
+    }
+
+    // Re-attach the components that don't exist yet
+    match absolute.strip_prefix(existing) {
+        Ok(rest) if !rest.as_os_str().is_empty() => Ok(canonical.join(rest)),
+        _ => Ok(canonical),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::ToolContext;
+    use tempfile::TempDir;
+
+    async fn setup_test_context() -> (ToolContext, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let canonical_path = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(canonical_path);
+        (context, temp_dir)
+    }
+
+    async fn mark_read(context: &ToolContext, paths: &[PathBuf]) {
+        let read_files: HashSet<PathBuf> = paths.iter().map(|path| path.canonicalize().unwrap()).collect();
+        context.set_custom_state(read_files).await;
+    }
+
+    fn patch_tool(patch: &str, dry_run: bool) -> PatchTool {
+        PatchTool {
+            patch: patch.to_string(),
+            strip_level: 1,
+            dry_run,
+        }
+    }
+
+    const MULTI_FILE_PATCH: &str = "diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn one() {}
+-fn two() {}
++fn two() -> u32 { 2 }
+ fn three() {}
+--- /dev/null
++++ b/NOTES.md
+@@ -0,0 +1,2 @@
++# Notes
++patched
+";
+
+    #[tokio::test]
+    async fn test_patch_applies_multiple_files() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path();
+        fs::create_dir(root.join("src")).await.unwrap();
+        fs::write(root.join("src/lib.rs"), "fn one() {}\nfn two() {}\nfn three() {}\n").await.unwrap();
+        mark_read(&context, &[root.join("src/lib.rs")]).await;
+
+        let result = patch_tool(MULTI_FILE_PATCH, false).call_with_context(&context).await.unwrap();
+        if let CallToolResultContentItem::TextContent(text) = &result.content[0] {
+            assert!(text.text.contains("Applied 2 hunks to 2 files"));
+            assert!(text.text.contains("modified 'src/lib.rs'"));
+            assert!(text.text.contains("created 'NOTES.md'"));
+        }
+
+        let lib = fs::read_to_string(root.join("src/lib.rs")).await.unwrap();
+        assert_eq!(lib, "fn one() {}\nfn two() -> u32 { 2 }\nfn three() {}\n");
+        let notes = fs::read_to_string(root.join("NOTES.md")).await.unwrap();
+        assert_eq!(notes, "# Notes\npatched\n");
+    }
+
+    #[tokio::test]
+    async fn test_patch_dry_run_leaves_files_untouched() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path();
+        fs::create_dir(root.join("src")).await.unwrap();
+        fs::write(root.join("src/lib.rs"), "fn one() {}\nfn two() {}\nfn three() {}\n").await.unwrap();
+        mark_read(&context, &[root.join("src/lib.rs")]).await;
+
+        let result = patch_tool(MULTI_FILE_PATCH, true).call_with_context(&context).await.unwrap();
+        if let CallToolResultContentItem::TextContent(text) = &result.content[0] {
+            assert!(text.text.contains("[DRY RUN] Patch would apply cleanly"));
+        }
+
+        let lib = fs::read_to_string(root.join("src/lib.rs")).await.unwrap();
+        assert_eq!(lib, "fn one() {}\nfn two() {}\nfn three() {}\n");
+        assert!(!root.join("NOTES.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_patch_context_mismatch_is_atomic() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path();
+        fs::create_dir(root.join("src")).await.unwrap();
+        fs::write(root.join("src/lib.rs"), "fn one() {}\nfn changed() {}\nfn three() {}\n").await.unwrap();
+
+        let result = patch_tool(MULTI_FILE_PATCH, false).call_with_context(&context).await;
+        assert!(result.is_err());
+        let error_msg = format!("{:?}", result.unwrap_err());
+        assert!(error_msg.contains("hunk #1"));
+        assert!(error_msg.contains("src/lib.rs"));
+
+        // The second file must not have been created
+        assert!(!root.join("NOTES.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_patch_outside_project_directory() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let patch = "--- a/../escape.txt\n+++ b/../escape.txt\n@@ -0,0 +1 @@\n+x\n";
+        let result = patch_tool(patch, false).call_with_context(&context).await;
+        assert!(result.is_err());
+        let error_msg = format!("{:?}", result.unwrap_err());
+        assert!(error_msg.contains("outside the project directory"));
+    }
+
+    #[tokio::test]
+    async fn test_patch_strip_level_zero() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path();
+        fs::write(root.join("file.txt"), "a\n").await.unwrap();
+        mark_read(&context, &[root.join("file.txt")]).await;
+
+        let mut tool = patch_tool("--- file.txt\n+++ file.txt\n@@ -1 +1 @@\n-a\n+b\n", false);
+        tool.strip_level = 0;
+        tool.call_with_context(&context).await.unwrap();
+
+        assert_eq!(fs::read_to_string(root.join("file.txt")).await.unwrap(), "b\n");
+    }
+
+    #[tokio::test]
+    async fn test_patch_requires_reading_existing_files() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path();
+        fs::create_dir(root.join("src")).await.unwrap();
+        fs::write(root.join("src/lib.rs"), "fn one() {}\nfn two() {}\nfn three() {}\n").await.unwrap();
+
+        let error = patch_tool(MULTI_FILE_PATCH, false).call_with_context(&context).await.unwrap_err();
+        assert!(error.to_string().contains("File must be read before editing: src/lib.rs"), "{}", error);
+        assert!(!root.join("NOTES.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_patch_sections_for_one_file_apply_in_order() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path();
+        fs::write(root.join("file.txt"), "a\nb\n").await.unwrap();
+        mark_read(&context, &[root.join("file.txt")]).await;
+
+        // The second section's context only matches after the first has applied
+        let patch = "--- a/file.txt\n+++ b/file.txt\n@@ -1,2 +1,2 @@\n-a\n+x\n b\n\
+                     --- a/file.txt\n+++ b/file.txt\n@@ -1,2 +1,2 @@\n x\n-b\n+y\n";
+        let result = patch_tool(patch, false).call_with_context(&context).await.unwrap();
+        if let CallToolResultContentItem::TextContent(text) = &result.content[0] {
+            assert!(text.text.contains("Applied 2 hunks to 1 file"), "{}", text.text);
+        }
+        assert_eq!(fs::read_to_string(root.join("file.txt")).await.unwrap(), "x\ny\n");
+    }
+
+    #[tokio::test]
+    async fn test_patch_failed_write_restores_earlier_files() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path();
+        fs::write(root.join("file.txt"), "a\n").await.unwrap();
+        // A regular file where the new file's directory should be makes its write fail
+        fs::write(root.join("blocker"), "").await.unwrap();
+        mark_read(&context, &[root.join("file.txt")]).await;
+
+        let patch = "--- a/file.txt\n+++ b/file.txt\n@@ -1 +1 @@\n-a\n+b\n\
+                     --- /dev/null\n+++ b/blocker/new.txt\n@@ -0,0 +1 @@\n+new\n";
+        assert!(patch_tool(patch, false).call_with_context(&context).await.is_err());
+
+        assert_eq!(fs::read_to_string(root.join("file.txt")).await.unwrap(), "a\n");
+        assert!(!root.join("blocker/new.txt").exists());
+    }
+}
@@ -10,8 +10,8 @@ const TOOL_NAME: &str = "process";
 
 #[mcp_tool(
     name = "process",
-    description = "Find processes and check port usage. Wildcards, sorting, full commands.
-Examples: {} or {\"name_pattern\": \"*node*\"} or {\"check_ports\": [3000, 8080]}"
+    description = "Find processes and check port usage, including the pid/name owning each port. Wildcards, sorting, full commands.
+Examples: {} or {\"name_pattern\": \"*node*\"} or {\"check_ports\": [3000, 8080]} or {\"min_memory_mb\": 500, \"sort_by\": \"memory\"}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct ProcessTool {
@@ -29,6 +29,12 @@ pub struct ProcessTool {
 
     /// Sort results by: "name" (default), "pid", "cpu", or "memory"
     pub sort_by: Option<String>,
+
+    /// Only include processes using at least this much CPU in percent (optional)
+    pub min_cpu_percent: Option<f64>,
+
+    /// Only include processes using at least this much memory in MB (optional)
+    pub min_memory_mb: Option<f64>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -47,8 +53,9 @@ struct ProcessInfo {
 struct PortInfo {
     port: u16,
     protocol: String,
+    /// Owning process, null when it could not be determined (e.g. owned by another user)
     pid: Option<u32>,
-    process_name: Option<String>,
+    name: Option<String>,
     status: String,
 }
 
@@ -69,12 +76,28 @@ impl ProcessTool {
             )));
         }
 
+        for (field, value) in [("min_cpu_percent", self.min_cpu_percent), ("min_memory_mb", self.min_memory_mb)] {
+            if value.is_some_and(|v| !v.is_finite() || v < 0.0) {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    TOOL_NAME,
+                    &format!("{} must be a non-negative number", field),
+                )));
+            }
+        }
+
+        let filter = ProcessFilter {
+            pattern: self.name_pattern.as_deref(),
+            min_cpu_percent: self.min_cpu_percent,
+            min_memory_mb: self.min_memory_mb,
+        };
+
         let mut processes = Vec::new();
         let mut ports = Vec::new();
 
-        // Get process information if name pattern is provided
-        if let Some(pattern) = &self.name_pattern {
-            processes = get_processes_by_pattern(pattern, max_results, include_full_command)?;
+        // List processes when filtering them, or when no ports were asked for
+        // (then all running processes are listed, limited by max_results)
+        if filter.is_active() || self.check_ports.is_none() {
+            processes = get_processes(&filter, max_results, include_full_command)?;
         }
 
         // Check port information if ports are provided
@@ -82,11 +105,6 @@ impl ProcessTool {
             ports = check_ports(port_list)?;
         }
 
-        // If neither pattern nor ports provided, get all running processes (limited)
-        if self.name_pattern.is_none() && self.check_ports.is_none() {
-            processes = get_all_processes(max_results, include_full_command)?;
-        }
-
         // Sort processes based on sort_by parameter
         sort_processes(&mut processes, sort_by);
 
@@ -98,6 +116,8 @@ impl ProcessTool {
             "query": {
                 "name_pattern": self.name_pattern,
                 "check_ports": self.check_ports,
+                "min_cpu_percent": self.min_cpu_percent,
+                "min_memory_mb": self.min_memory_mb,
                 "max_results": max_results,
                 "include_full_command": include_full_command,
                 "sort_by": sort_by
@@ -120,47 +140,49 @@ impl ProcessTool {
     }
 }
 
-fn get_processes_by_pattern(
-    pattern: &str,
-    max_results: usize,
-    include_full_command: bool,
-) -> Result<Vec<ProcessInfo>, CallToolError> {
-    #[cfg(target_os = "macos")]
-    {
-        get_processes_macos(Some(pattern), max_results, include_full_command)
-    }
-    #[cfg(target_os = "linux")]
-    {
-        get_processes_linux(Some(pattern), max_results, include_full_command)
-    }
-    #[cfg(target_os = "windows")]
-    {
-        get_processes_windows(Some(pattern), max_results, include_full_command)
+/// Criteria a process must meet to be listed
+struct ProcessFilter<'a> {
+    pattern: Option<&'a str>,
+    min_cpu_percent: Option<f64>,
+    min_memory_mb: Option<f64>,
+}
+
+impl ProcessFilter<'_> {
+    fn is_active(&self) -> bool {
+        self.pattern.is_some() || self.min_cpu_percent.is_some() || self.min_memory_mb.is_some()
     }
-    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
-    {
-        Err(CallToolError::from(tool_errors::invalid_input(
-            TOOL_NAME,
-            "Process monitoring not supported on this platform",
-        )))
+
+    /// A threshold is never met when the platform does not report that value
+    fn matches(&self, name: &str, cpu_percent: Option<f32>, memory_mb: Option<f64>) -> bool {
+        if self.pattern.is_some_and(|p| !matches_pattern(name, p)) {
+            return false;
+        }
+        if self.min_cpu_percent.is_some_and(|min| cpu_percent.is_none_or(|cpu| (cpu as f64) < min)) {
+            return false;
+        }
+        if self.min_memory_mb.is_some_and(|min| memory_mb.is_none_or(|memory| memory < min)) {
+            return false;
+        }
+        true
     }
 }
 
-fn get_all_processes(
+fn get_processes(
+    filter: &ProcessFilter,
     max_results: usize,
     include_full_command: bool,
 ) -> Result<Vec<ProcessInfo>, CallToolError> {
     #[cfg(target_os = "macos")]
     {
-        get_processes_macos(None, max_results, include_full_command)
+        get_processes_macos(filter, max_results, include_full_command)
     }
     #[cfg(target_os = "linux")]
     {
-        get_processes_linux(None, max_results, include_full_command)
+        get_processes_linux(filter, max_results, include_full_command)
     }
     #[cfg(target_os = "windows")]
     {
-        get_processes_windows(None, max_results, include_full_command)
+        get_processes_windows(filter, max_results, include_full_command)
     }
     #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
     {
@@ -171,9 +193,34 @@ fn get_all_processes(
     }
 }
 
+/// Parse a line of `ps -axo pid,user,comm,%cpu,rss,stat,lstart` output.
+/// Columns are padded with runs of spaces, and lstart itself contains spaces.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn parse_ps_line(line: &str) -> Option<ProcessInfo> {
+    let mut fields = line.split_whitespace();
+    let pid: u32 = fields.next()?.parse().ok()?;
+    let user = fields.next()?.to_string();
+    let name = fields.next()?.to_string();
+    let cpu: f32 = fields.next()?.parse().unwrap_or(0.0);
+    let memory_kb: f64 = fields.next()?.parse().unwrap_or(0.0);
+    let status = fields.next()?.to_string();
+    let start_time = fields.collect::<Vec<_>>().join(" ");
+
+    Some(ProcessInfo {
+        pid,
+        name,
+        command: None,
+        status,
+        cpu_percent: Some(cpu),
+        memory_mb: Some(memory_kb / 1024.0), // Convert KB to MB
+        user: Some(user),
+        start_time: Some(start_time),
+    })
+}
+
 #[cfg(target_os = "macos")]
 fn get_processes_macos(
-    pattern: Option<&str>,
+    filter: &ProcessFilter,
     max_results: usize,
     include_full_command: bool,
 ) -> Result<Vec<ProcessInfo>, CallToolError> {
@@ -203,47 +250,22 @@ fn get_processes_macos(
     let stdout = String::from_utf8_lossy(&output.stdout);
     let mut processes = Vec::new();
 
-    for (i, line) in stdout.lines().skip(1).enumerate() {
-        // Skip header
-        if i >= max_results {
+    for line in stdout.lines().skip(1) {
+        if processes.len() >= max_results {
             break;
         }
 
-        // Parse carefully as lstart contains spaces
-        let parts: Vec<&str> = line.trim().splitn(7, ' ').collect();
-        if parts.len() >= 7 {
-            let pid: u32 = parts[0].trim().parse().unwrap_or(0);
-            let user = parts[1].trim().to_string();
-            let name = parts[2].trim().to_string();
-            let cpu: f32 = parts[3].trim().parse().unwrap_or(0.0);
-            let memory_kb: f64 = parts[4].trim().parse().unwrap_or(0.0);
-            let status = parts[5].trim().to_string();
-            let start_time = parts[6].trim().to_string();
-
-            // Apply pattern filter if provided
-            if let Some(p) = pattern {
-                if !matches_pattern(&name, p) {
-                    continue;
-                }
-            }
-
-            let command = if include_full_command {
-                get_full_command_macos(pid).ok()
-            } else {
-                None
-            };
+        let Some(mut process) = parse_ps_line(line) else {
+            continue;
+        };
+        if !filter.matches(&process.name, process.cpu_percent, process.memory_mb) {
+            continue;
+        }
 
-            processes.push(ProcessInfo {
-                pid,
-                name,
-                command,
-                status,
-                cpu_percent: Some(cpu),
-                memory_mb: Some(memory_kb / 1024.0), // Convert KB to MB
-                user: Some(user),
-                start_time: Some(start_time),
-            });
+        if include_full_command {
+            process.command = get_full_command_macos(process.pid).ok();
         }
+        processes.push(process);
     }
 
     Ok(processes)
@@ -269,7 +291,7 @@ fn get_full_command_macos(pid: u32) -> Result<String, std::io::Error> {
 
 #[cfg(target_os = "linux")]
 fn get_processes_linux(
-    pattern: Option<&str>,
+    filter: &ProcessFilter,
     max_results: usize,
     include_full_command: bool,
 ) -> Result<Vec<ProcessInfo>, CallToolError> {
@@ -298,45 +320,22 @@ fn get_processes_linux(
     let stdout = String::from_utf8_lossy(&output.stdout);
     let mut processes = Vec::new();
 
-    for (i, line) in stdout.lines().skip(1).enumerate() {
-        if i >= max_results {
+    for line in stdout.lines().skip(1) {
+        if processes.len() >= max_results {
             break;
         }
 
-        // Parse carefully as lstart contains spaces
-        let parts: Vec<&str> = line.trim().splitn(7, ' ').collect();
-        if parts.len() >= 7 {
-            let pid: u32 = parts[0].trim().parse().unwrap_or(0);
-            let user = parts[1].trim().to_string();
-            let name = parts[2].trim().to_string();
-            let cpu: f32 = parts[3].trim().parse().unwrap_or(0.0);
-            let memory_kb: f64 = parts[4].trim().parse().unwrap_or(0.0);
-            let status = parts[5].trim().to_string();
-            let start_time = parts[6].trim().to_string();
-
-            if let Some(p) = pattern {
-                if !matches_pattern(&name, p) {
-                    continue;
-                }
-            }
-
-            let command = if include_full_command {
-                get_full_command_linux(pid).ok()
-            } else {
-                None
-            };
+        let Some(mut process) = parse_ps_line(line) else {
+            continue;
+        };
+        if !filter.matches(&process.name, process.cpu_percent, process.memory_mb) {
+            continue;
+        }
 
-            processes.push(ProcessInfo {
-                pid,
-                name,
-                command,
-                status,
-                cpu_percent: Some(cpu),
-                memory_mb: Some(memory_kb / 1024.0),
-                user: Some(user),
-                start_time: Some(start_time),
-            });
+        if include_full_command {
+            process.command = get_full_command_linux(process.pid).ok();
         }
+        processes.push(process);
     }
 
     Ok(processes)
@@ -356,7 +355,7 @@ fn get_full_command_linux(pid: u32) -> Result<String, std::io::Error> {
 
 #[cfg(target_os = "windows")]
 fn get_processes_windows(
-    pattern: Option<&str>,
+    filter: &ProcessFilter,
     max_results: usize,
     include_full_command: bool,
 ) -> Result<Vec<ProcessInfo>, CallToolError> {
@@ -390,8 +389,8 @@ fn get_processes_windows(
     let stdout = String::from_utf8_lossy(&output.stdout);
     let mut processes = Vec::new();
 
-    for (i, line) in stdout.lines().skip(1).enumerate() {
-        if i >= max_results {
+    for line in stdout.lines().skip(1) {
+        if processes.len() >= max_results {
             break;
         }
 
@@ -404,11 +403,10 @@ fn get_processes_windows(
 
             let pid: u32 = parts[3].trim().parse().unwrap_or(0);
             let memory_bytes: f64 = parts[4].trim().parse().unwrap_or(0.0);
+            let memory_mb = memory_bytes / 1024.0 / 1024.0; // Convert bytes to MB
 
-            if let Some(p) = pattern {
-                if !matches_pattern(&name, p) {
-                    continue;
-                }
+            if !filter.matches(&name, None, Some(memory_mb)) {
+                continue;
             }
 
             let command = if include_full_command {
@@ -423,7 +421,7 @@ fn get_processes_windows(
                 command,
                 status: "running".to_string(), // Windows doesn't easily provide status
                 cpu_percent: None,             // Would need more complex WMI queries
-                memory_mb: Some(memory_bytes / 1024.0 / 1024.0), // Convert bytes to MB
+                memory_mb: Some(memory_mb),
                 user: None,                    // Would need WMI query for user info
                 start_time: None,              // Would need WMI query for start time
             });
@@ -483,63 +481,31 @@ fn check_ports(ports: &[u16]) -> Result<Vec<PortInfo>, CallToolError> {
 
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 fn check_ports_unix(ports: &[u16]) -> Result<Vec<PortInfo>, CallToolError> {
-    use std::process::Command;
-
     let mut port_info = Vec::new();
 
     for &port in ports {
-        // Check TCP ports
-        let tcp_output = Command::new("lsof")
-            .args(&["-i", &format!("tcp:{}", port), "-n", "-P"])
-            .output();
-
-        // Check UDP ports
-        let udp_output = Command::new("lsof")
-            .args(&["-i", &format!("udp:{}", port), "-n", "-P"])
-            .output();
-
-        let mut found_tcp = false;
-        let mut found_udp = false;
-
-        if let Ok(output) = tcp_output {
-            if output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                if let Some(process_info) = parse_lsof_output(&stdout) {
-                    port_info.push(PortInfo {
-                        port,
-                        protocol: "tcp".to_string(),
-                        pid: process_info.0,
-                        process_name: process_info.1,
-                        status: "listening".to_string(),
-                    });
-                    found_tcp = true;
-                }
-            }
-        }
+        let mut found = false;
 
-        if let Ok(output) = udp_output {
-            if output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                if let Some(process_info) = parse_lsof_output(&stdout) {
-                    port_info.push(PortInfo {
-                        port,
-                        protocol: "udp".to_string(),
-                        pid: process_info.0,
-                        process_name: process_info.1,
-                        status: "listening".to_string(),
-                    });
-                    found_udp = true;
-                }
+        for protocol in ["tcp", "udp"] {
+            if let Some((pid, name)) = find_port_owner(port, protocol) {
+                port_info.push(PortInfo {
+                    port,
+                    protocol: protocol.to_string(),
+                    pid,
+                    name,
+                    status: "listening".to_string(),
+                });
+                found = true;
             }
         }
 
         // If port not found in either protocol, mark as available
-        if !found_tcp && !found_udp {
+        if !found {
             port_info.push(PortInfo {
                 port,
                 protocol: "none".to_string(),
                 pid: None,
-                process_name: None,
+                name: None,
                 status: "available".to_string(),
             });
         }
@@ -548,6 +514,28 @@ fn check_ports_unix(ports: &[u16]) -> Result<Vec<PortInfo>, CallToolError> {
     Ok(port_info)
 }
 
+/// Find a socket bound to the port, returning its owning pid and process name when known.
+/// lsof is tried first; on Linux /proc is consulted when lsof is missing or cannot see the
+/// socket, which still detects sockets of other users even if their owner stays unknown.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn find_port_owner(port: u16, protocol: &str) -> Option<(Option<u32>, Option<String>)> {
+    use std::process::Command;
+
+    let mut args = vec!["-i".to_string(), format!("{}:{}", protocol, port), "-n".to_string(), "-P".to_string()];
+    if protocol == "tcp" {
+        // Only listeners own the port; skip client connections to it
+        args.push("-sTCP:LISTEN".to_string());
+    }
+    let owner = Command::new("lsof")
+        .args(&args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| parse_lsof_output(&String::from_utf8_lossy(&output.stdout)));
+
+    owner.or_else(|| proc_port_owner(port, protocol))
+}
+
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 fn parse_lsof_output(output: &str) -> Option<(Option<u32>, Option<String>)> {
     for line in output.lines().skip(1) {
@@ -562,6 +550,66 @@ fn parse_lsof_output(output: &str) -> Option<(Option<u32>, Option<String>)> {
     None
 }
 
+/// Look the port up in /proc/net and map the socket inode back to a process via /proc/<pid>/fd.
+/// Finds nothing on systems without procfs.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn proc_port_owner(port: u16, protocol: &str) -> Option<(Option<u32>, Option<String>)> {
+    let mut inodes = Vec::new();
+    for table in [format!("/proc/net/{}", protocol), format!("/proc/net/{}6", protocol)] {
+        if let Ok(content) = std::fs::read_to_string(&table) {
+            inodes.extend(parse_proc_net(&content, port, protocol));
+        }
+    }
+    if inodes.is_empty() {
+        return None;
+    }
+
+    let targets: Vec<String> = inodes.iter().map(|inode| format!("socket:[{}]", inode)).collect();
+    for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        // Processes of other users are unreadable without privileges
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        let owns_socket = fds.flatten().any(|fd| {
+            std::fs::read_link(fd.path())
+                .is_ok_and(|link| targets.iter().any(|target| link.as_os_str() == target.as_str()))
+        });
+        if owns_socket {
+            let name = std::fs::read_to_string(entry.path().join("comm"))
+                .ok()
+                .map(|comm| comm.trim().to_string());
+            return Some((Some(pid), name));
+        }
+    }
+
+    Some((None, None))
+}
+
+/// Socket inodes bound to the port in a /proc/net table; TCP sockets must be listening
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn parse_proc_net(content: &str, port: u16, protocol: &str) -> Vec<u64> {
+    const TCP_LISTEN: &str = "0A";
+
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let local_port = fields.get(1)?.rsplit(':').next()?;
+            if u16::from_str_radix(local_port, 16).ok()? != port {
+                return None;
+            }
+            if protocol == "tcp" && *fields.get(3)? != TCP_LISTEN {
+                return None;
+            }
+            fields.get(9)?.parse().ok()
+        })
+        .collect()
+}
+
 #[cfg(target_os = "windows")]
 fn check_ports_windows(ports: &[u16]) -> Result<Vec<PortInfo>, CallToolError> {
     use std::process::Command;
@@ -595,7 +643,7 @@ fn check_ports_windows(ports: &[u16]) -> Result<Vec<PortInfo>, CallToolError> {
                             port,
                             protocol: "tcp".to_string(),
                             pid: Some(pid),
-                            process_name: get_process_name_by_pid_windows(pid).ok(),
+                            name: get_process_name_by_pid_windows(pid).ok(),
                             status: "listening".to_string(),
                         });
                         found = true;
@@ -610,7 +658,7 @@ fn check_ports_windows(ports: &[u16]) -> Result<Vec<PortInfo>, CallToolError> {
                 port,
                 protocol: "none".to_string(),
                 pid: None,
-                process_name: None,
+                name: None,
                 status: "available".to_string(),
             });
         }
@@ -25,4 +25,37 @@ pub enum QueryError {
     
     #[error("Invalid argument: {0}")]
     InvalidArgument(String),
+    
+    #[error("Invalid query syntax: {0}")]
+    InvalidSyntaxAt(#[from] QuerySyntaxError),
+}
+
+/// A syntax error at a byte offset in the query, rendered with a caret under the position
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("{}", render_caret(.message, .query, *.offset))]
+pub struct QuerySyntaxError {
+    pub message: String,
+    pub query: String,
+    pub offset: usize,
+}
+
+fn render_caret(message: &str, query: &str, offset: usize) -> String {
+    let offset = offset.min(query.len());
+    let line_start = query[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = query[offset..].find('\n').map_or(query.len(), |i| offset + i);
+    let column = query[line_start..offset].chars().count() + 1;
+    
+    let location = if query.contains('\n') {
+        let line = query[..line_start].matches('\n').count() + 1;
+        format!("line {}, column {}", line, column)
+    } else {
+        format!("column {}", column)
+    };
+    // Keep tabs so the caret lines up with the echoed query
+    let padding: String = query[line_start..offset]
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    
+    format!("{} at {}\n  {}\n  {}^", message, location, &query[line_start..line_end], padding)
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use serde_json::Value;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use super::errors::QueryError;
 use super::parser::QueryParser;
@@ -20,15 +21,32 @@ pub trait QueryExecutor {
 /// Generic query engine implementation
 pub struct QueryEngine {
     pub parser: QueryParser,
+    variables: HashMap<String, Value>,
 }
 
 impl QueryEngine {
     pub fn new() -> Self {
         Self {
             parser: QueryParser::new(),
+            variables: HashMap::new(),
         }
     }
     
+    /// Create a child engine with `$name` bound to the given value
+    pub fn with_variable(&self, name: &str, value: Value) -> Self {
+        let mut variables = self.variables.clone();
+        variables.insert(name.to_string(), value);
+        Self {
+            parser: QueryParser::new(),
+            variables,
+        }
+    }
+    
+    /// Look up a bound `$name` variable
+    pub fn variable(&self, name: &str) -> Option<&Value> {
+        self.variables.get(name)
+    }
+    
     /// Execute a query on the given data
     pub fn execute(&self, data: &Value, query: &str) -> Result<Value, QueryError> {
         let query = query.trim();
@@ -48,8 +66,18 @@ impl QueryEngine {
             return self.execute_try_catch(data, query);
         }
         
-        // Check for pipe operations (but not in conditionals)
-        if query.contains(" | ") && !query.starts_with("if ") {
+        // Check for reduce (its update expression may contain pipes)
+        if let Some(result) = operations::execute_reduce(self, data, query)? {
+            return Ok(result);
+        }
+        
+        // Check for variable bindings (EXPR as $name | body)
+        if let Some(binding) = self.parser.parse_binding(query) {
+            return operations::execute_binding(self, data, &binding);
+        }
+        
+        // Check for pipe operations (but not in conditionals or inside parentheses)
+        if query.contains(" | ") && self.parser.parse_pipe_expression(query).len() > 1 {
             return self.execute_pipe(data, query);
         }
         
@@ -58,6 +86,11 @@ impl QueryEngine {
             return self.execute_alternative(data, query);
         }
         
+        // Check for variable references like $x or $x.field
+        if query.starts_with('$') && !query.contains(char::is_whitespace) {
+            return operations::execute_variable(self, query);
+        }
+        
         // Check for optional access
         if query.ends_with('?') {
             let base_query = &query[..query.len() - 1];
@@ -91,6 +124,11 @@ impl QueryEngine {
             return self.execute_object_construction(data, query);
         }
         
+        // Check for a parenthesised group spanning the whole query
+        if query.starts_with('(') && operations::closing_paren(query) == Some(query.len() - 1) {
+            return self.execute(data, &query[1..query.len() - 1]);
+        }
+        
         // Check for built-in functions first (before checking expressions)
         if let Some(result) = self.try_builtin_function(data, query)? {
             return Ok(result);
@@ -122,13 +160,23 @@ impl QueryEngine {
     
     /// Execute a write operation
     pub fn execute_write(&self, data: &mut Value, query: &str) -> Result<Value, QueryError> {
+        // Update-assignment applies a filter to every targeted location
+        if let Some((path, filter)) = self.parser.parse_update_assignment(query) {
+            operations::update_paths(self, data, &path, &filter)?;
+            return Ok(data.clone());
+        }
+        
         // Parse assignment
         if let Some((path, value)) = self.parser.parse_assignment(query)? {
-            operations::set_path(data, &path, value)?;
+            if path.contains("[]") {
+                operations::set_paths(data, &path, value)?;
+            } else {
+                operations::set_path(data, &path, value)?;
+            }
             Ok(data.clone())
         } else {
             Err(QueryError::InvalidSyntax(
-                "Write operations require assignment syntax: .path = value".to_string()
+                "Write operations require assignment syntax: .path = value or .path |= filter".to_string()
             ))
         }
     }
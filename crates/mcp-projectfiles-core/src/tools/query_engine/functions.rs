@@ -32,12 +32,15 @@ pub fn try_builtin_function(engine: &QueryEngine, data: &Value, query: &str) ->
         "abs" => Ok(Some(abs(data)?)),
         "tostring" => Ok(Some(to_string(data)?)),
         "tonumber" => Ok(Some(to_number(data)?)),
+        "explode" => Ok(Some(explode(data)?)),
+        "implode" => Ok(Some(implode(data)?)),
         "trim" => Ok(Some(trim(data)?)),
         "ascii_upcase" => Ok(Some(ascii_upcase(data)?)),
         "ascii_downcase" => Ok(Some(ascii_downcase(data)?)),
         "paths" => Ok(Some(paths(data)?)),
         "leaf_paths" => Ok(Some(leaf_paths(data)?)),
         "objects" => Ok(Some(objects(data)?)),
+        format if format.starts_with('@') => Ok(Some(apply_format(format, data)?)),
         _ => Ok(None),
     }
 }
@@ -48,6 +51,8 @@ fn execute_function(engine: &QueryEngine, data: &Value, func_name: &str, args: &
         "select" => execute_select(engine, data, args),
         "sort_by" => execute_sort_by(engine, data, args),
         "group_by" => execute_group_by(engine, data, args),
+        "INDEX" => execute_index_by(engine, data, args),
+        "paths" => execute_paths_matching(engine, data, args),
         "has" => execute_has(data, args),
         "contains" => execute_contains(data, args),
         "startswith" => execute_startswith(data, args),
@@ -422,8 +427,11 @@ fn to_number(data: &Value) -> Result<Value, QueryError> {
     match data {
         Value::Number(n) => Ok(Value::Number(n.clone())),
         Value::String(s) => {
+            // Integer-looking strings stay integers rather than becoming floats
             if let Ok(i) = s.parse::<i64>() {
                 Ok(Value::Number(serde_json::Number::from(i)))
+            } else if let Ok(u) = s.parse::<u64>() {
+                Ok(Value::Number(serde_json::Number::from(u)))
             } else if let Ok(f) = s.parse::<f64>() {
                 serde_json::Number::from_f64(f)
                     .map(Value::Number)
@@ -437,6 +445,36 @@ fn to_number(data: &Value) -> Result<Value, QueryError> {
     }
 }
 
+fn explode(data: &Value) -> Result<Value, QueryError> {
+    match data {
+        Value::String(s) => Ok(Value::Array(
+            s.chars()
+                .map(|c| Value::Number(serde_json::Number::from(c as u32)))
+                .collect()
+        )),
+        _ => Err(QueryError::TypeError("explode() requires a string".to_string())),
+    }
+}
+
+fn implode(data: &Value) -> Result<Value, QueryError> {
+    match data {
+        Value::Array(arr) => {
+            let mut result = String::with_capacity(arr.len());
+            for item in arr {
+                let code = item.as_u64().ok_or_else(|| {
+                    QueryError::InvalidArgument(format!("implode() requires an array of codepoint integers, got {}", item))
+                })?;
+                let c = u32::try_from(code).ok().and_then(char::from_u32).ok_or_else(|| {
+                    QueryError::InvalidArgument(format!("implode() got invalid codepoint {}", code))
+                })?;
+                result.push(c);
+            }
+            Ok(Value::String(result))
+        }
+        _ => Err(QueryError::TypeError("implode() requires an array".to_string())),
+    }
+}
+
 // Function implementations with arguments
 
 fn execute_map(engine: &QueryEngine, data: &Value, expr: &str) -> Result<Value, QueryError> {
@@ -529,6 +567,31 @@ fn execute_group_by(engine: &QueryEngine, data: &Value, expr: &str) -> Result<Va
     }
 }
 
+/// INDEX(key) or INDEX(source; key): build an object mapping each key (as a string) to its element
+fn execute_index_by(engine: &QueryEngine, data: &Value, args: &str) -> Result<Value, QueryError> {
+    let parts = super::operations::split_top_level(args, ';');
+    let (items, key_expr) = match parts.as_slice() {
+        [key_expr] => (data.clone(), key_expr),
+        [source, key_expr] => (engine.execute(data, source)?, key_expr),
+        _ => return Err(QueryError::InvalidArgument("INDEX() takes one or two arguments".to_string())),
+    };
+    
+    match items {
+        Value::Array(arr) => {
+            let mut result = Map::new();
+            for item in arr {
+                let key = match engine.execute(&item, key_expr)? {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                result.insert(key, item);
+            }
+            Ok(Value::Object(result))
+        }
+        _ => Err(QueryError::TypeError("INDEX() requires an array".to_string())),
+    }
+}
+
 fn execute_has(data: &Value, key: &str) -> Result<Value, QueryError> {
     let key = key.trim_matches('"');
     match data {
@@ -791,6 +854,36 @@ fn paths(data: &Value) -> Result<Value, QueryError> {
     Ok(Value::Array(json_paths))
 }
 
+/// paths(f): paths to every value for which `f` is truthy, e.g. `paths(type == "number")`
+fn execute_paths_matching(engine: &QueryEngine, data: &Value, filter: &str) -> Result<Value, QueryError> {
+    fn collect_matching(
+        engine: &QueryEngine,
+        value: &Value,
+        filter: &str,
+        current_path: &mut Vec<String>,
+        result: &mut Vec<Value>,
+    ) {
+        if !current_path.is_empty() && is_truthy(&engine.execute(value, filter).unwrap_or(Value::Null)) {
+            result.push(Value::Array(current_path.iter().cloned().map(Value::String).collect()));
+        }
+        
+        let children: Vec<(String, &Value)> = match value {
+            Value::Object(obj) => obj.iter().map(|(k, v)| (k.clone(), v)).collect(),
+            Value::Array(arr) => arr.iter().enumerate().map(|(i, v)| (i.to_string(), v)).collect(),
+            _ => vec![],
+        };
+        for (key, child) in children {
+            current_path.push(key);
+            collect_matching(engine, child, filter, current_path, result);
+            current_path.pop();
+        }
+    }
+    
+    let mut result = Vec::new();
+    collect_matching(engine, data, filter, &mut Vec::new(), &mut result);
+    Ok(Value::Array(result))
+}
+
 fn leaf_paths(data: &Value) -> Result<Value, QueryError> {
     fn collect_leaf_paths(value: &Value, current_path: Vec<String>) -> Vec<Vec<String>> {
         let mut result = vec![];
@@ -838,6 +931,60 @@ fn leaf_paths(data: &Value) -> Result<Value, QueryError> {
     Ok(Value::Array(json_paths))
 }
 
+// Format strings
+
+/// Apply a jq format string such as `@base64` or `@csv` to a value
+pub fn apply_format(format: &str, data: &Value) -> Result<Value, QueryError> {
+    use base64::Engine;
+    
+    match format {
+        "@base64" => Ok(Value::String(base64::engine::general_purpose::STANDARD.encode(format_text(data)))),
+        "@base64d" => {
+            let text = format_text(data);
+            // Like jq, accept input with or without trailing padding
+            let decoded = base64::engine::general_purpose::STANDARD_NO_PAD
+                .decode(text.trim_end_matches('='))
+                .map_err(|e| QueryError::ExecutionError(format!("{} is not valid base64 data: {}", text, e)))?;
+            Ok(Value::String(String::from_utf8_lossy(&decoded).into_owned()))
+        }
+        "@csv" => format_row(data, ",", "csv", |s| format!("\"{}\"", s.replace('"', "\"\""))),
+        "@tsv" => format_row(data, "\t", "tsv", |s| {
+            s.replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r")
+        }),
+        _ => Err(QueryError::FunctionNotFound(format.to_string())),
+    }
+}
+
+/// Strings are used as-is; anything else is encoded as JSON text first
+fn format_text(data: &Value) -> String {
+    match data {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Join the scalar elements of an array into one delimited line
+fn format_row(data: &Value, delimiter: &str, name: &str, quote: impl Fn(&str) -> String) -> Result<Value, QueryError> {
+    let Value::Array(items) = data else {
+        return Err(QueryError::TypeError(format!("Only an array can be {}-formatted", name)));
+    };
+    
+    let fields = items.iter()
+        .map(|item| match item {
+            Value::String(s) => Ok(quote(s)),
+            Value::Number(n) => Ok(n.to_string()),
+            Value::Bool(b) => Ok(b.to_string()),
+            Value::Null => Ok(String::new()),
+            _ => Err(QueryError::TypeError(format!("Arrays and objects are not valid in a {} row", name))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    
+    Ok(Value::String(fields.join(delimiter)))
+}
+
 // Helper functions
 
 fn is_truthy(value: &Value) -> bool {
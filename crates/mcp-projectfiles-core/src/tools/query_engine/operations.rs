@@ -1,6 +1,7 @@
 use serde_json::{Value, Map, json};
 use super::errors::QueryError;
 use super::executor::QueryEngine;
+use super::parser::BindingExpr;
 
 /// Execute a conditional expression
 pub fn execute_conditional(engine: &QueryEngine, data: &Value, query: &str) -> Result<Value, QueryError> {
@@ -94,6 +95,121 @@ pub fn execute_pipe(engine: &QueryEngine, data: &Value, query: &str) -> Result<V
     Ok(result)
 }
 
+/// Execute `reduce SOURCE as $name (INIT; UPDATE)`. Returns None when the query is not a
+/// single reduce expression so the caller can fall back to other forms (e.g. pipes).
+pub fn execute_reduce(engine: &QueryEngine, data: &Value, query: &str) -> Result<Option<Value>, QueryError> {
+    let Some(rest) = query.strip_prefix("reduce ") else {
+        return Ok(None);
+    };
+    let Some(as_pos) = rest.find(" as $") else {
+        return Err(QueryError::InvalidSyntax("reduce requires 'SOURCE as $name (INIT; UPDATE)'".to_string()));
+    };
+    let source = rest[..as_pos].trim();
+    let after_as = &rest[as_pos + " as $".len()..];
+    let name_len = after_as
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(after_as.len());
+    let name = &after_as[..name_len];
+    let body = after_as[name_len..].trim();
+    if name.is_empty() {
+        return Err(QueryError::InvalidSyntax("reduce requires a variable name after 'as $'".to_string()));
+    }
+    
+    // The parenthesised body must close at the end of the query
+    if !body.starts_with('(') || closing_paren(body) != Some(body.len() - 1) {
+        return Ok(None);
+    }
+    let parts = split_top_level(&body[1..body.len() - 1], ';');
+    let [init, update] = parts.as_slice() else {
+        return Err(QueryError::InvalidSyntax("reduce body must be (INIT; UPDATE)".to_string()));
+    };
+    
+    let items = match engine.execute(data, source)? {
+        Value::Array(arr) => arr,
+        other => vec![other],
+    };
+    let mut acc = engine.execute(data, init)?;
+    for item in items {
+        acc = engine.with_variable(name, item).execute(&acc, update)?;
+    }
+    Ok(Some(acc))
+}
+
+/// Execute a variable reference, optionally followed by a path (e.g. `$item.id`)
+pub fn execute_variable(engine: &QueryEngine, query: &str) -> Result<Value, QueryError> {
+    let reference = &query[1..];
+    let name_len = reference
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(reference.len());
+    let (name, path) = reference.split_at(name_len);
+    let value = engine.variable(name)
+        .ok_or_else(|| QueryError::InvalidSyntax(format!("${} is not defined", name)))?;
+    if path.is_empty() {
+        Ok(value.clone())
+    } else {
+        engine.execute(value, path)
+    }
+}
+
+/// Find the index of the parenthesis closing the one at the start of `s`
+pub fn closing_paren(s: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escape = false;
+    
+    for (i, ch) in s.char_indices() {
+        if escape {
+            escape = false;
+            continue;
+        }
+        
+        match ch {
+            '\\' if in_string => escape = true,
+            '"' => in_string = !in_string,
+            '(' if !in_string => depth += 1,
+            ')' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    
+    None
+}
+
+/// Execute a variable binding, evaluating the body with `$name` in scope
+pub fn execute_binding(engine: &QueryEngine, data: &Value, binding: &BindingExpr) -> Result<Value, QueryError> {
+    let Some(input) = &binding.input else {
+        return bind_variable(engine, data, binding);
+    };
+    match engine.execute(data, input)? {
+        // An iterator before the binding runs it once per element, as in pipes
+        Value::Array(items) if input.ends_with("[]") => Ok(Value::Array(
+            items.iter()
+                .filter_map(|item| bind_variable(engine, item, binding).ok())
+                .filter(|result| !result.is_null())
+                .collect(),
+        )),
+        value => bind_variable(engine, &value, binding),
+    }
+}
+
+fn bind_variable(engine: &QueryEngine, input: &Value, binding: &BindingExpr) -> Result<Value, QueryError> {
+    match engine.execute(input, &binding.source)? {
+        // Binding an iterator evaluates the body once per element
+        Value::Array(items) if binding.source.ends_with("[]") => Ok(Value::Array(
+            items.into_iter()
+                .filter_map(|item| engine.with_variable(&binding.name, item).execute(input, &binding.body).ok())
+                .filter(|result| !result.is_null())
+                .collect(),
+        )),
+        value => engine.with_variable(&binding.name, value).execute(input, &binding.body),
+    }
+}
+
 /// Execute an alternative expression (// operator)
 pub fn execute_alternative(engine: &QueryEngine, data: &Value, query: &str) -> Result<Value, QueryError> {
     let parts: Vec<&str> = query.splitn(2, " // ").collect();
@@ -217,7 +333,7 @@ pub fn execute_object_construction(engine: &QueryEngine, data: &Value, query: &s
             };
             
             // Try to parse as literal value first, then as query if that fails
-            let value = if value_str.starts_with('.') || value_str.contains(' ') || value_str.contains('(') {
+            let value = if value_str.starts_with('.') || value_str.starts_with('$') || value_str.contains(' ') || value_str.contains('(') {
                 // Looks like a query, try executing first
                 engine.execute(data, value_str)
                     .or_else(|_| engine.parser.parse_value(value_str))?
@@ -227,7 +343,8 @@ pub fn execute_object_construction(engine: &QueryEngine, data: &Value, query: &s
                                          "reverse" | "sort" | "unique" | "flatten" | "min" | "max" |
                                          "empty" | "not" | "to_entries" | "from_entries" | "floor" |
                                          "ceil" | "round" | "abs" | "tostring" | "tonumber" | "trim" |
-                                         "ascii_upcase" | "ascii_downcase" | "paths" | "leaf_paths");
+                                         "ascii_upcase" | "ascii_downcase" | "paths" | "leaf_paths" |
+                                         "explode" | "implode");
                 
                 if is_function {
                     // Execute as function
@@ -240,6 +357,9 @@ pub fn execute_object_construction(engine: &QueryEngine, data: &Value, query: &s
             };
             
             object.insert(key.to_string(), value);
+        } else if let Some(name) = pair.strip_prefix('$') {
+            // Variable shorthand: {$name} means {name: $name}
+            object.insert(name.to_string(), execute_variable(engine, pair)?);
         } else {
             // Shorthand syntax: {name} means {name: .name}
             let key = pair;
@@ -318,6 +438,7 @@ pub fn execute_expression(engine: &QueryEngine, data: &Value, query: &str) -> Re
                         // Check if it's a known function or contains operators
                         let is_function_or_complex = |expr: &str| {
                             expr.starts_with('.') || 
+                            expr.starts_with('$') || 
                             expr.contains('[') || 
                             expr.contains('(') || 
                             operators.iter().any(|&o| expr.contains(o)) ||
@@ -326,7 +447,8 @@ pub fn execute_expression(engine: &QueryEngine, data: &Value, query: &str) -> Re
                                     "reverse" | "sort" | "unique" | "flatten" | "min" | "max" |
                                     "empty" | "not" | "to_entries" | "from_entries" | "floor" |
                                     "ceil" | "round" | "abs" | "tostring" | "tonumber" | "trim" |
-                                    "ascii_upcase" | "ascii_downcase" | "paths" | "leaf_paths")
+                                    "ascii_upcase" | "ascii_downcase" | "paths" | "leaf_paths" |
+                                    "explode" | "implode")
                         };
                         
                         let left = if is_function_or_complex(left_expr) {
@@ -670,6 +792,160 @@ pub fn set_path(data: &mut Value, path: &str, value: Value) -> Result<(), QueryE
     Ok(())
 }
 
+/// One step of a concrete location inside a value
+#[derive(Debug, Clone)]
+enum PathKey {
+    Field(String),
+    Index(usize),
+}
+
+/// One segment of a path expression, where `[]` iterates every element or value
+#[derive(Debug, Clone)]
+enum PathSegment {
+    Key(PathKey),
+    Iterate,
+}
+
+/// Split a path expression like `.users[].tags[0]` or `.["a b"]` into segments
+fn parse_path_segments(path: &str) -> Result<Vec<PathSegment>, QueryError> {
+    let path = path.trim();
+    let Some(mut rest) = path.strip_prefix('.') else {
+        return Err(QueryError::InvalidSyntax("Path must start with '.'".to_string()));
+    };
+    
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after_bracket) = rest.strip_prefix('[') {
+            let close = after_bracket.find(']')
+                .ok_or_else(|| QueryError::InvalidSyntax("Unclosed bracket".to_string()))?;
+            let inner = after_bracket[..close].trim();
+            segments.push(if inner.is_empty() {
+                PathSegment::Iterate
+            } else if inner.starts_with('"') && inner.ends_with('"') && inner.len() >= 2 {
+                PathSegment::Key(PathKey::Field(inner[1..inner.len() - 1].to_string()))
+            } else {
+                let index = inner.parse()
+                    .map_err(|_| QueryError::InvalidSyntax(format!("Invalid array index: {}", inner)))?;
+                PathSegment::Key(PathKey::Index(index))
+            });
+            rest = &after_bracket[close + 1..];
+        } else if let Some(after_dot) = rest.strip_prefix('.') {
+            rest = after_dot;
+        } else {
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            segments.push(PathSegment::Key(PathKey::Field(rest[..end].to_string())));
+            rest = &rest[end..];
+        }
+    }
+    
+    Ok(segments)
+}
+
+/// Expand a path expression into every concrete location it targets in `data`
+fn expand_path(data: &Value, path: &str) -> Result<Vec<Vec<PathKey>>, QueryError> {
+    let mut locations: Vec<Vec<PathKey>> = vec![vec![]];
+    
+    for segment in parse_path_segments(path)? {
+        match segment {
+            PathSegment::Key(key) => {
+                for location in &mut locations {
+                    location.push(key.clone());
+                }
+            }
+            PathSegment::Iterate => {
+                let mut expanded = Vec::new();
+                for location in locations {
+                    let keys: Vec<PathKey> = match get_at(data, &location) {
+                        Some(Value::Array(arr)) => (0..arr.len()).map(PathKey::Index).collect(),
+                        Some(Value::Object(obj)) => obj.keys().cloned().map(PathKey::Field).collect(),
+                        Some(Value::Null) | None => vec![],
+                        Some(_) => return Err(QueryError::TypeError("Cannot iterate over a scalar value".to_string())),
+                    };
+                    for key in keys {
+                        let mut next = location.clone();
+                        next.push(key);
+                        expanded.push(next);
+                    }
+                }
+                locations = expanded;
+            }
+        }
+    }
+    
+    Ok(locations)
+}
+
+fn get_at<'a>(data: &'a Value, location: &[PathKey]) -> Option<&'a Value> {
+    location.iter().try_fold(data, |current, key| match key {
+        PathKey::Field(name) => current.get(name),
+        PathKey::Index(index) => current.get(*index),
+    })
+}
+
+/// Set the value at a concrete location, creating missing object fields on the way
+fn set_at(data: &mut Value, location: &[PathKey], value: Value) -> Result<(), QueryError> {
+    let Some((last, parents)) = location.split_last() else {
+        *data = value;
+        return Ok(());
+    };
+    
+    let mut current = data;
+    for key in parents {
+        current = match key {
+            PathKey::Field(name) => {
+                if current.is_null() {
+                    *current = Value::Object(Map::new());
+                }
+                current.as_object_mut()
+                    .ok_or_else(|| QueryError::TypeError("Cannot access field on non-object".to_string()))?
+                    .entry(name.clone())
+                    .or_insert(Value::Null)
+            }
+            PathKey::Index(index) => current.as_array_mut()
+                .ok_or_else(|| QueryError::TypeError("Cannot index non-array".to_string()))?
+                .get_mut(*index)
+                .ok_or_else(|| QueryError::IndexOutOfBounds(format!("Index {} out of bounds", index)))?,
+        };
+    }
+    
+    match last {
+        PathKey::Field(name) => {
+            if current.is_null() {
+                *current = Value::Object(Map::new());
+            }
+            current.as_object_mut()
+                .ok_or_else(|| QueryError::TypeError("Cannot set field on non-object".to_string()))?
+                .insert(name.clone(), value);
+        }
+        PathKey::Index(index) => {
+            let slot = current.as_array_mut()
+                .ok_or_else(|| QueryError::TypeError("Cannot index non-array".to_string()))?
+                .get_mut(*index)
+                .ok_or_else(|| QueryError::IndexOutOfBounds(format!("Index {} out of bounds", index)))?;
+            *slot = value;
+        }
+    }
+    Ok(())
+}
+
+/// Assign a value to every location matched by a path expression containing `[]`
+pub fn set_paths(data: &mut Value, path: &str, value: Value) -> Result<(), QueryError> {
+    for location in expand_path(data, path)? {
+        set_at(data, &location, value.clone())?;
+    }
+    Ok(())
+}
+
+/// Replace every location matched by a path expression with `filter` applied to its current value
+pub fn update_paths(engine: &QueryEngine, data: &mut Value, path: &str, filter: &str) -> Result<(), QueryError> {
+    for location in expand_path(data, path)? {
+        let current = get_at(data, &location).cloned().unwrap_or(Value::Null);
+        let updated = engine.execute(&current, filter)?;
+        set_at(data, &location, updated)?;
+    }
+    Ok(())
+}
+
 // Helper functions
 
 fn is_truthy(value: &Value) -> bool {
@@ -735,18 +1011,32 @@ where
     }
 }
 
+/// Apply an arithmetic operator, keeping the result a JSON integer when both operands are
+/// integers and the integer operation succeeds; otherwise fall back to float arithmetic
+fn numeric_op(
+    left: &serde_json::Number,
+    right: &serde_json::Number,
+    int_op: impl Fn(i64, i64) -> Option<i64>,
+    float_op: impl Fn(f64, f64) -> f64,
+) -> Result<Value, QueryError> {
+    let integer_result = match (left.as_i64(), right.as_i64()) {
+        (Some(l), Some(r)) => int_op(l, r),
+        _ => None,
+    };
+    if let Some(result) = integer_result {
+        return Ok(Value::from(result));
+    }
+    
+    let l_f = left.as_f64().ok_or_else(|| QueryError::ExecutionError("Invalid number".to_string()))?;
+    let r_f = right.as_f64().ok_or_else(|| QueryError::ExecutionError("Invalid number".to_string()))?;
+    serde_json::Number::from_f64(float_op(l_f, r_f))
+        .map(Value::Number)
+        .ok_or_else(|| QueryError::ExecutionError("Invalid number result".to_string()))
+}
+
 fn add_values(left: &Value, right: &Value) -> Result<Value, QueryError> {
     match (left, right) {
-        (Value::Number(l), Value::Number(r)) => {
-            // Always use float arithmetic to match jq behavior
-            let l_f = l.as_f64().ok_or_else(|| QueryError::ExecutionError("Invalid number".to_string()))?;
-            let r_f = r.as_f64().ok_or_else(|| QueryError::ExecutionError("Invalid number".to_string()))?;
-            let result = l_f + r_f;
-            
-            serde_json::Number::from_f64(result)
-                .map(Value::Number)
-                .ok_or_else(|| QueryError::ExecutionError("Invalid number result".to_string()))
-        }
+        (Value::Number(l), Value::Number(r)) => numeric_op(l, r, i64::checked_add, |l, r| l + r),
         (Value::String(l), Value::String(r)) => Ok(Value::String(format!("{}{}", l, r))),
         (Value::Array(l), Value::Array(r)) => {
             let mut result = l.clone();
@@ -776,32 +1066,14 @@ fn add_values(left: &Value, right: &Value) -> Result<Value, QueryError> {
 
 fn subtract_values(left: &Value, right: &Value) -> Result<Value, QueryError> {
     match (left, right) {
-        (Value::Number(l), Value::Number(r)) => {
-            // Always use float arithmetic to match jq behavior
-            let l_f = l.as_f64().ok_or_else(|| QueryError::ExecutionError("Invalid number".to_string()))?;
-            let r_f = r.as_f64().ok_or_else(|| QueryError::ExecutionError("Invalid number".to_string()))?;
-            let result = l_f - r_f;
-            
-            serde_json::Number::from_f64(result)
-                .map(Value::Number)
-                .ok_or_else(|| QueryError::ExecutionError("Invalid number result".to_string()))
-        }
+        (Value::Number(l), Value::Number(r)) => numeric_op(l, r, i64::checked_sub, |l, r| l - r),
         _ => Err(QueryError::TypeError("Cannot subtract these types".to_string())),
     }
 }
 
 fn multiply_values(left: &Value, right: &Value) -> Result<Value, QueryError> {
     match (left, right) {
-        (Value::Number(l), Value::Number(r)) => {
-            // Always use float arithmetic to match jq behavior
-            let l_f = l.as_f64().ok_or_else(|| QueryError::ExecutionError("Invalid number".to_string()))?;
-            let r_f = r.as_f64().ok_or_else(|| QueryError::ExecutionError("Invalid number".to_string()))?;
-            let result = l_f * r_f;
-            
-            serde_json::Number::from_f64(result)
-                .map(Value::Number)
-                .ok_or_else(|| QueryError::ExecutionError("Invalid number result".to_string()))
-        }
+        (Value::Number(l), Value::Number(r)) => numeric_op(l, r, i64::checked_mul, |l, r| l * r),
         _ => Err(QueryError::TypeError("Cannot multiply these types".to_string())),
     }
 }
@@ -809,19 +1081,12 @@ fn multiply_values(left: &Value, right: &Value) -> Result<Value, QueryError> {
 fn divide_values(left: &Value, right: &Value) -> Result<Value, QueryError> {
     match (left, right) {
         (Value::Number(l), Value::Number(r)) => {
-            // Always use float arithmetic to match jq behavior
-            let l_f = l.as_f64().ok_or_else(|| QueryError::ExecutionError("Invalid number".to_string()))?;
-            let r_f = r.as_f64().ok_or_else(|| QueryError::ExecutionError("Invalid number".to_string()))?;
-            
-            if r_f == 0.0 {
+            if r.as_f64() == Some(0.0) {
                 return Err(QueryError::DivisionByZero);
             }
-            
-            let result = l_f / r_f;
-            
-            serde_json::Number::from_f64(result)
-                .map(Value::Number)
-                .ok_or_else(|| QueryError::ExecutionError("Invalid number result".to_string()))
+            // Integer division only stays integral when it divides evenly
+            let exact_div = |l: i64, r: i64| l.checked_rem(r).filter(|rem| *rem == 0).and_then(|_| l.checked_div(r));
+            numeric_op(l, r, exact_div, |l, r| l / r)
         }
         _ => Err(QueryError::TypeError("Cannot divide these types".to_string())),
     }
@@ -830,19 +1095,10 @@ fn divide_values(left: &Value, right: &Value) -> Result<Value, QueryError> {
 fn modulo_values(left: &Value, right: &Value) -> Result<Value, QueryError> {
     match (left, right) {
         (Value::Number(l), Value::Number(r)) => {
-            // Always use float arithmetic to match jq behavior
-            let l_f = l.as_f64().ok_or_else(|| QueryError::ExecutionError("Invalid number".to_string()))?;
-            let r_f = r.as_f64().ok_or_else(|| QueryError::ExecutionError("Invalid number".to_string()))?;
-            
-            if r_f == 0.0 {
+            if r.as_f64() == Some(0.0) {
                 return Err(QueryError::DivisionByZero);
             }
-            
-            let result = l_f % r_f;
-            
-            serde_json::Number::from_f64(result)
-                .map(Value::Number)
-                .ok_or_else(|| QueryError::ExecutionError("Invalid number result".to_string()))
+            numeric_op(l, r, i64::checked_rem, |l, r| l % r)
         }
         _ => Err(QueryError::TypeError("Cannot modulo these types".to_string())),
     }
@@ -1107,6 +1363,11 @@ fn has_top_level_comma(s: &str) -> bool {
 
 /// Split a string by top-level commas (not inside brackets, braces, or quotes)
 fn split_top_level_commas(s: &str) -> Vec<String> {
+    split_top_level(s, ',')
+}
+
+/// Split a string by a top-level separator (not inside brackets, braces, or quotes)
+pub fn split_top_level(s: &str, separator: char) -> Vec<String> {
     let mut result = Vec::new();
     let mut current = String::new();
     let mut depth = 0;
@@ -1137,7 +1398,7 @@ fn split_top_level_commas(s: &str) -> Vec<String> {
                 depth -= 1;
                 current.push(ch);
             }
-            ',' if !in_string && depth == 0 => {
+            c if c == separator && !in_string && depth == 0 => {
                 result.push(current.trim().to_string());
                 current.clear();
             }
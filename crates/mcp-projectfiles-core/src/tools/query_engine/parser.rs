@@ -1,5 +1,5 @@
 use serde_json::Value;
-use super::errors::QueryError;
+use super::errors::{QueryError, QuerySyntaxError};
 
 pub struct QueryParser;
 
@@ -30,6 +30,16 @@ pub struct ConditionalExpr {
     pub else_expr: Option<String>,
 }
 
+/// A variable binding `input | source as $name | body`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindingExpr {
+    /// Pipeline feeding the binding, if the binding is not at the start of the query
+    pub input: Option<String>,
+    pub source: String,
+    pub name: String,
+    pub body: String,
+}
+
 impl QueryParser {
     pub fn new() -> Self {
         Self
@@ -86,6 +96,19 @@ impl QueryParser {
         }
     }
     
+    /// Parse an update-assignment like ".users[].age |= . + 1" into the path and filter
+    pub fn parse_update_assignment(&self, query: &str) -> Option<(String, String)> {
+        let mut op_pos = None;
+        scan_top_level(query, |i| {
+            if query[i..].starts_with(" |= ") {
+                op_pos = Some(i);
+                return false;
+            }
+            true
+        });
+        op_pos.map(|pos| (query[..pos].trim().to_string(), query[pos + 4..].trim().to_string()))
+    }
+    
     /// Check if a string is an expression (arithmetic, comparison, etc.)
     pub fn is_expression(&self, query: &str) -> bool {
         // Check for arithmetic operators
@@ -115,11 +138,114 @@ impl QueryParser {
         false
     }
     
+    /// Check delimiters, strings, variables and dangling operators before execution so
+    /// malformed queries are reported with the position of the problem
+    pub fn validate(&self, query: &str) -> Result<(), QuerySyntaxError> {
+        let error = |offset: usize, message: String| QuerySyntaxError {
+            message,
+            query: query.to_string(),
+            offset,
+        };
+        let mut open: Vec<(char, usize)> = Vec::new();
+        let mut string_start = None;
+        let mut escape = false;
+        
+        for (i, ch) in query.char_indices() {
+            if string_start.is_some() {
+                match ch {
+                    _ if escape => escape = false,
+                    '\\' => escape = true,
+                    '"' => string_start = None,
+                    _ => {}
+                }
+                continue;
+            }
+            
+            match ch {
+                '"' => string_start = Some(i),
+                '(' | '[' | '{' => open.push((ch, i)),
+                ')' | ']' | '}' => match open.pop() {
+                    Some((opener, _)) if closer_for(opener) == ch => {}
+                    Some((opener, _)) => {
+                        return Err(error(i, format!("expected '{}' but found '{}'", closer_for(opener), ch)));
+                    }
+                    None => return Err(error(i, format!("unexpected '{}'", ch))),
+                },
+                '|' => {
+                    let operand = query[i + 1..].strip_prefix('=').unwrap_or(&query[i + 1..]).trim_start();
+                    if operand.is_empty() || operand.starts_with(['|', ')', ']', '}', ',', ';']) {
+                        return Err(error(i, "missing expression after '|'".to_string()));
+                    }
+                }
+                '$' if !query[i + 1..].starts_with(|c: char| c.is_alphanumeric() || c == '_') => {
+                    return Err(error(i, "expected a variable name after '$'".to_string()));
+                }
+                _ => {}
+            }
+        }
+        
+        if let Some(start) = string_start {
+            return Err(error(start, "unterminated string".to_string()));
+        }
+        if let Some((opener, at)) = open.pop() {
+            return Err(error(at, format!("unclosed '{}'", opener)));
+        }
+        
+        let trimmed = query.trim_end();
+        let dangling = [" =", " +", " -", " *", " /", " %", " ==", " !=", " <", " >", " <=", " >=", " and", " or", " //", ","];
+        if let Some(op) = dangling.iter().find(|op| trimmed.ends_with(*op)) {
+            let op = op.trim_start();
+            return Err(error(trimmed.len() - op.len(), format!("missing expression after '{}'", op)));
+        }
+        
+        Ok(())
+    }
+    
     /// Parse a pipe expression
     pub fn parse_pipe_expression(&self, query: &str) -> Vec<String> {
-        query.split(" | ")
-            .map(|s| s.trim().to_string())
-            .collect()
+        let mut parts = Vec::new();
+        let mut start = 0;
+        scan_top_level(query, |i| {
+            if query[i..].starts_with(" | ") {
+                parts.push(query[start..i].trim().to_string());
+                start = i + 3;
+            }
+            true
+        });
+        parts.push(query[start..].trim().to_string());
+        parts
+    }
+    
+    /// Parse a variable binding like `.items as $i | $i | length`. The source is the
+    /// term right before `as`; anything piped into it becomes the binding's input.
+    pub fn parse_binding(&self, query: &str) -> Option<BindingExpr> {
+        let mut last_pipe = None;
+        let mut binding = None;
+        scan_top_level(query, |i| {
+            if query[i..].starts_with(" | ") {
+                last_pipe = Some(i);
+                return true;
+            }
+            let Some(rest) = query[i..].strip_prefix(" as $") else {
+                return true;
+            };
+            let name_len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            // `reduce ... as $x (...)` is not a binding; keep scanning
+            let Some(body) = rest[name_len..].trim_start().strip_prefix('|') else {
+                return true;
+            };
+            let source_start = last_pipe.map_or(0, |p| p + 3);
+            binding = Some(BindingExpr {
+                input: last_pipe.map(|p| query[..p].trim().to_string()),
+                source: query[source_start..i].trim().to_string(),
+                name: rest[..name_len].to_string(),
+                body: body.trim().to_string(),
+            });
+            false
+        });
+        binding.filter(|b| !b.name.is_empty() && !b.source.is_empty())
     }
     
     /// Parse a conditional expression
@@ -330,6 +456,39 @@ pub enum PathSegment {
     ArraySlice(Option<usize>, Option<usize>),
 }
 
+fn closer_for(opener: char) -> char {
+    match opener {
+        '(' => ')',
+        '[' => ']',
+        _ => '}',
+    }
+}
+
+/// Call `visit` with the byte offset of every character outside strings, parentheses,
+/// brackets and braces. Scanning stops when `visit` returns false.
+fn scan_top_level(query: &str, mut visit: impl FnMut(usize) -> bool) {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escape = false;
+    
+    for (i, ch) in query.char_indices() {
+        if escape {
+            escape = false;
+            continue;
+        }
+        
+        match ch {
+            '\\' if in_string => escape = true,
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            _ if depth == 0 && !visit(i) => return,
+            _ => {}
+        }
+    }
+}
+
 impl Default for QueryParser {
     fn default() -> Self {
         Self::new()
@@ -1,6 +1,6 @@
 use crate::context::{StatefulTool, ToolContext};
-use crate::config::tool_errors;
-use crate::tools::utils::resolve_path_for_read;
+use crate::config::{max_result_bytes, tool_errors};
+use crate::tools::utils::{BINARY_SAMPLE_SIZE, DEFAULT_BINARY_THRESHOLD, BoundedOutput, check_binary_sample, resolve_path_for_read};
 use async_trait::async_trait;
 use rust_mcp_schema::{
     CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
@@ -8,12 +8,14 @@ use rust_mcp_schema::{
 use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 use tokio::fs;
-use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, BufReader};
 use regex::RegexBuilder;
-use encoding_rs;
+use encoding_rs::{self, Encoding};
 use chrono::{DateTime, Utc};
 
 const TOOL_NAME: &str = "read";
@@ -25,10 +27,25 @@ struct FileMetadata {
     modified: String,
     lines: usize,
     encoding: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detected_encoding: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encoding_confidence: Option<String>,
     has_bom: bool,
     is_binary: bool,
 }
 
+/// Bytes sampled when sniffing the encoding in "auto" mode
+pub(super) const ENCODING_SAMPLE_SIZE: usize = 64 * 1024;
+
+pub(super) const SUPPORTED_ENCODINGS: &str = "auto, utf-8, ascii, latin1, utf-16, utf-16le, utf-16be";
+
+/// Encoding picked by "auto" mode and how sure the detector was
+pub(super) struct DetectedEncoding {
+    pub(super) encoding: &'static Encoding,
+    confidence: &'static str,
+}
+
 fn default_encoding() -> String {
     "utf-8".to_string()
 }
@@ -45,35 +62,146 @@ fn default_binary_check() -> bool {
     true
 }
 
+fn default_binary_threshold() -> u8 {
+    DEFAULT_BINARY_THRESHOLD
+}
+
 fn default_case() -> String {
     "sensitive".to_string()
 }
 
+fn default_follow_timeout_ms() -> u64 {
+    5000
+}
+
+/// Upper bound on how long a single follow call may block
+const MAX_FOLLOW_TIMEOUT_MS: u64 = 60_000;
+
+/// How often the file is checked for new content while following
+const FOLLOW_POLL_INTERVAL_MS: u64 = 100;
+
+/// Bytes shown by a hex dump when no limit is given
+const DEFAULT_HEX_DUMP_BYTES: u64 = 4096;
+
+/// Bytes per hex dump row, matching xxd
+const HEX_DUMP_ROW_BYTES: usize = 16;
+
+/// Most files the read cache holds before evicting the least recently used
+const READ_CACHE_MAX_ENTRIES: usize = 32;
+
+/// Most decoded bytes the read cache holds; larger files are never cached
+const READ_CACHE_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+/// A decoded file remembered from an earlier read
+struct CachedFile {
+    modified: Option<SystemTime>,
+    size: u64,
+    /// The first bytes on disk, for binary detection without reopening the file
+    sample: Arc<[u8]>,
+    content: Arc<str>,
+    last_used: u64,
+}
+
+/// Decoded file contents kept in the session, keyed by canonical path and requested encoding.
+/// An entry is only used while the file's mtime and size are unchanged.
+#[derive(Default)]
+struct ReadCache {
+    entries: HashMap<(PathBuf, String), CachedFile>,
+    total_bytes: usize,
+    clock: u64,
+}
+
+impl ReadCache {
+    fn get(&mut self, key: &(PathBuf, String), metadata: &std::fs::Metadata) -> Option<(Arc<[u8]>, Arc<str>)> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(key)?;
+        if entry.modified.is_some() && entry.modified == metadata.modified().ok() && entry.size == metadata.len() {
+            entry.last_used = self.clock;
+            return Some((entry.sample.clone(), entry.content.clone()));
+        }
+        // Changed on disk since it was cached
+        if let Some(stale) = self.entries.remove(key) {
+            self.total_bytes -= stale.content.len();
+        }
+        None
+    }
+
+    fn insert(&mut self, key: (PathBuf, String), metadata: &std::fs::Metadata, sample: Arc<[u8]>, content: Arc<str>) {
+        if content.len() > READ_CACHE_MAX_BYTES {
+            return;
+        }
+        self.clock += 1;
+        self.total_bytes += content.len();
+        let replaced = self.entries.insert(key, CachedFile {
+            modified: metadata.modified().ok(),
+            size: metadata.len(),
+            sample,
+            content,
+            last_used: self.clock,
+        });
+        if let Some(replaced) = replaced {
+            self.total_bytes -= replaced.content.len();
+        }
+
+        while self.entries.len() > READ_CACHE_MAX_ENTRIES || self.total_bytes > READ_CACHE_MAX_BYTES {
+            let Some(oldest) = self.entries.iter().min_by_key(|(_, entry)| entry.last_used).map(|(key, _)| key.clone()) else {
+                break;
+            };
+            if let Some(evicted) = self.entries.remove(&oldest) {
+                self.total_bytes -= evicted.content.len();
+            }
+        }
+    }
+}
+
+/// The session's read cache, created on first use
+async fn read_cache(context: &ToolContext) -> Arc<Mutex<ReadCache>> {
+    if let Some(cache) = context.get_custom_state::<Mutex<ReadCache>>().await {
+        return cache;
+    }
+    context.set_custom_state(Mutex::new(ReadCache::default())).await;
+    context.get_custom_state::<Mutex<ReadCache>>().await
+        .expect("read cache was just stored")
+}
+
 
 #[mcp_tool(name = "read", description = "Read text files with line numbers, pattern filtering, ranges, and tail mode.
 
-Key features: offset/limit, line_range (\"10-20\"), pattern matching with context, tail mode, encoding detection, preview mode.
+Key features: offset/limit, line_range (\"10-20\"), pattern matching with context, tail mode, follow mode, encoding detection, preview mode.
+hex_dump shows an xxd-style dump of any file (binary included); offset/limit then count bytes instead of lines.
+Follow mode waits up to follow_timeout_ms and returns lines appended during that window (like tail -f, but one call per window).
 
 Examples:
 - {\"path\": \"src/main.rs\", \"line_range\": \"10-20\"}
 - {\"path\": \"app.log\", \"tail\": true, \"limit\": 20}
-- {\"path\": \"lib.rs\", \"pattern\": \"TODO\", \"context_after\": 2}")]
+- {\"path\": \"app.log\", \"tail\": true, \"limit\": 20, \"follow\": true, \"follow_timeout_ms\": 10000}
+- {\"path\": \"lib.rs\", \"pattern\": \"TODO\", \"context_after\": 2}
+- {\"path\": \"README.md\", \"pattern\": \"https?://[^ )]+\", \"only_matching\": true}
+- {\"path\": \"logo.png\", \"hex_dump\": true, \"limit\": 64}")]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct ReadTool {
     /// Path to the file to read (relative to project root)
     pub path: String,
-    /// Starting line number (1-indexed). Use 0 for beginning of file (default)
+    /// Starting line number (1-indexed). Use 0 for beginning of file (default).
+    /// With hex_dump, the 0-based byte offset to start from
     #[serde(default)]
     pub offset: u32,
-    /// Maximum number of lines to read. Use 0 for all lines (default)
+    /// Maximum number of lines to read. Use 0 for all lines (default).
+    /// With hex_dump, the number of bytes to dump (0 means 4096)
     #[serde(default)]
     pub limit: u32,
     /// Line range to read (e.g., \"10-20\"). Overrides offset/limit if provided
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub line_range: Option<String>,
-    /// Perform binary file detection if true (default: true)
+    /// Perform binary file detection if true (default: true). Prefer force_text to skip detection
     #[serde(default = "default_binary_check")]
     pub binary_check: bool,
+    /// Percentage of non-text bytes in the first 8192 bytes above which a file is treated as binary (default: 10)
+    #[serde(default = "default_binary_threshold")]
+    pub binary_threshold: u8,
+    /// Read the file as text no matter what it contains; skips binary detection (default: false)
+    #[serde(default)]
+    pub force_text: bool,
     /// Read from the end of the file (tail mode). If true, offset is from end (default: false)
     #[serde(default)]
     pub tail: bool,
@@ -83,6 +211,9 @@ pub struct ReadTool {
     /// Invert pattern matching - show lines that do NOT match the pattern (default: false)
     #[serde(default)]
     pub invert_match: bool,
+    /// Output only the matched text (capture group 1 if the pattern has one), one match per line (default: false)
+    #[serde(default)]
+    pub only_matching: bool,
     /// Number of context lines to show before each match (default: 0)
     #[serde(default)]
     pub context_before: u32,
@@ -93,7 +224,8 @@ pub struct ReadTool {
     #[serde(default = "default_case")]
     pub case: String,
     /// Text encoding to use when reading the file (default: "utf-8")
-    /// Supported: "utf-8", "ascii", "latin1", "utf-16", "utf-16le", "utf-16be"
+    /// Supported: "auto", "utf-8", "ascii", "latin1", "utf-16", "utf-16le", "utf-16be".
+    /// "auto" detects the encoding from a BOM or a sample of the file
     #[serde(default = "default_encoding")]
     pub encoding: String,
     /// Show line numbers in output (default: true)
@@ -108,14 +240,29 @@ pub struct ReadTool {
     /// Include file metadata in response (default: false)
     #[serde(default)]
     pub include_metadata: bool,
+    /// After the initial read, keep collecting lines appended to the file until follow_timeout_ms elapses (default: false)
+    #[serde(default)]
+    pub follow: bool,
+    /// How long follow mode waits for new lines in milliseconds (default: 5000, max: 60000)
+    #[serde(default = "default_follow_timeout_ms")]
+    pub follow_timeout_ms: u64,
+    /// Output an xxd-style hex and ASCII dump instead of text; skips the binary check (default: false)
+    #[serde(default)]
+    pub hex_dump: bool,
+    /// Bypass the session cache of decoded files and read from disk (default: false).
+    /// Cached content is only reused while the file's mtime and size are unchanged
+    #[serde(default)]
+    pub no_cache: bool,
 }
 
 #[async_trait]
 impl StatefulTool for ReadTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
         let project_root = context.get_project_root()
             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
         
@@ -127,8 +274,61 @@ impl StatefulTool for ReadTool {
             )));
         }
         
+        if self.only_matching {
+            if self.pattern.is_none() {
+                return Err(CallToolError::from(tool_errors::invalid_input(TOOL_NAME, "only_matching requires a pattern")));
+            }
+            if self.invert_match || self.context_before > 0 || self.context_after > 0 {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    TOOL_NAME,
+                    "only_matching cannot be combined with invert_match or context lines"
+                )));
+            }
+        }
+        
+        if self.binary_threshold > 100 {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Invalid binary_threshold {}. Must be a percentage between 0 and 100", self.binary_threshold)
+            )));
+        }
+        
+        if self.encoding.to_lowercase() != "auto" && explicit_encoding(&self.encoding).is_none() {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Unsupported encoding: {}. Supported: {}", self.encoding, SUPPORTED_ENCODINGS)
+            )));
+        }
+        
+        if self.hex_dump && (self.pattern.is_some() || self.line_range.is_some() || self.tail || self.follow) {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                "hex_dump cannot be combined with pattern, line_range, tail or follow"
+            )));
+        }
+        
+        if self.follow && (self.follow_timeout_ms == 0 || self.follow_timeout_ms > MAX_FOLLOW_TIMEOUT_MS) {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("follow_timeout_ms must be between 1 and {}", MAX_FOLLOW_TIMEOUT_MS)
+            )));
+        }
+        
         // Use the utility function to resolve path with symlink support
         let canonical_path = resolve_path_for_read(&self.path, &project_root, self.follow_symlinks, TOOL_NAME)?;
+        
+        // Following keeps a handle on the file for a while, so symlinks out of the project are not allowed
+        if self.follow {
+            let canonical_root = project_root.canonicalize()
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to canonicalize project root: {}", e))))?;
+            if !canonical_path.starts_with(&canonical_root) {
+                return Err(CallToolError::from(tool_errors::access_denied(
+                    TOOL_NAME,
+                    &self.path,
+                    "Follow mode only works on files inside the project directory"
+                )));
+            }
+        }
 
         if !canonical_path.exists() {
             return Err(CallToolError::from(tool_errors::file_not_found(
@@ -161,9 +361,40 @@ impl StatefulTool for ReadTool {
                 meta: None,
             });
         }
+        
+        if self.hex_dump {
+            let dump = self.hex_dump_file(&canonical_path, file_size).await?;
+            return Ok(CallToolResult {
+                content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                    dump, None,
+                ))],
+                is_error: Some(false),
+                meta: None,
+            });
+        }
+
+        // Reuse the decoded content of an unchanged file from an earlier read
+        let cache_key = (canonical_path.clone(), self.encoding.to_lowercase());
+        let cache = read_cache(context).await;
+        let cached = if self.no_cache {
+            None
+        } else {
+            cache.lock().unwrap().get(&cache_key, &file_metadata)
+        };
 
         // Binary file detection (unless skipped)
-        if self.binary_check {
+        if let (Some((sample, _)), true) = (&cached, self.binary_check && !self.force_text) {
+            if let Some((non_text_bytes, sample_size)) = check_binary_sample(sample, self.binary_threshold) {
+                return Err(CallToolError::from(tool_errors::binary_file(
+                    TOOL_NAME,
+                    &self.path,
+                    &format!(
+                        "{} of the first {} bytes are non-text, above the {}% threshold. Use force_text=true to read anyway",
+                        non_text_bytes, sample_size, self.binary_threshold
+                    ),
+                )));
+            }
+        } else if self.binary_check && !self.force_text {
             let mut file = tokio::fs::File::open(&canonical_path).await
                 .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to open file: {}", e))))?;
             
@@ -171,7 +402,7 @@ impl StatefulTool for ReadTool {
                 .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get file metadata: {}", e))))?
                 .len() as usize;
             
-            let sample_size = 8192.min(file_size);
+            let sample_size = BINARY_SAMPLE_SIZE.min(file_size);
             let mut buffer = vec![0; sample_size];
             
             let bytes_read = file.read(&mut buffer).await
@@ -180,18 +411,39 @@ impl StatefulTool for ReadTool {
             buffer.truncate(bytes_read);
             
             // Check for null bytes or high proportion of non-text bytes
-            let non_text_bytes = buffer.iter()
-                .filter(|&&b| b == 0 || (b < 32 && b != 9 && b != 10 && b != 13) || b > 126)
-                .count();
-            
-            if non_text_bytes > buffer.len() / 10 {
-                return Err(CallToolError::from(tool_errors::binary_file(TOOL_NAME, &self.path)));
+            if let Some((non_text_bytes, sample_size)) = check_binary_sample(&buffer, self.binary_threshold) {
+                return Err(CallToolError::from(tool_errors::binary_file(
+                    TOOL_NAME,
+                    &self.path,
+                    &format!(
+                        "{} of the first {} bytes are non-text, above the {}% threshold. Use force_text=true to read anyway",
+                        non_text_bytes, sample_size, self.binary_threshold
+                    ),
+                )));
+            }
+        }
+
+        // A forward window only needs the lines up to its end, so stop there rather than reading the whole file
+        let window = if cached.is_none() { self.forward_window()? } else { None };
+        if let Some((start, end)) = window {
+            let content = self.read_forward_window(&canonical_path, start, end).await?;
+            if let Some(content) = content {
+                return self.respond(context, canonical_path, content, file_size, &file_metadata).await;
             }
         }
 
         // Read the full file content with encoding support
-        let full_content = self.read_file_with_encoding(&canonical_path).await
-            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read file: {}", e))))?;
+        let full_content = match cached {
+            Some((_, content)) => content,
+            None => {
+                let bytes = fs::read(&canonical_path).await
+                    .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read file: {}", e))))?;
+                let content: Arc<str> = self.decode(&bytes).into();
+                let sample: Arc<[u8]> = bytes[..BINARY_SAMPLE_SIZE.min(bytes.len())].into();
+                cache.lock().unwrap().insert(cache_key, &file_metadata, sample, content.clone());
+                content
+            }
+        };
         
         let all_lines: Vec<&str> = full_content.lines().collect();
         let original_line_count = all_lines.len();
@@ -247,6 +499,16 @@ impl StatefulTool for ReadTool {
                         filtered_line_numbers.push(idx + 1);
                     }
                 }
+            } else if self.only_matching {
+                // Emit each match (or its first capture group) against the source line number
+                for (idx, line) in all_lines.iter().enumerate() {
+                    for captures in regex.captures_iter(line) {
+                        if let Some(found) = captures.get(1).or_else(|| captures.get(0)) {
+                            filtered_lines.push(found.as_str());
+                            filtered_line_numbers.push(idx + 1);
+                        }
+                    }
+                }
             } else {
                 // Simple filtering without context
                 for (idx, line) in all_lines.iter().enumerate() {
@@ -311,50 +573,177 @@ impl StatefulTool for ReadTool {
         };
         
         // Format the output
-        let content = if start >= total_lines {
+        let mut content = if start >= total_lines {
             String::from("[No content at specified offset]")
         } else {
             let selected_lines = &lines[start..end];
             let selected_line_numbers = &line_numbers[start..end];
-            let mut result = String::with_capacity((end - start) * 80); // Estimate capacity
+            let mut output = BoundedOutput::new();
             
             for (idx, line) in selected_lines.iter().enumerate() {
-                if self.linenumbers {
+                let fits = if self.linenumbers {
                     let line_num = selected_line_numbers[idx];
-                    result.push_str(&format!("{:>6}\t{}\n", line_num, line));
+                    output.push_str(&format!("{:>6}\t{}\n", line_num, line))
                 } else {
-                    result.push_str(&format!("{}\n", line));
+                    output.push_str(&format!("{}\n", line))
+                };
+                if !fits {
+                    break;
                 }
             }
             
-            // Add truncation notice if needed
-            if self.pattern.is_some() {
-                if self.limit > 0 && end < total_lines {
+            // Add truncation notice if needed; a size cut carries its own
+            let size_truncated = output.is_truncated();
+            let mut result = output.into_string();
+            if !size_truncated {
+                if self.pattern.is_some() {
+                    if self.limit > 0 && end < total_lines {
+                        result.push_str(&format!(
+                            "\n[Pattern matched {} lines out of {} total. Showing lines {}-{}. Use offset={} to continue]",
+                            total_lines, original_line_count, start + 1, end, end + 1
+                        ));
+                    } else if total_lines < original_line_count {
+                        result.push_str(&format!(
+                            "\n[Pattern matched {} lines out of {} total lines]",
+                            total_lines, original_line_count
+                        ));
+                    }
+                } else if self.tail && self.limit > 0 && start > 0 {
                     result.push_str(&format!(
-                        "\n[Pattern matched {} lines out of {} total. Showing lines {}-{}. Use offset={} to continue]",
-                        total_lines, original_line_count, start + 1, end, end + 1
+                        "\n[Tail mode: Showing last {} lines. File has {} total lines. Use limit={} to see more]",
+                        end - start, total_lines, self.limit + 10
                     ));
-                } else if total_lines < original_line_count {
+                } else if !self.tail && self.limit > 0 && end < total_lines {
                     result.push_str(&format!(
-                        "\n[Pattern matched {} lines out of {} total lines]",
-                        total_lines, original_line_count
+                        "\n[Truncated at line {}. File has {} total lines. Use offset={} to continue reading]",
+                        end, total_lines, end + 1
                     ));
                 }
-            } else if self.tail && self.limit > 0 && start > 0 {
-                result.push_str(&format!(
-                    "\n[Tail mode: Showing last {} lines. File has {} total lines. Use limit={} to see more]",
-                    end - start, total_lines, self.limit + 10
-                ));
-            } else if !self.tail && self.limit > 0 && end < total_lines {
-                result.push_str(&format!(
-                    "\n[Truncated at line {}. File has {} total lines. Use offset={} to continue reading]",
-                    end, total_lines, end + 1
-                ));
             }
             
             result
         };
+        
+        if self.follow {
+            // Followed lines share the result budget with what was already read
+            let budget = max_result_bytes().saturating_sub(content.len());
+            content.push_str(&self.follow_file(&canonical_path, file_size, original_line_count, budget, context).await?);
+        }
+
+        self.respond(context, canonical_path, content, file_size, &file_metadata).await
+    }
+}
+
+impl ReadTool {
+    pub async fn call(self) -> Result<CallToolResult, CallToolError> {
+        let context = ToolContext::new();
+        self.call_with_context(&context).await
+    }
+
+    /// The 0-based `[start, end)` line window of a plain forward read of a UTF-8 file.
+    /// None when the read needs the whole file: tail, pattern, follow, an open-ended range or no limit
+    fn forward_window(&self) -> Result<Option<(usize, usize)>, CallToolError> {
+        if self.tail || self.follow || self.pattern.is_some() {
+            return Ok(None);
+        }
+        if explicit_encoding(&self.encoding) != Some(encoding_rs::UTF_8) {
+            return Ok(None);
+        }
+
+        if let Some(ref range) = self.line_range {
+            // An open end resolves to the line count, which isn't known without reading to the end
+            if range.trim_end().ends_with('-') {
+                return Ok(None);
+            }
+            return Ok(match self.parse_line_range(range, 0)? {
+                (Some(rs), Some(re)) => Some((rs.saturating_sub(1), re)),
+                _ => None,
+            });
+        }
+
+        if self.limit == 0 {
+            return Ok(None);
+        }
+        let start = (self.offset as usize).saturating_sub(1);
+        Ok(Some((start, start + self.limit as usize)))
+    }
+
+    /// Read lines `[start, end)` line by line, stopping once the window or the result size cap is full.
+    /// Returns None for a file with a non-UTF-8 BOM, which needs the full decode
+    async fn read_forward_window(&self, path: &Path, start: usize, end: usize) -> Result<Option<String>, CallToolError> {
+        let file = fs::File::open(path).await
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to open file: {}", e))))?;
+        let mut reader = BufReader::new(file);
+
+        let mut output = BoundedOutput::new();
+        let mut buffer = Vec::new();
+        let mut line_number = 0;
+        let mut had_errors = false;
+        loop {
+            buffer.clear();
+            let bytes_read = reader.read_until(b'\n', &mut buffer).await
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read file: {}", e))))?;
+            if bytes_read == 0 || line_number >= end {
+                break;
+            }
+
+            let mut line = buffer.as_slice();
+            if line_number == 0 {
+                match Encoding::for_bom(line) {
+                    Some((encoding, bom_length)) if encoding == encoding_rs::UTF_8 => line = &line[bom_length..],
+                    Some(_) => return Ok(None),
+                    None => {}
+                }
+            }
+            line_number += 1;
+            if line_number <= start {
+                continue;
+            }
 
+            // Match str::lines: drop the "\n" or "\r\n" terminator
+            let line = line.strip_suffix(b"\n").unwrap_or(line);
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            let text = String::from_utf8_lossy(line);
+            had_errors |= matches!(text, std::borrow::Cow::Owned(_));
+            let fits = if self.linenumbers {
+                output.push_str(&format!("{:>6}\t{}\n", line_number, text))
+            } else {
+                output.push_str(&format!("{}\n", text))
+            };
+            if !fits {
+                break;
+            }
+        }
+
+        if had_errors {
+            eprintln!("Warning: Some characters could not be decoded with UTF-8 encoding");
+        }
+
+        // The loop stops one line past the window, so a non-empty buffer there means there's more
+        let more_lines = !buffer.is_empty();
+        if !more_lines && line_number <= start {
+            return Ok(Some(String::from("[No content at specified offset]")));
+        }
+        let size_truncated = output.is_truncated();
+        let mut result = output.into_string();
+        if !size_truncated && self.line_range.is_none() && more_lines {
+            result.push_str(&format!(
+                "\n[Truncated at line {}. Use offset={} to continue reading]",
+                end, end + 1
+            ));
+        }
+        Ok(Some(result))
+    }
+
+    /// Record the read and wrap `content`, with file metadata alongside if requested
+    async fn respond(
+        &self,
+        context: &ToolContext,
+        canonical_path: PathBuf,
+        content: String,
+        file_size: u64,
+        file_metadata: &std::fs::Metadata,
+    ) -> Result<CallToolResult, CallToolError> {
         let read_files = context.get_custom_state::<HashSet<PathBuf>>().await
             .unwrap_or_else(|| std::sync::Arc::new(HashSet::new()));
         let mut read_files_clone = (*read_files).clone();
@@ -363,7 +752,7 @@ impl StatefulTool for ReadTool {
 
         // Build response with optional metadata
         if self.include_metadata {
-            let metadata = self.create_file_metadata(&canonical_path, file_size, &file_metadata).await?;
+            let metadata = self.create_file_metadata(&canonical_path, file_size, file_metadata).await?;
             let response = json!({
                 "content": content,
                 "metadata": metadata
@@ -385,34 +774,174 @@ impl StatefulTool for ReadTool {
             })
         }
     }
-}
 
-impl ReadTool {
-    pub async fn call(self) -> Result<CallToolResult, CallToolError> {
-        let context = ToolContext::new();
-        self.call_with_context(&context).await
+    /// Dump `limit` bytes starting at byte `offset` in xxd format
+    async fn hex_dump_file(&self, path: &Path, file_size: u64) -> Result<String, CallToolError> {
+        let start = (self.offset as u64).min(file_size);
+        let length = if self.limit > 0 { self.limit as u64 } else { DEFAULT_HEX_DUMP_BYTES };
+        let end = start.saturating_add(length).min(file_size);
+        
+        let mut file = fs::File::open(path).await
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to open file: {}", e))))?;
+        file.seek(std::io::SeekFrom::Start(start)).await
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to seek file: {}", e))))?;
+        let mut buffer = vec![0u8; (end - start) as usize];
+        file.read_exact(&mut buffer).await
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read file: {}", e))))?;
+        
+        if buffer.is_empty() {
+            return Ok(String::from("[No content at specified offset]"));
+        }
+        
+        let mut result = String::with_capacity(buffer.len() / HEX_DUMP_ROW_BYTES * 68 + 68);
+        for (row, chunk) in buffer.chunks(HEX_DUMP_ROW_BYTES).enumerate() {
+            let address = start + (row * HEX_DUMP_ROW_BYTES) as u64;
+            let mut hex = String::with_capacity(40);
+            for (i, byte) in chunk.iter().enumerate() {
+                if i > 0 && i % 2 == 0 {
+                    hex.push(' ');
+                }
+                hex.push_str(&format!("{:02x}", byte));
+            }
+            let ascii: String = chunk.iter()
+                .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+                .collect();
+            result.push_str(&format!("{:08x}: {:<39}  {}\n", address, hex, ascii));
+        }
+        
+        if end < file_size {
+            result.push_str(&format!(
+                "\n[Showing bytes {}-{} of {}. Use offset={} to continue]",
+                start, end, file_size, end
+            ));
+        }
+        
+        Ok(result)
+    }
+
+    /// Poll the file for appended data until the follow timeout elapses.
+    /// A shrinking size (truncation) or a new inode (rotation) restarts reading from the top.
+    async fn follow_file(
+        &self,
+        path: &Path,
+        start_offset: u64,
+        start_line: usize,
+        budget: usize,
+        context: &ToolContext,
+    ) -> Result<String, CallToolError> {
+        let regex = match self.pattern {
+            Some(ref pattern) => Some(RegexBuilder::new(pattern)
+                .case_insensitive(self.case == "insensitive")
+                .build()
+                .map_err(|e| CallToolError::from(tool_errors::pattern_error(TOOL_NAME, pattern, &e.to_string())))?),
+            None => None,
+        };
+        
+        let mut output = BoundedOutput::with_limit(budget);
+        output.push_str(&format!("\n[Following for {}ms]\n", self.follow_timeout_ms));
+        let mut offset = start_offset;
+        let mut line_number = start_line;
+        let mut new_lines = 0;
+        let mut pending: Vec<u8> = Vec::new();
+        let mut inode = file_identity(&fs::metadata(path).await.ok());
+        
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_millis(self.follow_timeout_ms);
+        loop {
+            let now = tokio::time::Instant::now();
+            if now >= deadline {
+                break;
+            }
+            tokio::time::sleep((deadline - now).min(std::time::Duration::from_millis(FOLLOW_POLL_INTERVAL_MS))).await;
+            context.check_cancelled(TOOL_NAME)?;
+            
+            // The file may briefly disappear while it is being rotated
+            let metadata = fs::metadata(path).await.ok();
+            let Some(size) = metadata.as_ref().map(|m| m.len()) else {
+                continue;
+            };
+            let current_inode = file_identity(&metadata);
+            if current_inode != inode || size < offset {
+                let reason = if current_inode != inode { "rotated" } else { "truncated" };
+                output.push_str(&format!("[File {}, reading from start]\n", reason));
+                inode = current_inode;
+                offset = 0;
+                line_number = 0;
+                pending.clear();
+            }
+            if size == offset {
+                continue;
+            }
+            
+            let mut file = fs::File::open(path).await
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to open file: {}", e))))?;
+            file.seek(std::io::SeekFrom::Start(offset)).await
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to seek file: {}", e))))?;
+            let read = file.read_to_end(&mut pending).await
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read file: {}", e))))?;
+            offset += read as u64;
+            
+            // Only emit complete lines; a trailing partial line waits for the next poll
+            if let Some(last_newline) = pending.iter().rposition(|&b| b == b'\n') {
+                let complete: Vec<u8> = pending.drain(..=last_newline).collect();
+                for line in String::from_utf8_lossy(&complete).lines() {
+                    line_number += 1;
+                    new_lines += self.push_followed_line(&mut output, &regex, line, line_number);
+                }
+            }
+            // Once the output is full there is no point waiting for more lines
+            if output.is_truncated() {
+                pending.clear();
+                break;
+            }
+        }
+        
+        if !pending.is_empty() {
+            line_number += 1;
+            let line = String::from_utf8_lossy(&pending).into_owned();
+            new_lines += self.push_followed_line(&mut output, &regex, &line, line_number);
+        }
+        
+        let truncated = output.is_truncated();
+        let mut output = output.into_string();
+        if truncated {
+            output.push('\n');
+        }
+        output.push_str(&format!("[Follow ended: {} new {}]", new_lines, if new_lines == 1 { "line" } else { "lines" }));
+        Ok(output)
+    }
+    
+    /// Add one followed line unless the pattern filters it out. Returns 1 when the line fit
+    fn push_followed_line(&self, output: &mut BoundedOutput, regex: &Option<regex::Regex>, line: &str, line_number: usize) -> usize {
+        if regex.as_ref().is_some_and(|r| r.is_match(line) == self.invert_match) {
+            return 0;
+        }
+        let fits = if self.linenumbers {
+            output.push_str(&format!("{:>6}\t{}\n", line_number, line))
+        } else {
+            output.push_str(&format!("{}\n", line))
+        };
+        usize::from(fits)
     }
 
     async fn read_file_with_encoding(&self, path: &Path) -> Result<String, std::io::Error> {
         let bytes = fs::read(path).await?;
-        
-        let encoding = match self.encoding.to_lowercase().as_str() {
-            "utf-8" | "utf8" => encoding_rs::UTF_8,
-            "ascii" => encoding_rs::WINDOWS_1252, // ASCII is a subset of Windows-1252
-            "latin1" | "iso-8859-1" => encoding_rs::WINDOWS_1252,
-            "utf-16" => encoding_rs::UTF_16LE, // Default to little-endian
-            "utf-16le" => encoding_rs::UTF_16LE,
-            "utf-16be" => encoding_rs::UTF_16BE,
-            _ => encoding_rs::UTF_8, // Default fallback
+        Ok(self.decode(&bytes))
+    }
+
+    /// Decode raw file bytes with the requested encoding, detecting it in "auto" mode
+    fn decode(&self, bytes: &[u8]) -> String {
+        let encoding = match explicit_encoding(&self.encoding) {
+            Some(encoding) => encoding,
+            None => detect_encoding(bytes).encoding,
         };
 
-        let (decoded, _encoding_used, had_errors) = encoding.decode(&bytes);
+        let (decoded, encoding_used, had_errors) = encoding.decode(bytes);
         
         if had_errors {
-            eprintln!("Warning: Some characters could not be decoded with {} encoding", self.encoding);
+            eprintln!("Warning: Some characters could not be decoded with {} encoding", encoding_used.name());
         }
         
-        Ok(decoded.into_owned())
+        decoded.into_owned()
     }
 
     fn parse_line_range(&self, range: &str, total_lines: usize) -> Result<(Option<usize>, Option<usize>), CallToolError> {
@@ -500,13 +1029,18 @@ impl ReadTool {
                 .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read for binary check: {}", e))))?;
             
             buffer.truncate(bytes_read);
-            let non_text_bytes = buffer.iter()
-                .filter(|&&b| b == 0 || (b < 32 && b != 9 && b != 10 && b != 13) || b > 126)
-                .count();
-            
-            is_binary = non_text_bytes > buffer.len() / 10;
+            is_binary = check_binary_sample(&buffer, self.binary_threshold).is_some();
         }
         
+        // Report what "auto" mode picked so the guess can be checked
+        let detected = if self.encoding.eq_ignore_ascii_case("auto") {
+            let bytes = fs::read(path).await
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read file for encoding detection: {}", e))))?;
+            Some(detect_encoding(&bytes))
+        } else {
+            None
+        };
+        
         // Count lines if text file
         let line_count = if !is_binary && size > 0 {
             let content = self.read_file_with_encoding(path).await
@@ -541,12 +1075,60 @@ impl ReadTool {
             modified,
             lines: line_count,
             encoding: self.encoding.clone(),
+            detected_encoding: detected.as_ref().map(|d| d.encoding.name().to_string()),
+            encoding_confidence: detected.as_ref().map(|d| d.confidence.to_string()),
             has_bom,
             is_binary,
         })
     }
 }
 
+/// Map an explicit encoding name to its decoder; `None` for "auto" and unknown names
+pub(super) fn explicit_encoding(name: &str) -> Option<&'static Encoding> {
+    match name.to_lowercase().as_str() {
+        "utf-8" | "utf8" => Some(encoding_rs::UTF_8),
+        "ascii" => Some(encoding_rs::WINDOWS_1252), // ASCII is a subset of Windows-1252
+        "latin1" | "iso-8859-1" => Some(encoding_rs::WINDOWS_1252),
+        "utf-16" => Some(encoding_rs::UTF_16LE), // Default to little-endian
+        "utf-16le" => Some(encoding_rs::UTF_16LE),
+        "utf-16be" => Some(encoding_rs::UTF_16BE),
+        _ => None,
+    }
+}
+
+/// Sniff the encoding from a BOM, falling back to chardetng on a sample of the bytes
+pub(super) fn detect_encoding(bytes: &[u8]) -> DetectedEncoding {
+    if let Some((encoding, _bom_length)) = Encoding::for_bom(bytes) {
+        return DetectedEncoding { encoding, confidence: "high" };
+    }
+    
+    let sample = &bytes[..bytes.len().min(ENCODING_SAMPLE_SIZE)];
+    // Plain ASCII decodes the same under every candidate, so report it as UTF-8
+    if sample.is_ascii() {
+        return DetectedEncoding { encoding: encoding_rs::UTF_8, confidence: "high" };
+    }
+    
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(sample, sample.len() == bytes.len());
+    let (encoding, high_confidence) = detector.guess_assess(None, true);
+    DetectedEncoding {
+        encoding,
+        confidence: if high_confidence { "high" } else { "low" },
+    }
+}
+
+/// Identity of the file behind a path, used to notice log rotation while following
+#[cfg(unix)]
+fn file_identity(metadata: &Option<std::fs::Metadata>) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    metadata.as_ref().map(|m| m.ino())
+}
+
+#[cfg(not(unix))]
+fn file_identity(_metadata: &Option<std::fs::Metadata>) -> Option<u64> {
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -567,9 +1149,12 @@ mod tests {
             limit: 0,
             line_range: None,
             binary_check: true,
+            binary_threshold: 10,
+            force_text: false,
             tail: false,
             pattern: None,
             invert_match: false,
+            only_matching: false,
             context_before: 0,
             context_after: 0,
             case: "sensitive".to_string(),
@@ -578,6 +1163,10 @@ mod tests {
             follow_symlinks: true,
             preview_only: false,
             include_metadata: false,
+            follow: false,
+            follow_timeout_ms: 5000,
+            hex_dump: false,
+            no_cache: false,
         }
     }
 
@@ -697,7 +1286,45 @@ mod tests {
         let result = test_read_tool_in_dir(&temp_dir, tool).await;
         
         assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Binary file detected"));
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("Binary file detected"));
+        assert!(error.contains("9 of the first 9 bytes are non-text, above the 10% threshold"));
+    }
+
+    #[tokio::test]
+    async fn test_binary_threshold_configurable() {
+        let temp_dir = TempDir::new().unwrap();
+        // 20% null bytes
+        let content = b"abcd\x00".repeat(10);
+        async_fs::write(temp_dir.path().join("mostly_text.dat"), &content).await.unwrap();
+        
+        let tool = create_read_tool("mostly_text.dat");
+        let result = test_read_tool_in_dir(&temp_dir, tool).await;
+        assert!(result.unwrap_err().to_string().contains("10 of the first 50 bytes"));
+        
+        let mut tool = create_read_tool("mostly_text.dat");
+        tool.binary_threshold = 30;
+        assert!(test_read_tool_in_dir(&temp_dir, tool).await.is_ok());
+        
+        let mut tool = create_read_tool("mostly_text.dat");
+        tool.binary_threshold = 101;
+        assert!(test_read_tool_in_dir(&temp_dir, tool).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_force_text_skips_binary_check() {
+        let temp_dir = TempDir::new().unwrap();
+        async_fs::write(temp_dir.path().join("binary.bin"), [0u8, 1, 2, b'o', b'k']).await.unwrap();
+        
+        let mut tool = create_read_tool("binary.bin");
+        tool.force_text = true;
+        let result = test_read_tool_in_dir(&temp_dir, tool).await.unwrap();
+        
+        let output = match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+        assert!(output.contains("ok"));
     }
 
     #[tokio::test]
@@ -1366,4 +1993,403 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Invalid line range"));
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_follow_collects_appended_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = create_test_file(&temp_dir, "app.log", "one\ntwo\n").await;
+
+        let writer = tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+            let mut content = async_fs::read_to_string(&file_path).await.unwrap();
+            content.push_str("three\nfour\n");
+            async_fs::write(&file_path, content).await.unwrap();
+        });
+
+        let mut tool = create_read_tool("app.log");
+        tool.tail = true;
+        tool.limit = 1;
+        tool.follow = true;
+        tool.follow_timeout_ms = 600;
+
+        let result = test_read_tool_in_dir(&temp_dir, tool).await.unwrap();
+        writer.await.unwrap();
+        let content = match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+
+        assert!(content.contains("     2\ttwo"));
+        assert!(content.contains("     3\tthree\n     4\tfour"));
+        assert!(content.contains("[Follow ended: 2 new lines]"));
+    }
+
+    #[tokio::test]
+    async fn test_follow_handles_truncation() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = create_test_file(&temp_dir, "app.log", "old line 1\nold line 2\n").await;
+
+        let writer = tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+            let file = std::fs::OpenOptions::new().write(true).truncate(true).open(&file_path).unwrap();
+            drop(file);
+            tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+            async_fs::write(&file_path, "fresh\n").await.unwrap();
+        });
+
+        let mut tool = create_read_tool("app.log");
+        tool.follow = true;
+        tool.follow_timeout_ms = 700;
+
+        let result = test_read_tool_in_dir(&temp_dir, tool).await.unwrap();
+        writer.await.unwrap();
+        let content = match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+
+        assert!(content.contains("[File truncated, reading from start]"));
+        assert!(content.contains("     1\tfresh"));
+    }
+
+    #[tokio::test]
+    async fn test_follow_stops_when_cancelled() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_file(&temp_dir, "app.log", "one\n").await;
+
+        let token = tokio_util::sync::CancellationToken::new();
+        let context = ToolContext::with_project_root(temp_dir.path().to_path_buf()).with_cancellation(token.clone());
+        let canceller = tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+            token.cancel();
+        });
+
+        let mut tool = create_read_tool("app.log");
+        tool.follow = true;
+        tool.follow_timeout_ms = 10_000;
+        let started = std::time::Instant::now();
+        let error = tool.call_with_context(&context).await.unwrap_err();
+        canceller.await.unwrap();
+        assert!(error.to_string().contains("Cancelled by the client"), "{}", error);
+        assert!(started.elapsed() < std::time::Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_follow_output_is_bounded() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = create_test_file(&temp_dir, "app.log", "start\n").await;
+
+        let writer = tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+            let line = "x".repeat(1000);
+            let content = format!("start\n{}", format!("{}\n", line).repeat(crate::config::max_result_bytes() / 1000 + 10));
+            async_fs::write(&file_path, content).await.unwrap();
+        });
+
+        let mut tool = create_read_tool("app.log");
+        tool.follow = true;
+        tool.follow_timeout_ms = 5_000;
+        let started = std::time::Instant::now();
+        let result = test_read_tool_in_dir(&temp_dir, tool).await.unwrap();
+        writer.await.unwrap();
+        let content = match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+
+        assert!(content.contains("[output truncated: exceeded"), "{}", &content[content.len() - 200..]);
+        assert!(content.len() < crate::config::max_result_bytes() + 200);
+        // A full output ends the follow early instead of waiting out the window
+        assert!(started.elapsed() < std::time::Duration::from_secs(4));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_follow_rejects_symlink_outside_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let outside_dir = TempDir::new().unwrap();
+        let outside_file = outside_dir.path().join("outside.log");
+        async_fs::write(&outside_file, "secret\n").await.unwrap();
+        std::os::unix::fs::symlink(&outside_file, temp_dir.path().join("link.log")).unwrap();
+
+        let mut tool = create_read_tool("link.log");
+        tool.follow = true;
+        tool.follow_timeout_ms = 100;
+
+        let result = test_read_tool_in_dir(&temp_dir, tool).await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Follow mode only works on files inside the project directory"));
+    }
+
+    #[tokio::test]
+    async fn test_hex_dump_binary_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut bytes = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        bytes.extend_from_slice(&[0, 0, 0, 0x0D, b'I', b'H', b'D', b'R', 0xFF]);
+        async_fs::write(temp_dir.path().join("image.png"), &bytes).await.unwrap();
+
+        let mut tool = create_read_tool("image.png");
+        tool.hex_dump = true;
+
+        let result = test_read_tool_in_dir(&temp_dir, tool).await.unwrap();
+        let output = match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "00000000: 8950 4e47 0d0a 1a0a 0000 000d 4948 4452  .PNG........IHDR");
+        assert_eq!(lines[1], "00000010: ff                                       .");
+    }
+
+    #[tokio::test]
+    async fn test_hex_dump_byte_range() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_file(&temp_dir, "data.txt", "0123456789abcdefghij").await;
+
+        let mut tool = create_read_tool("data.txt");
+        tool.hex_dump = true;
+        tool.offset = 10;
+        tool.limit = 4;
+
+        let result = test_read_tool_in_dir(&temp_dir, tool).await.unwrap();
+        let output = match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+
+        assert!(output.starts_with("0000000a: 6162 6364"));
+        assert!(output.contains("abcd\n"));
+        assert!(output.contains("[Showing bytes 10-14 of 20. Use offset=14 to continue]"));
+    }
+
+    #[tokio::test]
+    async fn test_hex_dump_rejects_line_options() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_file(&temp_dir, "data.txt", "abc").await;
+
+        let mut tool = create_read_tool("data.txt");
+        tool.hex_dump = true;
+        tool.tail = true;
+
+        let result = test_read_tool_in_dir(&temp_dir, tool).await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("hex_dump cannot be combined"));
+    }
+
+    #[tokio::test]
+    async fn test_auto_encoding_decodes_latin1() {
+        let temp_dir = TempDir::new().unwrap();
+        // "café crème brûlée" in windows-1252
+        let bytes = b"caf\xe9 cr\xe8me br\xfbl\xe9e, na\xefve fa\xe7ade\n".to_vec();
+        async_fs::write(temp_dir.path().join("menu.txt"), &bytes).await.unwrap();
+
+        let mut tool = create_read_tool("menu.txt");
+        tool.encoding = "auto".to_string();
+        tool.binary_check = false;
+        tool.include_metadata = true;
+
+        let result = test_read_tool_in_dir(&temp_dir, tool).await.unwrap();
+        let output = match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+
+        let json: serde_json::Value = serde_json::from_str(output).unwrap();
+        assert!(json["content"].as_str().unwrap().contains("café crème brûlée"));
+        assert_eq!(json["metadata"]["encoding"], "auto");
+        assert_eq!(json["metadata"]["detected_encoding"], "windows-1252");
+        assert!(json["metadata"]["encoding_confidence"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_auto_encoding_uses_bom() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut bytes = vec![0xFF, 0xFE];
+        for unit in "hi\n".encode_utf16() {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        async_fs::write(temp_dir.path().join("wide.txt"), &bytes).await.unwrap();
+
+        let mut tool = create_read_tool("wide.txt");
+        tool.encoding = "auto".to_string();
+        tool.binary_check = false;
+        tool.preview_only = true;
+
+        let result = test_read_tool_in_dir(&temp_dir, tool).await.unwrap();
+        let output = match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+
+        let json: serde_json::Value = serde_json::from_str(output).unwrap();
+        assert_eq!(json["detected_encoding"], "UTF-16LE");
+        assert_eq!(json["encoding_confidence"], "high");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_encoding_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_file(&temp_dir, "test.txt", "content").await;
+
+        let mut tool = create_read_tool("test.txt");
+        tool.encoding = "klingon".to_string();
+
+        let result = test_read_tool_in_dir(&temp_dir, tool).await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Unsupported encoding: klingon"));
+    }
+
+    #[tokio::test]
+    async fn test_only_matching_multiple_per_line() {
+        let temp_dir = TempDir::new().unwrap();
+        let content = "see https://a.example and https://b.example\nnothing here\nHTTPS://c.example\n";
+        create_test_file(&temp_dir, "links.md", content).await;
+        
+        let mut tool = create_read_tool("links.md");
+        tool.pattern = Some("https://[a-z.]+".to_string());
+        tool.only_matching = true;
+        tool.case = "insensitive".to_string();
+        let result = test_read_tool_in_dir(&temp_dir, tool).await.unwrap();
+        
+        let output = match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+        assert!(output.contains("     1\thttps://a.example\n     1\thttps://b.example\n     3\tHTTPS://c.example\n"));
+        assert!(!output.contains("see"));
+    }
+
+    #[tokio::test]
+    async fn test_only_matching_uses_first_capture_group() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_file(&temp_dir, "Cargo.toml", "[package]\nversion = \"1.2.3\"\n").await;
+        
+        let mut tool = create_read_tool("Cargo.toml");
+        tool.pattern = Some(r#"version = "([^"]+)""#.to_string());
+        tool.only_matching = true;
+        tool.linenumbers = false;
+        let result = test_read_tool_in_dir(&temp_dir, tool).await.unwrap();
+        
+        let output = match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => &text.text,
+            _ => panic!("Expected text content"),
+        };
+        assert!(output.starts_with("1.2.3\n"));
+    }
+
+    #[tokio::test]
+    async fn test_only_matching_requires_pattern() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_file(&temp_dir, "test.txt", "content").await;
+        
+        let mut tool = create_read_tool("test.txt");
+        tool.only_matching = true;
+        let result = test_read_tool_in_dir(&temp_dir, tool).await;
+        assert!(result.unwrap_err().to_string().contains("only_matching requires a pattern"));
+    }
+
+    #[tokio::test]
+    async fn test_read_cache_keyed_by_mtime_and_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::with_project_root(temp_dir.path().to_path_buf());
+        let file_path = create_test_file(&temp_dir, "cached.txt", "first\n").await;
+        let mtime = filetime::FileTime::from_last_modification_time(&std::fs::metadata(&file_path).unwrap());
+
+        let read = |no_cache: bool| {
+            let mut tool = create_read_tool("cached.txt");
+            tool.linenumbers = false;
+            tool.no_cache = no_cache;
+            let context = context.clone();
+            async move {
+                match &tool.call_with_context(&context).await.unwrap().content[0] {
+                    CallToolResultContentItem::TextContent(text) => text.text.clone(),
+                    _ => panic!("Expected text content"),
+                }
+            }
+        };
+        assert!(read(false).await.contains("first"));
+
+        // Same size and mtime: the cached content is served unless no_cache is set
+        async_fs::write(&file_path, "secon\n").await.unwrap();
+        filetime::set_file_mtime(&file_path, mtime).unwrap();
+        assert!(read(false).await.contains("first"));
+        assert!(read(true).await.contains("secon"));
+
+        // A different size busts the cache
+        async_fs::write(&file_path, "second version\n").await.unwrap();
+        filetime::set_file_mtime(&file_path, mtime).unwrap();
+        assert!(read(false).await.contains("second version"));
+    }
+
+    #[tokio::test]
+    async fn test_forward_window_stops_at_range_end() {
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::with_project_root(temp_dir.path().to_path_buf());
+        let content: String = (1..=200_000).map(|i| format!("line {}\r\n", i)).collect();
+        create_test_file(&temp_dir, "large.txt", &format!("\u{FEFF}{}", content)).await;
+
+        let read = |tool: ReadTool| {
+            let context = context.clone();
+            async move {
+                match &tool.call_with_context(&context).await.unwrap().content[0] {
+                    CallToolResultContentItem::TextContent(text) => text.text.clone(),
+                    _ => panic!("Expected text content"),
+                }
+            }
+        };
+        let cached_files = || async { read_cache(&context).await.lock().unwrap().entries.len() };
+
+        let mut tool = create_read_tool("large.txt");
+        tool.limit = 2;
+        assert_eq!(
+            read(tool).await,
+            "     1\tline 1\n     2\tline 2\n\n[Truncated at line 2. Use offset=3 to continue reading]"
+        );
+
+        let mut tool = create_read_tool("large.txt");
+        tool.line_range = Some("10-11".to_string());
+        tool.linenumbers = false;
+        assert_eq!(read(tool).await, "line 10\nline 11\n");
+
+        // Neither read went through the whole-file path, which would have decoded and cached it
+        assert_eq!(cached_files().await, 0);
+
+        // Tail needs the end of the file, so it reads all of it
+        let mut tool = create_read_tool("large.txt");
+        tool.tail = true;
+        tool.limit = 1;
+        assert!(read(tool).await.starts_with("200000\tline 200000\n"));
+        assert_eq!(cached_files().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_output_capped_at_result_size_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::with_project_root(temp_dir.path().to_path_buf());
+        let content = format!("{}\n", "x".repeat(99)).repeat(20_000);
+        create_test_file(&temp_dir, "big.txt", &content).await;
+
+        let read = |tool: ReadTool| {
+            let context = context.clone();
+            async move {
+                match &tool.call_with_context(&context).await.unwrap().content[0] {
+                    CallToolResultContentItem::TextContent(text) => text.text.clone(),
+                    _ => panic!("Expected text content"),
+                }
+            }
+        };
+        let notice = format!("\n[output truncated: exceeded {} bytes]", crate::config::max_result_bytes());
+
+        for limit in [0, 15_000] {
+            // Without a limit the whole file is decoded; with one it is streamed
+            let mut tool = create_read_tool("big.txt");
+            tool.linenumbers = false;
+            tool.limit = limit;
+            let output = read(tool).await;
+            assert!(output.ends_with(&notice));
+            assert_eq!(output.len(), crate::config::max_result_bytes() + notice.len());
+            assert!(!output.contains("[Truncated at line"));
+        }
+    }
+}
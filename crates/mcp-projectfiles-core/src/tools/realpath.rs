@@ -0,0 +1,247 @@
+use crate::config::tool_errors;
+use crate::context::{StatefulTool, ToolContext};
+
+use async_trait::async_trait;
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+const TOOL_NAME: &str = "realpath";
+
+/// Links followed before giving up, matching the usual SYMLOOP_MAX
+const MAX_LINK_HOPS: usize = 40;
+
+#[mcp_tool(
+    name = "realpath",
+    description = "Explain where a path really points. Returns JSON with the canonical absolute path, whether the path is a symlink, every link in the chain with its raw and resolved target, and whether the final target is inside the project. Broken links and loops are reported, not errors.
+Examples: {\"path\": \"current\"}, {\"path\": \"config/settings.toml\"}"
+)]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct RealpathTool {
+    /// Path to resolve (relative to project root)
+    pub path: String,
+}
+
+#[derive(Serialize, Debug)]
+struct LinkHop {
+    /// The symlink itself
+    link: String,
+    /// Target as stored in the link, possibly relative
+    target: String,
+    /// Absolute path the target refers to
+    resolved: String,
+}
+
+/// Drop `.` and apply `..` without touching the filesystem
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Absolute location of `target` read from the link at `link`, with every directory
+/// resolved but the final component left as is, so a further link there is still visible
+fn resolve_target(link: &Path, target: &Path) -> PathBuf {
+    let joined = match link.parent() {
+        Some(parent) if target.is_relative() => parent.join(target),
+        _ => target.to_path_buf(),
+    };
+    match (joined.parent(), joined.file_name()) {
+        (Some(parent), Some(name)) => parent
+            .canonicalize()
+            .map(|parent| parent.join(name))
+            .unwrap_or_else(|_| normalize_lexically(&joined)),
+        // Targets ending in ".." or "/" name a directory, never a link
+        _ => joined.canonicalize().unwrap_or_else(|_| normalize_lexically(&joined)),
+    }
+}
+
+#[async_trait]
+impl StatefulTool for RealpathTool {
+    async fn call_with_context(
+        mut self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
+        let project_root = context.get_project_root().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to get project root: {}", e),
+            ))
+        })?;
+        let canonical_root = project_root.canonicalize().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to canonicalize project root: {}", e),
+            ))
+        })?;
+
+        // The path as written must stay in the project; where its links lead is what gets reported
+        let requested = normalize_lexically(&canonical_root.join(&self.path));
+        if !requested.starts_with(&canonical_root) {
+            return Err(CallToolError::from(tool_errors::access_denied(
+                TOOL_NAME,
+                &self.path,
+                "Path is outside the project directory",
+            )));
+        }
+        // Resolve the directories leading to the path, but not the path itself
+        let start = resolve_target(&requested, &requested);
+        if std::fs::symlink_metadata(&start).is_err() {
+            return Err(CallToolError::from(tool_errors::file_not_found(TOOL_NAME, &self.path)));
+        }
+
+        let mut chain = Vec::new();
+        let mut current = start.clone();
+        let mut is_loop = false;
+        while let Ok(metadata) = std::fs::symlink_metadata(&current) {
+            if !metadata.is_symlink() {
+                break;
+            }
+            if chain.len() == MAX_LINK_HOPS {
+                is_loop = true;
+                break;
+            }
+            let target = std::fs::read_link(&current).map_err(|e| {
+                CallToolError::from(tool_errors::invalid_input(
+                    TOOL_NAME,
+                    &format!("Failed to read link '{}': {}", current.display(), e),
+                ))
+            })?;
+            let resolved = resolve_target(&current, &target);
+            chain.push(LinkHop {
+                link: current.display().to_string(),
+                target: target.display().to_string(),
+                resolved: resolved.display().to_string(),
+            });
+            current = resolved;
+        }
+
+        let metadata = std::fs::metadata(&current).ok().filter(|_| !is_loop);
+        let canonical = metadata.as_ref().and_then(|_| current.canonicalize().ok());
+        let final_path = canonical.as_ref().unwrap_or(&current);
+        let path_type = match &metadata {
+            None => "none",
+            Some(m) if m.is_file() => "file",
+            Some(m) if m.is_dir() => "directory",
+            Some(_) => "other",
+        };
+
+        let mut result = serde_json::json!({
+            "path": self.path,
+            "absolute_path": start.display().to_string(),
+            "is_symlink": !chain.is_empty(),
+            "canonical_path": canonical.as_ref().map(|path| path.display().to_string()),
+            "type": path_type,
+            "inside_project": final_path.starts_with(&canonical_root),
+            "chain": chain,
+        });
+        if is_loop {
+            result["loop"] = serde_json::json!(true);
+        } else if metadata.is_none() {
+            result["dangling_target"] = serde_json::json!(current.display().to_string());
+        }
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                serde_json::to_string_pretty(&result).unwrap(),
+                None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn realpath(context: &ToolContext, path: &str) -> Result<serde_json::Value, CallToolError> {
+        let tool = RealpathTool { path: path.to_string() };
+        let result = tool.call_with_context(context).await?;
+        match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => Ok(serde_json::from_str(&text.text).unwrap()),
+            _ => panic!("Expected text content"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_realpath_follows_link_chains() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("project");
+        let outside = temp_dir.path().join("outside.txt");
+        std::fs::create_dir_all(root.join("real")).unwrap();
+        std::fs::write(root.join("real/file.txt"), "content").unwrap();
+        std::fs::write(&outside, "outside").unwrap();
+        std::os::unix::fs::symlink("real", root.join("dir_link")).unwrap();
+        std::os::unix::fs::symlink("dir_link/file.txt", root.join("first")).unwrap();
+        std::os::unix::fs::symlink("first", root.join("second")).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("escape")).unwrap();
+        let root = root.canonicalize().unwrap();
+        let context = ToolContext::with_project_root(root.clone());
+
+        let result = realpath(&context, "real/./file.txt").await.unwrap();
+        assert_eq!(result["is_symlink"], false);
+        assert_eq!(result["canonical_path"], root.join("real/file.txt").display().to_string());
+        assert_eq!(result["type"], "file");
+        assert_eq!(result["chain"], serde_json::json!([]));
+
+        let result = realpath(&context, "second").await.unwrap();
+        assert_eq!(result["is_symlink"], true);
+        assert_eq!(result["canonical_path"], root.join("real/file.txt").display().to_string());
+        assert_eq!(result["inside_project"], true);
+        let chain = result["chain"].as_array().unwrap();
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[0]["target"], "first");
+        assert_eq!(chain[1]["link"], root.join("first").display().to_string());
+        assert_eq!(chain[1]["target"], "dir_link/file.txt");
+        assert_eq!(chain[1]["resolved"], root.join("real/file.txt").display().to_string());
+
+        let result = realpath(&context, "escape").await.unwrap();
+        assert_eq!(result["inside_project"], false);
+        assert_eq!(result["canonical_path"], outside.canonicalize().unwrap().display().to_string());
+
+        // A path through a symlinked directory resolves, though it is not a link itself
+        let result = realpath(&context, "dir_link/file.txt").await.unwrap();
+        assert_eq!(result["is_symlink"], false);
+        assert_eq!(result["absolute_path"], root.join("real/file.txt").display().to_string());
+    }
+
+    #[tokio::test]
+    async fn test_realpath_reports_broken_links_and_loops() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(root.clone());
+        std::os::unix::fs::symlink("missing/target.txt", root.join("broken")).unwrap();
+        std::os::unix::fs::symlink("loop_b", root.join("loop_a")).unwrap();
+        std::os::unix::fs::symlink("loop_a", root.join("loop_b")).unwrap();
+
+        let result = realpath(&context, "broken").await.unwrap();
+        assert_eq!(result["is_symlink"], true);
+        assert_eq!(result["type"], "none");
+        assert_eq!(result["canonical_path"], serde_json::Value::Null);
+        assert_eq!(result["dangling_target"], root.join("missing/target.txt").display().to_string());
+        assert_eq!(result["inside_project"], true);
+
+        let result = realpath(&context, "loop_a").await.unwrap();
+        assert_eq!(result["loop"], true);
+        assert_eq!(result["chain"].as_array().unwrap().len(), MAX_LINK_HOPS);
+
+        assert!(realpath(&context, "nothing_here").await.is_err());
+        assert!(realpath(&context, "../escape").await.is_err());
+    }
+}
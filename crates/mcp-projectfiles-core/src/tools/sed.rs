@@ -0,0 +1,400 @@
+use crate::context::{StatefulTool, ToolContext};
+use crate::config::tool_errors;
+use crate::tools::grep::{BINARY_SAMPLE_SIZE, collect_files, is_binary_sample};
+use crate::tools::utils::{
+    BoundedOutput, GitignoreFilter, GlobPattern, format_count, format_path, resolve_path_for_read,
+    write_atomically,
+};
+use async_trait::async_trait;
+use regex::Regex;
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+use similar::TextDiff;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+const TOOL_NAME: &str = "sed";
+
+#[mcp_tool(
+    name = "sed",
+    description = "Find and replace with regex across files. Capture groups ($1, ${name}), include/exclude filters, dry-run diffs, backups. Files that change must have been read first.
+Examples: {\"pattern\": \"old_name\", \"replacement\": \"new_name\", \"path\": \"src/\", \"include\": \"*.rs\"} or {\"pattern\": \"v(\\\\d+)\", \"replacement\": \"version-$1\", \"dry_run\": true}"
+)]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct SedTool {
+    /// Regular expression pattern to search for
+    pub pattern: String,
+    /// Replacement text. Use $1, $2 or ${name} to insert capture groups, and $$ for a literal $
+    pub replacement: String,
+    /// File or directory to process (optional, default: "." - current directory)
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// File pattern to include, e.g., "*.rs", "*.txt" (optional)
+    #[serde(default)]
+    pub include: Option<String>,
+    /// File pattern to exclude, e.g., "*.log", "*.tmp" (optional)
+    #[serde(default)]
+    pub exclude: Option<String>,
+    /// Show a unified diff of what would change without modifying files (default: false)
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Create a .bak copy of each modified file before rewriting it (default: false)
+    #[serde(default)]
+    pub backup: bool,
+}
+
+fn default_path() -> String {
+    ".".to_string()
+}
+
+/// A file whose content changes under the substitution
+struct FileChange {
+    path: PathBuf,
+    original: String,
+    updated: String,
+    replacements: usize,
+}
+
+#[async_trait]
+impl StatefulTool for SedTool {
+    async fn call_with_context(
+        mut self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
+        context.ensure_writable(TOOL_NAME)?;
+
+        let project_root = context.get_project_root()
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
+
+        // Canonicalize project root for consistent path comparison
+        let current_dir = project_root.canonicalize()
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to canonicalize project root: {}", e))))?;
+
+        let regex = Regex::new(&self.pattern)
+            .map_err(|e| CallToolError::from(tool_errors::pattern_error(TOOL_NAME, &self.pattern, &e.to_string())))?;
+
+        let include_pattern = self.include.as_deref()
+            .map(GlobPattern::new)
+            .transpose()
+            .map_err(|e| CallToolError::from(tool_errors::pattern_error(
+                TOOL_NAME,
+                self.include.as_deref().unwrap_or_default(),
+                &format!("Invalid include pattern: {}", e),
+            )))?;
+
+        let exclude_pattern = self.exclude.as_deref()
+            .map(GlobPattern::new)
+            .transpose()
+            .map_err(|e| CallToolError::from(tool_errors::pattern_error(
+                TOOL_NAME,
+                self.exclude.as_deref().unwrap_or_default(),
+                &format!("Invalid exclude pattern: {}", e),
+            )))?;
+
+        let target = resolve_path_for_read(&self.path, &current_dir, true, TOOL_NAME)?;
+
+        let mut files = Vec::new();
+        if target.is_file() {
+            files.push(target);
+        } else {
+            // sed has no gitignore option, so every file under the target is a candidate
+            collect_files(
+                &target,
+                &include_pattern,
+                &exclude_pattern,
+                &GitignoreFilter::new(&current_dir, false),
+                &mut files,
+                context,
+                TOOL_NAME,
+            )
+            .await?;
+        }
+
+        let mut changes = Vec::new();
+        let mut files_searched = 0;
+        for file in files {
+            // Symlinked files must still resolve inside the project
+            let canonical = match file.canonicalize() {
+                Ok(path) if path.starts_with(&current_dir) => path,
+                _ => continue,
+            };
+            files_searched += 1;
+
+            let bytes = fs::read(&canonical).await
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read file '{}': {}", canonical.display(), e))))?;
+
+            // Skip binary and non-UTF-8 files silently
+            if is_binary_sample(&bytes[..BINARY_SAMPLE_SIZE.min(bytes.len())]) {
+                continue;
+            }
+            let Ok(original) = String::from_utf8(bytes) else {
+                continue;
+            };
+
+            let replacements = regex.find_iter(&original).count();
+            if replacements == 0 {
+                continue;
+            }
+
+            let updated = regex.replace_all(&original, self.replacement.as_str()).into_owned();
+            if updated != original {
+                changes.push(FileChange {
+                    path: canonical,
+                    original,
+                    updated,
+                    replacements,
+                });
+            }
+        }
+
+        let total_replacements: usize = changes.iter().map(|c| c.replacements).sum();
+        let relative = |path: &Path| path.strip_prefix(&current_dir).unwrap_or(path).to_path_buf();
+
+        let mut output = BoundedOutput::new();
+        if changes.is_empty() {
+            output.push_str(&format!(
+                "No matches found for pattern '{}' in {} searched.",
+                self.pattern,
+                format_count(files_searched, "file", "files")
+            ));
+        } else if self.dry_run {
+            output.push_str(&format!(
+                "[DRY RUN] Would make {} in {}:\n",
+                format_count(total_replacements, "replacement", "replacements"),
+                format_count(changes.len(), "file", "files")
+            ));
+            'diffs: for change in &changes {
+                let relative_path = relative(&change.path);
+                if !output.push_str(&format!("\n--- a/{}\n+++ b/{}\n", relative_path.display(), relative_path.display())) {
+                    break;
+                }
+                let diff = TextDiff::from_lines(&change.original, &change.updated);
+                for hunk in diff.unified_diff().context_radius(3).iter_hunks() {
+                    if !output.push_str(&hunk.to_string()) {
+                        break 'diffs;
+                    }
+                }
+            }
+            output.push_str("\nNo files were modified (dry run mode).");
+        } else {
+            // Like edit, every file about to change must have been read in this session
+            let read_files = context
+                .get_custom_state::<HashSet<PathBuf>>()
+                .await
+                .unwrap_or_default();
+            if let Some(unread) = changes.iter().find(|change| !read_files.contains(&change.path)) {
+                return Err(CallToolError::from(tool_errors::operation_not_permitted(
+                    TOOL_NAME,
+                    &format!("File must be read before editing: {}", relative(&unread.path).display()),
+                )));
+            }
+
+            for change in &changes {
+                if self.backup {
+                    let backup_path = PathBuf::from(format!("{}.bak", change.path.display()));
+                    write_atomically(&backup_path, &current_dir, change.original.as_bytes(), TOOL_NAME)?;
+                }
+                write_atomically(&change.path, &current_dir, change.updated.as_bytes(), TOOL_NAME)?;
+                context.record_modified(&change.path).await;
+            }
+            output.push_str(&format!(
+                "Made {} in {}:",
+                format_count(total_replacements, "replacement", "replacements"),
+                format_count(changes.len(), "file", "files")
+            ));
+            for change in &changes {
+                output.push_str(&format!(
+                    "\n  {} ({})",
+                    format_path(&relative(&change.path)),
+                    format_count(change.replacements, "replacement", "replacements")
+                ));
+            }
+            if self.backup {
+                output.push_str("\nBackups saved with .bak extension");
+            }
+        }
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                output.into_string(), None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::ToolContext;
+    use tempfile::TempDir;
+
+    async fn setup_test_context() -> (ToolContext, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let canonical_path = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(canonical_path);
+        (context, temp_dir)
+    }
+
+    fn sed(pattern: &str, replacement: &str) -> SedTool {
+        SedTool {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            path: ".".to_string(),
+            include: None,
+            exclude: None,
+            dry_run: false,
+            backup: false,
+        }
+    }
+
+    async fn mark_read(context: &ToolContext, paths: &[PathBuf]) {
+        let read_files: HashSet<PathBuf> = paths.iter().map(|path| path.canonicalize().unwrap()).collect();
+        context.set_custom_state(read_files).await;
+    }
+
+    fn text(result: &CallToolResult) -> String {
+        match &result.content[0] {
+            CallToolResultContentItem::TextContent(t) => t.text.clone(),
+            _ => panic!("Expected text content"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sed_replaces_with_capture_groups() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path();
+        fs::create_dir(root.join("src")).await.unwrap();
+        fs::write(root.join("src/lib.rs"), "let v1 = 1;\nlet v22 = 2;\n").await.unwrap();
+        fs::write(root.join("notes.txt"), "v3\n").await.unwrap();
+
+        let mut tool = sed(r"v(\d+)", "version_$1");
+        tool.include = Some("*.rs".to_string());
+        tool.backup = true;
+        mark_read(&context, &[root.join("src/lib.rs")]).await;
+
+        let output = text(&tool.call_with_context(&context).await.unwrap());
+        assert!(output.contains("Made 2 replacements in 1 file"));
+        assert!(output.contains("'src/lib.rs' (2 replacements)"));
+
+        let updated = fs::read_to_string(root.join("src/lib.rs")).await.unwrap();
+        assert_eq!(updated, "let version_1 = 1;\nlet version_22 = 2;\n");
+        let backup = fs::read_to_string(root.join("src/lib.rs.bak")).await.unwrap();
+        assert_eq!(backup, "let v1 = 1;\nlet v22 = 2;\n");
+
+        // Excluded by the include filter
+        assert_eq!(fs::read_to_string(root.join("notes.txt")).await.unwrap(), "v3\n");
+    }
+
+    #[tokio::test]
+    async fn test_sed_dry_run_shows_diff() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path();
+        fs::write(root.join("config.txt"), "host = old\nport = 80\n").await.unwrap();
+
+        let mut tool = sed("old", "new");
+        tool.dry_run = true;
+
+        let output = text(&tool.call_with_context(&context).await.unwrap());
+        assert!(output.contains("[DRY RUN] Would make 1 replacement in 1 file"));
+        assert!(output.contains("--- a/config.txt"));
+        assert!(output.contains("-host = old"));
+        assert!(output.contains("+host = new"));
+        assert!(output.contains("No files were modified"));
+
+        let content = fs::read_to_string(root.join("config.txt")).await.unwrap();
+        assert_eq!(content, "host = old\nport = 80\n");
+    }
+
+    #[tokio::test]
+    async fn test_sed_include_expands_braces() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path();
+        for name in ["a.rs", "b.toml", "c.txt"] {
+            fs::write(root.join(name), "old\n").await.unwrap();
+        }
+
+        let mut tool = sed("old", "new");
+        tool.include = Some("*.{rs,toml}".to_string());
+        tool.dry_run = true;
+
+        let output = text(&tool.call_with_context(&context).await.unwrap());
+        assert!(output.contains("--- a/a.rs"));
+        assert!(output.contains("--- a/b.toml"));
+        assert!(!output.contains("c.txt"));
+    }
+
+    #[tokio::test]
+    async fn test_sed_dry_run_diff_is_bounded() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path();
+        let line = format!("old {}\n", "x".repeat(1000));
+        let content = line.repeat(crate::config::max_result_bytes() / 1000 + 10);
+        fs::write(root.join("big.txt"), &content).await.unwrap();
+
+        let mut tool = sed("old", "new");
+        tool.dry_run = true;
+
+        let output = text(&tool.call_with_context(&context).await.unwrap());
+        assert!(output.contains("[output truncated: exceeded"));
+        assert!(output.len() < crate::config::max_result_bytes() + 200);
+    }
+
+    #[tokio::test]
+    async fn test_sed_skips_binary_files() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path();
+        let mut binary = vec![0u8; 100];
+        binary.extend_from_slice(b"old");
+        fs::write(root.join("data.bin"), &binary).await.unwrap();
+
+        let output = text(&sed("old", "new").call_with_context(&context).await.unwrap());
+        assert!(output.contains("No matches found"));
+        assert_eq!(fs::read(root.join("data.bin")).await.unwrap(), binary);
+    }
+
+    #[tokio::test]
+    async fn test_sed_requires_reading_every_changed_file() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path();
+        fs::write(root.join("a.txt"), "old\n").await.unwrap();
+        fs::write(root.join("b.txt"), "old\n").await.unwrap();
+        mark_read(&context, &[root.join("a.txt")]).await;
+
+        let error = sed("old", "new").call_with_context(&context).await.unwrap_err();
+        assert!(error.to_string().contains("File must be read before editing: b.txt"), "{}", error);
+        // Nothing is written unless every file may be
+        assert_eq!(fs::read_to_string(root.join("a.txt")).await.unwrap(), "old\n");
+
+        // Previewing needs no read
+        let mut tool = sed("old", "new");
+        tool.dry_run = true;
+        assert!(tool.call_with_context(&context).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_sed_outside_project_directory() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let mut tool = sed("a", "b");
+        tool.path = "../".to_string();
+
+        let result = tool.call_with_context(&context).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sed_invalid_pattern() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let result = sed("(unclosed", "x").call_with_context(&context).await;
+        assert!(result.is_err());
+    }
+}
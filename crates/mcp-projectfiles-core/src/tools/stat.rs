@@ -1,7 +1,9 @@
 use crate::context::{StatefulTool, ToolContext};
 use crate::config::tool_errors;
-use crate::tools::utils::{resolve_path_for_read, resolve_path_allowing_symlinks};
+use crate::tools::file::{CONTENT_SAMPLE_SIZE, detect_content_type};
+use crate::tools::utils::{expand_pattern_in_project, resolve_path_for_read, resolve_path_allowing_symlinks};
 use async_trait::async_trait;
+use std::path::Path;
 
 use rust_mcp_schema::{
     CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
@@ -9,6 +11,7 @@ use rust_mcp_schema::{
 use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use serde::{Deserialize, Serialize};
 use tokio::fs;
+use tokio::io::AsyncReadExt;
 use chrono::{DateTime, Local};
 
 const TOOL_NAME: &str = "stat";
@@ -20,115 +23,71 @@ fn default_follow_symlinks() -> bool {
 #[mcp_tool(
     name = "stat",
     description = "Get file/directory metadata: size, timestamps, permissions, ownership. Returns type, size_human, modified, mode.
-Examples: {\"path\": \"README.md\"}, {\"path\": \"link.txt\", \"follow_symlinks\": false}"
+Batch mode: pass paths or a glob with pattern=true to get a JSON array; entries that fail carry an error instead.
+Examples: {\"path\": \"README.md\"}, {\"path\": \"blob.dat\", \"include_mime\": true}, {\"path\": \"link.txt\", \"follow_symlinks\": false}, {\"paths\": [\"a.txt\", \"b.txt\"]}, {\"path\": \"src/*.rs\", \"pattern\": true}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct StatTool {
-    /// Path to get stats for (relative to project root)
+    /// Path to get stats for (relative to project root), or a glob when pattern is true
+    #[serde(default)]
     pub path: String,
     
+    /// Several paths to stat in one call; returns a JSON array (optional)
+    #[serde(default)]
+    pub paths: Option<Vec<String>>,
+    
+    /// Treat path as a glob pattern and return a JSON array of every match (default: false)
+    #[serde(default)]
+    pub pattern: bool,
+    
     /// Whether to follow symbolic links (default: true)
     #[serde(default = "default_follow_symlinks")]
     pub follow_symlinks: bool,
+    
+    /// Sniff regular files' content type from their first bytes and add mime_type and
+    /// description, e.g. "image/png" and "PNG image" for a PNG named .dat (default: false)
+    #[serde(default)]
+    pub include_mime: bool,
 }
 
 #[async_trait]
 impl StatefulTool for StatTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+        self.paths = self.paths.map(|paths| paths.iter().map(|path| context.resolve_in_working_dir(path)).collect());
+
         let project_root = context.get_project_root()
             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
         
-        // Use different path resolution based on follow_symlinks
-        let resolved_path = if self.follow_symlinks {
-            resolve_path_for_read(&self.path, &project_root, true, TOOL_NAME)?
-        } else {
-            // When not following symlinks, use the function that allows checking symlinks
-            resolve_path_allowing_symlinks(&self.path, &project_root, TOOL_NAME)?
-        };
-        
-        // Get metadata
-        let metadata = if self.follow_symlinks {
-            fs::metadata(&resolved_path).await
-        } else {
-            fs::symlink_metadata(&resolved_path).await
-        }.map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                CallToolError::from(tool_errors::file_not_found(TOOL_NAME, &self.path))
-            } else {
-                CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get metadata for '{}': {}", self.path, e)))
+        let result = if let Some(paths) = &self.paths {
+            if self.pattern {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    TOOL_NAME,
+                    "paths and pattern cannot be used together"
+                )));
             }
-        })?;
-        
-        // Build the result
-        let mut result = serde_json::json!({
-            "path": self.path,
-            "absolute_path": resolved_path.display().to_string(),
-            "exists": true,
-            "type": get_file_type(&metadata),
-            "size": metadata.len(),
-            "is_file": metadata.is_file(),
-            "is_dir": metadata.is_dir(),
-            "is_symlink": metadata.is_symlink(),
-            "readonly": metadata.permissions().readonly(),
-        });
-        
-        // If it's a symlink and we're not following, try to get the target
-        if metadata.is_symlink() {
-            match fs::read_link(&resolved_path).await {
-                Ok(target) => {
-                    result["symlink_target"] = serde_json::Value::String(target.display().to_string());
-                }
-                Err(_) => {
-                    // Ignore errors reading symlink target
-                }
+            if paths.is_empty() {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    TOOL_NAME,
+                    "paths array cannot be empty"
+                )));
             }
-        }
-        
-        // Add timestamps
-        if let Ok(modified) = metadata.modified() {
-            let modified_dt: DateTime<Local> = modified.into();
-            result["modified"] = serde_json::Value::String(modified_dt.format("%Y-%m-%d %H:%M:%S").to_string());
-            result["modified_timestamp"] = serde_json::Value::Number(
-                serde_json::Number::from(modified_dt.timestamp())
-            );
-        }
-        
-        if let Ok(accessed) = metadata.accessed() {
-            let accessed_dt: DateTime<Local> = accessed.into();
-            result["accessed"] = serde_json::Value::String(accessed_dt.format("%Y-%m-%d %H:%M:%S").to_string());
-            result["accessed_timestamp"] = serde_json::Value::Number(
-                serde_json::Number::from(accessed_dt.timestamp())
-            );
-        }
-        
-        if let Ok(created) = metadata.created() {
-            let created_dt: DateTime<Local> = created.into();
-            result["created"] = serde_json::Value::String(created_dt.format("%Y-%m-%d %H:%M:%S").to_string());
-            result["created_timestamp"] = serde_json::Value::Number(
-                serde_json::Number::from(created_dt.timestamp())
-            );
-        }
-        
-        // Add Unix-specific metadata
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::MetadataExt;
-            result["mode"] = serde_json::Value::String(format!("{:o}", metadata.mode()));
-            result["uid"] = serde_json::Value::Number(serde_json::Number::from(metadata.uid()));
-            result["gid"] = serde_json::Value::Number(serde_json::Number::from(metadata.gid()));
-            result["nlink"] = serde_json::Value::Number(serde_json::Number::from(metadata.nlink()));
-            result["dev"] = serde_json::Value::Number(serde_json::Number::from(metadata.dev()));
-            result["ino"] = serde_json::Value::Number(serde_json::Number::from(metadata.ino()));
-            
-            // Format permissions in human-readable form
-            result["permissions"] = serde_json::Value::String(format_permissions(metadata.mode()));
-        }
-        
-        // Format size in human-readable form
-        result["size_human"] = serde_json::Value::String(format_size(metadata.len()));
+            self.stat_batch(paths, &project_root).await
+        } else if self.pattern {
+            let paths = expand_pattern_in_project(&self.path, &project_root, TOOL_NAME)?;
+            self.stat_batch(&paths, &project_root).await
+        } else {
+            if self.path.is_empty() {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    TOOL_NAME,
+                    "One of path or paths must be provided"
+                )));
+            }
+            stat_path(&self.path, &project_root, self.follow_symlinks, self.include_mime).await?
+        };
         
         Ok(CallToolResult {
             content: vec![CallToolResultContentItem::TextContent(TextContent::new(
@@ -142,6 +101,136 @@ impl StatefulTool for StatTool {
     }
 }
 
+impl StatTool {
+    /// Stat each path, recording failures (including paths outside the project) as error entries
+    async fn stat_batch(&self, paths: &[String], project_root: &Path) -> serde_json::Value {
+        let mut entries = Vec::with_capacity(paths.len());
+        for path in paths {
+            let entry = match stat_path(path, project_root, self.follow_symlinks, self.include_mime).await {
+                Ok(entry) => entry,
+                Err(e) => serde_json::json!({
+                    "path": path,
+                    "error": e.to_string(),
+                }),
+            };
+            entries.push(entry);
+        }
+        serde_json::Value::Array(entries)
+    }
+}
+
+/// Collect the metadata object for a single path
+async fn stat_path(path: &str, project_root: &Path, follow_symlinks: bool, include_mime: bool) -> Result<serde_json::Value, CallToolError> {
+    // Use different path resolution based on follow_symlinks
+    let resolved_path = if follow_symlinks {
+        resolve_path_for_read(path, project_root, true, TOOL_NAME)?
+    } else {
+        // When not following symlinks, use the function that allows checking symlinks
+        resolve_path_allowing_symlinks(path, project_root, TOOL_NAME)?
+    };
+    
+    // Get metadata
+    let metadata = if follow_symlinks {
+        fs::metadata(&resolved_path).await
+    } else {
+        fs::symlink_metadata(&resolved_path).await
+    }.map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            CallToolError::from(tool_errors::file_not_found(TOOL_NAME, path))
+        } else {
+            CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get metadata for '{}': {}", path, e)))
+        }
+    })?;
+    
+    // Build the result
+    let relative_path = project_root.canonicalize().ok()
+        .and_then(|root| resolved_path.strip_prefix(root).ok().map(|p| p.to_string_lossy().to_string()))
+        .unwrap_or_else(|| path.to_string());
+    let mut result = serde_json::json!({
+        "path": path,
+        "relative_path": relative_path,
+        "absolute_path": resolved_path.display().to_string(),
+        "exists": true,
+        "type": get_file_type(&metadata),
+        "size": metadata.len(),
+        "is_file": metadata.is_file(),
+        "is_dir": metadata.is_dir(),
+        "is_symlink": metadata.is_symlink(),
+        "readonly": metadata.permissions().readonly(),
+    });
+    
+    // If it's a symlink and we're not following, try to get the target
+    if metadata.is_symlink() {
+        match fs::read_link(&resolved_path).await {
+            Ok(target) => {
+                result["symlink_target"] = serde_json::Value::String(target.display().to_string());
+            }
+            Err(_) => {
+                // Ignore errors reading symlink target
+            }
+        }
+    }
+    
+    // Add timestamps
+    if let Ok(modified) = metadata.modified() {
+        let modified_dt: DateTime<Local> = modified.into();
+        result["modified"] = serde_json::Value::String(modified_dt.format("%Y-%m-%d %H:%M:%S").to_string());
+        result["modified_timestamp"] = serde_json::Value::Number(
+            serde_json::Number::from(modified_dt.timestamp())
+        );
+    }
+    
+    if let Ok(accessed) = metadata.accessed() {
+        let accessed_dt: DateTime<Local> = accessed.into();
+        result["accessed"] = serde_json::Value::String(accessed_dt.format("%Y-%m-%d %H:%M:%S").to_string());
+        result["accessed_timestamp"] = serde_json::Value::Number(
+            serde_json::Number::from(accessed_dt.timestamp())
+        );
+    }
+    
+    if let Ok(created) = metadata.created() {
+        let created_dt: DateTime<Local> = created.into();
+        result["created"] = serde_json::Value::String(created_dt.format("%Y-%m-%d %H:%M:%S").to_string());
+        result["created_timestamp"] = serde_json::Value::Number(
+            serde_json::Number::from(created_dt.timestamp())
+        );
+    }
+    
+    // Add Unix-specific metadata
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        result["mode"] = serde_json::Value::String(format!("{:o}", metadata.mode()));
+        result["uid"] = serde_json::Value::Number(serde_json::Number::from(metadata.uid()));
+        result["gid"] = serde_json::Value::Number(serde_json::Number::from(metadata.gid()));
+        result["nlink"] = serde_json::Value::Number(serde_json::Number::from(metadata.nlink()));
+        result["dev"] = serde_json::Value::Number(serde_json::Number::from(metadata.dev()));
+        result["ino"] = serde_json::Value::Number(serde_json::Number::from(metadata.ino()));
+        
+        // Format permissions in human-readable form
+        result["permissions"] = serde_json::Value::String(format_permissions(metadata.mode()));
+    }
+    
+    // Format size in human-readable form
+    result["size_human"] = serde_json::Value::String(format_size(metadata.len()));
+    
+    if include_mime && metadata.is_file() {
+        let mut sample = vec![0u8; CONTENT_SAMPLE_SIZE];
+        let sampled = match fs::File::open(&resolved_path).await {
+            Ok(mut file) => file.read(&mut sample).await,
+            Err(e) => Err(e),
+        };
+        if let Ok(length) = sampled {
+            sample.truncate(length);
+            let (mime_type, description) = detect_content_type(&resolved_path, &sample);
+            result["mime_type"] = serde_json::Value::String(mime_type);
+            result["description"] = serde_json::Value::String(description);
+        }
+    }
+    
+    Ok(result)
+}
+
 fn get_file_type(metadata: &std::fs::Metadata) -> &'static str {
     if metadata.is_dir() {
         "directory"
@@ -247,7 +336,10 @@ mod tests {
         
         let stat_tool = StatTool {
             path: "test.txt".to_string(),
+            paths: None,
+            pattern: false,
             follow_symlinks: false,
+            include_mime: false,
         };
         
         let result = stat_tool.call_with_context(&context).await;
@@ -275,7 +367,10 @@ mod tests {
         
         let stat_tool = StatTool {
             path: "test_dir".to_string(),
+            paths: None,
+            pattern: false,
             follow_symlinks: false,
+            include_mime: false,
         };
         
         let result = stat_tool.call_with_context(&context).await;
@@ -297,7 +392,10 @@ mod tests {
         
         let stat_tool = StatTool {
             path: "nonexistent.txt".to_string(),
+            paths: None,
+            pattern: false,
             follow_symlinks: false,
+            include_mime: false,
         };
         
         let result = stat_tool.call_with_context(&context).await;
@@ -313,7 +411,10 @@ mod tests {
         
         let stat_tool = StatTool {
             path: "../outside.txt".to_string(),
+            paths: None,
+            pattern: false,
             follow_symlinks: false,
+            include_mime: false,
         };
         
         let result = stat_tool.call_with_context(&context).await;
@@ -341,7 +442,10 @@ mod tests {
         
         let stat_tool = StatTool {
             path: "perms_test.txt".to_string(),
+            paths: None,
+            pattern: false,
             follow_symlinks: false,
+            include_mime: false,
         };
         
         let result = stat_tool.call_with_context(&context).await;
@@ -367,7 +471,10 @@ mod tests {
         // Test with follow_symlinks=true
         let stat_tool = StatTool {
             path: "target.txt".to_string(),
+            paths: None,
+            pattern: false,
             follow_symlinks: true,
+            include_mime: false,
         };
         
         let result = stat_tool.call_with_context(&context).await;
@@ -376,7 +483,10 @@ mod tests {
         // Test with follow_symlinks=false  
         let stat_tool = StatTool {
             path: "target.txt".to_string(),
+            paths: None,
+            pattern: false,
             follow_symlinks: false,
+            include_mime: false,
         };
         
         let result = stat_tool.call_with_context(&context).await;
@@ -393,7 +503,10 @@ mod tests {
         
         let stat_tool = StatTool {
             path: "empty.txt".to_string(),
+            paths: None,
+            pattern: false,
             follow_symlinks: false,
+            include_mime: false,
         };
         
         let result = stat_tool.call_with_context(&context).await;
@@ -418,7 +531,10 @@ mod tests {
         
         let stat_tool = StatTool {
             path: "large.txt".to_string(),
+            paths: None,
+            pattern: false,
             follow_symlinks: false,
+            include_mime: false,
         };
         
         let result = stat_tool.call_with_context(&context).await;
@@ -455,7 +571,10 @@ mod tests {
         
         let stat_tool = StatTool {
             path: "link.txt".to_string(),
+            paths: None,
+            pattern: false,
             follow_symlinks: true,
+            include_mime: false,
         };
         
         let result = stat_tool.call_with_context(&context).await;
@@ -496,7 +615,10 @@ mod tests {
         
         let stat_tool = StatTool {
             path: "external_link.txt".to_string(),
+            paths: None,
+            pattern: false,
             follow_symlinks: true,
+            include_mime: false,
         };
         
         let result = stat_tool.call_with_context(&context).await;
@@ -533,7 +655,10 @@ mod tests {
         
         let stat_tool = StatTool {
             path: "link.txt".to_string(),
+            paths: None,
+            pattern: false,
             follow_symlinks: false,
+            include_mime: false,
         };
         
         let result = stat_tool.call_with_context(&context).await;
@@ -569,7 +694,10 @@ mod tests {
             
             let stat_tool = StatTool {
                 path: name.to_string(),
+                paths: None,
+                pattern: false,
                 follow_symlinks: false,
+                include_mime: false,
             };
             
             let result = stat_tool.call_with_context(&context).await;
@@ -605,7 +733,10 @@ mod tests {
         // With follow_symlinks=true, should fail because target doesn't exist
         let stat_tool = StatTool {
             path: "broken_link.txt".to_string(),
+            paths: None,
+            pattern: false,
             follow_symlinks: true,
+            include_mime: false,
         };
         
         let result = stat_tool.call_with_context(&context).await;
@@ -614,7 +745,10 @@ mod tests {
         // With follow_symlinks=false, may still fail due to broken symlink
         let stat_tool = StatTool {
             path: "broken_link.txt".to_string(),
+            paths: None,
+            pattern: false,
             follow_symlinks: false,
+            include_mime: false,
         };
         
         let result = stat_tool.call_with_context(&context).await;
@@ -629,4 +763,100 @@ mod tests {
             }
         }
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_stat_batch_paths() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let project_root = context.get_project_root().unwrap();
+        fs::write(project_root.join("a.txt"), "aaa").await.unwrap();
+        fs::create_dir(project_root.join("dir")).await.unwrap();
+        
+        let stat_tool = StatTool {
+            path: String::new(),
+            paths: Some(vec!["a.txt".to_string(), "../outside.txt".to_string(), "dir".to_string()]),
+            pattern: false,
+            follow_symlinks: true,
+            include_mime: false,
+        };
+        
+        let result = stat_tool.call_with_context(&context).await.unwrap();
+        let CallToolResultContentItem::TextContent(text) = &result.content[0] else {
+            panic!("Expected text content");
+        };
+        let entries: serde_json::Value = serde_json::from_str(&text.text).unwrap();
+        let entries = entries.as_array().unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0]["relative_path"], "a.txt");
+        assert_eq!(entries[0]["type"], "file");
+        assert_eq!(entries[0]["size"], 3);
+        assert_eq!(entries[1]["path"], "../outside.txt");
+        assert!(entries[1]["error"].is_string());
+        assert_eq!(entries[2]["type"], "directory");
+    }
+    
+    #[tokio::test]
+    async fn test_stat_pattern() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let project_root = context.get_project_root().unwrap();
+        fs::create_dir(project_root.join("src")).await.unwrap();
+        fs::write(project_root.join("src/b.rs"), "b").await.unwrap();
+        fs::write(project_root.join("src/a.rs"), "a").await.unwrap();
+        fs::write(project_root.join("src/notes.md"), "notes").await.unwrap();
+        
+        let stat_tool = |path: &str, paths: Option<Vec<String>>| StatTool {
+            path: path.to_string(),
+            paths,
+            pattern: true,
+            follow_symlinks: true,
+            include_mime: false,
+        };
+        
+        let result = stat_tool("src/*.rs", None).call_with_context(&context).await.unwrap();
+        let CallToolResultContentItem::TextContent(text) = &result.content[0] else {
+            panic!("Expected text content");
+        };
+        let entries: serde_json::Value = serde_json::from_str(&text.text).unwrap();
+        let names: Vec<_> = entries.as_array().unwrap().iter()
+            .map(|entry| entry["relative_path"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(names, vec!["src/a.rs", "src/b.rs"]);
+        
+        assert!(stat_tool("../*.rs", None).call_with_context(&context).await.is_err());
+        assert!(stat_tool("src/*.py", None).call_with_context(&context).await.is_err());
+        assert!(stat_tool("src/*.rs", Some(vec!["src/a.rs".to_string()])).call_with_context(&context).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_stat_include_mime() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let project_root = context.get_project_root().unwrap();
+        fs::write(project_root.join("photo.dat"), [0xFF, 0xD8, 0xFF, 0xE0, 0x00]).await.unwrap();
+        fs::create_dir(project_root.join("dir")).await.unwrap();
+        
+        let stat_tool = |path: &str, include_mime: bool| StatTool {
+            path: path.to_string(),
+            paths: None,
+            pattern: false,
+            follow_symlinks: true,
+            include_mime,
+        };
+        let stat = |result: CallToolResult| -> serde_json::Value {
+            let CallToolResultContentItem::TextContent(text) = &result.content[0] else {
+                panic!("Expected text content");
+            };
+            serde_json::from_str(&text.text).unwrap()
+        };
+        
+        let json = stat(stat_tool("photo.dat", true).call_with_context(&context).await.unwrap());
+        assert_eq!(json["mime_type"], "image/jpeg");
+        assert_eq!(json["description"], "JPG image");
+        
+        let json = stat(stat_tool("photo.dat", false).call_with_context(&context).await.unwrap());
+        assert!(json.get("mime_type").is_none());
+        let json = stat(stat_tool("dir", true).call_with_context(&context).await.unwrap());
+        assert!(json.get("mime_type").is_none());
+    }
+}
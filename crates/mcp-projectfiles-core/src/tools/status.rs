@@ -0,0 +1,125 @@
+use crate::config::tool_errors;
+use crate::context::{ModifiedFiles, StatefulTool, ToolContext};
+
+use async_trait::async_trait;
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+const TOOL_NAME: &str = "status";
+
+#[mcp_tool(
+    name = "status",
+    description = "List the files this session has read and modified. Returns JSON with files_read and files_modified, relative to the project root.
+Modified covers files written, edited, patched, created, moved, copied or deleted.
+
+Example: {}"
+)]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct StatusTool {}
+
+/// Sorted paths relative to the project root; anything outside it stays absolute
+fn relative_paths<'a>(paths: impl Iterator<Item = &'a PathBuf>, project_root: &Path) -> Vec<String> {
+    let mut relative: Vec<String> = paths
+        .map(|path| {
+            path.strip_prefix(project_root)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        })
+        .collect();
+    relative.sort();
+    relative
+}
+
+#[async_trait]
+impl StatefulTool for StatusTool {
+    async fn call_with_context(
+        self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        let project_root = context.get_project_root().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to get project root: {}", e),
+            ))
+        })?;
+        // Tracked paths are canonical, so compare against the canonical root
+        let project_root = project_root.canonicalize().unwrap_or(project_root);
+
+        let read_files = context.get_custom_state::<HashSet<PathBuf>>().await;
+        let modified_files = context.get_custom_state::<ModifiedFiles>().await;
+
+        let files_read = read_files
+            .map(|files| relative_paths(files.iter(), &project_root))
+            .unwrap_or_default();
+        let files_modified = modified_files
+            .map(|files| relative_paths(files.0.iter(), &project_root))
+            .unwrap_or_default();
+
+        let result = serde_json::json!({
+            "files_read": files_read,
+            "files_modified": files_modified,
+        });
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                serde_json::to_string_pretty(&result).unwrap(),
+                None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::{DeleteTool, WriteTool};
+    use tempfile::TempDir;
+
+    fn status_json(result: &CallToolResult) -> serde_json::Value {
+        match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => serde_json::from_str(&text.text).unwrap(),
+            _ => panic!("Expected text content"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_lists_read_and_modified_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(project_root.clone());
+
+        let status = StatusTool {}.call_with_context(&context).await.unwrap();
+        assert_eq!(
+            status_json(&status),
+            serde_json::json!({ "files_read": [], "files_modified": [] })
+        );
+
+        std::fs::write(project_root.join("old.txt"), "old").unwrap();
+        let write: WriteTool = serde_json::from_value(serde_json::json!({
+            "path": "notes/new.txt",
+            "content": "hello",
+            "create_dirs": true
+        }))
+        .unwrap();
+        write.call_with_context(&context).await.unwrap();
+        let delete: DeleteTool = serde_json::from_value(serde_json::json!({
+            "path": "old.txt",
+            "confirm": true,
+            "use_trash": false
+        }))
+        .unwrap();
+        delete.call_with_context(&context).await.unwrap();
+
+        let status = status_json(&StatusTool {}.call_with_context(&context).await.unwrap());
+        assert_eq!(status["files_read"], serde_json::json!(["notes/new.txt"]));
+        assert_eq!(status["files_modified"], serde_json::json!(["notes/new.txt", "old.txt"]));
+    }
+}
@@ -0,0 +1,310 @@
+use crate::config::tool_errors;
+use crate::context::{StatefulTool, ToolContext};
+use crate::tools::read::{ENCODING_SAMPLE_SIZE, SUPPORTED_ENCODINGS, detect_encoding, explicit_encoding};
+use crate::tools::utils::{BoundedOutput, resolve_path_for_read};
+
+use async_trait::async_trait;
+use encoding_rs::Encoding;
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+const TOOL_NAME: &str = "tail";
+
+/// Bytes read per step while scanning backwards for line breaks
+const TAIL_BLOCK_SIZE: u64 = 64 * 1024;
+
+fn default_lines() -> u32 {
+    10
+}
+
+fn default_encoding() -> String {
+    "utf-8".to_string()
+}
+
+#[mcp_tool(
+    name = "tail",
+    description = "Read the last lines of a file without loading the whole file, so multi-gigabyte logs are cheap. Reads backwards from the end until it has the requested number of lines. Output matches read with tail=true and linenumbers=false.
+Examples: {\"path\": \"logs/app.log\"}, {\"path\": \"logs/app.log\", \"lines\": 100}, {\"path\": \"legacy.log\", \"lines\": 20, \"encoding\": \"latin1\"}"
+)]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct TailLinesTool {
+    /// Path to the file to read (relative to project root)
+    pub path: String,
+    /// Number of lines to return from the end of the file (default: 10)
+    #[serde(default = "default_lines")]
+    pub lines: u32,
+    /// Text encoding of the file (default: "utf-8")
+    /// "auto" detects the encoding from a BOM or a sample of the start of the file
+    #[serde(default = "default_encoding")]
+    pub encoding: String,
+}
+
+/// Read `[offset, offset + length)` of `file`
+async fn read_block(file: &mut fs::File, offset: u64, length: u64) -> std::io::Result<Vec<u8>> {
+    file.seek(SeekFrom::Start(offset)).await?;
+    let mut block = vec![0; length as usize];
+    file.read_exact(&mut block).await?;
+    Ok(block)
+}
+
+/// The bytes holding the last `lines` lines, found by reading backwards from the end in blocks.
+/// Break positions are checked in `newline`-sized steps from `data_start`, so a UTF-16 line
+/// break is never matched across two code units
+async fn read_tail(
+    file: &mut fs::File,
+    context: &ToolContext,
+    data_start: u64,
+    file_size: u64,
+    newline: &[u8],
+    lines: u32,
+) -> Result<Vec<u8>, CallToolError> {
+    let read_error = |e: std::io::Error| {
+        CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read file: {}", e)))
+    };
+    let unit = newline.len() as u64;
+    // A stray odd byte at the end of UTF-16 text belongs to the last line, not to a code unit
+    let aligned_end = data_start + (file_size - data_start) / unit * unit;
+    let mut found = 0;
+    let mut high = aligned_end;
+    let mut blocks = Vec::new();
+
+    if aligned_end < file_size {
+        blocks.push(read_block(file, aligned_end, file_size - aligned_end).await.map_err(read_error)?);
+    }
+    while high > data_start {
+        context.check_cancelled(TOOL_NAME)?;
+        let low = high.saturating_sub(TAIL_BLOCK_SIZE).max(data_start);
+        let block = read_block(file, low, high - low).await.map_err(read_error)?;
+        let mut position = block.len();
+        while position >= newline.len() {
+            position -= newline.len();
+            if &block[position..position + newline.len()] != newline {
+                continue;
+            }
+            // A break that ends the file terminates the last line rather than starting another
+            let absolute = low + position as u64;
+            if absolute + unit == file_size {
+                continue;
+            }
+            found += 1;
+            if found == lines {
+                blocks.push(block[position + newline.len()..].to_vec());
+                blocks.reverse();
+                return Ok(blocks.concat());
+            }
+        }
+        blocks.push(block);
+        high = low;
+    }
+
+    blocks.reverse();
+    Ok(blocks.concat())
+}
+
+#[async_trait]
+impl StatefulTool for TailLinesTool {
+    async fn call_with_context(
+        mut self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
+        let project_root = context.get_project_root().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to get project root: {}", e),
+            ))
+        })?;
+
+        if self.lines == 0 {
+            return Err(CallToolError::from(tool_errors::invalid_input(TOOL_NAME, "lines must be at least 1")));
+        }
+        if !self.encoding.eq_ignore_ascii_case("auto") && explicit_encoding(&self.encoding).is_none() {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Unsupported encoding: {}. Supported: {}", self.encoding, SUPPORTED_ENCODINGS),
+            )));
+        }
+
+        let canonical_path = resolve_path_for_read(&self.path, &project_root, true, TOOL_NAME)?;
+        if !canonical_path.is_file() {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Path is not a file: {}", self.path),
+            )));
+        }
+
+        let read_error = |e: std::io::Error| {
+            CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read file: {}", e)))
+        };
+        let mut file = fs::File::open(&canonical_path).await.map_err(read_error)?;
+        let file_size = file.metadata().await.map_err(read_error)?.len();
+
+        // A BOM wins over the requested encoding, as it does for read
+        let mut head = vec![0; (ENCODING_SAMPLE_SIZE as u64).min(file_size) as usize];
+        file.read_exact(&mut head).await.map_err(read_error)?;
+        let (encoding, bom_length) = match Encoding::for_bom(&head) {
+            Some((encoding, bom_length)) => (encoding, bom_length as u64),
+            None => (
+                explicit_encoding(&self.encoding).unwrap_or_else(|| detect_encoding(&head).encoding),
+                0,
+            ),
+        };
+        drop(head);
+        let newline: &[u8] = if encoding == encoding_rs::UTF_16LE {
+            b"\n\0"
+        } else if encoding == encoding_rs::UTF_16BE {
+            b"\0\n"
+        } else {
+            b"\n"
+        };
+
+        let tail = read_tail(&mut file, context, bom_length, file_size, newline, self.lines).await?;
+
+        let (decoded, had_errors) = encoding.decode_without_bom_handling(&tail);
+        if had_errors {
+            eprintln!("Warning: Some characters could not be decoded with {} encoding", encoding.name());
+        }
+
+        let content = if decoded.is_empty() {
+            String::from("[No content at specified offset]")
+        } else {
+            let mut output = BoundedOutput::new();
+            for line in decoded.lines() {
+                if !output.push_str(&format!("{}\n", line)) {
+                    break;
+                }
+            }
+            output.into_string()
+        };
+
+        let read_files = context.get_custom_state::<HashSet<PathBuf>>().await
+            .unwrap_or_else(|| std::sync::Arc::new(HashSet::new()));
+        let mut read_files_clone = (*read_files).clone();
+        read_files_clone.insert(canonical_path);
+        context.set_custom_state(read_files_clone).await;
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                content, None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::ReadTool;
+    use tempfile::TempDir;
+
+    fn text(result: &CallToolResult) -> String {
+        match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        }
+    }
+
+    fn tail(path: &str, lines: u32, encoding: &str) -> TailLinesTool {
+        TailLinesTool {
+            path: path.to_string(),
+            lines,
+            encoding: encoding.to_string(),
+        }
+    }
+
+    /// What read returns for the same tail, minus its total-lines notice
+    async fn read_tail_lines(context: &ToolContext, path: &str, lines: u32, encoding: &str) -> String {
+        let tool: ReadTool = serde_json::from_value(serde_json::json!({
+            "path": path,
+            "tail": true,
+            "limit": lines,
+            "linenumbers": false,
+            "encoding": encoding,
+            "force_text": true,
+        }))
+        .unwrap();
+        let output = text(&tool.call_with_context(context).await.unwrap());
+        match output.find("\n\n[Tail mode:") {
+            Some(notice) => output[..notice + 1].to_string(),
+            None => output,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tail_matches_read() {
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::with_project_root(temp_dir.path().to_path_buf());
+        let long: String = (1..=20_000).map(|n| format!("line {}\n", n)).collect();
+        let files: Vec<(&str, Vec<u8>)> = vec![
+            ("empty.txt", Vec::new()),
+            ("newline.txt", b"\n".to_vec()),
+            ("short.txt", b"one\ntwo\n".to_vec()),
+            ("no_newline.txt", b"one\ntwo\nthree".to_vec()),
+            ("blank_lines.txt", b"one\n\n\ntwo\n\n".to_vec()),
+            ("crlf.txt", b"one\r\ntwo\r\nthree\r\n".to_vec()),
+            ("bom.txt", b"\xEF\xBB\xBFone\ntwo".to_vec()),
+            // Spans several blocks, so the scan crosses block boundaries
+            ("long.log", long.into_bytes()),
+        ];
+        for (name, content) in &files {
+            std::fs::write(temp_dir.path().join(name), content).unwrap();
+            for lines in [1, 2, 3, 10, 15_000, 50_000] {
+                let output = text(&tail(name, lines, "utf-8").call_with_context(&context).await.unwrap());
+                assert_eq!(output, read_tail_lines(&context, name, lines, "utf-8").await, "{} lines of {}", lines, name);
+            }
+        }
+
+        let output = text(&tail("no_newline.txt", 2, "utf-8").call_with_context(&context).await.unwrap());
+        assert_eq!(output, "two\nthree\n");
+        let output = text(&tail("long.log", 2, "utf-8").call_with_context(&context).await.unwrap());
+        assert_eq!(output, "line 19999\nline 20000\n");
+    }
+
+    #[tokio::test]
+    async fn test_tail_utf16() {
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::with_project_root(temp_dir.path().to_path_buf());
+        // "\u{a0a}" encodes as 0x0A 0x0A, so a byte-wise scan would split it
+        let content = "first\n\u{a0a}second\nthird";
+        let utf16le: Vec<u8> = content.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let mut with_bom = vec![0xFE, 0xFF];
+        with_bom.extend(content.encode_utf16().flat_map(u16::to_be_bytes));
+        std::fs::write(temp_dir.path().join("le.txt"), &utf16le).unwrap();
+        std::fs::write(temp_dir.path().join("be_bom.txt"), &with_bom).unwrap();
+
+        let output = text(&tail("le.txt", 2, "utf-16le").call_with_context(&context).await.unwrap());
+        assert_eq!(output, "\u{a0a}second\nthird\n");
+        assert_eq!(output, read_tail_lines(&context, "le.txt", 2, "utf-16le").await);
+
+        // The BOM picks big-endian even though little-endian was asked for, as read does
+        for lines in [1, 2, 5] {
+            let output = text(&tail("be_bom.txt", lines, "utf-16").call_with_context(&context).await.unwrap());
+            assert_eq!(output, read_tail_lines(&context, "be_bom.txt", lines, "utf-16").await);
+        }
+        let output = text(&tail("be_bom.txt", 5, "auto").call_with_context(&context).await.unwrap());
+        assert_eq!(output, "first\n\u{a0a}second\nthird\n");
+    }
+
+    #[tokio::test]
+    async fn test_tail_rejects_bad_input() {
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::with_project_root(temp_dir.path().to_path_buf());
+        std::fs::write(temp_dir.path().join("a.txt"), "a\n").unwrap();
+
+        assert!(tail("a.txt", 0, "utf-8").call_with_context(&context).await.is_err());
+        assert!(tail("a.txt", 1, "ebcdic").call_with_context(&context).await.is_err());
+        assert!(tail("missing.txt", 1, "utf-8").call_with_context(&context).await.is_err());
+        assert!(tail("../outside.txt", 1, "utf-8").call_with_context(&context).await.is_err());
+    }
+}
@@ -1,7 +1,9 @@
 use crate::context::{StatefulTool, ToolContext};
 use crate::config::tool_errors;
-use crate::tools::utils::resolve_path_for_read;
+use crate::tools::utils::{TextStyle, resolve_path_for_read, resolve_path_for_write, write_atomically};
+use crate::tools::write::{encode_like, read_text_file};
 use crate::tools::query_engine::{QueryEngine, QueryError as QueryEngineError};
+use crate::tools::query_engine::parser::QueryParser;
 use async_trait::async_trait;
 use rust_mcp_schema::{
     CallToolResult, CallToolResultContentItem, schema_utils::CallToolError,
@@ -33,15 +35,17 @@ pub enum TomlQueryError {
     QueryEngine(#[from] QueryEngineError),
 }
 
-#[mcp_tool(name = "tomlq", description = "Query and manipulate TOML files with jq syntax. Type preservation, full jq features.
+#[mcp_tool(name = "tomlq", description = "Query and manipulate TOML files with jq syntax. Type preservation, full jq features. Writes keep comments and key order.
+Use operation=\"validate\" to check that the file parses without running a query.
 Examples: \".package.name\" or \".dependencies | keys\" or \".debug = true\"")]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct TomlQueryTool {
     /// Path to the TOML file (relative to project root)
     pub file_path: String,
-    /// Query string using jq-style syntax
+    /// Query string using jq-style syntax (ignored for validate)
+    #[serde(default)]
     pub query: String,
-    /// Operation type: "read" (default) or "write"
+    /// Operation type: "read" (default), "write", or "validate" (report whether the file parses)
     #[serde(default = "default_operation")]
     pub operation: String,
     /// Output format: "toml" (default), "json", or "raw"
@@ -85,15 +89,38 @@ pub struct TomlQueryResult {
 
 impl TomlQueryTool {
 
-    fn read_toml_file(&self, file_path: &Path) -> Result<serde_json::Value, TomlQueryError> {
-        let content = std::fs::read_to_string(file_path)
+    fn read_file_content(file_path: &Path) -> Result<String, TomlQueryError> {
+        read_text_file(file_path)
+            .map(|(content, _, _)| content)
             .map_err(|e| {
                 if e.kind() == std::io::ErrorKind::NotFound {
                     TomlQueryError::FileNotFound(file_path.display().to_string())
                 } else {
                     TomlQueryError::IoError(e.to_string())
                 }
-            })?;
+            })
+    }
+    
+    /// Parse the file without querying it, reporting the line and column of the first syntax error
+    fn validate_toml_file(&self, file_path: &Path) -> Result<serde_json::Value, TomlQueryError> {
+        let content = Self::read_file_content(file_path)?;
+        
+        match toml::from_str::<toml::Value>(&content) {
+            Ok(_) => Ok(serde_json::json!({ "valid": true })),
+            Err(e) => {
+                let mut report = serde_json::json!({ "valid": false, "error": e.message() });
+                if let Some(span) = e.span() {
+                    let (line, column) = line_column(&content, span.start);
+                    report["line"] = line.into();
+                    report["column"] = column.into();
+                }
+                Ok(report)
+            }
+        }
+    }
+    
+    fn read_toml_file(&self, file_path: &Path) -> Result<serde_json::Value, TomlQueryError> {
+        let content = Self::read_file_content(file_path)?;
         
         // Parse TOML and convert to JSON Value for uniform processing
         let toml_value: toml::Value = toml::from_str(&content)
@@ -123,6 +150,7 @@ impl TomlQueryTool {
                 QueryEngineError::DivisionByZero => TomlQueryError::ExecutionError("Division by zero".to_string()),
                 QueryEngineError::FunctionNotFound(msg) => TomlQueryError::ExecutionError(format!("Function not found: {}", msg)),
                 QueryEngineError::InvalidArgument(msg) => TomlQueryError::ExecutionError(format!("Invalid argument: {}", msg)),
+                QueryEngineError::InvalidSyntaxAt(e) => TomlQueryError::InvalidQuery(e.to_string()),
             })
     }
     
@@ -305,26 +333,104 @@ impl TomlQueryTool {
         }
     }
     
-    fn write_toml_file(&self, file_path: &Path, data: &serde_json::Value, backup: bool) -> Result<(), TomlQueryError> {
+    /// Convert a JSON value into a toml_edit item, using inline values when `inline` is set
+    fn json_to_toml_item(&self, json_value: &serde_json::Value, inline: bool) -> Result<toml_edit::Item, TomlQueryError> {
+        match json_value {
+            serde_json::Value::Object(obj) if !inline => {
+                let mut table = toml_edit::Table::new();
+                for (key, value) in obj {
+                    table.insert(key, self.json_to_toml_item(value, false)?);
+                }
+                Ok(toml_edit::Item::Table(table))
+            }
+            serde_json::Value::Array(arr) if !inline && !arr.is_empty() && arr.iter().all(|v| v.is_object()) => {
+                let mut tables = toml_edit::ArrayOfTables::new();
+                for value in arr {
+                    if let toml_edit::Item::Table(table) = self.json_to_toml_item(value, false)? {
+                        tables.push(table);
+                    }
+                }
+                Ok(toml_edit::Item::ArrayOfTables(tables))
+            }
+            _ => {
+                let toml_str = self.json_to_toml_value(json_value)?.to_string();
+                let mut value = toml_str.parse::<toml_edit::Value>()
+                    .map_err(|e| TomlQueryError::ExecutionError(format!("TOML serialization failed: {}", e)))?;
+                value.decor_mut().clear();
+                Ok(toml_edit::Item::Value(value))
+            }
+        }
+    }
+    
+    /// Apply the difference between `old` and `new` to a parsed TOML item, leaving
+    /// unchanged keys (and their comments and formatting) untouched
+    fn merge_toml_item(&self, item: &mut toml_edit::Item, old: &serde_json::Value, new: &serde_json::Value) -> Result<(), TomlQueryError> {
+        if old == new {
+            return Ok(());
+        }
+        
+        if let (serde_json::Value::Object(old_obj), serde_json::Value::Object(new_obj)) = (old, new) {
+            let inline = item.is_inline_table();
+            if let Some(table) = item.as_table_like_mut() {
+                for key in old_obj.keys().filter(|key| !new_obj.contains_key(*key)) {
+                    table.remove(key);
+                }
+                for (key, new_value) in new_obj {
+                    match (old_obj.get(key), table.get_mut(key)) {
+                        (Some(old_value), Some(child)) => self.merge_toml_item(child, old_value, new_value)?,
+                        _ => {
+                            table.insert(key, self.json_to_toml_item(new_value, inline)?);
+                        }
+                    }
+                }
+                return Ok(());
+            }
+        }
+        
+        // Scalars, arrays and type changes are replaced, keeping any surrounding comments
+        let replacement = self.json_to_toml_item(new, item.is_value())?;
+        match (item.as_value_mut(), replacement) {
+            (Some(value), toml_edit::Item::Value(mut new_value)) => {
+                *new_value.decor_mut() = value.decor().clone();
+                *value = new_value;
+            }
+            (_, replacement) => *item = replacement,
+        }
+        Ok(())
+    }
+    
+    fn write_toml_file(&self, file_path: &Path, project_root: &Path, original: &serde_json::Value, data: &serde_json::Value, backup: bool) -> Result<(), CallToolError> {
+        let query_error = |e: TomlQueryError| CallToolError::from(tool_errors::invalid_input("tomlq", &e.to_string()));
+        
         if backup && file_path.exists() {
             let backup_path = format!("{}.bak", file_path.display());
             std::fs::copy(file_path, &backup_path)
-                .map_err(|e| TomlQueryError::IoError(format!("Failed to create backup: {}", e)))?;
+                .map_err(|e| query_error(TomlQueryError::IoError(format!("Failed to create backup: {}", e))))?;
         }
         
-        let toml_value = self.json_to_toml_value(data)?;
-        let toml_str = toml::to_string_pretty(&toml_value)
-            .map_err(|e| TomlQueryError::ExecutionError(format!("TOML serialization failed: {}", e)))?;
-        
-        // Atomic write using temporary file
-        let temp_path = format!("{}.tmp", file_path.display());
-        std::fs::write(&temp_path, toml_str)
-            .map_err(|e| TomlQueryError::IoError(format!("Failed to write temporary file: {}", e)))?;
+        if !data.is_object() {
+            return Err(query_error(TomlQueryError::ExecutionError("TOML documents must be a table at the top level".to_string())));
+        }
         
-        std::fs::rename(&temp_path, file_path)
-            .map_err(|e| TomlQueryError::IoError(format!("Failed to move temporary file: {}", e)))?;
+        // Edit the existing document so untouched keys keep their order, formatting and comments.
+        // New files start from an empty document.
+        let existing_file = read_text_file(file_path).ok();
+        let existing = existing_file.as_ref()
+            .and_then(|(content, _, _)| content.parse::<toml_edit::DocumentMut>().ok());
+        let (mut document, original) = match existing {
+            Some(document) => (document, original.clone()),
+            None => (toml_edit::DocumentMut::new(), serde_json::Value::Object(serde_json::Map::new())),
+        };
+        self.merge_toml_item(document.as_item_mut(), &original, data).map_err(query_error)?;
+        let mut toml_str = document.to_string();
+        // Keep the existing file's line endings, final newline and encoding
+        if let Some(style) = existing_file.as_ref().and_then(|(content, _, _)| TextStyle::detect(content)) {
+            toml_str = style.apply(&toml_str);
+        }
+        let (encoding, had_bom) = existing_file.map_or((encoding_rs::UTF_8, false), |(_, encoding, had_bom)| (encoding, had_bom));
+        let bytes = encode_like(&toml_str, encoding, had_bom, "tomlq")?;
         
-        Ok(())
+        write_atomically(file_path, project_root, &bytes, "tomlq")
     }
     
     fn parse_assignment(&self, query: &str) -> Result<Option<(String, serde_json::Value)>, TomlQueryError> {
@@ -379,6 +485,7 @@ impl TomlQueryTool {
                 QueryEngineError::DivisionByZero => TomlQueryError::ExecutionError("Division by zero".to_string()),
                 QueryEngineError::FunctionNotFound(msg) => TomlQueryError::ExecutionError(format!("Function not found: {}", msg)),
                 QueryEngineError::InvalidArgument(msg) => TomlQueryError::ExecutionError(format!("Invalid argument: {}", msg)),
+                QueryEngineError::InvalidSyntaxAt(e) => TomlQueryError::InvalidQuery(e.to_string()),
             })
     }
     
@@ -501,132 +608,53 @@ impl TomlQueryTool {
     }
 }
 
+/// One-based line and column of a byte offset
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+}
+
 #[async_trait]
 impl StatefulTool for TomlQueryTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.file_path = context.resolve_in_working_dir(&self.file_path);
+
+        if self.in_place {
+            context.ensure_writable("tomlq")?;
+        }
+
         // Get project root
         let project_root = context.get_project_root()
             .map_err(|e| CallToolError::from(tool_errors::invalid_input("tomlq", &format!("Failed to get project root: {}", e))))?;
         
         // For read operations, use symlink-aware path resolution
-        let canonical_path = if self.operation == "read" {
+        let canonical_path = if matches!(self.operation.as_str(), "read" | "validate") {
             resolve_path_for_read(&self.file_path, &project_root, self.follow_symlinks, "tomlq")
                 .map_err(|e| CallToolError::from(e))?
         } else {
-            // For write operations, ensure we don't write through symlinks
-            let requested_path = Path::new(&self.file_path);
-            let absolute_path = if requested_path.is_absolute() {
-                requested_path.to_path_buf()
-            } else {
-                project_root.join(requested_path)
-            };
-            
-            // For existing files, canonicalize the path to resolve all symlinks
-            // For new files, canonicalize the parent directory and ensure the full path is within bounds
-            if absolute_path.exists() {
-                let canonical = absolute_path.canonicalize()
-                    .map_err(|e| CallToolError::from(tool_errors::invalid_input("tomlq", &format!("Failed to resolve path '{}': {}", self.file_path, e))))?;
-                
-                // Ensure the canonicalized path is within the project directory
-                if !canonical.starts_with(&project_root) {
-                    return Err(CallToolError::from(tool_errors::access_denied(
-                        "tomlq",
-                        &self.file_path,
-                        "Path is outside the project directory"
-                    )));
-                }
-                
-                canonical
-            } else {
-                // For new files, canonicalize the parent directory
-                if let Some(parent) = absolute_path.parent() {
-                    if parent.exists() {
-                        let canonical_parent = parent.canonicalize()
-                            .map_err(|e| CallToolError::from(tool_errors::invalid_input("tomlq", &format!("Failed to resolve parent directory: {}", e))))?;
-                        
-                        // Ensure the parent is within the project directory
-                        if !canonical_parent.starts_with(&project_root) {
-                            return Err(CallToolError::from(tool_errors::access_denied(
-                                "tomlq",
-                                &self.file_path,
-                                "Path would be outside the project directory"
-                            )));
-                        }
-                        
-                        // Reconstruct the path with the canonical parent
-                        if let Some(file_name) = absolute_path.file_name() {
-                            canonical_parent.join(file_name)
-                        } else {
-                            return Err(CallToolError::from(tool_errors::invalid_input(
-                                "tomlq",
-                                &format!("Invalid file path: '{}'", self.file_path)
-                            )));
-                        }
-                    } else {
-                        // Parent doesn't exist yet, need to check each component
-                        // Walk up the path to find the first existing parent
-                        let mut current = parent;
-                        let mut components_to_create = vec![];
-                        
-                        while !current.exists() {
-                            if let Some(parent_of_current) = current.parent() {
-                                components_to_create.push(current);
-                                current = parent_of_current;
-                            } else {
-                                break;
-                            }
-                        }
-                        
-                        // Now current is the first existing ancestor
-                        if current.exists() {
-                            let canonical_existing = current.canonicalize()
-                                .map_err(|e| CallToolError::from(tool_errors::invalid_input("tomlq", &format!("Failed to resolve existing parent: {}", e))))?;
-                            
-                            // Check if the existing parent is within project
-                            if !canonical_existing.starts_with(&project_root) {
-                                return Err(CallToolError::from(tool_errors::access_denied(
-                                    "tomlq",
-                                    &self.file_path,
-                                    "Path would be outside the project directory"
-                                )));
-                            }
-                            
-                            // Reconstruct the full path
-                            let mut result = canonical_existing;
-                            for component in components_to_create.iter().rev() {
-                                if let Some(file_name) = component.file_name() {
-                                    result = result.join(file_name);
-                                }
-                            }
-                            if let Some(file_name) = absolute_path.file_name() {
-                                result.join(file_name)
-                            } else {
-                                result
-                            }
-                        } else {
-                            // No existing parent found, check if path is at least within project
-                            if !absolute_path.starts_with(&project_root) {
-                                return Err(CallToolError::from(tool_errors::access_denied(
-                                    "tomlq",
-                                    &self.file_path,
-                                    "Path would be outside the project directory"
-                                )));
-                            }
-                            absolute_path
-                        }
-                    }
-                } else {
-                    return Err(CallToolError::from(tool_errors::invalid_input(
-                        "tomlq",
-                        &format!("Invalid file path: '{}'", self.file_path)
-                    )));
-                }
-            }
+            resolve_path_for_write(&self.file_path, &project_root, "tomlq")?
         };
         
+        if self.operation == "validate" {
+            let report = self.validate_toml_file(&canonical_path).map_err(|e| CallToolError::from(tool_errors::invalid_input("tomlq", &e.to_string())))?;
+            return Ok(CallToolResult {
+                content: vec![CallToolResultContentItem::text_content(
+                    report.to_string(),
+                    None,
+                )],
+                is_error: Some(false),
+                meta: None,
+            });
+        }
+        
+        // Reject malformed queries before touching the file
+        QueryParser::new().validate(&self.query)
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input("tomlq", &TomlQueryError::InvalidQuery(e.to_string()).to_string())))?;
+        
         // Read the TOML file
         let mut data = self.read_toml_file(&canonical_path).map_err(|e| CallToolError::from(tool_errors::invalid_input("tomlq", &e.to_string())))?;
         
@@ -662,11 +690,12 @@ impl StatefulTool for TomlQueryTool {
                 if self.in_place {
                     // Parse simple assignment queries like ".field = value"
                     if let Some((path, value)) = self.parse_assignment(&self.query).map_err(|e| CallToolError::from(tool_errors::invalid_input("tomlq", &e.to_string())))? {
+                        let original = data.clone();
                         self.apply_assignment(&mut data, &path, value).map_err(|e| CallToolError::from(tool_errors::invalid_input("tomlq", &e.to_string())))?;
                         modified = true;
                         
                         // Write the modified data back to file
-                        self.write_toml_file(&canonical_path, &data, self.backup).map_err(|e| CallToolError::from(tool_errors::invalid_input("tomlq", &e.to_string())))?;
+                        self.write_toml_file(&canonical_path, &project_root, &original, &data, self.backup)?;
                         data.clone()
                     } else {
                         return Err(CallToolError::from(tool_errors::invalid_input("tomlq", 
@@ -680,10 +709,14 @@ impl StatefulTool for TomlQueryTool {
                 }
             }
             _ => return Err(CallToolError::from(tool_errors::invalid_input("tomlq", 
-                &format!("Invalid operation: {}. Must be 'read' or 'write'", self.operation)
+                &format!("Invalid operation: {}. Must be 'read', 'write', or 'validate'", self.operation)
             ))),
         };
         
+        if modified {
+            context.record_modified(&canonical_path).await;
+        }
+
         // Format the output
         let output = self.format_output(&result, &self.output_format).map_err(|e| CallToolError::from(tool_errors::invalid_input("tomlq", &e.to_string())))?;
         
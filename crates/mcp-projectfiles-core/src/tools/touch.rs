@@ -18,7 +18,7 @@ const TOOL_NAME: &str = "touch";
 #[mcp_tool(
     name = "touch", 
     description = "Create files or update timestamps. ISO 8601 dates, reference files, content.
-Examples: {\"path\": \"new.txt\"} or {\"path\": \"dated.txt\", \"mtime\": \"2023-01-01T00:00:00Z\"}"
+Examples: {\"path\": \"new.txt\"} or {\"path\": \"dated.txt\", \"timestamp\": \"2023-01-01T00:00:00Z\"}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct TouchTool {
@@ -41,6 +41,10 @@ pub struct TouchTool {
     /// If not provided, current time is used when update_mtime is true
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mtime: Option<String>,
+    /// Time to set as both access and modification time (RFC 3339 format: "2023-12-25T10:30:00Z")
+    /// Explicit atime/mtime values take precedence over this
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
     /// Reference file to copy timestamps from (relative to project root)
     /// If provided, timestamps are copied from this file instead of using atime/mtime
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -78,9 +82,14 @@ fn default_encoding() -> String {
 #[async_trait]
 impl StatefulTool for TouchTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+        self.reference = self.reference.map(|path| context.resolve_in_working_dir(&path));
+
+        context.ensure_writable(TOOL_NAME)?;
+
         let project_root = context.get_project_root()
             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
             
@@ -131,28 +140,11 @@ impl StatefulTool for TouchTool {
                         }
                     }
                 } else if self.create {
-                    // Need to validate the parent path would be within bounds before creating
-                    let mut check_path = parent;
-                    while let Some(ancestor) = check_path.parent() {
-                        if ancestor.exists() {
-                            let canonical_ancestor = ancestor.canonicalize()
-                                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to resolve ancestor directory: {}", e))))?;
-                            if !canonical_ancestor.starts_with(&current_dir) {
-                                return Err(CallToolError::from(tool_errors::access_denied(
-                                    TOOL_NAME,
-                                    &self.path,
-                                    "Path would be outside the project directory"
-                                )));
-                            }
-                            break;
-                        }
-                        check_path = ancestor;
-                    }
-                    
-                    // Create parent directories
-                    fs::create_dir_all(parent)
-                        .await
-                        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to create parent directory: {}", e))))?;
+                    // Touch never creates directories; the parent must already exist
+                    return Err(CallToolError::from(tool_errors::file_not_found(
+                        TOOL_NAME,
+                        &format!("Parent directory of '{}' does not exist", self.path)
+                    )));
                 }
             }
         }
@@ -192,6 +184,8 @@ impl StatefulTool for TouchTool {
                 if self.update_atime {
                     if let Some(ref atime_str) = self.atime {
                         time_updates.push(format!("atime={}", atime_str));
+                    } else if let Some(ref timestamp_str) = self.timestamp {
+                        time_updates.push(format!("atime={}", timestamp_str));
                     } else if let Some(ref ref_file) = self.reference {
                         time_updates.push(format!("atime from '{}'", ref_file));
                     } else {
@@ -201,6 +195,8 @@ impl StatefulTool for TouchTool {
                 if self.update_mtime {
                     if let Some(ref mtime_str) = self.mtime {
                         time_updates.push(format!("mtime={}", mtime_str));
+                    } else if let Some(ref timestamp_str) = self.timestamp {
+                        time_updates.push(format!("mtime={}", timestamp_str));
                     } else if let Some(ref ref_file) = self.reference {
                         time_updates.push(format!("mtime from '{}'", ref_file));
                     } else {
@@ -272,6 +268,7 @@ impl StatefulTool for TouchTool {
                     .await
                     .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to create file: {}", e))))?;
                 action = "created";
+                context.record_modified(&absolute_path).await;
             } else {
                 return Err(CallToolError::from(tool_errors::file_not_found(
                     TOOL_NAME,
@@ -342,6 +339,8 @@ impl StatefulTool for TouchTool {
             let new_atime = if self.update_atime {
                 if let Some(ref atime_str) = self.atime {
                     self.parse_timestamp(atime_str, "access time")?
+                } else if let Some(ref timestamp_str) = self.timestamp {
+                    self.parse_timestamp(timestamp_str, "timestamp")?
                 } else if self.reference.is_some() {
                     ref_atime
                 } else {
@@ -354,6 +353,8 @@ impl StatefulTool for TouchTool {
             let new_mtime = if self.update_mtime {
                 if let Some(ref mtime_str) = self.mtime {
                     self.parse_timestamp(mtime_str, "modification time")?
+                } else if let Some(ref timestamp_str) = self.timestamp {
+                    self.parse_timestamp(timestamp_str, "timestamp")?
                 } else if self.reference.is_some() {
                     ref_mtime
                 } else {
@@ -461,6 +462,7 @@ mod tests {
             update_mtime: true,
             atime: None,
             mtime: None,
+            timestamp: None,
             reference: None,
             encoding: "utf-8".to_string(),
             content: String::new(),
@@ -506,6 +508,7 @@ mod tests {
             update_mtime: true,
             atime: None,
             mtime: None,
+            timestamp: None,
             reference: None,
             encoding: "utf-8".to_string(),
             content: String::new(),
@@ -536,6 +539,7 @@ mod tests {
             update_mtime: true,
             atime: None,
             mtime: None,
+            timestamp: None,
             reference: None,
             encoding: "utf-8".to_string(),
             content: String::new(),
@@ -564,6 +568,7 @@ mod tests {
             update_mtime: true,
             atime: Some("2023-01-01T12:00:00Z".to_string()),
             mtime: Some("2023-01-01T12:00:00Z".to_string()),
+            timestamp: None,
             reference: None,
             encoding: "utf-8".to_string(),
             content: String::new(),
@@ -602,6 +607,7 @@ mod tests {
             update_mtime: true,
             atime: None,
             mtime: None,
+            timestamp: None,
             reference: Some("reference.txt".to_string()),
             encoding: "utf-8".to_string(),
             content: String::new(),
@@ -640,6 +646,7 @@ mod tests {
             update_mtime: true,
             atime: None,
             mtime: None,
+            timestamp: None,
             reference: None,
             encoding: "utf-8".to_string(),
             content: String::new(),
@@ -658,7 +665,7 @@ mod tests {
     }
     
     #[tokio::test]
-    async fn test_touch_with_parent_directories() {
+    async fn test_touch_missing_parent_directory_fails() {
         let (context, _temp_dir) = setup_test_context().await;
         
         let touch_tool = TouchTool {
@@ -668,6 +675,7 @@ mod tests {
             update_mtime: true,
             atime: None,
             mtime: None,
+            timestamp: None,
             reference: None,
             encoding: "utf-8".to_string(),
             content: String::new(),
@@ -675,14 +683,14 @@ mod tests {
         };
         
         let result = touch_tool.call_with_context(&context).await;
-        assert!(result.is_ok());
+        assert!(result.is_err());
+        
+        let error_msg = format!("{:?}", result.unwrap_err());
+        assert!(error_msg.contains("Parent directory"));
         
-        // Check parent directories and file were created
+        // Touch must not create missing parent directories
         let project_root = context.get_project_root().unwrap();
-        let file_path = project_root.join("subdir/nested/file.txt");
-        assert!(file_path.exists());
-        assert!(project_root.join("subdir").is_dir());
-        assert!(project_root.join("subdir/nested").is_dir());
+        assert!(!project_root.join("subdir").exists());
     }
     
     #[tokio::test]
@@ -696,6 +704,7 @@ mod tests {
             update_mtime: true,
             atime: Some("invalid-timestamp".to_string()),
             mtime: None,
+            timestamp: None,
             reference: None,
             encoding: "utf-8".to_string(),
             content: String::new(),
@@ -720,6 +729,7 @@ mod tests {
             update_mtime: true,
             atime: None,
             mtime: None,
+            timestamp: None,
             reference: None,
             encoding: "utf-8".to_string(),
             content: String::new(),
@@ -744,6 +754,7 @@ mod tests {
             update_mtime: true,
             atime: None,
             mtime: None,
+            timestamp: None,
             reference: Some("nonexistent_ref.txt".to_string()),
             encoding: "utf-8".to_string(),
             content: String::new(),
@@ -768,6 +779,7 @@ mod tests {
             update_mtime: true,
             atime: None,
             mtime: None,
+            timestamp: None,
             reference: None,
             encoding: "utf-8".to_string(),
             content: "Hello, World!".to_string(),
@@ -804,6 +816,7 @@ mod tests {
             update_mtime: true,
             atime: None,
             mtime: None,
+            timestamp: None,
             reference: None,
             encoding: "utf-8".to_string(),
             content: "Test content".to_string(),
@@ -837,6 +850,7 @@ mod tests {
             update_mtime: true,
             atime: Some("2023-12-25".to_string()),
             mtime: Some("2023-12-25".to_string()),
+            timestamp: None,
             reference: None,
             encoding: "utf-8".to_string(),
             content: String::new(),
@@ -863,6 +877,7 @@ mod tests {
             update_mtime: true,
             atime: None,
             mtime: None,
+            timestamp: None,
             reference: None,
             encoding: "ascii".to_string(),
             content: "ASCII text only".to_string(),
@@ -890,6 +905,7 @@ mod tests {
             update_mtime: true,
             atime: None,
             mtime: None,
+            timestamp: None,
             reference: None,
             encoding: "ascii".to_string(),
             content: "Hello 世界".to_string(),
@@ -902,4 +918,35 @@ mod tests {
         let error_msg = format!("{:?}", result.unwrap_err());
         assert!(error_msg.contains("non-ASCII"));
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_touch_with_timestamp_sets_both_times() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let project_root = context.get_project_root().unwrap();
+        let file_path = project_root.join("stamped.txt");
+        fs::write(&file_path, "content").await.unwrap();
+        
+        let touch_tool = TouchTool {
+            path: "stamped.txt".to_string(),
+            create: true,
+            update_atime: true,
+            update_mtime: true,
+            atime: None,
+            mtime: None,
+            timestamp: Some("2020-06-15T08:00:00Z".to_string()),
+            reference: None,
+            encoding: "utf-8".to_string(),
+            content: String::new(),
+            dry_run: false,
+        };
+        
+        let result = touch_tool.call_with_context(&context).await;
+        assert!(result.is_ok());
+        
+        let expected = FileTime::from_unix_time(1_592_208_000, 0);
+        let metadata = std::fs::metadata(&file_path).unwrap();
+        assert_eq!(FileTime::from_last_modification_time(&metadata), expected);
+        assert_eq!(FileTime::from_last_access_time(&metadata), expected);
+    }
+}
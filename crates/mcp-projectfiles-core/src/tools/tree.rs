@@ -1,8 +1,10 @@
 use crate::context::{StatefulTool, ToolContext};
-use crate::config::tool_errors;
-use crate::tools::utils::{format_size, format_count, format_path, resolve_path_for_read};
+use crate::config::{max_result_bytes, tool_errors};
+use crate::theme::{DiffTheme, EntryKind};
+use crate::tools::utils::{BoundedOutput, file_identity, format_size, format_count, format_path, resolve_path_for_read, GitignoreFilter, GlobPattern};
 use async_trait::async_trait;
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use rust_mcp_schema::{
     CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
 };
@@ -10,14 +12,13 @@ use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use serde::{Deserialize, Serialize};
 use serde_json;
 use tokio::fs;
-use glob::Pattern;
 
 const TOOL_NAME: &str = "tree";
 
 #[mcp_tool(
     name = "tree",
-    description = "Display directory tree with sizes, patterns, depth limits. Supports tree/json output.
-Examples: {\"path\": \"src\", \"max_depth\": 2}, {\"path\": \".\", \"dirs_only\": true, \"pattern_filter\": \"*.rs\"}"
+    description = "Display directory tree with sizes, patterns, depth limits. Supports ascii/json output; json is a nested name/type/size/children structure with the same filtering.
+Examples: {\"path\": \"src\", \"max_depth\": 2}, {\"path\": \".\", \"dirs_only\": true, \"pattern_filter\": \"*.rs\"}, {\"path\": \".\", \"output_format\": \"json\"}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct TreeTool {
@@ -45,17 +46,42 @@ pub struct TreeTool {
     // #[serde(default)]
     // pub exclude_pattern: Option<String>,
     
-    /// Follow symlinks for the tree root directory (default: true)
+    /// Follow symlinks for the tree root and size symlinked files by their target (default: true)
     #[serde(default = "default_follow_symlinks")]
     pub follow_symlinks: bool,
     
-    /// Output format: "tree" (default) or "json"
+    /// Output format: "ascii" (default, alias "tree") or "json"
     #[serde(default = "default_output_format")]
     pub output_format: Option<String>,
     
     /// Maximum number of files to include (optional, default: 1000)
     #[serde(default = "default_max_files")]
     pub max_files: Option<u32>,
+    
+    /// Append the directory/file count and total size summary (default: true)
+    #[serde(default = "default_true")]
+    pub show_summary: bool,
+    
+    /// Show file sizes, and recursive totals on directories (default: true)
+    #[serde(default = "default_true")]
+    pub show_size: bool,
+    
+    /// Skip entries ignored by the project's .gitignore or .git/info/exclude (default: false)
+    #[serde(default)]
+    pub respect_gitignore: bool,
+    
+    /// Color directories, executables and symlinks in ascii output with the diff theme
+    /// (YOLO_PROJECTFILES_THEME). Leave off for programmatic consumers, since the escape codes
+    /// end up in the text (default: false)
+    #[serde(default)]
+    pub color: bool,
+}
+
+impl TreeTool {
+    /// Theme for entry names; colors only when asked for
+    fn theme(&self) -> DiffTheme {
+        if self.color { DiffTheme::current() } else { DiffTheme::None }
+    }
 }
 
 fn default_path() -> String {
@@ -66,6 +92,10 @@ fn default_follow_symlinks() -> bool {
     true
 }
 
+fn default_true() -> bool {
+    true
+}
+
 fn default_output_format() -> Option<String> {
     None
 }
@@ -77,9 +107,11 @@ fn default_max_files() -> Option<u32> {
 #[async_trait]
 impl StatefulTool for TreeTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
         // Get project root and resolve path
         let project_root = context.get_project_root()
             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
@@ -102,7 +134,8 @@ impl StatefulTool for TreeTool {
             )));
         }
         
-        let output_format = self.output_format.as_deref().unwrap_or("tree");
+        let output_format = self.output_format.as_deref().unwrap_or("ascii");
+        let gitignore = GitignoreFilter::new(&project_root, self.respect_gitignore);
         
         match output_format {
             "json" => {
@@ -117,28 +150,43 @@ impl StatefulTool for TreeTool {
                 let relative_path = normalized_path.strip_prefix(&project_root)
                     .unwrap_or(&normalized_path);
                 
+                let walk = TreeWalk { request: &self, gitignore: &gitignore, context };
                 let root_node = build_json_tree(
+                    &walk,
                     &normalized_path,
                     &root_name,
                     relative_path.to_string_lossy().to_string(),
-                    &self,
+                    &project_root,
                     &mut stats,
                     0,
                 ).await?;
                 
                 let tree_output = TreeOutput {
                     root: root_node,
-                    stats: TreeSummary {
+                    stats: self.show_summary.then(|| TreeSummary {
                         total_directories: stats.directories,
                         total_files: stats.files,
                         total_size: stats.total_size,
                         total_size_human: format_size(stats.total_size),
                         files_shown: stats.files_shown,
                         files_omitted: stats.files_omitted,
-                    },
+                    }),
                 };
                 
-                let json_output = serde_json::to_string_pretty(&tree_output)
+                // Cut-off JSON is useless, so an oversized tree is an error rather than a truncated result
+                let mut writer = CappedWriter::new(max_result_bytes());
+                if let Err(e) = serde_json::to_writer_pretty(&mut writer, &tree_output) {
+                    return Err(CallToolError::from(if e.is_io() {
+                        tool_errors::limit_exceeded(
+                            TOOL_NAME,
+                            &format!("{} bytes of output", writer.limit),
+                            "larger tree; narrow it with max_depth, max_files or pattern_filter",
+                        )
+                    } else {
+                        tool_errors::invalid_input(TOOL_NAME, &format!("Failed to serialize JSON: {}", e))
+                    }));
+                }
+                let json_output = String::from_utf8(writer.buffer)
                     .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to serialize JSON: {}", e))))?;
                 
                 Ok(CallToolResult {
@@ -150,40 +198,39 @@ impl StatefulTool for TreeTool {
                     meta: None,
                 })
             },
-            "tree" | _ => {
-                let mut tree_output = String::new();
+            "ascii" | "tree" => {
+                let mut tree_output = BoundedOutput::new();
                 let mut stats = TreeStats::default();
                 
                 // Start with the root directory name
                 tree_output.push_str(&format!(
                     "{}\n",
-                    normalized_path.file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or(&self.path)
+                    self.theme().colorize_entry(
+                        normalized_path.file_name()
+                            .and_then(|n| n.to_str())
+                            .unwrap_or(&self.path),
+                        EntryKind::Directory
+                    )
                 ));
                 
                 // Build the tree
-                build_tree(
-                    &normalized_path,
-                    &mut tree_output,
-                    "",
-                    true,
-                    &self,
-                    &mut stats,
-                    0,
-                ).await?;
+                let walk = TreeWalk { request: &self, gitignore: &gitignore, context };
+                build_tree(&walk, &normalized_path, &mut tree_output, "", &mut stats, 0).await?;
                 
                 // Add summary with path
                 let relative_path = normalized_path.strip_prefix(&project_root)
                     .unwrap_or(&normalized_path);
                 
-                let mut summary = format!(
-                    "\nTree of {} - {}, {} ({})",
-                    format_path(relative_path),
-                    format_count(stats.directories, "directory", "directories"),
-                    format_count(stats.files, "file", "files"),
-                    format_size(stats.total_size)
-                );
+                let mut summary = String::new();
+                if self.show_summary {
+                    summary.push_str(&format!(
+                        "\nTree of {} - {}, {} ({})",
+                        format_path(relative_path),
+                        format_count(stats.directories, "directory", "directories"),
+                        format_count(stats.files, "file", "files"),
+                        format_size(stats.total_size)
+                    ));
+                }
                 
                 if stats.files_omitted > 0 {
                     summary.push_str(&format!(
@@ -193,6 +240,7 @@ impl StatefulTool for TreeTool {
                     ));
                 }
                 
+                let mut tree_output = tree_output.into_string();
                 tree_output.push_str(&summary);
                 
                 Ok(CallToolResult {
@@ -204,6 +252,10 @@ impl StatefulTool for TreeTool {
                     meta: None,
                 })
             }
+            other => Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Invalid output_format '{}'. Use 'ascii' or 'json'", other)
+            ))),
         }
     }
 }
@@ -215,6 +267,8 @@ struct TreeStats {
     total_size: u64,
     files_shown: usize,
     files_omitted: usize,
+    /// (device, inode) of files already sized, so hard links and symlinks count once
+    seen_files: HashSet<(u64, u64)>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -223,14 +277,17 @@ struct TreeNode {
     path: String,
     #[serde(rename = "type")]
     node_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     children: Option<Vec<TreeNode>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 struct TreeOutput {
     root: TreeNode,
-    stats: TreeSummary,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<TreeSummary>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -243,36 +300,31 @@ struct TreeSummary {
     files_omitted: usize,
 }
 
-async fn build_tree(
+/// Read and filter a directory's entries, directories first then alphabetically.
+/// Shared by the ascii and json renderers so both apply the same options.
+async fn read_tree_entries(
     dir: &Path,
-    output: &mut String,
-    prefix: &str,
-    _is_last: bool,
     request: &TreeTool,
-    stats: &mut TreeStats,
-    current_depth: u32,
-) -> Result<(), CallToolError> {
-    // Check max depth
-    if let Some(max_depth) = request.max_depth {
-        if current_depth >= max_depth {
-            return Ok(());
-        }
-    }
+    gitignore: &GitignoreFilter,
+) -> Result<Vec<(PathBuf, String, std::fs::Metadata)>, CallToolError> {
+    let pattern = match &request.pattern_filter {
+        Some(pattern_str) => Some(GlobPattern::new(pattern_str)
+            .map_err(|e| CallToolError::from(tool_errors::pattern_error(TOOL_NAME, pattern_str, &format!("Invalid pattern: {}", e))))?),
+        None => None,
+    };
     
     // Read directory entries
     let mut entries = fs::read_dir(dir).await
-        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read file: {}", e))))?;
-    
-    let mut items = Vec::new();
+        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read directory: {}", e))))?;
     
     // Collect all entries first to avoid Send issues
     let mut dir_entries = Vec::new();
     while let Some(entry) = entries.next_entry().await
-        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read file: {}", e))))? {
+        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read entry: {}", e))))? {
         dir_entries.push(entry);
     }
     
-    // Process entries
+    let mut items = Vec::new();
     for entry in dir_entries {
         let name = entry.file_name();
         let name_str = name.to_string_lossy();
@@ -286,18 +338,19 @@ async fn build_tree(
         let metadata = entry.metadata().await
             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get metadata: {}", e))))?;
         
+        // Ignored directories are skipped along with everything beneath them
+        if gitignore.is_ignored(&entry.path(), metadata.is_dir()) {
+            continue;
+        }
+        
         // Filter directories if dirs_only is set
         if request.dirs_only && !metadata.is_dir() {
             continue;
         }
         
         // Filter by pattern if provided
-        if let Some(pattern_str) = &request.pattern_filter {
-            let pattern = Pattern::new(pattern_str)
-                .map_err(|e| CallToolError::from(tool_errors::pattern_error(TOOL_NAME, pattern_str, &format!("Invalid pattern: {}", e))))?;
-            if !pattern.matches(&name_str) {
-                continue;
-            }
+        if pattern.as_ref().is_some_and(|p| !p.matches(&name_str)) {
+            continue;
         }
         
         // // Exclude by pattern if provided
@@ -321,200 +374,217 @@ async fn build_tree(
         }
     });
     
+    Ok(items)
+}
+
+/// Size of a non-directory entry and its identity for de-duplication.
+/// Symlinks are sized by their target only when follow_symlinks is set; links to
+/// directories are never descended into, so cycles cannot be followed.
+async fn entry_size(path: &Path, metadata: &std::fs::Metadata, request: &TreeTool) -> (u64, Option<(u64, u64)>) {
+    if metadata.file_type().is_symlink() {
+        if !request.follow_symlinks {
+            return (metadata.len(), None);
+        }
+        return match fs::metadata(path).await {
+            Ok(target) if target.is_file() => (target.len(), file_identity(&target)),
+            _ => (0, None),
+        };
+    }
+    (metadata.len(), file_identity(metadata))
+}
+
+/// Count a file against the max_files limit; returns false when it should be omitted
+fn record_file(stats: &mut TreeStats, request: &TreeTool, size: u64, identity: Option<(u64, u64)>) -> bool {
+    stats.files += 1;
+    if identity.is_none_or(|id| stats.seen_files.insert(id)) {
+        stats.total_size += size;
+    }
+    
+    // Check if we've reached the file limit
+    if request.max_files.is_some_and(|max_files| stats.files_shown >= max_files as usize) {
+        stats.files_omitted += 1;
+        return false;
+    }
+    stats.files_shown += 1;
+    true
+}
+
+/// Byte sink that fails once a write would take it past `limit`, so serialization stops early
+struct CappedWriter {
+    buffer: Vec<u8>,
+    limit: usize,
+}
+
+impl CappedWriter {
+    fn new(limit: usize) -> Self {
+        Self { buffer: Vec::new(), limit }
+    }
+}
+
+impl std::io::Write for CappedWriter {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        if self.buffer.len() + data.len() > self.limit {
+            return Err(std::io::Error::other("result size limit reached"));
+        }
+        self.buffer.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Report how much of the tree has been walked so far
+fn report_progress(context: &ToolContext, stats: &TreeStats) {
+    context.report_progress((stats.directories + stats.files) as u64, None, || {
+        format!(
+            "Walked {} and {}",
+            format_count(stats.directories, "directory", "directories"),
+            format_count(stats.files, "file", "files")
+        )
+    });
+}
+
+/// What stays the same at every level of one walk: the request, its ignore rules and
+/// the call's context
+#[derive(Clone, Copy)]
+struct TreeWalk<'a> {
+    request: &'a TreeTool,
+    gitignore: &'a GitignoreFilter,
+    context: &'a ToolContext,
+}
+
+async fn build_tree(
+    walk: &TreeWalk<'_>,
+    dir: &Path,
+    output: &mut BoundedOutput,
+    prefix: &str,
+    stats: &mut TreeStats,
+    current_depth: u32,
+) -> Result<(), CallToolError> {
+    let TreeWalk { request, gitignore, context } = *walk;
+    // Check max depth
+    if request.max_depth.is_some_and(|max_depth| current_depth >= max_depth) {
+        return Ok(());
+    }
+    
+    let items = read_tree_entries(dir, request, gitignore).await?;
     let entry_count = items.len();
+    let theme = request.theme();
     
     for (index, (path, name, metadata)) in items.iter().enumerate() {
         let is_last_entry = index == entry_count - 1;
-        let is_dir = metadata.is_dir();
-        
-        // Update stats
-        if is_dir {
-            stats.directories += 1;
-        } else {
-            stats.files += 1;
-            stats.total_size += metadata.len();
-            
-            // Check if we've reached the file limit
-            if let Some(max_files) = request.max_files {
-                if stats.files_shown >= max_files as usize {
-                    stats.files_omitted += 1;
-                    continue;
-                }
-            }
-            stats.files_shown += 1;
-        }
-        
-        // Build the tree branch
         let branch = if is_last_entry { "└── " } else { "├── " };
-        let size_info = if !is_dir {
-            format!(" ({})", format_size(metadata.len()))
-        } else {
-            String::new()
-        };
-        
-        output.push_str(&format!(
-            "{}{}{}{}\n",
-            prefix,
-            branch,
-            name,
-            size_info
-        ));
+        let name = theme.colorize_entry(name, EntryKind::from_metadata(metadata));
         
-        // Recursively process subdirectories
-        if is_dir {
+        if metadata.is_dir() {
+            stats.directories += 1;
+            report_progress(context, stats);
             let new_prefix = format!(
                 "{}{}",
                 prefix,
                 if is_last_entry { "    " } else { "│   " }
             );
             
-            Box::pin(build_tree(
-                path,
-                output,
-                &new_prefix,
-                is_last_entry,
-                request,
-                stats,
-                current_depth + 1,
-            )).await?;
+            // Render the subtree first so the directory line can carry its total size;
+            // the subtree only gets the room left in the output
+            let size_before = stats.total_size;
+            let mut subtree = output.nested();
+            Box::pin(build_tree(walk, path, &mut subtree, &new_prefix, stats, current_depth + 1)).await?;
+            
+            let size_info = if request.show_size {
+                format!(" ({})", format_size(stats.total_size - size_before))
+            } else {
+                String::new()
+            };
+            output.push_str(&format!("{}{}{}{}\n", prefix, branch, name, size_info));
+            output.append(subtree);
+            continue;
         }
+        
+        let (size, identity) = entry_size(path, metadata, request).await;
+        let shown = record_file(stats, request, size, identity);
+        report_progress(context, stats);
+        if !shown {
+            continue;
+        }
+        
+        let size_info = if request.show_size {
+            format!(" ({})", format_size(size))
+        } else {
+            String::new()
+        };
+        output.push_str(&format!("{}{}{}{}\n", prefix, branch, name, size_info));
     }
     
     Ok(())
 }
 
 async fn build_json_tree(
+    walk: &TreeWalk<'_>,
     dir: &Path,
     name: &str,
     path: String,
-    request: &TreeTool,
+    project_root: &Path,
     stats: &mut TreeStats,
     current_depth: u32,
 ) -> Result<TreeNode, CallToolError> {
-    // Directory node
-    stats.directories += 1;
-    
-    let mut node = TreeNode {
-        name: name.to_string(),
-        path,
-        node_type: "directory".to_string(),
-        size: None,
-        children: Some(Vec::new()),
-    };
+    let TreeWalk { request, gitignore, context } = *walk;
+    let mut children = Vec::new();
+    let size_before = stats.total_size;
     
     // Check max depth
-    if let Some(max_depth) = request.max_depth {
-        if current_depth >= max_depth {
-            return Ok(node);
-        }
-    }
-    
-    // Read directory entries
-    let mut entries = fs::read_dir(dir).await
-        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read directory: {}", e))))?;
-    
-    let mut items = Vec::new();
-    
-    // Collect all entries
-    let mut dir_entries = Vec::new();
-    while let Some(entry) = entries.next_entry().await
-        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to read entry: {}", e))))? {
-        dir_entries.push(entry);
-    }
-    
-    // Process entries
-    for entry in dir_entries {
-        let name = entry.file_name();
-        let name_str = name.to_string_lossy();
-        
-        // Filter hidden files if requested
-        if !request.show_hidden && name_str.starts_with('.') {
-            continue;
-        }
-        
-        // Get metadata
-        let metadata = entry.metadata().await
-            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get metadata: {}", e))))?;
-        
-        // Filter directories if dirs_only is set
-        if request.dirs_only && !metadata.is_dir() {
-            continue;
-        }
-        
-        // Filter by pattern if provided
-        if let Some(pattern_str) = &request.pattern_filter {
-            let pattern = Pattern::new(pattern_str)
-                .map_err(|e| CallToolError::from(tool_errors::pattern_error(TOOL_NAME, pattern_str, &format!("Invalid pattern: {}", e))))?;
-            if !pattern.matches(&name_str) {
-                continue;
-            }
-        }
-        
-        // // Exclude by pattern if provided
-        // if let Some(exclude_str) = &request.exclude_pattern {
-        //     let exclude = Pattern::new(exclude_str)
-        //         .map_err(|e| CallToolError::from(tool_errors::pattern_error(TOOL_NAME, exclude_str, &format!("Invalid exclude pattern: {}", e))))?;
-        //     if exclude.matches(&name_str) {
-        //         continue;
-        //     }
-        // }
-        
-        items.push((entry.path(), name_str.to_string(), metadata));
-    }
-    
-    // Sort entries (directories first, then alphabetically)
-    items.sort_by(|(_, a_name, a_meta), (_, b_name, b_meta)| {
-        match (a_meta.is_dir(), b_meta.is_dir()) {
-            (true, false) => std::cmp::Ordering::Less,
-            (false, true) => std::cmp::Ordering::Greater,
-            _ => a_name.cmp(b_name),
-        }
-    });
-    
-    let children = node.children.as_mut().unwrap();
+    let at_max_depth = request.max_depth.is_some_and(|max_depth| current_depth >= max_depth);
+    let items = if at_max_depth {
+        Vec::new()
+    } else {
+        read_tree_entries(dir, request, gitignore).await?
+    };
     
-    for (path, name, metadata) in items {
-        let is_dir = metadata.is_dir();
-        let relative_path = path.strip_prefix(std::env::current_dir()
-            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get current dir: {}", e))))?
-        ).unwrap_or(&path).to_string_lossy().to_string();
+    for (child_path, child_name, metadata) in items {
+        let relative_path = child_path.strip_prefix(project_root)
+            .unwrap_or(&child_path)
+            .to_string_lossy()
+            .to_string();
         
-        if is_dir {
+        if metadata.is_dir() {
+            stats.directories += 1;
+            report_progress(context, stats);
             // Recursively process subdirectory
             let child_node = Box::pin(build_json_tree(
-                &path,
-                &name,
+                walk,
+                &child_path,
+                &child_name,
                 relative_path,
-                request,
+                project_root,
                 stats,
                 current_depth + 1,
             )).await?;
             children.push(child_node);
         } else {
-            // File node
-            stats.files += 1;
-            stats.total_size += metadata.len();
-            
-            // Check if we've reached the file limit
-            if let Some(max_files) = request.max_files {
-                if stats.files_shown >= max_files as usize {
-                    stats.files_omitted += 1;
-                    continue;
-                }
+            let (size, identity) = entry_size(&child_path, &metadata, request).await;
+            let shown = record_file(stats, request, size, identity);
+            report_progress(context, stats);
+            if shown {
+                children.push(TreeNode {
+                    name: child_name,
+                    path: relative_path,
+                    node_type: "file".to_string(),
+                    size: request.show_size.then_some(size),
+                    children: None,
+                });
             }
-            stats.files_shown += 1;
-            
-            children.push(TreeNode {
-                name,
-                path: relative_path,
-                node_type: "file".to_string(),
-                size: Some(metadata.len()),
-                children: None,
-            });
         }
     }
     
-    Ok(node)
+    Ok(TreeNode {
+        name: name.to_string(),
+        path,
+        node_type: "directory".to_string(),
+        size: request.show_size.then_some(stats.total_size - size_before),
+        children: Some(children),
+    })
 }
 
 #[cfg(test)]
@@ -564,6 +634,10 @@ mod tests {
             follow_symlinks: true,
             output_format: None,
             max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
         };
         
         let result = tree_tool.call_with_context(&context).await;
@@ -602,6 +676,10 @@ mod tests {
             follow_symlinks: true,
             output_format: None,
             max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
         };
         
         let result = tree_tool.call_with_context(&context).await;
@@ -637,6 +715,10 @@ mod tests {
             follow_symlinks: true,
             output_format: None,
             max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
         };
         
         let result = tree_tool.call_with_context(&context).await;
@@ -670,6 +752,10 @@ mod tests {
             follow_symlinks: true,
             output_format: None,
             max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
         };
         
         let result = tree_tool.call_with_context(&context).await;
@@ -699,6 +785,10 @@ mod tests {
             follow_symlinks: true,
             output_format: None,
             max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
         };
         
         let result = tree_tool.call_with_context(&context).await;
@@ -732,6 +822,10 @@ mod tests {
             follow_symlinks: true,
             output_format: None,
             max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
         };
         
         let result = tree_tool.call_with_context(&context).await;
@@ -767,6 +861,10 @@ mod tests {
             follow_symlinks: true,
             output_format: None,
             max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
         };
         
         let result = tree_tool.call_with_context(&context).await;
@@ -795,6 +893,10 @@ mod tests {
             follow_symlinks: true,
             output_format: None,
             max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
         };
         
         let result = tree_tool.call_with_context(&context).await;
@@ -819,6 +921,10 @@ mod tests {
             follow_symlinks: false, // Disable symlink following to test security
             output_format: None,
             max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
         };
         
         let result = tree_tool.call_with_context(&context).await;
@@ -844,6 +950,10 @@ mod tests {
             follow_symlinks: true,
             output_format: None,
             max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
         };
         
         let result = tree_tool.call_with_context(&context).await;
@@ -872,6 +982,10 @@ mod tests {
             follow_symlinks: true,
             output_format: None,
             max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
         };
         
         let result = tree_tool.call_with_context(&context).await;
@@ -902,6 +1016,10 @@ mod tests {
             follow_symlinks: true,
             output_format: Some("json".to_string()),
             max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
         };
         
         let result = tree_tool.call_with_context(&context).await;
@@ -952,6 +1070,10 @@ mod tests {
             follow_symlinks: true,
             output_format: None,
             max_files: Some(5),
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
         };
         
         let result = tree_tool.call_with_context(&context).await;
@@ -972,4 +1094,215 @@ mod tests {
             assert_eq!(file_count, 5); // Should only show 5 files
         }
     }
+    
+    #[tokio::test]
+    async fn test_tree_json_matches_ascii_filtering() {
+        let (context, temp_dir) = setup_test_context().await;
+        fs::create_dir_all(temp_dir.path().join("src/nested")).await.unwrap();
+        fs::write(temp_dir.path().join("src/lib.rs"), "fn main() {}").await.unwrap();
+        fs::write(temp_dir.path().join("src/notes.txt"), "notes").await.unwrap();
+        fs::write(temp_dir.path().join("src/nested/deep.rs"), "deep").await.unwrap();
+        fs::write(temp_dir.path().join(".hidden.rs"), "hidden").await.unwrap();
+        
+        let make_tool = |output_format: &str| TreeTool {
+            path: ".".to_string(),
+            max_depth: Some(2),
+            show_hidden: false,
+            dirs_only: false,
+            pattern_filter: Some("*[!t]".to_string()),
+            follow_symlinks: true,
+            output_format: Some(output_format.to_string()),
+            max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
+        };
+        
+        let ascii = make_tool("ascii").call_with_context(&context).await.unwrap();
+        let ascii = match ascii.content.first() {
+            Some(CallToolResultContentItem::TextContent(text)) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        };
+        assert!(ascii.contains("lib.rs"));
+        assert!(!ascii.contains("notes.txt"));
+        assert!(!ascii.contains("deep.rs"));
+        assert!(ascii.contains("2 directories, 1 file"));
+        
+        let json = make_tool("json").call_with_context(&context).await.unwrap();
+        let json = match json.content.first() {
+            Some(CallToolResultContentItem::TextContent(text)) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        };
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["stats"]["total_directories"], 2);
+        assert_eq!(parsed["stats"]["total_files"], 1);
+        
+        let src = &parsed["root"]["children"][0];
+        assert_eq!(src["name"], "src");
+        assert_eq!(src["path"], "src");
+        assert_eq!(src["children"][0]["name"], "nested");
+        assert_eq!(src["children"][0]["children"].as_array().unwrap().len(), 0);
+        assert_eq!(src["children"][1]["name"], "lib.rs");
+        assert_eq!(src["children"][1]["path"], "src/lib.rs");
+        assert_eq!(src["children"][1]["type"], "file");
+        assert_eq!(src["children"][1]["size"], 12);
+        assert!(src["children"][1].get("children").is_none());
+        assert_eq!(src["size"], 12);
+    }
+    
+    #[tokio::test]
+    async fn test_tree_invalid_output_format() {
+        let (context, _temp_dir) = setup_test_context().await;
+        
+        let tree_tool = TreeTool {
+            path: ".".to_string(),
+            max_depth: None,
+            show_hidden: false,
+            dirs_only: false,
+            pattern_filter: None,
+            follow_symlinks: true,
+            output_format: Some("xml".to_string()),
+            max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
+        };
+        
+        let result = tree_tool.call_with_context(&context).await;
+        assert!(result.unwrap_err().to_string().contains("Invalid output_format 'xml'"));
+    }
+    
+    #[tokio::test]
+    async fn test_tree_directory_totals_and_hidden_sizes() {
+        let (context, temp_dir) = setup_test_context().await;
+        fs::create_dir_all(temp_dir.path().join("src/nested")).await.unwrap();
+        fs::write(temp_dir.path().join("src/a.txt"), "12345").await.unwrap();
+        fs::write(temp_dir.path().join("src/nested/b.txt"), "123").await.unwrap();
+        
+        let make_tool = |show_size: bool, show_summary: bool| TreeTool {
+            path: ".".to_string(),
+            max_depth: None,
+            show_hidden: false,
+            dirs_only: false,
+            pattern_filter: None,
+            follow_symlinks: true,
+            output_format: None,
+            max_files: None,
+            show_summary,
+            show_size,
+            respect_gitignore: false,
+            color: false,
+        };
+        let text = |result: CallToolResult| match result.content.first() {
+            Some(CallToolResultContentItem::TextContent(text)) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        };
+        
+        let output = text(make_tool(true, true).call_with_context(&context).await.unwrap());
+        assert!(output.contains("src (8 B)"));
+        assert!(output.contains("nested (3 B)"));
+        assert!(output.contains("a.txt (5 B)"));
+        assert!(output.contains("2 directories, 2 files (8 B)"));
+        
+        let output = text(make_tool(false, false).call_with_context(&context).await.unwrap());
+        assert!(output.contains("a.txt\n"));
+        assert!(!output.contains(" B)"));
+        assert!(!output.contains("Tree of"));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_tree_symlinks_not_double_counted() {
+        let (context, temp_dir) = setup_test_context().await;
+        fs::write(temp_dir.path().join("data.bin"), vec![0u8; 100]).await.unwrap();
+        std::os::unix::fs::symlink("data.bin", temp_dir.path().join("link.bin")).unwrap();
+        fs::hard_link(temp_dir.path().join("data.bin"), temp_dir.path().join("hard.bin")).await.unwrap();
+        fs::create_dir(temp_dir.path().join("dir")).await.unwrap();
+        std::os::unix::fs::symlink("..", temp_dir.path().join("dir/loop")).unwrap();
+        
+        let tree_tool = TreeTool {
+            path: ".".to_string(),
+            max_depth: None,
+            show_hidden: false,
+            dirs_only: false,
+            pattern_filter: None,
+            follow_symlinks: true,
+            output_format: Some("json".to_string()),
+            max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: false,
+            color: false,
+        };
+        
+        let result = tree_tool.call_with_context(&context).await.unwrap();
+        let json = match result.content.first() {
+            Some(CallToolResultContentItem::TextContent(text)) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        };
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["stats"]["total_files"], 4);
+        assert_eq!(parsed["stats"]["total_size"], 100);
+        assert_eq!(parsed["root"]["children"][0]["name"], "dir");
+        assert_eq!(parsed["root"]["children"][0]["size"], 0);
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_tree_entry_kinds_for_color() {
+        use std::os::unix::fs::PermissionsExt;
+        let (_context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path();
+        fs::create_dir(root.join("dir")).await.unwrap();
+        fs::write(root.join("plain.txt"), "text").await.unwrap();
+        fs::write(root.join("run.sh"), "#!/bin/sh").await.unwrap();
+        std::fs::set_permissions(root.join("run.sh"), std::fs::Permissions::from_mode(0o755)).unwrap();
+        std::os::unix::fs::symlink("dir", root.join("link")).unwrap();
+        
+        let kind = |name: &str| EntryKind::from_metadata(&std::fs::symlink_metadata(root.join(name)).unwrap());
+        assert_eq!(kind("dir"), EntryKind::Directory);
+        assert_eq!(kind("plain.txt"), EntryKind::File);
+        assert_eq!(kind("run.sh"), EntryKind::Executable);
+        assert_eq!(kind("link"), EntryKind::Symlink);
+        
+        // Without color the names come through untouched
+        assert_eq!(DiffTheme::None.colorize_entry("dir", EntryKind::Directory), "dir");
+        assert_eq!(DiffTheme::GitHub.colorize_entry("plain.txt", EntryKind::File), "plain.txt");
+    }
+    
+    #[tokio::test]
+    async fn test_tree_respect_gitignore() {
+        let (context, temp_dir) = setup_test_context().await;
+        fs::write(temp_dir.path().join(".gitignore"), "target/\n").await.unwrap();
+        fs::create_dir_all(temp_dir.path().join("target/debug")).await.unwrap();
+        fs::write(temp_dir.path().join("target/debug/app"), "binary").await.unwrap();
+        fs::create_dir(temp_dir.path().join("src")).await.unwrap();
+        fs::write(temp_dir.path().join("src/main.rs"), "fn main() {}").await.unwrap();
+        
+        let tree_tool = TreeTool {
+            path: ".".to_string(),
+            max_depth: None,
+            show_hidden: false,
+            dirs_only: false,
+            pattern_filter: None,
+            follow_symlinks: true,
+            output_format: None,
+            max_files: None,
+            show_summary: true,
+            show_size: true,
+            respect_gitignore: true,
+            color: false,
+        };
+        
+        let result = tree_tool.call_with_context(&context).await.unwrap();
+        let output = match result.content.first() {
+            Some(CallToolResultContentItem::TextContent(text)) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        };
+        assert!(output.contains("main.rs"));
+        assert!(!output.contains("target"));
+        assert!(output.contains("1 directory, 1 file"));
+    }
 }
@@ -1,7 +1,11 @@
 use crate::config::tool_errors;
-use std::path::{Path, PathBuf};
+use crate::context::ToolContext;
+use crate::error::CodedError;
+use std::path::{Component, Path, PathBuf};
 use rust_mcp_schema::schema_utils::CallToolError;
-use crate::config::{get_project_root, is_within_project_root, normalize_path};
+use crate::config::{get_project_root, is_within_project_root, max_result_bytes, normalize_path};
+use glob::{MatchOptions, Pattern, glob_with};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 
 const TOOL_NAME: &str = "utils";
 
@@ -23,7 +27,7 @@ pub fn validate_path(path: &str) -> Result<PathBuf, CallToolError> {
         return Err(CallToolError::from(tool_errors::access_denied(
             TOOL_NAME,
             path,
-            "Path is outside the project directory"
+            &outside_project_reason("Path is outside the project directory", &absolute_path)
         )));
     }
     
@@ -80,6 +84,18 @@ pub fn format_size(bytes: u64) -> String {
     }
 }
 
+/// (device, inode) of a file, so hard links to it can be counted once
+#[cfg(unix)]
+pub fn file_identity(metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+pub fn file_identity(_metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
 /// Format a count with proper singular/plural form
 pub fn format_count(count: usize, singular: &str, plural: &str) -> String {
     if count == 1 {
@@ -142,6 +158,364 @@ pub fn format_number(num: usize) -> String {
     result.chars().rev().collect()
 }
 
+/// Number of leading bytes inspected when deciding whether a file is binary
+pub const BINARY_SAMPLE_SIZE: usize = 8192;
+
+/// Default share of non-text bytes (in percent) above which a sample counts as binary
+pub const DEFAULT_BINARY_THRESHOLD: u8 = 10;
+
+/// Heuristic used by the read tool: a sample is binary when more than 10% of it
+/// is null bytes, control characters or bytes outside printable ASCII.
+/// Valid UTF-8 without null bytes is always treated as text.
+pub fn is_binary_sample(bytes: &[u8]) -> bool {
+    check_binary_sample(bytes, DEFAULT_BINARY_THRESHOLD).is_some()
+}
+
+/// Same heuristic as `is_binary_sample` with a configurable threshold.
+/// Returns the non-text byte count and sample size when the sample is flagged as binary.
+pub fn check_binary_sample(bytes: &[u8], threshold_percent: u8) -> Option<(usize, usize)> {
+    let sample = &bytes[..bytes.len().min(BINARY_SAMPLE_SIZE)];
+    if !sample.contains(&0) {
+        // The sample may cut a multi-byte character in half at the end
+        match std::str::from_utf8(sample) {
+            Ok(_) => return None,
+            Err(e) if e.error_len().is_none() => return None,
+            Err(_) => {}
+        }
+    }
+    let non_text_bytes = sample.iter()
+        .filter(|&&b| b == 0 || (b < 32 && b != 9 && b != 10 && b != 13) || b > 126)
+        .count();
+    (non_text_bytes * 100 > sample.len() * threshold_percent as usize)
+        .then_some((non_text_bytes, sample.len()))
+}
+
+/// Line-ending convention of a text file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        }
+    }
+}
+
+/// Rewrite every line break in `content` to `ending`
+pub fn convert_line_endings(content: &str, ending: LineEnding) -> String {
+    let normalized = content.replace("\r\n", "\n");
+    match ending {
+        LineEnding::Lf => normalized,
+        LineEnding::Crlf => normalized.replace('\n', "\r\n"),
+    }
+}
+
+/// Line-ending and final-newline conventions of existing file content, so rewrites
+/// don't turn CRLF into LF or add or drop the last newline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextStyle {
+    /// Dominant line ending, or `None` when the content has no line breaks
+    pub line_ending: Option<LineEnding>,
+    pub trailing_newline: bool,
+}
+
+impl TextStyle {
+    /// Detect the style of existing content; empty content has none to preserve
+    pub fn detect(content: &str) -> Option<Self> {
+        if content.is_empty() {
+            return None;
+        }
+        let crlf = content.matches("\r\n").count();
+        let lf = content.matches('\n').count() - crlf;
+        let line_ending = match (lf, crlf) {
+            (0, 0) => None,
+            (lf, crlf) if crlf > lf => Some(LineEnding::Crlf),
+            _ => Some(LineEnding::Lf),
+        };
+        Some(Self { line_ending, trailing_newline: content.ends_with('\n') })
+    }
+
+    /// Rewrite `content` to use this style's line ending and final newline
+    pub fn apply(&self, content: &str) -> String {
+        let mut styled = match self.line_ending {
+            Some(ending) => convert_line_endings(content, ending),
+            None => content.to_string(),
+        };
+        if self.trailing_newline && !styled.is_empty() && !styled.ends_with('\n') {
+            styled.push_str(self.line_ending.unwrap_or(LineEnding::Lf).as_str());
+        } else if !self.trailing_newline && styled.ends_with('\n') {
+            styled.pop();
+            if styled.ends_with('\r') {
+                styled.pop();
+            }
+        }
+        styled
+    }
+}
+
+/// Tool output capped at a byte budget. Text past the budget is dropped as it arrives,
+/// and the finished string ends with a notice marking the cut
+#[derive(Debug)]
+pub struct BoundedOutput {
+    text: String,
+    limit: usize,
+    truncated: bool,
+}
+
+impl Default for BoundedOutput {
+    fn default() -> Self {
+        Self::with_limit(max_result_bytes())
+    }
+}
+
+impl BoundedOutput {
+    /// An empty buffer capped at the configured result size
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_limit(limit: usize) -> Self {
+        Self { text: String::new(), limit, truncated: false }
+    }
+
+    /// Append `text`, keeping only what fits. Returns false once the budget is spent,
+    /// so callers can stop producing output
+    pub fn push_str(&mut self, text: &str) -> bool {
+        if self.truncated {
+            return false;
+        }
+        let room = self.remaining();
+        if text.len() <= room {
+            self.text.push_str(text);
+            return true;
+        }
+        let mut cut = room;
+        while !text.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        self.text.push_str(&text[..cut]);
+        self.truncated = true;
+        false
+    }
+
+    /// Append another buffer's text, carrying over its truncation
+    pub fn append(&mut self, other: BoundedOutput) -> bool {
+        if self.push_str(&other.text) && other.truncated {
+            self.truncated = true;
+        }
+        !self.truncated
+    }
+
+    /// A buffer for output that will be appended to this one, capped at the room left here
+    pub fn nested(&self) -> BoundedOutput {
+        Self::with_limit(self.remaining())
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.limit.saturating_sub(self.text.len())
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// The collected text, followed by the truncation notice if anything was dropped
+    pub fn into_string(mut self) -> String {
+        if self.truncated {
+            self.text.push_str(&format!("\n[output truncated: exceeded {} bytes]", self.limit));
+        }
+        self.text
+    }
+}
+
+/// Cap on the patterns one brace expression may expand to, so `{a,b}{c,d}...` can't blow up
+const MAX_BRACE_EXPANSIONS: usize = 1024;
+
+/// Expand shell-style braces before globbing: `*.{rs,toml}` becomes `*.rs` and `*.toml`.
+/// Groups nest (`{a,b{c,d}}`) and combine (`{a,b}.{x,y}`); a group without a top-level
+/// comma such as `{a}` stays literal, as do braces and commas inside `[...]` classes,
+/// so `[{]` and `[,]` match the characters themselves
+pub fn expand_braces(pattern: &str) -> Result<Vec<String>, String> {
+    let mut expanded = Vec::new();
+    expand_braces_into(pattern, &mut expanded)?;
+    Ok(expanded)
+}
+
+fn expand_braces_into(pattern: &str, expanded: &mut Vec<String>) -> Result<(), String> {
+    let Some((open, close, alternatives)) = find_brace_group(pattern) else {
+        if expanded.len() == MAX_BRACE_EXPANSIONS {
+            return Err(format!("Brace expansion produces more than {} patterns", MAX_BRACE_EXPANSIONS));
+        }
+        expanded.push(pattern.to_string());
+        return Ok(());
+    };
+    for alternative in alternatives {
+        let candidate = format!("{}{}{}", &pattern[..open], alternative, &pattern[close + 1..]);
+        expand_braces_into(&candidate, expanded)?;
+    }
+    Ok(())
+}
+
+/// The first brace group with a top-level comma: its open and close offsets and alternatives
+fn find_brace_group(pattern: &str) -> Option<(usize, usize, Vec<&str>)> {
+    let bytes = pattern.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'[' => i = skip_char_class(bytes, i),
+            b'{' => {
+                if let Some((close, commas)) = match_brace(bytes, i)
+                    && !commas.is_empty()
+                {
+                    let mut alternatives = Vec::with_capacity(commas.len() + 1);
+                    let mut start = i + 1;
+                    for comma in commas {
+                        alternatives.push(&pattern[start..comma]);
+                        start = comma + 1;
+                    }
+                    alternatives.push(&pattern[start..close]);
+                    return Some((i, close, alternatives));
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// The offset of the `}` closing the group opened at `open`, with its top-level commas
+fn match_brace(bytes: &[u8], open: usize) -> Option<(usize, Vec<usize>)> {
+    let mut depth = 0;
+    let mut commas = Vec::new();
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'[' => {
+                i = skip_char_class(bytes, i);
+                continue;
+            }
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((i, commas));
+                }
+            }
+            b',' if depth == 1 => commas.push(i),
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// The offset just past the `[...]` class starting at `open`, following glob's rules that a
+/// leading `]` (after an optional `!`) is literal; an unclosed `[` is skipped on its own
+fn skip_char_class(bytes: &[u8], open: usize) -> usize {
+    let mut i = open + 1;
+    if bytes.get(i) == Some(&b'!') {
+        i += 1;
+    }
+    if bytes.get(i) == Some(&b']') {
+        i += 1;
+    }
+    match bytes[i.min(bytes.len())..].iter().position(|&b| b == b']') {
+        Some(offset) => i + offset + 1,
+        None => open + 1,
+    }
+}
+
+/// A glob pattern with brace expansion, matching when any of its expansions does
+#[derive(Debug, Clone)]
+pub struct GlobPattern {
+    patterns: Vec<Pattern>,
+}
+
+impl GlobPattern {
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        let patterns = expand_braces(pattern)?
+            .iter()
+            .map(|expanded| Pattern::new(expanded).map_err(|e| e.to_string()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { patterns })
+    }
+
+    pub fn matches(&self, s: &str) -> bool {
+        self.patterns.iter().any(|pattern| pattern.matches(s))
+    }
+
+    pub fn matches_with(&self, s: &str, options: MatchOptions) -> bool {
+        self.patterns.iter().any(|pattern| pattern.matches_with(s, options))
+    }
+}
+
+/// Expand a glob relative to the project root into sorted relative paths
+pub fn expand_pattern_in_project(pattern: &str, project_root: &Path, tool_name: &str) -> Result<Vec<String>, CallToolError> {
+    let matches = match_pattern_in_project(pattern, project_root, tool_name)?;
+    if matches.is_empty() {
+        return Err(CallToolError::from(tool_errors::file_not_found(
+            tool_name,
+            &format!("No files found matching pattern: {}", pattern)
+        )));
+    }
+    Ok(matches)
+}
+
+/// Like `expand_pattern_in_project`, but an empty match is not an error.
+/// Braces are expanded first, so `src/*.{rs,toml}` globs `src/*.rs` and `src/*.toml`
+pub fn match_pattern_in_project(pattern: &str, project_root: &Path, tool_name: &str) -> Result<Vec<String>, CallToolError> {
+    let expansions = expand_braces(pattern)
+        .map_err(|e| CallToolError::from(tool_errors::pattern_error(tool_name, pattern, &e)))?;
+
+    // Checked per expansion, since `{..,src}/*` only shows its `..` once expanded
+    for expanded in &expansions {
+        let requested = Path::new(expanded);
+        if requested.is_absolute()
+            || requested.components().any(|c| matches!(c, Component::ParentDir))
+        {
+            return Err(CallToolError::from(tool_errors::access_denied(
+                tool_name,
+                pattern,
+                "Patterns must be relative to the project root and cannot contain '..'",
+            )));
+        }
+    }
+    
+    let current_dir = project_root.canonicalize()
+        .map_err(|e| CallToolError::from(tool_errors::invalid_input(tool_name, &format!("Failed to canonicalize project root: {}", e))))?;
+    
+    let options = MatchOptions {
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+        ..Default::default()
+    };
+    let mut matches = Vec::new();
+    for expanded in &expansions {
+        let pattern_path = format!("{}/{}", current_dir.display(), expanded);
+        matches.extend(
+            glob_with(&pattern_path, options)
+                .map_err(|e| CallToolError::from(tool_errors::pattern_error(tool_name, pattern, &e.to_string())))?
+                .filter_map(Result::ok)
+                .map(|path| path.strip_prefix(&current_dir).unwrap_or(&path).to_string_lossy().to_string()),
+        );
+    }
+    matches.sort();
+    matches.dedup();
+    Ok(matches)
+}
+
+/// Reason for an access-denied error on a path that escaped the project, naming where it
+/// actually resolved so a symlink or `..` that leads elsewhere is easy to spot
+pub fn outside_project_reason(reason: &str, resolved: &Path) -> String {
+    format!("{} (resolved to '{}')", reason, resolved.display())
+}
+
 /// Resolve a path within the project directory, optionally following symlinks
 /// for read-only operations. This allows symlinks within the project to point
 /// to content outside the project directory for reading purposes only.
@@ -227,13 +601,185 @@ pub fn resolve_path_for_read(
         return Err(CallToolError::from(tool_errors::access_denied(
             tool_name,
             path,
-            "Path is outside the project directory"
+            &outside_project_reason("Path is outside the project directory", &canonical_path)
         )));
     }
 
     Ok(canonical_path)
 }
 
+/// Resolve a path that is about to be written, which may not exist yet. Existing paths
+/// are canonicalized; for new paths the deepest existing ancestor is canonicalized and the
+/// missing components are appended, so the result is where the write will actually land.
+/// Either way it must be inside the project directory.
+pub fn resolve_path_for_write(
+    path: &str,
+    project_root: &Path,
+    tool_name: &str,
+) -> Result<PathBuf, CallToolError> {
+    let requested_path = Path::new(path);
+    let absolute_path = if requested_path.is_absolute() {
+        requested_path.to_path_buf()
+    } else {
+        project_root.join(requested_path)
+    };
+
+    let canonical_project_root = project_root.canonicalize()
+        .map_err(|e| CallToolError::from(tool_errors::invalid_input(
+            tool_name,
+            &format!("Failed to canonicalize project root: {}", e)
+        )))?;
+
+    if absolute_path.exists() {
+        let canonical_path = absolute_path.canonicalize()
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(
+                tool_name,
+                &format!("Failed to resolve path '{}': {}", path, e)
+            )))?;
+
+        if !canonical_path.starts_with(&canonical_project_root) {
+            return Err(CallToolError::from(tool_errors::access_denied(
+                tool_name,
+                path,
+                &outside_project_reason("Path is outside the project directory", &canonical_path)
+            )));
+        }
+
+        return Ok(canonical_path);
+    }
+
+    // Walk up to the deepest ancestor that exists, remembering the components to create
+    let mut existing = absolute_path.as_path();
+    let mut missing = Vec::new();
+    while !existing.exists() {
+        // A dangling symlink would be followed when the write creates its target
+        if existing.is_symlink() {
+            return Err(CallToolError::from(tool_errors::access_denied(
+                tool_name,
+                path,
+                "Path contains a symlink to a missing target"
+            )));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name);
+                existing = parent;
+            }
+            _ => {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    tool_name,
+                    &format!("Invalid file path: '{}'", path)
+                )));
+            }
+        }
+    }
+
+    let mut resolved = existing.canonicalize()
+        .map_err(|e| CallToolError::from(tool_errors::invalid_input(
+            tool_name,
+            &format!("Failed to resolve parent directory: {}", e)
+        )))?;
+
+    if !resolved.starts_with(&canonical_project_root) {
+        return Err(CallToolError::from(tool_errors::access_denied(
+            tool_name,
+            path,
+            &outside_project_reason("Path would be outside the project directory", &resolved)
+        )));
+    }
+
+    resolved.extend(missing.iter().rev());
+    Ok(resolved)
+}
+
+/// Create the directory `dir` and any missing parents without leaving the project, and
+/// return its real path. `path` is the name reported in errors.
+///
+/// The deepest existing ancestor is resolved and checked against the project root before
+/// anything is created, then each missing component is created on its own and checked
+/// again, so a symlink already in the path (or swapped in along the way) is refused before
+/// a directory is created through it.
+pub fn create_dir_all_in_project(
+    dir: &Path,
+    project_root: &Path,
+    path: &str,
+    tool: &str,
+) -> Result<PathBuf, CallToolError> {
+    let canonical_project_root = project_root.canonicalize()
+        .map_err(|e| CallToolError::from(tool_errors::invalid_input(
+            tool,
+            &format!("Failed to canonicalize project root: {}", e)
+        )))?;
+    let check_inside = |resolved: &Path| {
+        if resolved.starts_with(&canonical_project_root) {
+            Ok(())
+        } else {
+            Err(CallToolError::from(tool_errors::access_denied(
+                tool,
+                path,
+                &outside_project_reason("Path would be outside the project directory", resolved)
+            )))
+        }
+    };
+    let resolve = |dir: &Path| {
+        dir.canonicalize().map_err(|e| CallToolError::from(tool_errors::invalid_input(
+            tool,
+            &format!("Failed to resolve directory '{}': {}", dir.display(), e)
+        )))
+    };
+
+    // Walk up to the deepest ancestor that exists, remembering the components to create
+    let mut existing = dir;
+    let mut missing = Vec::new();
+    while !existing.exists() {
+        if existing.is_symlink() {
+            return Err(CallToolError::from(tool_errors::access_denied(
+                tool,
+                path,
+                "Path contains a symlink to a missing target"
+            )));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name);
+                existing = parent;
+            }
+            _ => {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    tool,
+                    &format!("Invalid directory path: '{}'", path)
+                )));
+            }
+        }
+    }
+
+    let mut current = resolve(existing)?;
+    check_inside(&current)?;
+    for name in missing.iter().rev() {
+        let next = current.join(name);
+        match std::fs::create_dir(&next) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(e) => {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    tool,
+                    &format!("Failed to create directory '{}': {}", next.display(), e)
+                )));
+            }
+        }
+        current = resolve(&next)?;
+        check_inside(&current)?;
+    }
+
+    if !current.is_dir() {
+        return Err(CallToolError::from(tool_errors::invalid_input(
+            tool,
+            &format!("'{}' is not a directory", path)
+        )));
+    }
+    Ok(current)
+}
+
 /// Resolve a path for operations that need to check symlinks without following them
 /// (like exists and stat tools). This allows checking if a symlink exists within
 /// the project directory without following it to its target.
@@ -269,7 +815,7 @@ pub fn resolve_path_allowing_symlinks(
                 return Err(CallToolError::from(tool_errors::access_denied(
                     tool_name,
                     path,
-                    "Path is outside the project directory"
+                    &outside_project_reason("Path is outside the project directory", &canonical_parent)
                 )));
             }
             
@@ -304,13 +850,283 @@ pub fn resolve_path_allowing_symlinks(
         return Err(CallToolError::from(tool_errors::access_denied(
             tool_name,
             path,
-            "Path is outside the project directory"
+            &outside_project_reason("Path is outside the project directory", &normalized)
         )));
     }
     
     Ok(absolute_path)
 }
 
+/// Open a file for writing without racing a symlink swap between validation and the write.
+///
+/// `path` should already be resolved and checked against the project root. The final
+/// component is opened with `O_NOFOLLOW`, and the real path of the opened descriptor is
+/// checked against the project root again, so a file or parent directory swapped for a
+/// symlink after validation is refused before anything is written. Existing files are only
+/// truncated once that check passes (unless `append` is set), and a file created outside
+/// the project by the race is removed again.
+///
+/// Returns the open file and its verified real path.
+pub fn open_for_write_in_project(
+    path: &Path,
+    project_root: &Path,
+    append: bool,
+    tool: &str,
+) -> Result<(std::fs::File, PathBuf), CallToolError> {
+    open_in_project(path, project_root, append, false, tool)
+}
+
+/// Like [`open_for_write_in_project`], but only ever creates a new file: if anything already
+/// exists at `path` (including a dangling symlink) nothing is opened and an error is returned.
+pub fn create_new_in_project(
+    path: &Path,
+    project_root: &Path,
+    tool: &str,
+) -> Result<(std::fs::File, PathBuf), CallToolError> {
+    open_in_project(path, project_root, false, true, tool)
+}
+
+/// Write through a temporary file next to `path` and rename it into place, so readers
+/// never see a partially written file. Keeps the permissions of an existing file.
+///
+/// The temporary file gets a random name and is created with [`create_new_in_project`], so
+/// a symlink planted where it would go can't redirect the write out of the project. It is
+/// synced before the rename, and removed again if anything fails. Missing parent
+/// directories are created with [`create_dir_all_in_project`].
+pub fn write_atomically(path: &Path, project_root: &Path, content: &[u8], tool: &str) -> Result<(), CallToolError> {
+    use std::io::Write;
+
+    let (Some(parent), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return Err(CallToolError::from(tool_errors::invalid_input(
+            tool,
+            &format!("Cannot write to '{}'", path.display()),
+        )));
+    };
+    create_dir_all_in_project(parent, project_root, &parent.display().to_string(), tool)?;
+
+    let temp_name = format!(".{}.{}.tmp", file_name.to_string_lossy(), uuid::Uuid::new_v4().simple());
+    let (mut file, verified_temp) = create_new_in_project(&parent.join(temp_name), project_root, tool)?;
+    let written = (|| {
+        file.write_all(content)?;
+        if let Ok(metadata) = std::fs::metadata(path) {
+            file.set_permissions(metadata.permissions())?;
+        }
+        file.sync_all()
+    })();
+    drop(file);
+
+    // Rename within the directory the temporary file was verified to be in
+    let target = verified_temp.with_file_name(file_name);
+    let result = written
+        .map_err(|e| format!("Failed to write temporary file: {}", e))
+        .and_then(|_| std::fs::rename(&verified_temp, &target).map_err(|e| format!("Failed to move temporary file: {}", e)));
+    if let Err(message) = result {
+        let _ = std::fs::remove_file(&verified_temp);
+        return Err(CallToolError::from(tool_errors::invalid_input(tool, &message)));
+    }
+    Ok(())
+}
+
+/// Run synchronous tool work, such as archiving or (de)compressing a large file, on the
+/// blocking thread pool so it doesn't stall the runtime
+pub async fn run_blocking<T, F>(tool: &str, work: F) -> Result<T, CallToolError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, CallToolError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || work().map_err(CodedError::from_call_tool_error))
+        .await
+        .map_err(|e| CallToolError::from(tool_errors::invalid_input(
+            tool,
+            &format!("Background task failed: {}", e),
+        )))?
+        .map_err(CallToolError::new)
+}
+
+/// A reader that fails once the call is cancelled, so a long copy stops between chunks.
+/// The error is only a signal; callers report [`tool_errors::cancelled`] instead
+pub struct CancellableReader<R> {
+    inner: R,
+    context: ToolContext,
+}
+
+impl<R> CancellableReader<R> {
+    pub fn new(inner: R, context: &ToolContext) -> Self {
+        Self { inner, context: context.clone() }
+    }
+}
+
+impl<R: std::io::Read> std::io::Read for CancellableReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.context.is_cancelled() {
+            return Err(std::io::Error::other("cancelled"));
+        }
+        self.inner.read(buf)
+    }
+}
+
+fn open_in_project(
+    path: &Path,
+    project_root: &Path,
+    append: bool,
+    create_only: bool,
+    tool: &str,
+) -> Result<(std::fs::File, PathBuf), CallToolError> {
+    let root = project_root.canonicalize().unwrap_or_else(|_| project_root.to_path_buf());
+    let display = path.display().to_string();
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).append(append);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.custom_flags(libc::O_NOFOLLOW);
+    }
+    #[cfg(not(unix))]
+    {
+        if std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink()) {
+            return Err(CallToolError::from(tool_errors::access_denied(
+                tool,
+                &display,
+                "Refusing to write through a symlink",
+            )));
+        }
+    }
+
+    // Try to create the file first so we know whether to clean up after a refused write
+    let (file, created) = match options.clone().create_new(true).open(path) {
+        Ok(file) => (file, true),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && create_only => {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                tool,
+                &format!("'{}' already exists", display),
+            )));
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            let file = options.open(path).map_err(|e| open_error(tool, &display, e))?;
+            (file, false)
+        }
+        Err(e) => return Err(open_error(tool, &display, e)),
+    };
+
+    let real_path = opened_file_path(&file, path).map_err(|e| {
+        CallToolError::from(tool_errors::invalid_input(
+            tool,
+            &format!("Failed to verify '{}' after opening: {}", display, e),
+        ))
+    })?;
+
+    if !real_path.starts_with(&root) {
+        drop(file);
+        if created {
+            let _ = std::fs::remove_file(&real_path);
+        }
+        return Err(CallToolError::from(tool_errors::access_denied(
+            tool,
+            &display,
+            &outside_project_reason("Path resolved outside the project directory while opening", &real_path),
+        )));
+    }
+
+    if !append {
+        file.set_len(0).map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                tool,
+                &format!("Failed to truncate '{}': {}", display, e),
+            ))
+        })?;
+    }
+
+    Ok((file, real_path))
+}
+
+fn open_error(tool: &str, display: &str, error: std::io::Error) -> CallToolError {
+    // O_NOFOLLOW reports a symlinked final component as ELOOP
+    #[cfg(unix)]
+    {
+        if error.raw_os_error() == Some(libc::ELOOP) {
+            return CallToolError::from(tool_errors::access_denied(
+                tool,
+                display,
+                "Refusing to write through a symlink",
+            ));
+        }
+    }
+    CallToolError::from(tool_errors::invalid_input(
+        tool,
+        &format!("Failed to open '{}' for writing: {}", display, error),
+    ))
+}
+
+/// The path the kernel has for an open file, independent of how it was reached
+#[cfg(target_os = "linux")]
+fn opened_file_path(file: &std::fs::File, _path: &Path) -> std::io::Result<PathBuf> {
+    use std::os::fd::AsRawFd;
+    std::fs::read_link(format!("/proc/self/fd/{}", file.as_raw_fd()))
+}
+
+#[cfg(target_os = "macos")]
+fn opened_file_path(file: &std::fs::File, _path: &Path) -> std::io::Result<PathBuf> {
+    use std::ffi::CStr;
+    use std::os::fd::AsRawFd;
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut buffer = vec![0 as libc::c_char; libc::PATH_MAX as usize];
+    // SAFETY: F_GETPATH writes a NUL-terminated path of at most PATH_MAX bytes into the buffer
+    let result = unsafe { libc::fcntl(file.as_raw_fd(), libc::F_GETPATH, buffer.as_mut_ptr()) };
+    if result == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // SAFETY: the buffer was NUL-terminated by a successful F_GETPATH
+    let path = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+    Ok(PathBuf::from(std::ffi::OsStr::from_bytes(path.to_bytes())))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn opened_file_path(_file: &std::fs::File, path: &Path) -> std::io::Result<PathBuf> {
+    path.canonicalize()
+}
+
+/// Matcher for the project's `.gitignore` and `.git/info/exclude`, shared by the
+/// tools that walk directories when `respect_gitignore` is set
+pub struct GitignoreFilter {
+    root: PathBuf,
+    matcher: Option<Gitignore>,
+}
+
+impl GitignoreFilter {
+    /// Load the ignore files at the project root; when `enabled` is false nothing is ignored
+    pub fn new(project_root: &Path, enabled: bool) -> Self {
+        let root = project_root.canonicalize().unwrap_or_else(|_| project_root.to_path_buf());
+        let matcher = if enabled {
+            let mut builder = GitignoreBuilder::new(&root);
+            for file in [root.join(".gitignore"), root.join(".git/info/exclude")] {
+                // Invalid lines are skipped rather than failing the whole search
+                if file.is_file() {
+                    let _ = builder.add(file);
+                }
+            }
+            builder.build().ok()
+        } else {
+            None
+        };
+        Self { root, matcher }
+    }
+    
+    /// Whether `path` or one of its parent directories is ignored.
+    /// Paths outside the project root are never ignored.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Some(matcher) = &self.matcher else {
+            return false;
+        };
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        !relative.as_os_str().is_empty()
+            && matcher.matched_path_or_any_parents(relative, is_dir).is_ignore()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,6 +1142,57 @@ mod tests {
         assert_eq!(format_size(1073741824), "1.0 GiB");
     }
 
+    #[test]
+    fn test_bounded_output() {
+        let mut output = BoundedOutput::with_limit(10);
+        assert!(output.push_str("12345"));
+        assert!(!output.push_str("678éé"));
+        assert!(!output.push_str("more"));
+        assert!(output.is_truncated());
+        // The cut lands on a character boundary
+        assert_eq!(output.into_string(), "12345678é\n[output truncated: exceeded 10 bytes]");
+
+        let mut output = BoundedOutput::with_limit(8);
+        output.push_str("ab");
+        let mut nested = output.nested();
+        assert!(!nested.push_str("cdefghij"));
+        assert!(!output.append(nested));
+        assert_eq!(output.into_string(), "abcdefgh\n[output truncated: exceeded 8 bytes]");
+
+        let mut output = BoundedOutput::with_limit(8);
+        assert!(output.push_str("12345678"));
+        assert_eq!(output.into_string(), "12345678");
+    }
+
+    #[test]
+    fn test_expand_braces() {
+        assert_eq!(expand_braces("*.rs").unwrap(), vec!["*.rs"]);
+        assert_eq!(expand_braces("*.{rs,toml}").unwrap(), vec!["*.rs", "*.toml"]);
+        // Nested groups, and groups whose alternatives contain commas of their own
+        assert_eq!(
+            expand_braces("src/{a,b{c,d}}.rs").unwrap(),
+            vec!["src/a.rs", "src/bc.rs", "src/bd.rs"]
+        );
+        assert_eq!(
+            expand_braces("{x,{y,z}}{1,2}").unwrap(),
+            vec!["x1", "x2", "y1", "y2", "z1", "z2"]
+        );
+        assert_eq!(expand_braces("{,test_}*.rs").unwrap(), vec!["*.rs", "test_*.rs"]);
+        // Groups without a top-level comma, unbalanced braces and character classes are literal
+        assert_eq!(expand_braces("{a}.{b,c}").unwrap(), vec!["{a}.b", "{a}.c"]);
+        assert_eq!(expand_braces("{a,b").unwrap(), vec!["{a,b"]);
+        assert_eq!(expand_braces("a[{,}]b").unwrap(), vec!["a[{,}]b"]);
+        assert_eq!(expand_braces("{[,],x}").unwrap(), vec!["[,]", "x"]);
+
+        assert!(expand_braces(&"{a,b}".repeat(11)).is_err());
+
+        let pattern = GlobPattern::new("*.{rs,toml}").unwrap();
+        assert!(pattern.matches("main.rs"));
+        assert!(pattern.matches("Cargo.toml"));
+        assert!(!pattern.matches("README.md"));
+        assert!(GlobPattern::new("{a,[}").is_err());
+    }
+
     #[test]
     fn test_format_count() {
         assert_eq!(format_count(0, "file", "files"), "0 files");
@@ -368,4 +1235,212 @@ mod tests {
         assert_eq!(format_number(1000), "1,000");
         assert_eq!(format_number(1234567), "1,234,567");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_expand_pattern_in_project() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir(root.join("src")).unwrap();
+        for name in ["src/main.rs", "src/lib.rs", "Cargo.toml", "README.md"] {
+            std::fs::write(root.join(name), "").unwrap();
+        }
+
+        assert_eq!(
+            expand_pattern_in_project("{src/*.rs,*.toml,src/main.*}", root, "test").unwrap(),
+            vec!["Cargo.toml", "src/lib.rs", "src/main.rs"]
+        );
+        assert!(match_pattern_in_project("*.missing", root, "test").unwrap().is_empty());
+        assert!(expand_pattern_in_project("*.missing", root, "test").is_err());
+        // `..` hidden inside a brace group is still rejected
+        assert!(match_pattern_in_project("{..,src}/*", root, "test").is_err());
+    }
+
+    #[test]
+    fn test_gitignore_filter() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(root.join(".gitignore"), "target/\n*.log\n!keep.log\n").unwrap();
+        std::fs::create_dir_all(root.join(".git/info")).unwrap();
+        std::fs::write(root.join(".git/info/exclude"), "/local.txt\n").unwrap();
+        
+        let filter = GitignoreFilter::new(&root, true);
+        assert!(filter.is_ignored(&root.join("target"), true));
+        assert!(filter.is_ignored(&root.join("target/debug/app"), false));
+        assert!(filter.is_ignored(&root.join("src/debug.log"), false));
+        assert!(!filter.is_ignored(&root.join("keep.log"), false));
+        assert!(filter.is_ignored(&root.join("local.txt"), false));
+        assert!(!filter.is_ignored(&root.join("src/local.txt"), false));
+        assert!(!filter.is_ignored(&root.join("src/main.rs"), false));
+        
+        let disabled = GitignoreFilter::new(&root, false);
+        assert!(!disabled.is_ignored(&root.join("target"), true));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_open_for_write_refuses_symlink_swap() {
+        use std::io::Write;
+        use std::os::unix::fs::symlink;
+
+        let project = tempfile::TempDir::new().unwrap();
+        let outside = tempfile::TempDir::new().unwrap();
+        let root = project.path().canonicalize().unwrap();
+        let secret = outside.path().join("secret.txt");
+        std::fs::write(&secret, "keep me").unwrap();
+
+        // Validated while still a regular directory, then swapped for a symlink before the write
+        std::fs::create_dir(root.join("sub")).unwrap();
+        std::fs::write(root.join("sub/secret.txt"), "inside").unwrap();
+        let validated = root.join("sub/secret.txt").canonicalize().unwrap();
+        assert!(validated.starts_with(&root));
+        std::fs::remove_dir_all(root.join("sub")).unwrap();
+        symlink(outside.path(), root.join("sub")).unwrap();
+
+        assert!(open_for_write_in_project(&validated, &root, false, "test").is_err());
+        assert_eq!(std::fs::read_to_string(&secret).unwrap(), "keep me");
+
+        // A file created through the swapped directory is removed again
+        assert!(open_for_write_in_project(&root.join("sub/new.txt"), &root, false, "test").is_err());
+        assert!(!outside.path().join("new.txt").exists());
+
+        // Swapping the file itself for a symlink is caught by O_NOFOLLOW
+        std::fs::write(root.join("file.txt"), "inside").unwrap();
+        let validated = root.join("file.txt").canonicalize().unwrap();
+        std::fs::remove_file(&validated).unwrap();
+        symlink(&secret, &validated).unwrap();
+        assert!(open_for_write_in_project(&validated, &root, true, "test").is_err());
+        assert_eq!(std::fs::read_to_string(&secret).unwrap(), "keep me");
+
+        // Regular files inside the project are truncated and written
+        std::fs::write(root.join("plain.txt"), "old contents").unwrap();
+        let (mut file, real_path) = open_for_write_in_project(&root.join("plain.txt"), &root, false, "test").unwrap();
+        file.write_all(b"new").unwrap();
+        assert_eq!(real_path, root.join("plain.txt"));
+        assert_eq!(std::fs::read_to_string(root.join("plain.txt")).unwrap(), "new");
+
+        // Exclusive creation refuses existing files and dangling symlinks alike
+        assert!(create_new_in_project(&root.join("plain.txt"), &root, "test").is_err());
+        symlink(outside.path().join("missing.txt"), root.join("dangling.txt")).unwrap();
+        assert!(create_new_in_project(&root.join("dangling.txt"), &root, "test").is_err());
+        assert!(!outside.path().join("missing.txt").exists());
+        assert!(create_new_in_project(&root.join("fresh.txt"), &root, "test").is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomically_ignores_planted_temp_files() {
+        use std::os::unix::fs::{PermissionsExt, symlink};
+
+        let project = tempfile::TempDir::new().unwrap();
+        let outside = tempfile::TempDir::new().unwrap();
+        let root = project.path().canonicalize().unwrap();
+        let secret = outside.path().join("secret.txt");
+        std::fs::write(&secret, "keep me").unwrap();
+        std::fs::write(root.join("file.txt"), "old").unwrap();
+        std::fs::set_permissions(root.join("file.txt"), std::fs::Permissions::from_mode(0o640)).unwrap();
+        symlink(&secret, root.join("file.txt.tmp")).unwrap();
+
+        write_atomically(&root.join("file.txt"), &root, b"new", "test").unwrap();
+        assert_eq!(std::fs::read_to_string(root.join("file.txt")).unwrap(), "new");
+        assert_eq!(std::fs::metadata(root.join("file.txt")).unwrap().permissions().mode() & 0o777, 0o640);
+        assert_eq!(std::fs::read_to_string(&secret).unwrap(), "keep me");
+
+        // Nothing is left behind but the file and the planted link
+        let mut names: Vec<String> = std::fs::read_dir(&root)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["file.txt", "file.txt.tmp"]);
+
+        // A directory swapped for a symlink out of the project is refused
+        std::fs::create_dir(root.join("sub")).unwrap();
+        let validated = root.join("sub/new.txt");
+        std::fs::remove_dir(root.join("sub")).unwrap();
+        symlink(outside.path(), root.join("sub")).unwrap();
+        assert!(write_atomically(&validated, &root, b"escape", "test").is_err());
+        assert!(!outside.path().join("new.txt").exists());
+        assert_eq!(std::fs::read_dir(outside.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomically_creates_no_directories_through_symlinked_parent() {
+        let project = tempfile::TempDir::new().unwrap();
+        let outside = tempfile::TempDir::new().unwrap();
+        let root = project.path().canonicalize().unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.join("link")).unwrap();
+
+        let error = write_atomically(&root.join("link/new/dir/file.txt"), &root, b"escape", "test").unwrap_err();
+        assert!(error.to_string().contains("outside the project directory"), "{}", error);
+        assert!(!outside.path().join("new").exists());
+
+        // Missing directories inside the project are still created
+        write_atomically(&root.join("new/dir/file.txt"), &root, b"inside", "test").unwrap();
+        assert_eq!(std::fs::read_to_string(root.join("new/dir/file.txt")).unwrap(), "inside");
+    }
+
+    #[test]
+    fn test_text_style_detect_and_apply() {
+        let crlf = TextStyle::detect("a\r\nb\r\n").unwrap();
+        assert_eq!(crlf, TextStyle { line_ending: Some(LineEnding::Crlf), trailing_newline: true });
+        assert_eq!(crlf.apply("x\ny"), "x\r\ny\r\n");
+        
+        // Mixed endings follow the majority; a missing final newline stays missing
+        let lf = TextStyle::detect("a\nb\nc\r\nd").unwrap();
+        assert_eq!(lf, TextStyle { line_ending: Some(LineEnding::Lf), trailing_newline: false });
+        assert_eq!(lf.apply("x\r\ny\r\n"), "x\ny");
+        
+        assert_eq!(TextStyle::detect(""), None);
+        assert_eq!(TextStyle::detect("single line").unwrap().apply("one\ntwo\n"), "one\ntwo");
+        assert_eq!(convert_line_endings("a\r\nb\n", LineEnding::Crlf), "a\r\nb\r\n");
+    }
+    
+    #[test]
+    fn test_resolve_path_for_write() {
+        let project = tempfile::TempDir::new().unwrap();
+        let root = project.path().canonicalize().unwrap();
+        std::fs::write(root.join("existing.yaml"), "a: 1").unwrap();
+        
+        assert_eq!(resolve_path_for_write("existing.yaml", &root, "test").unwrap(), root.join("existing.yaml"));
+        assert_eq!(resolve_path_for_write("new.yaml", &root, "test").unwrap(), root.join("new.yaml"));
+        assert_eq!(
+            resolve_path_for_write("a/b/new.yaml", &root, "test").unwrap(),
+            root.join("a/b/new.yaml")
+        );
+        
+        assert!(resolve_path_for_write("../escape.yaml", &root, "test").is_err());
+        assert!(resolve_path_for_write("missing/../../escape.yaml", &root, "test").is_err());
+        
+        #[cfg(unix)]
+        {
+            let outside = tempfile::TempDir::new().unwrap();
+            std::os::unix::fs::symlink(outside.path(), root.join("link")).unwrap();
+            assert!(resolve_path_for_write("link/new.yaml", &root, "test").is_err());
+            assert!(resolve_path_for_write("link/deeper/new.yaml", &root, "test").is_err());
+            
+            std::os::unix::fs::symlink(outside.path().join("nowhere"), root.join("dangling")).unwrap();
+            assert!(resolve_path_for_write("dangling", &root, "test").is_err());
+        }
+    }
+    
+    #[test]
+    fn test_outside_project_errors_name_resolved_path() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let base = temp.path().canonicalize().unwrap();
+        let root = base.join("project");
+        std::fs::create_dir(&root).unwrap();
+        std::fs::write(base.join("secret.txt"), "secret").unwrap();
+        
+        let error = resolve_path_for_read("../secret.txt", &root, false, "test").unwrap_err().to_string();
+        assert!(error.contains("../secret.txt"), "{}", error);
+        assert!(error.contains(&format!("resolved to '{}'", base.join("secret.txt").display())), "{}", error);
+        
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&base, root.join("link")).unwrap();
+            let error = resolve_path_for_write("link/new.yaml", &root, "test").unwrap_err().to_string();
+            assert!(error.contains(&format!("resolved to '{}'", base.display())), "{}", error);
+        }
+    }
+}
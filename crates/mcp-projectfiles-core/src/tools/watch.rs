@@ -0,0 +1,332 @@
+use crate::context::{StatefulTool, ToolContext};
+use crate::config::tool_errors;
+use crate::tools::utils::{BoundedOutput, format_count, format_path, resolve_path_for_read};
+use async_trait::async_trait;
+use notify::{EventKind, RecursiveMode, Watcher};
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+const TOOL_NAME: &str = "watch";
+
+/// Upper bound on a single collection window
+const MAX_TIMEOUT_MS: u64 = 60_000;
+
+/// Changes kept for the report; later ones are only counted
+const MAX_RECORDED_CHANGES: usize = 1000;
+
+/// How often the wait for events stops to check for cancellation
+const CANCEL_POLL_INTERVAL_MS: u64 = 100;
+
+#[mcp_tool(
+    name = "watch",
+    description = "Collect file create/modify/delete events for a time window. One-shot: each call watches for timeout_ms and returns the batch of events seen, it is not a persistent subscription. Up to 1000 changes are listed; later ones are only counted.
+Examples: {\"path\": \"src\", \"timeout_ms\": 5000} or {\"path\": \"config.toml\", \"recursive\": false}"
+)]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct WatchTool {
+    /// File or directory to watch (relative to project root, default: ".")
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// Watch subdirectories as well (default: true)
+    #[serde(default = "default_recursive")]
+    pub recursive: bool,
+    /// How long to collect events for in milliseconds (default: 1000, max: 60000)
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_path() -> String {
+    ".".to_string()
+}
+
+fn default_recursive() -> bool {
+    true
+}
+
+fn default_timeout_ms() -> u64 {
+    1000
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChangeKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+impl ChangeKind {
+    fn from_event_kind(kind: &EventKind) -> Option<Self> {
+        match kind {
+            EventKind::Create(_) => Some(ChangeKind::Created),
+            EventKind::Modify(_) => Some(ChangeKind::Modified),
+            EventKind::Remove(_) => Some(ChangeKind::Deleted),
+            _ => None,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::Created => "created",
+            ChangeKind::Modified => "modified",
+            ChangeKind::Deleted => "deleted",
+        }
+    }
+}
+
+#[async_trait]
+impl StatefulTool for WatchTool {
+    async fn call_with_context(
+        mut self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
+        let project_root = context.get_project_root()
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
+
+        // Canonicalize project root for consistent path comparison
+        let current_dir = project_root.canonicalize()
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to canonicalize project root: {}", e))))?;
+
+        if self.timeout_ms == 0 || self.timeout_ms > MAX_TIMEOUT_MS {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("timeout_ms must be between 1 and {}", MAX_TIMEOUT_MS)
+            )));
+        }
+
+        let watch_path = resolve_path_for_read(&self.path, &current_dir, false, TOOL_NAME)?;
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        })
+        .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to create watcher: {}", e))))?;
+
+        let mode = if self.recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        watcher.watch(&watch_path, mode)
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to watch '{}': {}", self.path, e))))?;
+
+        let mut changes: Vec<(ChangeKind, PathBuf)> = Vec::new();
+        let mut unrecorded = 0;
+        let deadline = Instant::now() + Duration::from_millis(self.timeout_ms);
+        loop {
+            context.check_cancelled(TOOL_NAME)?;
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            let wake = deadline.min(now + Duration::from_millis(CANCEL_POLL_INTERVAL_MS));
+            let result = match tokio::time::timeout_at(wake, rx.recv()).await {
+                Ok(Some(result)) => result,
+                Ok(None) => break,
+                Err(_) => continue,
+            };
+            // Backend errors only affect individual events; keep collecting
+            let Ok(event) = result else {
+                continue;
+            };
+            let Some(kind) = ChangeKind::from_event_kind(&event.kind) else {
+                continue;
+            };
+            for path in event.paths {
+                // Drop events that resolve outside the project (e.g. through symlinks)
+                let Some(resolved) = resolve_event_path(&path, &current_dir) else {
+                    continue;
+                };
+                // Backends often report several events for one write; collapse repeats
+                if changes.last() == Some(&(kind, resolved.clone())) {
+                    continue;
+                }
+                if changes.len() < MAX_RECORDED_CHANGES {
+                    changes.push((kind, resolved));
+                } else {
+                    unrecorded += 1;
+                }
+            }
+        }
+        drop(watcher);
+
+        let watched = watch_path.strip_prefix(&current_dir).unwrap_or(&watch_path);
+        let mut output = BoundedOutput::new();
+        if changes.is_empty() {
+            output.push_str(&format!("No changes observed in {} within {}ms", format_path(watched), self.timeout_ms));
+        } else {
+            output.push_str(&format!(
+                "Observed {} in {} within {}ms:",
+                format_count(changes.len() + unrecorded, "change", "changes"),
+                format_path(watched),
+                self.timeout_ms
+            ));
+        }
+        for (kind, path) in &changes {
+            let relative = path.strip_prefix(&current_dir).unwrap_or(path);
+            if !output.push_str(&format!("\n  {} {}", kind.label(), format_path(relative))) {
+                break;
+            }
+        }
+        if unrecorded > 0 {
+            output.push_str(&format!(
+                "\n[{} not recorded after the first {}]",
+                format_count(unrecorded, "change", "changes"),
+                MAX_RECORDED_CHANGES
+            ));
+        }
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                output.into_string(), None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+}
+
+/// Canonicalize an event path (or its parent, for deleted files) and keep it only if it is inside the project
+fn resolve_event_path(path: &Path, project_root: &Path) -> Option<PathBuf> {
+    let resolved = match path.canonicalize() {
+        Ok(canonical) => canonical,
+        Err(_) => path.parent()?.canonicalize().ok()?.join(path.file_name()?),
+    };
+    resolved.starts_with(project_root).then_some(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::ToolContext;
+    use tempfile::TempDir;
+
+    async fn setup_test_context() -> (ToolContext, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let canonical_path = temp_dir.path().canonicalize().unwrap();
+        let context = ToolContext::with_project_root(canonical_path);
+        (context, temp_dir)
+    }
+
+    fn text(result: &CallToolResult) -> String {
+        match &result.content[0] {
+            CallToolResultContentItem::TextContent(t) => t.text.clone(),
+            _ => panic!("Expected text content"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_reports_created_file() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir(root.join("src")).unwrap();
+
+        let writer_root = root.clone();
+        let writer = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            tokio::fs::write(writer_root.join("src/new.txt"), "hello").await.unwrap();
+        });
+
+        let tool = WatchTool {
+            path: ".".to_string(),
+            recursive: true,
+            timeout_ms: 1500,
+        };
+        let output = text(&tool.call_with_context(&context).await.unwrap());
+        writer.await.unwrap();
+
+        assert!(output.contains("created 'src/new.txt'"), "unexpected output: {}", output);
+    }
+
+    #[tokio::test]
+    async fn test_watch_caps_recorded_changes() {
+        let (context, temp_dir) = setup_test_context().await;
+        let root = temp_dir.path().canonicalize().unwrap();
+
+        let writer = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            for i in 0..MAX_RECORDED_CHANGES + 50 {
+                std::fs::write(root.join(format!("file{}.txt", i)), "").unwrap();
+            }
+        });
+
+        let tool = WatchTool {
+            path: ".".to_string(),
+            recursive: true,
+            timeout_ms: 2000,
+        };
+        let output = text(&tool.call_with_context(&context).await.unwrap());
+        writer.await.unwrap();
+
+        assert_eq!(output.lines().count(), MAX_RECORDED_CHANGES + 2, "{}", &output[output.len() - 200..]);
+        assert!(output.contains(&format!("not recorded after the first {}]", MAX_RECORDED_CHANGES)));
+    }
+
+    #[tokio::test]
+    async fn test_watch_stops_when_cancelled() {
+        let temp_dir = TempDir::new().unwrap();
+        let token = tokio_util::sync::CancellationToken::new();
+        let context = ToolContext::with_project_root(temp_dir.path().canonicalize().unwrap())
+            .with_cancellation(token.clone());
+        let canceller = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            token.cancel();
+        });
+
+        let tool = WatchTool {
+            path: ".".to_string(),
+            recursive: true,
+            timeout_ms: 10_000,
+        };
+        let started = std::time::Instant::now();
+        let error = tool.call_with_context(&context).await.unwrap_err();
+        canceller.await.unwrap();
+        assert!(error.to_string().contains("Cancelled by the client"), "{}", error);
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_watch_no_changes() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let tool = WatchTool {
+            path: ".".to_string(),
+            recursive: true,
+            timeout_ms: 100,
+        };
+        let output = text(&tool.call_with_context(&context).await.unwrap());
+        assert!(output.contains("No changes observed"));
+    }
+
+    #[tokio::test]
+    async fn test_watch_invalid_timeout() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let tool = WatchTool {
+            path: ".".to_string(),
+            recursive: true,
+            timeout_ms: 0,
+        };
+        assert!(tool.call_with_context(&context).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_watch_outside_project_directory() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let tool = WatchTool {
+            path: "..".to_string(),
+            recursive: false,
+            timeout_ms: 100,
+        };
+        assert!(tool.call_with_context(&context).await.is_err());
+    }
+}
@@ -1,13 +1,17 @@
 use crate::config::tool_errors;
 use crate::context::{StatefulTool, ToolContext};
-use crate::tools::utils::{format_count, format_path, format_size, resolve_path_for_read};
+use crate::tools::utils::{
+    format_count, format_path, format_size, is_binary_sample, match_pattern_in_project,
+    resolve_path_for_read,
+};
 use async_trait::async_trait;
-use encoding_rs;
+use encoding_rs::{self, Encoding};
 use rust_mcp_schema::{
     CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
 };
 use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use tokio::fs;
 
 use chrono::{DateTime, Local};
@@ -16,14 +20,19 @@ const TOOL_NAME: &str = "wc";
 
 #[mcp_tool(
     name = "wc",
-    description = "Count lines, words, characters, bytes in text files. Max line length, multiple encodings.
-Examples: {\"path\": \"README.md\"} or {\"path\": \"stats.log\", \"output_format\": \"json\"}"
+    description = "Count lines, words, characters, bytes in text files. Max line length, multiple encodings. Binary files report bytes only.
+With pattern=true, path is a glob and output has per-file counts plus a total; matches that cannot be read are listed as skipped.
+Examples: {\"path\": \"README.md\"} or {\"path\": \"src/**/*.rs\", \"pattern\": true} or {\"path\": \"stats.log\", \"output_format\": \"json\"}"
 )]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct WcTool {
-    /// Path to the file to count (relative to project root)
+    /// Path to the file to count (relative to project root), or a glob when pattern is true
     pub path: String,
 
+    /// Treat path as a glob pattern and count every matching file (default: false)
+    #[serde(default)]
+    pub pattern: bool,
+
     /// Whether to count lines (default: true)
     #[serde(default = "default_true")]
     pub count_lines: bool,
@@ -36,7 +45,7 @@ pub struct WcTool {
     #[serde(default = "default_true")]
     pub count_chars: bool,
 
-    /// Whether to count bytes (default: false)
+    /// Whether to count bytes (default: false, always reported for patterns and binary files)
     #[serde(default)]
     pub count_bytes: bool,
 
@@ -92,9 +101,25 @@ struct WcJsonOutput {
     #[serde(skip_serializing_if = "Option::is_none")]
     max_line_length: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    binary: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     metadata: Option<FileMetadata>,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+struct WcJsonMultiOutput {
+    files: Vec<WcJsonOutput>,
+    total: WcJsonOutput,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<WcJsonError>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WcJsonError {
+    path: String,
+    error: String,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 struct FileMetadata {
     size: u64,
@@ -104,12 +129,25 @@ struct FileMetadata {
     is_binary: bool,
 }
 
+/// Counts for a single file; text counts stay zero for binary files
+#[derive(Debug, Default)]
+struct FileCounts {
+    lines: usize,
+    words: usize,
+    chars: usize,
+    bytes: u64,
+    max_line_length: usize,
+    is_binary: bool,
+}
+
 #[async_trait]
 impl StatefulTool for WcTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
         // Validate encoding
         let encoding = match self.encoding.to_lowercase().as_str() {
             "utf-8" | "utf8" => encoding_rs::UTF_8,
@@ -145,6 +183,10 @@ impl StatefulTool for WcTool {
             ))
         })?;
 
+        if self.pattern {
+            return self.count_pattern(&project_root, encoding).await;
+        }
+
         // Use the utility function to resolve path with symlink support
         let normalized_path =
             resolve_path_for_read(&self.path, &project_root, self.follow_symlinks, TOOL_NAME)?;
@@ -171,66 +213,7 @@ impl StatefulTool for WcTool {
             ))
         })?;
 
-        // Read file bytes for encoding detection
-        let file_bytes = fs::read(&normalized_path).await.map_err(|e| {
-            CallToolError::from(tool_errors::invalid_input(
-                TOOL_NAME,
-                &format!("Failed to read file: {}", e),
-            ))
-        })?;
-
-        // Check if file is binary
-        let is_binary = is_likely_binary(&file_bytes);
-        if is_binary {
-            return Err(CallToolError::from(tool_errors::invalid_input(
-                TOOL_NAME,
-                &format!(
-                    "File '{}' appears to be binary. The wc tool only works with text files.",
-                    self.path
-                ),
-            )));
-        }
-
-        // Decode file contents
-        let (contents, _encoding_used, had_errors) = encoding.decode(&file_bytes);
-        if had_errors {
-            eprintln!(
-                "Warning: Some characters could not be decoded with {} encoding",
-                self.encoding
-            );
-        }
-
-        // Get byte count
-        let byte_count = file_metadata.len();
-
-        // Perform counts
-        let line_count = if self.count_lines {
-            contents.lines().count()
-        } else {
-            0
-        };
-
-        let word_count = if self.count_words {
-            count_words(&contents)
-        } else {
-            0
-        };
-
-        let char_count = if self.count_chars {
-            contents.chars().count()
-        } else {
-            0
-        };
-
-        let max_line_len = if self.max_line_length {
-            contents
-                .lines()
-                .map(|line| line.chars().count())
-                .max()
-                .unwrap_or(0)
-        } else {
-            0
-        };
+        let counts = self.count_file(&normalized_path, encoding).await?;
 
         // Format path relative to project root
         let relative_path = normalized_path
@@ -239,58 +222,15 @@ impl StatefulTool for WcTool {
 
         // Get file metadata if requested
         let metadata = if self.include_metadata {
-            let modified = file_metadata
-                .modified()
-                .ok()
-                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                .map(|d| {
-                    let datetime = DateTime::<Local>::from(std::time::UNIX_EPOCH + d);
-                    datetime.format("%Y-%m-%d %H:%M:%S").to_string()
-                })
-                .unwrap_or_else(|| "Unknown".to_string());
-
-            Some(FileMetadata {
-                size: byte_count,
-                size_human: format_size(byte_count),
-                modified,
-                encoding: self.encoding.clone(),
-                is_binary: false,
-            })
+            Some(self.file_metadata(&file_metadata, counts.is_binary))
         } else {
             None
         };
 
         // Generate output based on format
         let output = if self.output_format == "json" {
-            let json_output = WcJsonOutput {
-                path: relative_path.display().to_string(),
-                lines: if self.count_lines {
-                    Some(line_count)
-                } else {
-                    None
-                },
-                words: if self.count_words {
-                    Some(word_count)
-                } else {
-                    None
-                },
-                characters: if self.count_chars {
-                    Some(char_count)
-                } else {
-                    None
-                },
-                bytes: if self.count_bytes {
-                    Some(byte_count)
-                } else {
-                    None
-                },
-                max_line_length: if self.max_line_length {
-                    Some(max_line_len)
-                } else {
-                    None
-                },
-                metadata,
-            };
+            let mut json_output = self.json_counts(relative_path.display().to_string(), &counts, self.count_bytes);
+            json_output.metadata = metadata;
 
             serde_json::to_string_pretty(&json_output)
                 .unwrap_or_else(|e| format!("Error serializing JSON: {}", e))
@@ -300,35 +240,38 @@ impl StatefulTool for WcTool {
             output_lines.push(format!("Word count for {}", format_path(relative_path)));
             output_lines.push("".to_string());
 
-            if self.count_lines {
+            if counts.is_binary {
+                output_lines.push("Binary file, reporting bytes only".to_string());
+            }
+            if self.count_lines && !counts.is_binary {
                 output_lines.push(format!(
                     "Lines:           {}",
-                    format_count(line_count, "line", "lines")
+                    format_count(counts.lines, "line", "lines")
                 ));
             }
-            if self.count_words {
+            if self.count_words && !counts.is_binary {
                 output_lines.push(format!(
                     "Words:           {}",
-                    format_count(word_count, "word", "words")
+                    format_count(counts.words, "word", "words")
                 ));
             }
-            if self.count_chars {
+            if self.count_chars && !counts.is_binary {
                 output_lines.push(format!(
                     "Characters:      {}",
-                    format_count(char_count, "character", "characters")
+                    format_count(counts.chars, "character", "characters")
                 ));
             }
-            if self.count_bytes {
+            if self.count_bytes || counts.is_binary {
                 output_lines.push(format!(
                     "Bytes:           {} ({})",
-                    byte_count,
-                    format_size(byte_count)
+                    counts.bytes,
+                    format_size(counts.bytes)
                 ));
             }
-            if self.max_line_length {
+            if self.max_line_length && !counts.is_binary {
                 output_lines.push(format!(
                     "Max line length: {}",
-                    format_count(max_line_len, "character", "characters")
+                    format_count(counts.max_line_length, "character", "characters")
                 ));
             }
 
@@ -353,47 +296,237 @@ impl StatefulTool for WcTool {
     }
 }
 
-fn count_words(text: &str) -> usize {
-    text.split_whitespace().count()
-}
+impl WcTool {
+    /// Count every file matching the glob in `self.path`, with per-file rows and a total
+    async fn count_pattern(
+        &self,
+        project_root: &Path,
+        encoding: &'static Encoding,
+    ) -> Result<CallToolResult, CallToolError> {
+        let current_dir = project_root.canonicalize().map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to canonicalize project root: {}", e),
+            ))
+        })?;
+
+        let matches = match_pattern_in_project(&self.path, &current_dir, TOOL_NAME)?;
+        let mut rows = Vec::new();
+        let mut failures = Vec::new();
+        let mut total = FileCounts::default();
+        for relative_path in matches {
+            // One unreadable match (e.g. a symlink out of the project) is reported
+            // on its own line instead of failing the whole batch
+            let resolved =
+                match resolve_path_for_read(&relative_path, &current_dir, self.follow_symlinks, TOOL_NAME) {
+                    Ok(resolved) => resolved,
+                    Err(e) => {
+                        failures.push((relative_path, e.to_string()));
+                        continue;
+                    }
+                };
+            if !resolved.is_file() {
+                continue;
+            }
+            let counts = match self.count_file(&resolved, encoding).await {
+                Ok(counts) => counts,
+                Err(e) => {
+                    failures.push((relative_path, e.to_string()));
+                    continue;
+                }
+            };
+            total.lines += counts.lines;
+            total.words += counts.words;
+            total.chars += counts.chars;
+            total.bytes += counts.bytes;
+            total.max_line_length = total.max_line_length.max(counts.max_line_length);
+            rows.push((relative_path, counts));
+        }
+
+        let output = if self.output_format == "json" {
+            let json_output = WcJsonMultiOutput {
+                files: rows
+                    .iter()
+                    .map(|(path, counts)| self.json_counts(path.clone(), counts, true))
+                    .collect(),
+                total: self.json_counts("total".to_string(), &total, true),
+                errors: failures
+                    .iter()
+                    .map(|(path, error)| WcJsonError {
+                        path: path.clone(),
+                        error: error.clone(),
+                    })
+                    .collect(),
+            };
+            serde_json::to_string_pretty(&json_output)
+                .unwrap_or_else(|e| format!("Error serializing JSON: {}", e))
+        } else {
+            let mut output_lines = vec![
+                format!(
+                    "Word count for {} matching {}",
+                    format_count(rows.len(), "file", "files"),
+                    format_path(Path::new(&self.path))
+                ),
+                "".to_string(),
+            ];
+
+            let mut header = String::new();
+            for (enabled, label) in [
+                (self.count_lines, "lines"),
+                (self.count_words, "words"),
+                (self.count_chars, "chars"),
+                (true, "bytes"),
+            ] {
+                if enabled {
+                    header.push_str(&format!("{:>10}", label));
+                }
+            }
+            output_lines.push(format!("{}  path", header));
+
+            for (path, counts) in &rows {
+                let suffix = if counts.is_binary { " (binary)" } else { "" };
+                output_lines.push(format!(
+                    "{}  {}{}",
+                    self.table_row(counts),
+                    path,
+                    suffix
+                ));
+            }
+            output_lines.push(format!("{}  total", self.table_row(&total)));
+
+            if !failures.is_empty() {
+                output_lines.push("".to_string());
+                output_lines.push(format!(
+                    "Skipped {}:",
+                    format_count(failures.len(), "file", "files")
+                ));
+                for (path, error) in &failures {
+                    output_lines.push(format!("  {}: {}", path, error));
+                }
+            }
+
+            output_lines.join("\n")
+        };
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::TextContent(TextContent::new(
+                output, None,
+            ))],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+
+    /// Read a file and compute the enabled counts; binary files only get a byte count
+    async fn count_file(
+        &self,
+        path: &Path,
+        encoding: &'static Encoding,
+    ) -> Result<FileCounts, CallToolError> {
+        let file_bytes = fs::read(path).await.map_err(|e| {
+            CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Failed to read file: {}", e),
+            ))
+        })?;
+
+        let mut counts = FileCounts {
+            bytes: file_bytes.len() as u64,
+            ..Default::default()
+        };
+
+        // Binary files use the same heuristic as the read tool
+        if is_binary_sample(&file_bytes) {
+            counts.is_binary = true;
+            return Ok(counts);
+        }
+
+        // Decode file contents
+        let (contents, _encoding_used, had_errors) = encoding.decode(&file_bytes);
+        if had_errors {
+            eprintln!(
+                "Warning: Some characters could not be decoded with {} encoding",
+                self.encoding
+            );
+        }
+
+        if self.count_lines {
+            counts.lines = contents.lines().count();
+        }
+        if self.count_words {
+            counts.words = count_words(&contents);
+        }
+        if self.count_chars {
+            counts.chars = contents.chars().count();
+        }
+        if self.max_line_length {
+            counts.max_line_length = contents
+                .lines()
+                .map(|line| line.chars().count())
+                .max()
+                .unwrap_or(0);
+        }
+
+        Ok(counts)
+    }
+
+    fn file_metadata(&self, file_metadata: &std::fs::Metadata, is_binary: bool) -> FileMetadata {
+        let modified = file_metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| {
+                let datetime = DateTime::<Local>::from(std::time::UNIX_EPOCH + d);
+                datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+            })
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        FileMetadata {
+            size: file_metadata.len(),
+            size_human: format_size(file_metadata.len()),
+            modified,
+            encoding: self.encoding.clone(),
+            is_binary,
+        }
+    }
 
-fn is_likely_binary(bytes: &[u8]) -> bool {
-    // Check for common binary file signatures
-    if bytes.len() >= 4 {
-        // Check for common binary signatures
-        match &bytes[0..4] {
-            // Images
-            b"\x89PNG" => return true,
-            b"\xFF\xD8\xFF\xDB" => return true, // JPEG
-            b"\xFF\xD8\xFF\xE0" => return true, // JPEG
-            b"GIF8" => return true,
-            // Archives
-            b"PK\x03\x04" => return true,   // ZIP
-            b"\x1F\x8B\x08" => return true, // GZIP
-            // Executables
-            b"\x7FELF" => return true,    // ELF
-            b"MZ\x90\x00" => return true, // PE
-            _ => {}
+    fn json_counts(&self, path: String, counts: &FileCounts, include_bytes: bool) -> WcJsonOutput {
+        let text = !counts.is_binary;
+        WcJsonOutput {
+            path,
+            lines: (self.count_lines && text).then_some(counts.lines),
+            words: (self.count_words && text).then_some(counts.words),
+            characters: (self.count_chars && text).then_some(counts.chars),
+            bytes: (include_bytes || counts.is_binary).then_some(counts.bytes),
+            max_line_length: (self.max_line_length && text).then_some(counts.max_line_length),
+            binary: counts.is_binary.then_some(true),
+            metadata: None,
         }
     }
 
-    // Check for null bytes or high proportion of non-text characters
-    let sample_size = bytes.len().min(8192);
-    let mut null_count = 0;
-    let mut non_ascii_count = 0;
-
-    for &byte in &bytes[0..sample_size] {
-        if byte == 0 {
-            null_count += 1;
-        } else if byte < 0x20 && byte != b'\t' && byte != b'\n' && byte != b'\r' {
-            non_ascii_count += 1;
-        } else if byte > 0x7E && byte < 0x80 {
-            non_ascii_count += 1;
+    fn table_row(&self, counts: &FileCounts) -> String {
+        let mut row = String::new();
+        for (enabled, value) in [
+            (self.count_lines, counts.lines),
+            (self.count_words, counts.words),
+            (self.count_chars, counts.chars),
+        ] {
+            if !enabled {
+                continue;
+            }
+            if counts.is_binary {
+                row.push_str(&format!("{:>10}", "-"));
+            } else {
+                row.push_str(&format!("{:>10}", value));
+            }
         }
+        row.push_str(&format!("{:>10}", counts.bytes));
+        row
     }
+}
 
-    // If more than 1% null bytes or 30% non-ASCII, likely binary
-    null_count > 0 || non_ascii_count > sample_size / 3
+fn count_words(text: &str) -> usize {
+    text.split_whitespace().count()
 }
 
 #[cfg(test)]
@@ -425,6 +558,7 @@ mod tests {
     fn create_wc_tool(path: &str) -> WcTool {
         WcTool {
             path: path.to_string(),
+            pattern: false,
             count_lines: true,
             count_words: true,
             count_chars: true,
@@ -445,6 +579,7 @@ mod tests {
 
         let wc_tool = WcTool {
             path: "test.txt".to_string(),
+            pattern: false,
             count_lines: true,
             count_words: true,
             count_chars: true,
@@ -1058,11 +1193,14 @@ mod tests {
 
         let wc_tool = create_wc_tool("binary.png");
 
-        let result = wc_tool.call_with_context(&context).await;
-        assert!(result.is_err());
-
-        let error = result.unwrap_err();
-        assert!(error.to_string().contains("appears to be binary"));
+        let result = wc_tool.call_with_context(&context).await.unwrap();
+        if let Some(CallToolResultContentItem::TextContent(text)) = result.content.first() {
+            assert!(text.text.contains("Binary file, reporting bytes only"));
+            assert!(text.text.contains("Bytes:           8"));
+            assert!(!text.text.contains("Lines:"));
+        } else {
+            panic!("Expected text content");
+        }
     }
 
     #[tokio::test]
@@ -1092,5 +1230,127 @@ mod tests {
             assert!(json["bytes"].is_null());
         }
     }
-}
 
+    #[tokio::test]
+    async fn test_wc_pattern_per_file_and_total() {
+        let (context, temp_dir) = setup_test_context().await;
+        fs::create_dir(temp_dir.path().join("docs")).await.unwrap();
+        create_test_file(temp_dir.path(), "docs/a.txt", "one two\nthree\n").await;
+        create_test_file(temp_dir.path(), "docs/b.txt", "four\n").await;
+        fs::write(temp_dir.path().join("docs/c.txt"), [0u8, 1, 2, 3])
+            .await
+            .unwrap();
+
+        let mut wc_tool = create_wc_tool("docs/*.txt");
+        wc_tool.pattern = true;
+        wc_tool.count_chars = false;
+
+        let result = wc_tool.call_with_context(&context).await.unwrap();
+        if let Some(CallToolResultContentItem::TextContent(text)) = result.content.first() {
+            let lines: Vec<&str> = text.text.lines().collect();
+            assert_eq!(lines[0], "Word count for 3 files matching 'docs/*.txt'");
+            assert_eq!(lines[2], "     lines     words     bytes  path");
+            assert_eq!(lines[3], "         2         3        14  docs/a.txt");
+            assert_eq!(lines[4], "         1         1         5  docs/b.txt");
+            assert_eq!(lines[5], "         -         -         4  docs/c.txt (binary)");
+            assert_eq!(lines[6], "         3         4        23  total");
+        } else {
+            panic!("Expected text content");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wc_pattern_json_output() {
+        let (context, temp_dir) = setup_test_context().await;
+        create_test_file(temp_dir.path(), "a.md", "hello world\n").await;
+        create_test_file(temp_dir.path(), "b.md", "bye\n").await;
+
+        let mut wc_tool = create_wc_tool("*.md");
+        wc_tool.pattern = true;
+        wc_tool.output_format = "json".to_string();
+
+        let result = wc_tool.call_with_context(&context).await.unwrap();
+        if let Some(CallToolResultContentItem::TextContent(text)) = result.content.first() {
+            let json: serde_json::Value = serde_json::from_str(&text.text).unwrap();
+            assert_eq!(json["files"].as_array().unwrap().len(), 2);
+            assert_eq!(json["files"][0]["path"], "a.md");
+            assert_eq!(json["total"]["lines"], 2);
+            assert_eq!(json["total"]["words"], 3);
+            assert_eq!(json["total"]["bytes"], 16);
+        } else {
+            panic!("Expected text content");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wc_pattern_outside_project() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let mut wc_tool = create_wc_tool("../*.txt");
+        wc_tool.pattern = true;
+
+        let result = wc_tool.call_with_context(&context).await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("projectfiles:wc"));
+    }
+
+    #[tokio::test]
+    async fn test_wc_pattern_no_matches() {
+        let (context, _temp_dir) = setup_test_context().await;
+
+        let mut wc_tool = create_wc_tool("*.missing");
+        wc_tool.pattern = true;
+
+        let result = wc_tool.call_with_context(&context).await.unwrap();
+        if let Some(CallToolResultContentItem::TextContent(text)) = result.content.first() {
+            let lines: Vec<&str> = text.text.lines().collect();
+            assert_eq!(lines[0], "Word count for 0 files matching '*.missing'");
+            assert!(lines.last().unwrap().ends_with("  total"));
+        } else {
+            panic!("Expected text content");
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_wc_pattern_reports_bad_match_and_counts_rest() {
+        let (context, temp_dir) = setup_test_context().await;
+        let outside = TempDir::new().unwrap();
+        std::fs::write(outside.path().join("secret.txt"), "hidden words\n").unwrap();
+        create_test_file(temp_dir.path(), "a.txt", "one two\n").await;
+        std::os::unix::fs::symlink(
+            outside.path().join("secret.txt"),
+            temp_dir.path().join("b.txt"),
+        )
+        .unwrap();
+
+        let mut wc_tool = create_wc_tool("*.txt");
+        wc_tool.pattern = true;
+        wc_tool.follow_symlinks = false;
+
+        let result = wc_tool.call_with_context(&context).await.unwrap();
+        if let Some(CallToolResultContentItem::TextContent(text)) = result.content.first() {
+            assert!(text.text.starts_with("Word count for 1 file matching '*.txt'"));
+            assert!(text.text.contains("a.txt"));
+            assert!(text.text.contains("Skipped 1 file:"));
+            assert!(text.text.contains("  b.txt: "));
+            assert!(!text.text.contains("hidden"));
+        } else {
+            panic!("Expected text content");
+        }
+
+        let mut wc_tool = create_wc_tool("*.txt");
+        wc_tool.pattern = true;
+        wc_tool.follow_symlinks = false;
+        wc_tool.output_format = "json".to_string();
+        let result = wc_tool.call_with_context(&context).await.unwrap();
+        if let Some(CallToolResultContentItem::TextContent(text)) = result.content.first() {
+            let json: serde_json::Value = serde_json::from_str(&text.text).unwrap();
+            assert_eq!(json["files"].as_array().unwrap().len(), 1);
+            assert_eq!(json["total"]["words"], 2);
+            assert_eq!(json["errors"][0]["path"], "b.txt");
+        } else {
+            panic!("Expected text content");
+        }
+    }
+}
@@ -1,7 +1,11 @@
 use crate::context::{StatefulTool, ToolContext};
 use crate::config::tool_errors;
-use crate::tools::utils::{format_size, format_path, resolve_path_for_read};
+use crate::tools::utils::{
+    LineEnding, TextStyle, convert_line_endings, create_new_in_project, format_size, format_path,
+    open_for_write_in_project, resolve_path_for_read, resolve_path_for_write,
+};
 use crate::theme::DiffTheme;
+use crate::tools::read::{SUPPORTED_ENCODINGS, detect_encoding, explicit_encoding};
 use async_trait::async_trait;
 use rust_mcp_schema::{
     CallToolResult, CallToolResultContentItem, TextContent, schema_utils::CallToolError,
@@ -12,7 +16,7 @@ use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
-use encoding_rs;
+use encoding_rs::{self, Encoding};
 use similar::{ChangeTag, TextDiff};
 use chrono::Utc;
 
@@ -27,11 +31,18 @@ fn default_follow_symlinks() -> bool {
     true
 }
 
+fn default_line_ending() -> String {
+    "preserve".to_string()
+}
+
 #[mcp_tool(name = "write", description = "Write or append content to files. Supports backup, diff preview, and safety checks.
 
 Examples:
 - {\"path\": \"config.json\", \"content\": \"{...}\"}
-- {\"path\": \"log.txt\", \"content\": \"entry\", \"append\": true}")]
+- {\"path\": \"log.txt\", \"content\": \"entry\", \"append\": true}
+- {\"path\": \"config.json\", \"content\": \"{...}\", \"dry_run\": true} (returns the diff without writing)
+- {\"path\": \"legacy.txt\", \"content\": \"...\", \"encoding\": \"auto\"} (keeps the file's existing encoding)
+- {\"path\": \"src/new/module.rs\", \"content\": \"...\", \"create_dirs\": true, \"create_only\": true}")]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct WriteTool {
     /// Path to the file to write (relative to project root)
@@ -45,7 +56,8 @@ pub struct WriteTool {
     #[serde(default)]
     pub backup: bool,
     /// Text encoding to use when writing the file (default: "utf-8")
-    /// Supported: "utf-8", "ascii", "latin1", "utf-16", "utf-16le", "utf-16be"
+    /// Supported: "utf-8", "ascii", "latin1", "utf-16", "utf-16le", "utf-16be", or "auto" to keep
+    /// an existing file's encoding (detected as read does) and BOM. New files get UTF-8
     #[serde(default = "default_encoding")]
     pub encoding: String,
     /// Follow symlinks when writing files (default: true)
@@ -54,7 +66,7 @@ pub struct WriteTool {
     /// Show a diff of what will be changed when overwriting (default: false)
     #[serde(default)]
     pub show_diff: bool,
-    /// Perform a dry run - preview the operation without writing (default: false)
+    /// Perform a dry run - preview the operation and its diff without writing (default: false)
     #[serde(default)]
     pub dry_run: bool,
     /// Force write even if file exceeds size limits (default: false)
@@ -63,17 +75,53 @@ pub struct WriteTool {
     /// Include detailed metadata in the response (default: false)
     #[serde(default)]
     pub include_metadata: bool,
+    /// Create missing parent directories inside the project, like mkdir -p (default: false)
+    #[serde(default)]
+    pub create_dirs: bool,
+    /// Only create a new file; fail instead of overwriting if the path already exists (default: false)
+    #[serde(default)]
+    pub create_only: bool,
+    /// Line endings to write: "preserve" (default) keeps an existing file's dominant line ending
+    /// and final-newline presence, "lf" or "crlf" convert the content
+    #[serde(default = "default_line_ending")]
+    pub line_ending: String,
 }
 
 #[async_trait]
 impl StatefulTool for WriteTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.path = context.resolve_in_working_dir(&self.path);
+
+        context.ensure_writable(TOOL_NAME)?;
+
         let project_root = context.get_project_root()
             .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to get project root: {}", e))))?;
         
+        if self.create_only && self.append {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                "create_only cannot be combined with append"
+            )));
+        }
+        
+        let auto_encoding = self.encoding.eq_ignore_ascii_case("auto");
+        if !auto_encoding && explicit_encoding(&self.encoding).is_none() {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Unsupported encoding: {}. Supported: {}", self.encoding, SUPPORTED_ENCODINGS)
+            )));
+        }
+        
+        if !matches!(self.line_ending.as_str(), "preserve" | "lf" | "crlf") {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("Invalid line_ending '{}'. Must be 'preserve', 'lf', or 'crlf'", self.line_ending)
+            )));
+        }
+        
         // Use the same path resolution as read tool for consistency
         let canonical_path = if self.path.is_empty() {
             return Err(CallToolError::from(tool_errors::invalid_input(
@@ -108,9 +156,17 @@ impl StatefulTool for WriteTool {
                         }
                         
                         canonical_parent.join(absolute_path.file_name().unwrap())
+                    } else if self.create_dirs {
+                        // Containment is checked from the deepest existing ancestor down
+                        resolve_path_for_write(&self.path, &project_root, TOOL_NAME)?
                     } else {
-                        // Parent doesn't exist, we'll create it
-                        absolute_path
+                        return Err(CallToolError::from(tool_errors::file_not_found(
+                            TOOL_NAME,
+                            &format!(
+                                "Parent directory {} not found. Set create_dirs=true to create it",
+                                format_path(Path::new(&self.path).parent().unwrap_or(Path::new(".")))
+                            )
+                        )));
                     }
                 } else {
                     return Err(CallToolError::from(tool_errors::invalid_input(
@@ -141,6 +197,12 @@ impl StatefulTool for WriteTool {
 
         // Collect metadata about the operation
         let file_existed = canonical_path.exists();
+        if file_existed && self.create_only {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                TOOL_NAME,
+                &format!("'{}' already exists and create_only is set", self.path)
+            )));
+        }
         let previous_size = if file_existed {
             fs::metadata(&canonical_path).await.ok().map(|m| m.len())
         } else {
@@ -158,15 +220,19 @@ impl StatefulTool for WriteTool {
             )));
         }
         
-        // Read existing content if needed for diff or safety check
-        let existing_content = if file_existed && (self.show_diff || self.dry_run) && !self.append {
-            match fs::read_to_string(&canonical_path).await {
-                Ok(content) => Some(content),
-                Err(_) => None, // File might be binary or unreadable
-            }
+        // Read existing content for the diff preview and to keep its line-ending style
+        let existing_bytes = if file_existed {
+            fs::read(&canonical_path).await.ok()
         } else {
             None
         };
+        let (encoding, had_bom) = target_encoding(&self.encoding, existing_bytes.as_deref());
+        let existing_content = existing_bytes.as_deref().and_then(|bytes| {
+            let bytes = if had_bom { &bytes[bom(encoding).len()..] } else { bytes };
+            let (content, had_errors) = encoding.decode_without_bom_handling(bytes);
+            // The file might be binary or in another encoding
+            (!had_errors).then(|| content.into_owned())
+        });
         
         let read_files = context.get_custom_state::<HashSet<PathBuf>>().await
             .unwrap_or_else(|| std::sync::Arc::new(HashSet::new()));
@@ -178,11 +244,31 @@ impl StatefulTool for WriteTool {
             )));
         }
 
-        if let Some(parent) = canonical_path.parent() {
-            if !parent.exists() && !self.dry_run {
-                fs::create_dir_all(parent)
-                    .await
-                    .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to create parent directories: {}", e))))?;
+        // Missing parents, outermost first; a dry run only reports them
+        let mut created_dirs: Vec<PathBuf> = canonical_path.parent()
+            .map(|parent| parent.ancestors().take_while(|dir| !dir.exists()).map(Path::to_path_buf).collect())
+            .unwrap_or_default();
+        created_dirs.reverse();
+        if !self.dry_run {
+            for dir in &created_dirs {
+                match fs::create_dir(dir).await {
+                    Ok(()) => {}
+                    // Another process may have created it in the meantime
+                    Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && dir.is_dir() => {}
+                    Err(e) => {
+                        return Err(CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to create parent directories: {}", e))));
+                    }
+                }
+                // Re-check each new component so a symlink swapped in along the way is refused
+                let resolved = dir.canonicalize()
+                    .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to resolve created directory: {}", e))))?;
+                if !resolved.starts_with(&project_root) {
+                    return Err(CallToolError::from(tool_errors::access_denied(
+                        TOOL_NAME,
+                        &self.path,
+                        "Path is outside the project directory"
+                    )));
+                }
             }
         }
 
@@ -209,37 +295,49 @@ impl StatefulTool for WriteTool {
             backup_created = true;
         }
 
-        // Encode content
-        let encoded_bytes = self.encode_content()?;
+        let content = match self.line_ending.as_str() {
+            "lf" => convert_line_endings(&self.content, LineEnding::Lf),
+            "crlf" => convert_line_endings(&self.content, LineEnding::Crlf),
+            _ => match existing_content.as_deref().and_then(TextStyle::detect) {
+                // Appended text only adopts the file's line ending
+                Some(style) if self.append => match style.line_ending {
+                    Some(ending) => convert_line_endings(&self.content, ending),
+                    None => self.content.clone(),
+                },
+                Some(style) => style.apply(&self.content),
+                None => self.content.clone(),
+            },
+        };
+
+        // Encode content; a BOM the file started with is kept, but never repeated by an append
+        let mut encoded_bytes = if had_bom && !self.append { bom(encoding).to_vec() } else { Vec::new() };
+        encoded_bytes.extend(encode_text(&content, encoding, &self.encoding, TOOL_NAME)?);
         
         // Perform write operation (unless dry run)
         if !self.dry_run {
-            if self.append {
-                use tokio::fs::OpenOptions;
-                
-                let mut file = OpenOptions::new()
-                    .create(true)
-                    .append(true)
-                    .open(&canonical_path)
-                    .await
-                    .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to open file for appending: {}", e))))?;
-                
-                file.write_all(&encoded_bytes)
-                    .await
-                    .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to append to file: {}", e))))?;
+            // Re-checks containment on the opened file so a symlink swapped in since validation is refused
+            // With create_only the file is opened exclusively, so one created since the check above is not clobbered
+            let (file, _) = if self.create_only {
+                create_new_in_project(&canonical_path, &project_root, TOOL_NAME)?
             } else {
-                fs::write(&canonical_path, &encoded_bytes)
-                    .await
-                    .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to write file: {}", e))))?;
-            }
+                open_for_write_in_project(&canonical_path, &project_root, self.append, TOOL_NAME)?
+            };
+            let mut file = fs::File::from_std(file);
+            file.write_all(&encoded_bytes)
+                .await
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to write file: {}", e))))?;
+            file.flush()
+                .await
+                .map_err(|e| CallToolError::from(tool_errors::invalid_input(TOOL_NAME, &format!("Failed to write file: {}", e))))?;
             
             let mut read_files_clone = (*read_files).clone();
             read_files_clone.insert(canonical_path.clone());
             context.set_custom_state(read_files_clone).await;
+            context.record_modified(&canonical_path).await;
         }
 
         // Calculate content size
-        let content_size = self.content.len() as u64;
+        let content_size = encoded_bytes.len() as u64;
         let size_str = format_size(content_size);
         
         // Format the path relative to project root
@@ -266,13 +364,27 @@ impl StatefulTool for WriteTool {
             message.push_str(" (backup created)");
         }
         
+        let created_dir_names: Vec<String> = created_dirs.iter()
+            .map(|dir| dir.strip_prefix(&project_root).unwrap_or(dir).display().to_string())
+            .collect();
+        if !created_dir_names.is_empty() {
+            let listed: Vec<String> = created_dir_names.iter().map(|dir| format_path(Path::new(dir))).collect();
+            message.push_str(&format!(
+                " ({} {} {})",
+                if self.dry_run { "would create" } else { "created" },
+                if listed.len() == 1 { "directory" } else { "directories" },
+                listed.join(", ")
+            ));
+        }
+        
         response_parts.push(message);
         
-        // Show diff if requested
-        if self.show_diff && existing_content.is_some() && !self.append {
+        // Show diff if requested; a dry run always previews it, with every line of a new file added
+        let original = if file_existed { existing_content.as_deref() } else { Some("") };
+        if (self.show_diff || self.dry_run) && !self.append && let Some(original) = original {
             let diff = generate_colored_diff(
-                existing_content.as_ref().unwrap(),
-                &self.content,
+                original,
+                &content,
                 &relative_path.display().to_string()
             );
             
@@ -293,12 +405,17 @@ impl StatefulTool for WriteTool {
                 path: relative_path.display().to_string(),
                 size_written: content_size,
                 size_human: size_str.clone(),
-                encoding_used: self.encoding.clone(),
+                encoding_used: if auto_encoding {
+                    format!("auto ({})", encoding.name())
+                } else {
+                    self.encoding.clone()
+                },
                 backup_created,
                 backup_path: backup_path_str,
                 timestamp: Utc::now().to_rfc3339(),
                 file_existed,
                 previous_size,
+                created_dirs: created_dir_names,
             };
             
             response_parts.push(format!("\n{}", serde_json::to_string_pretty(&metadata)
@@ -316,6 +433,87 @@ impl StatefulTool for WriteTool {
     }
 }
 
+/// Encoding to write with and whether the existing file starts with its BOM. "auto" keeps the
+/// existing file's encoding, detected as read does, and falls back to UTF-8 for new files
+fn target_encoding(name: &str, existing: Option<&[u8]>) -> (&'static Encoding, bool) {
+    let existing = existing.unwrap_or_default();
+    if let Some(encoding) = explicit_encoding(name) {
+        let had_bom = !bom(encoding).is_empty() && existing.starts_with(bom(encoding));
+        return (encoding, had_bom);
+    }
+    match Encoding::for_bom(existing) {
+        Some((encoding, _)) => (encoding, true),
+        None if existing.is_empty() => (encoding_rs::UTF_8, false),
+        None => (detect_encoding(existing).encoding, false),
+    }
+}
+
+/// Byte order mark of a Unicode encoding; empty for the rest
+fn bom(encoding: &'static Encoding) -> &'static [u8] {
+    if encoding == encoding_rs::UTF_8 {
+        b"\xEF\xBB\xBF"
+    } else if encoding == encoding_rs::UTF_16LE {
+        b"\xFF\xFE"
+    } else if encoding == encoding_rs::UTF_16BE {
+        b"\xFE\xFF"
+    } else {
+        b""
+    }
+}
+
+/// Read a file as text in the encoding it is stored in, detected as "auto" does. Also returns
+/// the encoding and whether the file had a BOM, so in-place edits (jq, yq, tomlq) can write it
+/// back the same way with [`encode_like`]
+pub(super) fn read_text_file(path: &Path) -> std::io::Result<(String, &'static Encoding, bool)> {
+    let bytes = std::fs::read(path)?;
+    let (encoding, had_bom) = target_encoding("auto", Some(&bytes));
+    let body = if had_bom { &bytes[bom(encoding).len()..] } else { &bytes[..] };
+    let (content, had_errors) = encoding.decode_without_bom_handling(body);
+    if had_errors {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("File is not valid {}", encoding.name()),
+        ));
+    }
+    Ok((content.into_owned(), encoding, had_bom))
+}
+
+/// Encode `content` to replace a file read with [`read_text_file`], keeping its BOM
+pub(super) fn encode_like(content: &str, encoding: &'static Encoding, had_bom: bool, tool_name: &str) -> Result<Vec<u8>, CallToolError> {
+    let mut bytes = if had_bom { bom(encoding).to_vec() } else { Vec::new() };
+    bytes.extend(encode_text(content, encoding, encoding.name(), tool_name)?);
+    Ok(bytes)
+}
+
+/// Encode `content`, refusing characters the encoding cannot represent instead of
+/// substituting them. `name` is the requested encoding, for "ascii" and error messages
+fn encode_text(content: &str, encoding: &'static Encoding, name: &str, tool_name: &str) -> Result<Vec<u8>, CallToolError> {
+    // encoding_rs only encodes UTF-16 as UTF-8, per the WHATWG standard, so do it by hand
+    if encoding == encoding_rs::UTF_16LE {
+        return Ok(content.encode_utf16().flat_map(u16::to_le_bytes).collect());
+    }
+    if encoding == encoding_rs::UTF_16BE {
+        return Ok(content.encode_utf16().flat_map(u16::to_be_bytes).collect());
+    }
+
+    // "ascii" shares latin1's encoder, so hold it to ASCII separately
+    let (encoded, _, had_errors) = encoding.encode(content);
+    let unencodable = if name.eq_ignore_ascii_case("ascii") {
+        content.chars().find(|c| !c.is_ascii())
+    } else if had_errors {
+        content.chars().find(|c| encoding.encode(c.encode_utf8(&mut [0; 4])).2)
+    } else {
+        None
+    };
+    if let Some(c) = unencodable {
+        return Err(CallToolError::from(tool_errors::invalid_input(
+            tool_name,
+            &format!("Content contains '{}' (U+{:04X}), which cannot be encoded as {}", c, c as u32, name)
+        )));
+    }
+    Ok(encoded.into_owned())
+}
+
 /// Generate a colored unified diff between two strings
 fn generate_colored_diff(original: &str, new_content: &str, file_path: &str) -> String {
     let diff = TextDiff::from_lines(original, new_content);
@@ -398,6 +596,7 @@ struct WriteMetadata {
     timestamp: String,
     file_existed: bool,
     previous_size: Option<u64>,
+    created_dirs: Vec<String>,
 }
 
 impl WriteTool {
@@ -405,26 +604,6 @@ impl WriteTool {
         let context = ToolContext::new();
         self.call_with_context(&context).await
     }
-
-    fn encode_content(&self) -> Result<Vec<u8>, CallToolError> {
-        let encoding = match self.encoding.to_lowercase().as_str() {
-            "utf-8" | "utf8" => encoding_rs::UTF_8,
-            "ascii" => encoding_rs::WINDOWS_1252, // ASCII is a subset of Windows-1252
-            "latin1" | "iso-8859-1" => encoding_rs::WINDOWS_1252,
-            "utf-16" => encoding_rs::UTF_16LE, // Default to little-endian
-            "utf-16le" => encoding_rs::UTF_16LE,
-            "utf-16be" => encoding_rs::UTF_16BE,
-            _ => encoding_rs::UTF_8, // Default fallback
-        };
-
-        let (encoded, _encoding_used, had_errors) = encoding.encode(&self.content);
-        
-        if had_errors {
-            eprintln!("Warning: Some characters could not be encoded with {} encoding", self.encoding);
-        }
-        
-        Ok(encoded.into_owned())
-    }
 }
 
 #[cfg(test)]
@@ -455,6 +634,9 @@ mod tests {
             dry_run: false,
             force: false,
             include_metadata: false,
+            create_dirs: false,
+            create_only: false,
+            line_ending: "preserve".to_string(),
         }
     }
     
@@ -493,7 +675,107 @@ mod tests {
         let content = fs::read_to_string(&file_path).await.unwrap();
         assert_eq!(content, "Nested content");
     }
-    
+
+    #[tokio::test]
+    async fn test_write_create_dirs() {
+        let (context, _temp_dir) = setup_test_context().await;
+        let project_root = context.get_project_root().unwrap();
+
+        // Missing parents are an error unless create_dirs is set
+        let write_tool = create_test_write_tool("src/new/module.rs", "pub fn f() {}\n");
+        let error = write_tool.call_with_context(&context).await.unwrap_err();
+        assert!(format!("{:?}", error).contains("create_dirs=true"));
+        assert!(!project_root.join("src").exists());
+
+        let mut write_tool = create_test_write_tool("src/new/module.rs", "pub fn f() {}\n");
+        write_tool.create_dirs = true;
+        write_tool.include_metadata = true;
+        let result = write_tool.call_with_context(&context).await.unwrap();
+        let CallToolResultContentItem::TextContent(text) = &result.content[0] else {
+            panic!("Expected text content");
+        };
+        assert!(text.text.contains("(created directories 'src', 'src/new')"), "unexpected output: {}", text.text);
+        assert!(text.text.contains("\"created_dirs\""));
+        let content = fs::read_to_string(project_root.join("src/new/module.rs")).await.unwrap();
+        assert_eq!(content, "pub fn f() {}\n");
+    }
+
+    #[tokio::test]
+    async fn test_write_create_dirs_dry_run_and_outside_project() {
+        let (context, _temp_dir) = setup_test_context().await;
+        let project_root = context.get_project_root().unwrap();
+
+        let mut write_tool = create_test_write_tool("docs/guide.md", "# Guide\n");
+        write_tool.create_dirs = true;
+        write_tool.dry_run = true;
+        let result = write_tool.call_with_context(&context).await.unwrap();
+        let CallToolResultContentItem::TextContent(text) = &result.content[0] else {
+            panic!("Expected text content");
+        };
+        assert!(text.text.contains("(would create directory 'docs')"), "unexpected output: {}", text.text);
+        assert!(!project_root.join("docs").exists());
+
+        let mut write_tool = create_test_write_tool("new/../../outside/file.txt", "escape");
+        write_tool.create_dirs = true;
+        assert!(write_tool.call_with_context(&context).await.is_err());
+        assert!(!project_root.join("new").exists());
+        assert!(!project_root.parent().unwrap().join("outside").exists());
+    }
+
+    #[tokio::test]
+    async fn test_write_create_only() {
+        let (context, _temp_dir) = setup_test_context().await;
+        let project_root = context.get_project_root().unwrap();
+
+        let mut write_tool = create_test_write_tool("scaffold.rs", "fn main() {}\n");
+        write_tool.create_only = true;
+        write_tool.call_with_context(&context).await.unwrap();
+        assert_eq!(fs::read_to_string(project_root.join("scaffold.rs")).await.unwrap(), "fn main() {}\n");
+
+        // The file was just written, so only create_only stands in the way of overwriting it
+        let mut write_tool = create_test_write_tool("scaffold.rs", "clobbered");
+        write_tool.create_only = true;
+        let error = write_tool.call_with_context(&context).await.unwrap_err();
+        assert!(format!("{:?}", error).contains("already exists"));
+        assert_eq!(fs::read_to_string(project_root.join("scaffold.rs")).await.unwrap(), "fn main() {}\n");
+
+        let mut write_tool = create_test_write_tool("log.txt", "entry");
+        write_tool.create_only = true;
+        write_tool.append = true;
+        assert!(write_tool.call_with_context(&context).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_write_preserves_crlf_line_endings() {
+        let (context, _temp_dir) = setup_test_context().await;
+        let project_root = context.get_project_root().unwrap();
+        let file_path = project_root.join("windows.txt");
+        fs::write(&file_path, "first\r\nsecond\r\n").await.unwrap();
+        let mut read_files = HashSet::new();
+        read_files.insert(file_path.clone());
+        context.set_custom_state(read_files).await;
+
+        // LF content without a final newline still comes out as CRLF with one
+        let write_tool = create_test_write_tool("windows.txt", "one\ntwo\nthree");
+        write_tool.call_with_context(&context).await.unwrap();
+        assert_eq!(fs::read_to_string(&file_path).await.unwrap(), "one\r\ntwo\r\nthree\r\n");
+
+        let mut append_tool = create_test_write_tool("windows.txt", "four\n");
+        append_tool.append = true;
+        append_tool.call_with_context(&context).await.unwrap();
+        assert_eq!(fs::read_to_string(&file_path).await.unwrap(), "one\r\ntwo\r\nthree\r\nfour\r\n");
+
+        // An explicit line_ending overrides the file's style
+        let mut write_tool = create_test_write_tool("windows.txt", "one\r\ntwo\r\n");
+        write_tool.line_ending = "lf".to_string();
+        write_tool.call_with_context(&context).await.unwrap();
+        assert_eq!(fs::read_to_string(&file_path).await.unwrap(), "one\ntwo\n");
+
+        let mut write_tool = create_test_write_tool("windows.txt", "one");
+        write_tool.line_ending = "cr".to_string();
+        assert!(write_tool.call_with_context(&context).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_write_append_mode() {
         let (context, _temp_dir) = setup_test_context().await;
@@ -605,6 +887,67 @@ mod tests {
         assert!(result.is_ok());
     }
     
+    #[tokio::test]
+    async fn test_write_encodes_utf16_and_latin1() {
+        let (context, _temp_dir) = setup_test_context().await;
+        let project_root = context.get_project_root().unwrap();
+        
+        let mut write_tool = create_test_write_tool("wide.txt", "h\u{e9}\n");
+        write_tool.encoding = "utf-16le".to_string();
+        write_tool.call_with_context(&context).await.unwrap();
+        assert_eq!(fs::read(project_root.join("wide.txt")).await.unwrap(), b"h\0\xE9\0\n\0");
+        
+        let mut write_tool = create_test_write_tool("legacy.txt", "caf\u{e9}\n");
+        write_tool.encoding = "latin1".to_string();
+        write_tool.call_with_context(&context).await.unwrap();
+        assert_eq!(fs::read(project_root.join("legacy.txt")).await.unwrap(), b"caf\xE9\n");
+        
+        // Characters the encoding lacks are refused rather than mangled
+        for (encoding, content) in [("latin1", "\u{65e5}"), ("ascii", "caf\u{e9}"), ("ebcdic", "text")] {
+            let mut write_tool = create_test_write_tool("refused.txt", content);
+            write_tool.encoding = encoding.to_string();
+            assert!(write_tool.call_with_context(&context).await.is_err(), "{}", encoding);
+        }
+        assert!(!project_root.join("refused.txt").exists());
+    }
+    
+    #[tokio::test]
+    async fn test_write_auto_encoding_keeps_existing_encoding() {
+        let (context, _temp_dir) = setup_test_context().await;
+        let project_root = context.get_project_root().unwrap();
+        let file_path = project_root.join("notes.txt");
+        let utf16be = |text: &str| -> Vec<u8> {
+            let mut bytes = vec![0xFE, 0xFF];
+            bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+            bytes
+        };
+        fs::write(&file_path, utf16be("old\r\nline\r\n")).await.unwrap();
+        context.set_custom_state(HashSet::from([file_path.clone()])).await;
+        
+        // The BOM, byte order and CRLF line endings all survive, and the diff sees the decoded text
+        let mut write_tool = create_test_write_tool("notes.txt", "new\nline\n");
+        write_tool.encoding = "auto".to_string();
+        write_tool.show_diff = true;
+        let result = write_tool.call_with_context(&context).await.unwrap();
+        let CallToolResultContentItem::TextContent(text) = &result.content[0] else {
+            panic!("Expected text content");
+        };
+        assert!(text.text.contains("-old\r\n+new\r\n"), "{}", text.text);
+        assert_eq!(fs::read(&file_path).await.unwrap(), utf16be("new\r\nline\r\n"));
+        
+        let mut append_tool = create_test_write_tool("notes.txt", "more\n");
+        append_tool.encoding = "auto".to_string();
+        append_tool.append = true;
+        append_tool.call_with_context(&context).await.unwrap();
+        assert_eq!(fs::read(&file_path).await.unwrap(), utf16be("new\r\nline\r\nmore\r\n"));
+        
+        // New files default to UTF-8
+        let mut write_tool = create_test_write_tool("fresh.txt", "caf\u{e9}");
+        write_tool.encoding = "auto".to_string();
+        write_tool.call_with_context(&context).await.unwrap();
+        assert_eq!(fs::read_to_string(project_root.join("fresh.txt")).await.unwrap(), "caf\u{e9}");
+    }
+    
     #[tokio::test]
     async fn test_write_empty_content() {
         let (context, _temp_dir) = setup_test_context().await;
@@ -671,6 +1014,30 @@ mod tests {
         }
     }
     
+    #[tokio::test]
+    async fn test_write_dry_run_previews_diff() {
+        let (context, _temp_dir) = setup_test_context().await;
+        let project_root = context.get_project_root().unwrap();
+        let text = |result: CallToolResult| match &result.content[0] {
+            CallToolResultContentItem::TextContent(text) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        };
+
+        // A new file shows up as all additions
+        let mut write_tool = create_test_write_tool("new.txt", "one\ntwo\n");
+        write_tool.dry_run = true;
+        let output = text(write_tool.call_with_context(&context).await.unwrap());
+        assert!(output.contains("@@ -0,0 +1,2 @@\n+one\n+two\n"), "unexpected output: {}", output);
+        assert!(!project_root.join("new.txt").exists());
+
+        std::fs::write(project_root.join("existing.txt"), "keep\nold\n").unwrap();
+        let mut write_tool = create_test_write_tool("existing.txt", "keep\nnew\n");
+        write_tool.dry_run = true;
+        let output = text(write_tool.call_with_context(&context).await.unwrap());
+        assert!(output.contains(" keep\n-old\n+new\n"), "unexpected output: {}", output);
+        assert_eq!(fs::read_to_string(project_root.join("existing.txt")).await.unwrap(), "keep\nold\n");
+    }
+    
     #[tokio::test]
     async fn test_write_show_diff() {
         let (context, _temp_dir) = setup_test_context().await;
@@ -745,5 +1112,15 @@ mod tests {
         
         let result = write_tool.call_with_context(&context).await;
         assert!(result.is_ok());
+    }    
+    #[tokio::test]
+    async fn test_write_rejected_in_read_only_mode() {
+        let (mut context, _temp_dir) = setup_test_context().await;
+        context.read_only = true;
+        
+        let write_tool = create_test_write_tool("blocked.txt", "content");
+        let error = write_tool.call_with_context(&context).await.unwrap_err();
+        assert!(error.to_string().contains("read-only mode"));
+        assert!(!context.get_project_root().unwrap().join("blocked.txt").exists());
     }
 }
\ No newline at end of file
@@ -0,0 +1,316 @@
+use crate::context::{StatefulTool, ToolContext};
+use crate::config::tool_errors;
+use crate::tools::query_engine::{QueryEngine, QueryError as QueryEngineError};
+use crate::tools::query_engine::parser::QueryParser;
+use crate::tools::utils::resolve_path_for_read;
+use async_trait::async_trait;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use rust_mcp_schema::{
+    CallToolResult, CallToolResultContentItem, schema_utils::CallToolError,
+};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum XmlQueryError {
+    #[error("Error: projectfiles:xq - File not found: {0}")]
+    FileNotFound(String),
+
+    #[error("Error: projectfiles:xq - Invalid XML in file {file}: {error}")]
+    InvalidXml { file: String, error: String },
+
+    #[error("Error: projectfiles:xq - Invalid query syntax: {0}")]
+    InvalidQuery(String),
+
+    #[error("Error: projectfiles:xq - IO error: {0}")]
+    IoError(String),
+
+    #[error("Error: projectfiles:xq - {0}")]
+    QueryEngine(#[from] QueryEngineError),
+}
+
+#[mcp_tool(name = "xq", description = "Query XML files with jq syntax. Read-only.
+The document becomes {\"<root>\": ...}. Attributes are keys prefixed with \"@\" (.server.@port), text is under \"#text\" (.server.#text), and an element with only text is just its string. Repeated child elements become an array. All values are strings; comments and processing instructions are dropped.
+Use operation=\"validate\" to check that the file parses without running a query.
+Examples: \".project.version\" or \".project.dependencies.dependency[] | .artifactId\" or \".config.server.@port\"")]
+#[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct XmlQueryTool {
+    /// Path to the XML file (relative to project root)
+    pub file_path: String,
+    /// Query string using jq-style syntax (ignored for validate)
+    #[serde(default)]
+    pub query: String,
+    /// Operation type: "read" (default) or "validate" (report whether the file parses)
+    #[serde(default = "default_operation")]
+    pub operation: String,
+    /// Output format: "json" (default) or "raw"
+    #[serde(default = "default_output_format")]
+    pub output_format: String,
+    /// Follow symlinks when reading files (default: true)
+    #[serde(default = "default_follow_symlinks")]
+    pub follow_symlinks: bool,
+}
+
+fn default_operation() -> String {
+    "read".to_string()
+}
+
+fn default_output_format() -> String {
+    "json".to_string()
+}
+
+fn default_follow_symlinks() -> bool {
+    true
+}
+
+/// An XML syntax error and the byte offset it was detected at
+#[derive(Debug)]
+struct XmlSyntaxError {
+    message: String,
+    offset: usize,
+}
+
+/// An element whose end tag has not been reached yet
+struct OpenElement {
+    name: String,
+    fields: Map<String, Value>,
+    text: String,
+}
+
+impl OpenElement {
+    fn new(start: &BytesStart, reader: &Reader<&[u8]>) -> Result<Self, XmlSyntaxError> {
+        let syntax_error = |message: String| XmlSyntaxError { message, offset: reader.buffer_position() as usize };
+        let name = String::from_utf8_lossy(start.name().as_ref()).into_owned();
+        let mut fields = Map::new();
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(|e| syntax_error(e.to_string()))?;
+            let value = attribute.unescape_value().map_err(|e| syntax_error(e.to_string()))?;
+            fields.insert(
+                format!("@{}", String::from_utf8_lossy(attribute.key.as_ref())),
+                Value::String(value.into_owned()),
+            );
+        }
+        Ok(Self { name, fields, text: String::new() })
+    }
+
+    /// Text-only elements collapse to a string and empty ones to null
+    fn into_value(mut self) -> Value {
+        if self.fields.is_empty() {
+            return if self.text.is_empty() { Value::Null } else { Value::String(self.text) };
+        }
+        if !self.text.is_empty() {
+            self.fields.insert("#text".to_string(), Value::String(self.text));
+        }
+        Value::Object(self.fields)
+    }
+}
+
+/// Add a child element, turning repeated names into an array in document order
+fn insert_child(fields: &mut Map<String, Value>, name: String, value: Value) {
+    match fields.get_mut(&name) {
+        Some(Value::Array(items)) => items.push(value),
+        Some(existing) => {
+            let first = existing.take();
+            *existing = Value::Array(vec![first, value]);
+        }
+        None => {
+            fields.insert(name, value);
+        }
+    }
+}
+
+/// Convert an XML document into JSON following the conventions in the tool description
+fn xml_to_json(content: &str) -> Result<Value, XmlSyntaxError> {
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().trim_text(true);
+
+    let mut stack: Vec<OpenElement> = Vec::new();
+    let mut root: Option<(String, Value)> = None;
+    loop {
+        let event = reader.read_event().map_err(|e| XmlSyntaxError {
+            message: e.to_string(),
+            offset: reader.error_position() as usize,
+        })?;
+        let syntax_error = |message: String| XmlSyntaxError { message, offset: reader.buffer_position() as usize };
+
+        let completed = match event {
+            Event::Start(start) => {
+                stack.push(OpenElement::new(&start, &reader)?);
+                None
+            }
+            Event::Empty(start) => Some(OpenElement::new(&start, &reader)?),
+            Event::End(_) => stack.pop(),
+            Event::Text(text) => {
+                let text = text.unescape().map_err(|e| syntax_error(e.to_string()))?;
+                match stack.last_mut() {
+                    Some(element) => element.text.push_str(&text),
+                    None => return Err(syntax_error("text outside of the root element".to_string())),
+                }
+                None
+            }
+            Event::CData(cdata) => {
+                let text = cdata.decode().map_err(|e| syntax_error(e.to_string()))?;
+                match stack.last_mut() {
+                    Some(element) => element.text.push_str(&text),
+                    None => return Err(syntax_error("CDATA outside of the root element".to_string())),
+                }
+                None
+            }
+            Event::Eof => break,
+            Event::Comment(_) | Event::Decl(_) | Event::PI(_) | Event::DocType(_) => None,
+        };
+
+        let Some(element) = completed else {
+            continue;
+        };
+        let name = element.name.clone();
+        let value = element.into_value();
+        match stack.last_mut() {
+            Some(parent) => insert_child(&mut parent.fields, name, value),
+            None if root.is_none() => root = Some((name, value)),
+            None => return Err(syntax_error(format!("unexpected second root element <{}>", name))),
+        }
+    }
+
+    if let Some(unclosed) = stack.last() {
+        return Err(XmlSyntaxError {
+            message: format!("unclosed element <{}>", unclosed.name),
+            offset: content.len(),
+        });
+    }
+    let (name, value) = root.ok_or_else(|| XmlSyntaxError {
+        message: "document has no root element".to_string(),
+        offset: content.len(),
+    })?;
+    let mut document = Map::new();
+    document.insert(name, value);
+    Ok(Value::Object(document))
+}
+
+/// One-based line and column of a byte offset
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+}
+
+impl XmlQueryTool {
+
+    fn read_file_content(file_path: &Path) -> Result<String, XmlQueryError> {
+        std::fs::read_to_string(file_path)
+            .map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    XmlQueryError::FileNotFound(file_path.display().to_string())
+                } else {
+                    XmlQueryError::IoError(e.to_string())
+                }
+            })
+    }
+
+    /// Parse the file without querying it, reporting the line and column of the first syntax error
+    fn validate_xml_file(&self, file_path: &Path) -> Result<Value, XmlQueryError> {
+        let content = Self::read_file_content(file_path)?;
+
+        match xml_to_json(&content) {
+            Ok(_) => Ok(serde_json::json!({ "valid": true })),
+            Err(e) => {
+                let (line, column) = line_column(&content, e.offset);
+                Ok(serde_json::json!({ "valid": false, "error": e.message, "line": line, "column": column }))
+            }
+        }
+    }
+
+    fn read_xml_file(&self, file_path: &Path) -> Result<Value, XmlQueryError> {
+        let content = Self::read_file_content(file_path)?;
+
+        xml_to_json(&content).map_err(|e| {
+            let (line, column) = line_column(&content, e.offset);
+            XmlQueryError::InvalidXml {
+                file: file_path.display().to_string(),
+                error: format!("{} at line {} column {}", e.message, line, column),
+            }
+        })
+    }
+
+    fn format_output(&self, value: &Value, format: &str) -> Result<String, XmlQueryError> {
+        match format {
+            "json" => serde_json::to_string_pretty(value)
+                .map_err(|e| XmlQueryError::IoError(format!("JSON serialization failed: {}", e))),
+            "raw" => match value {
+                Value::String(s) => Ok(s.clone()),
+                Value::Number(n) => Ok(n.to_string()),
+                Value::Bool(b) => Ok(b.to_string()),
+                Value::Null => Ok("null".to_string()),
+                _ => serde_json::to_string_pretty(value)
+                    .map_err(|e| XmlQueryError::IoError(format!("JSON serialization failed: {}", e))),
+            },
+            _ => Err(XmlQueryError::InvalidQuery(format!("Invalid output format: {}. Must be 'json' or 'raw'", format))),
+        }
+    }
+}
+
+#[async_trait]
+impl StatefulTool for XmlQueryTool {
+    async fn call_with_context(
+        mut self,
+        context: &ToolContext,
+    ) -> Result<CallToolResult, CallToolError> {
+        self.file_path = context.resolve_in_working_dir(&self.file_path);
+
+        // Get project root
+        let project_root = context.get_project_root()
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input("xq", &format!("Failed to get project root: {}", e))))?;
+
+        if !matches!(self.operation.as_str(), "read" | "validate") {
+            return Err(CallToolError::from(tool_errors::invalid_input("xq",
+                &format!("Invalid operation: {}. Must be 'read' or 'validate'", self.operation)
+            )));
+        }
+
+        let canonical_path = resolve_path_for_read(&self.file_path, &project_root, self.follow_symlinks, "xq")?;
+
+        if self.operation == "validate" {
+            let report = self.validate_xml_file(&canonical_path).map_err(|e| CallToolError::from(tool_errors::invalid_input("xq", &e.to_string())))?;
+            return Ok(CallToolResult {
+                content: vec![CallToolResultContentItem::text_content(
+                    report.to_string(),
+                    None,
+                )],
+                is_error: Some(false),
+                meta: None,
+            });
+        }
+
+        // Reject malformed queries before touching the file
+        QueryParser::new().validate(&self.query)
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input("xq", &XmlQueryError::InvalidQuery(e.to_string()).to_string())))?;
+
+        let data = self.read_xml_file(&canonical_path).map_err(|e| CallToolError::from(tool_errors::invalid_input("xq", &e.to_string())))?;
+
+        // Track file as read
+        let read_files = context.get_custom_state::<HashSet<PathBuf>>().await
+            .unwrap_or_else(|| std::sync::Arc::new(HashSet::new()));
+        let mut read_files_clone = (*read_files).clone();
+        read_files_clone.insert(canonical_path.clone());
+        context.set_custom_state(read_files_clone).await;
+
+        let result = QueryEngine::new().execute(&data, &self.query)
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input("xq", &XmlQueryError::from(e).to_string())))?;
+
+        let output = self.format_output(&result, &self.output_format).map_err(|e| CallToolError::from(tool_errors::invalid_input("xq", &e.to_string())))?;
+
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::text_content(
+                output,
+                None,
+            )],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+}
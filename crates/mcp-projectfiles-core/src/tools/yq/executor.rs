@@ -78,13 +78,16 @@ impl YamlQueryExecutor {
            query == "tonumber" || query == ".tonumber" ||
            query == "ascii_downcase" || query == ".ascii_downcase" ||
            query == "ascii_upcase" || query == ".ascii_upcase" ||
+           query == "explode" || query == ".explode" ||
+           query == "implode" || query == ".implode" ||
            query.contains(" | split(") || query.contains(" | join(") ||
            query.contains(" | contains(") || query.contains(" | startswith(") ||
            query.contains(" | endswith(") || query.contains(" | test(") ||
            query.contains(" | match(") || query.contains(" | ltrimstr(") ||
            query.contains(" | rtrimstr(") || query.ends_with(" | trim") ||
            query.ends_with(" | tostring") || query.ends_with(" | tonumber") ||
-           query.ends_with(" | ascii_downcase") || query.ends_with(" | ascii_upcase") {
+           query.ends_with(" | ascii_downcase") || query.ends_with(" | ascii_upcase") ||
+           query.ends_with(" | explode") || query.ends_with(" | implode") {
             return functions::execute_string_function(self, data, query);
         }
         
@@ -164,10 +167,17 @@ impl YamlQueryExecutor {
         
         // Check for string functions without parentheses
         if query == "trim" || query == "tostring" || query == "tonumber" ||
-           query == "ascii_upcase" || query == "ascii_downcase" {
+           query == "ascii_upcase" || query == "ascii_downcase" ||
+           query == "explode" || query == "implode" {
             return functions::execute_string_function(self, data, query);
         }
         
+        // Format strings such as @base64 and @csv are shared with the jq engine
+        if query.starts_with('@') {
+            return crate::tools::query_engine::functions::apply_format(query, data)
+                .map_err(|e| YamlQueryError::ExecutionError(e.to_string()));
+        }
+        
         // If none of the above, try to parse as a simple path
         self.execute_path_query(data, query)
     }
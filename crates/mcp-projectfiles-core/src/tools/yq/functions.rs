@@ -420,6 +420,10 @@ pub fn execute_string_function(executor: &YamlQueryExecutor, data: &serde_json::
         execute_ascii_upcase_function(data)
     } else if func_query == "ascii_downcase" {
         execute_ascii_downcase_function(data)
+    } else if func_query == "explode" {
+        execute_explode_function(data)
+    } else if func_query == "implode" {
+        execute_implode_function(data)
     } else {
         Err(YamlQueryError::InvalidQuery(format!("Unknown string function: {}", func_query)))
     }
@@ -988,6 +992,8 @@ fn execute_tonumber_function(data: &serde_json::Value) -> Result<serde_json::Val
         serde_json::Value::String(s) => {
             if let Ok(i) = s.parse::<i64>() {
                 Ok(serde_json::Value::Number(serde_json::Number::from(i)))
+            } else if let Ok(u) = s.parse::<u64>() {
+                Ok(serde_json::Value::Number(serde_json::Number::from(u)))
             } else if let Ok(f) = s.parse::<f64>() {
                 Ok(serde_json::Value::Number(serde_json::Number::from_f64(f).unwrap_or(serde_json::Number::from(0))))
             } else {
@@ -998,6 +1004,34 @@ fn execute_tonumber_function(data: &serde_json::Value) -> Result<serde_json::Val
     }
 }
 
+fn execute_explode_function(data: &serde_json::Value) -> Result<serde_json::Value, YamlQueryError> {
+    match data {
+        serde_json::Value::String(s) => Ok(serde_json::Value::Array(
+            s.chars()
+                .map(|c| serde_json::Value::Number(serde_json::Number::from(c as u32)))
+                .collect()
+        )),
+        _ => Err(YamlQueryError::ExecutionError("explode can only be applied to strings".to_string()))
+    }
+}
+
+fn execute_implode_function(data: &serde_json::Value) -> Result<serde_json::Value, YamlQueryError> {
+    match data {
+        serde_json::Value::Array(arr) => {
+            let mut result = String::with_capacity(arr.len());
+            for item in arr {
+                let c = item.as_u64()
+                    .and_then(|code| u32::try_from(code).ok())
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| YamlQueryError::ExecutionError(format!("implode requires an array of codepoint integers, got {}", item)))?;
+                result.push(c);
+            }
+            Ok(serde_json::Value::String(result))
+        }
+        _ => Err(YamlQueryError::ExecutionError("implode can only be applied to arrays".to_string()))
+    }
+}
+
 fn execute_ascii_upcase_function(data: &serde_json::Value) -> Result<serde_json::Value, YamlQueryError> {
     if let Some(s) = data.as_str() {
         Ok(serde_json::Value::String(s.to_uppercase()))
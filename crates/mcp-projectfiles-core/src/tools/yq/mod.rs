@@ -6,7 +6,9 @@ mod conditionals;
 
 use crate::context::{StatefulTool, ToolContext};
 use crate::config::tool_errors;
-use crate::tools::utils::resolve_path_for_read;
+use crate::tools::query_engine::parser::QueryParser;
+use crate::tools::utils::{TextStyle, expand_pattern_in_project, resolve_path_for_read, resolve_path_for_write, write_atomically};
+use crate::tools::write::{encode_like, read_text_file};
 use async_trait::async_trait;
 use rust_mcp_schema::{
     CallToolResult, CallToolResultContentItem, schema_utils::CallToolError,
@@ -37,14 +39,18 @@ pub enum YamlQueryError {
 }
 
 #[mcp_tool(name = "yq", description = "Query and manipulate YAML files with jq syntax. Multi-document support, type preservation.
+Use operation=\"validate\" to check that the file parses and count its documents without running a query.
+Set slurp=true to read each non-empty line as a separate value.
+With pattern=true, file_path is a glob and the query runs against every match, returning a mapping keyed by path; files that fail carry an error instead.
 Examples: \".users | map(.email)\" or \".enabled = true\" or \"select(.environment == \\\"prod\\\")\"")]
 #[derive(JsonSchema, Serialize, Deserialize, Debug, Clone)]
 pub struct YamlQueryTool {
-    /// Path to the YAML file (relative to project root)
+    /// Path to the YAML file (relative to project root), or a glob when pattern is true
     pub file_path: String,
-    /// jq-style query string for YAML data manipulation
+    /// jq-style query string for YAML data manipulation (ignored for validate)
+    #[serde(default)]
     pub query: String,
-    /// Operation type: "read" (default) or "write"
+    /// Operation type: "read" (default), "write", or "validate" (report whether the file parses)
     #[serde(default = "default_operation")]
     pub operation: String,
     /// Output format: "yaml" (default), "json", or "raw"
@@ -59,6 +65,12 @@ pub struct YamlQueryTool {
     /// Follow symlinks when reading files (default: true)
     #[serde(default = "default_follow_symlinks")]
     pub follow_symlinks: bool,
+    /// Parse each non-empty line as a separate value and query them as an array, like jq -s (default: false)
+    #[serde(default)]
+    pub slurp: bool,
+    /// Treat file_path as a glob and run the query against every matching file; read operations only (default: false)
+    #[serde(default)]
+    pub pattern: bool,
 }
 
 fn default_operation() -> String {
@@ -86,37 +98,121 @@ pub struct YamlQueryResult {
 
 impl YamlQueryTool {
 
-    fn read_yaml_file(&self, file_path: &Path) -> Result<serde_json::Value, YamlQueryError> {
-        let content = std::fs::read_to_string(file_path)
+    fn read_file_content(file_path: &Path) -> Result<String, YamlQueryError> {
+        read_text_file(file_path)
+            .map(|(content, _, _)| content)
             .map_err(|e| {
                 if e.kind() == std::io::ErrorKind::NotFound {
                     YamlQueryError::FileNotFound(file_path.display().to_string())
                 } else {
                     YamlQueryError::IoError(e.to_string())
                 }
-            })?;
+            })
+    }
+    
+    /// Parse the file without querying it. Reports the document count, or the line and
+    /// column of the first syntax error.
+    fn validate_yaml_file(&self, file_path: &Path) -> Result<serde_json::Value, YamlQueryError> {
+        let content = Self::read_file_content(file_path)?;
         
-        // Parse YAML and convert to JSON Value for uniform processing
-        let yaml_value: serde_yaml::Value = serde_yaml::from_str(&content)
-            .map_err(|e| YamlQueryError::InvalidYaml {
-                file: file_path.display().to_string(),
-                error: e.to_string(),
-            })?;
+        // With slurp every non-empty line is its own value; otherwise documents are split on `---`
+        let sources: Vec<(usize, &str)> = if self.slurp {
+            content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()).collect()
+        } else {
+            vec![(0, content.as_str())]
+        };
         
-        // Convert YAML Value to JSON Value for jq processing
-        let json_str = serde_json::to_string(&yaml_value)
-            .map_err(|e| YamlQueryError::ExecutionError(format!("YAML to JSON conversion failed: {}", e)))?;
+        let mut documents = 0;
+        for (line_offset, text) in sources {
+            for document in serde_yaml::Deserializer::from_str(text) {
+                if let Err(e) = serde_yaml::Value::deserialize(document) {
+                    let mut report = serde_json::json!({ "valid": false, "error": e.to_string() });
+                    if let Some(location) = e.location() {
+                        // serde_yaml appends the position to its message; report it as separate fields instead
+                        let suffix = format!(" at line {} column {}", location.line(), location.column());
+                        if let Some(message) = report["error"].as_str().and_then(|m| m.strip_suffix(&suffix)) {
+                            report["error"] = message.into();
+                        }
+                        report["line"] = (location.line() + line_offset).into();
+                        report["column"] = location.column().into();
+                    }
+                    return Ok(report);
+                }
+                documents += 1;
+            }
+        }
         
-        serde_json::from_str(&json_str)
-            .map_err(|e| YamlQueryError::ExecutionError(format!("JSON parsing failed: {}", e)))
+        Ok(serde_json::json!({ "valid": true, "documents": documents }))
     }
     
-    fn format_output(&self, value: &serde_json::Value, format: &str) -> Result<String, YamlQueryError> {
+    /// Read a YAML file, returning the data and whether it contained multiple documents.
+    /// Multi-document files are collected into a JSON array with one element per document.
+    fn read_yaml_file(&self, file_path: &Path) -> Result<(serde_json::Value, bool), YamlQueryError> {
+        let content = Self::read_file_content(file_path)?;
+        
+        if self.slurp {
+            let mut values = Vec::new();
+            for (i, line) in content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+                let yaml_value: serde_yaml::Value = serde_yaml::from_str(line)
+                    .map_err(|e| YamlQueryError::InvalidYaml {
+                        file: format!("{} (line {})", file_path.display(), i + 1),
+                        error: e.to_string(),
+                    })?;
+                values.push(serde_json::to_value(&yaml_value)
+                    .map_err(|e| YamlQueryError::ExecutionError(format!("YAML to JSON conversion failed: {}", e)))?);
+            }
+            return Ok((serde_json::Value::Array(values), false));
+        }
+        
+        // Parse every YAML document, not just the first
+        let mut documents = Vec::new();
+        for document in serde_yaml::Deserializer::from_str(&content) {
+            let yaml_value = serde_yaml::Value::deserialize(document)
+                .map_err(|e| YamlQueryError::InvalidYaml {
+                    file: file_path.display().to_string(),
+                    error: e.to_string(),
+                })?;
+            
+            // Convert YAML Value to JSON Value for jq processing
+            let json_str = serde_json::to_string(&yaml_value)
+                .map_err(|e| YamlQueryError::ExecutionError(format!("YAML to JSON conversion failed: {}", e)))?;
+            
+            documents.push(serde_json::from_str(&json_str)
+                .map_err(|e| YamlQueryError::ExecutionError(format!("JSON parsing failed: {}", e)))?);
+        }
+        
+        match documents.len() {
+            0 => Ok((serde_json::Value::Null, false)),
+            1 => Ok((documents.remove(0), false)),
+            _ => Ok((serde_json::Value::Array(documents), true)),
+        }
+    }
+    
+    /// Serialize a value to YAML, emitting one `---`-separated document per array element
+    /// when the value is an array that came from a multi-document file.
+    fn to_yaml_documents(value: &serde_json::Value, multi_document: bool) -> Result<String, YamlQueryError> {
+        match value {
+            serde_json::Value::Array(documents) if multi_document => {
+                let mut output = String::new();
+                for (i, document) in documents.iter().enumerate() {
+                    if i > 0 {
+                        output.push_str("---\n");
+                    }
+                    output.push_str(&serde_yaml::to_string(document)
+                        .map_err(|e| YamlQueryError::ExecutionError(format!("YAML serialization failed: {}", e)))?);
+                }
+                Ok(output)
+            }
+            _ => serde_yaml::to_string(value)
+                .map_err(|e| YamlQueryError::ExecutionError(format!("YAML serialization failed: {}", e))),
+        }
+    }
+    
+    fn format_output(&self, value: &serde_json::Value, format: &str, multi_document: bool) -> Result<String, YamlQueryError> {
         match format {
             "yaml" => {
                 // Convert JSON Value back to YAML
-                serde_yaml::to_string(value)
-                    .map_err(|e| YamlQueryError::ExecutionError(format!("YAML serialization failed: {}", e)))
+                Self::to_yaml_documents(value, multi_document)
             }
             "json" => serde_json::to_string_pretty(value)
                 .map_err(|e| YamlQueryError::ExecutionError(format!("JSON serialization failed: {}", e))),
@@ -126,166 +222,142 @@ impl YamlQueryTool {
                     serde_json::Value::Number(n) => Ok(n.to_string()),
                     serde_json::Value::Bool(b) => Ok(b.to_string()),
                     serde_json::Value::Null => Ok("null".to_string()),
-                    _ => serde_yaml::to_string(value)
-                        .map_err(|e| YamlQueryError::ExecutionError(format!("YAML serialization failed: {}", e))),
+                    _ => Self::to_yaml_documents(value, multi_document),
                 }
             }
             _ => Err(YamlQueryError::ExecutionError(format!("Invalid output format: {}", format))),
         }
     }
     
-    fn write_yaml_file(&self, file_path: &Path, data: &serde_json::Value, backup: bool) -> Result<(), YamlQueryError> {
+    /// Run the query against every file matching the `file_path` glob. Each file maps to its
+    /// result, or to an `error` entry when it cannot be read, parsed or queried.
+    async fn query_pattern(&self, context: &ToolContext, project_root: &Path) -> Result<CallToolResult, CallToolError> {
+        use std::collections::HashSet;
+        
+        QueryParser::new().validate(&self.query)
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input("yq", &YamlQueryError::InvalidQuery(e.to_string()).to_string())))?;
+        
+        let executor = YamlQueryExecutor::new();
+        let mut results = serde_json::Map::new();
+        let mut read_paths = Vec::new();
+        for relative_path in expand_pattern_in_project(&self.file_path, project_root, "yq")? {
+            let file_path = match resolve_path_for_read(&relative_path, project_root, self.follow_symlinks, "yq") {
+                Ok(path) if !path.is_file() => continue,
+                Ok(path) => path,
+                Err(e) => {
+                    results.insert(relative_path, serde_json::json!({ "error": e.to_string() }));
+                    continue;
+                }
+            };
+            let result = self.read_yaml_file(&file_path)
+                .inspect(|_| read_paths.push(file_path.clone()))
+                .and_then(|(data, _)| executor.execute(&data, &self.query));
+            let entry = result.unwrap_or_else(|e| serde_json::json!({ "error": e.to_string() }));
+            results.insert(relative_path, entry);
+        }
+        
+        // Track every file whose contents were read
+        let read_files = context.get_custom_state::<HashSet<PathBuf>>().await
+            .unwrap_or_else(|| std::sync::Arc::new(HashSet::new()));
+        let mut read_files_clone = (*read_files).clone();
+        read_files_clone.extend(read_paths);
+        context.set_custom_state(read_files_clone).await;
+        
+        let output = self.format_output(&serde_json::Value::Object(results), &self.output_format, false)
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input("yq", &e.to_string())))?;
+        Ok(CallToolResult {
+            content: vec![CallToolResultContentItem::text_content(
+                output,
+                None,
+            )],
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+    
+    fn write_yaml_file(&self, file_path: &Path, project_root: &Path, data: &serde_json::Value, backup: bool, multi_document: bool) -> Result<(), CallToolError> {
+        let io_error = |e: YamlQueryError| CallToolError::from(tool_errors::invalid_input("yq", &e.to_string()));
+        
         if backup && file_path.exists() {
             let backup_path = format!("{}.bak", file_path.display());
             std::fs::copy(file_path, &backup_path)
-                .map_err(|e| YamlQueryError::IoError(format!("Failed to create backup: {}", e)))?;
+                .map_err(|e| io_error(YamlQueryError::IoError(format!("Failed to create backup: {}", e))))?;
         }
         
-        let yaml_str = serde_yaml::to_string(data)
-            .map_err(|e| YamlQueryError::ExecutionError(format!("YAML serialization failed: {}", e)))?;
-        
-        // Atomic write using temporary file
-        let temp_path = format!("{}.tmp", file_path.display());
-        std::fs::write(&temp_path, yaml_str)
-            .map_err(|e| YamlQueryError::IoError(format!("Failed to write temporary file: {}", e)))?;
-        
-        std::fs::rename(&temp_path, file_path)
-            .map_err(|e| YamlQueryError::IoError(format!("Failed to move temporary file: {}", e)))?;
+        let mut yaml_str = Self::to_yaml_documents(data, multi_document).map_err(io_error)?;
+        // Keep the existing file's line endings, final newline and encoding
+        let existing = read_text_file(file_path).ok();
+        if let Some(style) = existing.as_ref().and_then(|(content, _, _)| TextStyle::detect(content)) {
+            yaml_str = style.apply(&yaml_str);
+        }
+        let (encoding, had_bom) = existing.map_or((encoding_rs::UTF_8, false), |(_, encoding, had_bom)| (encoding, had_bom));
+        let bytes = encode_like(&yaml_str, encoding, had_bom, "yq")?;
         
-        Ok(())
+        write_atomically(file_path, project_root, &bytes, "yq")
     }
 }
 
 #[async_trait]
 impl StatefulTool for YamlQueryTool {
     async fn call_with_context(
-        self,
+        mut self,
         context: &ToolContext,
     ) -> Result<CallToolResult, CallToolError> {
+        self.file_path = context.resolve_in_working_dir(&self.file_path);
+
+        if self.in_place {
+            context.ensure_writable("yq")?;
+        }
+
         use std::collections::HashSet;
         
         // Get project root
         let project_root = context.get_project_root()
             .map_err(|e| CallToolError::from(tool_errors::invalid_input("yq", &format!("Failed to get project root: {}", e))))?;
         
+        if self.pattern {
+            if self.operation != "read" {
+                return Err(CallToolError::from(tool_errors::invalid_input(
+                    "yq",
+                    "pattern is only supported for read operations"
+                )));
+            }
+            return self.query_pattern(context, &project_root).await;
+        }
+        
         // For read operations, use symlink-aware path resolution
-        let canonical_path = if self.operation == "read" {
+        let canonical_path = if matches!(self.operation.as_str(), "read" | "validate") {
             resolve_path_for_read(&self.file_path, &project_root, self.follow_symlinks, "yq")
                 .map_err(|e| CallToolError::from(e))?
         } else {
-            // For write operations, ensure we don't write through symlinks
-            let requested_path = Path::new(&self.file_path);
-            let absolute_path = if requested_path.is_absolute() {
-                requested_path.to_path_buf()
-            } else {
-                project_root.join(requested_path)
-            };
-            
-            // For existing files, canonicalize the path to resolve all symlinks
-            // For new files, canonicalize the parent directory and ensure the full path is within bounds
-            if absolute_path.exists() {
-                let canonical = absolute_path.canonicalize()
-                    .map_err(|e| CallToolError::from(tool_errors::invalid_input("yq", &format!("Failed to resolve path '{}': {}", self.file_path, e))))?;
-                
-                // Ensure the canonicalized path is within the project directory
-                if !canonical.starts_with(&project_root) {
-                    return Err(CallToolError::from(tool_errors::access_denied(
-                        "yq",
-                        &self.file_path,
-                        "Path is outside the project directory"
-                    )));
-                }
-                
-                canonical
-            } else {
-                // For new files, canonicalize the parent directory
-                if let Some(parent) = absolute_path.parent() {
-                    if parent.exists() {
-                        let canonical_parent = parent.canonicalize()
-                            .map_err(|e| CallToolError::from(tool_errors::invalid_input("yq", &format!("Failed to resolve parent directory: {}", e))))?;
-                        
-                        // Ensure the parent is within the project directory
-                        if !canonical_parent.starts_with(&project_root) {
-                            return Err(CallToolError::from(tool_errors::access_denied(
-                                "yq",
-                                &self.file_path,
-                                "Path would be outside the project directory"
-                            )));
-                        }
-                        
-                        // Reconstruct the path with the canonical parent
-                        if let Some(file_name) = absolute_path.file_name() {
-                            canonical_parent.join(file_name)
-                        } else {
-                            return Err(CallToolError::from(tool_errors::invalid_input(
-                                "yq",
-                                &format!("Invalid file path: '{}'", self.file_path)
-                            )));
-                        }
-                    } else {
-                        // Parent doesn't exist yet, need to check each component
-                        // Walk up the path to find the first existing parent
-                        let mut current = parent;
-                        let mut components_to_create = vec![];
-                        
-                        while !current.exists() {
-                            if let Some(parent_of_current) = current.parent() {
-                                components_to_create.push(current);
-                                current = parent_of_current;
-                            } else {
-                                break;
-                            }
-                        }
-                        
-                        // Now current is the first existing ancestor
-                        if current.exists() {
-                            let canonical_existing = current.canonicalize()
-                                .map_err(|e| CallToolError::from(tool_errors::invalid_input("yq", &format!("Failed to resolve existing parent: {}", e))))?;
-                            
-                            // Check if the existing parent is within project
-                            if !canonical_existing.starts_with(&project_root) {
-                                return Err(CallToolError::from(tool_errors::access_denied(
-                                    "yq",
-                                    &self.file_path,
-                                    "Path would be outside the project directory"
-                                )));
-                            }
-                            
-                            // Reconstruct the full path
-                            let mut result = canonical_existing;
-                            for component in components_to_create.iter().rev() {
-                                if let Some(file_name) = component.file_name() {
-                                    result = result.join(file_name);
-                                }
-                            }
-                            if let Some(file_name) = absolute_path.file_name() {
-                                result.join(file_name)
-                            } else {
-                                result
-                            }
-                        } else {
-                            // No existing parent found, check if path is at least within project
-                            if !absolute_path.starts_with(&project_root) {
-                                return Err(CallToolError::from(tool_errors::access_denied(
-                                    "yq",
-                                    &self.file_path,
-                                    "Path would be outside the project directory"
-                                )));
-                            }
-                            absolute_path
-                        }
-                    }
-                } else {
-                    return Err(CallToolError::from(tool_errors::invalid_input(
-                        "yq",
-                        &format!("Invalid file path: '{}'", self.file_path)
-                    )));
-                }
-            }
+            resolve_path_for_write(&self.file_path, &project_root, "yq")?
         };
         
+        if self.slurp && self.operation == "write" {
+            return Err(CallToolError::from(tool_errors::invalid_input(
+                "yq",
+                "slurp is only supported for read operations"
+            )));
+        }
+        
+        if self.operation == "validate" {
+            let report = self.validate_yaml_file(&canonical_path).map_err(|e| CallToolError::from(tool_errors::invalid_input("yq", &e.to_string())))?;
+            return Ok(CallToolResult {
+                content: vec![CallToolResultContentItem::text_content(
+                    report.to_string(),
+                    None,
+                )],
+                is_error: Some(false),
+                meta: None,
+            });
+        }
+        
+        // Reject malformed queries before touching the file
+        QueryParser::new().validate(&self.query)
+            .map_err(|e| CallToolError::from(tool_errors::invalid_input("yq", &YamlQueryError::InvalidQuery(e.to_string()).to_string())))?;
+        
         // Read the YAML file
-        let mut data = self.read_yaml_file(&canonical_path).map_err(|e| CallToolError::from(tool_errors::invalid_input("yq", &e.to_string())))?;
+        let (mut data, multi_document) = self.read_yaml_file(&canonical_path).map_err(|e| CallToolError::from(tool_errors::invalid_input("yq", &e.to_string())))?;
         
         let mut modified = false;
         
@@ -323,7 +395,8 @@ impl StatefulTool for YamlQueryTool {
                     modified = true;
                     
                     // Write the modified data back to file
-                    self.write_yaml_file(&canonical_path, &data, self.backup).map_err(|e| CallToolError::from(tool_errors::invalid_input("yq", &e.to_string())))?;
+                    self.write_yaml_file(&canonical_path, &project_root, &data, self.backup, multi_document)?;
+                    context.record_modified(&canonical_path).await;
                     result
                 } else {
                     return Err(CallToolError::from(tool_errors::invalid_input("yq",
@@ -332,12 +405,16 @@ impl StatefulTool for YamlQueryTool {
                 }
             }
             _ => return Err(CallToolError::from(tool_errors::invalid_input("yq", 
-                &format!("Invalid operation: {}. Must be 'read' or 'write'", self.operation)
+                &format!("Invalid operation: {}. Must be 'read', 'write', or 'validate'", self.operation)
             ))),
         };
         
+        // Only the document stream itself goes back out as separate documents; an array the
+        // query built, like `.[0].spec.ports`, is a single YAML sequence
+        let document_stream = multi_document && result == data;
+        
         // Format the output
-        let output = self.format_output(&result, &self.output_format).map_err(|e| CallToolError::from(tool_errors::invalid_input("yq", &e.to_string())))?;
+        let output = self.format_output(&result, &self.output_format, document_stream).map_err(|e| CallToolError::from(tool_errors::invalid_input("yq", &e.to_string())))?;
         
         // For write operations, return a summary of the operation
         let content = if self.operation == "write" && modified {
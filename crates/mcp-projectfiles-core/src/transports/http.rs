@@ -0,0 +1,900 @@
+use crate::context::{ProgressReporter, ToolContext};
+use crate::handler::{CoreHandler, create_server_details, error_result};
+use axum::{
+    Json, Router,
+    body::Bytes,
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    middleware::{self, Next},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, Sse},
+    },
+    routing::{get, post},
+};
+use futures::{
+    StreamExt,
+    future::join_all,
+    stream::{self, PollNext},
+};
+use rust_mcp_schema::{
+    CallToolRequest, CallToolRequestParams, CancelledNotificationParams,
+    ListToolsRequest, ListToolsRequestParams, ProgressNotification, ProgressToken, RequestId,
+    RpcError,
+};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use subtle::ConstantTimeEq;
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tracing::{debug, error, info, instrument, warn};
+
+/// Path of the single Streamable HTTP endpoint
+pub const MCP_ENDPOINT: &str = "/mcp";
+
+/// Path of the Prometheus metrics endpoint
+pub const METRICS_ENDPOINT: &str = "/metrics";
+
+/// Path of the liveness endpoint, answered without the MCP handshake
+pub const HEALTH_ENDPOINT: &str = "/health";
+
+/// Header carrying the session id assigned in the initialize response
+pub const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Maximum number of batched requests dispatched concurrently on an SSE stream
+const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// Default number of sessions that may be open at once
+pub const DEFAULT_MAX_SESSIONS: usize = 256;
+
+/// Default time after its last request when a session is dropped
+pub const DEFAULT_SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// An open client session
+struct Session {
+    context: ToolContext,
+    last_used: Instant,
+}
+
+/// Streamable HTTP transport handler that wraps the core handler
+pub struct HttpHandler {
+    core: CoreHandler,
+    /// Open sessions by id, each with its own tool context so clients don't share read
+    /// tracking or working directories. In-process callers without a session use the core
+    /// handler's context; HTTP requests always need one
+    sessions: Mutex<HashMap<String, Session>>,
+    /// How many sessions may be open at once
+    max_sessions: usize,
+    /// Sessions unused for this long are dropped
+    session_idle_timeout: Duration,
+    /// Bearer token the MCP endpoint requires, if any
+    auth_token: Option<String>,
+}
+
+impl HttpHandler {
+    pub fn new() -> Self {
+        Self::with_core(CoreHandler::new())
+    }
+
+    /// Create a handler around an existing core handler
+    pub fn with_core(core: CoreHandler) -> Self {
+        Self {
+            core,
+            sessions: Mutex::new(HashMap::new()),
+            max_sessions: DEFAULT_MAX_SESSIONS,
+            session_idle_timeout: DEFAULT_SESSION_IDLE_TIMEOUT,
+            auth_token: crate::config::auth_token(),
+        }
+    }
+
+    /// Replace how many sessions may be open at once and how long an idle one is kept.
+    /// Defaults to [`DEFAULT_MAX_SESSIONS`] and [`DEFAULT_SESSION_IDLE_TIMEOUT`]
+    pub fn with_session_limits(mut self, max_sessions: usize, idle_timeout: Duration) -> Self {
+        self.max_sessions = max_sessions;
+        self.session_idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Replace the bearer token required on the MCP endpoint; `None` leaves it open.
+    /// Defaults to [`crate::config::auth_token`]
+    pub fn with_auth_token(mut self, auth_token: Option<String>) -> Self {
+        self.auth_token = auth_token;
+        self
+    }
+
+    /// Whether `authorization`, the raw header value, carries the required bearer token.
+    /// The token is compared in constant time so its value can't be recovered from timing
+    fn is_authorized(&self, authorization: Option<&str>) -> bool {
+        let Some(expected) = &self.auth_token else {
+            return true;
+        };
+        let Some(token) = authorization.and_then(|value| value.strip_prefix("Bearer ")) else {
+            return false;
+        };
+        token.trim().as_bytes().ct_eq(expected.as_bytes()).into()
+    }
+
+    /// Start a session with a fresh tool context and return its id. Idle sessions are
+    /// dropped first; returns `None` when the rest still fill every slot
+    pub fn create_session(&self) -> Option<String> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let idle_timeout = self.session_idle_timeout;
+        sessions.retain(|id, session| {
+            let keep = session.last_used.elapsed() < idle_timeout;
+            if !keep {
+                debug!(session_id = %id, "HTTP transport: expired idle session");
+            }
+            keep
+        });
+        if sessions.len() >= self.max_sessions {
+            warn!(max_sessions = self.max_sessions, "HTTP transport: refusing session, limit reached");
+            return None;
+        }
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let context = self.core.context().for_new_session();
+        sessions.insert(id.clone(), Session { context, last_used: Instant::now() });
+        debug!(session_id = %id, "HTTP transport: created session");
+        Some(id)
+    }
+
+    /// The context of session `id`, marking it as used. An expired session is dropped
+    /// and treated as closed
+    fn session_context(&self, id: &str) -> Option<ToolContext> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let session = sessions.get_mut(id)?;
+        if session.last_used.elapsed() >= self.session_idle_timeout {
+            sessions.remove(id);
+            debug!(session_id = %id, "HTTP transport: expired idle session");
+            return None;
+        }
+        session.last_used = Instant::now();
+        Some(session.context.clone())
+    }
+
+    /// End a session, dropping its state. Returns false if it did not exist
+    pub fn close_session(&self, id: &str) -> bool {
+        let closed = self.sessions.lock().unwrap().remove(id).is_some();
+        if closed {
+            debug!(session_id = %id, "HTTP transport: closed session");
+        }
+        closed
+    }
+
+    /// Whether `id` names an open session
+    pub fn has_session(&self, id: &str) -> bool {
+        self.session_context(id).is_some()
+    }
+
+    /// Dispatch a single JSON-RPC message. Notifications and client responses produce no reply.
+    pub async fn handle_message(&self, message: Value) -> Option<Value> {
+        self.handle_message_with_progress(message, None).await
+    }
+
+    /// Dispatch a single JSON-RPC message, sending progress for tool calls that carry a
+    /// `progressToken` to `progress`
+    pub async fn handle_message_with_progress(
+        &self,
+        message: Value,
+        progress: Option<UnboundedSender<ProgressNotification>>,
+    ) -> Option<Value> {
+        self.handle_session_message(None, message, progress).await
+    }
+
+    /// Dispatch a single JSON-RPC message within the session `session_id`, or outside any
+    /// session when it is `None`. Requests for an unknown session get an error
+    #[instrument(level = "debug", skip(self, message, progress))]
+    pub async fn handle_session_message(
+        &self,
+        session_id: Option<&str>,
+        message: Value,
+        progress: Option<UnboundedSender<ProgressNotification>>,
+    ) -> Option<Value> {
+        let context = match session_id {
+            Some(id) => self.session_context(id),
+            None => Some(self.core.context().clone()),
+        };
+        let Some(object) = message.as_object() else {
+            return Some(error_response(
+                Value::Null,
+                RpcError::invalid_request().with_message("JSON-RPC message must be an object".to_string()),
+            ));
+        };
+
+        let id = object.get("id").cloned();
+        let Some(method) = object.get("method").and_then(Value::as_str) else {
+            // Responses to server-initiated requests carry a result or error and need no reply
+            if object.contains_key("result") || object.contains_key("error") {
+                return None;
+            }
+            return Some(error_response(
+                id.unwrap_or(Value::Null),
+                RpcError::invalid_request().with_message("JSON-RPC request is missing 'method'".to_string()),
+            ));
+        };
+
+        let params = object.get("params").cloned().unwrap_or(Value::Null);
+        let Some(id) = id else {
+            self.handle_notification(session_id, method, params);
+            return None;
+        };
+        let Some(context) = context else {
+            return Some(error_response(
+                id,
+                RpcError::invalid_request().with_message("Unknown or closed session".to_string()),
+            ));
+        };
+
+        Some(match self.dispatch(method, params, &id, session_id, &context, progress).await {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => error_response(id, error),
+        })
+    }
+
+    /// Act on a client notification. Only cancellation needs handling; the rest are ignored
+    fn handle_notification(&self, session_id: Option<&str>, method: &str, params: Value) {
+        if method != "notifications/cancelled" {
+            debug!(method, "HTTP transport: ignoring notification");
+            return;
+        }
+        match serde_json::from_value::<CancelledNotificationParams>(params) {
+            Ok(params) => {
+                if !self.core.cancel_request(session_id, &params.request_id) {
+                    debug!(?params.request_id, "HTTP transport: cancelled request is not running");
+                }
+            }
+            Err(e) => debug!(error = %e, "HTTP transport: ignoring malformed cancel notification"),
+        }
+    }
+
+    async fn dispatch(
+        &self,
+        method: &str,
+        params: Value,
+        id: &Value,
+        session_id: Option<&str>,
+        context: &ToolContext,
+        progress: Option<UnboundedSender<ProgressNotification>>,
+    ) -> Result<Value, RpcError> {
+        match method {
+            "initialize" => to_result(create_server_details()),
+            "ping" => Ok(json!({})),
+            "tools/list" => {
+                debug!("HTTP transport: handling list_tools request");
+                let params: Option<ListToolsRequestParams> = parse_params(params)?;
+                to_result(self.core.list_tools(ListToolsRequest::new(params)).await?)
+            }
+            "tools/call" => {
+                // The typed params drop `_meta`, so the progress token is read from the raw JSON
+                let progress_token = params
+                    .pointer("/_meta/progressToken")
+                    .and_then(|token| serde_json::from_value::<ProgressToken>(token.clone()).ok());
+                let reporter = progress_token
+                    .zip(progress)
+                    .map(|(token, sender)| ProgressReporter::new(token, sender));
+                let params: CallToolRequestParams = parse_params(params)?;
+                debug!(tool_name = %params.name, "HTTP transport: handling call_tool request");
+                // Tool failures are reported in-band, matching the stdio runtime
+                let result = self
+                    .core
+                    .call_tool_in_context(
+                        CallToolRequest::new(params),
+                        context,
+                        session_id,
+                        serde_json::from_value::<RequestId>(id.clone()).ok(),
+                        reporter,
+                    )
+                    .await
+                    .unwrap_or_else(error_result);
+                to_result(result)
+            }
+            _ => Err(RpcError::method_not_found().with_message(format!("Unknown method: {}", method))),
+        }
+    }
+}
+
+impl Default for HttpHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How responses to a POST are delivered, chosen from the request's Accept header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResponseFormat {
+    Json,
+    EventStream,
+}
+
+impl ResponseFormat {
+    /// Prefer a plain JSON body and only stream when the client accepts nothing but SSE
+    fn negotiate(accept: Option<&str>) -> Option<Self> {
+        let Some(accept) = accept else {
+            return Some(ResponseFormat::Json);
+        };
+        let media_types: Vec<&str> = accept
+            .split(',')
+            .map(|part| part.split(';').next().unwrap_or("").trim())
+            .collect();
+        let accepts = |wanted: &[&str]| media_types.iter().any(|t| wanted.contains(t));
+
+        if accepts(&["application/json", "application/*", "*/*"]) {
+            Some(ResponseFormat::Json)
+        } else if accepts(&["text/event-stream", "text/*"]) {
+            Some(ResponseFormat::EventStream)
+        } else {
+            None
+        }
+    }
+}
+
+/// Build the router serving the MCP endpoint and the health check. POST carries messages and
+/// DELETE ends a session; other methods on the endpoint get 405. With an auth token set, the
+/// MCP and metrics endpoints reject requests without it, while the health check stays open
+pub fn http_router(handler: Arc<HttpHandler>) -> Router {
+    Router::new()
+        .route(MCP_ENDPOINT, post(handle_post).delete(handle_delete))
+        .route(METRICS_ENDPOINT, get(handle_metrics))
+        .route_layer(middleware::from_fn_with_state(handler.clone(), require_auth))
+        .route(HEALTH_ENDPOINT, get(handle_health))
+        .with_state(handler)
+}
+
+/// Answer 401 unless the request carries the handler's bearer token
+async fn require_auth(State(handler): State<Arc<HttpHandler>>, request: Request, next: Next) -> Response {
+    let authorization = request.headers().get(header::AUTHORIZATION).and_then(|v| v.to_str().ok());
+    if !handler.is_authorized(authorization) {
+        debug!("HTTP transport: rejecting request without a valid bearer token");
+        return (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            "Missing or invalid bearer token",
+        )
+            .into_response();
+    }
+    next.run(request).await
+}
+
+/// Per-tool call counts, errors and durations in the Prometheus text format
+async fn handle_metrics(State(handler): State<Arc<HttpHandler>>) -> Response {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        handler.core.metrics().to_prometheus(),
+    )
+        .into_response()
+}
+
+/// Report the server name and version for liveness probes, without touching any session
+async fn handle_health() -> Json<Value> {
+    let details = create_server_details();
+    Json(json!({
+        "status": "ok",
+        "name": details.server_info.name,
+        "version": details.server_info.version,
+    }))
+}
+
+fn session_header(headers: &HeaderMap) -> Option<&str> {
+    headers.get(SESSION_HEADER).and_then(|v| v.to_str().ok())
+}
+
+/// Close the session named in the request, as clients do when they disconnect
+async fn handle_delete(State(handler): State<Arc<HttpHandler>>, headers: HeaderMap) -> Response {
+    match session_header(&headers) {
+        None => (StatusCode::BAD_REQUEST, "Missing Mcp-Session-Id header").into_response(),
+        Some(id) if handler.close_session(id) => StatusCode::OK.into_response(),
+        Some(_) => (StatusCode::NOT_FOUND, "Unknown or closed session").into_response(),
+    }
+}
+
+async fn handle_post(
+    State(handler): State<Arc<HttpHandler>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let accept = headers.get(header::ACCEPT).and_then(|v| v.to_str().ok());
+    let Some(format) = ResponseFormat::negotiate(accept) else {
+        return (
+            StatusCode::NOT_ACCEPTABLE,
+            "Accept header must allow application/json or text/event-stream",
+        )
+            .into_response();
+    };
+
+    let (messages, is_batch) = match serde_json::from_slice::<Value>(&body) {
+        Ok(Value::Array(messages)) => (messages, true),
+        Ok(message) => (vec![message], false),
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(error_response(
+                    Value::Null,
+                    RpcError::parse_error().with_message(format!("Invalid JSON: {}", e)),
+                )),
+            )
+                .into_response();
+        }
+    };
+
+    if messages.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(error_response(
+                Value::Null,
+                RpcError::invalid_request().with_message("Batch must not be empty".to_string()),
+            )),
+        )
+            .into_response();
+    }
+
+    // Initialize opens a new session. Anything else runs in the session it names, which must
+    // still be open; there is no shared context for clients without one
+    let created_session = if messages.iter().any(is_initialize) {
+        match handler.create_session() {
+            Some(id) => Some(id),
+            None => return (StatusCode::SERVICE_UNAVAILABLE, "Too many open sessions").into_response(),
+        }
+    } else {
+        None
+    };
+    let session_id = match (&created_session, session_header(&headers)) {
+        (Some(id), _) => Some(id.clone()),
+        (None, None) => {
+            return (StatusCode::BAD_REQUEST, "Missing Mcp-Session-Id header").into_response();
+        }
+        (None, Some(id)) if !handler.has_session(id) => {
+            return (StatusCode::NOT_FOUND, "Unknown or closed session").into_response();
+        }
+        (None, Some(id)) => Some(id.to_string()),
+    };
+
+    // Batches of only notifications or responses are acknowledged without a body
+    if !messages.iter().any(is_request) {
+        join_all(
+            messages
+                .into_iter()
+                .map(|m| handler.handle_session_message(session_id.as_deref(), m, None)),
+        )
+        .await;
+        return StatusCode::ACCEPTED.into_response();
+    }
+
+    let mut response = match format {
+        ResponseFormat::Json => {
+            let responses: Vec<Value> = join_all(
+                messages
+                    .into_iter()
+                    .map(|m| handler.handle_session_message(session_id.as_deref(), m, None)),
+            )
+            .await
+                .into_iter()
+                .flatten()
+                .collect();
+            if is_batch {
+                Json(Value::Array(responses)).into_response()
+            } else {
+                Json(responses.into_iter().next().unwrap_or(Value::Null)).into_response()
+            }
+        }
+        ResponseFormat::EventStream => {
+            // Each request holds a progress sender until it finishes, so the progress stream
+            // ends together with the last response
+            let (progress_tx, progress_rx) = mpsc::unbounded_channel::<ProgressNotification>();
+            let requests: Vec<_> = messages
+                .into_iter()
+                .map(|message| (message, progress_tx.clone()))
+                .collect();
+            drop(progress_tx);
+
+            // Each response is sent as soon as it is ready; the stream closes after the last one
+            let responses = stream::iter(requests)
+                .map(move |(message, progress)| {
+                    let handler = handler.clone();
+                    let session_id = session_id.clone();
+                    async move {
+                        handler
+                            .handle_session_message(session_id.as_deref(), message, Some(progress))
+                            .await
+                    }
+                })
+                .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+                .filter_map(|response| async move { response });
+            let notifications = stream::unfold(progress_rx, |mut rx| async move {
+                rx.recv().await.map(|notification| (notification, rx))
+            })
+            .map(|notification| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": "notifications/progress",
+                    "params": notification.params,
+                })
+            });
+
+            // Pending progress goes out before a response that finished at the same time
+            let events = stream::select_with_strategy(notifications, responses, |_: &mut ()| PollNext::Left)
+                .map(|message| Ok::<_, Infallible>(Event::default().event("message").data(message.to_string())));
+            Sse::new(events).into_response()
+        }
+    };
+
+    if let Some(value) = created_session.and_then(|id| HeaderValue::from_str(&id).ok()) {
+        response.headers_mut().insert(SESSION_HEADER, value);
+    }
+    response
+}
+
+fn is_request(message: &Value) -> bool {
+    message.get("method").is_some() && message.get("id").is_some()
+}
+
+fn is_initialize(message: &Value) -> bool {
+    is_request(message) && message.get("method").and_then(Value::as_str) == Some("initialize")
+}
+
+fn parse_params<T: serde::de::DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params)
+        .map_err(|e| RpcError::invalid_params().with_message(format!("Invalid params: {}", e)))
+}
+
+fn to_result<T: serde::Serialize>(value: T) -> Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|e| RpcError::internal_error().with_message(e.to_string()))
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": error })
+}
+
+/// Run the MCP server with the Streamable HTTP transport
+#[instrument(level = "info")]
+pub async fn run_http_server(addr: SocketAddr) -> anyhow::Result<()> {
+    // Load .env file if it exists (ignore errors if not found)
+    if let Err(e) = dotenv::dotenv() {
+        debug!("No .env file loaded: {}", e);
+    } else {
+        info!("Loaded environment variables from .env file");
+    }
+
+    info!("Initializing HTTP transport handler");
+    let handler = Arc::new(HttpHandler::new());
+    if handler.auth_token.is_some() {
+        info!("Bearer token authentication enabled");
+    }
+
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to bind HTTP transport to {}: {}", addr, e))?;
+
+    info!(%addr, endpoint = MCP_ENDPOINT, health = HEALTH_ENDPOINT, metrics = METRICS_ENDPOINT, "Starting HTTP server");
+    axum::serve(listener, http_router(handler)).await.map_err(|e| {
+        error!(error = %e, "HTTP server failed");
+        anyhow::anyhow!("Server error: {}", e)
+    })?;
+
+    info!("HTTP server stopped");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn test_negotiate_response_format() {
+        assert_eq!(ResponseFormat::negotiate(None), Some(ResponseFormat::Json));
+        assert_eq!(
+            ResponseFormat::negotiate(Some("application/json, text/event-stream")),
+            Some(ResponseFormat::Json)
+        );
+        assert_eq!(
+            ResponseFormat::negotiate(Some("text/event-stream")),
+            Some(ResponseFormat::EventStream)
+        );
+        assert_eq!(ResponseFormat::negotiate(Some("text/html")), None);
+    }
+
+    #[tokio::test]
+    async fn test_handle_message_dispatch() {
+        let handler = HttpHandler::new();
+
+        let response = handler
+            .handle_message(json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"}))
+            .await
+            .unwrap();
+        assert_eq!(response["id"], 1);
+        assert!(!response["result"]["tools"].as_array().unwrap().is_empty());
+
+        let response = handler
+            .handle_message(json!({"jsonrpc": "2.0", "id": "a", "method": "no/such"}))
+            .await
+            .unwrap();
+        assert_eq!(response["id"], "a");
+        assert_eq!(response["error"]["code"], -32601);
+
+        let notification = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        assert!(handler.handle_message(notification).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tool_call_sends_progress_for_token() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir(project_root.join("src")).unwrap();
+        std::fs::write(project_root.join("src/lib.rs"), "pub fn lib() {}").unwrap();
+        let handler = HttpHandler::with_core(CoreHandler::new_with_context(
+            crate::context::ToolContext::with_project_root(project_root),
+        ));
+        let call = |meta: Value| {
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "tools/call",
+                "params": {"name": "tree", "arguments": {"path": "."}, "_meta": meta}
+            })
+        };
+
+        let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
+        let response = handler
+            .handle_message_with_progress(call(json!({"progressToken": "walk-1"})), Some(progress_tx))
+            .await
+            .unwrap();
+        assert!(response["result"]["content"][0]["text"].as_str().unwrap().contains("lib.rs"));
+        let notification = progress_rx.recv().await.unwrap();
+        assert!(matches!(&notification.params.progress_token, ProgressToken::String(token) if token == "walk-1"));
+        assert!(notification.params.progress >= 1.0);
+        assert!(notification.params.message.unwrap().starts_with("Walked "));
+
+        // Without a token nothing is reported, and the sender is released with the request
+        let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
+        handler.handle_message_with_progress(call(json!({})), Some(progress_tx)).await.unwrap();
+        assert!(progress_rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_notification_stops_running_call() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(project_root.join("app.log"), "started\n").unwrap();
+        let handler = Arc::new(HttpHandler::with_core(CoreHandler::new_with_context(
+            crate::context::ToolContext::with_project_root(project_root),
+        )));
+        let session = handler.create_session().unwrap();
+
+        let call = json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "tools/call",
+            "params": {"name": "read", "arguments": {"path": "app.log", "follow": true, "follow_timeout_ms": 30000}}
+        });
+        let running = tokio::spawn({
+            let handler = handler.clone();
+            let session = session.clone();
+            async move { handler.handle_session_message(Some(&session), call, None).await }
+        });
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        let cancel = json!({"jsonrpc": "2.0", "method": "notifications/cancelled", "params": {"requestId": 7}});
+        assert!(handler.handle_session_message(Some(&session), cancel, None).await.is_none());
+        let response = tokio::time::timeout(Duration::from_secs(5), running).await.unwrap().unwrap().unwrap();
+        assert_eq!(response["result"]["isError"], true);
+        assert!(response["result"]["content"][0]["text"].as_str().unwrap().contains("Cancelled by the client"), "{}", response);
+    }
+
+    #[tokio::test]
+    async fn test_sessions_track_reads_independently() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(project_root.join("notes.txt"), "hello world\n").unwrap();
+        let handler = HttpHandler::with_core(CoreHandler::new_with_context(
+            crate::context::ToolContext::with_project_root(project_root),
+        ));
+        let call = |name: &str, arguments: Value| {
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "tools/call",
+                "params": {"name": name, "arguments": arguments}
+            })
+        };
+        let edit = |new: &str| call("edit", json!({"path": "notes.txt", "old": "hello", "new": new}));
+        let is_error = |response: Value| response["result"]["isError"] == true;
+
+        let first = handler.create_session().unwrap();
+        let second = handler.create_session().unwrap();
+        let response = handler
+            .handle_session_message(Some(&first), call("read", json!({"path": "notes.txt"})), None)
+            .await
+            .unwrap();
+        assert!(!is_error(response));
+
+        // The read only counts for the session that made it
+        let response = handler.handle_session_message(Some(&second), edit("bye"), None).await.unwrap();
+        assert!(is_error(response.clone()), "{}", response);
+        assert!(response.to_string().contains("must be read before editing"));
+        let response = handler.handle_message(edit("bye")).await.unwrap();
+        assert!(is_error(response));
+        let response = handler.handle_session_message(Some(&first), edit("howdy"), None).await.unwrap();
+        assert!(!is_error(response.clone()), "{}", response);
+
+        // A closed session's state is gone with it
+        assert!(handler.close_session(&first));
+        assert!(!handler.close_session(&first));
+        let response = handler.handle_session_message(Some(&first), edit("hey"), None).await.unwrap();
+        assert_eq!(response["error"]["code"], -32600);
+    }
+
+    async fn send(addr: SocketAddr, method: &str, path: &str, headers: &str, body: &str) -> String {
+        let request = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            method,
+            path,
+            addr,
+            headers,
+            body.len(),
+            body
+        );
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_initialize_opens_session_until_delete() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handler = Arc::new(HttpHandler::new());
+        let server = tokio::spawn(axum::serve(listener, http_router(handler.clone())).into_future());
+
+        let response = send(addr, "POST", MCP_ENDPOINT, "", r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#).await;
+        let session_id = response
+            .lines()
+            .find_map(|line| line.strip_prefix("mcp-session-id: "))
+            .unwrap_or_else(|| panic!("no session header: {}", response))
+            .to_string();
+        assert!(handler.has_session(&session_id));
+
+        let session = format!("Mcp-Session-Id: {}\r\n", session_id);
+        let ping = r#"{"jsonrpc":"2.0","id":2,"method":"ping"}"#;
+        let response = send(addr, "POST", MCP_ENDPOINT, &session, ping).await;
+        assert!(response.starts_with("HTTP/1.1 200"), "unexpected response: {}", response);
+        assert!(!response.contains("mcp-session-id"));
+
+        let response = send(addr, "DELETE", MCP_ENDPOINT, &session, "").await;
+        assert!(response.starts_with("HTTP/1.1 200"), "unexpected response: {}", response);
+        assert!(!handler.has_session(&session_id));
+        let response = send(addr, "POST", MCP_ENDPOINT, &session, ping).await;
+        assert!(response.starts_with("HTTP/1.1 404"), "unexpected response: {}", response);
+
+        // There is no shared context to fall back on
+        let response = send(addr, "POST", MCP_ENDPOINT, "", ping).await;
+        server.abort();
+        assert!(response.starts_with("HTTP/1.1 400"), "unexpected response: {}", response);
+        assert!(handler.sessions.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sessions_are_capped_and_expire() {
+        let handler = HttpHandler::new().with_session_limits(1, Duration::from_secs(3600));
+        let first = handler.create_session().unwrap();
+        assert!(handler.create_session().is_none());
+        assert!(handler.close_session(&first));
+        assert!(handler.create_session().is_some());
+
+        // An idle session is dropped on its next use and frees its slot
+        let handler = HttpHandler::new().with_session_limits(1, Duration::ZERO);
+        let first = handler.create_session().unwrap();
+        let second = handler.create_session().unwrap();
+        assert!(!handler.has_session(&first));
+        assert!(!handler.has_session(&second));
+        assert!(handler.sessions.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_health_endpoint() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handler = Arc::new(HttpHandler::new());
+        let server = tokio::spawn(axum::serve(listener, http_router(handler.clone())).into_future());
+
+        let response = send(addr, "GET", HEALTH_ENDPOINT, "", "").await;
+        let wrong_method = send(addr, "POST", HEALTH_ENDPOINT, "", "{}").await;
+        server.abort();
+
+        assert!(response.starts_with("HTTP/1.1 200"), "unexpected response: {}", response);
+        let body: Value = serde_json::from_str(response.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+        assert_eq!(body, json!({"status": "ok", "name": "projectfiles", "version": "0.1.0"}));
+        assert!(handler.sessions.lock().unwrap().is_empty());
+        assert!(wrong_method.starts_with("HTTP/1.1 405"), "unexpected response: {}", wrong_method);
+    }
+
+    #[tokio::test]
+    async fn test_bearer_token_required_when_set() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handler = Arc::new(HttpHandler::new().with_auth_token(Some("s3cret".to_string())));
+        let server = tokio::spawn(axum::serve(listener, http_router(handler.clone())).into_future());
+
+        let ping = r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#;
+        let missing = send(addr, "POST", MCP_ENDPOINT, "", ping).await;
+        let wrong = send(addr, "POST", MCP_ENDPOINT, "Authorization: Bearer s3cre\r\n", ping).await;
+        let basic = send(addr, "POST", MCP_ENDPOINT, "Authorization: Basic s3cret\r\n", ping).await;
+        let delete = send(addr, "DELETE", MCP_ENDPOINT, "Mcp-Session-Id: x\r\n", "").await;
+        let initialize = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#;
+        let valid = send(addr, "POST", MCP_ENDPOINT, "Authorization: Bearer s3cret\r\n", initialize).await;
+        let health = send(addr, "GET", HEALTH_ENDPOINT, "", "").await;
+        server.abort();
+
+        for response in [&missing, &wrong, &basic, &delete] {
+            assert!(response.starts_with("HTTP/1.1 401"), "unexpected response: {}", response);
+            assert!(response.contains("www-authenticate: Bearer"));
+        }
+        assert!(valid.starts_with("HTTP/1.1 200"), "unexpected response: {}", valid);
+        assert!(health.starts_with("HTTP/1.1 200"), "unexpected response: {}", health);
+
+        let open = HttpHandler::new().with_auth_token(None);
+        assert!(open.is_authorized(None));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_counts_tool_calls() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(project_root.join("notes.txt"), "hello\n").unwrap();
+        let handler = Arc::new(HttpHandler::with_core(CoreHandler::new_with_context(
+            crate::context::ToolContext::with_project_root(project_root),
+        )));
+        let call = |path: &str| {
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "tools/call",
+                "params": {"name": "read", "arguments": {"path": path}}
+            })
+        };
+        handler.handle_message(call("notes.txt")).await.unwrap();
+        handler.handle_message(call("missing.txt")).await.unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(axum::serve(listener, http_router(handler.clone())).into_future());
+        let response = send(addr, "GET", METRICS_ENDPOINT, "", "").await;
+        server.abort();
+
+        assert!(response.starts_with("HTTP/1.1 200"), "unexpected response: {}", response);
+        assert!(response.contains("text/plain; version=0.0.4"));
+        assert!(response.contains("projectfiles_tool_calls_total{tool=\"read\"} 2\n"), "{}", response);
+        assert!(response.contains("projectfiles_tool_errors_total{tool=\"read\"} 1\n"), "{}", response);
+        assert!(response.contains("projectfiles_tool_duration_seconds_total{tool=\"read\"} "));
+    }
+
+    #[tokio::test]
+    async fn test_post_batch_as_event_stream() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(axum::serve(listener, http_router(Arc::new(HttpHandler::new()))).into_future());
+
+        let body = r#"[{"jsonrpc":"2.0","id":1,"method":"ping"},{"jsonrpc":"2.0","id":2,"method":"initialize","params":{}}]"#;
+        let request = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nAccept: text/event-stream\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            MCP_ENDPOINT,
+            addr,
+            body.len(),
+            body
+        );
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        server.abort();
+
+        assert!(response.starts_with("HTTP/1.1 200"), "unexpected response: {}", response);
+        assert!(response.contains("text/event-stream"));
+        assert_eq!(response.matches("event: message").count(), 2);
+        assert!(response.contains(r#""protocolVersion":"2025-03-26""#));
+    }
+}
@@ -1,3 +1,5 @@
+pub mod http;
 pub mod stdio;
 
+pub use http::{HttpHandler, run_http_server};
 pub use stdio::{StdioHandler, run_stdio_server};
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::handler::{CoreHandler, create_server_details};
+use crate::handler::{CoreHandler, create_server_details, error_result};
 use async_trait::async_trait;
 use rust_mcp_schema::{
     CallToolRequest, CallToolResult, ListToolsRequest, ListToolsResult, RpcError,
@@ -44,7 +44,8 @@ impl ServerHandler for StdioHandler {
         _runtime: &dyn McpServer,
     ) -> std::result::Result<CallToolResult, CallToolError> {
         debug!(tool_name = %request.params.name, "Stdio transport: handling call_tool request");
-        self.core.call_tool(request).await
+        // Failures are reported in-band here so the result can carry the error code
+        Ok(self.core.call_tool(request).await.unwrap_or_else(error_result))
     }
 }
 
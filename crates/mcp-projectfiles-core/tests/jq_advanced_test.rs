@@ -42,6 +42,11 @@ async fn test_array_iteration() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -71,6 +76,11 @@ async fn test_map_operation() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -99,6 +109,11 @@ async fn test_select_operation() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -133,6 +148,11 @@ async fn test_pipe_operations() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -159,6 +179,11 @@ async fn test_keys_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -186,6 +211,11 @@ async fn test_values_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -219,6 +249,11 @@ async fn test_length_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -234,6 +269,11 @@ async fn test_length_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -249,6 +289,11 @@ async fn test_length_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -278,6 +323,11 @@ async fn test_type_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -292,6 +342,11 @@ async fn test_type_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -320,6 +375,11 @@ async fn test_comparison_in_select() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -339,6 +399,11 @@ async fn test_comparison_in_select() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -382,6 +447,11 @@ async fn test_complex_nested_operations() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -425,6 +495,11 @@ async fn test_recursive_descent() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -461,6 +536,11 @@ async fn test_wildcard_query() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -501,6 +581,11 @@ async fn test_array_wildcard() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -549,6 +634,11 @@ async fn test_complex_recursive_and_filter() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -587,6 +677,11 @@ async fn test_object_construction() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -616,6 +711,11 @@ async fn test_to_entries() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -653,6 +753,11 @@ async fn test_from_entries() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -685,6 +790,11 @@ async fn test_object_construction_with_map() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -719,10 +829,15 @@ async fn test_arithmetic_operations() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
-    assert_eq!(extract_text_content(&result).trim(), "150.0");
+    assert_eq!(extract_text_content(&result).trim(), "150");
     
     // Test multiplication
     let tool = JsonQueryTool {
@@ -733,10 +848,15 @@ async fn test_arithmetic_operations() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
-    assert_eq!(extract_text_content(&result).trim(), "300.0");
+    assert_eq!(extract_text_content(&result).trim(), "300");
     
     // Test complex expression
     let tool = JsonQueryTool {
@@ -747,6 +867,11 @@ async fn test_arithmetic_operations() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -771,6 +896,11 @@ async fn test_string_concatenation() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -794,6 +924,11 @@ async fn test_array_concatenation() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -823,6 +958,11 @@ async fn test_arithmetic_in_map() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -837,10 +977,15 @@ async fn test_arithmetic_in_map() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
-    assert_eq!(extract_text_content(&result).trim(), "50.0");
+    assert_eq!(extract_text_content(&result).trim(), "50");
 }
 
 #[tokio::test]
@@ -863,6 +1008,11 @@ async fn test_string_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -877,6 +1027,11 @@ async fn test_string_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -891,6 +1046,11 @@ async fn test_string_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -906,6 +1066,11 @@ async fn test_string_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -920,6 +1085,11 @@ async fn test_string_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -944,6 +1114,11 @@ async fn test_join_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -958,6 +1133,11 @@ async fn test_join_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -984,6 +1164,11 @@ async fn test_string_functions_in_map() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -998,6 +1183,11 @@ async fn test_string_functions_in_map() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1024,6 +1214,11 @@ async fn test_if_then_else_basic() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1038,6 +1233,11 @@ async fn test_if_then_else_basic() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1052,6 +1252,11 @@ async fn test_if_then_else_basic() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1076,6 +1281,11 @@ async fn test_if_then_else_nested() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1104,6 +1314,11 @@ async fn test_if_then_else_with_expressions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1118,6 +1333,11 @@ async fn test_if_then_else_with_expressions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1147,6 +1367,11 @@ async fn test_if_then_else_in_map() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1180,6 +1405,11 @@ async fn test_boolean_operators() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1194,6 +1424,11 @@ async fn test_boolean_operators() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1208,6 +1443,11 @@ async fn test_boolean_operators() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1222,6 +1462,11 @@ async fn test_boolean_operators() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1250,6 +1495,11 @@ async fn test_alternative_operator() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1264,6 +1514,11 @@ async fn test_alternative_operator() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1278,6 +1533,11 @@ async fn test_alternative_operator() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1292,6 +1552,11 @@ async fn test_alternative_operator() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1320,6 +1585,11 @@ async fn test_optional_operator() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1334,6 +1604,11 @@ async fn test_optional_operator() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1349,6 +1624,11 @@ async fn test_optional_operator() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1364,6 +1644,11 @@ async fn test_optional_operator() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1390,6 +1675,11 @@ async fn test_try_catch() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1404,6 +1694,11 @@ async fn test_try_catch() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1427,6 +1722,11 @@ async fn test_array_add_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1446,6 +1746,11 @@ async fn test_array_add_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1469,6 +1774,11 @@ async fn test_array_min_max_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1485,6 +1795,11 @@ async fn test_array_min_max_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1507,6 +1822,11 @@ async fn test_array_unique_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1531,6 +1851,11 @@ async fn test_array_reverse_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1555,6 +1880,11 @@ async fn test_array_sort_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1574,6 +1904,11 @@ async fn test_array_sort_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1601,6 +1936,11 @@ async fn test_array_sort_by_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1624,6 +1964,11 @@ async fn test_array_sort_by_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1655,6 +2000,11 @@ async fn test_array_flatten_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1671,6 +2021,11 @@ async fn test_array_flatten_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1699,6 +2054,11 @@ async fn test_array_group_by_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1738,6 +2098,11 @@ async fn test_array_slicing() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1754,6 +2119,11 @@ async fn test_array_slicing() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1770,6 +2140,11 @@ async fn test_array_slicing() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1791,6 +2166,11 @@ async fn test_array_slicing() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1815,6 +2195,11 @@ async fn test_indices_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1834,6 +2219,11 @@ async fn test_indices_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1861,6 +2251,11 @@ async fn test_has_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1877,6 +2272,11 @@ async fn test_has_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1905,6 +2305,11 @@ async fn test_del_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1936,6 +2341,11 @@ async fn test_with_entries_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1976,6 +2386,11 @@ async fn test_paths_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -2015,6 +2430,11 @@ async fn test_string_test_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -2034,6 +2454,11 @@ async fn test_string_test_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -2057,6 +2482,11 @@ async fn test_string_match_function() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -2085,6 +2515,11 @@ async fn test_string_trim_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -2104,6 +2539,11 @@ async fn test_string_trim_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -2128,6 +2568,11 @@ async fn test_math_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -2147,6 +2592,11 @@ async fn test_math_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -2166,6 +2616,11 @@ async fn test_math_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -2185,6 +2640,11 @@ async fn test_math_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -2204,6 +2664,11 @@ async fn test_math_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -2228,6 +2693,11 @@ async fn test_debugging_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -2245,6 +2715,11 @@ async fn test_debugging_functions() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await;
@@ -2253,4 +2728,506 @@ async fn test_debugging_functions() {
         let error_msg = e.to_string();
         assert!(error_msg.contains("This is an error message"));
     }
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_tonumber_explode_implode() {
+    let (context, temp_dir) = setup_test_context().await;
+    let content = json!({
+        "int_str": "42",
+        "word": "Hi",
+        "codes": [72, 105],
+        "bad_codes": [72, "x"]
+    });
+    create_test_file(&temp_dir, "test.json", &content.to_string()).await;
+    
+    let query = |q: &str| JsonQueryTool {
+        file_path: "test.json".to_string(),
+        query: q.to_string(),
+        operation: "read".to_string(),
+        output_format: "json".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
+    };
+    
+    // Integer strings stay integers
+    let result = query(".int_str | tonumber").call_with_context(&context).await.unwrap();
+    assert_eq!(extract_text_content(&result).trim(), "42");
+    
+    let result = query(".word | explode").call_with_context(&context).await.unwrap();
+    let value: serde_json::Value = serde_json::from_str(extract_text_content(&result)).unwrap();
+    assert_eq!(value, json!([72, 105]));
+    
+    let result = query(".codes | implode").call_with_context(&context).await.unwrap();
+    assert_eq!(extract_text_content(&result).trim(), "\"Hi\"");
+    
+    let result = query(".bad_codes | implode").call_with_context(&context).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_slurp_ndjson() {
+    let (context, temp_dir) = setup_test_context().await;
+    let ndjson: String = (0..100)
+        .map(|i| format!("{{\"id\": {}, \"level\": \"{}\"}}\n", i, if i % 10 == 0 { "error" } else { "info" }))
+        .collect();
+    create_test_file(&temp_dir, "log.ndjson", &format!("{}\n", ndjson)).await;
+    
+    let tool = JsonQueryTool {
+        file_path: "log.ndjson".to_string(),
+        query: "length".to_string(),
+        operation: "read".to_string(),
+        output_format: "raw".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: true,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
+    };
+    let result = tool.call_with_context(&context).await.unwrap();
+    assert_eq!(extract_text_content(&result).trim(), "100");
+    
+    let tool = JsonQueryTool {
+        file_path: "log.ndjson".to_string(),
+        query: "map(select(.level == \"error\")) | length".to_string(),
+        operation: "read".to_string(),
+        output_format: "raw".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: true,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
+    };
+    let result = tool.call_with_context(&context).await.unwrap();
+    assert_eq!(extract_text_content(&result).trim(), "10");
+    
+    // Without slurp the file is not a single JSON document
+    let tool = JsonQueryTool {
+        file_path: "log.ndjson".to_string(),
+        query: "length".to_string(),
+        operation: "read".to_string(),
+        output_format: "raw".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
+    };
+    assert!(tool.call_with_context(&context).await.is_err());
+}
+
+#[tokio::test]
+async fn test_in_place_write_is_atomic() {
+    let (context, temp_dir) = setup_test_context().await;
+    let records: Vec<serde_json::Value> = (0..50_000)
+        .map(|i| json!({"id": i, "name": format!("record-{}", i), "tags": ["a", "b", "c"]}))
+        .collect();
+    let file_path = create_test_file(&temp_dir, "large.json", &json!({"records": records}).to_string()).await;
+    
+    let original_len = std::fs::metadata(&file_path).unwrap().len();
+    let original_content = fs::read_to_string(&file_path).await.unwrap();
+    
+    // A second link to the original inode only keeps its content if the file is replaced, not rewritten
+    let pinned = temp_dir.path().join("pinned.json");
+    std::fs::hard_link(&file_path, &pinned).unwrap();
+    
+    // Poll the file while it is rewritten. The pretty-printed result is larger than the compact
+    // original, so a truncated or half-written file shows up as a shorter length.
+    let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let reader = {
+        let stop = stop.clone();
+        let file_path = file_path.clone();
+        std::thread::spawn(move || {
+            while !stop.load(std::sync::atomic::Ordering::Relaxed) {
+                let len = std::fs::metadata(&file_path).unwrap().len();
+                assert!(len >= original_len, "observed a partially written file ({} of {} bytes)", len, original_len);
+            }
+        })
+    };
+    
+    let tool = JsonQueryTool {
+        file_path: "large.json".to_string(),
+        query: ".updated = true".to_string(),
+        operation: "write".to_string(),
+        output_format: "json".to_string(),
+        in_place: true,
+        backup: true,
+        follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
+    };
+    let result = tool.call_with_context(&context).await;
+    stop.store(true, std::sync::atomic::Ordering::Relaxed);
+    reader.join().expect("reader saw a partially written file");
+    result.unwrap();
+    
+    let written: serde_json::Value = serde_json::from_str(&fs::read_to_string(&file_path).await.unwrap()).unwrap();
+    assert_eq!(written["updated"], true);
+    assert_eq!(written["records"].as_array().unwrap().len(), 50_000);
+    assert_eq!(fs::read_to_string(&pinned).await.unwrap(), original_content);
+    assert!(temp_dir.path().join("large.json.bak").exists());
+    assert!(!temp_dir.path().join("large.json.tmp").exists());
+}
+
+#[tokio::test]
+async fn test_indent_and_sort_keys() {
+    let (context, temp_dir) = setup_test_context().await;
+    let file_path = create_test_file(&temp_dir, "config.json", r#"{"zeta": 1, "alpha": {"b": 2, "a": 1}}"#).await;
+    
+    let tool = JsonQueryTool {
+        file_path: "config.json".to_string(),
+        query: ".alpha".to_string(),
+        operation: "read".to_string(),
+        output_format: "json".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        indent: Some(0),
+        sort_keys: true,
+        schema_path: None,
+        pattern: false,
+    };
+    let result = tool.call_with_context(&context).await.unwrap();
+    assert_eq!(extract_text_content(&result), "{\n\t\"a\": 1,\n\t\"b\": 2\n}");
+    
+    let tool = JsonQueryTool {
+        file_path: "config.json".to_string(),
+        query: ".beta = true".to_string(),
+        operation: "write".to_string(),
+        output_format: "json".to_string(),
+        in_place: true,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        indent: Some(4),
+        sort_keys: true,
+        schema_path: None,
+        pattern: false,
+    };
+    tool.call_with_context(&context).await.unwrap();
+    let written = fs::read_to_string(&file_path).await.unwrap();
+    assert_eq!(
+        written,
+        "{\n    \"alpha\": {\n        \"a\": 1,\n        \"b\": 2\n    },\n    \"beta\": true,\n    \"zeta\": 1\n}"
+    );
+    
+    let tool = JsonQueryTool {
+        file_path: "config.json".to_string(),
+        query: ".".to_string(),
+        operation: "read".to_string(),
+        output_format: "json".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        indent: Some(100),
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
+    };
+    assert!(tool.call_with_context(&context).await.is_err());
+}
+
+#[tokio::test]
+async fn test_integer_arithmetic_stays_integral() {
+    let (context, temp_dir) = setup_test_context().await;
+    create_test_file(&temp_dir, "test.json", r#"{"count": 7, "limit": 100, "ratio": 0.5}"#).await;
+    
+    let cases = [
+        (".limit / 4", "25"),
+        (".count / 2", "3.5"),
+        (".count % 3", "1"),
+        (".limit - .count", "93"),
+        (".count * .ratio", "3.5"),
+        (".limit * 1.0", "100.0"),
+    ];
+    for (query, expected) in cases {
+        let tool = JsonQueryTool {
+            file_path: "test.json".to_string(),
+            query: query.to_string(),
+            operation: "read".to_string(),
+            output_format: "raw".to_string(),
+            in_place: false,
+            backup: false,
+            follow_symlinks: true,
+            slurp: false,
+            indent: None,
+            sort_keys: false,
+            schema_path: None,
+            pattern: false,
+        };
+        let result = tool.call_with_context(&context).await.unwrap();
+        assert_eq!(extract_text_content(&result).trim(), expected, "query: {}", query);
+    }
+}
+
+#[tokio::test]
+async fn test_reduce_expression() {
+    let (context, temp_dir) = setup_test_context().await;
+    create_test_file(&temp_dir, "test.json", r#"{"numbers": [1, 2, 3, 4, 5], "items": [{"price": 3}, {"price": 4}]}"#).await;
+    
+    let cases = [
+        ("reduce .numbers[] as $n (0; . + $n)", "15"),
+        ("reduce .numbers[] as $n (1; . * $n)", "120"),
+        ("reduce .items[] as $item (0; . + $item.price)", "7"),
+        ("reduce .numbers[] as $n (0; . + $n) | . * 2", "30"),
+    ];
+    for (query, expected) in cases {
+        let tool = JsonQueryTool {
+            file_path: "test.json".to_string(),
+            query: query.to_string(),
+            operation: "read".to_string(),
+            output_format: "raw".to_string(),
+            in_place: false,
+            backup: false,
+            follow_symlinks: true,
+            slurp: false,
+            indent: None,
+            sort_keys: false,
+            schema_path: None,
+            pattern: false,
+        };
+        let result = tool.call_with_context(&context).await.unwrap();
+        assert_eq!(extract_text_content(&result).trim(), expected, "query: {}", query);
+    }
+}
+
+#[tokio::test]
+async fn test_index_function() {
+    let (context, temp_dir) = setup_test_context().await;
+    let content = json!({
+        "users": [
+            {"id": 1, "name": "alice"},
+            {"id": 2, "name": "bob"}
+        ]
+    });
+    create_test_file(&temp_dir, "users.json", &content.to_string()).await;
+    
+    for query in [".users | INDEX(.id)", "INDEX(.users[]; .id)"] {
+        let tool = JsonQueryTool {
+            file_path: "users.json".to_string(),
+            query: query.to_string(),
+            operation: "read".to_string(),
+            output_format: "json".to_string(),
+            in_place: false,
+            backup: false,
+            follow_symlinks: true,
+            slurp: false,
+            indent: None,
+            sort_keys: false,
+            schema_path: None,
+            pattern: false,
+        };
+        
+        let result = tool.call_with_context(&context).await.unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(extract_text_content(&result)).unwrap();
+        assert_eq!(
+            parsed,
+            json!({
+                "1": {"id": 1, "name": "alice"},
+                "2": {"id": 2, "name": "bob"}
+            }),
+            "query: {}",
+            query
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_variable_bindings() {
+    let (context, temp_dir) = setup_test_context().await;
+    let content = json!({
+        "items": [1, 2, 3],
+        "factor": 10,
+        "inner": 2,
+        "users": [{"name": "alice"}, {"name": "bob"}]
+    });
+    create_test_file(&temp_dir, "test.json", &content.to_string()).await;
+    
+    let cases = [
+        (".items as $i | $i | length", json!(3)),
+        (".factor as $f | .items | map(. * $f)", json!([10, 20, 30])),
+        (".factor as $x | .inner as $x | $x", json!(2)),
+        (".factor as $x | (.inner as $x | $x) + $x", json!(12)),
+        (".users[] as $u | $u.name", json!(["alice", "bob"])),
+        (".users | map(.name as $n | {user: $n})", json!([{"user": "alice"}, {"user": "bob"}])),
+        (".inner as $inner | {$inner}", json!({"inner": 2})),
+    ];
+    for (query, expected) in cases {
+        let tool = JsonQueryTool {
+            file_path: "test.json".to_string(),
+            query: query.to_string(),
+            operation: "read".to_string(),
+            output_format: "json".to_string(),
+            in_place: false,
+            backup: false,
+            follow_symlinks: true,
+            slurp: false,
+            indent: None,
+            sort_keys: false,
+            schema_path: None,
+            pattern: false,
+        };
+        let result = tool.call_with_context(&context).await.unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(extract_text_content(&result)).unwrap();
+        assert_eq!(parsed, expected, "query: {}", query);
+    }
+    
+    let tool = JsonQueryTool {
+        file_path: "test.json".to_string(),
+        query: "$undefined".to_string(),
+        operation: "read".to_string(),
+        output_format: "json".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
+    };
+    assert!(tool.call_with_context(&context).await.is_err());
+}
+
+#[tokio::test]
+async fn test_update_assignment_in_place() {
+    let (context, temp_dir) = setup_test_context().await;
+    let content = json!({
+        "users": [
+            {"name": "alice", "active": false, "age": 30},
+            {"name": "bob", "age": 25},
+            {"name": "carol", "active": false, "age": 41}
+        ]
+    });
+    let file_path = create_test_file(&temp_dir, "users.json", &content.to_string()).await;
+    
+    for query in [".users[].active |= true", ".users[].age |= . + 1", ".users[].role = \"member\""] {
+        let tool = JsonQueryTool {
+            file_path: "users.json".to_string(),
+            query: query.to_string(),
+            operation: "write".to_string(),
+            output_format: "json".to_string(),
+            in_place: true,
+            backup: false,
+            follow_symlinks: true,
+            slurp: false,
+            indent: None,
+            sort_keys: false,
+            schema_path: None,
+            pattern: false,
+        };
+        tool.call_with_context(&context).await.unwrap();
+    }
+    
+    let written: serde_json::Value = serde_json::from_str(&fs::read_to_string(&file_path).await.unwrap()).unwrap();
+    assert_eq!(
+        written,
+        json!({
+            "users": [
+                {"name": "alice", "active": true, "age": 31, "role": "member"},
+                {"name": "bob", "active": true, "age": 26, "role": "member"},
+                {"name": "carol", "active": true, "age": 42, "role": "member"}
+            ]
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_paths_with_filter() {
+    let (context, temp_dir) = setup_test_context().await;
+    create_test_file(&temp_dir, "test.json", r#"{"a": 1, "b": {"c": "x", "d": 2}, "e": [3, "y"]}"#).await;
+    
+    let tool = JsonQueryTool {
+        file_path: "test.json".to_string(),
+        query: "paths(type == \"number\")".to_string(),
+        operation: "read".to_string(),
+        output_format: "json".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
+    };
+    let result = tool.call_with_context(&context).await.unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(extract_text_content(&result)).unwrap();
+    assert_eq!(parsed, json!([["a"], ["b", "d"], ["e", "0"]]));
+}
+
+#[tokio::test]
+async fn test_validate_against_json_schema() {
+    let (context, temp_dir) = setup_test_context().await;
+    let schema = json!({
+        "type": "object",
+        "required": ["name", "port"],
+        "properties": {
+            "name": {"type": "string"},
+            "port": {"type": "integer", "minimum": 1}
+        }
+    });
+    create_test_file(&temp_dir, "schema.json", &schema.to_string()).await;
+    create_test_file(&temp_dir, "good.json", r#"{"name": "api", "port": 8080}"#).await;
+    create_test_file(&temp_dir, "bad.json", r#"{"port": "80"}"#).await;
+    
+    let validate = |file: &str, schema_path: &str| JsonQueryTool {
+        file_path: file.to_string(),
+        query: String::new(),
+        operation: "validate".to_string(),
+        output_format: "json".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: Some(schema_path.to_string()),
+        pattern: false,
+    };
+    
+    let result = validate("good.json", "schema.json").call_with_context(&context).await.unwrap();
+    let report: serde_json::Value = serde_json::from_str(extract_text_content(&result)).unwrap();
+    assert_eq!(report, json!({"valid": true, "errors": []}));
+    
+    let result = validate("bad.json", "schema.json").call_with_context(&context).await.unwrap();
+    let report: serde_json::Value = serde_json::from_str(extract_text_content(&result)).unwrap();
+    assert_eq!(report["valid"], false);
+    let errors = report["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 2, "errors: {:?}", errors);
+    assert!(errors.iter().any(|e| e["instance_path"] == "/port" && e["message"].as_str().unwrap().contains("integer")));
+    assert!(errors.iter().any(|e| e["instance_path"] == "" && e["message"].as_str().unwrap().contains("name")));
+    
+    // The schema must live inside the project too
+    let outside_dir = TempDir::new().unwrap();
+    let outside = outside_dir.path().join("schema.json");
+    fs::write(&outside, schema.to_string()).await.unwrap();
+    let error = validate("good.json", outside.to_str().unwrap()).call_with_context(&context).await.unwrap_err();
+    assert!(error.to_string().contains("outside"), "unexpected error: {}", error);
+    
+    // schema_path is rejected for non-validate operations
+    let mut read = validate("good.json", "schema.json");
+    read.operation = "read".to_string();
+    read.query = ".".to_string();
+    assert!(read.call_with_context(&context).await.is_err());
+}
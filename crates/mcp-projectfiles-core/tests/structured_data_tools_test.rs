@@ -1,4 +1,4 @@
-use mcp_projectfiles_core::tools::{JsonQueryTool, YamlQueryTool, TomlQueryTool};
+use mcp_projectfiles_core::tools::{JsonQueryTool, YamlQueryTool, TomlQueryTool, XmlQueryTool};
 use mcp_projectfiles_core::context::ToolContext;
 use mcp_projectfiles_core::StatefulTool;
 use mcp_projectfiles_core::protocol::CallToolResultContentItem;
@@ -57,6 +57,11 @@ async fn test_jq_tool_read_basic() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await;
@@ -74,6 +79,11 @@ async fn test_jq_tool_read_basic() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = nested_tool.call_with_context(&context).await;
@@ -121,6 +131,11 @@ async fn test_jq_tool_complex_paths() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await;
@@ -138,6 +153,11 @@ async fn test_jq_tool_complex_paths() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = array_tool.call_with_context(&context).await;
@@ -169,6 +189,11 @@ async fn test_jq_tool_write_operations() {
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     let _ = read_tool.call_with_context(&context).await;
     
@@ -181,6 +206,11 @@ async fn test_jq_tool_write_operations() {
         in_place: true,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await;
@@ -222,6 +252,8 @@ config:
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await;
@@ -238,6 +270,8 @@ config:
         in_place: false,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = nested_tool.call_with_context(&context).await;
@@ -655,4 +689,572 @@ std = []
     assert!(result.is_ok());
     let output = extract_text_content(&result.unwrap());
     assert_eq!(output.trim(), "default");
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+#[serial]
+async fn test_tomlq_write_preserves_document() {
+    let (temp_dir, context) = setup_test_env();
+    let temp_path = temp_dir.path();
+    
+    let toml_content = r#"# Package manifest
+[package]
+name = "demo"
+version = "1.0.0" # bumped on release
+edition = "2021"
+
+[dependencies]
+serde = "1.0"
+"#;
+    fs::write(temp_path.join("Cargo.toml"), toml_content).unwrap();
+    
+    // Files must be read before they can be written
+    let read_tool = TomlQueryTool {
+        file_path: "Cargo.toml".to_string(),
+        query: ".package.version".to_string(),
+        operation: "read".to_string(),
+        output_format: "raw".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+    };
+    read_tool.clone().call_with_context(&context).await.unwrap();
+    
+    let write_tool = TomlQueryTool {
+        file_path: "Cargo.toml".to_string(),
+        query: ".package.version = \"2.0.0\"".to_string(),
+        operation: "write".to_string(),
+        output_format: "toml".to_string(),
+        in_place: true,
+        backup: true,
+        follow_symlinks: true,
+    };
+    let result = write_tool.call_with_context(&context).await;
+    assert!(result.is_ok());
+    
+    let output = extract_text_content(&read_tool.call_with_context(&context).await.unwrap());
+    assert_eq!(output.trim(), "2.0.0");
+    
+    // Comments, ordering and untouched keys survive the edit
+    let written = fs::read_to_string(temp_path.join("Cargo.toml")).unwrap();
+    assert_eq!(written, toml_content.replace("1.0.0", "2.0.0"));
+    
+    let backup = fs::read_to_string(temp_path.join("Cargo.toml.bak")).unwrap();
+    assert_eq!(backup, toml_content);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_tomlq_write_changes_only_target_line() {
+    let (temp_dir, context) = setup_test_env();
+    let temp_path = temp_dir.path();
+    
+    let toml_content = r#"# Workspace member manifest
+# Keep this file sorted by hand.
+
+[package]
+name = "demo"       # crate name
+version = "0.3.1"
+authors = ["someone"]
+
+# Runtime dependencies
+[dependencies]
+# async runtime
+tokio = { version = "1", features = ["full"] } # pinned major
+serde = { version = "1.0", features = ["derive"] }
+
+[features]
+# nothing enabled by default
+default = []
+"#;
+    fs::write(temp_path.join("Cargo.toml"), toml_content).unwrap();
+    
+    let read_tool = TomlQueryTool {
+        file_path: "Cargo.toml".to_string(),
+        query: ".".to_string(),
+        operation: "read".to_string(),
+        output_format: "json".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+    };
+    read_tool.call_with_context(&context).await.unwrap();
+    
+    let write_tool = TomlQueryTool {
+        file_path: "Cargo.toml".to_string(),
+        query: ".dependencies.serde.version = \"1.0.200\"".to_string(),
+        operation: "write".to_string(),
+        output_format: "toml".to_string(),
+        in_place: true,
+        backup: false,
+        follow_symlinks: true,
+    };
+    write_tool.call_with_context(&context).await.unwrap();
+    
+    let written = fs::read_to_string(temp_path.join("Cargo.toml")).unwrap();
+    let changed: Vec<(&str, &str)> = toml_content.lines()
+        .zip(written.lines())
+        .filter(|(before, after)| before != after)
+        .collect();
+    
+    assert_eq!(toml_content.lines().count(), written.lines().count());
+    assert_eq!(changed, vec![(
+        r#"serde = { version = "1.0", features = ["derive"] }"#,
+        r#"serde = { version = "1.0.200", features = ["derive"] }"#,
+    )]);
+}
+
+// Format strings are shared by the jq, yq, and tomlq engines
+#[tokio::test]
+#[serial]
+async fn test_format_strings_across_query_tools() {
+    let (temp_dir, context) = setup_test_env();
+    fs::write(
+        temp_dir.path().join("data.json"),
+        r#"{"row": ["plain", "has,comma", "say \"hi\"", 42, true, null], "secret": "hello world"}"#,
+    ).unwrap();
+    fs::write(
+        temp_dir.path().join("data.yaml"),
+        "row:\n  - plain\n  - \"has,comma\"\n  - 'say \"hi\"'\n  - 42\n  - true\n  - null\nsecret: hello world\n",
+    ).unwrap();
+    fs::write(
+        temp_dir.path().join("data.toml"),
+        "row = [\"plain\", \"has,comma\", 'say \"hi\"']\nsecret = \"hello world\"\n",
+    ).unwrap();
+    
+    let jq = |query: &str| JsonQueryTool {
+        file_path: "data.json".to_string(),
+        query: query.to_string(),
+        operation: "read".to_string(),
+        output_format: "raw".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
+    };
+    let cases = [
+        (".row | @csv", r#""plain","has,comma","say ""hi""",42,true,"#),
+        (".row | @tsv", "plain\thas,comma\tsay \"hi\"\t42\ttrue\t"),
+        (".secret | @base64", "aGVsbG8gd29ybGQ="),
+        (".secret | @base64 | @base64d", "hello world"),
+    ];
+    for (query, expected) in cases {
+        let output = extract_text_content(&jq(query).call_with_context(&context).await.unwrap());
+        assert_eq!(output, expected, "query: {}", query);
+    }
+    assert!(jq(".secret | @csv").call_with_context(&context).await.is_err());
+    
+    let yq = YamlQueryTool {
+        file_path: "data.yaml".to_string(),
+        query: ".row | @csv".to_string(),
+        operation: "read".to_string(),
+        output_format: "raw".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        pattern: false,
+    };
+    let output = extract_text_content(&yq.call_with_context(&context).await.unwrap());
+    assert_eq!(output.trim(), r#""plain","has,comma","say ""hi""",42,true,"#);
+    
+    let tomlq = TomlQueryTool {
+        file_path: "data.toml".to_string(),
+        query: ".secret | @base64".to_string(),
+        operation: "read".to_string(),
+        output_format: "raw".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+    };
+    let output = extract_text_content(&tomlq.call_with_context(&context).await.unwrap());
+    assert_eq!(output.trim(), "aGVsbG8gd29ybGQ=");
+    
+    let tomlq = TomlQueryTool {
+        file_path: "data.toml".to_string(),
+        query: ".row | @csv".to_string(),
+        operation: "read".to_string(),
+        output_format: "raw".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+    };
+    let output = extract_text_content(&tomlq.call_with_context(&context).await.unwrap());
+    assert_eq!(output.trim(), r#""plain","has,comma","say ""hi""""#);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_query_syntax_errors_point_at_position() {
+    let (temp_dir, context) = setup_test_env();
+    fs::write(temp_dir.path().join("data.json"), r#"{"items": [1, 2]}"#).unwrap();
+    fs::write(temp_dir.path().join("data.yaml"), "items:\n  - 1\n  - 2\n").unwrap();
+    fs::write(temp_dir.path().join("data.toml"), "items = [1, 2]\n").unwrap();
+    
+    let jq = |query: &str| JsonQueryTool {
+        file_path: "data.json".to_string(),
+        query: query.to_string(),
+        operation: "read".to_string(),
+        output_format: "json".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
+    };
+    let cases = [
+        (".items | map(. * 2", "unclosed '(' at column 13\n  .items | map(. * 2\n              ^"),
+        (".items | map(. * 2]", "expected ')' but found ']' at column 19\n  .items | map(. * 2]\n                    ^"),
+        (".items | ", "missing expression after '|' at column 8\n  .items | \n         ^"),
+        (".items[0] + ", "missing expression after '+' at column 11\n  .items[0] + \n            ^"),
+        ("select(.name == \"x)", "unterminated string at column 17\n  select(.name == \"x)\n                  ^"),
+    ];
+    for (query, expected) in cases {
+        let error = jq(query).call_with_context(&context).await.unwrap_err().to_string();
+        assert!(error.contains(expected), "query: {}\nerror: {}", query, error);
+    }
+    
+    let yq = YamlQueryTool {
+        file_path: "data.yaml".to_string(),
+        query: ".items | map(. * 2".to_string(),
+        operation: "read".to_string(),
+        output_format: "json".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        pattern: false,
+    };
+    let error = yq.call_with_context(&context).await.unwrap_err().to_string();
+    assert!(error.contains("unclosed '(' at column 13\n  .items | map(. * 2\n              ^"), "error: {}", error);
+    
+    let tomlq = TomlQueryTool {
+        file_path: "data.toml".to_string(),
+        query: ".items | map(. * 2".to_string(),
+        operation: "read".to_string(),
+        output_format: "json".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+    };
+    let error = tomlq.call_with_context(&context).await.unwrap_err().to_string();
+    assert!(error.contains("unclosed '(' at column 13\n  .items | map(. * 2\n              ^"), "error: {}", error);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_validate_operation_across_query_tools() {
+    let (temp_dir, context) = setup_test_env();
+    fs::write(temp_dir.path().join("good.json"), r#"{"a": 1}"#).unwrap();
+    fs::write(temp_dir.path().join("bad.json"), "{\"a\": 1,\n  \"b\": }\n").unwrap();
+    fs::write(temp_dir.path().join("multi.yaml"), "a: 1\n---\nb: 2\n---\nc: 3\n").unwrap();
+    fs::write(temp_dir.path().join("bad.yaml"), "a: 1\nb: [1, 2\n").unwrap();
+    fs::write(temp_dir.path().join("good.toml"), "[package]\nname = \"demo\"\n").unwrap();
+    fs::write(temp_dir.path().join("bad.toml"), "[package]\nname = \n").unwrap();
+    
+    let jq = |file: &str| JsonQueryTool {
+        file_path: file.to_string(),
+        query: String::new(),
+        operation: "validate".to_string(),
+        output_format: "json".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
+    };
+    let report = |output: String| serde_json::from_str::<serde_json::Value>(&output).unwrap();
+    
+    let valid = report(extract_text_content(&jq("good.json").call_with_context(&context).await.unwrap()));
+    assert_eq!(valid, serde_json::json!({"valid": true}));
+    let invalid = report(extract_text_content(&jq("bad.json").call_with_context(&context).await.unwrap()));
+    assert_eq!(invalid["valid"], false);
+    assert_eq!(invalid["error"], "expected value");
+    assert_eq!((invalid["line"].as_u64(), invalid["column"].as_u64()), (Some(2), Some(8)));
+    
+    let yq = |file: &str| YamlQueryTool {
+        file_path: file.to_string(),
+        query: String::new(),
+        operation: "validate".to_string(),
+        output_format: "yaml".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        pattern: false,
+    };
+    let valid = report(extract_text_content(&yq("multi.yaml").call_with_context(&context).await.unwrap()));
+    assert_eq!(valid, serde_json::json!({"valid": true, "documents": 3}));
+    let invalid = report(extract_text_content(&yq("bad.yaml").call_with_context(&context).await.unwrap()));
+    assert_eq!(invalid["valid"], false);
+    assert!(invalid["line"].as_u64().is_some() && invalid["column"].as_u64().is_some(), "report: {}", invalid);
+    
+    let tomlq = |file: &str| TomlQueryTool {
+        file_path: file.to_string(),
+        query: String::new(),
+        operation: "validate".to_string(),
+        output_format: "toml".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+    };
+    let valid = report(extract_text_content(&tomlq("good.toml").call_with_context(&context).await.unwrap()));
+    assert_eq!(valid, serde_json::json!({"valid": true}));
+    let invalid = report(extract_text_content(&tomlq("bad.toml").call_with_context(&context).await.unwrap()));
+    assert_eq!(invalid["valid"], false);
+    assert_eq!((invalid["line"].as_u64(), invalid["column"].as_u64()), (Some(2), Some(8)));
+    
+    // A missing file is still an error rather than an invalid report
+    assert!(jq("missing.json").call_with_context(&context).await.is_err());
+}
+
+// XQ Tool Tests
+#[tokio::test]
+#[serial]
+async fn test_xq_tool_read_conventions() {
+    let (temp_dir, context) = setup_test_env();
+    
+    let xml_content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- build configuration -->
+<project version="2.1">
+    <name>demo &amp; co</name>
+    <server host="localhost" port="8080">primary</server>
+    <dependency scope="test">junit</dependency>
+    <dependency scope="compile">serde</dependency>
+    <empty/>
+    <script><![CDATA[a < b]]></script>
+</project>"#;
+    fs::write(temp_dir.path().join("project.xml"), xml_content).unwrap();
+    
+    let xq = |query: &str, output_format: &str| XmlQueryTool {
+        file_path: "project.xml".to_string(),
+        query: query.to_string(),
+        operation: "read".to_string(),
+        output_format: output_format.to_string(),
+        follow_symlinks: true,
+    };
+    let query = |query: &str| {
+        let tool = xq(query, "json");
+        let context = &context;
+        async move {
+            let output = extract_text_content(&tool.call_with_context(context).await.unwrap());
+            serde_json::from_str::<serde_json::Value>(&output).unwrap()
+        }
+    };
+    
+    assert_eq!(query(".project.@version").await, "2.1");
+    assert_eq!(query(".project.name").await, "demo & co");
+    assert_eq!(
+        query(".project.server").await,
+        serde_json::json!({"@host": "localhost", "@port": "8080", "#text": "primary"})
+    );
+    assert_eq!(query(".project.dependency | map(.#text)").await, serde_json::json!(["junit", "serde"]));
+    assert_eq!(query(".project.empty").await, serde_json::Value::Null);
+    assert_eq!(query(".project.script").await, "a < b");
+    
+    let raw = extract_text_content(&xq(".project.name", "raw").call_with_context(&context).await.unwrap());
+    assert_eq!(raw, "demo & co");
+}
+
+#[tokio::test]
+#[serial]
+async fn test_xq_tool_validate_and_read_only() {
+    let (temp_dir, context) = setup_test_env();
+    fs::write(temp_dir.path().join("good.xml"), "<a><b>1</b></a>").unwrap();
+    fs::write(temp_dir.path().join("bad.xml"), "<a>\n  <b>1</c>\n</a>").unwrap();
+    fs::write(temp_dir.path().join("unclosed.xml"), "<a><b>1</b>").unwrap();
+    
+    let xq = |file: &str, operation: &str| XmlQueryTool {
+        file_path: file.to_string(),
+        query: ".a".to_string(),
+        operation: operation.to_string(),
+        output_format: "json".to_string(),
+        follow_symlinks: true,
+    };
+    let report = |output: String| serde_json::from_str::<serde_json::Value>(&output).unwrap();
+    
+    let valid = report(extract_text_content(&xq("good.xml", "validate").call_with_context(&context).await.unwrap()));
+    assert_eq!(valid, serde_json::json!({"valid": true}));
+    let invalid = report(extract_text_content(&xq("bad.xml", "validate").call_with_context(&context).await.unwrap()));
+    assert_eq!(invalid["valid"], false);
+    assert_eq!(invalid["line"], 2, "report: {}", invalid);
+    let unclosed = report(extract_text_content(&xq("unclosed.xml", "validate").call_with_context(&context).await.unwrap()));
+    assert_eq!(unclosed["error"], "unclosed element <a>");
+    
+    let error = xq("bad.xml", "read").call_with_context(&context).await.unwrap_err();
+    assert!(error.to_string().contains("Invalid XML"), "error: {}", error);
+    
+    let error = xq("good.xml", "write").call_with_context(&context).await.unwrap_err();
+    assert!(error.to_string().contains("Must be 'read' or 'validate'"), "error: {}", error);
+    assert!(xq("../good.xml", "read").call_with_context(&context).await.is_err());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_pattern_queries_across_files() {
+    let (temp_dir, context) = setup_test_env();
+    fs::create_dir_all(temp_dir.path().join("services")).unwrap();
+    fs::write(temp_dir.path().join("services/api.yaml"), "name: api\nport: 8080\n").unwrap();
+    fs::write(temp_dir.path().join("services/web.yaml"), "name: web\nport: 3000\n").unwrap();
+    fs::write(temp_dir.path().join("services/broken.yaml"), "name: [unclosed\n").unwrap();
+    fs::write(temp_dir.path().join("a.json"), r#"{"version": 1}"#).unwrap();
+    fs::write(temp_dir.path().join("b.json"), r#"{"version": 2}"#).unwrap();
+    
+    let yq = |file: &str, query: &str, operation: &str, pattern: bool| YamlQueryTool {
+        file_path: file.to_string(),
+        query: query.to_string(),
+        operation: operation.to_string(),
+        output_format: "json".to_string(),
+        in_place: true,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        pattern,
+    };
+    let output = extract_text_content(&yq("services/*.yaml", ".port", "read", true).call_with_context(&context).await.unwrap());
+    let results: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(results["services/api.yaml"], 8080);
+    assert_eq!(results["services/web.yaml"], 3000);
+    assert!(results["services/broken.yaml"]["error"].as_str().unwrap().contains("Invalid YAML"), "results: {}", results);
+    
+    // Every matched file now counts as read, so it can be written without a separate read
+    yq("services/web.yaml", ".port = 3001", "write", false).call_with_context(&context).await.unwrap();
+    assert!(fs::read_to_string(temp_dir.path().join("services/web.yaml")).unwrap().contains("3001"));
+    
+    let error = yq("services/*.yaml", ".port = 1", "write", true).call_with_context(&context).await.unwrap_err();
+    assert!(error.to_string().contains("only supported for read"), "error: {}", error);
+    assert!(yq("../*.yaml", ".port", "read", true).call_with_context(&context).await.is_err());
+    assert!(yq("*.toml", ".port", "read", true).call_with_context(&context).await.is_err());
+    
+    let jq = JsonQueryTool {
+        file_path: "*.json".to_string(),
+        query: ".version".to_string(),
+        operation: "read".to_string(),
+        output_format: "compact".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: true,
+    };
+    let output = extract_text_content(&jq.call_with_context(&context).await.unwrap());
+    assert_eq!(output, r#"{"a.json":1,"b.json":2}"#);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_in_place_query_writes_preserve_line_endings() {
+    let (temp_dir, context) = setup_test_env();
+    let toml_path = temp_dir.path().join("config.toml");
+    let yaml_path = temp_dir.path().join("config.yaml");
+    fs::write(&toml_path, "# settings\r\n[server]\r\nport = 80\r\n").unwrap();
+    fs::write(&yaml_path, "port: 80").unwrap();
+    
+    let tomlq = |operation: &str, query: &str| TomlQueryTool {
+        file_path: "config.toml".to_string(),
+        query: query.to_string(),
+        operation: operation.to_string(),
+        output_format: "toml".to_string(),
+        in_place: true,
+        backup: false,
+        follow_symlinks: true,
+    };
+    tomlq("read", ".server.port").call_with_context(&context).await.unwrap();
+    tomlq("write", ".server.port = 8080").call_with_context(&context).await.unwrap();
+    assert_eq!(fs::read_to_string(&toml_path).unwrap(), "# settings\r\n[server]\r\nport = 8080\r\n");
+    
+    // A file without a final newline keeps lacking one
+    let yq = |operation: &str, query: &str| YamlQueryTool {
+        file_path: "config.yaml".to_string(),
+        query: query.to_string(),
+        operation: operation.to_string(),
+        output_format: "yaml".to_string(),
+        in_place: true,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        pattern: false,
+    };
+    yq("read", ".port").call_with_context(&context).await.unwrap();
+    yq("write", ".port = 8080").call_with_context(&context).await.unwrap();
+    assert_eq!(fs::read_to_string(&yaml_path).unwrap(), "port: 8080");
+}
+
+#[tokio::test]
+#[serial]
+async fn test_in_place_writes_keep_file_encoding() {
+    let (temp_dir, context) = setup_test_env();
+    let temp_path = temp_dir.path();
+    
+    // latin1 JSON: "café" with é as the single byte 0xE9
+    fs::write(temp_path.join("data.json"), b"{\"name\": \"caf\xe9\", \"n\": 1}\n").unwrap();
+    let jq = |operation: &str, query: &str| JsonQueryTool {
+        file_path: "data.json".to_string(),
+        query: query.to_string(),
+        operation: operation.to_string(),
+        output_format: "json".to_string(),
+        in_place: true,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
+    };
+    jq("read", ".name").call_with_context(&context).await.unwrap();
+    jq("write", ".n = 2").call_with_context(&context).await.unwrap();
+    let written = fs::read(temp_path.join("data.json")).unwrap();
+    assert!(written.windows(5).any(|w| w == b"caf\xe9\""), "{:?}", written);
+    assert!(std::str::from_utf8(&written).is_err());
+    
+    // UTF-16LE YAML with a BOM stays UTF-16LE with its BOM
+    let utf16 = |text: &str| -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        bytes
+    };
+    fs::write(temp_path.join("config.yaml"), utf16("name: café\nport: 80\n")).unwrap();
+    let yq = |operation: &str, query: &str| YamlQueryTool {
+        file_path: "config.yaml".to_string(),
+        query: query.to_string(),
+        operation: operation.to_string(),
+        output_format: "yaml".to_string(),
+        in_place: true,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        pattern: false,
+    };
+    yq("read", ".port").call_with_context(&context).await.unwrap();
+    yq("write", ".port = 8080").call_with_context(&context).await.unwrap();
+    assert_eq!(fs::read(temp_path.join("config.yaml")).unwrap(), utf16("name: café\nport: 8080\n"));
+    
+    // latin1 TOML
+    fs::write(temp_path.join("app.toml"), b"name = \"caf\xe9\"\nport = 80\n").unwrap();
+    let tomlq = |operation: &str, query: &str| TomlQueryTool {
+        file_path: "app.toml".to_string(),
+        query: query.to_string(),
+        operation: operation.to_string(),
+        output_format: "toml".to_string(),
+        in_place: true,
+        backup: false,
+        follow_symlinks: true,
+    };
+    tomlq("read", ".port").call_with_context(&context).await.unwrap();
+    tomlq("write", ".port = 8080").call_with_context(&context).await.unwrap();
+    assert_eq!(fs::read(temp_path.join("app.toml")).unwrap(), b"name = \"caf\xe9\"\nport = 8080\n");
+}
@@ -46,6 +46,7 @@ async fn test_copy_tool_blocks_symlink_destination() {
         source: "source.txt".to_string(),
         destination: "external_link/copied.txt".to_string(),
         overwrite: false,
+        recursive: true,
         preserve_metadata: true,
     };
     
@@ -69,6 +70,7 @@ async fn test_copy_tool_blocks_symlink_destination_with_subdirs() {
         source: "source.txt".to_string(),
         destination: "external_link/subdir/copied.txt".to_string(),
         overwrite: false,
+        recursive: true,
         preserve_metadata: true,
     };
     
@@ -95,6 +97,7 @@ async fn test_copy_tool_blocks_new_subdir_in_symlink() {
         source: "source.txt".to_string(),
         destination: "external_link/newdir/copied.txt".to_string(),
         overwrite: false,
+        recursive: true,
         preserve_metadata: true,
     };
     
@@ -148,6 +151,8 @@ async fn test_yq_write_blocks_new_subdir_in_symlink() {
         in_place: true,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = yq_tool.call_with_context(&context).await;
@@ -175,6 +180,9 @@ async fn test_write_tool_blocks_symlink_path() {
         dry_run: false,
         force: false,
         include_metadata: false,
+        create_dirs: false,
+        create_only: false,
+        line_ending: "preserve".to_string(),
     };
     
     let result = write_tool.call_with_context(&context).await;
@@ -196,6 +204,7 @@ async fn test_touch_tool_blocks_symlink_path() {
         update_mtime: true,
         atime: None,
         mtime: None,
+        timestamp: None,
         reference: None,
         encoding: "utf-8".to_string(),
         content: String::new(),
@@ -236,6 +245,7 @@ async fn test_delete_tool_blocks_symlink_path() {
         confirm: true,
         force: false,
         pattern: false,
+        use_trash: false,
     };
     
     let result = delete_tool.call_with_context(&context).await;
@@ -273,6 +283,7 @@ async fn test_chmod_tool_blocks_symlink_path() {
         mode: "755".to_string(),
         recursive: false,
         pattern: false,
+        dry_run: false,
     };
     
     let result = chmod_tool.call_with_context(&context).await;
@@ -295,6 +306,7 @@ async fn test_edit_tool_blocks_symlink_path() {
         show_diff: false,
         dry_run: false,
         replace_all: None,
+        line_range: None,
     };
     
     let result = edit_tool.call_with_context(&context).await;
@@ -337,6 +349,8 @@ async fn test_yq_write_blocks_symlink_path() {
         in_place: true,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = yq_tool.call_with_context(&context).await;
@@ -358,6 +372,11 @@ async fn test_jq_write_blocks_symlink_path() {
         in_place: true,
         backup: false,
         follow_symlinks: true,
+        slurp: false,
+        indent: None,
+        sort_keys: false,
+        schema_path: None,
+        pattern: false,
     };
     
     let result = jq_tool.call_with_context(&context).await;
@@ -379,6 +398,7 @@ async fn test_copy_allows_within_project() {
         source: "source.txt".to_string(),
         destination: "dest.txt".to_string(),
         overwrite: false,
+        recursive: true,
         preserve_metadata: true,
     };
     
@@ -410,6 +430,9 @@ async fn test_nested_symlink_blocked() {
         dry_run: false,
         force: false,
         include_metadata: false,
+        create_dirs: false,
+        create_only: false,
+        line_ending: "preserve".to_string(),
     };
     
     let result = write_tool.call_with_context(&context).await;
@@ -440,6 +463,9 @@ async fn test_symlink_to_parent_directory_blocked() {
         dry_run: false,
         force: false,
         include_metadata: false,
+        create_dirs: false,
+        create_only: false,
+        line_ending: "preserve".to_string(),
     };
     
     let result = write_tool.call_with_context(&context).await;
@@ -447,4 +473,50 @@ async fn test_symlink_to_parent_directory_blocked() {
     let error_msg = format!("{:?}", result.unwrap_err());
     assert!(error_msg.contains("Path would be outside the project directory") || 
             error_msg.contains("Path is outside the project directory"));
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_edit_and_tomlq_ignore_planted_temp_symlinks() {
+    let (project_dir, external_dir, context) = setup_symlink_test_env().await;
+    let project_root = project_dir.path().canonicalize().unwrap();
+    let external_root = external_dir.path().canonicalize().unwrap();
+
+    // The old writers wrote "<file>.tmp" without checking for symlinks
+    fs::write(project_root.join("notes.txt"), "hello").await.unwrap();
+    fs::write(project_root.join("config.toml"), "[section]\nkey = \"value\"\n").await.unwrap();
+    unix_symlink(external_root.join("external.txt"), project_root.join("notes.txt.tmp")).unwrap();
+    unix_symlink(external_root.join("config.toml"), project_root.join("config.toml.tmp")).unwrap();
+
+    let read: mcp_projectfiles_core::tools::ReadTool =
+        serde_json::from_value(serde_json::json!({"path": "notes.txt"})).unwrap();
+    read.call_with_context(&context).await.unwrap();
+    let edit_tool = EditTool {
+        path: "notes.txt".to_string(),
+        old: Some("hello".to_string()),
+        new: Some("goodbye".to_string()),
+        expected: None,
+        edits: None,
+        show_diff: false,
+        dry_run: false,
+        replace_all: None,
+        line_range: None,
+    };
+    edit_tool.call_with_context(&context).await.unwrap();
+
+    let tomlq = |operation: &str, query: &str| TomlQueryTool {
+        file_path: "config.toml".to_string(),
+        query: query.to_string(),
+        operation: operation.to_string(),
+        output_format: "toml".to_string(),
+        in_place: operation == "write",
+        backup: false,
+        follow_symlinks: false,
+    };
+    tomlq("read", ".").call_with_context(&context).await.unwrap();
+    tomlq("write", ".section.key = \"changed\"").call_with_context(&context).await.unwrap();
+
+    assert_eq!(fs::read_to_string(project_root.join("notes.txt")).await.unwrap(), "goodbye");
+    assert!(fs::read_to_string(project_root.join("config.toml")).await.unwrap().contains("changed"));
+    assert_eq!(fs::read_to_string(external_root.join("external.txt")).await.unwrap(), "External content");
+    assert_eq!(fs::read_to_string(external_root.join("config.toml")).await.unwrap(), "[section]\nkey = \"value\"");
+}
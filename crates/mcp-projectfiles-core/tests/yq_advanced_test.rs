@@ -45,6 +45,8 @@ items:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -76,6 +78,8 @@ users:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -110,6 +114,8 @@ users:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -150,6 +156,8 @@ data:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -180,6 +188,8 @@ active: true
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -212,6 +222,8 @@ numbers:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -252,6 +264,8 @@ strings:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -267,6 +281,8 @@ strings:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -282,6 +298,8 @@ strings:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -323,6 +341,8 @@ data:
             in_place: false,
             backup: true,
             follow_symlinks: true,
+            slurp: false,
+            pattern: false,
         };
         
         let result = tool.call_with_context(&context).await.unwrap();
@@ -360,6 +380,8 @@ math:
             in_place: false,
             backup: true,
             follow_symlinks: true,
+            slurp: false,
+            pattern: false,
         };
         
         let result = tool.call_with_context(&context).await.unwrap();
@@ -390,6 +412,8 @@ text:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -410,6 +434,8 @@ text:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -425,6 +451,8 @@ text:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -440,6 +468,8 @@ text:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -455,6 +485,8 @@ text:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -494,6 +526,8 @@ users:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -511,6 +545,8 @@ users:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -529,6 +565,8 @@ users:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -546,6 +584,8 @@ users:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -563,6 +603,8 @@ users:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -598,6 +640,8 @@ users:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -617,6 +661,8 @@ users:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -655,6 +701,8 @@ data:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -670,6 +718,8 @@ data:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -696,6 +746,8 @@ data:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -711,6 +763,8 @@ data:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -745,6 +799,8 @@ config:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -768,6 +824,8 @@ config:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -787,6 +845,8 @@ config:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -801,6 +861,8 @@ config:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -830,6 +892,8 @@ user:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let _result = read_tool.call_with_context(&context).await.unwrap();
@@ -843,6 +907,8 @@ user:
         in_place: true,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = write_tool.call_with_context(&context).await.unwrap();
@@ -857,6 +923,8 @@ user:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = verify_tool.call_with_context(&context).await.unwrap();
@@ -896,6 +964,8 @@ config:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -911,6 +981,8 @@ config:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -927,6 +999,8 @@ config:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -961,6 +1035,8 @@ data:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1008,6 +1084,8 @@ numbers:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1023,6 +1101,8 @@ numbers:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1038,6 +1118,8 @@ numbers:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
@@ -1053,9 +1135,174 @@ numbers:
         in_place: false,
         backup: true,
         follow_symlinks: true,
+        slurp: false,
+        pattern: false,
     };
     
     let result = tool.call_with_context(&context).await.unwrap();
     let content = extract_text_content(&result);
     assert_eq!(content.trim(), "3.2");
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_explode_implode() {
+    let (context, temp_dir) = setup_test_context().await;
+    let yaml_content = r#"
+word: Hi
+codes: [72, 105]
+"#;
+    create_test_yaml_file(&temp_dir, "test.yaml", yaml_content).await;
+    
+    let tool = YamlQueryTool {
+        file_path: "test.yaml".to_string(),
+        query: ".word | explode".to_string(),
+        operation: "read".to_string(),
+        output_format: "json".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        pattern: false,
+    };
+    
+    let result = tool.call_with_context(&context).await.unwrap();
+    let value: serde_json::Value = serde_json::from_str(extract_text_content(&result)).unwrap();
+    assert_eq!(value, json!([72, 105]));
+    
+    let tool = YamlQueryTool {
+        file_path: "test.yaml".to_string(),
+        query: ".codes | implode".to_string(),
+        operation: "read".to_string(),
+        output_format: "json".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        pattern: false,
+    };
+    
+    let result = tool.call_with_context(&context).await.unwrap();
+    assert_eq!(extract_text_content(&result).trim(), "\"Hi\"");
+}
+
+#[tokio::test]
+async fn test_multi_document_round_trip() {
+    let (context, temp_dir) = setup_test_context().await;
+    let yaml_content = r#"kind: Deployment
+name: web
+---
+kind: Service
+name: web
+---
+kind: ConfigMap
+name: settings
+"#;
+    create_test_yaml_file(&temp_dir, "manifests.yaml", yaml_content).await;
+    
+    // Every document is visible to the query
+    let tool = YamlQueryTool {
+        file_path: "manifests.yaml".to_string(),
+        query: "length".to_string(),
+        operation: "read".to_string(),
+        output_format: "raw".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        pattern: false,
+    };
+    let result = tool.call_with_context(&context).await.unwrap();
+    assert_eq!(extract_text_content(&result).trim(), "3");
+    
+    // YAML output re-emits separate documents
+    let tool = YamlQueryTool {
+        file_path: "manifests.yaml".to_string(),
+        query: ".".to_string(),
+        operation: "read".to_string(),
+        output_format: "yaml".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        pattern: false,
+    };
+    let result = tool.call_with_context(&context).await.unwrap();
+    let output = extract_text_content(&result);
+    assert_eq!(output.matches("---\n").count(), 2);
+    
+    let kinds: Vec<String> = serde_yaml::Deserializer::from_str(output)
+        .map(|doc| {
+            let value = <serde_yaml::Value as serde::Deserialize>::deserialize(doc).unwrap();
+            value["kind"].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(kinds, vec!["Deployment", "Service", "ConfigMap"]);
+    
+    // In-place writes keep the file multi-document
+    let tool = YamlQueryTool {
+        file_path: "manifests.yaml".to_string(),
+        query: ".[1].name = \"api\"".to_string(),
+        operation: "write".to_string(),
+        output_format: "yaml".to_string(),
+        in_place: true,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        pattern: false,
+    };
+    tool.call_with_context(&context).await.unwrap();
+    
+    let written = fs::read_to_string(temp_dir.path().join("manifests.yaml")).await.unwrap();
+    assert_eq!(written.matches("---\n").count(), 2);
+    assert!(written.contains("name: api"));
+}
+
+#[tokio::test]
+async fn test_multi_document_nested_array_is_one_document() {
+    let (context, temp_dir) = setup_test_context().await;
+    let yaml_content = r#"kind: Service
+spec:
+  ports:
+    - port: 80
+    - port: 443
+---
+kind: ConfigMap
+"#;
+    create_test_yaml_file(&temp_dir, "manifests.yaml", yaml_content).await;
+    
+    let tool = YamlQueryTool {
+        file_path: "manifests.yaml".to_string(),
+        query: ".[0].spec.ports".to_string(),
+        operation: "read".to_string(),
+        output_format: "yaml".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: false,
+        pattern: false,
+    };
+    let result = tool.call_with_context(&context).await.unwrap();
+    let output = extract_text_content(&result);
+    assert!(!output.contains("---"), "{}", output);
+    assert_eq!(output, "- port: 80\n- port: 443\n");
+}
+
+#[tokio::test]
+async fn test_slurp_lines() {
+    let (context, temp_dir) = setup_test_context().await;
+    create_test_yaml_file(&temp_dir, "events.log", "{name: a, ok: true}\n\n{name: b, ok: false}\n{\"name\": \"c\", \"ok\": true}\n").await;
+    
+    let tool = YamlQueryTool {
+        file_path: "events.log".to_string(),
+        query: "length".to_string(),
+        operation: "read".to_string(),
+        output_format: "raw".to_string(),
+        in_place: false,
+        backup: false,
+        follow_symlinks: true,
+        slurp: true,
+        pattern: false,
+    };
+    let result = tool.call_with_context(&context).await.unwrap();
+    assert_eq!(extract_text_content(&result).trim(), "3");
+}
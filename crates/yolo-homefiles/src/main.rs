@@ -1,8 +1,8 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use std::io::IsTerminal;
+use mcp_projectfiles_core::cli::{HttpArgs, ServerArgs};
+use mcp_projectfiles_core::logging::{self, LogFormat};
 use tracing::info;
-use tracing_subscriber::{EnvFilter, fmt};
 
 #[derive(Parser)]
 #[command(name = "yolo-homefiles")]
@@ -10,11 +10,16 @@ use tracing_subscriber::{EnvFilter, fmt};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Log format on stderr: human-readable text, or one JSON object per line for log collectors
+    #[arg(long, global = true, env = "MCP_LOG_FORMAT", value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Run the MCP server using stdio transport
+    #[command(mut_arg("project_root", |arg| arg.help("Project root directory (defaults to home directory)")))]
     Stdio {
         /// Server name
         #[arg(long, default_value = "yolo-homefiles")]
@@ -24,37 +29,36 @@ enum Commands {
         #[arg(long, default_value = env!("CARGO_PKG_VERSION"))]
         version: String,
 
-        /// Project root directory (defaults to home directory)
-        #[arg(long, env = "MCP_PROJECT_ROOT")]
-        project_root: Option<std::path::PathBuf>,
+        #[command(flatten)]
+        server: ServerArgs,
+    },
+    /// Run the MCP server using the Streamable HTTP transport
+    #[command(mut_arg("project_root", |arg| arg.help("Project root directory (defaults to home directory)")))]
+    Http {
+        #[command(flatten)]
+        server: ServerArgs,
+
+        #[command(flatten)]
+        http: HttpArgs,
     },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize logging with TTY detection for conditional ANSI colors
-    let filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new("mcp_projectfiles_core=info,yolo_homefiles=info,info"));
-
-    fmt()
-        .with_ansi(std::io::stderr().is_terminal()) // Only use ANSI colors if stderr is a TTY
-        .with_env_filter(filter)
-        .with_target(false)
-        .with_writer(std::io::stderr)
-        .init();
-
     let cli = Cli::parse();
 
+    logging::init(cli.log_format, "yolo_homefiles");
+
     match cli.command {
-        Commands::Stdio { name: _, version: _, project_root } => {
-            // Initialize project root - default to home directory for homefiles
-            let root = project_root.or_else(|| dirs::home_dir());
-            if let Some(root) = root {
-                info!("Setting project root to: {:?}", root);
-                mcp_projectfiles_core::config::init_project_root(root);
-            }
+        Commands::Stdio { name: _, version: _, server } => {
+            server.init(dirs::home_dir());
             info!("Starting YOLO HomeFiles MCP server with stdio transport");
             mcp_projectfiles_core::run_stdio_server().await
         }
+        Commands::Http { server, http } => {
+            server.init(dirs::home_dir());
+            info!("Starting YOLO HomeFiles MCP server with HTTP transport on {}", http.addr());
+            http.run().await
+        }
     }
 }
\ No newline at end of file
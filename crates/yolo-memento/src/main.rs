@@ -1,8 +1,8 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use std::io::IsTerminal;
+use mcp_projectfiles_core::cli::{HttpArgs, ServerArgs};
+use mcp_projectfiles_core::logging::{self, LogFormat};
 use tracing::info;
-use tracing_subscriber::{EnvFilter, fmt};
 
 #[derive(Parser)]
 #[command(name = "yolo-memento")]
@@ -10,6 +10,10 @@ use tracing_subscriber::{EnvFilter, fmt};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Log format on stderr: human-readable text, or one JSON object per line for log collectors
+    #[arg(long, global = true, env = "MCP_LOG_FORMAT", value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
 }
 
 #[derive(Subcommand)]
@@ -24,36 +28,35 @@ enum Commands {
         #[arg(long, default_value = env!("CARGO_PKG_VERSION"))]
         version: String,
 
-        /// Project root directory (defaults to current working directory)
-        #[arg(long, env = "MCP_PROJECT_ROOT")]
-        project_root: Option<std::path::PathBuf>,
+        #[command(flatten)]
+        server: ServerArgs,
+    },
+    /// Run the MCP server using the Streamable HTTP transport
+    Http {
+        #[command(flatten)]
+        server: ServerArgs,
+
+        #[command(flatten)]
+        http: HttpArgs,
     },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize logging with TTY detection for conditional ANSI colors
-    let filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new("mcp_projectfiles_core=info,yolo_memento=info,info"));
-
-    fmt()
-        .with_ansi(std::io::stderr().is_terminal()) // Only use ANSI colors if stderr is a TTY
-        .with_env_filter(filter)
-        .with_target(false)
-        .with_writer(std::io::stderr)
-        .init();
-
     let cli = Cli::parse();
 
+    logging::init(cli.log_format, "yolo_memento");
+
     match cli.command {
-        Commands::Stdio { name: _, version: _, project_root } => {
-            // Initialize project root if provided
-            if let Some(root) = project_root {
-                info!("Setting project root to: {:?}", root);
-                mcp_projectfiles_core::config::init_project_root(root);
-            }
+        Commands::Stdio { name: _, version: _, server } => {
+            server.init(None);
             info!("Starting YOLO Memento MCP server with stdio transport");
             mcp_projectfiles_core::run_stdio_server().await
         }
+        Commands::Http { server, http } => {
+            server.init(None);
+            info!("Starting YOLO Memento MCP server with HTTP transport on {}", http.addr());
+            http.run().await
+        }
     }
 }
\ No newline at end of file